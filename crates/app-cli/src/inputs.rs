@@ -0,0 +1,138 @@
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// A diagram source read from a file or from stdin
+#[derive(Debug, PartialEq)]
+pub struct Source {
+    pub path: Option<PathBuf>,
+    pub content: String,
+}
+
+impl Source {
+    pub fn name(&self) -> String {
+        self.path
+            .as_ref()
+            .map(|path: &PathBuf| path.display().to_string())
+            .unwrap_or_else(|| "<stdin>".to_string())
+    }
+}
+
+/// Reads every input, expanding glob patterns; no input at all means stdin
+pub fn read_sources(patterns: &[String], stdin: &mut impl Read) -> Result<Vec<Source>, String> {
+    if patterns.is_empty() {
+        return Ok(vec![read_stdin(stdin)?]);
+    }
+
+    let mut sources: Vec<Source> = Vec::new();
+    for pattern in patterns {
+        if pattern == "-" {
+            sources.push(read_stdin(stdin)?);
+        } else if is_glob(pattern) {
+            let mut paths: Vec<PathBuf> = glob::glob(pattern)
+                .map_err(|e: glob::PatternError| format!("Invalid pattern '{}': {}", pattern, e))?
+                .filter_map(Result::ok)
+                .filter(|path: &PathBuf| path.is_file())
+                .collect();
+            if paths.is_empty() {
+                return Err(format!("No file matches '{}'", pattern));
+            }
+            paths.sort();
+            for path in paths {
+                sources.push(read_file(&path)?);
+            }
+        } else {
+            sources.push(read_file(Path::new(pattern))?);
+        }
+    }
+    Ok(sources)
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+fn read_file(path: &Path) -> Result<Source, String> {
+    fs::read_to_string(path)
+        .map(|content: String| Source {
+            path: Some(path.to_path_buf()),
+            content,
+        })
+        .map_err(|e: std::io::Error| format!("{}: {}", path.display(), e))
+}
+
+fn read_stdin(stdin: &mut impl Read) -> Result<Source, String> {
+    let mut content: String = String::new();
+    stdin
+        .read_to_string(&mut content)
+        .map_err(|e: std::io::Error| format!("<stdin>: {}", e))?;
+    Ok(Source {
+        path: None,
+        content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::inputs::{Source, read_sources};
+
+    #[test]
+    fn test_globs_are_expanded_in_order() {
+        let dir: PathBuf = std::env::temp_dir().join("diagrama-cli-inputs");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b.puml"), "B").unwrap();
+        fs::write(dir.join("a.puml"), "A").unwrap();
+        fs::write(dir.join("c.txt"), "C").unwrap();
+
+        let sources: Vec<Source> = read_sources(
+            &[dir.join("*.puml").display().to_string()],
+            &mut "".as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            sources,
+            vec![
+                Source {
+                    path: Some(dir.join("a.puml")),
+                    content: "A".to_string()
+                },
+                Source {
+                    path: Some(dir.join("b.puml")),
+                    content: "B".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stdin_is_read_without_inputs_or_with_dash() {
+        let expected: Vec<Source> = vec![Source {
+            path: None,
+            content: "@startuml\n@enduml".to_string(),
+        }];
+
+        assert_eq!(
+            read_sources(&[], &mut "@startuml\n@enduml".as_bytes()),
+            Ok(expected)
+        );
+        assert_eq!(
+            read_sources(&["-".to_string()], &mut "x".as_bytes()).map(|s: Vec<Source>| s[0].name()),
+            Ok("<stdin>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unmatched_globs_are_reported() {
+        assert_eq!(
+            read_sources(&["/nonexistent/*.puml".to_string()], &mut "".as_bytes()),
+            Err("No file matches '/nonexistent/*.puml'".to_string())
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+};
+
+use clap::Parser;
+use lib_convert::{
+    diagnostics::Diagnostic,
+    formats::OutputFormat,
+    lint::{self, Finding, Level, LintConfig, RuleSetting},
+};
+use lib_core::{
+    adapters::graph_gateway::GraphGateway,
+    entities::{graph::Graph, warning::Warning},
+    use_cases::load_graph::{LoadGraph, LoadGraphUseCase},
+};
+use lib_plantuml::infrastructure::formatter;
+use lib_svg::infrastructure::image_map::ImageMap;
+
+use crate::{
+    cli::{Cli, Command, ErrorFormat, InputArgs, OutputArgs},
+    inputs::Source,
+};
+
+mod cli;
+mod inputs;
+mod pipeline;
+#[cfg(feature = "plugins")]
+mod plugins;
+
+fn main() -> ExitCode {
+    #[cfg(feature = "plugins")]
+    plugins::load();
+    let cli: Cli = Cli::parse();
+
+    let succeeded: bool = smol::block_on(async {
+        match cli.command {
+            Command::Convert { inputs, to, output } => transform(&inputs, to, &output, false).await,
+            Command::Render {
+                inputs,
+                format,
+                image_map,
+                output,
+            } => transform(&inputs, format.into(), &output, image_map).await,
+            Command::Validate { inputs } => validate(&inputs),
+            Command::Lint { inputs, rules } => lint(&inputs, &rules),
+            Command::Fmt { inputs, check } => format(&inputs, check),
+            Command::Modules { path, to, output } => modules(&path, to, &output).await,
+            #[cfg(feature = "database")]
+            Command::Introspect { url, to, output } => introspect(&url, to, &output).await,
+        }
+    });
+
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+async fn transform(
+    inputs: &InputArgs,
+    to: OutputFormat,
+    output: &OutputArgs,
+    image_map: bool,
+) -> bool {
+    let Some(sources) = sources(&inputs.inputs) else {
+        return false;
+    };
+
+    let mut succeeded: bool = true;
+    for (source, graph) in sources.iter().zip(pipeline::load_all(
+        &sources,
+        inputs.from,
+        inputs.profile.into(),
+    )) {
+        let result: Result<(), Diagnostic> = match graph {
+            Ok(graph) if image_map => {
+                warn(source, &graph);
+                render_mapped(source, graph, output, sources.len() > 1)
+                    .await
+                    .map_err(Diagnostic::from)
+            }
+            Ok(graph) => {
+                warn(source, &graph);
+                lib_convert::pipeline::emit(graph, to)
+                    .await
+                    .and_then(|content: String| {
+                        write(source, &content, to, output, sources.len() > 1)
+                    })
+                    .map_err(Diagnostic::from)
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            report(source, &e, inputs.error_format);
+            succeeded = false;
+        }
+    }
+    succeeded
+}
+
+/// Draws the module graph of a Rust crate
+async fn modules(path: &Path, to: OutputFormat, output: &OutputArgs) -> bool {
+    use lib_rust::infrastructure::adapters::rust_module_graph_gateway::RustModuleGraphGateway;
+
+    draw(
+        Arc::new(RustModuleGraphGateway::new()),
+        &path.display().to_string(),
+        to,
+        output,
+    )
+    .await
+}
+
+/// Draws a database's schema
+#[cfg(feature = "database")]
+async fn introspect(url: &str, to: OutputFormat, output: &OutputArgs) -> bool {
+    use lib_sql::infrastructure::adapters::database_graph_gateway::DatabaseGraphGateway;
+
+    draw(Arc::new(DatabaseGraphGateway::new()), url, to, output).await
+}
+
+/// Draws what a gateway reads from something other than a source text, such
+/// as a directory or a URL, which stands in for the source
+async fn draw(
+    gateway: Arc<dyn GraphGateway + Send + Sync>,
+    input: &str,
+    to: OutputFormat,
+    output: &OutputArgs,
+) -> bool {
+    let source: Source = Source {
+        path: None,
+        content: input.to_string(),
+    };
+    let result: Result<(), String> = match LoadGraph::new(gateway).execute(input).await {
+        Ok(graph) => lib_convert::pipeline::emit(graph, to).await,
+        Err(e) => Err(e),
+    }
+    .and_then(|content: String| write(&source, &content, to, output, false));
+
+    if let Err(e) = &result {
+        eprintln!("{}: {}", input, e);
+    }
+    result.is_ok()
+}
+
+/// Draws SVG and writes the image map of the drawing next to it
+async fn render_mapped(
+    source: &Source,
+    graph: Graph,
+    output: &OutputArgs,
+    many: bool,
+) -> Result<(), String> {
+    let (svg, map): (String, ImageMap) = lib_convert::pipeline::render_with_map(graph).await?;
+    write(source, &svg, OutputFormat::Svg, output, many)?;
+    let Some(path) = target(source, OutputFormat::Svg, output, many) else {
+        return Ok(());
+    };
+
+    let name: &str = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("diagram");
+    let json: String = serde_json::to_string_pretty(&map.to_json())
+        .map_err(|e: serde_json::Error| e.to_string())?
+        + "\n";
+    [("map.json", json), ("map.html", map.to_html(name))]
+        .into_iter()
+        .try_for_each(|(extension, content): (&str, String)| {
+            let path: PathBuf = path.with_extension(extension);
+            fs::write(&path, content)
+                .map_err(|e: std::io::Error| format!("{}: {}", path.display(), e))
+        })
+}
+
+fn report(source: &Source, diagnostic: &Diagnostic, format: ErrorFormat) {
+    match format {
+        ErrorFormat::Rich => eprint!("{}", diagnostic.render(&source.name(), &source.content)),
+        ErrorFormat::Plain => eprintln!("{}: {}", source.name(), diagnostic),
+    }
+}
+
+/// Lists what the reader dropped or guessed at; the graph is still used
+fn warn(source: &Source, graph: &Graph) {
+    graph.warnings.iter().for_each(|warning: &Warning| {
+        eprintln!("{}: {}", source.name(), warning);
+    });
+}
+
+fn validate(inputs: &InputArgs) -> bool {
+    let Some(sources) = sources(&inputs.inputs) else {
+        return false;
+    };
+
+    let mut succeeded: bool = true;
+    for (source, graph) in sources.iter().zip(pipeline::load_all(
+        &sources,
+        inputs.from,
+        inputs.profile.into(),
+    )) {
+        match graph {
+            Ok(graph) => {
+                println!(
+                    "{}: ok ({} nodes, {} edges)",
+                    source.name(),
+                    graph.nodes.len(),
+                    graph.edges.len()
+                );
+                warn(source, &graph);
+            }
+            Err(e) => {
+                report(source, &e, inputs.error_format);
+                succeeded = false;
+            }
+        }
+    }
+    succeeded
+}
+
+fn lint(inputs: &InputArgs, rules: &[RuleSetting]) -> bool {
+    let Some(sources) = sources(&inputs.inputs) else {
+        return false;
+    };
+    let config: LintConfig = LintConfig::new(rules);
+
+    let mut succeeded: bool = true;
+    for (source, graph) in sources.iter().zip(pipeline::load_all(
+        &sources,
+        inputs.from,
+        inputs.profile.into(),
+    )) {
+        let graph: Graph = match graph {
+            Ok(graph) => graph,
+            Err(e) => {
+                report(source, &e, inputs.error_format);
+                succeeded = false;
+                continue;
+            }
+        };
+        // Known, as the source was read
+        let Ok(format) = pipeline::notation(source, inputs.from) else {
+            continue;
+        };
+
+        lint::lint(&graph, format, &source.content, &config)
+            .iter()
+            .for_each(|finding: &Finding| {
+                println!("{}: {}", source.name(), finding);
+                succeeded &= finding.level != Level::Error;
+            });
+    }
+    succeeded
+}
+
+/// Rewrites the files whose formatting changes; stdin is printed formatted
+fn format(inputs: &[String], check: bool) -> bool {
+    let Some(sources) = sources(inputs) else {
+        return false;
+    };
+
+    let mut succeeded: bool = true;
+    for source in &sources {
+        let formatted: String = match formatter::format_source(&source.content) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                eprintln!("{}: {}", source.name(), String::from(e));
+                succeeded = false;
+                continue;
+            }
+        };
+
+        let result: Result<(), String> = match (&source.path, check) {
+            (_, true) if formatted != source.content => {
+                println!("{}", source.name());
+                Err("not formatted".to_string())
+            }
+            (_, true) => Ok(()),
+            (None, false) => {
+                print!("{}", formatted);
+                Ok(())
+            }
+            (Some(path), false) if formatted != source.content => {
+                fs::write(path, formatted).map_err(|e: std::io::Error| e.to_string())
+            }
+            (Some(_), false) => Ok(()),
+        };
+        if let Err(e) = result {
+            if !check {
+                eprintln!("{}: {}", source.name(), e);
+            }
+            succeeded = false;
+        }
+    }
+    succeeded
+}
+
+fn sources(inputs: &[String]) -> Option<Vec<Source>> {
+    inputs::read_sources(inputs, &mut std::io::stdin())
+        .inspect_err(|e: &String| eprintln!("{}", e))
+        .ok()
+}
+
+/// Writes to stdout, to the output file, or into the output directory when
+/// several sources are converted at once
+fn write(
+    source: &Source,
+    content: &str,
+    format: OutputFormat,
+    output: &OutputArgs,
+    many: bool,
+) -> Result<(), String> {
+    let Some(path) = target(source, format, output, many) else {
+        print!("{}", content);
+        return Ok(());
+    };
+
+    if many {
+        fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+            .map_err(|e: std::io::Error| e.to_string())?;
+    }
+    fs::write(&path, content).map_err(|e: std::io::Error| format!("{}: {}", path.display(), e))
+}
+
+/// The file a source's output goes to, `None` for stdout
+fn target(
+    source: &Source,
+    format: OutputFormat,
+    output: &OutputArgs,
+    many: bool,
+) -> Option<PathBuf> {
+    let target: &PathBuf = output.output.as_ref()?;
+    if !many {
+        return Some(target.clone());
+    }
+
+    let stem: &str = source
+        .path
+        .as_deref()
+        .and_then(Path::file_stem)
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("stdin");
+    Some(target.join(format!("{}.{}", stem, format.extension())))
+}
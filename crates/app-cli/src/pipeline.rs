@@ -0,0 +1,117 @@
+use lib_convert::{diagnostics::Diagnostic, formats::InputFormat, pipeline};
+use lib_core::{adapters::graph_gateway::GraphGatewayError, entities::graph::Graph};
+use lib_plantuml::infrastructure::profile::Profile;
+
+use crate::inputs::Source;
+
+/// Reads each source with the given notation, or the one its extension
+/// implies. Sources sharing a notation are parsed in parallel; the results
+/// keep the order of the sources.
+pub fn load_all(
+    sources: &[Source],
+    from: Option<InputFormat>,
+    profile: Profile,
+) -> Vec<Result<Graph, Diagnostic>> {
+    let mut results: Vec<Option<Result<Graph, Diagnostic>>> = vec![None; sources.len()];
+    let mut batches: Vec<(InputFormat, Vec<usize>)> = Vec::new();
+
+    for (index, source) in sources.iter().enumerate() {
+        match notation(source, from) {
+            Ok(format) => match batches
+                .iter_mut()
+                .find(|(batch, _): &&mut (InputFormat, Vec<usize>)| *batch == format)
+            {
+                Some((_, indices)) => indices.push(index),
+                None => batches.push((format, vec![index])),
+            },
+            Err(e) => results[index] = Some(Err(Diagnostic::from(e))),
+        }
+    }
+
+    for (format, indices) in batches {
+        let inputs: Vec<&str> = indices
+            .iter()
+            .map(|index: &usize| sources[*index].content.as_str())
+            .collect();
+        for (index, result) in indices
+            .into_iter()
+            .zip(pipeline::load_all(&inputs, format, profile))
+        {
+            results[index] = Some(result.map_err(|e: GraphGatewayError| {
+                Diagnostic::new(e, format, &sources[index].content)
+            }));
+        }
+    }
+    results.into_iter().flatten().collect()
+}
+
+/// The notation a source is read with
+pub fn notation(source: &Source, from: Option<InputFormat>) -> Result<InputFormat, String> {
+    from.or_else(|| source.path.as_deref().and_then(InputFormat::from_path))
+        .ok_or_else(|| {
+            format!(
+                "Cannot tell the notation of {}, use --from to set it",
+                source.name()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use lib_convert::diagnostics::Diagnostic;
+    use lib_core::entities::graph::Graph;
+    use lib_plantuml::infrastructure::profile::Profile;
+    use pretty_assertions::assert_eq;
+
+    use crate::{inputs::Source, pipeline::load_all};
+
+    #[test]
+    fn test_notation_is_guessed_from_the_extension() {
+        let graphs: Vec<Result<Graph, Diagnostic>> =
+            load_all(&[source("model.puml")], None, Profile::Permissive);
+
+        assert_eq!(graphs[0].as_ref().unwrap().nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_reports_unknown_notations() {
+        assert_eq!(
+            load_all(&[source("model.txt")], None, Profile::Permissive),
+            vec![Err(Diagnostic::from(
+                "Cannot tell the notation of model.txt, use --from to set it".to_string()
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_keeps_the_order_of_mixed_notations() {
+        let sources: Vec<Source> = vec![
+            source("a.puml"),
+            Source {
+                path: Some(PathBuf::from("b.yuml")),
+                content: "[Order]->[Line]\n".to_string(),
+            },
+            source("c.txt"),
+            source("d.puml"),
+        ];
+
+        let sizes: Vec<Result<usize, Diagnostic>> = load_all(&sources, None, Profile::Permissive)
+            .into_iter()
+            .map(|graph: Result<Graph, Diagnostic>| graph.map(|graph: Graph| graph.nodes.len()))
+            .collect();
+
+        assert_eq!(sizes[0], Ok(1));
+        assert_eq!(sizes[1], Ok(2));
+        assert!(sizes[2].is_err());
+        assert_eq!(sizes[3], Ok(1));
+    }
+
+    fn source(path: &str) -> Source {
+        Source {
+            path: Some(PathBuf::from(path)),
+            content: "@startuml\nclass User\n@enduml\n".to_string(),
+        }
+    }
+}
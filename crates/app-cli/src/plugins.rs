@@ -0,0 +1,44 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use lib_plugin::infrastructure::host::{PluginError, WasmPlugin};
+
+/// Registers the WebAssembly plugins `DIAGRAMA_PLUGINS` names, files or
+/// directories of `.wasm` files separated like `PATH`. Runs before the
+/// arguments are parsed so `--from` and `--to` accept the plugins' formats.
+pub fn load() {
+    let Some(paths) = env::var_os("DIAGRAMA_PLUGINS") else {
+        return;
+    };
+
+    env::split_paths(&paths)
+        .flat_map(|path: PathBuf| modules(&path))
+        .for_each(|path: PathBuf| {
+            let registered: Result<(), String> = WasmPlugin::load(&path)
+                .map_err(|e: PluginError| e.to_string())
+                .and_then(WasmPlugin::register);
+            if let Err(e) = registered {
+                eprintln!("{}: {}", path.display(), e);
+            }
+        });
+}
+
+fn modules(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+
+    let mut modules: Vec<PathBuf> = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry: fs::DirEntry| entry.path())
+        .filter(|path: &PathBuf| path.extension() == Some(&OsString::from("wasm")))
+        .collect();
+    modules.sort();
+    modules
+}
@@ -0,0 +1,114 @@
+use lib_core::adapters::graph_gateway::GraphGatewayError;
+use lib_plantuml::infrastructure::syntax::{self, SourceIndex, Span};
+use lsp_types::{Position, Range};
+
+/// An open PlantUML source with its index. While the text does not parse the
+/// index of the last version that did is kept, so completion and navigation
+/// keep working mid-edit.
+pub struct Document {
+    pub text: String,
+    pub index: SourceIndex,
+    pub error: Option<GraphGatewayError>,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let mut document: Document = Self {
+            text: String::new(),
+            index: SourceIndex::default(),
+            error: None,
+        };
+        document.update(text);
+        document
+    }
+
+    pub fn update(&mut self, text: String) {
+        match syntax::index_source(&text) {
+            Ok(index) => {
+                self.index = index;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+        self.text = text;
+    }
+
+    /// LSP positions count UTF-16 code units within a line
+    pub fn position(&self, offset: usize) -> Position {
+        let offset: usize = offset.min(self.text.len());
+        let before: &str = &self.text[..offset];
+        let line_start: usize = before.rfind('\n').map_or(0, |i: usize| i + 1);
+
+        Position {
+            line: before.matches('\n').count() as u32,
+            character: before[line_start..].encode_utf16().count() as u32,
+        }
+    }
+
+    pub fn offset(&self, position: Position) -> usize {
+        let line_start: usize = self
+            .text
+            .split_inclusive('\n')
+            .take(position.line as usize)
+            .map(str::len)
+            .sum();
+        let line: &str = self.text[line_start..].split('\n').next().unwrap_or("");
+
+        let mut units: usize = 0;
+        line.char_indices()
+            .find(|(_, c): &(usize, char)| {
+                units += c.len_utf16();
+                units > position.character as usize
+            })
+            .map_or(line_start + line.len(), |(i, _): (usize, char)| {
+                line_start + i
+            })
+    }
+
+    pub fn range(&self, span: Span) -> Range {
+        Range {
+            start: self.position(span.start),
+            end: self.position(span.end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+    use pretty_assertions::assert_eq;
+
+    use crate::document::Document;
+
+    #[test]
+    fn test_converts_between_offsets_and_positions() {
+        let document: Document = Document::new("@startuml\nclass \"Ünïcode\" as U\n@enduml".into());
+        let alias: usize = document.text.find(" U").unwrap() + 1;
+
+        assert_eq!(
+            document.position(alias),
+            Position {
+                line: 1,
+                character: 19
+            }
+        );
+        assert_eq!(document.offset(document.position(alias)), alias);
+        assert_eq!(
+            document.offset(Position {
+                line: 1,
+                character: 99
+            }),
+            document.text.find("\n@enduml").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_keeps_the_last_index_while_the_text_does_not_parse() {
+        let mut document: Document = Document::new("@startuml\nclass User\n@enduml".into());
+
+        document.update("@startuml\nclass User\nclass\n@enduml".into());
+
+        assert!(document.error.is_some());
+        assert!(document.index.definition("User").is_some());
+    }
+}
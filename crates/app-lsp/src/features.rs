@@ -0,0 +1,406 @@
+use lib_core::{adapters::graph_gateway::GraphGatewayError, entities::member::MemberKind};
+use lib_plantuml::infrastructure::{
+    incremental::TextEdit as SourceEdit,
+    refactor,
+    syntax::{KEYWORDS, Span, Symbol, SymbolKind as SourceSymbolKind},
+    tokens::{self, Token, TokenKind},
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, DocumentSymbol, Position,
+    Range, SemanticToken, SemanticTokenType, SemanticTokensLegend, SymbolKind, TextEdit,
+};
+
+use crate::document::Document;
+
+pub fn diagnostics(document: &Document) -> Vec<Diagnostic> {
+    let Some(error) = &document.error else {
+        return Vec::new();
+    };
+
+    let (message, range): (String, Range) = match error {
+        GraphGatewayError::Parse {
+            message,
+            line,
+            column,
+            ..
+        } => {
+            let start: Position = Position {
+                line: line.saturating_sub(1) as u32,
+                character: column.saturating_sub(1) as u32,
+            };
+            // Pest messages end with the "= expected ..." summary
+            let summary: &str = message.rsplit("= ").next().unwrap_or(message);
+            (
+                summary.trim().to_string(),
+                Range {
+                    start,
+                    end: Position {
+                        line: start.line,
+                        character: start.character + 1,
+                    },
+                },
+            )
+        }
+        GraphGatewayError::Semantic { message, .. } => (message.clone(), Range::default()),
+    };
+
+    vec![Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("diagrama".to_string()),
+        message,
+        ..Default::default()
+    }]
+}
+
+pub fn document_symbols(document: &Document) -> Vec<DocumentSymbol> {
+    fn convert(document: &Document, symbol: &Symbol) -> DocumentSymbol {
+        let (kind, detail): (SymbolKind, Option<String>) = match &symbol.kind {
+            SourceSymbolKind::Element(keyword) => (element_kind(keyword), Some(keyword.clone())),
+            SourceSymbolKind::Member(kind) => (
+                match kind {
+                    MemberKind::Field => SymbolKind::FIELD,
+                    MemberKind::Method => SymbolKind::METHOD,
+                    MemberKind::EnumValue => SymbolKind::ENUM_MEMBER,
+                    MemberKind::Separator => SymbolKind::NULL,
+                },
+                Some(document.text[symbol.span.start..symbol.span.end].to_string()),
+            ),
+            SourceSymbolKind::Package => (SymbolKind::PACKAGE, None),
+        };
+
+        #[allow(deprecated)]
+        DocumentSymbol {
+            name: symbol.name.clone(),
+            detail,
+            kind,
+            tags: None,
+            deprecated: None,
+            range: document.range(symbol.span),
+            selection_range: document.range(symbol.selection),
+            children: Some(
+                symbol
+                    .children
+                    .iter()
+                    .map(|child: &Symbol| convert(document, child))
+                    .collect(),
+            ),
+        }
+    }
+
+    document
+        .index
+        .symbols
+        .iter()
+        .map(|symbol: &Symbol| convert(document, symbol))
+        .collect()
+}
+
+/// Where the element under the cursor is declared
+pub fn definition(document: &Document, position: Position) -> Option<Range> {
+    let id: &str = document.index.id_at(document.offset(position))?;
+    document
+        .index
+        .definition(id)
+        .map(|symbol: &Symbol| document.range(symbol.selection))
+}
+
+/// Where the element under the cursor is used, optionally with where it is
+/// declared
+pub fn references(
+    document: &Document,
+    position: Position,
+    include_declaration: bool,
+) -> Vec<Range> {
+    let Some(id) = document.index.id_at(document.offset(position)) else {
+        return Vec::new();
+    };
+
+    let declaration: Option<Span> = document
+        .index
+        .definition(id)
+        .map(|symbol: &Symbol| symbol.selection);
+    document
+        .index
+        .find_references(id)
+        .into_iter()
+        .filter(|span: &Span| include_declaration || Some(*span) != declaration)
+        .map(|span: Span| document.range(span))
+        .collect()
+}
+
+/// Edits renaming the element under the cursor wherever it is used
+pub fn rename(
+    document: &Document,
+    position: Position,
+    new_name: &str,
+) -> Result<Vec<TextEdit>, String> {
+    let Some(id) = document.index.id_at(document.offset(position)) else {
+        return Ok(Vec::new());
+    };
+
+    refactor::rename(&document.text, id, new_name)
+        .map(|edits: Vec<SourceEdit>| {
+            edits
+                .into_iter()
+                .map(|edit: SourceEdit| TextEdit {
+                    range: document.range(edit.span),
+                    new_text: edit.text,
+                })
+                .collect()
+        })
+        .map_err(|e: GraphGatewayError| match e {
+            GraphGatewayError::Parse { message, .. }
+            | GraphGatewayError::Semantic { message, .. } => message,
+        })
+}
+
+pub fn completion(document: &Document) -> Vec<CompletionItem> {
+    let keywords = KEYWORDS.iter().map(|keyword: &&str| CompletionItem {
+        label: keyword.to_string(),
+        kind: Some(CompletionItemKind::KEYWORD),
+        ..Default::default()
+    });
+    let ids = document
+        .index
+        .ids()
+        .into_iter()
+        .map(|id: &str| CompletionItem {
+            label: id.to_string(),
+            kind: Some(CompletionItemKind::CLASS),
+            detail: document
+                .index
+                .definition(id)
+                .map(|symbol: &Symbol| symbol.name.clone()),
+            ..Default::default()
+        });
+
+    keywords.chain(ids).collect()
+}
+
+/// Token types in the order of `TokenKind::ALL`, which semantic tokens index
+pub fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TokenKind::ALL
+            .iter()
+            .map(|kind: &TokenKind| match kind {
+                TokenKind::Keyword => SemanticTokenType::KEYWORD,
+                TokenKind::Name => SemanticTokenType::CLASS,
+                TokenKind::Arrow => SemanticTokenType::OPERATOR,
+                TokenKind::Stereotype => SemanticTokenType::DECORATOR,
+                TokenKind::String => SemanticTokenType::STRING,
+                TokenKind::Comment => SemanticTokenType::COMMENT,
+            })
+            .collect(),
+        token_modifiers: Vec::new(),
+    }
+}
+
+/// Tokens relative to the previous one, with block comments split per line
+pub fn semantic_tokens(document: &Document) -> Vec<SemanticToken> {
+    let mut previous: Position = Position::default();
+    let mut data: Vec<SemanticToken> = Vec::new();
+
+    tokens::tokenize(&document.text)
+        .iter()
+        .for_each(|token: &Token| {
+            let token_type: u32 = TokenKind::ALL
+                .iter()
+                .position(|kind: &TokenKind| *kind == token.kind)
+                .unwrap_or_default() as u32;
+
+            let mut start: usize = token.span.start;
+            document.text[token.span.start..token.span.end]
+                .split('\n')
+                .for_each(|line: &str| {
+                    let position: Position = document.position(start);
+                    let length: u32 = line.trim_end_matches('\r').encode_utf16().count() as u32;
+                    start += line.len() + 1;
+                    if length == 0 {
+                        return;
+                    }
+
+                    data.push(SemanticToken {
+                        delta_line: position.line - previous.line,
+                        delta_start: if position.line == previous.line {
+                            position.character - previous.character
+                        } else {
+                            position.character
+                        },
+                        length,
+                        token_type,
+                        token_modifiers_bitset: 0,
+                    });
+                    previous = position;
+                });
+        });
+
+    data
+}
+
+fn element_kind(keyword: &str) -> SymbolKind {
+    match keyword {
+        "interface" | "protocol" => SymbolKind::INTERFACE,
+        "enum" => SymbolKind::ENUM,
+        "struct" => SymbolKind::STRUCT,
+        "component" | "database" => SymbolKind::MODULE,
+        "actor" => SymbolKind::OBJECT,
+        _ => SymbolKind::CLASS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{
+        CompletionItem, Diagnostic, DocumentSymbol, Position, Range, SemanticToken, SymbolKind,
+        TextEdit,
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::{document::Document, features};
+
+    const SOURCE: &str = "@startuml\npackage \"Core\" {\n  class \"User Account\" as User {\n    +login()\n  }\n}\nUser --> Profile\n@enduml\n";
+
+    #[test]
+    fn test_lists_nested_document_symbols() {
+        let symbols: Vec<DocumentSymbol> =
+            features::document_symbols(&Document::new(SOURCE.into()));
+
+        assert_eq!(symbols[0].name, "Core");
+        assert_eq!(symbols[0].kind, SymbolKind::PACKAGE);
+        let children: &Vec<DocumentSymbol> = symbols[0].children.as_ref().unwrap();
+        assert_eq!(children[0].name, "User Account");
+        assert_eq!(children[0].detail.as_deref(), Some("class"));
+        let members: &Vec<DocumentSymbol> = children[0].children.as_ref().unwrap();
+        assert_eq!(members[0].name, "login");
+        assert_eq!(members[0].kind, SymbolKind::METHOD);
+        assert_eq!(members[0].detail.as_deref(), Some("+login()"));
+    }
+
+    #[test]
+    fn test_goes_to_the_aliased_declaration() {
+        let document: Document = Document::new(SOURCE.into());
+
+        let range: Option<Range> = features::definition(
+            &document,
+            Position {
+                line: 6,
+                character: 1,
+            },
+        );
+
+        assert_eq!(
+            range,
+            Some(Range {
+                start: Position {
+                    line: 2,
+                    character: 26
+                },
+                end: Position {
+                    line: 2,
+                    character: 30
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_lists_references_with_or_without_the_declaration() {
+        let document: Document = Document::new(SOURCE.into());
+        let relation: Position = Position {
+            line: 6,
+            character: 0,
+        };
+
+        let lines: Vec<u32> = features::references(&document, relation, true)
+            .iter()
+            .map(|range: &Range| range.start.line)
+            .collect();
+
+        assert_eq!(lines, vec![2, 6]);
+        assert_eq!(features::references(&document, relation, false).len(), 1);
+    }
+
+    #[test]
+    fn test_renames_the_element_under_the_cursor() {
+        let document: Document = Document::new(SOURCE.into());
+
+        let edits: Vec<TextEdit> = features::rename(
+            &document,
+            Position {
+                line: 6,
+                character: 9,
+            },
+            "Settings",
+        )
+        .unwrap();
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].range.start,
+            Position {
+                line: 6,
+                character: 9
+            }
+        );
+        assert_eq!(edits[0].new_text, "Settings");
+        assert!(
+            features::rename(&document, Position::default(), "Settings")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_completes_keywords_and_known_ids() {
+        let items: Vec<CompletionItem> = features::completion(&Document::new(SOURCE.into()));
+        let labels: Vec<&str> = items
+            .iter()
+            .map(|item: &CompletionItem| item.label.as_str())
+            .collect();
+
+        assert!(labels.contains(&"class"));
+        assert!(labels.contains(&"Profile"));
+        assert!(labels.contains(&"User"));
+    }
+
+    #[test]
+    fn test_encodes_semantic_tokens_relative_to_each_other() {
+        let document: Document = Document::new("@startuml\n/' a\nb '/ class A\n@enduml".into());
+
+        let data: Vec<(u32, u32, u32, u32)> = features::semantic_tokens(&document)
+            .iter()
+            .map(|token: &SemanticToken| {
+                (
+                    token.delta_line,
+                    token.delta_start,
+                    token.length,
+                    token.token_type,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            data,
+            vec![
+                (0, 0, 9, 0),
+                (1, 0, 4, 5),
+                (1, 0, 4, 5),
+                (0, 5, 5, 0),
+                (0, 6, 1, 1),
+                (1, 0, 7, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_syntax_errors_at_their_position() {
+        let diagnostics: Vec<Diagnostic> = features::diagnostics(&Document::new(
+            "@startuml\nclass User\nUser -->\n@enduml".into(),
+        ));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 3);
+        assert!(features::diagnostics(&Document::new(SOURCE.into())).is_empty());
+    }
+}
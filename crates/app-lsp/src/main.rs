@@ -0,0 +1,58 @@
+use std::process::ExitCode;
+
+use lsp_server::Connection;
+use lsp_types::{
+    CompletionOptions, InitializeParams, OneOf, SemanticTokensFullOptions, SemanticTokensOptions,
+    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind,
+};
+
+use crate::server::Server;
+
+mod document;
+mod features;
+mod server;
+
+/// Language server for PlantUML sources over stdio
+fn main() -> ExitCode {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities: ServerCapabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: features::semantic_tokens_legend(),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..Default::default()
+            },
+        )),
+        ..Default::default()
+    };
+
+    let result: Result<(), String> = serde_json::to_value(capabilities)
+        .map_err(|e: serde_json::Error| e.to_string())
+        .and_then(|capabilities: serde_json::Value| {
+            connection
+                .initialize(capabilities)
+                .map_err(|e: lsp_server::ProtocolError| e.to_string())
+        })
+        .and_then(|params: serde_json::Value| {
+            serde_json::from_value::<InitializeParams>(params)
+                .map_err(|e: serde_json::Error| e.to_string())
+        })
+        .and_then(|_| Server::default().run(&connection));
+    drop(connection);
+
+    match result.and_then(|_| io_threads.join().map_err(|e: std::io::Error| e.to_string())) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    CompletionParams, CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams,
+    GotoDefinitionResponse, Location, PublishDiagnosticsParams, Range, ReferenceParams,
+    RenameParams, SemanticTokens, SemanticTokensParams, SemanticTokensResult,
+    TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as LspNotification, PublishDiagnostics,
+    },
+    request::{
+        Completion, DocumentSymbolRequest, GotoDefinition, References, Rename,
+        Request as LspRequest, SemanticTokensFullRequest,
+    },
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{document::Document, features};
+
+/// Keeps the open documents and answers requests about them
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<Url, Document>,
+}
+
+impl Server {
+    pub fn run(mut self, connection: &Connection) -> Result<(), String> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection
+                        .handle_shutdown(&request)
+                        .map_err(|e: lsp_server::ProtocolError| e.to_string())?
+                    {
+                        return Ok(());
+                    }
+                    send(connection, Message::Response(self.handle_request(request)))?;
+                }
+                Message::Notification(notification) => {
+                    if let Some(uri) = self.handle_notification(notification) {
+                        let diagnostics: PublishDiagnosticsParams = PublishDiagnosticsParams {
+                            diagnostics: self
+                                .documents
+                                .get(&uri)
+                                .map(features::diagnostics)
+                                .unwrap_or_default(),
+                            uri,
+                            version: None,
+                        };
+                        send(
+                            connection,
+                            Message::Notification(Notification::new(
+                                PublishDiagnostics::METHOD.to_string(),
+                                diagnostics,
+                            )),
+                        )?;
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let result: Result<Value, String> = match request.method.as_str() {
+            DocumentSymbolRequest::METHOD => {
+                params(&request).and_then(|params: DocumentSymbolParams| {
+                    let symbols: DocumentSymbolResponse = DocumentSymbolResponse::Nested(
+                        self.documents
+                            .get(&params.text_document.uri)
+                            .map(features::document_symbols)
+                            .unwrap_or_default(),
+                    );
+                    to_value(symbols)
+                })
+            }
+            GotoDefinition::METHOD => params(&request).and_then(|params: GotoDefinitionParams| {
+                let position: TextDocumentPositionParams = params.text_document_position_params;
+                let location: Option<GotoDefinitionResponse> = self
+                    .documents
+                    .get(&position.text_document.uri)
+                    .and_then(|document: &Document| {
+                        features::definition(document, position.position)
+                    })
+                    .map(|range: Range| {
+                        GotoDefinitionResponse::Scalar(Location {
+                            uri: position.text_document.uri.clone(),
+                            range,
+                        })
+                    });
+                to_value(location)
+            }),
+            Completion::METHOD => params(&request).and_then(|params: CompletionParams| {
+                let items: CompletionResponse = CompletionResponse::Array(
+                    self.documents
+                        .get(&params.text_document_position.text_document.uri)
+                        .map(features::completion)
+                        .unwrap_or_default(),
+                );
+                to_value(items)
+            }),
+            References::METHOD => params(&request).and_then(|params: ReferenceParams| {
+                let position: TextDocumentPositionParams = params.text_document_position;
+                let locations: Vec<Location> = self
+                    .documents
+                    .get(&position.text_document.uri)
+                    .map(|document: &Document| {
+                        features::references(
+                            document,
+                            position.position,
+                            params.context.include_declaration,
+                        )
+                    })
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|range: Range| Location {
+                        uri: position.text_document.uri.clone(),
+                        range,
+                    })
+                    .collect();
+                to_value(locations)
+            }),
+            Rename::METHOD => params(&request).and_then(|params: RenameParams| {
+                let position: TextDocumentPositionParams = params.text_document_position;
+                let edits: Vec<TextEdit> = match self.documents.get(&position.text_document.uri) {
+                    Some(document) => {
+                        features::rename(document, position.position, &params.new_name)?
+                    }
+                    None => Vec::new(),
+                };
+                to_value(WorkspaceEdit {
+                    changes: Some(HashMap::from([(position.text_document.uri, edits)])),
+                    ..Default::default()
+                })
+            }),
+            SemanticTokensFullRequest::METHOD => {
+                params(&request).and_then(|params: SemanticTokensParams| {
+                    let tokens: SemanticTokensResult =
+                        SemanticTokensResult::Tokens(SemanticTokens {
+                            result_id: None,
+                            data: self
+                                .documents
+                                .get(&params.text_document.uri)
+                                .map(features::semantic_tokens)
+                                .unwrap_or_default(),
+                        });
+                    to_value(tokens)
+                })
+            }
+            method => {
+                return Response::new_err(
+                    request.id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported method {}", method),
+                );
+            }
+        };
+
+        match result {
+            Ok(value) => Response::new_ok(request.id, value),
+            Err(e) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, e),
+        }
+    }
+
+    /// Applies a document change, returning the document whose diagnostics
+    /// need publishing
+    fn handle_notification(&mut self, notification: Notification) -> Option<Url> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params).ok()?;
+                let uri: Url = params.text_document.uri;
+                self.documents
+                    .insert(uri.clone(), Document::new(params.text_document.text));
+                Some(uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params).ok()?;
+                // Full sync: the last change holds the whole text
+                let text: String = params.content_changes.into_iter().last()?.text;
+                let uri: Url = params.text_document.uri;
+                match self.documents.get_mut(&uri) {
+                    Some(document) => document.update(text),
+                    None => {
+                        self.documents.insert(uri.clone(), Document::new(text));
+                    }
+                }
+                Some(uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params).ok()?;
+                self.documents.remove(&params.text_document.uri);
+                Some(params.text_document.uri)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn params<P: DeserializeOwned>(request: &Request) -> Result<P, String> {
+    serde_json::from_value(request.params.clone()).map_err(|e: serde_json::Error| e.to_string())
+}
+
+fn to_value(value: impl Serialize) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e: serde_json::Error| e.to_string())
+}
+
+fn send(connection: &Connection, message: Message) -> Result<(), String> {
+    connection
+        .sender
+        .send(message)
+        .map_err(|e: crossbeam_channel::SendError<Message>| e.to_string())
+}
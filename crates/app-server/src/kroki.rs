@@ -0,0 +1,86 @@
+use base64::{
+    Engine, alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+};
+use miniz_oxide::inflate::{self, TINFLStatus};
+
+/// Kroki encodes sources as deflated, URL-safe base64 text; padding is optional
+const ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    Invalid(String),
+    /// The source inflates to more than the allowed bytes
+    TooLarge,
+}
+
+/// Decodes a source from a Kroki URL, inflating at most `limit` bytes. Zlib
+/// streams are what Kroki clients send, raw deflate streams are accepted too.
+pub fn decode(encoded: &str, limit: usize) -> Result<String, DecodeError> {
+    let compressed: Vec<u8> = ENGINE
+        .decode(encoded.trim())
+        .map_err(|e: base64::DecodeError| {
+            DecodeError::Invalid(format!("Invalid encoded source: {}", e))
+        })?;
+    let bytes: Vec<u8> = match inflate::decompress_to_vec_zlib_with_limit(&compressed, limit) {
+        Err(e) if e.status != TINFLStatus::HasMoreOutput => {
+            inflate::decompress_to_vec_with_limit(&compressed, limit)
+        }
+        inflated => inflated,
+    }
+    .map_err(|e: inflate::DecompressError| match e.status {
+        TINFLStatus::HasMoreOutput => DecodeError::TooLarge,
+        _ => DecodeError::Invalid(format!("Invalid encoded source: {}", e)),
+    })?;
+
+    String::from_utf8(bytes)
+        .map_err(|_| DecodeError::Invalid("Encoded source is not UTF-8 text".to_string()))
+}
+
+#[cfg(test)]
+pub fn encode(source: &str) -> String {
+    ENGINE.encode(miniz_oxide::deflate::compress_to_vec_zlib(
+        source.as_bytes(),
+        9,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+    use pretty_assertions::assert_eq;
+
+    use crate::kroki::{DecodeError, ENGINE, decode, encode};
+
+    #[test]
+    fn test_decodes_zlib_and_raw_deflate_sources() {
+        let source: &str = "@startuml\nclass User\n@enduml\n";
+        let raw: String =
+            ENGINE.encode(miniz_oxide::deflate::compress_to_vec(source.as_bytes(), 6));
+
+        assert_eq!(decode(&encode(source), 1024), Ok(source.to_string()));
+        assert_eq!(decode(&raw, 1024), Ok(source.to_string()));
+    }
+
+    #[test]
+    fn test_rejects_malformed_sources() {
+        assert!(matches!(
+            decode("not base64!", 1024),
+            Err(DecodeError::Invalid(message)) if message.starts_with("Invalid encoded source")
+        ));
+        assert!(matches!(
+            decode("aGVsbG8", 1024),
+            Err(DecodeError::Invalid(message)) if message.starts_with("Invalid encoded source")
+        ));
+    }
+
+    #[test]
+    fn test_stops_inflating_at_the_limit() {
+        let bomb: String = encode(&"class A\n".repeat(100_000));
+
+        assert_eq!(decode(&bomb, 1024), Err(DecodeError::TooLarge));
+    }
+}
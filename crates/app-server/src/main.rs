@@ -0,0 +1,81 @@
+use std::{io::Read, num::NonZero, process::ExitCode, sync::Arc, thread};
+
+use clap::Parser;
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::routes::Reply;
+
+mod kroki;
+mod routes;
+
+/// Serves diagram conversion and rendering over HTTP
+#[derive(Debug, Parser)]
+#[command(name = "diagrama-server", version)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8000")]
+    address: String,
+    /// Requests handled at once, one per CPU by default
+    #[arg(long)]
+    workers: Option<usize>,
+}
+
+fn main() -> ExitCode {
+    let args: Args = Args::parse();
+
+    let server: Arc<Server> = match Server::http(&args.address) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            eprintln!("{}: {}", args.address, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("Listening on http://{}", args.address);
+
+    let workers: usize = args
+        .workers
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZero::get));
+    let handles: Vec<thread::JoinHandle<()>> = (0..workers.max(1))
+        .map(|_| {
+            let server: Arc<Server> = server.clone();
+            thread::spawn(move || server.incoming_requests().for_each(respond))
+        })
+        .collect();
+    handles
+        .into_iter()
+        .for_each(|handle: thread::JoinHandle<()>| {
+            let _ = handle.join();
+        });
+
+    ExitCode::SUCCESS
+}
+
+fn respond(mut request: Request) {
+    let mut body: String = String::new();
+    let reply: Reply = match request
+        .as_reader()
+        .take(routes::MAX_SOURCE_BYTES as u64 + 1)
+        .read_to_string(&mut body)
+    {
+        Ok(read) if read > routes::MAX_SOURCE_BYTES => routes::too_large(),
+        Ok(_) => smol::block_on(routes::handle(
+            request.method().as_str(),
+            request.url(),
+            &body,
+        )),
+        Err(e) => Reply {
+            status: 400,
+            content_type: "text/plain; charset=utf-8",
+            body: format!("Cannot read the request body: {}", e),
+        },
+    };
+
+    let mut response: Response<std::io::Cursor<Vec<u8>>> =
+        Response::from_string(reply.body).with_status_code(reply.status);
+    if let Ok(header) = Header::from_bytes("Content-Type", reply.content_type) {
+        response.add_header(header);
+    }
+    if let Err(e) = request.respond(response) {
+        eprintln!("Cannot send the response: {}", e);
+    }
+}
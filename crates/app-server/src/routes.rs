@@ -0,0 +1,208 @@
+use lib_convert::{
+    formats::{InputFormat, OutputFormat},
+    pipeline,
+};
+
+use crate::kroki::{self, DecodeError};
+
+const TEXT: &str = "text/plain; charset=utf-8";
+
+/// Largest source accepted, whether posted or inflated from a Kroki URL
+pub const MAX_SOURCE_BYTES: usize = 1024 * 1024;
+
+/// Notations whose readers bound how deep a source may nest, so that no
+/// request can overflow the stack of the worker parsing it
+const SERVED: [InputFormat; 10] = [
+    InputFormat::Plantuml,
+    InputFormat::Yuml,
+    InputFormat::Blockdiag,
+    InputFormat::Graphml,
+    InputFormat::Xmi,
+    InputFormat::Protobuf,
+    InputFormat::Excalidraw,
+    InputFormat::Openapi,
+    InputFormat::Cargo,
+    InputFormat::Jsonschema,
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Reply {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: TEXT,
+            body: message.into(),
+        }
+    }
+}
+
+/// Answers the service endpoints:
+///
+/// - `POST /convert?from=<notation>&to=<format>` with the source as body
+/// - `POST /render?from=<notation>`, which draws SVG
+/// - `GET /<notation>/<format>/<encoded source>` and `POST /<notation>/<format>`,
+///   as Kroki does
+pub async fn handle(method: &str, url: &str, body: &str) -> Reply {
+    let (path, query): (&str, &str) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment: &&str| !segment.is_empty())
+        .collect();
+
+    match (method, segments.as_slice()) {
+        ("POST", ["convert"]) => match (param(query, "from"), param(query, "to")) {
+            (Ok(from), Ok(to)) => convert(body, from, to).await,
+            (Err(e), _) | (_, Err(e)) => e,
+        },
+        ("POST", ["render"]) => match param(query, "from") {
+            Ok(from) => convert(body, from, OutputFormat::Svg.name()).await,
+            Err(e) => e,
+        },
+        ("GET", [from, to, encoded]) => match kroki::decode(encoded, MAX_SOURCE_BYTES) {
+            Ok(source) => convert(&source, from, to).await,
+            Err(DecodeError::Invalid(e)) => Reply::error(400, e),
+            Err(DecodeError::TooLarge) => too_large(),
+        },
+        ("POST", [from, to]) => convert(body, from, to).await,
+        _ => Reply::error(404, format!("No route for {} {}", method, path)),
+    }
+}
+
+/// Answers a source past `MAX_SOURCE_BYTES`
+pub fn too_large() -> Reply {
+    Reply::error(
+        413,
+        format!("Sources are limited to {} bytes", MAX_SOURCE_BYTES),
+    )
+}
+
+async fn convert(source: &str, from: &str, to: &str) -> Reply {
+    if source.len() > MAX_SOURCE_BYTES {
+        return too_large();
+    }
+    let formats: Result<(InputFormat, OutputFormat), String> = from
+        .parse()
+        .and_then(|from: InputFormat| Ok((from, to.parse()?)));
+    let (from, to): (InputFormat, OutputFormat) = match formats {
+        Ok(formats) => formats,
+        Err(e) => return Reply::error(400, e),
+    };
+    if !SERVED.contains(&from) {
+        return Reply::error(
+            400,
+            format!(
+                "Notation '{}' is not served, as its reader does not bound nesting",
+                from.name()
+            ),
+        );
+    }
+
+    match pipeline::convert(source, from, to).await {
+        Ok(body) => Reply {
+            status: 200,
+            content_type: to.media_type(),
+            body,
+        },
+        Err(e) => Reply::error(400, e),
+    }
+}
+
+fn param<'a>(query: &'a str, name: &str) -> Result<&'a str, Reply> {
+    query
+        .split('&')
+        .filter_map(|pair: &str| pair.split_once('='))
+        .find(|(key, _): &(&str, &str)| *key == name)
+        .map(|(_, value): (&str, &str)| value)
+        .ok_or_else(|| Reply::error(400, format!("Missing query parameter '{}'", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        kroki,
+        routes::{Reply, handle, too_large},
+    };
+
+    const PLANTUML: &str = "@startuml\nclass User\nUser --> Profile\n@enduml\n";
+
+    #[test]
+    fn test_converts_posted_sources() {
+        smol::block_on(async {
+            let reply: Reply = handle("POST", "/convert?from=plantuml&to=mermaid", PLANTUML).await;
+
+            assert_eq!(reply.status, 200);
+            assert_eq!(reply.content_type, "text/plain; charset=utf-8");
+            assert!(reply.body.starts_with("flowchart"));
+
+            let reply: Reply = handle("POST", "/render?from=plantuml", PLANTUML).await;
+            assert_eq!(reply.content_type, "image/svg+xml");
+            assert!(reply.body.starts_with("<svg"));
+        });
+    }
+
+    #[test]
+    fn test_renders_kroki_urls() {
+        smol::block_on(async {
+            let url: String = format!("/plantuml/svg/{}", kroki::encode(PLANTUML));
+
+            let reply: Reply = handle("GET", &url, "").await;
+
+            assert_eq!(reply.status, 200);
+            assert!(reply.body.starts_with("<svg"));
+        });
+    }
+
+    #[test]
+    fn test_reports_bad_requests() {
+        smol::block_on(async {
+            assert_eq!(
+                handle("POST", "/convert?to=svg", PLANTUML).await,
+                Reply {
+                    status: 400,
+                    content_type: "text/plain; charset=utf-8",
+                    body: "Missing query parameter 'from'".to_string(),
+                }
+            );
+            assert_eq!(handle("POST", "/plantuml/png", PLANTUML).await.status, 400);
+            assert_eq!(handle("GET", "/convert", "").await.status, 404);
+        });
+    }
+
+    #[test]
+    fn test_rejects_oversized_sources_on_every_route() {
+        smol::block_on(async {
+            let source: String = format!("@startuml\n{}@enduml\n", "class A\n".repeat(200_000));
+            let requests: [(&str, String); 4] = [
+                ("POST", "/convert?from=plantuml&to=mermaid".to_string()),
+                ("POST", "/render?from=plantuml".to_string()),
+                ("POST", "/plantuml/svg".to_string()),
+                ("GET", format!("/plantuml/svg/{}", kroki::encode(&source))),
+            ];
+
+            for (method, url) in requests {
+                assert_eq!(handle(method, &url, &source).await, too_large(), "{}", url);
+            }
+        });
+    }
+
+    #[test]
+    fn test_serves_only_notations_that_bound_nesting() {
+        smol::block_on(async {
+            let reply: Reply = handle("POST", "/convert?from=rust&to=mermaid", "struct A;").await;
+
+            assert_eq!(reply.status, 400);
+            assert_eq!(
+                reply.body,
+                "Notation 'rust' is not served, as its reader does not bound nesting"
+            );
+        });
+    }
+}
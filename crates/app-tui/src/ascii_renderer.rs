@@ -49,14 +49,14 @@ fn render_edge(edge: &Edge, graph: &Graph) -> String {
     let from_label = graph
         .nodes
         .get(&edge.from)
-        .and_then(|n| n.label.clone())
-        .unwrap_or_else(|| edge.from.clone());
+        .and_then(|n| n.label.as_deref().map(str::to_string))
+        .unwrap_or_else(|| edge.from.to_string());
 
     let to_label = graph
         .nodes
         .get(&edge.to)
-        .and_then(|n| n.label.clone())
-        .unwrap_or_else(|| edge.to.clone());
+        .and_then(|n| n.label.as_deref().map(str::to_string))
+        .unwrap_or_else(|| edge.to.to_string());
 
     let arrow = if edge.directed { "-->" } else { "--" };
 
@@ -78,8 +78,8 @@ fn render_group(group: &Group, graph: &Graph) -> String {
             graph
                 .nodes
                 .get(id)
-                .and_then(|n| n.label.clone())
-                .or_else(|| Some(id.clone()))
+                .and_then(|n| n.label.as_deref().map(str::to_string))
+                .or_else(|| Some(id.to_string()))
         })
         .collect();
 
@@ -89,9 +89,10 @@ fn render_group(group: &Group, graph: &Graph) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use lib_core::entities::edge::{Edge, EdgeKind};
+    use lib_core::entities::edge::{Edge, EdgeKind, EdgeLayout};
     use lib_core::entities::id::Id;
     use lib_core::entities::node::{Node, NodeKind};
+    use lib_core::entities::style::Colors;
     use std::collections::HashMap;
 
     #[test]
@@ -108,11 +109,18 @@ mod tests {
             Id::from("node1"),
             Node {
                 id: Id::from("node1"),
-                label: Some("TestNode".to_string()),
+                label: Some("TestNode".into()),
+                members: Vec::new(),
                 kind: NodeKind::Entity,
                 parent: None,
                 style: None,
+                colors: Colors::default(),
                 data: HashMap::new(),
+                geometry: None,
+                label_anchor: None,
+                hidden: false,
+                rich_label: None,
+                target_edge: None,
             },
         );
 
@@ -126,19 +134,33 @@ mod tests {
         let mut graph = Graph::default();
         let node1 = Node {
             id: Id::from("n1"),
-            label: Some("A".to_string()),
+            label: Some("A".into()),
+            members: Vec::new(),
             kind: NodeKind::Entity,
             parent: None,
             style: None,
+            colors: Colors::default(),
             data: HashMap::new(),
+            geometry: None,
+            label_anchor: None,
+            hidden: false,
+            rich_label: None,
+            target_edge: None,
         };
         let node2 = Node {
             id: Id::from("n2"),
-            label: Some("B".to_string()),
+            label: Some("B".into()),
+            members: Vec::new(),
             kind: NodeKind::Entity,
             parent: None,
             style: None,
+            colors: Colors::default(),
             data: HashMap::new(),
+            geometry: None,
+            label_anchor: None,
+            hidden: false,
+            rich_label: None,
+            target_edge: None,
         };
 
         graph.nodes.insert(node1.id.clone(), node1);
@@ -152,9 +174,19 @@ mod tests {
                 to: Id::from("n2"),
                 kind: EdgeKind::Association,
                 directed: true,
-                label: Some("relates to".to_string()),
+                label: Some("relates to".into()),
                 style: None,
+                colors: Colors::default(),
                 data: HashMap::new(),
+                waypoints: Vec::new(),
+                layout: EdgeLayout::default(),
+                hidden: false,
+                order: 0,
+                from_port: None,
+                to_port: None,
+                from_cardinality: None,
+                to_cardinality: None,
+                rich_label: None,
             },
         );
 
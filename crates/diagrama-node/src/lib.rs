@@ -0,0 +1,62 @@
+//! Node.js bindings, built with `npm run build` (napi-rs) from this directory.
+//! The crate sits outside the cargo workspace so the workspace builds without
+//! a Node toolchain.
+
+use lib_convert::{
+    formats::{InputFormat, OutputFormat},
+    pipeline,
+};
+use lib_json::infrastructure::emitter;
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+use serde_json::Value as Json;
+
+/// Reads a diagram written in `format` (`plantuml`, `yuml`, `dbml`, ...) and
+/// returns its model as a plain object
+#[napi]
+pub fn parse(source: String, format: String) -> Result<Json> {
+    let format: InputFormat = format.parse().map_err(invalid)?;
+    block_on(pipeline::load(&source, format)).map(|graph| emitter::graph_to_json(&graph))
+}
+
+/// Converts a diagram between notations (`mermaid`, `plantuml`, `svg`, `json`,
+/// `text`)
+#[napi]
+pub fn convert(source: String, from: String, to: String) -> Result<String> {
+    let from: InputFormat = from.parse().map_err(invalid)?;
+    let to: OutputFormat = to.parse().map_err(invalid)?;
+    block_on(pipeline::convert(&source, from, to))
+}
+
+/// Lays out a diagram and draws it as SVG
+#[napi(js_name = "renderSvg")]
+pub fn render_svg(source: String, format: String) -> Result<String> {
+    let from: InputFormat = format.parse().map_err(invalid)?;
+    block_on(pipeline::convert(&source, from, OutputFormat::Svg))
+}
+
+#[napi(js_name = "inputFormats")]
+pub fn input_formats() -> Vec<String> {
+    InputFormat::ALL
+        .iter()
+        .map(|format: &InputFormat| format.name().to_string())
+        .collect()
+}
+
+#[napi(js_name = "outputFormats")]
+pub fn output_formats() -> Vec<String> {
+    OutputFormat::ALL
+        .iter()
+        .map(|format: &OutputFormat| format.name().to_string())
+        .collect()
+}
+
+/// Pipeline errors are thrown as `Error` with the pipeline message
+fn block_on<T>(future: impl Future<Output = std::result::Result<T, String>>) -> Result<T> {
+    futures_lite::future::block_on(future)
+        .map_err(|e: String| Error::new(Status::GenericFailure, e))
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(Status::InvalidArg, message)
+}
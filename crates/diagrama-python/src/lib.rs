@@ -0,0 +1,273 @@
+//! Python bindings, built with `maturin build` from this directory. The crate
+//! sits outside the cargo workspace so the workspace builds without a Python
+//! toolchain.
+
+use std::collections::HashMap;
+
+use lib_convert::{
+    formats::{InputFormat, OutputFormat},
+    pipeline,
+};
+use lib_core::entities::{
+    edge::Edge,
+    geometry::{Point, Rect},
+    graph::Graph,
+    group::Group,
+    id::Text,
+    member::{Member, MemberKind},
+    node::Node,
+    value::Value,
+};
+use lib_json::infrastructure::emitter;
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+/// A parsed diagram, keeping the model so it can be converted or rendered
+#[pyclass(module = "diagrama", frozen)]
+struct Diagram {
+    graph: Graph,
+}
+
+#[pymethods]
+impl Diagram {
+    #[getter]
+    fn kind(&self) -> &'static str {
+        emitter::graph_kind(&self.graph.kind)
+    }
+
+    #[getter]
+    fn title(&self) -> Option<String> {
+        self.graph.metadata.title.clone()
+    }
+
+    /// Nodes sorted by id
+    #[getter]
+    fn nodes(&self) -> Vec<DiagramNode> {
+        let mut nodes: Vec<&Node> = self.graph.nodes.values().collect();
+        nodes.sort_by(|a: &&Node, b: &&Node| a.id.cmp(&b.id));
+        nodes.into_iter().map(DiagramNode::from).collect()
+    }
+
+    /// Edges sorted by source, target and id
+    #[getter]
+    fn edges(&self) -> Vec<DiagramEdge> {
+        let mut edges: Vec<&Edge> = self.graph.edges.values().collect();
+        edges.sort_by(|a: &&Edge, b: &&Edge| (&a.from, &a.to, &a.id).cmp(&(&b.from, &b.to, &b.id)));
+        edges.into_iter().map(DiagramEdge::from).collect()
+    }
+
+    /// Groups (packages, clusters, ...) sorted by id
+    #[getter]
+    fn groups(&self) -> Vec<DiagramGroup> {
+        let mut groups: Vec<&Group> = self.graph.groups.values().collect();
+        groups.sort_by(|a: &&Group, b: &&Group| a.id.cmp(&b.id));
+        groups.into_iter().map(DiagramGroup::from).collect()
+    }
+
+    fn node(&self, id: &str) -> Option<DiagramNode> {
+        self.graph.nodes.get(id).map(DiagramNode::from)
+    }
+
+    /// Writes the diagram in another notation (`mermaid`, `plantuml`, `svg`, `json`, `text`)
+    fn convert(&self, to: &str) -> PyResult<String> {
+        let to: OutputFormat = to.parse().map_err(PyValueError::new_err)?;
+        block_on(pipeline::emit(self.graph.clone(), to))
+    }
+
+    fn render(&self) -> PyResult<String> {
+        block_on(pipeline::emit(self.graph.clone(), OutputFormat::Svg))
+    }
+
+    fn to_json(&self) -> String {
+        emitter::graph_to_json(&self.graph).to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Diagram(kind='{}', nodes={}, edges={})",
+            self.kind(),
+            self.graph.nodes.len(),
+            self.graph.edges.len()
+        )
+    }
+}
+
+#[pyclass(module = "diagrama", name = "Node", get_all, frozen)]
+#[derive(Clone)]
+struct DiagramNode {
+    id: String,
+    kind: String,
+    label: Option<String>,
+    parent: Option<String>,
+    members: Vec<DiagramMember>,
+    data: HashMap<String, String>,
+    /// `(x, y, width, height)` once laid out
+    geometry: Option<(f64, f64, f64, f64)>,
+}
+
+#[pymethods]
+impl DiagramNode {
+    fn __repr__(&self) -> String {
+        format!("Node(id='{}', kind='{}')", self.id, self.kind)
+    }
+}
+
+impl From<&Node> for DiagramNode {
+    fn from(node: &Node) -> Self {
+        Self {
+            id: node.id.to_string(),
+            kind: emitter::node_kind(&node.kind).to_string(),
+            label: node.label.as_deref().map(str::to_string),
+            parent: node.parent.as_deref().map(str::to_string),
+            members: node.members.iter().map(DiagramMember::from).collect(),
+            data: data(&node.data),
+            geometry: node.geometry.map(rect),
+        }
+    }
+}
+
+#[pyclass(module = "diagrama", name = "Member", get_all, frozen)]
+#[derive(Clone)]
+struct DiagramMember {
+    name: String,
+    kind: String,
+    type_name: Option<String>,
+    data: HashMap<String, String>,
+}
+
+impl From<&Member> for DiagramMember {
+    fn from(member: &Member) -> Self {
+        Self {
+            name: member.name.clone(),
+            kind: match member.kind {
+                MemberKind::Field => "field",
+                MemberKind::Method => "method",
+                MemberKind::EnumValue => "enum_value",
+                MemberKind::Separator => "separator",
+            }
+            .to_string(),
+            type_name: member.type_name.clone(),
+            data: data(&member.data),
+        }
+    }
+}
+
+#[pyclass(module = "diagrama", name = "Edge", get_all, frozen)]
+#[derive(Clone)]
+struct DiagramEdge {
+    id: String,
+    source: String,
+    target: String,
+    kind: String,
+    directed: bool,
+    label: Option<String>,
+    data: HashMap<String, String>,
+    waypoints: Vec<(f64, f64)>,
+}
+
+#[pymethods]
+impl DiagramEdge {
+    fn __repr__(&self) -> String {
+        format!(
+            "Edge(source='{}', target='{}', kind='{}')",
+            self.source, self.target, self.kind
+        )
+    }
+}
+
+impl From<&Edge> for DiagramEdge {
+    fn from(edge: &Edge) -> Self {
+        Self {
+            id: edge.id.to_string(),
+            source: edge.from.to_string(),
+            target: edge.to.to_string(),
+            kind: emitter::edge_kind(&edge.kind).to_string(),
+            directed: edge.directed,
+            label: edge.label.as_deref().map(str::to_string),
+            data: data(&edge.data),
+            waypoints: edge.waypoints.iter().map(|p: &Point| (p.x, p.y)).collect(),
+        }
+    }
+}
+
+#[pyclass(module = "diagrama", name = "Group", get_all, frozen)]
+#[derive(Clone)]
+struct DiagramGroup {
+    id: String,
+    kind: String,
+    label: Option<String>,
+    parent: Option<String>,
+    children: Vec<String>,
+    geometry: Option<(f64, f64, f64, f64)>,
+}
+
+impl From<&Group> for DiagramGroup {
+    fn from(group: &Group) -> Self {
+        Self {
+            id: group.id.to_string(),
+            kind: emitter::group_kind(&group.kind).to_string(),
+            label: group.label.as_deref().map(str::to_string),
+            parent: group.parent.as_deref().map(str::to_string),
+            children: group.children.iter().map(ToString::to_string).collect(),
+            geometry: group.geometry.map(rect),
+        }
+    }
+}
+
+/// Reads a diagram written in `format` (`plantuml`, `yuml`, `dbml`, ...)
+#[pyfunction]
+fn parse(source: &str, format: &str) -> PyResult<Diagram> {
+    let format: InputFormat = format.parse().map_err(PyValueError::new_err)?;
+    block_on(pipeline::load(source, format)).map(|graph: Graph| Diagram { graph })
+}
+
+/// Converts a diagram between notations in one go
+#[pyfunction]
+#[pyo3(signature = (source, *, source_format, to))]
+fn convert(source: &str, source_format: &str, to: &str) -> PyResult<String> {
+    let from: InputFormat = source_format.parse().map_err(PyValueError::new_err)?;
+    let to: OutputFormat = to.parse().map_err(PyValueError::new_err)?;
+    block_on(pipeline::convert(source, from, to))
+}
+
+/// Lays out a diagram and draws it as SVG
+#[pyfunction]
+fn render(source: &str, format: &str) -> PyResult<String> {
+    let from: InputFormat = format.parse().map_err(PyValueError::new_err)?;
+    block_on(pipeline::convert(source, from, OutputFormat::Svg))
+}
+
+#[pymodule]
+fn diagrama(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(parse, module)?)?;
+    module.add_function(wrap_pyfunction!(convert, module)?)?;
+    module.add_function(wrap_pyfunction!(render, module)?)?;
+    module.add_class::<Diagram>()?;
+    module.add_class::<DiagramNode>()?;
+    module.add_class::<DiagramMember>()?;
+    module.add_class::<DiagramEdge>()?;
+    module.add_class::<DiagramGroup>()?;
+    Ok(())
+}
+
+/// Pipeline errors surface as `ValueError` with the pipeline message
+fn block_on<T>(future: impl Future<Output = Result<T, String>>) -> PyResult<T> {
+    futures_lite::future::block_on(future).map_err(PyValueError::new_err)
+}
+
+/// Data values flattened to text, lists and objects as JSON
+fn data(entries: &HashMap<Text, Value>) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(key, value): (&Text, &Value)| {
+            let text: String = match value {
+                Value::String(text) => text.clone(),
+                other => emitter::value_to_json(other).to_string(),
+            };
+            (key.to_string(), text)
+        })
+        .collect()
+}
+
+fn rect(rect: Rect) -> (f64, f64, f64, f64) {
+    (rect.x, rect.y, rect.width, rect.height)
+}
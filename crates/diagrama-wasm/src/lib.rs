@@ -0,0 +1,201 @@
+//! JavaScript bindings, built with `wasm-pack build crates/diagrama-wasm`.
+//! Errors are thrown as `Error` objects carrying the pipeline message.
+
+use lib_convert::{
+    formats::{InputFormat, OutputFormat},
+    pipeline,
+};
+use lib_core::entities::member::MemberKind;
+use lib_json::infrastructure::emitter;
+use lib_plantuml::infrastructure::{
+    syntax::{self, Symbol, SymbolKind},
+    tokens::{self, Token},
+};
+use serde::Serialize;
+use serde_json::Value as Json;
+use wasm_bindgen::prelude::*;
+
+/// Reads a diagram and returns its model as a plain object
+#[wasm_bindgen]
+pub fn parse(source: &str, format: &str) -> Result<JsValue, JsError> {
+    let graph: Json = parse_to_json(source, format).map_err(|e: String| JsError::new(&e))?;
+    graph
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e: serde_wasm_bindgen::Error| JsError::new(&e.to_string()))
+}
+
+/// Converts a diagram between notations (`mermaid`, `plantuml`, `svg`, `json`,
+/// `text`)
+#[wasm_bindgen]
+pub fn convert(source: &str, from: &str, to: &str) -> Result<String, JsError> {
+    convert_source(source, from, to).map_err(|e: String| JsError::new(&e))
+}
+
+/// Lays out a diagram and draws it as SVG
+#[wasm_bindgen(js_name = renderSvg)]
+pub fn render_svg(source: &str, from: &str) -> Result<String, JsError> {
+    convert_source(source, from, OutputFormat::Svg.name()).map_err(|e: String| JsError::new(&e))
+}
+
+/// Classified spans of a PlantUML source for highlighting, as
+/// `{ kind, start, end }` objects with offsets in UTF-16 code units
+#[wasm_bindgen]
+pub fn tokenize(source: &str) -> Result<JsValue, JsError> {
+    highlight(source)
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e: serde_wasm_bindgen::Error| JsError::new(&e.to_string()))
+}
+
+/// Packages, elements and members of a PlantUML source as nested
+/// `{ id, name, kind, start, end, children }` objects, offsets in UTF-16 code units
+#[wasm_bindgen]
+pub fn outline(source: &str) -> Result<JsValue, JsError> {
+    outline_to_json(source)
+        .map_err(|e: String| JsError::new(&e))?
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e: serde_wasm_bindgen::Error| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = inputFormats)]
+pub fn input_formats() -> Vec<String> {
+    InputFormat::ALL
+        .iter()
+        .map(|format: &InputFormat| format.name().to_string())
+        .collect()
+}
+
+#[wasm_bindgen(js_name = outputFormats)]
+pub fn output_formats() -> Vec<String> {
+    OutputFormat::ALL
+        .iter()
+        .map(|format: &OutputFormat| format.name().to_string())
+        .collect()
+}
+
+fn parse_to_json(source: &str, format: &str) -> Result<Json, String> {
+    futures_lite::future::block_on(pipeline::load(source, format.parse()?))
+        .map(|graph| emitter::graph_to_json(&graph))
+}
+
+fn convert_source(source: &str, from: &str, to: &str) -> Result<String, String> {
+    futures_lite::future::block_on(pipeline::convert(source, from.parse()?, to.parse()?))
+}
+
+fn outline_to_json(source: &str) -> Result<Vec<Json>, String> {
+    fn convert(source: &str, symbol: &Symbol) -> Json {
+        let kind: &str = match &symbol.kind {
+            SymbolKind::Element(keyword) => keyword,
+            SymbolKind::Member(MemberKind::Method) => "method",
+            SymbolKind::Member(_) => "field",
+            SymbolKind::Package => "package",
+        };
+        serde_json::json!({
+            "id": symbol.id,
+            "name": symbol.name,
+            "kind": kind,
+            "start": utf16(source, symbol.span.start),
+            "end": utf16(source, symbol.span.end),
+            "children": symbol
+                .children
+                .iter()
+                .map(|child: &Symbol| convert(source, child))
+                .collect::<Vec<Json>>(),
+        })
+    }
+
+    syntax::outline(source)
+        .map(|symbols: Vec<Symbol>| {
+            symbols
+                .iter()
+                .map(|symbol: &Symbol| convert(source, symbol))
+                .collect()
+        })
+        .map_err(String::from)
+}
+
+fn highlight(source: &str) -> Vec<Json> {
+    let utf16 = |offset: usize| utf16(source, offset);
+    tokens::tokenize(source)
+        .iter()
+        .map(|token: &Token| {
+            serde_json::json!({
+                "kind": token.kind.name(),
+                "start": utf16(token.span.start),
+                "end": utf16(token.span.end),
+            })
+        })
+        .collect()
+}
+
+/// JavaScript strings index UTF-16 code units, not bytes
+fn utf16(source: &str, offset: usize) -> usize {
+    source[..offset].encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::{Value as Json, json};
+
+    use crate::{convert_source, highlight, outline_to_json, parse_to_json};
+
+    const PLANTUML: &str = "@startuml\nclass User\nUser --> Profile\n@enduml\n";
+
+    #[test]
+    fn test_parses_to_json() {
+        let graph: Json = parse_to_json(PLANTUML, "plantuml").unwrap();
+
+        assert_eq!(graph["nodes"].as_array().map(Vec::len), Some(2));
+        assert_eq!(graph["edges"][0]["kind"], json!("association"));
+    }
+
+    #[test]
+    fn test_converts_and_renders() {
+        assert!(
+            convert_source(PLANTUML, "plantuml", "mermaid")
+                .unwrap()
+                .starts_with("flowchart")
+        );
+        assert!(
+            convert_source(PLANTUML, "plantuml", "svg")
+                .unwrap()
+                .starts_with("<svg")
+        );
+    }
+
+    #[test]
+    fn test_highlights_with_utf16_offsets() {
+        assert_eq!(
+            highlight("class \"Ü\" as U"),
+            vec![
+                json!({"kind": "keyword", "start": 0, "end": 5}),
+                json!({"kind": "string", "start": 6, "end": 9}),
+                json!({"kind": "keyword", "start": 10, "end": 12}),
+                json!({"kind": "name", "start": 13, "end": 14}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outlines_elements_and_members() {
+        let outline: Vec<Json> =
+            outline_to_json("@startuml\nclass User {\n  +login()\n}\n@enduml").unwrap();
+
+        assert_eq!(outline[0]["kind"], json!("class"));
+        assert_eq!(
+            outline[0]["children"][0],
+            json!({"id": "login", "name": "login", "kind": "method", "start": 25, "end": 33, "children": []})
+        );
+    }
+
+    #[test]
+    fn test_reports_unknown_formats() {
+        assert_eq!(
+            convert_source(PLANTUML, "visio", "svg"),
+            Err(
+                "Unknown input format 'visio', expected one of: plantuml, yuml, dbml, blockdiag, graphml, xmi, excalidraw, rust, sql, openapi, protobuf, cargo, jsonschema, prisma"
+                    .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod asciidoc_document_gateway;
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::document_gateway::{DocumentGateway, DocumentGatewayError},
+    entities::document::SourceBlock,
+};
+
+use crate::infrastructure::parser::{self, AsciiDocParseError};
+
+pub struct AsciiDocDocumentGateway {
+    languages: Vec<String>,
+}
+
+impl AsciiDocDocumentGateway {
+    pub fn new() -> Self {
+        Self::with_languages(&["plantuml", "mermaid"])
+    }
+
+    /// Only diagram blocks whose info string starts with one of these languages are extracted
+    pub fn with_languages(languages: &[&str]) -> Self {
+        Self {
+            languages: languages
+                .iter()
+                .map(|language: &&str| language.to_lowercase())
+                .collect(),
+        }
+    }
+}
+
+impl Default for AsciiDocDocumentGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DocumentGateway for AsciiDocDocumentGateway {
+    async fn read_blocks_from_raw_input(
+        &self,
+        input: &str,
+    ) -> Result<Vec<SourceBlock>, DocumentGatewayError> {
+        parser::parse_asciidoc(input, &self.languages).map_err(DocumentGatewayError::from)
+    }
+}
+
+impl From<AsciiDocParseError> for DocumentGatewayError {
+    fn from(err: AsciiDocParseError) -> Self {
+        match err {
+            AsciiDocParseError::UnterminatedBlock {
+                language,
+                line,
+                column,
+            } => DocumentGatewayError::Parse {
+                source: "asciidoc".into(),
+                message: format!("Unterminated '{}' diagram block", language),
+                line,
+                column,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lib_core::{
+        adapters::document_gateway::{DocumentGateway, DocumentGatewayError},
+        entities::document::{Document, SourceBlock, SourceSpan},
+        use_cases::load_document::{LoadDocument, LoadDocumentUseCase},
+    };
+    use lib_plantuml::infrastructure::adapters::plant_uml_graph_gateway::PlantUmlGraphGateway;
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::asciidoc_document_gateway::AsciiDocDocumentGateway;
+
+    #[test]
+    fn test_extract_diagram_blocks() {
+        smol::block_on(async {
+            let input: &str = "= Title\n\n[plantuml, target=classes, format=svg]\n.Classes\n----\nA --> B\n----\n\n[mermaid]\n....\ngraph TD\n....\n";
+
+            let blocks: Vec<SourceBlock> = AsciiDocDocumentGateway::new()
+                .read_blocks_from_raw_input(input)
+                .await
+                .expect("Failed to extract blocks");
+
+            assert_eq!(
+                blocks,
+                vec![
+                    SourceBlock {
+                        language: "plantuml".to_string(),
+                        source: "A --> B".to_string(),
+                        span: SourceSpan {
+                            start: 62,
+                            end: 70,
+                            start_line: 6,
+                            end_line: 6,
+                        },
+                    },
+                    SourceBlock {
+                        language: "mermaid".to_string(),
+                        source: "graph TD".to_string(),
+                        span: SourceSpan {
+                            start: 91,
+                            end: 100,
+                            start_line: 11,
+                            end_line: 11,
+                        },
+                    },
+                ]
+            );
+            assert_eq!(
+                &input[blocks[0].span.start..blocks[0].span.end],
+                "A --> B\n"
+            );
+        });
+    }
+
+    #[test]
+    fn test_ignores_other_verbatim_blocks() {
+        smol::block_on(async {
+            let input: &str = "[source,plantuml]\n----\nA --> B\n----\n\n----\n[plantuml]\n----\n\n----\n\n////\n[plantuml]\n////\n\n[plantuml]\n\n----\nA --> B\n----\n";
+
+            let blocks: Vec<SourceBlock> = AsciiDocDocumentGateway::new()
+                .read_blocks_from_raw_input(input)
+                .await
+                .expect("Failed to extract blocks");
+
+            assert_eq!(blocks, Vec::new());
+        });
+    }
+
+    #[test]
+    fn test_unterminated_block_is_reported() {
+        smol::block_on(async {
+            let result: Result<Vec<SourceBlock>, DocumentGatewayError> =
+                AsciiDocDocumentGateway::new()
+                    .read_blocks_from_raw_input("Intro\n\n[plantuml]\n----\nA --> B\n")
+                    .await;
+
+            assert_eq!(
+                result,
+                Err(DocumentGatewayError::Parse {
+                    source: "asciidoc".to_string(),
+                    message: "Unterminated 'plantuml' diagram block".to_string(),
+                    line: 4,
+                    column: 1,
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_document_parses_blocks_with_registered_gateways() {
+        smol::block_on(async {
+            let input: &str = "[plantuml]\n----\n@startuml\nA --> B\n@enduml\n----\n";
+
+            let use_case: LoadDocument<AsciiDocDocumentGateway> =
+                LoadDocument::new(Arc::new(AsciiDocDocumentGateway::new()))
+                    .with_graph_gateway("plantuml", Arc::new(PlantUmlGraphGateway::new()));
+
+            let document: Document = use_case
+                .execute(input)
+                .await
+                .expect("Failed to load document");
+
+            assert_eq!(document.blocks.len(), 1);
+            assert_eq!(
+                document.blocks[0].graph.as_ref().map(|g| g.edges.len()),
+                Ok(1)
+            );
+        });
+    }
+}
@@ -0,0 +1,126 @@
+use lib_core::entities::document::{SourceBlock, SourceSpan};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsciiDocParseError {
+    UnterminatedBlock {
+        language: String,
+        line: usize,
+        column: usize,
+    },
+}
+
+struct Delimiter {
+    text: String,
+    // Language of the diagram block, None for other verbatim blocks
+    language: Option<String>,
+    line: usize,
+    content_start: usize,
+}
+
+/// Extracts the delimited blocks styled with one of the given languages
+/// (e.g. `[plantuml, target=diagram]` followed by a `----` listing block).
+/// Other verbatim blocks (listing, literal, passthrough, comment) are skipped.
+pub fn parse_asciidoc(
+    input: &str,
+    languages: &[String],
+) -> Result<Vec<SourceBlock>, AsciiDocParseError> {
+    let mut blocks: Vec<SourceBlock> = Vec::new();
+    let mut open: Option<Delimiter> = None;
+    let mut pending_language: Option<String> = None;
+    let mut content: Vec<&str> = Vec::new();
+    let mut offset: usize = 0;
+
+    for (index, raw_line) in input.split_inclusive('\n').enumerate() {
+        let line_number: usize = index + 1;
+        let line: &str = raw_line.trim_end_matches(['\n', '\r']);
+        let line_start: usize = offset;
+        offset += raw_line.len();
+
+        match &open {
+            Some(delimiter) if line.trim_end() == delimiter.text => {
+                if let Some(language) = &delimiter.language {
+                    blocks.push(SourceBlock {
+                        language: language.clone(),
+                        source: content.join("\n"),
+                        span: SourceSpan {
+                            start: delimiter.content_start,
+                            end: line_start,
+                            start_line: delimiter.line + 1,
+                            end_line: (line_number - 1).max(delimiter.line + 1),
+                        },
+                    });
+                }
+                open = None;
+            }
+            Some(_) => content.push(line),
+            None if is_verbatim_delimiter(line.trim_end()) => {
+                open = Some(Delimiter {
+                    text: line.trim_end().to_string(),
+                    language: pending_language
+                        .take()
+                        .filter(|language: &String| languages.contains(language)),
+                    line: line_number,
+                    content_start: offset,
+                });
+                content.clear();
+            }
+            None => {
+                if let Some(attributes) = attribute_list(line) {
+                    // Only the first attribute line carries the block style
+                    if pending_language.is_none() {
+                        pending_language = Some(block_style(attributes));
+                    }
+                } else if !is_block_title(line) {
+                    pending_language = None;
+                }
+            }
+        }
+    }
+
+    match open {
+        Some(Delimiter {
+            language: Some(language),
+            line,
+            ..
+        }) => Err(AsciiDocParseError::UnterminatedBlock {
+            language,
+            line,
+            column: 1,
+        }),
+        _ => Ok(blocks),
+    }
+}
+
+fn is_verbatim_delimiter(line: &str) -> bool {
+    line.len() >= 4
+        && ['-', '.', '/', '+']
+            .iter()
+            .any(|c: &char| line.chars().all(|l: char| l == *c))
+}
+
+fn attribute_list(line: &str) -> Option<&str> {
+    let line: &str = line.trim_end();
+    if line.starts_with("[[") {
+        // Anchors, not attribute lists
+        return None;
+    }
+
+    line.strip_prefix('[')?.strip_suffix(']')
+}
+
+fn is_block_title(line: &str) -> bool {
+    line.starts_with('.') && !line.starts_with("..") && line.len() > 1
+}
+
+fn block_style(attributes: &str) -> String {
+    // The style is the first positional attribute, possibly followed by id/role shorthands
+    attributes
+        .split(',')
+        .next()
+        .unwrap_or_default()
+        .split(['#', '.', '%'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
@@ -0,0 +1 @@
+pub mod blockdiag_graph_gateway;
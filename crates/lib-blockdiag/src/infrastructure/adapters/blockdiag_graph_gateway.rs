@@ -0,0 +1,269 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, BlockdiagParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct BlockdiagGraphGateway;
+
+impl BlockdiagGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for BlockdiagGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_blockdiag(input)
+            .map_err(GraphGatewayError::from)
+            .map(|ast| transformer::GraphBuilder::new().build(ast))
+    }
+}
+
+impl From<BlockdiagParseError> for GraphGatewayError {
+    fn from(err: BlockdiagParseError) -> Self {
+        match err {
+            BlockdiagParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "blockdiag".into(),
+                message,
+                line,
+                column,
+            },
+            BlockdiagParseError::Internal(msg) => GraphGatewayError::Semantic {
+                source: "blockdiag".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            group::Group,
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::blockdiag_graph_gateway::BlockdiagGraphGateway;
+
+    #[test]
+    fn test_parse_blockdiag_nodes_edges_and_groups() {
+        smol::block_on(async {
+            let source: &str = r##"
+            blockdiag {
+                orientation = portrait;
+
+                A [label = "Start", shape = box];
+                A -> B, C [label = "fan out"];
+                C <- D;
+                B -- E;
+
+                group {
+                    label = "Workers";
+                    color = "#77FF77";
+                    B; C;
+                }
+            }
+            "##;
+
+            let graph: Graph = parse(source).await;
+
+            assert_eq!(graph.kind, GraphKind::Generic);
+            assert_eq!(
+                graph
+                    .metadata
+                    .properties
+                    .get("orientation")
+                    .map(String::as_str),
+                Some("portrait")
+            );
+            assert_eq!(graph.nodes.len(), 5);
+
+            let start: &Node = graph.nodes.get("A").expect("Missing A node");
+            assert_eq!(start.label.as_deref(), Some("Start"));
+            assert_eq!(
+                start.data.get("shape"),
+                Some(&Value::String("box".to_string()))
+            );
+
+            let fan_out: Vec<&Edge> = graph
+                .edges
+                .values()
+                .filter(|e: &&Edge| e.label.as_deref() == Some("fan out"))
+                .collect();
+            assert_eq!(fan_out.len(), 2);
+            assert!(fan_out.iter().all(|e: &&Edge| e.from == "A" && e.directed));
+
+            let reversed: &Edge = find_edge(&graph, "D", "C").expect("Missing D -> C edge");
+            assert_eq!(reversed.kind, EdgeKind::Association);
+
+            let undirected: &Edge = find_edge(&graph, "B", "E").expect("Missing B -- E edge");
+            assert_eq!(undirected.kind, EdgeKind::Undirected);
+            assert!(!undirected.directed);
+
+            let group: &Group = graph.groups.values().next().expect("Missing group");
+            assert_eq!(group.label.as_deref(), Some("Workers"));
+            assert_eq!(group.children, vec!["B".to_string(), "C".to_string()]);
+        });
+    }
+
+    #[test]
+    fn test_parse_nwdiag_networks() {
+        smol::block_on(async {
+            let source: &str = r#"
+            nwdiag {
+                network dmz {
+                    address = "210.x.x.x/24";
+                    web01 [address = "210.x.x.1"];
+                    web02;
+                }
+                network internal {
+                    web01 [address = "172.x.x.1"];
+                    db01;
+                }
+            }
+            "#;
+
+            let graph: Graph = parse(source).await;
+
+            assert_eq!(graph.kind, GraphKind::Network);
+
+            let dmz: &Node = graph.nodes.get("dmz").expect("Missing dmz network");
+            assert_eq!(dmz.kind, NodeKind::Custom("network".to_string()));
+            assert_eq!(
+                dmz.data.get("address"),
+                Some(&Value::String("210.x.x.x/24".to_string()))
+            );
+
+            let web01_dmz: &Edge = find_edge(&graph, "web01", "dmz").expect("Missing link");
+            assert_eq!(web01_dmz.label.as_deref(), Some("210.x.x.1"));
+            let web01_internal: &Edge =
+                find_edge(&graph, "web01", "internal").expect("Missing link");
+            assert_eq!(web01_internal.label.as_deref(), Some("172.x.x.1"));
+            assert!(find_edge(&graph, "db01", "internal").is_some());
+            assert_eq!(graph.edges.len(), 4);
+        });
+    }
+
+    #[test]
+    fn test_parse_seqdiag_messages_in_order() {
+        smol::block_on(async {
+            let source: &str = r#"
+            seqdiag {
+                browser -> webserver [label = "GET /index.html"] {
+                    webserver -> database;
+                }
+                browser <-- webserver;
+            }
+            "#;
+
+            let graph: Graph = parse(source).await;
+
+            assert_eq!(graph.kind, GraphKind::Sequence);
+
+            let request: &Edge = find_edge(&graph, "browser", "webserver").expect("Missing");
+            assert_eq!(request.kind, EdgeKind::Flow);
+            assert_eq!(request.data.get("sequence"), Some(&Value::Number(1.0)));
+
+            let query: &Edge = find_edge(&graph, "webserver", "database").expect("Missing");
+            assert_eq!(query.data.get("sequence"), Some(&Value::Number(2.0)));
+
+            let reply: &Edge = graph
+                .edges
+                .values()
+                .find(|e: &&Edge| e.data.get("sequence") == Some(&Value::Number(3.0)))
+                .expect("Missing reply");
+            assert_eq!(
+                (reply.from.as_str(), reply.to.as_str()),
+                ("webserver", "browser")
+            );
+            assert_eq!(
+                reply.data.get("line_style"),
+                Some(&Value::String("dashed".to_string()))
+            );
+        });
+    }
+
+    #[test]
+    fn test_invalid_source_is_reported_as_parse_error() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = BlockdiagGraphGateway::new()
+                .read_graph_from_raw_input("blockdiag { A -> ; }")
+                .await;
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Parse { ref source, .. }) if source == "blockdiag"),
+                "Expected a blockdiag parse error, got {:?}",
+                result
+            );
+        });
+    }
+
+    #[test]
+    fn test_deep_groups_are_a_parse_error() {
+        smol::block_on(async {
+            let nested = |depth: usize| -> String {
+                format!(
+                    "blockdiag {{\n{}A;{}\n}}",
+                    "group { label = \"{\";\n".repeat(depth),
+                    "}".repeat(depth)
+                )
+            };
+            assert!(
+                BlockdiagGraphGateway::new()
+                    .read_graph_from_raw_input(&nested(63))
+                    .await
+                    .is_ok()
+            );
+
+            let result: Result<Graph, GraphGatewayError> = BlockdiagGraphGateway::new()
+                .read_graph_from_raw_input(&nested(20_000))
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Parse {
+                    message,
+                    line,
+                    column,
+                    ..
+                }) => {
+                    assert_eq!(message, "Blocks nest past the depth limit of 64");
+                    assert_eq!((line, column), (65, 7));
+                }
+                other => panic!("Expected GraphGatewayError::Parse, got {:?}", other),
+            }
+        });
+    }
+
+    async fn parse(source: &str) -> Graph {
+        BlockdiagGraphGateway::new()
+            .read_graph_from_raw_input(source)
+            .await
+            .expect("Failed to parse valid source")
+    }
+
+    fn find_edge<'a>(graph: &'a Graph, from: &str, to: &str) -> Option<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .find(|e: &&Edge| e.from == from && e.to == to)
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod blockdiag_ast;
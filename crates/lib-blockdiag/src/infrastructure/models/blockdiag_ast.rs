@@ -0,0 +1,37 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockdiagDocument {
+    pub diagram_type: String,
+    pub statements: Vec<BlockdiagAst>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockdiagAst {
+    Attribute {
+        key: String,
+        value: String,
+    },
+    Node {
+        id: String,
+        attributes: Vec<BlockdiagAttribute>,
+    },
+    Edge {
+        nodes: Vec<Vec<String>>,
+        arrows: Vec<String>,
+        attributes: Vec<BlockdiagAttribute>,
+        children: Vec<BlockdiagAst>,
+    },
+    Group {
+        name: Option<String>,
+        children: Vec<BlockdiagAst>,
+    },
+    Network {
+        name: Option<String>,
+        children: Vec<BlockdiagAst>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockdiagAttribute {
+    pub key: String,
+    pub value: Option<String>,
+}
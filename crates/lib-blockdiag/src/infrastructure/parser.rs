@@ -0,0 +1,155 @@
+use lib_core::nesting;
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::infrastructure::models::blockdiag_ast::{
+    BlockdiagAst, BlockdiagAttribute, BlockdiagDocument,
+};
+
+/// How deep groups, networks and edge blocks may nest inside each other
+const MAX_DEPTH: usize = 64;
+
+#[derive(Parser)]
+#[grammar = "infrastructure/blockdiag.pest"]
+pub struct BlockdiagParser;
+
+pub fn parse_blockdiag(input: &str) -> Result<BlockdiagDocument, BlockdiagParseError> {
+    if let Some((line, column)) = nesting::deepest_brace(input, &['"'], MAX_DEPTH) {
+        return Err(BlockdiagParseError::Syntax {
+            message: format!("Blocks nest past the depth limit of {}", MAX_DEPTH),
+            line,
+            column,
+        });
+    }
+    let document: pest::iterators::Pair<Rule> = BlockdiagParser::parse(Rule::document, input)
+        .map_err(BlockdiagParseError::from)?
+        .next()
+        .ok_or_else(|| BlockdiagParseError::Internal("Missing document root".to_string()))?;
+
+    let mut inner: pest::iterators::Pairs<Rule> = document.into_inner();
+    let diagram_type: String = inner
+        .next()
+        .map(|p: pest::iterators::Pair<Rule>| p.as_str().to_string())
+        .ok_or_else(|| BlockdiagParseError::Internal("Missing diagram type".to_string()))?;
+
+    Ok(BlockdiagDocument {
+        diagram_type,
+        statements: inner.filter_map(parse_statement).collect(),
+    })
+}
+
+fn parse_statement(pair: pest::iterators::Pair<Rule>) -> Option<BlockdiagAst> {
+    match pair.as_rule() {
+        Rule::attribute => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let key: String = inner.next()?.as_str().to_string();
+            let value: String = unquote(inner.next()?.as_str());
+
+            Some(BlockdiagAst::Attribute { key, value })
+        }
+        Rule::node => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let id: String = unquote(inner.next()?.as_str());
+            let attributes: Vec<BlockdiagAttribute> =
+                inner.next().map(parse_attributes).unwrap_or_default();
+
+            Some(BlockdiagAst::Node { id, attributes })
+        }
+        Rule::edge => {
+            let mut nodes: Vec<Vec<String>> = Vec::new();
+            let mut arrows: Vec<String> = Vec::new();
+            let mut attributes: Vec<BlockdiagAttribute> = Vec::new();
+            let mut children: Vec<BlockdiagAst> = Vec::new();
+
+            pair.into_inner()
+                .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+                    Rule::node_list => nodes.push(
+                        item.into_inner()
+                            .map(|n: pest::iterators::Pair<Rule>| unquote(n.as_str()))
+                            .collect(),
+                    ),
+                    Rule::edge_op => arrows.push(item.as_str().to_string()),
+                    Rule::attributes => attributes = parse_attributes(item),
+                    _ => children.extend(parse_statement(item)),
+                });
+
+            Some(BlockdiagAst::Edge {
+                nodes,
+                arrows,
+                attributes,
+                children,
+            })
+        }
+        Rule::group => {
+            let (name, children): (Option<String>, Vec<BlockdiagAst>) =
+                parse_block(pair.into_inner());
+
+            Some(BlockdiagAst::Group { name, children })
+        }
+        Rule::network => {
+            let (name, children): (Option<String>, Vec<BlockdiagAst>) =
+                parse_block(pair.into_inner());
+
+            Some(BlockdiagAst::Network { name, children })
+        }
+        _ => None,
+    }
+}
+
+fn parse_block(inner: pest::iterators::Pairs<Rule>) -> (Option<String>, Vec<BlockdiagAst>) {
+    let mut name: Option<String> = None;
+    let mut children: Vec<BlockdiagAst> = Vec::new();
+
+    inner.for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+        Rule::identifier => name = Some(item.as_str().to_string()),
+        _ => children.extend(parse_statement(item)),
+    });
+
+    (name, children)
+}
+
+fn parse_attributes(pair: pest::iterators::Pair<Rule>) -> Vec<BlockdiagAttribute> {
+    pair.into_inner()
+        .filter_map(|entry: pest::iterators::Pair<Rule>| {
+            let mut inner: pest::iterators::Pairs<Rule> = entry.into_inner();
+            let key: String = inner.next()?.as_str().to_string();
+            let value: Option<String> = inner
+                .next()
+                .map(|v: pest::iterators::Pair<Rule>| unquote(v.as_str()));
+
+            Some(BlockdiagAttribute { key, value })
+        })
+        .collect()
+}
+
+fn unquote(raw: &str) -> String {
+    raw.strip_prefix('"')
+        .and_then(|s: &str| s.strip_suffix('"'))
+        .unwrap_or(raw)
+        .to_string()
+}
+
+#[derive(Debug)]
+pub enum BlockdiagParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Internal(String),
+}
+
+impl From<pest::error::Error<Rule>> for BlockdiagParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column): (usize, usize) = match err.line_col {
+            pest::error::LineColLocation::Pos((l, c)) => (l, c),
+            pest::error::LineColLocation::Span((l, c), _) => (l, c),
+        };
+
+        BlockdiagParseError::Syntax {
+            message: err.to_string(),
+            line,
+            column,
+        }
+    }
+}
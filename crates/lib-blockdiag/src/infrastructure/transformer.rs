@@ -0,0 +1,293 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use uuid::Uuid;
+
+use crate::infrastructure::models::blockdiag_ast::{
+    BlockdiagAst, BlockdiagAttribute, BlockdiagDocument,
+};
+
+pub struct GraphBuilder {
+    graph: Graph,
+    message_count: usize, // Orders seqdiag messages as they appear in the source
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                ..Default::default()
+            },
+            message_count: 0,
+        }
+    }
+
+    pub fn build(mut self, document: BlockdiagDocument) -> Graph {
+        self.graph.kind = match document.diagram_type.as_str() {
+            "nwdiag" => GraphKind::Network,
+            "seqdiag" => GraphKind::Sequence,
+            _ => GraphKind::Generic,
+        };
+        self.graph
+            .metadata
+            .properties
+            .insert("diagram_type".to_string(), document.diagram_type);
+
+        document
+            .statements
+            .iter()
+            .for_each(|statement: &BlockdiagAst| {
+                self.process_ast_node(statement, None, None);
+            });
+        self.graph
+    }
+
+    fn process_ast_node(
+        &mut self,
+        statement: &BlockdiagAst,
+        group_id: Option<&str>,
+        network_id: Option<&str>,
+    ) {
+        match statement {
+            BlockdiagAst::Attribute { key, value } => {
+                self.apply_attribute(key, value, group_id, network_id);
+            }
+            BlockdiagAst::Node { id, attributes } => {
+                self.ensure_node_exists(id, group_id);
+
+                let mut address: Option<String> = None;
+                attributes
+                    .iter()
+                    .for_each(|attribute: &BlockdiagAttribute| {
+                        match (attribute.key.as_str(), network_id) {
+                            // Addresses are specific to the network the node is declared in
+                            ("address", Some(_)) => address = attribute.value.clone(),
+                            _ => self.apply_node_attribute(id, attribute),
+                        }
+                    });
+
+                if let Some(network) = network_id {
+                    self.add_edge(id, network, false, EdgeKind::Undirected, address);
+                }
+            }
+            BlockdiagAst::Edge {
+                nodes,
+                arrows,
+                attributes,
+                children,
+            } => {
+                nodes
+                    .iter()
+                    .flatten()
+                    .for_each(|id: &String| self.ensure_node_exists(id, group_id));
+
+                nodes.windows(2).zip(arrows.iter()).for_each(
+                    |(pair, arrow): (&[Vec<String>], &String)| {
+                        pair[0].iter().for_each(|left: &String| {
+                            pair[1].iter().for_each(|right: &String| {
+                                self.add_message(left, right, arrow, attributes);
+                            });
+                        });
+                    },
+                );
+
+                children.iter().for_each(|child: &BlockdiagAst| {
+                    self.process_ast_node(child, group_id, network_id);
+                });
+            }
+            BlockdiagAst::Group { name, children } => {
+                let id: Id = Uuid::new_v4().to_string().into();
+                self.graph.groups.insert(
+                    id.clone(),
+                    Group {
+                        id: id.clone(),
+                        label: name.as_deref().map(Text::from),
+                        parent: group_id.map(Id::from),
+                        ..Default::default()
+                    },
+                );
+
+                children.iter().for_each(|child: &BlockdiagAst| {
+                    self.process_ast_node(child, Some(&id), network_id);
+                });
+            }
+            BlockdiagAst::Network { name, children } => {
+                let id: Id = Id::from(name.clone().unwrap_or_else(|| Uuid::new_v4().to_string()));
+                self.graph.nodes.insert(
+                    id.clone(),
+                    Node {
+                        id: id.clone(),
+                        kind: NodeKind::Custom("network".to_string()),
+                        label: name.as_deref().map(Text::from),
+                        parent: group_id.map(Id::from),
+                        ..Default::default()
+                    },
+                );
+
+                children.iter().for_each(|child: &BlockdiagAst| {
+                    self.process_ast_node(child, group_id, Some(&id));
+                });
+            }
+        }
+    }
+
+    fn apply_attribute(
+        &mut self,
+        key: &str,
+        value: &str,
+        group_id: Option<&str>,
+        network_id: Option<&str>,
+    ) {
+        if let Some(network) = network_id {
+            self.apply_node_attribute(
+                network,
+                &BlockdiagAttribute {
+                    key: key.to_string(),
+                    value: Some(value.to_string()),
+                },
+            );
+        } else if let Some(group) = group_id.and_then(|g| self.graph.groups.get_mut(g)) {
+            if key == "label" {
+                group.label = Some(value.into());
+            }
+        } else {
+            self.graph
+                .metadata
+                .properties
+                .insert(key.to_string(), value.to_string());
+        }
+    }
+
+    fn apply_node_attribute(&mut self, id: &str, attribute: &BlockdiagAttribute) {
+        let Some(node) = self.graph.nodes.get_mut(id) else {
+            return;
+        };
+
+        match (attribute.key.as_str(), &attribute.value) {
+            ("label", Some(label)) => node.label = Some(label.as_str().into()),
+            (key, Some(value)) => {
+                node.data.insert(key.into(), Value::String(value.clone()));
+            }
+            (key, None) => {
+                node.data.insert(key.into(), Value::Bool(true));
+            }
+        }
+    }
+
+    fn add_message(
+        &mut self,
+        left: &str,
+        right: &str,
+        arrow: &str,
+        attributes: &[BlockdiagAttribute],
+    ) {
+        let (from, to): (&str, &str) = if arrow.starts_with('<') && arrow != "<->" {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        let directed: bool = arrow != "--";
+
+        let kind: EdgeKind = match (&self.graph.kind, directed) {
+            (GraphKind::Sequence, _) => EdgeKind::Flow,
+            (_, true) => EdgeKind::Association,
+            (_, false) => EdgeKind::Undirected,
+        };
+
+        let label: Option<String> = attributes
+            .iter()
+            .find(|a: &&BlockdiagAttribute| a.key == "label")
+            .and_then(|a: &BlockdiagAttribute| a.value.clone());
+
+        let edge_id: Id = self.add_edge(from, to, directed, kind, label);
+        let Some(edge) = self.graph.edges.get_mut(edge_id.as_str()) else {
+            return;
+        };
+
+        if arrow == "<->" {
+            edge.data.insert("bidirectional".into(), Value::Bool(true));
+        }
+        if arrow.contains("--") && directed {
+            edge.data
+                .insert("line_style".into(), Value::String("dashed".to_string()));
+        }
+        if arrow.contains(">>") || arrow.contains("<<") {
+            edge.data.insert("async".into(), Value::Bool(true));
+        }
+        if edge.kind == EdgeKind::Flow {
+            self.message_count += 1;
+            edge.data
+                .insert("sequence".into(), Value::Number(self.message_count as f64));
+        }
+
+        attributes
+            .iter()
+            .filter(|a: &&BlockdiagAttribute| a.key != "label")
+            .for_each(|a: &BlockdiagAttribute| {
+                edge.data.insert(
+                    a.key.as_str().into(),
+                    a.value
+                        .clone()
+                        .map(Value::String)
+                        .unwrap_or(Value::Bool(true)),
+                );
+            });
+    }
+
+    fn add_edge(
+        &mut self,
+        from: &str,
+        to: &str,
+        directed: bool,
+        kind: EdgeKind,
+        label: Option<String>,
+    ) -> Id {
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id.clone(),
+                from: from.into(),
+                to: to.into(),
+                directed,
+                kind,
+                label: label.map(Text::from),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+        edge_id
+    }
+
+    fn ensure_node_exists(&mut self, id: &str, group_id: Option<&str>) {
+        if !self.graph.nodes.contains_key(id) {
+            self.graph.nodes.insert(
+                id.into(),
+                Node {
+                    id: id.into(),
+                    kind: NodeKind::Entity,
+                    label: Some(id.into()),
+                    ..Default::default()
+                },
+            );
+        }
+
+        // Nodes mentioned inside a group belong to the first group that mentions them
+        let Some(group) = group_id.and_then(|g| self.graph.groups.get_mut(g)) else {
+            return;
+        };
+        let Some(node) = self.graph.nodes.get_mut(id) else {
+            return;
+        };
+        if node.parent.is_none() && node.kind != NodeKind::Custom("network".to_string()) {
+            node.parent = Some(group.id.clone());
+            group.children.push(id.into());
+        }
+    }
+}
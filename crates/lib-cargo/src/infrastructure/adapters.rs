@@ -0,0 +1 @@
+pub mod cargo_graph_gateway;
@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, CargoParseError},
+    transformer,
+};
+
+/// Reads the JSON `cargo metadata --format-version 1` prints, drawing the
+/// workspace members and their direct dependencies
+#[derive(Default)]
+pub struct CargoGraphGateway;
+
+impl CargoGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for CargoGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_cargo_metadata(input)
+            .map_err(GraphGatewayError::from)
+            .map(|metadata| transformer::GraphBuilder::new().build(metadata))
+    }
+}
+
+impl From<CargoParseError> for GraphGatewayError {
+    fn from(err: CargoParseError) -> Self {
+        match err {
+            CargoParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "cargo".into(),
+                message,
+                line,
+                column,
+            },
+            CargoParseError::Semantic(msg) => GraphGatewayError::Semantic {
+                source: "cargo".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::Graph,
+            id::Id,
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::cargo_graph_gateway::CargoGraphGateway;
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {"id": "path+file:///ws/app#0.1.0", "name": "app", "version": "0.1.0", "source": null, "dependencies": []},
+            {"id": "path+file:///ws/core#0.1.0", "name": "core", "version": "0.1.0", "source": null, "dependencies": []},
+            {"id": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.200", "name": "serde", "version": "1.0.200",
+             "source": "registry+https://github.com/rust-lang/crates.io-index", "dependencies": []},
+            {"id": "registry+https://github.com/rust-lang/crates.io-index#syn@1.0.109", "name": "syn", "version": "1.0.109",
+             "source": "registry+https://github.com/rust-lang/crates.io-index", "dependencies": []},
+            {"id": "registry+https://github.com/rust-lang/crates.io-index#syn@2.0.60", "name": "syn", "version": "2.0.60",
+             "source": "registry+https://github.com/rust-lang/crates.io-index", "dependencies": []},
+            {"id": "registry+https://github.com/rust-lang/crates.io-index#itoa@1.0.11", "name": "itoa", "version": "1.0.11",
+             "source": "registry+https://github.com/rust-lang/crates.io-index", "dependencies": []}
+        ],
+        "workspace_members": ["path+file:///ws/app#0.1.0", "path+file:///ws/core#0.1.0"],
+        "resolve": {
+            "nodes": [
+                {"id": "path+file:///ws/app#0.1.0", "deps": [
+                    {"name": "core", "pkg": "path+file:///ws/core#0.1.0", "dep_kinds": [{"kind": null, "target": null}]},
+                    {"name": "syn", "pkg": "registry+https://github.com/rust-lang/crates.io-index#syn@1.0.109", "dep_kinds": [{"kind": "build", "target": null}]}
+                ]},
+                {"id": "path+file:///ws/core#0.1.0", "deps": [
+                    {"name": "serde", "pkg": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.200",
+                     "dep_kinds": [{"kind": "dev", "target": null}, {"kind": null, "target": null}]},
+                    {"name": "syn", "pkg": "registry+https://github.com/rust-lang/crates.io-index#syn@2.0.60", "dep_kinds": [{"kind": "dev", "target": null}]}
+                ]},
+                {"id": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.200", "deps": [
+                    {"name": "itoa", "pkg": "registry+https://github.com/rust-lang/crates.io-index#itoa@1.0.11", "dep_kinds": [{"kind": null, "target": null}]}
+                ]}
+            ],
+            "root": null
+        },
+        "workspace_root": "/ws",
+        "version": 1
+    }"#;
+
+    fn edge<'a>(graph: &'a Graph, from: &str, to: &str) -> &'a Edge {
+        graph
+            .edges
+            .values()
+            .find(|edge: &&Edge| edge.from == from && edge.to == to)
+            .unwrap_or_else(|| panic!("Missing edge {} -> {}", from, to))
+    }
+
+    #[test]
+    fn test_members_and_direct_dependencies_are_clustered() {
+        smol::block_on(async {
+            let graph: Graph = CargoGraphGateway::new()
+                .read_graph_from_raw_input(METADATA)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                graph.groups["workspace"].children,
+                vec![Id::from("app"), Id::from("core")]
+            );
+            assert_eq!(
+                graph.groups["external"].children,
+                vec![
+                    Id::from("syn@1.0.109"),
+                    Id::from("serde"),
+                    Id::from("syn@2.0.60")
+                ]
+            );
+
+            assert_eq!(
+                graph
+                    .metadata
+                    .properties
+                    .get("workspace_root")
+                    .map(String::as_str),
+                Some("/ws")
+            );
+
+            // Only direct dependencies of the workspace are drawn
+            assert!(!graph.nodes.contains_key("itoa"));
+
+            let serde: &Node = &graph.nodes["serde"];
+            assert_eq!(serde.kind, NodeKind::Component);
+            assert_eq!(serde.label.as_deref(), Some("serde"));
+            assert_eq!(
+                serde.data.get("version"),
+                Some(&Value::String("1.0.200".to_string()))
+            );
+            assert_eq!(serde.parent, Some(Id::from("external")));
+        });
+    }
+
+    #[test]
+    fn test_dependencies_keep_their_strongest_kind() {
+        smol::block_on(async {
+            let graph: Graph = CargoGraphGateway::new()
+                .read_graph_from_raw_input(METADATA)
+                .await
+                .unwrap();
+
+            assert_eq!(graph.edges.len(), 4);
+            assert_eq!(edge(&graph, "app", "core").kind, EdgeKind::Dependency);
+            assert_eq!(edge(&graph, "app", "core").data.get("kind"), None);
+            assert_eq!(edge(&graph, "core", "serde").data.get("kind"), None);
+            assert_eq!(
+                edge(&graph, "app", "syn@1.0.109").data.get("kind"),
+                Some(&Value::String("build".to_string()))
+            );
+            assert_eq!(
+                edge(&graph, "core", "syn@2.0.60").data.get("kind"),
+                Some(&Value::String("dev".to_string()))
+            );
+        });
+    }
+
+    #[test]
+    fn test_unresolved_metadata_falls_back_to_declarations() {
+        smol::block_on(async {
+            let graph: Graph = CargoGraphGateway::new()
+                .read_graph_from_raw_input(
+                    r#"{
+                        "packages": [
+                            {"id": "app 0.1.0", "name": "app", "version": "0.1.0", "source": null, "dependencies": [
+                                {"name": "core", "req": "*", "kind": null, "optional": false},
+                                {"name": "serde", "req": "^1.0", "kind": null, "optional": false},
+                                {"name": "serde", "req": "^1.0", "kind": "dev", "optional": false}
+                            ]},
+                            {"id": "core 0.1.0", "name": "core", "version": "0.1.0", "source": null, "dependencies": []}
+                        ],
+                        "workspace_members": ["app 0.1.0", "core 0.1.0"],
+                        "resolve": null
+                    }"#,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(graph.nodes.len(), 3);
+            assert_eq!(graph.edges.len(), 2);
+            assert_eq!(edge(&graph, "app", "core").kind, EdgeKind::Dependency);
+            assert_eq!(
+                graph.nodes["serde"].data.get("version"),
+                Some(&Value::String("^1.0".to_string()))
+            );
+            assert_eq!(edge(&graph, "app", "serde").data.get("kind"), None);
+        });
+    }
+
+    #[test]
+    fn test_other_json_is_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = CargoGraphGateway::new()
+                .read_graph_from_raw_input(r#"{"elements": []}"#)
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Semantic { source, message }) => {
+                    assert_eq!(source, "cargo");
+                    assert_eq!(
+                        message,
+                        "Expected `cargo metadata` output listing workspace members"
+                    );
+                }
+                other => panic!("Expected a semantic error, got {:?}", other),
+            }
+        });
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+/// The output of `cargo metadata --format-version 1`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CargoMetadata {
+    #[serde(default)]
+    pub packages: Vec<CargoPackage>,
+    #[serde(default)]
+    pub workspace_members: Vec<String>,
+    /// Missing when the metadata was read with `--no-deps`
+    pub resolve: Option<CargoResolve>,
+    pub workspace_root: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CargoPackage {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<CargoDependency>,
+}
+
+/// A dependency as its manifest declares it, before resolution
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CargoDependency {
+    pub name: String,
+    pub req: String,
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CargoResolve {
+    #[serde(default)]
+    pub nodes: Vec<CargoResolveNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CargoResolveNode {
+    pub id: String,
+    #[serde(default)]
+    pub deps: Vec<CargoNodeDep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CargoNodeDep {
+    pub pkg: String,
+    #[serde(default)]
+    pub dep_kinds: Vec<CargoDepKind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CargoDepKind {
+    /// `dev` or `build`, or nothing for a normal dependency
+    pub kind: Option<String>,
+}
@@ -0,0 +1,33 @@
+use crate::infrastructure::models::cargo_metadata::CargoMetadata;
+
+pub fn parse_cargo_metadata(input: &str) -> Result<CargoMetadata, CargoParseError> {
+    let metadata: CargoMetadata = serde_json::from_str(input).map_err(CargoParseError::from)?;
+
+    if metadata.workspace_members.is_empty() {
+        return Err(CargoParseError::Semantic(
+            "Expected `cargo metadata` output listing workspace members".to_string(),
+        ));
+    }
+
+    Ok(metadata)
+}
+
+#[derive(Debug)]
+pub enum CargoParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Semantic(String),
+}
+
+impl From<serde_json::Error> for CargoParseError {
+    fn from(err: serde_json::Error) -> Self {
+        CargoParseError::Syntax {
+            message: err.to_string(),
+            line: err.line(),
+            column: err.column(),
+        }
+    }
+}
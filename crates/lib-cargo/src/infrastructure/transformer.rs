@@ -0,0 +1,251 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::Graph,
+    group::Group,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::cargo_metadata::{
+    CargoDepKind, CargoDependency, CargoMetadata, CargoNodeDep, CargoPackage, CargoResolveNode,
+};
+
+const WORKSPACE: &str = "workspace";
+const EXTERNAL: &str = "external";
+
+/// A crate drawn in the diagram, keyed by its package id, or by its name
+/// for dependencies that were never resolved
+struct Crate {
+    key: String,
+    name: String,
+    version: String,
+    source: Option<String>,
+    member: bool,
+}
+
+pub struct GraphBuilder {
+    graph: Graph,
+    crates: Vec<Crate>,
+    dependencies: Vec<(String, String, Option<String>)>, // From and to crate keys, with the dependency kind
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                ..Default::default()
+            },
+            crates: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Draws the workspace members and the crates they directly depend on
+    pub fn build(mut self, metadata: CargoMetadata) -> Graph {
+        let packages: HashMap<&str, &CargoPackage> = metadata
+            .packages
+            .iter()
+            .map(|package: &CargoPackage| (package.id.as_str(), package))
+            .collect();
+        let members: Vec<&CargoPackage> = metadata
+            .workspace_members
+            .iter()
+            .filter_map(|id: &String| packages.get(id.as_str()).copied())
+            .collect();
+        members
+            .iter()
+            .for_each(|member: &&CargoPackage| self.add_crate(member, true));
+
+        if let Some(root) = metadata.workspace_root {
+            self.graph
+                .metadata
+                .properties
+                .insert("workspace_root".to_string(), root);
+        }
+
+        match &metadata.resolve {
+            Some(resolve) => {
+                let nodes: HashMap<&str, &CargoResolveNode> = resolve
+                    .nodes
+                    .iter()
+                    .map(|node: &CargoResolveNode| (node.id.as_str(), node))
+                    .collect();
+
+                members.iter().for_each(|member: &&CargoPackage| {
+                    let deps: &[CargoNodeDep] = nodes
+                        .get(member.id.as_str())
+                        .map_or(&[], |node: &&CargoResolveNode| &node.deps);
+                    deps.iter().for_each(|dep: &CargoNodeDep| {
+                        let Some(target) = packages.get(dep.pkg.as_str()) else {
+                            return;
+                        };
+                        self.add_crate(target, false);
+                        self.add_dependency(
+                            &member.id,
+                            &target.id,
+                            strongest(
+                                dep.dep_kinds
+                                    .iter()
+                                    .map(|kind: &CargoDepKind| kind.kind.clone()),
+                            ),
+                        );
+                    });
+                });
+            }
+            // Read with `--no-deps`, only what the manifests declare is known
+            None => members.iter().for_each(|member: &&CargoPackage| {
+                member
+                    .dependencies
+                    .iter()
+                    .for_each(|dependency: &CargoDependency| {
+                        let target: String = match members
+                            .iter()
+                            .find(|other: &&&CargoPackage| other.name == dependency.name)
+                        {
+                            Some(other) => other.id.clone(),
+                            None => {
+                                self.push_crate(Crate {
+                                    key: dependency.name.clone(),
+                                    name: dependency.name.clone(),
+                                    version: dependency.req.clone(),
+                                    source: None,
+                                    member: false,
+                                });
+                                dependency.name.clone()
+                            }
+                        };
+                        self.add_dependency(&member.id, &target, dependency.kind.clone());
+                    });
+            }),
+        }
+
+        self.emit()
+    }
+
+    fn add_crate(&mut self, package: &CargoPackage, member: bool) {
+        self.push_crate(Crate {
+            key: package.id.clone(),
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source: package.source.clone(),
+            member,
+        });
+    }
+
+    fn push_crate(&mut self, c: Crate) {
+        if !self.crates.iter().any(|other: &Crate| other.key == c.key) {
+            self.crates.push(c);
+        }
+    }
+
+    /// Records a dependency, keeping the strongest kind when a crate is
+    /// depended on several times, as for both building and testing
+    fn add_dependency(&mut self, from: &str, to: &str, kind: Option<String>) {
+        match self
+            .dependencies
+            .iter_mut()
+            .find(|(f, t, _): &&mut (String, String, Option<String>)| f == from && t == to)
+        {
+            Some(existing) => existing.2 = strongest([existing.2.take(), kind]),
+            None => self
+                .dependencies
+                .push((from.to_string(), to.to_string(), kind)),
+        }
+    }
+
+    fn emit(mut self) -> Graph {
+        let mut names: HashMap<&str, usize> = HashMap::new();
+        self.crates.iter().for_each(|c: &Crate| {
+            *names.entry(c.name.as_str()).or_default() += 1;
+        });
+
+        [(WORKSPACE, "Workspace"), (EXTERNAL, "External")]
+            .into_iter()
+            .for_each(|(id, label): (&str, &str)| {
+                self.graph.groups.insert(
+                    Id::from(id),
+                    Group {
+                        id: Id::from(id),
+                        label: Some(label.into()),
+                        ..Default::default()
+                    },
+                );
+            });
+
+        let mut ids: HashMap<&str, Id> = HashMap::new();
+        self.crates.iter().for_each(|c: &Crate| {
+            // Crates present in several versions are told apart by them
+            let id: Id = if names[c.name.as_str()] > 1 {
+                format!("{}@{}", c.name, c.version).into()
+            } else {
+                Id::from(c.name.as_str())
+            };
+            ids.insert(c.key.as_str(), id.clone());
+
+            let group: Id = Id::from(if c.member { WORKSPACE } else { EXTERNAL });
+            if let Some(group) = self.graph.groups.get_mut(&group) {
+                group.children.push(id.clone());
+            }
+
+            let mut data: HashMap<Text, Value> =
+                HashMap::from([("version".into(), Value::String(c.version.clone()))]);
+            if let Some(source) = &c.source {
+                data.insert("source".into(), Value::String(source.clone()));
+            }
+
+            self.graph.nodes.insert(
+                id.clone(),
+                Node {
+                    id,
+                    kind: NodeKind::Component,
+                    label: Some(c.name.as_str().into()),
+                    data,
+                    parent: Some(group),
+                    ..Default::default()
+                },
+            );
+        });
+
+        self.dependencies
+            .iter()
+            .for_each(|(from, to, kind): &(String, String, Option<String>)| {
+                let mut data: HashMap<Text, Value> = HashMap::new();
+                if let Some(kind) = kind {
+                    data.insert("kind".into(), Value::String(kind.clone()));
+                }
+
+                let edge_id: Id = Uuid::new_v4().to_string().into();
+                self.graph.edges.insert(
+                    edge_id.clone(),
+                    Edge {
+                        id: edge_id,
+                        from: ids[from.as_str()].clone(),
+                        to: ids[to.as_str()].clone(),
+                        directed: true,
+                        kind: EdgeKind::Dependency,
+                        data,
+                        order: self.graph.edges.len(),
+                        ..Default::default()
+                    },
+                );
+            });
+
+        self.graph
+    }
+}
+
+/// Normal dependencies (no kind) outrank build ones, which outrank dev ones
+fn strongest(kinds: impl IntoIterator<Item = Option<String>>) -> Option<String> {
+    kinds
+        .into_iter()
+        .min_by_key(|kind: &Option<String>| match kind.as_deref() {
+            None => 0,
+            Some("build") => 1,
+            Some(_) => 2,
+        })
+        .flatten()
+}
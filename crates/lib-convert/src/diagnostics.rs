@@ -0,0 +1,327 @@
+use std::fmt;
+
+use lib_core::adapters::graph_gateway::GraphGatewayError;
+
+use crate::formats::InputFormat;
+
+/// A problem found while reading a source, located in it when possible
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Notation whose reader reported the problem, as in `plantuml`
+    pub source: Option<String>,
+    pub message: String,
+    /// 1-based line and column
+    pub location: Option<(usize, usize)>,
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    /// Describes a reader's error, guessing a hint from the source it read
+    pub fn new(error: GraphGatewayError, format: InputFormat, text: &str) -> Self {
+        let (source, message, location): (String, String, Option<(usize, usize)>) = match error {
+            GraphGatewayError::Parse {
+                source,
+                message,
+                line,
+                column,
+            } => (source, summary(&message), Some((line, column))),
+            GraphGatewayError::Semantic { source, message } => (source, message, None),
+        };
+
+        Self {
+            source: Some(source),
+            message,
+            location,
+            hint: hint(format, text, location),
+        }
+    }
+
+    /// Draws the diagnostic over an excerpt of the source, a caret pointing
+    /// at where it went wrong
+    pub fn render(&self, name: &str, text: &str) -> String {
+        let mut lines: Vec<String> = vec![match &self.source {
+            Some(source) => format!("error[{}]: {}", source, self.message),
+            None => format!("error: {}", self.message),
+        }];
+
+        let Some((line, column)) = self.location else {
+            lines.push(format!(" --> {}", name));
+            lines.extend(
+                self.hint
+                    .iter()
+                    .map(|hint: &String| format!("  = hint: {}", hint)),
+            );
+            return lines.join("\n") + "\n";
+        };
+
+        // The offending line comes with the one before it, for context
+        let source: Vec<&str> = text.lines().collect();
+        let shown: Vec<usize> = (line.saturating_sub(1).max(1)..=line)
+            .filter(|number: &usize| *number <= source.len() + 1)
+            .collect();
+        let width: usize = line.to_string().len();
+        let gutter: String = " ".repeat(width);
+
+        lines.push(format!("{}--> {}:{}:{}", gutter, name, line, column));
+        lines.push(format!("{} |", gutter));
+        shown.iter().for_each(|number: &usize| {
+            let content: &str = source.get(number - 1).copied().unwrap_or_default();
+            lines.push(
+                format!("{:>width$} | {}", number, expand_tabs(content))
+                    .trim_end()
+                    .to_string(),
+            );
+        });
+
+        let content: &str = source
+            .get(line.saturating_sub(1))
+            .copied()
+            .unwrap_or_default();
+        let (offset, length): (usize, usize) = span(content, column);
+        lines.push(format!(
+            "{} | {}{} {}",
+            gutter,
+            " ".repeat(offset),
+            "^".repeat(length),
+            self.message
+        ));
+        lines.push(format!("{} |", gutter));
+        lines.extend(
+            self.hint
+                .iter()
+                .map(|hint: &String| format!("{} = hint: {}", gutter, hint)),
+        );
+
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Problems that are not about a source's content, such as an unknown notation
+impl From<String> for Diagnostic {
+    fn from(message: String) -> Self {
+        Self {
+            source: None,
+            message,
+            location: None,
+            hint: None,
+        }
+    }
+}
+
+/// The single line form, as in `[dbml:3:7] Parse Error: expected column`
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.source, self.location) {
+            (Some(source), Some((line, column))) => write!(
+                f,
+                "[{}:{}:{}] Parse Error: {}",
+                source, line, column, self.message
+            ),
+            (Some(source), None) => write!(f, "[{}] Semantic Error: {}", source, self.message),
+            (None, _) => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Pest draws its own excerpt and ends with an "= expected ..." summary,
+/// which is all that is kept; other messages are kept whole
+fn summary(message: &str) -> String {
+    message
+        .lines()
+        .rev()
+        .find_map(|line: &str| line.trim_start().strip_prefix("= "))
+        .unwrap_or(message)
+        .trim()
+        .to_string()
+}
+
+/// Where the caret goes under a line once tabs are expanded, and how many
+/// characters it spans: the whole word at the column, or a single one
+fn span(content: &str, column: usize) -> (usize, usize) {
+    let chars: Vec<char> = content.chars().collect();
+    let start: usize = column.saturating_sub(1).min(chars.len());
+    let offset: usize = expand_tabs(&chars[..start].iter().collect::<String>())
+        .chars()
+        .count();
+
+    let is_word = |c: &&char| c.is_alphanumeric() || **c == '_';
+    let length: usize = match chars.get(start) {
+        Some(c) if is_word(&c) => chars[start..].iter().take_while(is_word).count(),
+        _ => 1,
+    };
+    (offset, length)
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', "    ")
+}
+
+/// Guesses what is most likely missing from a source that failed to read
+fn hint(format: InputFormat, text: &str, location: Option<(usize, usize)>) -> Option<String> {
+    if format == InputFormat::Plantuml {
+        let tag = |prefix: &str| -> Option<String> {
+            text.lines()
+                .map(str::trim)
+                .find(|line: &&str| line.starts_with(prefix))
+                .map(|line: &str| {
+                    line[prefix.len()..]
+                        .split(|c: char| !c.is_alphanumeric())
+                        .next()
+                        .unwrap_or_default()
+                        .to_string()
+                })
+        };
+        match (tag("@start"), tag("@end")) {
+            (Some(kind), None) => return Some(format!("did you forget `@end{}`?", kind)),
+            (None, _) => {
+                return Some("diagrams start with `@startuml` and end with `@enduml`".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(line) = unclosed_brace(text) {
+        return Some(format!("the `{{` on line {} is never closed", line));
+    }
+
+    let (line, _): (usize, usize) = location?;
+    let content: &str = text.lines().nth(line.checked_sub(1)?)?;
+    (content.matches('"').count() % 2 == 1)
+        .then(|| "a string on this line is never closed".to_string())
+}
+
+/// The line of the innermost `{` left open at the end of the source, braces
+/// within strings and line comments set aside
+fn unclosed_brace(text: &str) -> Option<usize> {
+    let mut open: Vec<usize> = Vec::new();
+
+    text.lines()
+        .enumerate()
+        .for_each(|(index, line): (usize, &str)| {
+            let mut quoted: bool = false;
+            let mut previous: char = ' ';
+            for c in line.chars() {
+                match c {
+                    '"' if previous != '\\' => quoted = !quoted,
+                    '/' if previous == '/' && !quoted => break,
+                    '{' if !quoted => open.push(index + 1),
+                    '}' if !quoted => {
+                        open.pop();
+                    }
+                    _ => {}
+                }
+                previous = c;
+            }
+        });
+
+    open.pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::adapters::graph_gateway::GraphGatewayError;
+    use pretty_assertions::assert_eq;
+
+    use crate::{diagnostics::Diagnostic, formats::InputFormat};
+
+    #[test]
+    fn test_renders_an_excerpt_with_a_caret() {
+        let text: &str = "message Order {\n  string id = ;\n}\n";
+        let error: GraphGatewayError = smol::block_on(
+            InputFormat::Protobuf
+                .gateway()
+                .read_graph_from_raw_input(text),
+        )
+        .expect_err("Expected a parse error");
+
+        let diagnostic: Diagnostic = Diagnostic::new(error, InputFormat::Protobuf, text);
+
+        assert_eq!(
+            diagnostic.render("shop.proto", text),
+            [
+                "error[protobuf]: expected int_lit",
+                " --> shop.proto:2:15",
+                "  |",
+                "1 | message Order {",
+                "2 |   string id = ;",
+                "  |               ^ expected int_lit",
+                "  |",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "[protobuf:2:15] Parse Error: expected int_lit"
+        );
+    }
+
+    #[test]
+    fn test_hints_at_a_missing_end_tag() {
+        let text: &str = "@startuml\nclass User {\n  name\n}\n";
+        let diagnostic: Diagnostic = Diagnostic::new(
+            GraphGatewayError::Parse {
+                source: "plantuml".to_string(),
+                message: "expected end".to_string(),
+                line: 5,
+                column: 1,
+            },
+            InputFormat::Plantuml,
+            text,
+        );
+
+        assert_eq!(
+            diagnostic.hint.as_deref(),
+            Some("did you forget `@enduml`?")
+        );
+        assert!(
+            diagnostic
+                .render("model.puml", text)
+                .ends_with("  |\n  = hint: did you forget `@enduml`?\n")
+        );
+    }
+
+    #[test]
+    fn test_hints_at_unclosed_braces_and_strings() {
+        let unclosed = |text: &str| -> Option<String> {
+            Diagnostic::new(
+                GraphGatewayError::Parse {
+                    source: "dbml".to_string(),
+                    message: "expected }".to_string(),
+                    line: 2,
+                    column: 3,
+                },
+                InputFormat::Dbml,
+                text,
+            )
+            .hint
+        };
+
+        assert_eq!(
+            unclosed("Table users {\n  id int [note: \"{\"]\n").as_deref(),
+            Some("the `{` on line 1 is never closed")
+        );
+        assert_eq!(
+            unclosed("Table users {\n  id int [note: \"open]\n}").as_deref(),
+            Some("a string on this line is never closed")
+        );
+        assert_eq!(unclosed("Table users {\n  id int\n}"), None);
+    }
+
+    #[test]
+    fn test_semantic_errors_name_their_source() {
+        let diagnostic: Diagnostic = Diagnostic::new(
+            GraphGatewayError::Semantic {
+                source: "cargo".to_string(),
+                message: "Expected `cargo metadata` output listing workspace members".to_string(),
+            },
+            InputFormat::Cargo,
+            "[]",
+        );
+
+        assert_eq!(
+            diagnostic.render("metadata.json", "[]"),
+            "error[cargo]: Expected `cargo metadata` output listing workspace members\n --> metadata.json\n"
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use std::{fmt, path::Path, str::FromStr, sync::Arc};
+
+use lib_blockdiag::infrastructure::adapters::blockdiag_graph_gateway::BlockdiagGraphGateway;
+use lib_cargo::infrastructure::adapters::cargo_graph_gateway::CargoGraphGateway;
+use lib_core::adapters::{
+    graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter},
+    graph_gateway::GraphGateway,
+};
+use lib_dbml::infrastructure::adapters::dbml_graph_gateway::DbmlGraphGateway;
+use lib_excalidraw::infrastructure::adapters::excalidraw_graph_gateway::ExcalidrawGraphGateway;
+use lib_graphml::infrastructure::adapters::graphml_graph_gateway::GraphMlGraphGateway;
+use lib_json::infrastructure::adapters::json_graph_emitter::JsonGraphEmitter;
+use lib_mermaid::infrastructure::adapters::mermaid_graph_emitter::MermaidGraphEmitter;
+use lib_openapi::infrastructure::adapters::{
+    json_schema_graph_gateway::JsonSchemaGraphGateway, openapi_graph_gateway::OpenApiGraphGateway,
+};
+use lib_plantuml::infrastructure::{
+    adapters::{
+        plant_uml_graph_emitter::PlantUmlGraphEmitter,
+        plant_uml_graph_gateway::PlantUmlGraphGateway,
+    },
+    profile::Profile,
+};
+use lib_prisma::infrastructure::adapters::prisma_graph_gateway::PrismaGraphGateway;
+use lib_protobuf::infrastructure::adapters::protobuf_graph_gateway::ProtobufGraphGateway;
+use lib_rust::infrastructure::adapters::rust_graph_gateway::RustGraphGateway;
+use lib_sql::infrastructure::adapters::sql_graph_gateway::SqlGraphGateway;
+use lib_svg::infrastructure::adapters::svg_graph_emitter::SvgGraphEmitter;
+use lib_text::infrastructure::adapters::{
+    narration_graph_emitter::NarrationGraphEmitter, text_graph_emitter::TextGraphEmitter,
+};
+use lib_xmi::infrastructure::adapters::xmi_graph_gateway::XmiGraphGateway;
+use lib_yuml::infrastructure::adapters::yuml_graph_gateway::YumlGraphGateway;
+
+use crate::registry::{self, PluginId};
+
+/// Notations diagrams can be read from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    Plantuml,
+    Yuml,
+    Dbml,
+    Blockdiag,
+    Graphml,
+    Xmi,
+    Excalidraw,
+    Rust,
+    Sql,
+    Openapi,
+    Protobuf,
+    Cargo,
+    Jsonschema,
+    Prisma,
+    /// Read by a format registered at runtime
+    Plugin(PluginId),
+}
+
+impl InputFormat {
+    pub const ALL: [InputFormat; 14] = [
+        Self::Plantuml,
+        Self::Yuml,
+        Self::Dbml,
+        Self::Blockdiag,
+        Self::Graphml,
+        Self::Xmi,
+        Self::Excalidraw,
+        Self::Rust,
+        Self::Sql,
+        Self::Openapi,
+        Self::Protobuf,
+        Self::Cargo,
+        Self::Jsonschema,
+        Self::Prisma,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Plantuml => "plantuml",
+            Self::Yuml => "yuml",
+            Self::Dbml => "dbml",
+            Self::Blockdiag => "blockdiag",
+            Self::Graphml => "graphml",
+            Self::Xmi => "xmi",
+            Self::Excalidraw => "excalidraw",
+            Self::Rust => "rust",
+            Self::Sql => "sql",
+            Self::Openapi => "openapi",
+            Self::Protobuf => "protobuf",
+            Self::Cargo => "cargo",
+            Self::Jsonschema => "jsonschema",
+            Self::Prisma => "prisma",
+            Self::Plugin(id) => registry::reader_name(*id),
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        // Schemas are told apart from other JSON by their conventional suffix
+        let name: String = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".schema.json") {
+            return Some(Self::Jsonschema);
+        }
+
+        let extension: String = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "puml" | "plantuml" | "pu" | "iuml" | "wsd" => Some(Self::Plantuml),
+            "yuml" | "nomnoml" => Some(Self::Yuml),
+            "dbml" => Some(Self::Dbml),
+            "diag" | "blockdiag" => Some(Self::Blockdiag),
+            "graphml" => Some(Self::Graphml),
+            "xmi" | "uml" => Some(Self::Xmi),
+            "excalidraw" => Some(Self::Excalidraw),
+            "rs" => Some(Self::Rust),
+            "sql" | "ddl" => Some(Self::Sql),
+            "yaml" | "yml" => Some(Self::Openapi),
+            "proto" => Some(Self::Protobuf),
+            "prisma" => Some(Self::Prisma),
+            _ => registry::reader_for_extension(&extension).map(Self::Plugin),
+        }
+    }
+
+    pub fn gateway(&self) -> Arc<dyn GraphGateway + Send + Sync> {
+        self.gateway_with_profile(Profile::default())
+    }
+
+    /// The gateway of the notation, holding PlantUML sources to a dialect
+    pub fn gateway_with_profile(&self, profile: Profile) -> Arc<dyn GraphGateway + Send + Sync> {
+        match self {
+            Self::Plantuml => Arc::new(PlantUmlGraphGateway::with_arena().with_profile(profile)),
+            Self::Yuml => Arc::new(YumlGraphGateway::new()),
+            Self::Dbml => Arc::new(DbmlGraphGateway::new()),
+            Self::Blockdiag => Arc::new(BlockdiagGraphGateway::new()),
+            Self::Graphml => Arc::new(GraphMlGraphGateway::new()),
+            Self::Xmi => Arc::new(XmiGraphGateway::new()),
+            Self::Excalidraw => Arc::new(ExcalidrawGraphGateway::new()),
+            Self::Rust => Arc::new(RustGraphGateway::new()),
+            Self::Sql => Arc::new(SqlGraphGateway::new()),
+            Self::Openapi => Arc::new(OpenApiGraphGateway::new()),
+            Self::Protobuf => Arc::new(ProtobufGraphGateway::new()),
+            Self::Cargo => Arc::new(CargoGraphGateway::new()),
+            Self::Jsonschema => Arc::new(JsonSchemaGraphGateway::new()),
+            Self::Prisma => Arc::new(PrismaGraphGateway::new()),
+            Self::Plugin(id) => registry::gateway(*id),
+        }
+    }
+}
+
+/// Notations diagrams can be written to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Mermaid,
+    Plantuml,
+    Svg,
+    Json,
+    Text,
+    /// A Markdown report telling the relations in words
+    Narration,
+    /// Written by a format registered at runtime
+    Plugin(PluginId),
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 6] = [
+        Self::Mermaid,
+        Self::Plantuml,
+        Self::Svg,
+        Self::Json,
+        Self::Text,
+        Self::Narration,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mermaid => "mermaid",
+            Self::Plantuml => "plantuml",
+            Self::Svg => "svg",
+            Self::Json => "json",
+            Self::Text => "text",
+            Self::Narration => "narration",
+            Self::Plugin(id) => registry::writer_name(*id),
+        }
+    }
+
+    pub fn emitter(&self) -> Arc<dyn GraphEmitterAdapter + Send + Sync> {
+        self.emitter_with_options(EmitOptions::default())
+    }
+
+    /// Plugins only see the elements the options keep; how they lay out
+    /// their text is up to them
+    pub fn emitter_with_options(
+        &self,
+        options: EmitOptions,
+    ) -> Arc<dyn GraphEmitterAdapter + Send + Sync> {
+        match self {
+            Self::Mermaid => Arc::new(MermaidGraphEmitter::new().with_options(options)),
+            Self::Plantuml => Arc::new(PlantUmlGraphEmitter::new().with_options(options)),
+            Self::Svg => Arc::new(SvgGraphEmitter::new().with_options(options)),
+            Self::Json => Arc::new(JsonGraphEmitter::new().with_options(options)),
+            Self::Text => Arc::new(TextGraphEmitter::new().with_options(options)),
+            Self::Narration => Arc::new(NarrationGraphEmitter::new().with_options(options)),
+            Self::Plugin(id) => registry::emitter(*id),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mermaid => "mmd",
+            Self::Plantuml => "puml",
+            Self::Svg => "svg",
+            Self::Json => "json",
+            Self::Text => "txt",
+            Self::Narration => "md",
+            Self::Plugin(id) => registry::writer_extension(*id),
+        }
+    }
+
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Json => "application/json",
+            Self::Narration => "text/markdown; charset=utf-8",
+            Self::Mermaid | Self::Plantuml | Self::Text | Self::Plugin(_) => {
+                "text/plain; charset=utf-8"
+            }
+        }
+    }
+
+    /// Drawn formats need node geometry, source formats do not
+    pub fn needs_layout(&self) -> bool {
+        matches!(self, Self::Svg)
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let formats: Vec<InputFormat> = Self::ALL
+            .into_iter()
+            .chain(registry::readers().into_iter().map(Self::Plugin))
+            .collect();
+        formats
+            .iter()
+            .find(|format: &&InputFormat| format.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| unknown("input", s, formats.iter().map(InputFormat::name)))
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let formats: Vec<OutputFormat> = Self::ALL
+            .into_iter()
+            .chain(registry::writers().into_iter().map(Self::Plugin))
+            .collect();
+        formats
+            .iter()
+            .find(|format: &&OutputFormat| format.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| unknown("output", s, formats.iter().map(OutputFormat::name)))
+    }
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn unknown(
+    direction: &str,
+    name: &str,
+    expected: impl IntoIterator<Item = &'static str>,
+) -> String {
+    format!(
+        "Unknown {} format '{}', expected one of: {}",
+        direction,
+        name,
+        expected.into_iter().collect::<Vec<&str>>().join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::formats::{InputFormat, OutputFormat};
+
+    #[test]
+    fn test_input_format_is_guessed_from_extension() {
+        assert_eq!(
+            InputFormat::from_path(Path::new("docs/model.PUML")),
+            Some(InputFormat::Plantuml)
+        );
+        assert_eq!(
+            InputFormat::from_path(Path::new("schema.dbml")),
+            Some(InputFormat::Dbml)
+        );
+        assert_eq!(
+            InputFormat::from_path(Path::new("invoice.schema.json")),
+            Some(InputFormat::Jsonschema)
+        );
+        assert_eq!(InputFormat::from_path(Path::new("notes.txt")), None);
+        assert_eq!(InputFormat::from_path(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn test_formats_are_parsed_by_name() {
+        assert_eq!("PlantUML".parse(), Ok(InputFormat::Plantuml));
+        assert_eq!("svg".parse(), Ok(OutputFormat::Svg));
+        assert_eq!(
+            "png".parse::<OutputFormat>(),
+            Err(
+                "Unknown output format 'png', expected one of: mermaid, plantuml, svg, json, text, narration"
+                    .to_string()
+            )
+        );
+    }
+}
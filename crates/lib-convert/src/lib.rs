@@ -0,0 +1,5 @@
+pub mod diagnostics;
+pub mod formats;
+pub mod lint;
+pub mod pipeline;
+pub mod registry;
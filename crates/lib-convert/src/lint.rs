@@ -0,0 +1,490 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use lib_core::entities::{
+    edge::Edge, graph::Graph, group::Group, id::Id, node::Node, value::Value,
+};
+use lib_plantuml::infrastructure::syntax::{self, Reference, SourceIndex, Symbol, SymbolKind};
+
+use crate::formats::InputFormat;
+
+/// Marks an element as exempt from rules, in a stereotype such as
+/// `<<lint:allow isolated-node>>` or a PlantUML comment
+const ALLOW: &str = "lint:allow";
+
+/// How much a rule's findings matter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Warning,
+    Error,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" | "allow" => Ok(Self::Off),
+            "warning" | "warn" => Ok(Self::Warning),
+            "error" | "deny" => Ok(Self::Error),
+            _ => Err(format!(
+                "Unknown lint level '{}', expected one of: off, warning, error",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Off => "off",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// A house rule checked against every graph
+pub struct Rule {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Level the rule runs at unless configured otherwise
+    pub level: Level,
+    check: fn(&Graph) -> Vec<(Element, String)>,
+}
+
+pub const RULES: [Rule; 4] = [
+    Rule {
+        name: "edge-label",
+        description: "Every edge has a label",
+        level: Level::Off,
+        check: unlabelled_edges,
+    },
+    Rule {
+        name: "isolated-node",
+        description: "Every node takes part in a relation",
+        level: Level::Warning,
+        check: isolated_nodes,
+    },
+    Rule {
+        name: "duplicate-label",
+        description: "No two nodes share a label",
+        level: Level::Warning,
+        check: duplicate_labels,
+    },
+    Rule {
+        name: "empty-group",
+        description: "Every group holds something",
+        level: Level::Warning,
+        check: empty_groups,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Element {
+    Node(Id),
+    Edge(Id),
+    Group(Id),
+}
+
+/// A rule an element breaks
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub level: Level,
+    pub element: Element,
+    pub message: String,
+}
+
+/// As in `warning[isolated-node]: `Legend` takes part in no relation`
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.level, self.rule, self.message)
+    }
+}
+
+/// A level for one rule, written `rule=level` as in `edge-label=error`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleSetting {
+    pub rule: String,
+    pub level: Level,
+}
+
+impl FromStr for RuleSetting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rule, level): (&str, &str) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected RULE=LEVEL, got '{}'", s))?;
+        let rule: &str = rule.trim();
+
+        if !RULES.iter().any(|known: &Rule| known.name == rule) {
+            return Err(format!(
+                "Unknown lint rule '{}', expected one of: {}",
+                rule,
+                RULES.map(|known: Rule| known.name).join(", ")
+            ));
+        }
+        Ok(Self {
+            rule: rule.to_string(),
+            level: level.trim().parse()?,
+        })
+    }
+}
+
+/// Levels chosen for rules, the defaults standing for the others
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintConfig {
+    levels: HashMap<String, Level>,
+}
+
+impl LintConfig {
+    /// Later settings of a rule win over earlier ones
+    pub fn new(settings: &[RuleSetting]) -> Self {
+        Self {
+            levels: settings
+                .iter()
+                .map(|setting: &RuleSetting| (setting.rule.clone(), setting.level))
+                .collect(),
+        }
+    }
+
+    pub fn level(&self, rule: &Rule) -> Level {
+        self.levels.get(rule.name).copied().unwrap_or(rule.level)
+    }
+}
+
+/// Runs the enabled rules over a graph read from `text`, leaving out the
+/// elements that allow a rule. Findings come in rule order, then by message.
+pub fn lint(graph: &Graph, format: InputFormat, text: &str, config: &LintConfig) -> Vec<Finding> {
+    let allowed: HashMap<Element, HashSet<String>> = allowances(graph, format, text);
+
+    RULES
+        .iter()
+        .filter(|rule: &&Rule| config.level(rule) != Level::Off)
+        .flat_map(|rule: &Rule| {
+            let mut findings: Vec<Finding> = (rule.check)(graph)
+                .into_iter()
+                .filter(|(element, _): &(Element, String)| {
+                    !allowed
+                        .get(element)
+                        .is_some_and(|rules: &HashSet<String>| rules.contains(rule.name))
+                })
+                .map(|(element, message): (Element, String)| Finding {
+                    rule: rule.name,
+                    level: config.level(rule),
+                    element,
+                    message,
+                })
+                .collect();
+            findings.sort_by(|a: &Finding, b: &Finding| a.message.cmp(&b.message));
+            findings
+        })
+        .collect()
+}
+
+fn unlabelled_edges(graph: &Graph) -> Vec<(Element, String)> {
+    graph
+        .edges
+        .values()
+        .filter(|edge: &&Edge| {
+            edge.label
+                .as_deref()
+                .is_none_or(|label: &str| label.trim().is_empty())
+        })
+        .map(|edge: &Edge| {
+            (
+                Element::Edge(edge.id.clone()),
+                format!(
+                    "The edge from {} to {} has no label",
+                    name(graph, &edge.from),
+                    name(graph, &edge.to)
+                ),
+            )
+        })
+        .collect()
+}
+
+fn isolated_nodes(graph: &Graph) -> Vec<(Element, String)> {
+    let related: HashSet<&Id> = graph
+        .edges
+        .values()
+        .flat_map(|edge: &Edge| [&edge.from, &edge.to])
+        .collect();
+
+    graph
+        .nodes
+        .keys()
+        .filter(|id: &&Id| !related.contains(id))
+        .map(|id: &Id| {
+            (
+                Element::Node(id.clone()),
+                format!("{} takes part in no relation", name(graph, id)),
+            )
+        })
+        .collect()
+}
+
+/// Every node after the first, by id, of those sharing a label
+fn duplicate_labels(graph: &Graph) -> Vec<(Element, String)> {
+    let mut nodes: Vec<&Node> = graph.nodes.values().collect();
+    nodes.sort_by(|a: &&Node, b: &&Node| a.id.cmp(&b.id));
+
+    let mut first: HashMap<&str, &Id> = HashMap::new();
+    nodes
+        .into_iter()
+        .filter_map(|node: &Node| {
+            let label: &str = node.label.as_deref()?;
+            match first.get(label) {
+                Some(original) => Some((
+                    Element::Node(node.id.clone()),
+                    format!("`{}` shares its label with `{}`", node.id, original),
+                )),
+                None => {
+                    first.insert(label, &node.id);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn empty_groups(graph: &Graph) -> Vec<(Element, String)> {
+    let parents: HashSet<&Id> = graph
+        .nodes
+        .values()
+        .filter_map(|node: &Node| node.parent.as_ref())
+        .chain(
+            graph
+                .groups
+                .values()
+                .filter_map(|group: &Group| group.parent.as_ref()),
+        )
+        .collect();
+
+    graph
+        .groups
+        .values()
+        .filter(|group: &&Group| group.children.is_empty() && !parents.contains(&group.id))
+        .map(|group: &Group| {
+            (
+                Element::Group(group.id.clone()),
+                format!(
+                    "Group `{}` is empty",
+                    group.label.as_deref().unwrap_or(&group.id)
+                ),
+            )
+        })
+        .collect()
+}
+
+fn name(graph: &Graph, id: &Id) -> String {
+    format!(
+        "`{}`",
+        graph
+            .nodes
+            .get(id)
+            .and_then(|node: &Node| node.label.as_deref())
+            .unwrap_or(id)
+    )
+}
+
+/// The rules each element allows, from node stereotypes and, in PlantUML
+/// sources, from comments on or just above a statement
+fn allowances(graph: &Graph, format: InputFormat, text: &str) -> HashMap<Element, HashSet<String>> {
+    let mut allowed: HashMap<Element, HashSet<String>> = HashMap::new();
+
+    graph.nodes.values().for_each(|node: &Node| {
+        if let Some(Value::String(stereotype)) = node.data.get("stereotype") {
+            let rules: HashSet<String> = allowed_rules(stereotype);
+            if !rules.is_empty() {
+                allowed.insert(Element::Node(node.id.clone()), rules);
+            }
+        }
+    });
+
+    if format != InputFormat::Plantuml {
+        return allowed;
+    }
+    let Ok(index) = syntax::index_source(text) else {
+        return allowed;
+    };
+
+    let annotations: HashMap<usize, HashSet<String>> = annotated_lines(text);
+    let line = |offset: usize| -> usize { text[..offset].matches('\n').count() + 1 };
+    let mut add = |element: Element, offset: usize| {
+        if let Some(rules) = annotations.get(&line(offset)) {
+            allowed
+                .entry(element)
+                .or_default()
+                .extend(rules.iter().cloned());
+        }
+    };
+
+    fn elements<'a>(symbols: &'a [Symbol], found: &mut Vec<&'a Symbol>) {
+        symbols.iter().for_each(|symbol: &Symbol| {
+            if let SymbolKind::Element(_) = symbol.kind {
+                found.push(symbol);
+            }
+            elements(&symbol.children, found);
+        });
+    }
+    let mut declared: Vec<&Symbol> = Vec::new();
+    elements(&index.symbols, &mut declared);
+    declared.iter().for_each(|symbol: &&Symbol| {
+        add(
+            Element::Node(Id::from(symbol.id.as_str())),
+            symbol.span.start,
+        )
+    });
+
+    // Relation endpoints are indexed in pairs, in source order
+    relations(&index).for_each(|(from, to): (&Reference, &Reference)| {
+        graph
+            .edges
+            .values()
+            .filter(|edge: &&Edge| edge.from == from.id && edge.to == to.id)
+            .for_each(|edge: &Edge| add(Element::Edge(edge.id.clone()), from.span.start));
+    });
+
+    allowed
+}
+
+fn relations(index: &SourceIndex) -> impl Iterator<Item = (&Reference, &Reference)> {
+    index
+        .references
+        .chunks_exact(2)
+        .map(|pair: &[Reference]| (&pair[0], &pair[1]))
+}
+
+/// Lines a `' lint:allow rule` comment applies to: its own when it follows a
+/// statement, or else the next line holding one
+fn annotated_lines(text: &str) -> HashMap<usize, HashSet<String>> {
+    let mut annotated: HashMap<usize, HashSet<String>> = HashMap::new();
+    let mut pending: HashSet<String> = HashSet::new();
+
+    text.lines()
+        .enumerate()
+        .for_each(|(index, line): (usize, &str)| {
+            let (code, comment): (&str, &str) = line.split_once('\'').unwrap_or((line, ""));
+            let rules: HashSet<String> = allowed_rules(comment);
+
+            if code.trim().is_empty() {
+                pending.extend(rules);
+            } else {
+                let entry: &mut HashSet<String> = annotated.entry(index + 1).or_default();
+                entry.extend(pending.drain());
+                entry.extend(rules);
+            }
+        });
+
+    annotated.retain(|_, rules: &mut HashSet<String>| !rules.is_empty());
+    annotated
+}
+
+/// Rule names following `lint:allow` in a stereotype or comment
+fn allowed_rules(text: &str) -> HashSet<String> {
+    text.split_once(ALLOW)
+        .map(|(_, rules): (&str, &str)| {
+            rules
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|rule: &&str| !rule.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::entities::{graph::Graph, value::Value};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        formats::InputFormat,
+        lint::{Element, Finding, Level, LintConfig, RuleSetting, lint},
+        pipeline::load,
+    };
+
+    fn findings(text: &str, format: InputFormat, settings: &[&str]) -> Vec<String> {
+        let graph: Graph = smol::block_on(load(text, format)).unwrap();
+        let settings: Vec<RuleSetting> = settings
+            .iter()
+            .map(|setting: &&str| setting.parse().unwrap())
+            .collect();
+
+        lint(&graph, format, text, &LintConfig::new(&settings))
+            .iter()
+            .map(Finding::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_default_rules_and_configured_levels() {
+        let text: &str = "@startuml\nclass User\nclass Order\nclass Legend\npackage \"Empty\" {\n}\nUser --> Order\n@enduml\n";
+
+        assert_eq!(
+            findings(text, InputFormat::Plantuml, &[]),
+            vec![
+                "warning[isolated-node]: `Legend` takes part in no relation",
+                "warning[empty-group]: Group `Empty` is empty",
+            ]
+        );
+        assert_eq!(
+            findings(
+                text,
+                InputFormat::Plantuml,
+                &["edge-label=error", "isolated-node=off", "empty-group=off"]
+            ),
+            vec!["error[edge-label]: The edge from `User` to `Order` has no label"]
+        );
+    }
+
+    #[test]
+    fn test_comments_allow_rules_for_the_statement_they_annotate() {
+        let text: &str = "@startuml\n' lint:allow isolated-node\nclass Legend\nclass Note ' lint:allow isolated-node\nclass Stray\nA --> B ' lint:allow edge-label\nA --> C\n@enduml\n";
+
+        assert_eq!(
+            findings(text, InputFormat::Plantuml, &["edge-label=warning"]),
+            vec![
+                "warning[edge-label]: The edge from `A` to `C` has no label",
+                "warning[isolated-node]: `Stray` takes part in no relation",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stereotypes_allow_rules_in_any_notation() {
+        let mut graph: Graph =
+            smol::block_on(load("[Legend]\n[Order]->[Line]\n", InputFormat::Yuml)).unwrap();
+        assert_eq!(
+            lint(&graph, InputFormat::Yuml, "", &LintConfig::default())
+                .into_iter()
+                .map(|finding: Finding| (finding.element, finding.level))
+                .collect::<Vec<(Element, Level)>>(),
+            vec![(Element::Node("Legend".into()), Level::Warning)]
+        );
+
+        graph.nodes.get_mut("Legend").unwrap().data.insert(
+            "stereotype".into(),
+            Value::String("lint:allow isolated-node".to_string()),
+        );
+        assert_eq!(
+            lint(&graph, InputFormat::Yuml, "", &LintConfig::default()),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_rules_and_levels() {
+        assert!("edge-label=loud".parse::<RuleSetting>().is_err());
+        assert!("no-such-rule=error".parse::<RuleSetting>().is_err());
+        assert!("edge-label".parse::<RuleSetting>().is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use std::{borrow::Cow, sync::Arc};
+
+use lib_core::{
+    adapters::{graph_emitter_adapter::EmitOptions, graph_gateway::GraphGatewayError},
+    entities::graph::Graph,
+    use_cases::{
+        emit_graph::{EmitGraph, EmitGraphUseCase},
+        layout_graph::{LayoutGraph, LayoutGraphUseCase},
+        load_graph::{LoadGraph, LoadGraphUseCase},
+        load_graphs::{LoadGraphs, LoadGraphsUseCase},
+    },
+};
+use lib_layout::infrastructure::adapters::{
+    automatic_layout_engine::AutomaticLayoutEngine, label_placement_engine::LabelPlacementEngine,
+};
+use lib_plantuml::infrastructure::profile::Profile;
+use lib_svg::infrastructure::image_map::ImageMap;
+
+use crate::formats::{InputFormat, OutputFormat};
+
+pub async fn load(input: &str, format: InputFormat) -> Result<Graph, String> {
+    LoadGraph::new(format.gateway()).execute(input).await
+}
+
+/// Loads many sources of one notation across threads, in their given order.
+/// PlantUML sources are held to the given profile.
+pub fn load_all(
+    inputs: &[&str],
+    format: InputFormat,
+    profile: Profile,
+) -> Vec<Result<Graph, GraphGatewayError>> {
+    LoadGraphs::new(format.gateway_with_profile(profile)).execute(inputs)
+}
+
+/// Places nodes, routes edges and positions their labels
+pub async fn layout(graph: Graph) -> Result<Graph, String> {
+    let graph: Graph = LayoutGraph::new(Arc::new(AutomaticLayoutEngine::new()))
+        .execute(graph)
+        .await?;
+    LayoutGraph::new(Arc::new(LabelPlacementEngine::new()))
+        .execute(graph)
+        .await
+}
+
+/// Writes a graph in the target notation, laying it out first when the
+/// target is drawn
+pub async fn emit(graph: Graph, to: OutputFormat) -> Result<String, String> {
+    emit_with_options(graph, to, EmitOptions::default()).await
+}
+
+/// `emit` with the output settings shared by every format
+pub async fn emit_with_options(
+    graph: Graph,
+    to: OutputFormat,
+    options: EmitOptions,
+) -> Result<String, String> {
+    // Dropping elements before layout keeps them from taking up room; hidden
+    // ones stay until the emitter, as they still shape the layout
+    let prepared: Option<Graph> = match options.prune(&graph) {
+        Cow::Owned(prepared) => Some(prepared),
+        Cow::Borrowed(_) => None,
+    };
+    let mut graph: Graph = prepared.unwrap_or(graph);
+    if to.needs_layout() {
+        graph = layout(graph).await?;
+    }
+    EmitGraph::new(to.emitter_with_options(options))
+        .execute(&graph)
+        .await
+}
+
+/// Draws a graph as SVG along with a map of where each element ended up
+pub async fn render_with_map(graph: Graph) -> Result<(String, ImageMap), String> {
+    let graph: Graph = layout(graph).await?;
+    let svg: String = EmitGraph::new(OutputFormat::Svg.emitter())
+        .execute(&graph)
+        .await?;
+    Ok((svg, ImageMap::new(&graph)))
+}
+
+pub async fn convert(input: &str, from: InputFormat, to: OutputFormat) -> Result<String, String> {
+    emit(load(input, from).await?, to).await
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{adapters::graph_gateway::GraphGatewayError, entities::graph::Graph};
+    use lib_plantuml::infrastructure::profile::Profile;
+    use lib_svg::infrastructure::image_map::{Area, ImageMap};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        formats::{InputFormat, OutputFormat},
+        pipeline::{convert, load, load_all, render_with_map},
+    };
+
+    const PLANTUML: &str = "@startuml\nclass User\nUser --> Profile\n@enduml\n";
+
+    #[test]
+    fn test_converts_between_notations() {
+        smol::block_on(async {
+            let output: String = convert(PLANTUML, InputFormat::Plantuml, OutputFormat::Mermaid)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                "flowchart TB\n    Profile[\"Profile\"]\n    User[\"User\"]\n    User --> Profile\n"
+            );
+        });
+    }
+
+    #[test]
+    fn test_renders_laid_out_svg() {
+        smol::block_on(async {
+            let output: String = convert(PLANTUML, InputFormat::Plantuml, OutputFormat::Svg)
+                .await
+                .unwrap();
+
+            assert!(output.starts_with("<svg"));
+            assert!(output.contains(">User</text>"));
+            assert!(output.contains("<polyline"));
+        });
+    }
+
+    #[test]
+    fn test_maps_rendered_elements() {
+        smol::block_on(async {
+            let graph: Graph = load(PLANTUML, InputFormat::Plantuml).await.unwrap();
+            let (svg, map): (String, ImageMap) = render_with_map(graph).await.unwrap();
+
+            assert!(svg.starts_with(&format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\"",
+                map.width
+            )));
+            assert_eq!(
+                map.areas
+                    .iter()
+                    .map(|area: &Area| area.id.as_str())
+                    .collect::<Vec<&str>>(),
+                vec!["Profile", "User"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_reports_parse_errors() {
+        smol::block_on(async {
+            let error: String = load("@startuml\nclass\n", InputFormat::Plantuml)
+                .await
+                .unwrap_err();
+
+            assert!(error.starts_with("[plantuml:"), "{}", error);
+        });
+    }
+
+    #[test]
+    fn test_loads_many_sources() {
+        let graphs: Vec<Result<Graph, GraphGatewayError>> = load_all(
+            &[PLANTUML, "@startuml\nclass\n"],
+            InputFormat::Plantuml,
+            Profile::Permissive,
+        );
+
+        assert_eq!(graphs.len(), 2);
+        assert_eq!(graphs[0].as_ref().unwrap().nodes.len(), 2);
+        assert!(graphs[1].is_err());
+
+        // The implicit `Profile` node is an error in the strict profile
+        let strict: Vec<Result<Graph, GraphGatewayError>> =
+            load_all(&[PLANTUML], InputFormat::Plantuml, Profile::Strict);
+        assert!(strict[0].is_err());
+    }
+}
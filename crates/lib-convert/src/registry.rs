@@ -0,0 +1,159 @@
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use lib_core::adapters::{graph_emitter_adapter::GraphEmitterAdapter, graph_gateway::GraphGateway};
+
+use crate::formats::{InputFormat, OutputFormat};
+
+/// A notation added at runtime, such as one a WebAssembly plugin implements.
+/// It can read diagrams, write them, or both.
+#[derive(Clone)]
+pub struct FormatPlugin {
+    pub name: String,
+    /// File extensions, without the dot, that imply the notation when reading
+    pub extensions: Vec<String>,
+    pub gateway: Option<Arc<dyn GraphGateway + Send + Sync>>,
+    pub emitter: Option<Arc<dyn GraphEmitterAdapter + Send + Sync>>,
+}
+
+/// Index of a registered reader or writer. Only the registry hands these
+/// out, and it never forgets a format, so an index always resolves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PluginId(usize);
+
+struct Reader {
+    name: &'static str,
+    extensions: Vec<String>,
+    gateway: Arc<dyn GraphGateway + Send + Sync>,
+}
+
+struct Writer {
+    name: &'static str,
+    extension: &'static str,
+    emitter: Arc<dyn GraphEmitterAdapter + Send + Sync>,
+}
+
+static READERS: RwLock<Vec<Reader>> = RwLock::new(Vec::new());
+static WRITERS: RwLock<Vec<Writer>> = RwLock::new(Vec::new());
+
+/// Makes a notation available to `--from`/`--to` style lookups, extension
+/// guessing and conversions for the rest of the process. Names already taken
+/// by a built-in or another plugin are refused.
+pub fn register(plugin: FormatPlugin) -> Result<(), String> {
+    let taken: bool =
+        plugin.name.parse::<InputFormat>().is_ok() || plugin.name.parse::<OutputFormat>().is_ok();
+    if taken {
+        return Err(format!("Format '{}' is already registered", plugin.name));
+    }
+    if plugin.gateway.is_none() && plugin.emitter.is_none() {
+        return Err(format!("Format '{}' neither reads nor writes", plugin.name));
+    }
+
+    // Formats live as long as the process, so their names can too
+    let name: &'static str = Box::leak(plugin.name.into_boxed_str());
+    if let Some(gateway) = plugin.gateway {
+        write(&READERS).push(Reader {
+            name,
+            extensions: plugin.extensions.clone(),
+            gateway,
+        });
+    }
+    if let Some(emitter) = plugin.emitter {
+        write(&WRITERS).push(Writer {
+            name,
+            extension: plugin
+                .extensions
+                .first()
+                .map_or(name, |extension: &String| {
+                    Box::leak(extension.clone().into_boxed_str())
+                }),
+            emitter,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn readers() -> Vec<PluginId> {
+    (0..read(&READERS).len()).map(PluginId).collect()
+}
+
+pub(crate) fn writers() -> Vec<PluginId> {
+    (0..read(&WRITERS).len()).map(PluginId).collect()
+}
+
+pub(crate) fn reader_name(id: PluginId) -> &'static str {
+    read(&READERS)[id.0].name
+}
+
+pub(crate) fn reader_for_extension(extension: &str) -> Option<PluginId> {
+    read(&READERS)
+        .iter()
+        .position(|reader: &Reader| {
+            reader
+                .extensions
+                .iter()
+                .any(|known: &String| known.eq_ignore_ascii_case(extension))
+        })
+        .map(PluginId)
+}
+
+pub(crate) fn gateway(id: PluginId) -> Arc<dyn GraphGateway + Send + Sync> {
+    read(&READERS)[id.0].gateway.clone()
+}
+
+pub(crate) fn writer_name(id: PluginId) -> &'static str {
+    read(&WRITERS)[id.0].name
+}
+
+pub(crate) fn writer_extension(id: PluginId) -> &'static str {
+    read(&WRITERS)[id.0].extension
+}
+
+pub(crate) fn emitter(id: PluginId) -> Arc<dyn GraphEmitterAdapter + Send + Sync> {
+    read(&WRITERS)[id.0].emitter.clone()
+}
+
+// A panic while holding the lock leaves the lists intact, so a poisoned lock
+// is still safe to use
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lib_core::adapters::graph_gateway::GraphGateway;
+    use lib_yuml::infrastructure::adapters::yuml_graph_gateway::YumlGraphGateway;
+    use pretty_assertions::assert_eq;
+
+    use crate::registry::{FormatPlugin, register};
+
+    // Registering for real would leak into the other tests of this process,
+    // so the plugin crate covers that
+    #[test]
+    fn test_refuses_taken_names_and_formats_doing_nothing() {
+        let plugin = |name: &str, reads: bool| -> FormatPlugin {
+            FormatPlugin {
+                name: name.to_string(),
+                extensions: Vec::new(),
+                gateway: reads.then(|| {
+                    Arc::new(YumlGraphGateway::new()) as Arc<dyn GraphGateway + Send + Sync>
+                }),
+                emitter: None,
+            }
+        };
+
+        assert_eq!(
+            register(plugin("Mermaid", true)).unwrap_err(),
+            "Format 'Mermaid' is already registered"
+        );
+        assert_eq!(
+            register(plugin("idle", false)).unwrap_err(),
+            "Format 'idle' neither reads nor writes"
+        );
+    }
+}
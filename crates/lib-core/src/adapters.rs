@@ -1 +1,4 @@
+pub mod document_gateway;
+pub mod graph_emitter_adapter;
 pub mod graph_gateway;
+pub mod layout_engine_adapter;
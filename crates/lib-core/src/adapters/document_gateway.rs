@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+
+use crate::entities::document::SourceBlock;
+
+#[async_trait]
+pub trait DocumentGateway {
+    async fn read_blocks_from_raw_input(
+        &self,
+        input: &str,
+    ) -> Result<Vec<SourceBlock>, DocumentGatewayError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentGatewayError {
+    Parse {
+        source: String,
+        message: String,
+        line: usize,
+        column: usize,
+    },
+}
@@ -0,0 +1,295 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use async_trait::async_trait;
+
+use crate::entities::{
+    edge::Edge,
+    graph::Graph,
+    group::Group,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+};
+
+/// Writes a graph out in another notation (e.g. Mermaid source or SVG)
+#[async_trait]
+pub trait GraphEmitterAdapter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphEmitterError {
+    Unsupported { target: String, message: String },
+}
+
+/// Output settings every emitter honours where its notation has the notion,
+/// so the same options give comparable output across formats. The defaults
+/// reproduce each emitter's usual output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmitOptions {
+    pub indent: Indent,
+    pub quoting: Quoting,
+    pub order: ElementOrder,
+    /// Keeps named styles and the references to them
+    pub styles: bool,
+    /// Keeps annotation nodes and the edges attaching them
+    pub notes: bool,
+}
+
+/// Text added per nesting level
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Indent {
+    /// Whatever the notation usually uses
+    #[default]
+    Native,
+    Spaces(usize),
+    Tab,
+}
+
+/// When labels are written as quoted strings
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Quoting {
+    #[default]
+    Always,
+    /// Only labels the notation could not read unquoted
+    AsNeeded,
+}
+
+/// Order nodes and groups are written in. Either is stable across runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ElementOrder {
+    #[default]
+    Id,
+    /// By label, then id, as a reader sees them
+    Label,
+}
+
+impl Default for EmitOptions {
+    fn default() -> Self {
+        Self {
+            indent: Indent::default(),
+            quoting: Quoting::default(),
+            order: ElementOrder::default(),
+            styles: true,
+            notes: true,
+        }
+    }
+}
+
+impl EmitOptions {
+    /// One level of indentation, `native` being the notation's own
+    pub fn indent(&self, native: &str) -> String {
+        match self.indent {
+            Indent::Native => native.to_string(),
+            Indent::Spaces(width) => " ".repeat(width),
+            Indent::Tab => "\t".to_string(),
+        }
+    }
+
+    /// The graph as it is written out: without the elements the options
+    /// leave out nor hidden elements, the edges touching them and the notes
+    /// on those edges, borrowed when nothing is left out
+    pub fn prepare<'a>(&self, graph: &'a Graph) -> Cow<'a, Graph> {
+        let mut hidden: HashSet<Id> = graph.hidden();
+        let edges: HashSet<Id> = graph
+            .edges
+            .values()
+            .filter(|edge: &&Edge| {
+                edge.hidden || hidden.contains(&edge.from) || hidden.contains(&edge.to)
+            })
+            .map(|edge: &Edge| edge.id.clone())
+            .collect();
+        hidden.extend(
+            graph
+                .nodes
+                .values()
+                .filter(|node: &&Node| {
+                    node.target_edge
+                        .as_ref()
+                        .is_some_and(|edge: &Id| edges.contains(edge))
+                })
+                .map(|node: &Node| node.id.clone()),
+        );
+        if hidden.is_empty() && edges.is_empty() {
+            return self.prune(graph);
+        }
+
+        let mut graph: Graph = self.prune(graph).into_owned();
+        graph.nodes.retain(|id: &Id, _| !hidden.contains(id));
+        graph.groups.retain(|id: &Id, _| !hidden.contains(id));
+        graph.edges.retain(|id: &Id, edge: &mut Edge| {
+            !edges.contains(id) && !hidden.contains(&edge.from) && !hidden.contains(&edge.to)
+        });
+        graph.groups.values_mut().for_each(|group: &mut Group| {
+            group.children.retain(|child: &Id| !hidden.contains(child));
+        });
+        Cow::Owned(graph)
+    }
+
+    /// The graph without the elements the options leave out, hidden ones
+    /// included, as laid out before writing: hidden elements still take up
+    /// room
+    pub fn prune<'a>(&self, graph: &'a Graph) -> Cow<'a, Graph> {
+        if self.styles && self.notes {
+            return Cow::Borrowed(graph);
+        }
+
+        let mut graph: Graph = graph.clone();
+        if !self.styles {
+            graph.styles.clear();
+            graph
+                .nodes
+                .values_mut()
+                .for_each(|node: &mut Node| node.style = None);
+            graph
+                .edges
+                .values_mut()
+                .for_each(|edge: &mut Edge| edge.style = None);
+        }
+        if !self.notes {
+            let notes: HashSet<Id> = graph
+                .nodes
+                .values()
+                .filter(|node: &&Node| node.kind == NodeKind::Annotation)
+                .map(|node: &Node| node.id.clone())
+                .collect();
+            graph.nodes.retain(|id: &Id, _| !notes.contains(id));
+            graph.edges.retain(|_, edge: &mut Edge| {
+                !notes.contains(&edge.from) && !notes.contains(&edge.to)
+            });
+            graph.groups.values_mut().for_each(|group: &mut Group| {
+                group.children.retain(|child: &Id| !notes.contains(child));
+            });
+        }
+        Cow::Owned(graph)
+    }
+
+    pub fn sort_nodes(&self, nodes: &mut [&Node]) {
+        match self.order {
+            ElementOrder::Id => nodes.sort_by(|a: &&Node, b: &&Node| a.id.cmp(&b.id)),
+            ElementOrder::Label => nodes.sort_by(|a: &&Node, b: &&Node| {
+                (name(&a.label, &a.id), &a.id).cmp(&(name(&b.label, &b.id), &b.id))
+            }),
+        }
+    }
+
+    pub fn sort_groups(&self, groups: &mut [&Group]) {
+        match self.order {
+            ElementOrder::Id => groups.sort_by(|a: &&Group, b: &&Group| a.id.cmp(&b.id)),
+            ElementOrder::Label => groups.sort_by(|a: &&Group, b: &&Group| {
+                (name(&a.label, &a.id), &a.id).cmp(&(name(&b.label, &b.id), &b.id))
+            }),
+        }
+    }
+}
+
+fn name<'a>(label: &'a Option<Text>, id: &'a Id) -> &'a str {
+    label.as_deref().unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{builder::GraphBuilder, entities::edge::EdgeKind, entities::graph::GraphKind};
+
+    #[test]
+    fn test_prepare_drops_notes_and_styles_only_when_asked() {
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("A", NodeKind::Entity, "Zebra")
+            .node("B", NodeKind::Entity, "Ant")
+            .node("N", NodeKind::Annotation, "Remember")
+            .group("g", "G", &["A", "N"])
+            .edge("N", "A", EdgeKind::Undirected, None)
+            .edge("A", "B", EdgeKind::Association, None)
+            .build();
+        graph.nodes.get_mut("A").unwrap().style = Some("bold".into());
+
+        let options: EmitOptions = EmitOptions {
+            styles: false,
+            notes: false,
+            order: ElementOrder::Label,
+            ..Default::default()
+        };
+        let prepared: Cow<Graph> = options.prepare(&graph);
+        let mut nodes: Vec<&Node> = prepared.nodes.values().collect();
+        options.sort_nodes(&mut nodes);
+
+        assert!(matches!(
+            EmitOptions::default().prepare(&graph),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            nodes
+                .iter()
+                .map(|node: &&Node| node.id.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["B", "A"]
+        );
+        assert_eq!(nodes[1].style, None);
+        assert_eq!(prepared.edges.len(), 1);
+        assert_eq!(prepared.groups["g"].children, vec!["A"]);
+        assert_eq!(options.indent("  "), "  ");
+    }
+
+    #[test]
+    fn test_prepare_drops_hidden_elements_that_prune_keeps() {
+        let graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("A", NodeKind::Entity, "A")
+            .node("B", NodeKind::Entity, "B")
+            .node("C", NodeKind::Entity, "C")
+            .node("D", NodeKind::Entity, "D")
+            .group("inner", "Inner", &["C"])
+            .group("outer", "Outer", &["inner", "D"])
+            .edge("A", "B", EdgeKind::Association, None)
+            .edge("A", "C", EdgeKind::Association, None)
+            .hide("B")
+            .hide("inner")
+            .build();
+
+        let prepared: Cow<Graph> = EmitOptions::default().prepare(&graph);
+
+        assert!(matches!(
+            EmitOptions::default().prune(&graph),
+            Cow::Borrowed(_)
+        ));
+        let mut nodes: Vec<&str> = prepared.nodes.keys().map(Id::as_str).collect();
+        nodes.sort();
+        assert_eq!(nodes, vec!["A", "D"]);
+        assert!(prepared.edges.is_empty());
+        assert_eq!(prepared.groups.len(), 1);
+        assert_eq!(prepared.groups["outer"].children, vec!["D"]);
+    }
+
+    #[test]
+    fn test_prepare_drops_hidden_edges_and_the_notes_on_dropped_edges() {
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("A", NodeKind::Entity, "A")
+            .node("B", NodeKind::Entity, "B")
+            .node("C", NodeKind::Entity, "C")
+            .node("cached", NodeKind::Annotation, "cached")
+            .node("kept", NodeKind::Annotation, "kept")
+            .node("skipped", NodeKind::Annotation, "skipped")
+            .edge("A", "B", EdgeKind::Association, None)
+            .edge("A", "C", EdgeKind::Association, None)
+            .edge("C", "A", EdgeKind::Dependency, None)
+            .hide("B")
+            .hide("e3")
+            .build();
+        [("cached", "e1"), ("kept", "e2"), ("skipped", "e3")]
+            .into_iter()
+            .for_each(|(note, edge): (&str, &str)| {
+                graph.nodes.get_mut(note).unwrap().target_edge = Some(edge.into());
+            });
+
+        let prepared: Cow<Graph> = EmitOptions::default().prepare(&graph);
+
+        let mut nodes: Vec<&str> = prepared.nodes.keys().map(Id::as_str).collect();
+        nodes.sort();
+        assert_eq!(nodes, vec!["A", "C", "kept"]);
+        assert_eq!(
+            prepared.edges.keys().map(Id::as_str).collect::<Vec<&str>>(),
+            vec!["e2"]
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::entities::graph::Graph;
+
+/// Computes geometry for a graph: node and group rectangles and edge waypoints
+/// are written back into the given graph.
+#[async_trait]
+pub trait LayoutEngineAdapter {
+    async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutEngineError {
+    Unsupported { engine: String, message: String },
+    Failed { engine: String, message: String },
+}
@@ -0,0 +1,154 @@
+use crate::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind, LayoutDirection},
+    group::Group,
+    id::{Id, Text},
+    member::Member,
+    node::{Node, NodeKind},
+    value::Value,
+};
+
+/// Assembles a graph in code, for tools generating diagrams rather than
+/// reading them. Edges get ids in insertion order, and in sequence diagrams
+/// flows are numbered the way the sequence readers number messages.
+#[derive(Debug, Clone, Default)]
+pub struct GraphBuilder {
+    graph: Graph,
+    messages: usize,
+}
+
+impl GraphBuilder {
+    pub fn new(kind: GraphKind) -> Self {
+        Self {
+            graph: Graph {
+                kind,
+                ..Default::default()
+            },
+            messages: 0,
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.graph.metadata.title = Some(title.to_string());
+        self
+    }
+
+    pub fn direction(mut self, direction: LayoutDirection) -> Self {
+        self.graph.direction = direction;
+        self
+    }
+
+    /// Adds a node, replacing any node with the same id
+    pub fn node(mut self, id: &str, kind: NodeKind, label: &str) -> Self {
+        self.graph.nodes.insert(
+            id.into(),
+            Node {
+                id: id.into(),
+                kind,
+                label: Some(label.into()),
+                ..Default::default()
+            },
+        );
+        self
+    }
+
+    /// Appends a member to an existing node; unknown nodes are ignored
+    pub fn member(mut self, node: &str, member: Member) -> Self {
+        if let Some(node) = self.graph.nodes.get_mut(node) {
+            node.members.push(member);
+        }
+        self
+    }
+
+    /// Adds a group around nodes or groups added before it
+    pub fn group(mut self, id: &str, label: &str, children: &[&str]) -> Self {
+        let group_id: Id = id.into();
+        children.iter().for_each(|child: &&str| {
+            if let Some(node) = self.graph.nodes.get_mut(*child) {
+                node.parent = Some(group_id.clone());
+            } else if let Some(group) = self.graph.groups.get_mut(*child) {
+                group.parent = Some(group_id.clone());
+            }
+        });
+        self.graph.groups.insert(
+            group_id.clone(),
+            Group {
+                id: group_id,
+                label: Some(label.into()),
+                children: children
+                    .iter()
+                    .map(|child: &&str| Id::from(*child))
+                    .collect(),
+                ..Default::default()
+            },
+        );
+        self
+    }
+
+    pub fn edge(mut self, from: &str, to: &str, kind: EdgeKind, label: Option<&str>) -> Self {
+        let id: Id = format!("e{}", self.graph.edges.len() + 1).into();
+        let mut edge: Edge = Edge {
+            id: id.clone(),
+            from: from.into(),
+            to: to.into(),
+            directed: kind != EdgeKind::Undirected,
+            kind,
+            label: label.map(Text::from),
+            order: self.graph.edges.len(),
+            ..Default::default()
+        };
+        if self.graph.kind == GraphKind::Sequence && edge.kind == EdgeKind::Flow {
+            self.messages += 1;
+            edge.data
+                .insert("sequence".into(), Value::Number(self.messages as f64));
+        }
+        self.graph.edges.insert(id, edge);
+        self
+    }
+
+    /// Hides a node, group or edge added before, which is then laid out but
+    /// not drawn; unknown ids are ignored
+    pub fn hide(mut self, id: &str) -> Self {
+        if let Some(node) = self.graph.nodes.get_mut(id) {
+            node.hidden = true;
+        } else if let Some(group) = self.graph.groups.get_mut(id) {
+            group.hidden = true;
+        } else if let Some(edge) = self.graph.edges.get_mut(id) {
+            edge.hidden = true;
+        }
+        self
+    }
+
+    pub fn build(self) -> Graph {
+        self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_builds_grouped_nodes_and_numbered_messages() {
+        let graph: Graph = GraphBuilder::new(GraphKind::Sequence)
+            .title("Login")
+            .node("user", NodeKind::Actor, "User")
+            .node("app", NodeKind::Component, "App")
+            .group("front", "Front end", &["app"])
+            .group("system", "System", &["front"])
+            .edge("user", "app", EdgeKind::Flow, Some("sign in"))
+            .edge("app", "user", EdgeKind::Flow, None)
+            .build();
+
+        assert_eq!(graph.metadata.title.as_deref(), Some("Login"));
+        assert_eq!(graph.nodes["app"].parent.as_deref(), Some("front"));
+        assert_eq!(graph.groups["front"].parent.as_deref(), Some("system"));
+        assert_eq!(graph.edges["e1"].label.as_deref(), Some("sign in"));
+        assert_eq!(
+            graph.edges["e2"].data.get("sequence"),
+            Some(&Value::Number(2.0))
+        );
+    }
+}
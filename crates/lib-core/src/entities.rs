@@ -1,7 +1,16 @@
+pub mod computed_style;
+pub mod document;
 pub mod edge;
+pub mod geometry;
 pub mod graph;
 pub mod group;
+pub mod history;
 pub mod id;
+pub mod member;
 pub mod node;
+pub mod resolved;
+pub mod rich_text;
+pub mod sequence;
 pub mod style;
 pub mod value;
+pub mod warning;
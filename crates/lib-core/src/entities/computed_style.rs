@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::entities::{
+    edge::{Edge, EdgeKind},
+    graph::Graph,
+    group::Group,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+    style::{Style, StyleRef},
+    value::Value,
+};
+
+/// Data key holding an element's own style, as `key:value` pairs separated by
+/// `,` or `;` (`fill:#f9f, stroke:#333`)
+pub const STYLE: &str = "style";
+
+/// Properties applied to every element of a type, above the built-in
+/// defaults and below anything the diagram itself says
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Theme {
+    pub node: HashMap<String, String>,
+    pub edge: HashMap<String, String>,
+    pub group: HashMap<String, String>,
+}
+
+impl Theme {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_node(mut self, key: &str, value: &str) -> Self {
+        self.node.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_edge(mut self, key: &str, value: &str) -> Self {
+        self.edge.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_group(mut self, key: &str, value: &str) -> Self {
+        self.group.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+/// The final style properties of one element
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComputedStyle {
+    pub properties: BTreeMap<String, String>,
+}
+
+impl ComputedStyle {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(String::as_str)
+    }
+
+    fn layer<'a>(mut self, properties: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        properties
+            .into_iter()
+            .for_each(|(key, value): (&str, &str)| {
+                self.properties.insert(key.to_string(), value.to_string());
+            });
+        self
+    }
+}
+
+/// Works out each element's style from, lowest precedence first: the
+/// defaults for its kind, the theme, the graph style it references, its
+/// own `style` data and its colors
+pub struct StyleResolver<'a> {
+    graph: &'a Graph,
+    theme: &'a Theme,
+}
+
+impl<'a> StyleResolver<'a> {
+    pub fn new(graph: &'a Graph, theme: &'a Theme) -> Self {
+        Self { graph, theme }
+    }
+
+    pub fn node(&self, node: &Node) -> ComputedStyle {
+        let fill: &str = match node.kind {
+            NodeKind::Annotation => "#ffffcc",
+            _ => "#fff",
+        };
+        ComputedStyle::default()
+            .layer([("fill", fill), ("stroke", "#333")])
+            .layer(pairs(&self.theme.node))
+            .layer(self.sheet(&node.style))
+            .layer(inline(&node.data))
+            .layer(node.colors.properties().iter().map(pair))
+    }
+
+    pub fn edge(&self, edge: &Edge) -> ComputedStyle {
+        let dashes: Option<(&str, &str)> = match edge.kind {
+            EdgeKind::Dependency | EdgeKind::Realization => Some(("stroke-dasharray", "6,4")),
+            _ => None,
+        };
+        ComputedStyle::default()
+            .layer([("stroke", "#333")])
+            .layer(dashes)
+            .layer(pairs(&self.theme.edge))
+            .layer(self.sheet(&edge.style))
+            .layer(inline(&edge.data))
+            .layer(edge.colors.properties().iter().map(pair))
+    }
+
+    pub fn group(&self, _group: &Group) -> ComputedStyle {
+        ComputedStyle::default()
+            .layer([("fill", "#f8f8f8"), ("stroke", "#333")])
+            .layer(pairs(&self.theme.group))
+    }
+
+    /// Properties of the referenced style; a reference to a missing style
+    /// adds nothing
+    fn sheet(&self, style: &StyleRef) -> impl Iterator<Item = (&'a str, &'a str)> {
+        style
+            .as_ref()
+            .and_then(|id: &Id| self.graph.styles.get(id))
+            .into_iter()
+            .flat_map(|style: &'a Style| pairs(&style.properties))
+    }
+}
+
+fn pairs(properties: &HashMap<String, String>) -> impl Iterator<Item = (&str, &str)> {
+    properties
+        .iter()
+        .map(|(key, value): (&String, &String)| (key.as_str(), value.as_str()))
+}
+
+fn pair<'a>((key, value): &'a (&'static str, String)) -> (&'a str, &'a str) {
+    (key, value.as_str())
+}
+
+/// Pairs of an element's `style` data; pieces without a `:` are skipped
+fn inline(data: &HashMap<Text, Value>) -> impl Iterator<Item = (&str, &str)> {
+    let style: &str = match data.get(STYLE) {
+        Some(Value::String(style)) => style,
+        _ => "",
+    };
+    style
+        .split([',', ';'])
+        .filter_map(|piece: &str| piece.split_once(':'))
+        .map(|(key, value): (&str, &str)| (key.trim(), value.trim()))
+        .filter(|(key, _): &(&str, &str)| !key.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{builder::GraphBuilder, entities::graph::GraphKind};
+
+    #[test]
+    fn test_layers_defaults_theme_sheet_and_inline_style() {
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("A", NodeKind::Entity, "A")
+            .node("B", NodeKind::Annotation, "B")
+            .edge("A", "B", EdgeKind::Dependency, None)
+            .build();
+        graph.styles.insert(
+            "warm".into(),
+            Style {
+                id: "warm".into(),
+                properties: HashMap::from([
+                    ("fill".to_string(), "#fc9".to_string()),
+                    ("stroke".to_string(), "#900".to_string()),
+                ]),
+            },
+        );
+        let a: &mut Node = graph.nodes.get_mut("A").unwrap();
+        a.style = Some("warm".into());
+        a.data.insert(
+            STYLE.into(),
+            Value::String("stroke: #000; bold".to_string()),
+        );
+        let theme: Theme = Theme::new()
+            .with_node("fill", "#eee")
+            .with_node("font-weight", "bold")
+            .with_edge("stroke", "#666");
+
+        let resolver: StyleResolver = StyleResolver::new(&graph, &theme);
+        let a: ComputedStyle = resolver.node(&graph.nodes["A"]);
+        let b: ComputedStyle = resolver.node(&graph.nodes["B"]);
+        let edge: ComputedStyle = resolver.edge(&graph.edges["e1"]);
+
+        assert_eq!(a.get("fill"), Some("#fc9"));
+        assert_eq!(a.get("stroke"), Some("#000"));
+        assert_eq!(a.get("font-weight"), Some("bold"));
+        assert_eq!(b.get("fill"), Some("#eee"));
+        assert_eq!(b.get("stroke"), Some("#333"));
+        assert_eq!(edge.get("stroke"), Some("#666"));
+        assert_eq!(edge.get("stroke-dasharray"), Some("6,4"));
+        assert_eq!(
+            StyleResolver::new(&graph, &Theme::new())
+                .node(&graph.nodes["B"])
+                .get("fill"),
+            Some("#ffffcc")
+        );
+    }
+}
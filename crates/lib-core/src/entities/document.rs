@@ -0,0 +1,33 @@
+use crate::entities::graph::Graph;
+
+/// A host document (e.g. Markdown) with the diagrams embedded in it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    pub blocks: Vec<DiagramBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramBlock {
+    pub language: String,
+    pub source: String,
+    pub span: SourceSpan,
+    pub graph: Result<Graph, String>,
+}
+
+/// A diagram block as found in the host document, before being parsed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceBlock {
+    pub language: String,
+    pub source: String,
+    pub span: SourceSpan,
+}
+
+/// Location of a block's content within the host document.
+/// Offsets are in bytes, lines are 1-based and inclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+    pub start_line: usize,
+    pub end_line: usize,
+}
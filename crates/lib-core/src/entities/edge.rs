@@ -1,27 +1,131 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
-use crate::entities::{id::Id, style::StyleRef, value::Value};
+use crate::entities::{
+    geometry::{Point, Rect},
+    id::{Id, Text},
+    rich_text::RichText,
+    style::{Colors, StyleRef},
+    value::Value,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Edge {
     pub id: Id,
     pub from: Id,
     pub to: Id,
     pub directed: bool,
     pub kind: EdgeKind,
-    pub label: Option<String>,
-    pub data: HashMap<String, Value>,
+    pub label: Option<Text>,
+    pub data: HashMap<Text, Value>,
     pub style: StyleRef,
+    /// Colors given to this edge alone (e.g. `-[#red]->`)
+    pub colors: Colors,
+    pub waypoints: Vec<Point>,
+    pub layout: EdgeLayout,
+    /// Laid out like any other edge but left out of renderings and exports,
+    /// where `layout.hidden` edges are still written as invisible links
+    pub hidden: bool,
+    /// Place of the edge among those its source declares, counting from 0;
+    /// parallel edges are numbered and written in this order
+    pub order: usize,
+    /// Member of the source the edge starts at (e.g. `customer` of
+    /// `Order::customer`)
+    pub from_port: Option<String>,
+    /// Member of the target the edge ends at
+    pub to_port: Option<String>,
+    /// Multiplicity at the source end (e.g. `1` of `A "1" --> "*" B`)
+    pub from_cardinality: Option<Text>,
+    /// Multiplicity at the target end
+    pub to_cardinality: Option<Text>,
+    /// The label with its formatting, when the source marks it up; `label`
+    /// then holds its plain text
+    pub rich_label: Option<RichText>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum EdgeKind {
+    #[default]
     Association,
     Dependency,
     Inheritance,
+    Realization,
     Aggregation,
     Composition,
     Flow,
     Undirected,
     Custom(String),
 }
+
+/// Layout-only information attached to an edge
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EdgeLayout {
+    /// Placement of the target relative to the source (e.g. `-up->`)
+    pub direction: Option<EdgeDirection>,
+    /// Minimum number of ranks between both ends (e.g. `--->`)
+    pub length: Option<usize>,
+    /// Hidden edges shape the layout but are not drawn
+    pub hidden: bool,
+    /// Texts drawn along the edge, filled in by label placement
+    pub labels: Vec<EdgeLabel>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A text drawn next to an edge, `bounds` being the box it occupies
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeLabel {
+    pub kind: EdgeLabelKind,
+    pub text: String,
+    pub bounds: Rect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeLabelKind {
+    Label,
+    FromCardinality,
+    ToCardinality,
+    FromRole,
+    ToRole,
+}
+
+/// Names an edge by what it joins rather than by its id: its ends and its
+/// place among the edges with the same ends, counting from 0
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeKey {
+    pub from: Id,
+    pub to: Id,
+    pub ordinal: usize,
+}
+
+impl fmt::Display for EdgeKey {
+    /// `A->B` for the first edge, then `A->B#1`, `A->B#2`…
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ordinal {
+            0 => write!(f, "{}->{}", self.from, self.to),
+            ordinal => write!(f, "{}->{}#{}", self.from, self.to, ordinal),
+        }
+    }
+}
+
+/// Where an edge sits among the edges joining the same two nodes, whichever
+/// way they point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parallel {
+    pub ordinal: usize,
+    pub count: usize,
+}
+
+impl Parallel {
+    /// How far to draw the edge from the line joining its ends, so parallel
+    /// edges sit `spacing` apart around that line. Positive is to the left
+    /// looking from the end with the lower id.
+    pub fn offset(&self, spacing: f64) -> f64 {
+        (self.ordinal as f64 - (self.count as f64 - 1.0) / 2.0) * spacing
+    }
+}
@@ -1,15 +1,180 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
 
-use crate::entities::{edge::Edge, group::Group, id::Id, node::Node, style::Style};
+use crate::entities::{
+    edge::{Edge, EdgeKey, Parallel},
+    group::Group,
+    id::Id,
+    node::Node,
+    sequence::{Activation, Fragment, Operand},
+    style::Style,
+    warning::Warning,
+};
 
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Graph {
     pub id: Id,
+    pub kind: GraphKind,
+    pub direction: LayoutDirection,
     pub metadata: Metadata,
     pub nodes: HashMap<Id, Node>,
     pub edges: HashMap<Id, Edge>,
     pub groups: HashMap<Id, Group>,
     pub styles: HashMap<Id, Style>,
+    /// Blocks around messages, for sequence diagrams
+    pub fragments: HashMap<Id, Fragment>,
+    /// Active spans of lifelines, for sequence diagrams
+    pub activations: Vec<Activation>,
+    /// What the reader could not represent faithfully
+    pub warnings: Vec<Warning>,
+}
+
+impl Graph {
+    /// Every edge's key. Edges with the same ends are numbered in source
+    /// order, so the numbering does not hang on ids a reader made up.
+    pub fn edge_keys(&self) -> HashMap<Id, EdgeKey> {
+        let mut bundles: HashMap<(&Id, &Id), Vec<&Edge>> = HashMap::new();
+        self.edges.values().for_each(|edge: &Edge| {
+            bundles
+                .entry((&edge.from, &edge.to))
+                .or_default()
+                .push(edge);
+        });
+
+        bundles
+            .into_values()
+            .flat_map(|mut bundle: Vec<&Edge>| {
+                bundle.sort_by(bundle_order);
+                bundle
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ordinal, edge): (usize, &Edge)| {
+                        let key: EdgeKey = EdgeKey {
+                            from: edge.from.clone(),
+                            to: edge.to.clone(),
+                            ordinal,
+                        };
+                        (edge.id.clone(), key)
+                    })
+            })
+            .collect()
+    }
+
+    /// Where each edge sits among the edges joining the same two nodes either
+    /// way, those pointing from the lower id first; edges without a parallel
+    /// are left out
+    pub fn parallel_edges(&self) -> HashMap<Id, Parallel> {
+        let mut bundles: HashMap<(&Id, &Id), Vec<&Edge>> = HashMap::new();
+        self.edges.values().for_each(|edge: &Edge| {
+            let ends: (&Id, &Id) = match edge.from <= edge.to {
+                true => (&edge.from, &edge.to),
+                false => (&edge.to, &edge.from),
+            };
+            bundles.entry(ends).or_default().push(edge);
+        });
+
+        bundles
+            .into_values()
+            .filter(|bundle: &Vec<&Edge>| bundle.len() > 1)
+            .flat_map(|mut bundle: Vec<&Edge>| {
+                bundle.sort_by(|a: &&Edge, b: &&Edge| {
+                    (&a.from, &a.to)
+                        .cmp(&(&b.from, &b.to))
+                        .then_with(|| bundle_order(a, b))
+                });
+                let count: usize = bundle.len();
+                bundle
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(ordinal, edge): (usize, &Edge)| {
+                        (edge.id.clone(), Parallel { ordinal, count })
+                    })
+            })
+            .collect()
+    }
+
+    /// Ids of the hidden nodes and groups and of everything inside a hidden
+    /// group
+    pub fn hidden(&self) -> HashSet<Id> {
+        let nodes = self
+            .nodes
+            .values()
+            .filter(|node: &&Node| node.hidden || inside_hidden(&self.groups, node.parent.as_ref()))
+            .map(|node: &Node| node.id.clone());
+        let groups = self
+            .groups
+            .values()
+            .filter(|group: &&Group| {
+                group.hidden || inside_hidden(&self.groups, group.parent.as_ref())
+            })
+            .map(|group: &Group| group.id.clone());
+        nodes.chain(groups).collect()
+    }
+
+    /// Renames every edge after its key (`A->B`, `A->B#1`…), so two reads of
+    /// the same source compare and diff edge by edge even when the reader
+    /// makes up edge ids
+    pub fn normalize_edge_ids(&mut self) {
+        let renamed: HashMap<Id, Id> = self
+            .edge_keys()
+            .into_iter()
+            .map(|(id, key): (Id, EdgeKey)| (id, Id::from(key.to_string())))
+            .collect();
+        self.rename_edges(&renamed);
+    }
+
+    /// Renames edges, and the messages fragments and activations refer to
+    pub fn rename_edges(&mut self, renamed: &HashMap<Id, Id>) {
+        let rename = |id: &mut Id| {
+            if let Some(to) = renamed.get(id) {
+                *id = to.clone();
+            }
+        };
+
+        self.edges = std::mem::take(&mut self.edges)
+            .into_values()
+            .map(|mut edge: Edge| {
+                rename(&mut edge.id);
+                (edge.id.clone(), edge)
+            })
+            .collect();
+        self.nodes
+            .values_mut()
+            .for_each(|node: &mut Node| node.target_edge.iter_mut().for_each(rename));
+        self.fragments
+            .values_mut()
+            .flat_map(|fragment: &mut Fragment| fragment.operands.iter_mut())
+            .for_each(|operand: &mut Operand| operand.messages.iter_mut().for_each(rename));
+        self.activations
+            .iter_mut()
+            .for_each(|activation: &mut Activation| {
+                activation.start.iter_mut().for_each(rename);
+                activation.end.iter_mut().for_each(rename);
+            });
+    }
+}
+
+/// Whether `parent` or any group around it is hidden
+fn inside_hidden<'a>(groups: &'a HashMap<Id, Group>, mut parent: Option<&'a Id>) -> bool {
+    // Bounded, so a parent cycle cannot hang
+    for _ in 0..=groups.len() {
+        match parent.and_then(|id: &Id| groups.get(id)) {
+            Some(group) if group.hidden => return true,
+            Some(group) => parent = group.parent.as_ref(),
+            None => return false,
+        }
+    }
+    false
+}
+
+/// Order of edges sharing their ends: as their source declares them, then,
+/// for edges built in code, by what they show and by id
+fn bundle_order(a: &&Edge, b: &&Edge) -> Ordering {
+    (a.order, &a.label, format!("{:?}", a.kind), a.directed)
+        .cmp(&(b.order, &b.label, format!("{:?}", b.kind), b.directed))
+        .then_with(|| a.id.cmp(&b.id))
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -17,4 +182,188 @@ pub struct Metadata {
     pub title: Option<String>,
     pub description: Option<String>,
     pub properties: HashMap<String, String>,
+    pub legend: Option<Legend>,
+}
+
+/// A box of text explaining the diagram (e.g. PlantUML's `legend right`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Legend {
+    pub text: String,
+    /// Above or below the diagram
+    pub vertical: VerticalPosition,
+    /// Against one of the diagram's sides, or centered
+    pub horizontal: HorizontalPosition,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum VerticalPosition {
+    Top,
+    #[default]
+    Bottom,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum HorizontalPosition {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum GraphKind {
+    #[default]
+    Generic,
+    Class,
+    EntityRelationship,
+    Sequence,
+    Network,
+    MindMap,
+    Wbs,
+    /// A UI mockup, such as a PlantUML Salt wireframe
+    Wireframe,
+    /// States and the transitions between them, with `start` and `end`
+    /// pseudo-states
+    State,
+    /// Steps and decisions linked by control flow, such as an activity
+    /// diagram
+    Flowchart,
+    /// Actors and the use cases they take part in, within system boundaries
+    UseCase,
+}
+
+/// Main flow of a diagram (e.g. `left to right direction`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LayoutDirection {
+    #[default]
+    TopToBottom,
+    LeftToRight,
+    BottomToTop,
+    RightToLeft,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        builder::GraphBuilder,
+        entities::{edge::EdgeKind, node::NodeKind},
+        testing,
+    };
+
+    /// Two reads of `A` calling `B` twice and `B` answering once, with
+    /// made-up edge ids
+    fn read(ids: [&str; 3]) -> Graph {
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("A", NodeKind::Entity, "A")
+            .node("B", NodeKind::Entity, "B")
+            .edge("A", "B", EdgeKind::Association, Some("reads"))
+            .edge("A", "B", EdgeKind::Association, Some("writes"))
+            .edge("B", "A", EdgeKind::Dependency, None)
+            .build();
+        graph.edges = std::mem::take(&mut graph.edges)
+            .into_values()
+            .map(|mut edge: Edge| {
+                let index: usize = edge.id.trim_start_matches('e').parse::<usize>().unwrap() - 1;
+                edge.id = Id::from(ids[index]);
+                (edge.id.clone(), edge)
+            })
+            .collect();
+        graph
+    }
+
+    #[test]
+    fn test_numbers_parallel_edges_whatever_their_ids() {
+        let graph: Graph = read(["x9", "x1", "x5"]);
+
+        let keys: HashMap<Id, EdgeKey> = graph.edge_keys();
+        let parallel: HashMap<Id, Parallel> = graph.parallel_edges();
+
+        assert_eq!(keys["x9"].to_string(), "A->B");
+        assert_eq!(keys["x1"].to_string(), "A->B#1");
+        assert_eq!(keys["x5"].to_string(), "B->A");
+        assert_eq!(
+            [&parallel["x9"], &parallel["x1"], &parallel["x5"]],
+            [
+                &Parallel {
+                    ordinal: 0,
+                    count: 3
+                },
+                &Parallel {
+                    ordinal: 1,
+                    count: 3
+                },
+                &Parallel {
+                    ordinal: 2,
+                    count: 3
+                },
+            ]
+        );
+        assert_eq!(parallel["x9"].offset(10.0), -10.0);
+        assert_eq!(parallel["x5"].offset(10.0), 10.0);
+    }
+
+    #[test]
+    fn test_numbers_parallel_edges_in_source_order_then_by_content() {
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("A", NodeKind::Entity, "A")
+            .node("B", NodeKind::Entity, "B")
+            .edge("A", "B", EdgeKind::Association, Some("writes"))
+            .edge("A", "B", EdgeKind::Association, Some("reads"))
+            .build();
+
+        let in_source_order: HashMap<Id, EdgeKey> = graph.edge_keys();
+        graph
+            .edges
+            .values_mut()
+            .for_each(|edge: &mut Edge| edge.order = 0);
+
+        assert_eq!(in_source_order["e1"].to_string(), "A->B");
+        assert_eq!(in_source_order["e2"].to_string(), "A->B#1");
+        assert_eq!(graph.edge_keys()["e2"].to_string(), "A->B");
+    }
+
+    #[test]
+    fn test_normalized_reads_diff_clean() {
+        let mut first: Graph = read(["x9", "x1", "x5"]);
+        let mut second: Graph = read(["y1", "y2", "y3"]);
+
+        first.normalize_edge_ids();
+        second.normalize_edge_ids();
+
+        assert_eq!(first.edges.len(), 3);
+        assert_eq!(testing::diff(&first, &second), vec![]);
+        assert_eq!(first.edges["A->B#1"].label.as_deref(), Some("writes"));
+    }
+
+    #[test]
+    fn test_renaming_edges_follows_fragments_and_activations() {
+        let mut graph: Graph = read(["x9", "x1", "x5"]);
+        graph.fragments.insert(
+            "f".into(),
+            Fragment {
+                id: "f".into(),
+                operands: vec![Operand {
+                    messages: vec!["x1".into(), "x5".into()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        graph.activations.push(Activation {
+            participant: "B".into(),
+            start: Some("x9".into()),
+            end: None,
+        });
+
+        graph.normalize_edge_ids();
+
+        assert_eq!(
+            graph.fragments["f"].operands[0].messages,
+            vec![Id::from("A->B#1"), Id::from("B->A")]
+        );
+        assert_eq!(graph.activations[0].start, Some(Id::from("A->B")));
+    }
 }
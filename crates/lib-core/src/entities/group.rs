@@ -1,9 +1,29 @@
-use crate::entities::id::Id;
+use crate::entities::{
+    geometry::Rect,
+    id::{Id, Text},
+    node::DeploymentKind,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Group {
     pub id: Id,
-    pub label: Option<String>,
+    pub kind: GroupKind,
+    pub label: Option<Text>,
     pub children: Vec<Id>,
     pub parent: Option<Id>,
+    pub geometry: Option<Rect>,
+    /// Laid out but left out of renderings and exports, along with
+    /// everything inside
+    pub hidden: bool,
+}
+
+/// What a group stands for, which decides how it is drawn and declared
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupKind {
+    #[default]
+    Package,
+    /// The boundary of a system in a use case diagram
+    Boundary,
+    /// A deployment element holding others, such as a cloud
+    Deployment(DeploymentKind),
 }
@@ -0,0 +1,265 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::SystemTime,
+};
+
+use crate::entities::{
+    edge::Edge,
+    graph::{Graph, GraphKind, LayoutDirection, Metadata},
+    group::Group,
+    id::Id,
+    node::Node,
+    style::Style,
+};
+
+/// One edit to a graph. Elements are put or removed whole, by id; removing a
+/// node leaves its edges alone, since a patch list spells out every edit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Patch {
+    Kind(GraphKind),
+    Direction(LayoutDirection),
+    Metadata(Metadata),
+    PutNode(Node),
+    RemoveNode(Id),
+    PutEdge(Edge),
+    RemoveEdge(Id),
+    PutGroup(Group),
+    RemoveGroup(Id),
+    PutStyle(Style),
+    RemoveStyle(Id),
+}
+
+/// A set of patches and who made them, when and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub author: String,
+    pub timestamp: SystemTime,
+    pub message: String,
+    pub patches: Vec<Patch>,
+}
+
+/// A diagram's past: the graph it started as and every revision since, in
+/// order. Revision `0` is the base and revision `n` the graph after the
+/// `n`th set of patches.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    pub base: Graph,
+    pub revisions: Vec<Revision>,
+}
+
+impl Patch {
+    /// Patches turning `from` into `to`, sorted by element id. Warnings and
+    /// the graph id are not part of a diagram's content and are left out.
+    pub fn between(from: &Graph, to: &Graph) -> Vec<Patch> {
+        let mut patches: Vec<Patch> = Vec::new();
+
+        if from.kind != to.kind {
+            patches.push(Patch::Kind(to.kind.clone()));
+        }
+        if from.direction != to.direction {
+            patches.push(Patch::Direction(to.direction));
+        }
+        if from.metadata != to.metadata {
+            patches.push(Patch::Metadata(to.metadata.clone()));
+        }
+        entries(
+            &mut patches,
+            &from.nodes,
+            &to.nodes,
+            Patch::PutNode,
+            Patch::RemoveNode,
+        );
+        entries(
+            &mut patches,
+            &from.edges,
+            &to.edges,
+            Patch::PutEdge,
+            Patch::RemoveEdge,
+        );
+        entries(
+            &mut patches,
+            &from.groups,
+            &to.groups,
+            Patch::PutGroup,
+            Patch::RemoveGroup,
+        );
+        entries(
+            &mut patches,
+            &from.styles,
+            &to.styles,
+            Patch::PutStyle,
+            Patch::RemoveStyle,
+        );
+
+        patches
+    }
+
+    pub fn apply(&self, graph: &mut Graph) {
+        match self {
+            Self::Kind(kind) => graph.kind = kind.clone(),
+            Self::Direction(direction) => graph.direction = *direction,
+            Self::Metadata(metadata) => graph.metadata = metadata.clone(),
+            Self::PutNode(node) => {
+                graph.nodes.insert(node.id.clone(), node.clone());
+            }
+            Self::RemoveNode(id) => {
+                graph.nodes.remove(id);
+            }
+            Self::PutEdge(edge) => {
+                graph.edges.insert(edge.id.clone(), edge.clone());
+            }
+            Self::RemoveEdge(id) => {
+                graph.edges.remove(id);
+            }
+            Self::PutGroup(group) => {
+                graph.groups.insert(group.id.clone(), group.clone());
+            }
+            Self::RemoveGroup(id) => {
+                graph.groups.remove(id);
+            }
+            Self::PutStyle(style) => {
+                graph.styles.insert(style.id.clone(), style.clone());
+            }
+            Self::RemoveStyle(id) => {
+                graph.styles.remove(id);
+            }
+        }
+    }
+}
+
+impl History {
+    pub fn new(base: Graph) -> Self {
+        Self {
+            base,
+            revisions: Vec::new(),
+        }
+    }
+
+    /// Number of the latest revision, `0` while only the base exists
+    pub fn head(&self) -> usize {
+        self.revisions.len()
+    }
+
+    /// Appends the patches turning the latest revision into `graph`
+    pub fn record(&mut self, author: &str, timestamp: SystemTime, message: &str, graph: &Graph) {
+        let patches: Vec<Patch> = self
+            .checkout(self.head())
+            .map(|head: Graph| Patch::between(&head, graph))
+            .unwrap_or_default();
+        self.revisions.push(Revision {
+            author: author.to_string(),
+            timestamp,
+            message: message.to_string(),
+            patches,
+        });
+    }
+
+    /// The graph as of `revision`, or `None` past the latest one
+    pub fn checkout(&self, revision: usize) -> Option<Graph> {
+        Some(self.revisions.get(..revision)?.iter().fold(
+            self.base.clone(),
+            |mut graph: Graph, revision: &Revision| {
+                revision
+                    .patches
+                    .iter()
+                    .for_each(|patch: &Patch| patch.apply(&mut graph));
+                graph
+            },
+        ))
+    }
+
+    /// Patches turning revision `from` into revision `to`, in either direction
+    pub fn diff(&self, from: usize, to: usize) -> Option<Vec<Patch>> {
+        Some(Patch::between(&self.checkout(from)?, &self.checkout(to)?))
+    }
+}
+
+fn entries<T: Clone + PartialEq>(
+    patches: &mut Vec<Patch>,
+    from: &HashMap<Id, T>,
+    to: &HashMap<Id, T>,
+    put: fn(T) -> Patch,
+    remove: fn(Id) -> Patch,
+) {
+    let ids: BTreeSet<&Id> = from.keys().chain(to.keys()).collect();
+    ids.into_iter()
+        .for_each(|id: &Id| match (from.get(id), to.get(id)) {
+            (_, Some(after)) if from.get(id) != Some(after) => patches.push(put(after.clone())),
+            (Some(_), None) => patches.push(remove(id.clone())),
+            _ => {}
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{builder::GraphBuilder, entities::edge::EdgeKind, entities::node::NodeKind};
+
+    fn history() -> (History, [Graph; 3]) {
+        let base: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("A", NodeKind::Entity, "A")
+            .node("B", NodeKind::Entity, "B")
+            .build();
+        let linked: Graph = GraphBuilder::new(GraphKind::Class)
+            .title("Model")
+            .node("A", NodeKind::Entity, "A")
+            .node("B", NodeKind::Interface, "B")
+            .edge("A", "B", EdgeKind::Realization, None)
+            .build();
+        let trimmed: Graph = GraphBuilder::new(GraphKind::Class)
+            .title("Model")
+            .node("A", NodeKind::Entity, "A")
+            .build();
+
+        let mut history: History = History::new(base.clone());
+        history.record("ada", SystemTime::UNIX_EPOCH, "Link A to B", &linked);
+        history.record(
+            "lin",
+            SystemTime::UNIX_EPOCH + Duration::from_secs(60),
+            "Drop B",
+            &trimmed,
+        );
+        (history, [base, linked, trimmed])
+    }
+
+    #[test]
+    fn test_checks_out_every_revision() {
+        let (history, graphs): (History, [Graph; 3]) = history();
+
+        assert_eq!(history.head(), 2);
+        graphs
+            .iter()
+            .enumerate()
+            .for_each(|(revision, graph): (usize, &Graph)| {
+                assert_eq!(history.checkout(revision).as_ref(), Some(graph));
+            });
+        assert_eq!(history.checkout(3), None);
+        assert_eq!(history.revisions[1].message, "Drop B");
+    }
+
+    #[test]
+    fn test_records_only_what_changed() {
+        let (history, graphs): (History, [Graph; 3]) = history();
+
+        assert_eq!(
+            history.revisions[0].patches,
+            vec![
+                Patch::Metadata(graphs[1].metadata.clone()),
+                Patch::PutNode(graphs[1].nodes["B"].clone()),
+                Patch::PutEdge(graphs[1].edges["e1"].clone()),
+            ]
+        );
+        assert_eq!(
+            history.diff(2, 0),
+            Some(vec![
+                Patch::Metadata(Metadata::default()),
+                Patch::PutNode(graphs[0].nodes["B"].clone()),
+            ])
+        );
+        assert_eq!(history.diff(1, 1), Some(Vec::new()));
+    }
+}
@@ -1 +1,113 @@
-pub type Id = String;
+use std::collections::HashSet;
+
+use smol_str::SmolStr;
+
+/// Short ids are stored inline and longer ones shared, so cloning an id
+/// never allocates
+pub type Id = SmolStr;
+
+/// Labels and data keys, which are cloned about as often as ids and stored
+/// the same way
+pub type Text = SmolStr;
+
+/// Hands out ids unique within one namespace, such as every element of a
+/// graph across all its clusters. A taken id gets the first free `_2`, `_3`…
+/// suffix, so the same requests in the same order always get the same ids.
+#[derive(Debug, Clone, Default)]
+pub struct IdAllocator {
+    taken: HashSet<Id>,
+}
+
+impl IdAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An allocator avoiding ids already in use
+    pub fn with_taken<'a>(ids: impl IntoIterator<Item = &'a Id>) -> Self {
+        Self {
+            taken: ids.into_iter().cloned().collect(),
+        }
+    }
+
+    pub fn is_taken(&self, id: &str) -> bool {
+        self.taken.contains(id)
+    }
+
+    /// An id derived from a display name
+    pub fn for_name(&mut self, name: &str) -> Id {
+        self.claim(&slugify(name))
+    }
+
+    /// `id` itself while it is free, a suffixed variant otherwise
+    pub fn claim(&mut self, id: &str) -> Id {
+        let mut candidate: Id = Id::from(id);
+        let mut suffix: usize = 2;
+        while self.taken.contains(&candidate) {
+            candidate = Id::from(format!("{}_{}", id, suffix));
+            suffix += 1;
+        }
+        self.taken.insert(candidate.clone());
+        candidate
+    }
+}
+
+/// Lowercase ASCII letters and digits, with every other run of characters
+/// turned into a single `_`, as in `Order Line` → `order_line`
+pub fn slugify(name: &str) -> String {
+    let slug: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part: &&str| !part.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<String>>()
+        .join("_");
+    if slug.is_empty() {
+        "id".to_string()
+    } else {
+        slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::entities::{node::Node, value::Value};
+
+    #[test]
+    fn test_slugifies_names() {
+        assert_eq!(slugify("Order Line"), "order_line");
+        assert_eq!(slugify("  HTTP/2 -> gRPC  "), "http_2_grpc");
+        assert_eq!(slugify("¿?"), "id");
+    }
+
+    #[test]
+    fn test_suffixes_taken_ids_deterministically() {
+        let mut ids: IdAllocator = IdAllocator::with_taken(&[Id::from("user_2")]);
+
+        assert_eq!(ids.for_name("User"), "user");
+        assert_eq!(ids.for_name("user"), "user_3");
+        assert_eq!(ids.claim("user"), "user_4");
+        assert_eq!(ids.claim("Order"), "Order");
+        assert!(ids.is_taken("user_3"));
+    }
+
+    #[test]
+    fn test_cloned_nodes_share_their_labels_and_data_keys() {
+        let node: Node = Node {
+            id: Id::from("order_service"),
+            label: Some(Text::from("Order service, billing and fulfilment")),
+            data: HashMap::from([(Text::from("stereotype"), Value::Bool(true))]),
+            ..Node::default()
+        };
+
+        let clone: Node = node.clone();
+
+        let label = |node: &Node| -> *const u8 { node.label.as_deref().unwrap().as_ptr() };
+        assert_eq!(label(&clone), label(&node));
+        assert!(clone.data.keys().all(|key: &Text| !key.is_heap_allocated()));
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use crate::entities::{id::Text, value::Value};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Member {
+    pub name: String,
+    pub kind: MemberKind,
+    pub type_name: Option<String>,
+    pub data: HashMap<Text, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum MemberKind {
+    #[default]
+    Field,
+    Method,
+    EnumValue,
+    /// A line between sections of members, titled by the member's name
+    /// unless it is empty. Its `line` data says how it is drawn when not
+    /// solid: `dotted`, `double` or `thick`.
+    Separator,
+}
+
+impl Member {
+    /// Whether the member belongs to its type rather than to instances,
+    /// as its `static` data says
+    pub fn is_static(&self) -> bool {
+        matches!(self.data.get("static"), Some(Value::Bool(true)))
+    }
+
+    /// Whether the member is declared without an implementation, as its
+    /// `abstract` data says
+    pub fn is_abstract(&self) -> bool {
+        matches!(self.data.get("abstract"), Some(Value::Bool(true)))
+    }
+}
@@ -1,25 +1,97 @@
 use std::collections::HashMap;
 
-use crate::entities::{id::Id, style::StyleRef, value::Value};
+use crate::entities::{
+    geometry::{Point, Rect},
+    id::{Id, Text},
+    member::Member,
+    rich_text::RichText,
+    style::{Colors, StyleRef},
+    value::Value,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Node {
     pub id: Id,
     pub kind: NodeKind,
-    pub label: Option<String>,
-    pub data: HashMap<String, Value>,
+    pub label: Option<Text>,
+    pub members: Vec<Member>,
+    pub data: HashMap<Text, Value>,
     pub style: StyleRef,
+    /// Colors given to this node alone (e.g. `class Foo #pink`)
+    pub colors: Colors,
     pub parent: Option<Id>,
+    pub geometry: Option<Rect>,
+    /// Center of the node's label, filled in by label placement
+    pub label_anchor: Option<Point>,
+    /// Laid out like any other node but left out of renderings and exports,
+    /// for showing several views of one model
+    pub hidden: bool,
+    /// The label with its formatting, when the source marks it up; `label`
+    /// then holds its plain text
+    pub rich_label: Option<RichText>,
+    /// The edge an annotation node annotates, for notes attached to a link
+    /// rather than to nodes
+    pub target_edge: Option<Id>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum NodeKind {
+    #[default]
     Entity,
     Interface,
     Actor,
     Component,
     Database,
+    Enum,
     Group,
     Annotation,
+    /// An element of a deployment diagram
+    Deployment(DeploymentKind),
     Custom(String),
 }
+
+/// The elements of deployment diagrams, some of which may also group others
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeploymentKind {
+    Node,
+    Cloud,
+    Artifact,
+    Queue,
+    Storage,
+    Card,
+    File,
+    Collections,
+}
+
+impl DeploymentKind {
+    pub const ALL: [DeploymentKind; 8] = [
+        DeploymentKind::Node,
+        DeploymentKind::Cloud,
+        DeploymentKind::Artifact,
+        DeploymentKind::Queue,
+        DeploymentKind::Storage,
+        DeploymentKind::Card,
+        DeploymentKind::File,
+        DeploymentKind::Collections,
+    ];
+
+    /// The word naming the kind, as PlantUML declares it
+    pub fn keyword(self) -> &'static str {
+        match self {
+            DeploymentKind::Node => "node",
+            DeploymentKind::Cloud => "cloud",
+            DeploymentKind::Artifact => "artifact",
+            DeploymentKind::Queue => "queue",
+            DeploymentKind::Storage => "storage",
+            DeploymentKind::Card => "card",
+            DeploymentKind::File => "file",
+            DeploymentKind::Collections => "collections",
+        }
+    }
+
+    pub fn from_keyword(keyword: &str) -> Option<DeploymentKind> {
+        DeploymentKind::ALL
+            .into_iter()
+            .find(|kind: &DeploymentKind| kind.keyword() == keyword)
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use crate::entities::{
+    edge::Edge,
+    graph::Graph,
+    group::Group,
+    id::Id,
+    node::{Node, NodeKind},
+};
+
+/// Position of a node in `ResolvedDiagram::nodes`. Only resolution hands
+/// these out, so one always points at a node of the diagram it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeRef(usize);
+
+/// Position of a group in `ResolvedDiagram::groups`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GroupRef(usize);
+
+/// What an edge end names: a node or, in notations allowing it, a group
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endpoint {
+    Node(NodeRef),
+    Group(GroupRef),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedEdge<'a> {
+    pub edge: &'a Edge,
+    pub from: Endpoint,
+    pub to: Endpoint,
+}
+
+/// An annotation node and the elements edges attach it to, or the edge it
+/// annotates
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedNote<'a> {
+    pub note: NodeRef,
+    pub targets: Vec<Endpoint>,
+    pub target_edge: Option<&'a Edge>,
+}
+
+/// An edge end naming no node or group
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unresolved {
+    pub edge: Id,
+    pub id: Id,
+}
+
+/// A graph with its references checked once. Elements are sorted by id, and
+/// edges with an end that names nothing are left out and listed in
+/// `unresolved` instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedDiagram<'a> {
+    pub graph: &'a Graph,
+    pub nodes: Vec<&'a Node>,
+    pub groups: Vec<&'a Group>,
+    pub edges: Vec<ResolvedEdge<'a>>,
+    pub notes: Vec<ResolvedNote<'a>>,
+    pub unresolved: Vec<Unresolved>,
+}
+
+impl<'a> ResolvedDiagram<'a> {
+    pub fn new(graph: &'a Graph) -> Self {
+        let mut nodes: Vec<&Node> = graph.nodes.values().collect();
+        nodes.sort_by(|a: &&Node, b: &&Node| a.id.cmp(&b.id));
+        let mut groups: Vec<&Group> = graph.groups.values().collect();
+        groups.sort_by(|a: &&Group, b: &&Group| a.id.cmp(&b.id));
+        let mut sorted: Vec<&Edge> = graph.edges.values().collect();
+        sorted.sort_by(|a: &&Edge, b: &&Edge| a.id.cmp(&b.id));
+
+        let node_refs: HashMap<&Id, NodeRef> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node): (usize, &&Node)| (&node.id, NodeRef(index)))
+            .collect();
+        let group_refs: HashMap<&Id, GroupRef> = groups
+            .iter()
+            .enumerate()
+            .map(|(index, group): (usize, &&Group)| (&group.id, GroupRef(index)))
+            .collect();
+        let endpoint = |id: &Id| -> Option<Endpoint> {
+            node_refs
+                .get(id)
+                .map(|node: &NodeRef| Endpoint::Node(*node))
+                .or_else(|| {
+                    group_refs
+                        .get(id)
+                        .map(|group: &GroupRef| Endpoint::Group(*group))
+                })
+        };
+
+        let mut edges: Vec<ResolvedEdge> = Vec::with_capacity(sorted.len());
+        let mut unresolved: Vec<Unresolved> = Vec::new();
+        for edge in sorted {
+            match (endpoint(&edge.from), endpoint(&edge.to)) {
+                (Some(from), Some(to)) => edges.push(ResolvedEdge { edge, from, to }),
+                (from, to) => [(from, &edge.from), (to, &edge.to)]
+                    .into_iter()
+                    .filter(|(end, _): &(Option<Endpoint>, &Id)| end.is_none())
+                    .for_each(|(_, id): (Option<Endpoint>, &Id)| {
+                        unresolved.push(Unresolved {
+                            edge: edge.id.clone(),
+                            id: id.clone(),
+                        })
+                    }),
+            }
+        }
+
+        let notes: Vec<ResolvedNote> = nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node): &(usize, &&Node)| node.kind == NodeKind::Annotation)
+            .map(|(index, node): (usize, &&Node)| {
+                let note: Endpoint = Endpoint::Node(NodeRef(index));
+                ResolvedNote {
+                    note: NodeRef(index),
+                    targets: edges
+                        .iter()
+                        .filter_map(|edge: &ResolvedEdge| match (edge.from, edge.to) {
+                            (from, to) if from == note && to != note => Some(to),
+                            (from, to) if to == note && from != note => Some(from),
+                            _ => None,
+                        })
+                        .collect(),
+                    target_edge: node.target_edge.as_ref().and_then(|id: &Id| {
+                        edges
+                            .iter()
+                            .find(|edge: &&ResolvedEdge| edge.edge.id == *id)
+                            .map(|edge: &ResolvedEdge| edge.edge)
+                    }),
+                }
+            })
+            .collect();
+
+        Self {
+            graph,
+            nodes,
+            groups,
+            edges,
+            notes,
+            unresolved,
+        }
+    }
+
+    pub fn node(&self, node: NodeRef) -> &'a Node {
+        self.nodes[node.0]
+    }
+
+    pub fn group(&self, group: GroupRef) -> &'a Group {
+        self.groups[group.0]
+    }
+
+    pub fn id(&self, endpoint: Endpoint) -> &'a Id {
+        match endpoint {
+            Endpoint::Node(node) => &self.node(node).id,
+            Endpoint::Group(group) => &self.group(group).id,
+        }
+    }
+
+    pub fn node_ref(&self, id: &str) -> Option<NodeRef> {
+        self.nodes
+            .binary_search_by(|node: &&Node| node.id.as_str().cmp(id))
+            .ok()
+            .map(NodeRef)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        builder::GraphBuilder,
+        entities::{edge::EdgeKind, graph::GraphKind},
+    };
+
+    #[test]
+    fn test_resolves_edges_and_notes_and_lists_dangling_ends() {
+        let graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("User", NodeKind::Entity, "User")
+            .node("note", NodeKind::Annotation, "Stored hashed")
+            .node("Auth", NodeKind::Component, "Auth")
+            .group("security", "Security", &["Auth"])
+            .edge("User", "security", EdgeKind::Dependency, None)
+            .edge("note", "User", EdgeKind::Undirected, None)
+            .edge("User", "Profile", EdgeKind::Association, None)
+            .build();
+
+        let resolved: ResolvedDiagram = ResolvedDiagram::new(&graph);
+        let user: NodeRef = resolved.node_ref("User").unwrap();
+
+        assert_eq!(resolved.edges.len(), 2);
+        assert_eq!(resolved.edges[0].from, Endpoint::Node(user));
+        assert_eq!(resolved.id(resolved.edges[0].to), "security");
+        assert_eq!(
+            resolved.notes,
+            vec![ResolvedNote {
+                note: resolved.node_ref("note").unwrap(),
+                targets: vec![Endpoint::Node(user)],
+                target_edge: None,
+            }]
+        );
+        assert_eq!(
+            resolved.unresolved,
+            vec![Unresolved {
+                edge: "e3".into(),
+                id: "Profile".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_resolves_the_edges_notes_annotate() {
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("User", NodeKind::Entity, "User")
+            .node("Auth", NodeKind::Component, "Auth")
+            .node("note", NodeKind::Annotation, "Cached")
+            .node("stale", NodeKind::Annotation, "Gone")
+            .edge("User", "Auth", EdgeKind::Dependency, None)
+            .build();
+        graph.nodes.get_mut("note").unwrap().target_edge = Some("e1".into());
+        graph.nodes.get_mut("stale").unwrap().target_edge = Some("e9".into());
+
+        let resolved: ResolvedDiagram = ResolvedDiagram::new(&graph);
+
+        assert_eq!(
+            resolved
+                .notes
+                .iter()
+                .map(|note: &ResolvedNote| note.target_edge.map(|edge: &Edge| edge.id.as_str()))
+                .collect::<Vec<Option<&str>>>(),
+            vec![Some("e1"), None]
+        );
+    }
+}
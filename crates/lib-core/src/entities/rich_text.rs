@@ -0,0 +1,99 @@
+/// A text with its formatting, line by line, for labels whose source marks
+/// it up (e.g. PlantUML's `**bold**` or `* item`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichText {
+    pub lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    Text(Vec<Span>),
+    /// An item of a bulleted list, `depth` counting from 1
+    Bullet {
+        depth: usize,
+        spans: Vec<Span>,
+    },
+    /// A row of a table, each cell being a run of spans
+    Row {
+        header: bool,
+        cells: Vec<Vec<Span>>,
+    },
+}
+
+/// A run of text formatted one way
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub format: Format,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Format {
+    pub bold: bool,
+    pub italic: bool,
+    pub monospace: bool,
+    pub underline: bool,
+    pub strike: bool,
+}
+
+impl RichText {
+    /// The text without its formatting, lines apart, table cells apart by
+    /// ` | `
+    pub fn plain(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line: &Line| match line {
+                Line::Text(spans) | Line::Bullet { spans, .. } => text(spans),
+                Line::Row { cells, .. } => cells
+                    .iter()
+                    .map(|cell: &Vec<Span>| text(cell))
+                    .collect::<Vec<String>>()
+                    .join(" | "),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+fn text(spans: &[Span]) -> String {
+    spans.iter().map(|span: &Span| span.text.as_str()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_plain_text_drops_the_formatting() {
+        let span = |text: &str, format: Format| -> Span {
+            Span {
+                text: text.to_string(),
+                format,
+            }
+        };
+        let bold: Format = Format {
+            bold: true,
+            ..Format::default()
+        };
+        let rich: RichText = RichText {
+            lines: vec![
+                Line::Text(vec![
+                    span("Order ", Format::default()),
+                    span("service", bold),
+                ]),
+                Line::Bullet {
+                    depth: 1,
+                    spans: vec![span("pays", Format::default())],
+                },
+                Line::Row {
+                    header: true,
+                    cells: vec![vec![span("a", bold)], vec![span("b", bold)]],
+                },
+            ],
+        };
+
+        assert_eq!(rich.plain(), "Order service\npays\na | b");
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+
+use crate::entities::{edge::Edge, graph::Graph, id::Id, value::Value};
+
+/// What an interaction fragment does with the messages inside it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum FragmentKind {
+    /// One operand, chosen by its condition, happens
+    #[default]
+    Alt,
+    /// The single operand happens only if its condition holds
+    Opt,
+    /// The single operand repeats while its condition holds
+    Loop,
+    /// The operands happen concurrently
+    Par,
+    /// The operand happens instead of the rest of the enclosing fragment
+    Break,
+    /// The operands happen atomically
+    Critical,
+    /// A labelled box with no further meaning
+    Group,
+}
+
+/// One part of a fragment, such as each `else` branch of an `alt`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Operand {
+    pub condition: Option<String>,
+    /// Message edges directly inside the operand, in order
+    pub messages: Vec<Id>,
+    /// Fragments directly inside the operand
+    pub fragments: Vec<Id>,
+}
+
+/// A block around messages of a sequence diagram (`alt`, `opt`, `loop`…)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Fragment {
+    pub id: Id,
+    pub kind: FragmentKind,
+    pub operands: Vec<Operand>,
+    pub parent: Option<Id>,
+}
+
+/// A span of a participant's lifeline during which it is active. Its ends
+/// are the messages it follows: no `start` means from the beginning, no
+/// `end` until the end.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Activation {
+    pub participant: Id,
+    pub start: Option<Id>,
+    pub end: Option<Id>,
+}
+
+/// One step of a sequence diagram, as it is written from top to bottom
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step<'a> {
+    Message(&'a Edge),
+    Activate(&'a Id),
+    Deactivate(&'a Id),
+    /// A fragment begins with its first operand
+    Open(&'a Fragment),
+    /// A further operand of a fragment, such as an `else`
+    Operand(&'a Fragment, &'a Operand),
+    Close(&'a Fragment),
+}
+
+/// Something a fragment operand or the diagram itself holds directly
+enum Item<'a> {
+    Message(&'a Edge),
+    Fragment(&'a Fragment),
+}
+
+/// The steps of a sequence diagram: messages by sequence number, each
+/// fragment where its first message falls, and activations after the
+/// messages they follow
+pub fn steps(graph: &Graph) -> Vec<Step<'_>> {
+    let nested: HashSet<&Id> = graph
+        .fragments
+        .values()
+        .flat_map(|fragment: &Fragment| &fragment.operands)
+        .flat_map(|operand: &Operand| &operand.messages)
+        .collect();
+    let items: Vec<Item> = graph
+        .edges
+        .values()
+        .filter(|edge: &&Edge| !nested.contains(&edge.id))
+        .map(Item::Message)
+        .chain(
+            graph
+                .fragments
+                .values()
+                .filter(|fragment: &&Fragment| fragment.parent.is_none())
+                .map(Item::Fragment),
+        )
+        .collect();
+
+    let mut messages: Vec<Step> = Vec::new();
+    write_items(graph, items, &mut messages);
+
+    let mut steps: Vec<Step> = graph
+        .activations
+        .iter()
+        .filter(|activation: &&Activation| {
+            activation
+                .start
+                .as_ref()
+                .is_none_or(|start: &Id| !graph.edges.contains_key(start))
+        })
+        .map(|activation: &Activation| Step::Activate(&activation.participant))
+        .collect();
+    messages.into_iter().for_each(|step: Step| {
+        let Step::Message(edge) = step else {
+            steps.push(step);
+            return;
+        };
+        steps.push(step);
+        graph
+            .activations
+            .iter()
+            .for_each(|activation: &Activation| {
+                if activation.start.as_ref() == Some(&edge.id) {
+                    steps.push(Step::Activate(&activation.participant));
+                }
+            });
+        graph
+            .activations
+            .iter()
+            .for_each(|activation: &Activation| {
+                if activation.end.as_ref() == Some(&edge.id) {
+                    steps.push(Step::Deactivate(&activation.participant));
+                }
+            });
+    });
+    steps
+}
+
+fn write_items<'a>(graph: &'a Graph, mut items: Vec<Item<'a>>, steps: &mut Vec<Step<'a>>) {
+    let key = |item: &Item| -> (f64, Id) {
+        match item {
+            Item::Message(edge) => (sequence(edge), edge.id.clone()),
+            Item::Fragment(fragment) => (first(graph, fragment), fragment.id.clone()),
+        }
+    };
+    items.sort_by(|a: &Item, b: &Item| {
+        let (a, b): ((f64, Id), (f64, Id)) = (key(a), key(b));
+        a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1))
+    });
+    items.into_iter().for_each(|item: Item| match item {
+        Item::Message(edge) => steps.push(Step::Message(edge)),
+        Item::Fragment(fragment) => {
+            fragment
+                .operands
+                .iter()
+                .enumerate()
+                .for_each(|(i, operand): (usize, &Operand)| {
+                    steps.push(match i {
+                        0 => Step::Open(fragment),
+                        _ => Step::Operand(fragment, operand),
+                    });
+                    write_items(graph, operand_items(graph, operand), steps);
+                });
+            if fragment.operands.is_empty() {
+                steps.push(Step::Open(fragment));
+            }
+            steps.push(Step::Close(fragment));
+        }
+    });
+}
+
+fn operand_items<'a>(graph: &'a Graph, operand: &'a Operand) -> Vec<Item<'a>> {
+    operand
+        .messages
+        .iter()
+        .filter_map(|id: &Id| graph.edges.get(id).map(Item::Message))
+        .chain(
+            operand
+                .fragments
+                .iter()
+                .filter_map(|id: &Id| graph.fragments.get(id).map(Item::Fragment)),
+        )
+        .collect()
+}
+
+/// A message's sequence number, unnumbered messages coming last
+fn sequence(edge: &Edge) -> f64 {
+    match edge.data.get("sequence") {
+        Some(Value::Number(sequence)) => *sequence,
+        _ => f64::INFINITY,
+    }
+}
+
+/// The sequence number of the first message anywhere inside a fragment
+fn first(graph: &Graph, fragment: &Fragment) -> f64 {
+    fragment
+        .operands
+        .iter()
+        .flat_map(|operand: &Operand| operand_items(graph, operand))
+        .map(|item: Item| match item {
+            Item::Message(edge) => sequence(edge),
+            Item::Fragment(fragment) => first(graph, fragment),
+        })
+        .min_by(|a: &f64, b: &f64| a.total_cmp(b))
+        .unwrap_or(f64::INFINITY)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        builder::GraphBuilder,
+        entities::{edge::EdgeKind, graph::GraphKind, node::NodeKind},
+    };
+
+    fn describe(step: &Step) -> String {
+        match step {
+            Step::Message(edge) => edge.label.as_deref().unwrap_or_default().to_string(),
+            Step::Activate(participant) => format!("activate {}", participant),
+            Step::Deactivate(participant) => format!("deactivate {}", participant),
+            Step::Open(fragment) => format!("{:?}", fragment.kind),
+            Step::Operand(_, operand) => format!("else {}", operand.condition.as_deref().unwrap()),
+            Step::Close(_) => "end".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_steps_follow_sequence_numbers_not_ids() {
+        // Ids are renamed to sort in the opposite order to the messages
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Sequence)
+            .node("A", NodeKind::Actor, "A")
+            .node("B", NodeKind::Component, "B")
+            .edge("A", "B", EdgeKind::Flow, Some("login"))
+            .edge("B", "A", EdgeKind::Flow, Some("ok"))
+            .edge("B", "A", EdgeKind::Flow, Some("denied"))
+            .edge("A", "B", EdgeKind::Flow, Some("logout"))
+            .build();
+        let mut relabelled: Graph = graph.clone();
+        relabelled.edges = graph
+            .edges
+            .drain()
+            .map(|(id, mut edge): (Id, Edge)| {
+                edge.id = format!("m{}", 5 - id[1..].parse::<usize>().unwrap()).into();
+                (edge.id.clone(), edge)
+            })
+            .collect();
+        relabelled.fragments.insert(
+            "f".into(),
+            Fragment {
+                id: "f".into(),
+                kind: FragmentKind::Alt,
+                operands: vec![
+                    Operand {
+                        condition: Some("valid".to_string()),
+                        messages: vec!["m3".into()],
+                        fragments: Vec::new(),
+                    },
+                    Operand {
+                        condition: Some("invalid".to_string()),
+                        messages: vec!["m2".into()],
+                        fragments: Vec::new(),
+                    },
+                ],
+                parent: None,
+            },
+        );
+        relabelled.activations.push(Activation {
+            participant: "B".into(),
+            start: Some("m4".into()),
+            end: Some("m1".into()),
+        });
+
+        assert_eq!(
+            steps(&relabelled)
+                .iter()
+                .map(describe)
+                .collect::<Vec<String>>(),
+            [
+                "login",
+                "activate B",
+                "Alt",
+                "ok",
+                "else invalid",
+                "denied",
+                "end",
+                "logout",
+                "deactivate B",
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
-use crate::entities::id::Id;
+use crate::entities::id::{Id, Text};
 
 pub type StyleRef = Option<Id>;
 
@@ -9,3 +9,117 @@ pub struct Style {
     pub id: Id,
     pub properties: HashMap<String, String>,
 }
+
+/// A color as the source gives it: a hexadecimal value, or a name such as
+/// `lightblue`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    /// Red, green, blue and alpha, from `#rgb`, `#rrggbb` or `#rrggbbaa`
+    Rgba([u8; 4]),
+    Named(Text),
+}
+
+impl Color {
+    /// The color `text` spells, with or without its leading `#`; anything
+    /// but a hexadecimal value or a plain name is no color
+    pub fn parse(text: &str) -> Option<Color> {
+        let text: &str = text.trim().trim_start_matches('#');
+        let digits: Vec<u8> = text
+            .chars()
+            .map(|c: char| c.to_digit(16).map(|digit: u32| digit as u8))
+            .collect::<Option<Vec<u8>>>()
+            .unwrap_or_default();
+        let channels: Option<Vec<u8>> = match digits.len() {
+            3 => Some(digits.iter().map(|digit: &u8| digit * 17).collect()),
+            6 | 8 => Some(
+                digits
+                    .chunks(2)
+                    .map(|pair: &[u8]| pair[0] * 16 + pair[1])
+                    .collect(),
+            ),
+            _ => None,
+        };
+        match channels {
+            Some(channels) => {
+                let alpha: u8 = channels.get(3).copied().unwrap_or(255);
+                Some(Color::Rgba([channels[0], channels[1], channels[2], alpha]))
+            }
+            None if !text.is_empty() && text.chars().all(|c: char| c.is_ascii_alphabetic()) => {
+                Some(Color::Named(text.into()))
+            }
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    /// The color as CSS writes it: `#rrggbb`, `#rrggbbaa` when it is not
+    /// opaque, or its name
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgba([red, green, blue, 255]) => {
+                write!(f, "#{:02x}{:02x}{:02x}", red, green, blue)
+            }
+            Color::Rgba([red, green, blue, alpha]) => {
+                write!(f, "#{:02x}{:02x}{:02x}{:02x}", red, green, blue, alpha)
+            }
+            Color::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// The colors an element gives itself, over those of the style it references
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Colors {
+    /// Background of a node; with `gradient`, where its gradient starts
+    pub fill: Option<Color>,
+    /// Where the background's gradient ends
+    pub gradient: Option<Color>,
+    /// Border of a node, or line of an edge
+    pub stroke: Option<Color>,
+    pub text: Option<Color>,
+}
+
+impl Colors {
+    pub fn is_empty(&self) -> bool {
+        *self == Colors::default()
+    }
+
+    /// The colors set, as the style properties they stand for
+    pub fn properties(&self) -> Vec<(&'static str, String)> {
+        [
+            ("fill", &self.fill),
+            ("gradient", &self.gradient),
+            ("stroke", &self.stroke),
+            ("color", &self.text),
+        ]
+        .into_iter()
+        .filter_map(|(key, color): (&str, &Option<Color>)| Some((key, color.as_ref()?.to_string())))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parses_hexadecimal_and_named_colors() {
+        assert_eq!(Color::parse("#FF0000"), Some(Color::Rgba([255, 0, 0, 255])));
+        assert_eq!(Color::parse("ccc"), Some(Color::Rgba([204, 204, 204, 255])));
+        assert_eq!(
+            Color::parse("#00ff0080"),
+            Some(Color::Rgba([0, 255, 0, 128]))
+        );
+        assert_eq!(
+            Color::parse("#LightBlue"),
+            Some(Color::Named("LightBlue".into()))
+        );
+        assert_eq!(Color::parse("#12"), None);
+        assert_eq!(Color::parse("red;"), None);
+        assert_eq!(Color::Rgba([255, 0, 0, 255]).to_string(), "#ff0000");
+        assert_eq!(Color::Rgba([0, 255, 0, 128]).to_string(), "#00ff0080");
+    }
+}
@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+/// Data keys an element's link is read from, first match wins: an explicit
+/// `href`, `link` or `url`, then the `file` a source-derived element came from
+pub const LINK_KEYS: [&str; 4] = ["href", "link", "url", "file"];
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     String(String),
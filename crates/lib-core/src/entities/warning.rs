@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// Something a reader dropped or guessed at without failing, such as an
+/// ignored directive or a node only a relation mentions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    /// Notation of the reader, as in `plantuml`
+    pub source: String,
+    pub message: String,
+    /// 1-based, when the reader knows where it happened
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "[{}:{}] Warning: {}", self.source, line, self.message),
+            None => write!(f, "[{}] Warning: {}", self.source, self.message),
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::entities::{
+    edge::{Edge, EdgeDirection, EdgeKind, EdgeLayout},
+    graph::{Graph, GraphKind, LayoutDirection, Legend, Metadata},
+    group::{Group, GroupKind},
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{DeploymentKind, Node, NodeKind},
+    style::Style,
+    value::Value,
+};
+
+/// Most nodes a generated graph holds, edges being at most twice as many
+const MAX_NODES: usize = 8;
+const MAX_GROUPS: usize = 3;
+const MAX_STYLES: usize = 2;
+
+/// Builds a graph from a seed, for property tests that do not run under a
+/// fuzzer. The same seed always gives the same graph.
+pub fn sample(seed: u64) -> Graph {
+    let mut state: u64 = seed;
+    let bytes: Vec<u8> = (0..512)
+        .flat_map(|_| splitmix(&mut state).to_le_bytes())
+        .collect();
+    Graph::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap_or_default()
+}
+
+fn splitmix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z: u64 = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Graphs as a reader would produce them: ids are unique, relations and
+/// parents only name elements of the graph, and nothing is laid out yet
+impl<'a> Arbitrary<'a> for Graph {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut taken: HashSet<Id> = HashSet::new();
+        let mut unique = |u: &mut Unstructured<'a>, id: Id| -> Result<Id> {
+            let mut id: Id = id;
+            while taken.contains(&id) {
+                id = Id::from(format!("{}_{}", id, u.int_in_range(0..=9)?));
+            }
+            taken.insert(id.clone());
+            Ok(id)
+        };
+
+        let mut styles: Vec<Style> = Vec::new();
+        for _ in 0..u.int_in_range(0..=MAX_STYLES)? {
+            let id: Id = identifier(u)?;
+            styles.push(Style {
+                id: unique(u, id)?,
+                properties: map(u, 3, text)?,
+            });
+        }
+        let style_ids: Vec<Id> = styles
+            .iter()
+            .map(|style: &Style| style.id.clone())
+            .collect();
+        let style_of = |u: &mut Unstructured<'a>| -> Result<Option<Id>> {
+            match style_ids.is_empty() || u.arbitrary::<bool>()? {
+                true => Ok(None),
+                false => Ok(Some(u.choose(&style_ids)?.clone())),
+            }
+        };
+
+        let mut groups: Vec<Group> = Vec::new();
+        for _ in 0..u.int_in_range(0..=MAX_GROUPS)? {
+            let mut group: Group = Group::arbitrary(u)?;
+            group.id = unique(u, group.id)?;
+            // Only earlier groups can hold this one, so nesting never loops
+            if !groups.is_empty() && u.arbitrary::<bool>()? {
+                group.parent = Some(u.choose(&groups)?.id.clone());
+            }
+            groups.push(group);
+        }
+        let group_ids: Vec<Id> = groups
+            .iter()
+            .map(|group: &Group| group.id.clone())
+            .collect();
+
+        let mut nodes: Vec<Node> = Vec::new();
+        for _ in 0..u.int_in_range(0..=MAX_NODES)? {
+            let mut node: Node = Node::arbitrary(u)?;
+            node.id = unique(u, node.id)?;
+            node.style = style_of(u)?;
+            if !group_ids.is_empty() && u.arbitrary::<bool>()? {
+                node.parent = Some(u.choose(&group_ids)?.clone());
+            }
+            nodes.push(node);
+        }
+        let node_ids: Vec<Id> = nodes.iter().map(|node: &Node| node.id.clone()).collect();
+
+        let mut edges: Vec<Edge> = Vec::new();
+        if !node_ids.is_empty() {
+            for _ in 0..u.int_in_range(0..=MAX_NODES * 2)? {
+                let mut edge: Edge = Edge::arbitrary(u)?;
+                edge.id = unique(u, edge.id)?;
+                edge.from = u.choose(&node_ids)?.clone();
+                edge.to = u.choose(&node_ids)?.clone();
+                edge.style = style_of(u)?;
+                edges.push(edge);
+            }
+        }
+
+        groups.iter_mut().for_each(|group: &mut Group| {
+            group.children = nodes
+                .iter()
+                .filter(|node: &&Node| node.parent.as_ref() == Some(&group.id))
+                .map(|node: &Node| node.id.clone())
+                .collect();
+        });
+
+        Ok(Graph {
+            id: identifier(u)?,
+            kind: GraphKind::arbitrary(u)?,
+            direction: LayoutDirection::arbitrary(u)?,
+            metadata: Metadata::arbitrary(u)?,
+            nodes: keyed(nodes, |node: &Node| node.id.clone()),
+            edges: keyed(edges, |edge: &Edge| edge.id.clone()),
+            groups: keyed(groups, |group: &Group| group.id.clone()),
+            styles: keyed(styles, |style: &Style| style.id.clone()),
+            fragments: HashMap::new(),
+            activations: Vec::new(),
+            warnings: Vec::new(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Metadata {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            title: optional(u, text)?,
+            description: optional(u, text)?,
+            properties: map(u, 2, text)?,
+            legend: optional(u, |u: &mut Unstructured<'a>| {
+                Ok(Legend {
+                    text: text(u)?,
+                    ..Legend::default()
+                })
+            })?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Node {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            id: identifier(u)?,
+            kind: NodeKind::arbitrary(u)?,
+            label: optional(u, text)?.map(Text::from),
+            members: (0..u.int_in_range(0..=4)?)
+                .map(|_| Member::arbitrary(u))
+                .collect::<Result<Vec<Member>>>()?,
+            data: map(u, 2, |u: &mut Unstructured<'a>| value(u, 1))?,
+            ..Self::default()
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Edge {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            id: identifier(u)?,
+            from: identifier(u)?,
+            to: identifier(u)?,
+            directed: u.arbitrary()?,
+            kind: EdgeKind::arbitrary(u)?,
+            label: optional(u, text)?.map(Text::from),
+            layout: EdgeLayout {
+                direction: optional(u, EdgeDirection::arbitrary)?,
+                length: optional(u, |u: &mut Unstructured<'a>| u.int_in_range(1..=3))?,
+                hidden: u.ratio(1, 8)?,
+                labels: Vec::new(),
+            },
+            ..Self::default()
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Group {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            id: identifier(u)?,
+            kind: match u.int_in_range(0..=2)? {
+                0 => GroupKind::Package,
+                1 => GroupKind::Boundary,
+                _ => GroupKind::Deployment(*u.choose(&DeploymentKind::ALL)?),
+            },
+            label: optional(u, text)?.map(Text::from),
+            ..Self::default()
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Member {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            name: identifier(u)?.to_string(),
+            kind: MemberKind::arbitrary(u)?,
+            type_name: optional(u, |u: &mut Unstructured<'a>| Ok(identifier(u)?.to_string()))?,
+            data: HashMap::new(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Value {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        value(u, 2)
+    }
+}
+
+impl<'a> Arbitrary<'a> for GraphKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(u.choose(&[
+            Self::Generic,
+            Self::Class,
+            Self::EntityRelationship,
+            Self::Sequence,
+            Self::Network,
+            Self::MindMap,
+            Self::Wbs,
+            Self::Wireframe,
+            Self::State,
+            Self::Flowchart,
+            Self::UseCase,
+        ])?
+        .clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for LayoutDirection {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[
+            Self::TopToBottom,
+            Self::LeftToRight,
+            Self::BottomToTop,
+            Self::RightToLeft,
+        ])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for NodeKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=9)? {
+            0 => Ok(Self::Entity),
+            1 => Ok(Self::Interface),
+            2 => Ok(Self::Actor),
+            3 => Ok(Self::Component),
+            4 => Ok(Self::Database),
+            5 => Ok(Self::Enum),
+            6 => Ok(Self::Group),
+            7 => Ok(Self::Annotation),
+            8 => Ok(Self::Deployment(*u.choose(&DeploymentKind::ALL)?)),
+            // Custom kinds never spell a typed one, which reads back typed
+            _ => {
+                let kind: String = identifier(u)?.to_lowercase();
+                Ok(match DeploymentKind::from_keyword(&kind) {
+                    Some(_) => Self::Custom(format!("{}_", kind)),
+                    None => Self::Custom(kind),
+                })
+            }
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for EdgeKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=8)? {
+            0 => Ok(Self::Association),
+            1 => Ok(Self::Dependency),
+            2 => Ok(Self::Inheritance),
+            3 => Ok(Self::Realization),
+            4 => Ok(Self::Aggregation),
+            5 => Ok(Self::Composition),
+            6 => Ok(Self::Flow),
+            7 => Ok(Self::Undirected),
+            _ => Ok(Self::Custom(identifier(u)?.to_lowercase())),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for MemberKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(u.choose(&[Self::Field, Self::Method, Self::EnumValue])?
+            .clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for EdgeDirection {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[Self::Up, Self::Down, Self::Left, Self::Right])?)
+    }
+}
+
+/// A name every notation accepts unquoted, as in `Order_2`
+fn identifier(u: &mut Unstructured) -> Result<Id> {
+    const FIRST: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    const REST: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_";
+
+    let mut name: String = String::from(*u.choose(FIRST)? as char);
+    for _ in 0..u.int_in_range(0..=7)? {
+        name.push(*u.choose(REST)? as char);
+    }
+    Ok(Id::from(name))
+}
+
+/// A few words, for labels and titles
+fn text(u: &mut Unstructured) -> Result<String> {
+    let words: Vec<String> = (0..u.int_in_range(1..=3)?)
+        .map(|_| Ok(identifier(u)?.to_string()))
+        .collect::<Result<Vec<String>>>()?;
+    Ok(words.join(" "))
+}
+
+/// Finite numbers only, and nesting stops after `depth` levels
+fn value(u: &mut Unstructured, depth: usize) -> Result<Value> {
+    match u.int_in_range(0..=if depth == 0 { 2 } else { 4 })? {
+        0 => Ok(Value::String(text(u)?)),
+        1 => Ok(Value::Number(f64::from(
+            u.int_in_range::<i32>(-1000..=1000)?,
+        ))),
+        2 => Ok(Value::Bool(u.arbitrary()?)),
+        3 => Ok(Value::List(
+            (0..u.int_in_range(0..=3)?)
+                .map(|_| value(u, depth - 1))
+                .collect::<Result<Vec<Value>>>()?,
+        )),
+        _ => Ok(Value::Object(map(u, 3, |u: &mut Unstructured| {
+            value(u, depth - 1)
+        })?)),
+    }
+}
+
+fn optional<'a, T>(
+    u: &mut Unstructured<'a>,
+    item: impl FnOnce(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<Option<T>> {
+    match u.arbitrary::<bool>()? {
+        true => Ok(Some(item(u)?)),
+        false => Ok(None),
+    }
+}
+
+/// Up to `max` entries keyed by identifier
+fn map<'a, K: From<Id> + Eq + Hash, T>(
+    u: &mut Unstructured<'a>,
+    max: usize,
+    item: impl Fn(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<HashMap<K, T>> {
+    (0..u.int_in_range(0..=max)?)
+        .map(|_| Ok((K::from(identifier(u)?), item(u)?)))
+        .collect()
+}
+
+fn keyed<T>(items: Vec<T>, id: impl Fn(&T) -> Id) -> HashMap<Id, T> {
+    items.into_iter().map(|item: T| (id(&item), item)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_generated_graphs_only_reference_their_own_elements() {
+        (0..64).map(sample).for_each(|graph: Graph| {
+            graph.edges.values().for_each(|edge: &Edge| {
+                assert!(graph.nodes.contains_key(&edge.from));
+                assert!(graph.nodes.contains_key(&edge.to));
+            });
+            graph.nodes.values().for_each(|node: &Node| {
+                assert!(
+                    node.parent
+                        .iter()
+                        .all(|id: &Id| graph.groups.contains_key(id))
+                );
+                assert!(
+                    node.style
+                        .iter()
+                        .all(|id: &Id| graph.styles.contains_key(id))
+                );
+            });
+            graph.groups.values().for_each(|group: &Group| {
+                assert!(
+                    group
+                        .parent
+                        .iter()
+                        .all(|id: &Id| graph.groups.contains_key(id))
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn test_samples_are_reproducible_and_varied() {
+        let sizes: HashSet<usize> = (0..64).map(|seed: u64| sample(seed).nodes.len()).collect();
+
+        assert_eq!(sample(7), sample(7));
+        assert!(sizes.len() > 1);
+    }
+}
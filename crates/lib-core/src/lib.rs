@@ -1,3 +1,12 @@
 pub mod adapters;
+pub mod builder;
 pub mod entities;
+/// `Arbitrary` graphs for fuzzers and property tests
+#[cfg(any(test, feature = "arbitrary"))]
+pub mod fuzzing;
+pub mod nesting;
+pub mod templates;
+/// Structural graph comparison for golden tests in downstream crates
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod use_cases;
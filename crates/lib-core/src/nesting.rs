@@ -0,0 +1,136 @@
+/// Line and column of the first XML element nested deeper than `max_depth`,
+/// if any. XML parsers recurse once per element, so readers check this
+/// before handing them a source.
+pub fn deepest_element(input: &str, max_depth: usize) -> Option<(usize, usize)> {
+    let mut depth: usize = 0;
+    let mut offset: usize = 0;
+    while let Some(start) = input[offset..].find('<').map(|at: usize| offset + at) {
+        let rest: &str = &input[start..];
+        let (closer, nesting): (&str, isize) = if rest.starts_with("<!--") {
+            ("-->", 0)
+        } else if rest.starts_with("<![CDATA[") {
+            ("]]>", 0)
+        } else if rest.starts_with("<?") {
+            ("?>", 0)
+        } else if rest.starts_with("</") {
+            (">", -1)
+        } else if rest.starts_with("<!") {
+            (">", 0)
+        } else {
+            (">", 1)
+        };
+        // Unterminated markup is left for the parser to report
+        let end: usize = tag_end(rest, closer)?;
+
+        match nesting {
+            1 if rest[..end].ends_with('/') => {}
+            1 if depth == max_depth => return Some(line_col(input, start)),
+            1 => depth += 1,
+            -1 => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        offset = start + end + closer.len();
+    }
+    None
+}
+
+/// Line and column of the first `{` nested deeper than `max_depth`, if any,
+/// in a C-like source with `//` and `/* */` comments and strings between any
+/// of `quotes`. Parsers of such sources recurse once per block.
+pub fn deepest_brace(input: &str, quotes: &[char], max_depth: usize) -> Option<(usize, usize)> {
+    let mut depth: usize = 0;
+    let mut offset: usize = 0;
+    while let Some(c) = input[offset..].chars().next() {
+        let rest: &str = &input[offset..];
+        offset += match c {
+            '/' if rest.starts_with("//") => rest.find('\n').unwrap_or(rest.len()),
+            '/' if rest.starts_with("/*") => {
+                rest.find("*/").map_or(rest.len(), |end: usize| end + 2)
+            }
+            quote if quotes.contains(&quote) => {
+                let mut escaped: bool = false;
+                rest[1..]
+                    .find(|c: char| {
+                        let closes: bool = c == quote && !escaped;
+                        escaped = c == '\\' && !escaped;
+                        closes
+                    })
+                    .map_or(rest.len(), |end: usize| end + 2)
+            }
+            '{' if depth == max_depth => return Some(line_col(input, offset)),
+            '{' => {
+                depth += 1;
+                1
+            }
+            '}' => {
+                depth = depth.saturating_sub(1);
+                1
+            }
+            c => c.len_utf8(),
+        };
+    }
+    None
+}
+
+fn line_col(input: &str, offset: usize) -> (usize, usize) {
+    let line_start: usize = input[..offset].rfind('\n').map_or(0, |n: usize| n + 1);
+    (
+        input[..offset].matches('\n').count() + 1,
+        offset - line_start + 1,
+    )
+}
+
+/// Offset of `closer` ending the markup `tag` starts with, past any quoted
+/// attribute values
+fn tag_end(tag: &str, closer: &str) -> Option<usize> {
+    if closer != ">" {
+        return tag.find(closer);
+    }
+    let mut quote: Option<char> = None;
+    tag.char_indices()
+        .find(|&(_, c): &(usize, char)| match quote {
+            Some(open) if c == open => {
+                quote = None;
+                false
+            }
+            Some(_) => false,
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                false
+            }
+            None => c == '>',
+        })
+        .map(|(at, _): (usize, char)| at)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::nesting::{deepest_brace, deepest_element};
+
+    #[test]
+    fn test_finds_the_first_element_past_the_limit() {
+        let input: &str = "<a>\n  <b><c/>\n    <c></c>\n  </b>\n</a>";
+
+        assert_eq!(deepest_element(input, 3), None);
+        assert_eq!(deepest_element(input, 2), Some((3, 5)));
+        assert_eq!(deepest_element(input, 1), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_skips_comments_cdata_and_quoted_brackets() {
+        let input: &str =
+            "<?xml version=\"1.0\"?>\n<a x=\"<b>\"><!-- <b><b> --><![CDATA[<b><b>]]></a>";
+
+        assert_eq!(deepest_element(input, 1), None);
+    }
+
+    #[test]
+    fn test_finds_the_first_brace_past_the_limit() {
+        let input: &str = "a {\n  b { \"{\" }\n  c { // {\n    d { /* { */ }\n  }\n}";
+
+        assert_eq!(deepest_brace(input, &['"'], 3), None);
+        assert_eq!(deepest_brace(input, &['"'], 2), Some((4, 7)));
+    }
+}
@@ -0,0 +1,280 @@
+//! Starting points for new diagrams. Each template is a function taking the
+//! names to fill in; `Template` lists them with sample names so a tool can
+//! offer "new diagram from template" without asking for anything first.
+
+use crate::{
+    builder::GraphBuilder,
+    entities::{
+        edge::EdgeKind,
+        graph::{Graph, GraphKind, LayoutDirection},
+        id::{Id, IdAllocator},
+        node::NodeKind,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Template {
+    LayeredArchitecture,
+    HexagonalArchitecture,
+    CrudSequence,
+}
+
+impl Template {
+    pub const ALL: [Template; 3] = [
+        Template::LayeredArchitecture,
+        Template::HexagonalArchitecture,
+        Template::CrudSequence,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LayeredArchitecture => "layered-architecture",
+            Self::HexagonalArchitecture => "hexagonal-architecture",
+            Self::CrudSequence => "crud-sequence",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::LayeredArchitecture => "Layers depending on the one below",
+            Self::HexagonalArchitecture => "A domain core with ports and adapters",
+            Self::CrudSequence => "Create, read, update and delete messages",
+        }
+    }
+
+    /// The template filled in with placeholder names
+    pub fn sample(&self) -> Graph {
+        match self {
+            Self::LayeredArchitecture => {
+                layered_architecture(&["Presentation", "Application", "Domain", "Infrastructure"])
+            }
+            Self::HexagonalArchitecture => {
+                hexagonal_architecture("Domain", &["Web", "CLI"], &["Database", "Mail"])
+            }
+            Self::CrudSequence => crud_sequence("User", "Item"),
+        }
+    }
+}
+
+/// One component per layer, top to bottom, each depending on the next
+pub fn layered_architecture(layers: &[&str]) -> Graph {
+    let mut allocator: IdAllocator = IdAllocator::new();
+    let ids: Vec<Id> = layers
+        .iter()
+        .map(|layer: &&str| allocator.for_name(layer))
+        .collect();
+
+    let builder: GraphBuilder = layers.iter().zip(&ids).fold(
+        GraphBuilder::new(GraphKind::Generic).title("Layered architecture"),
+        |builder: GraphBuilder, (layer, id): (&&str, &Id)| {
+            builder.node(id, NodeKind::Component, layer)
+        },
+    );
+    ids.windows(2)
+        .fold(builder, |builder: GraphBuilder, pair: &[Id]| {
+            builder.edge(&pair[0], &pair[1], EdgeKind::Dependency, None)
+        })
+        .build()
+}
+
+/// A domain core in the middle. Driving adapters call it through ports it
+/// implements; it calls driven adapters through ports they implement.
+pub fn hexagonal_architecture(core: &str, driving: &[&str], driven: &[&str]) -> Graph {
+    let mut allocator: IdAllocator =
+        IdAllocator::with_taken(&[Id::from("application"), Id::from("adapters")]);
+    let core_id: Id = allocator.for_name(core);
+    let mut application: Vec<Id> = vec![core_id.clone()];
+    let mut adapters: Vec<Id> = Vec::new();
+
+    let mut builder: GraphBuilder = GraphBuilder::new(GraphKind::Generic)
+        .title("Hexagonal architecture")
+        .direction(LayoutDirection::LeftToRight)
+        .node(&core_id, NodeKind::Component, core);
+
+    for (adapter, inbound) in driving
+        .iter()
+        .map(|adapter: &&str| (*adapter, true))
+        .chain(driven.iter().map(|adapter: &&str| (*adapter, false)))
+    {
+        let (adapter_label, port_label): (String, String) =
+            (format!("{} adapter", adapter), format!("{} port", adapter));
+        let adapter_id: Id = allocator.for_name(&adapter_label);
+        let port_id: Id = allocator.for_name(&port_label);
+        builder = builder
+            .node(&adapter_id, NodeKind::Component, &adapter_label)
+            .node(&port_id, NodeKind::Interface, &port_label);
+        builder = if inbound {
+            builder
+                .edge(&adapter_id, &port_id, EdgeKind::Dependency, None)
+                .edge(&core_id, &port_id, EdgeKind::Realization, None)
+        } else {
+            builder
+                .edge(&core_id, &port_id, EdgeKind::Dependency, None)
+                .edge(&adapter_id, &port_id, EdgeKind::Realization, None)
+        };
+        application.push(port_id);
+        adapters.push(adapter_id);
+    }
+
+    builder
+        .group("application", "Application", &borrowed(&application))
+        .group("adapters", "Adapters", &borrowed(&adapters))
+        .build()
+}
+
+/// An actor creating, reading, updating and deleting a resource through a
+/// service backed by a database, each request answered in turn
+pub fn crud_sequence(actor: &str, resource: &str) -> Graph {
+    let resource_name: String = resource.to_lowercase();
+    let operations: [(&str, &str, &str); 4] = [
+        ("Create", "INSERT", "created"),
+        ("Read", "SELECT", "found"),
+        ("Update", "UPDATE", "updated"),
+        ("Delete", "DELETE", "deleted"),
+    ];
+
+    operations
+        .iter()
+        .fold(
+            GraphBuilder::new(GraphKind::Sequence)
+                .title(&format!("{} CRUD", resource))
+                .node("actor", NodeKind::Actor, actor)
+                .node(
+                    "service",
+                    NodeKind::Component,
+                    &format!("{} service", resource),
+                )
+                .node("database", NodeKind::Database, "Database"),
+            |builder: GraphBuilder, (verb, statement, outcome): &(&str, &str, &str)| {
+                builder
+                    .edge(
+                        "actor",
+                        "service",
+                        EdgeKind::Flow,
+                        Some(&format!("{} {}", verb, resource_name)),
+                    )
+                    .edge(
+                        "service",
+                        "database",
+                        EdgeKind::Flow,
+                        Some(&format!("{} {}", statement, resource_name)),
+                    )
+                    .edge(
+                        "service",
+                        "actor",
+                        EdgeKind::Flow,
+                        Some(&format!("{} {}", resource_name, outcome)),
+                    )
+            },
+        )
+        .build()
+}
+
+fn borrowed(ids: &[Id]) -> Vec<&str> {
+    ids.iter().map(Id::as_str).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::entities::{edge::Edge, id::Text, value::Value};
+
+    fn relations(graph: &Graph) -> Vec<(String, String, EdgeKind)> {
+        let mut relations: Vec<(String, String, EdgeKind)> = graph
+            .edges
+            .values()
+            .map(|edge: &Edge| {
+                (
+                    edge.from.to_string(),
+                    edge.to.to_string(),
+                    edge.kind.clone(),
+                )
+            })
+            .collect();
+        relations.sort_by(
+            |a: &(String, String, EdgeKind), b: &(String, String, EdgeKind)| {
+                (&a.0, &a.1).cmp(&(&b.0, &b.1))
+            },
+        );
+        relations
+    }
+
+    #[test]
+    fn test_chains_layers_downwards() {
+        let graph: Graph = layered_architecture(&["Web UI", "Domain", "Storage"]);
+
+        assert_eq!(graph.nodes["web_ui"].label.as_deref(), Some("Web UI"));
+        assert_eq!(
+            relations(&graph),
+            vec![
+                ("domain".into(), "storage".into(), EdgeKind::Dependency),
+                ("web_ui".into(), "domain".into(), EdgeKind::Dependency),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_points_dependencies_at_the_core_s_ports() {
+        let graph: Graph = hexagonal_architecture("Billing", &["REST"], &["Ledger"]);
+
+        assert_eq!(
+            relations(&graph),
+            vec![
+                ("billing".into(), "ledger_port".into(), EdgeKind::Dependency),
+                ("billing".into(), "rest_port".into(), EdgeKind::Realization),
+                (
+                    "ledger_adapter".into(),
+                    "ledger_port".into(),
+                    EdgeKind::Realization
+                ),
+                (
+                    "rest_adapter".into(),
+                    "rest_port".into(),
+                    EdgeKind::Dependency
+                ),
+            ]
+        );
+        assert_eq!(
+            graph.groups["application"].children,
+            vec!["billing", "rest_port", "ledger_port"]
+        );
+        assert_eq!(
+            graph.nodes["rest_adapter"].parent.as_deref(),
+            Some("adapters")
+        );
+    }
+
+    #[test]
+    fn test_numbers_crud_messages_in_order() {
+        let graph: Graph = crud_sequence("Clerk", "Order");
+        let mut messages: Vec<(usize, Text)> = graph
+            .edges
+            .values()
+            .map(|edge: &Edge| match edge.data["sequence"] {
+                Value::Number(sequence) => (sequence as usize, edge.label.clone().unwrap()),
+                _ => panic!("{} has no sequence number", edge.id),
+            })
+            .collect();
+        messages.sort();
+
+        assert_eq!(graph.kind, GraphKind::Sequence);
+        assert_eq!(messages.len(), 12);
+        assert_eq!(
+            messages[..3],
+            [
+                (1, Text::from("Create order")),
+                (2, Text::from("INSERT order")),
+                (3, Text::from("order created")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_every_template_has_a_sample() {
+        Template::ALL.iter().for_each(|template: &Template| {
+            assert!(!template.sample().nodes.is_empty(), "{}", template.name());
+        });
+    }
+}
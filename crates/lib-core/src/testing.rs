@@ -0,0 +1,349 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    hash::Hash,
+};
+
+use crate::entities::{edge::Edge, graph::Graph, group::Group, node::Node, style::Style};
+
+/// One difference between an expected and an actual graph
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Only the actual graph has the element, as in "node `B`"
+    Added(String),
+    /// Only the expected graph has the element
+    Removed(String),
+    /// Both have the element but a field differs, as in "node `A` label"
+    Changed {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added(path) => write!(f, "+ {}", path),
+            Self::Removed(path) => write!(f, "- {}", path),
+            Self::Changed {
+                path,
+                expected,
+                actual,
+            } => write!(f, "~ {}: {} -> {}", path, expected, actual),
+        }
+    }
+}
+
+/// Compares two graphs element by element, matching nodes, edges, groups and
+/// styles by id. Changes come sorted by element so the output is stable.
+pub fn diff(expected: &Graph, actual: &Graph) -> Vec<Change> {
+    let mut changes: Vec<Change> = Vec::new();
+
+    field(&mut changes, "graph kind", &expected.kind, &actual.kind);
+    field(
+        &mut changes,
+        "graph direction",
+        &expected.direction,
+        &actual.direction,
+    );
+    field(
+        &mut changes,
+        "graph title",
+        &expected.metadata.title,
+        &actual.metadata.title,
+    );
+    field(
+        &mut changes,
+        "graph description",
+        &expected.metadata.description,
+        &actual.metadata.description,
+    );
+    field(
+        &mut changes,
+        "graph legend",
+        &expected.metadata.legend,
+        &actual.metadata.legend,
+    );
+    entries(
+        &mut changes,
+        "graph property",
+        &expected.metadata.properties,
+        &actual.metadata.properties,
+        field,
+    );
+    entries(&mut changes, "node", &expected.nodes, &actual.nodes, node);
+    entries(&mut changes, "edge", &expected.edges, &actual.edges, edge);
+    entries(
+        &mut changes,
+        "group",
+        &expected.groups,
+        &actual.groups,
+        group,
+    );
+    entries(
+        &mut changes,
+        "style",
+        &expected.styles,
+        &actual.styles,
+        style,
+    );
+    entries(
+        &mut changes,
+        "fragment",
+        &expected.fragments,
+        &actual.fragments,
+        field,
+    );
+    field(
+        &mut changes,
+        "graph activations",
+        &expected.activations,
+        &actual.activations,
+    );
+    field(
+        &mut changes,
+        "graph warnings",
+        &expected.warnings,
+        &actual.warnings,
+    );
+
+    changes
+}
+
+/// Panics with the structural diff when the graphs differ, for golden tests
+#[track_caller]
+pub fn assert_graph_eq(expected: &Graph, actual: &Graph) {
+    let changes: Vec<Change> = diff(expected, actual);
+    if !changes.is_empty() {
+        let lines: Vec<String> = changes.iter().map(Change::to_string).collect();
+        panic!(
+            "graphs differ (- expected, + actual):\n{}",
+            lines.join("\n")
+        );
+    }
+}
+
+fn node(changes: &mut Vec<Change>, path: &str, expected: &Node, actual: &Node) {
+    let at = |name: &str| -> String { format!("{} {}", path, name) };
+    field(changes, &at("kind"), &expected.kind, &actual.kind);
+    field(changes, &at("label"), &expected.label, &actual.label);
+    field(changes, &at("members"), &expected.members, &actual.members);
+    entries(changes, &at("data"), &expected.data, &actual.data, field);
+    field(changes, &at("style"), &expected.style, &actual.style);
+    field(changes, &at("colors"), &expected.colors, &actual.colors);
+    field(changes, &at("parent"), &expected.parent, &actual.parent);
+    field(
+        changes,
+        &at("geometry"),
+        &expected.geometry,
+        &actual.geometry,
+    );
+    field(
+        changes,
+        &at("label anchor"),
+        &expected.label_anchor,
+        &actual.label_anchor,
+    );
+    field(changes, &at("hidden"), &expected.hidden, &actual.hidden);
+    field(
+        changes,
+        &at("rich label"),
+        &expected.rich_label,
+        &actual.rich_label,
+    );
+    field(
+        changes,
+        &at("target edge"),
+        &expected.target_edge,
+        &actual.target_edge,
+    );
+}
+
+fn edge(changes: &mut Vec<Change>, path: &str, expected: &Edge, actual: &Edge) {
+    let at = |name: &str| -> String { format!("{} {}", path, name) };
+    field(changes, &at("from"), &expected.from, &actual.from);
+    field(changes, &at("to"), &expected.to, &actual.to);
+    field(
+        changes,
+        &at("directed"),
+        &expected.directed,
+        &actual.directed,
+    );
+    field(changes, &at("kind"), &expected.kind, &actual.kind);
+    field(changes, &at("label"), &expected.label, &actual.label);
+    entries(changes, &at("data"), &expected.data, &actual.data, field);
+    field(changes, &at("style"), &expected.style, &actual.style);
+    field(changes, &at("colors"), &expected.colors, &actual.colors);
+    field(
+        changes,
+        &at("waypoints"),
+        &expected.waypoints,
+        &actual.waypoints,
+    );
+    field(changes, &at("layout"), &expected.layout, &actual.layout);
+    field(changes, &at("hidden"), &expected.hidden, &actual.hidden);
+    field(changes, &at("order"), &expected.order, &actual.order);
+    field(
+        changes,
+        &at("from cardinality"),
+        &expected.from_cardinality,
+        &actual.from_cardinality,
+    );
+    field(
+        changes,
+        &at("to cardinality"),
+        &expected.to_cardinality,
+        &actual.to_cardinality,
+    );
+    field(
+        changes,
+        &at("rich label"),
+        &expected.rich_label,
+        &actual.rich_label,
+    );
+}
+
+fn group(changes: &mut Vec<Change>, path: &str, expected: &Group, actual: &Group) {
+    let at = |name: &str| -> String { format!("{} {}", path, name) };
+    field(changes, &at("kind"), &expected.kind, &actual.kind);
+    field(changes, &at("label"), &expected.label, &actual.label);
+    field(
+        changes,
+        &at("children"),
+        &expected.children,
+        &actual.children,
+    );
+    field(changes, &at("parent"), &expected.parent, &actual.parent);
+    field(
+        changes,
+        &at("geometry"),
+        &expected.geometry,
+        &actual.geometry,
+    );
+    field(changes, &at("hidden"), &expected.hidden, &actual.hidden);
+}
+
+fn style(changes: &mut Vec<Change>, path: &str, expected: &Style, actual: &Style) {
+    entries(
+        changes,
+        &format!("{} property", path),
+        &expected.properties,
+        &actual.properties,
+        field,
+    );
+}
+
+/// Matches two maps by key, reporting missing and extra entries and
+/// comparing the shared ones with `compare`
+fn entries<K, V>(
+    changes: &mut Vec<Change>,
+    what: &str,
+    expected: &HashMap<K, V>,
+    actual: &HashMap<K, V>,
+    compare: impl Fn(&mut Vec<Change>, &str, &V, &V),
+) where
+    K: Ord + Hash + fmt::Display,
+{
+    let keys: BTreeSet<&K> = expected.keys().chain(actual.keys()).collect();
+    keys.into_iter().for_each(|key: &K| {
+        let path: String = format!("{} `{}`", what, key);
+        match (expected.get(key), actual.get(key)) {
+            (Some(a), Some(b)) => compare(changes, &path, a, b),
+            (Some(_), None) => changes.push(Change::Removed(path)),
+            (None, Some(_)) => changes.push(Change::Added(path)),
+            (None, None) => {}
+        }
+    });
+}
+
+fn field<T: fmt::Debug + PartialEq + ?Sized>(
+    changes: &mut Vec<Change>,
+    path: &str,
+    expected: &T,
+    actual: &T,
+) {
+    if expected != actual {
+        changes.push(Change::Changed {
+            path: path.to_string(),
+            expected: format!("{:?}", expected),
+            actual: format!("{:?}", actual),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::entities::{
+        id::{Id, Text},
+        node::NodeKind,
+    };
+
+    fn graph(nodes: &[(&str, Option<&str>)], edges: &[(&str, &str, &str)]) -> Graph {
+        Graph {
+            nodes: nodes
+                .iter()
+                .map(|(id, label): &(&str, Option<&str>)| {
+                    let node: Node = Node {
+                        id: Id::new(id),
+                        label: label.map(Text::from),
+                        ..Node::default()
+                    };
+                    (node.id.clone(), node)
+                })
+                .collect(),
+            edges: edges
+                .iter()
+                .map(|(id, from, to): &(&str, &str, &str)| {
+                    let edge: Edge = Edge {
+                        id: Id::new(id),
+                        from: Id::new(from),
+                        to: Id::new(to),
+                        ..Edge::default()
+                    };
+                    (edge.id.clone(), edge)
+                })
+                .collect(),
+            ..Graph::default()
+        }
+    }
+
+    #[test]
+    fn test_reports_added_removed_and_changed_elements() {
+        let expected: Graph = graph(&[("A", Some("A")), ("B", None)], &[("e1", "A", "B")]);
+        let mut actual: Graph = graph(&[("A", Some("Alpha")), ("C", None)], &[("e1", "A", "C")]);
+        actual.nodes.get_mut("C").unwrap().kind = NodeKind::Interface;
+
+        let lines: Vec<String> = diff(&expected, &actual)
+            .iter()
+            .map(Change::to_string)
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "~ node `A` label: Some(\"A\") -> Some(\"Alpha\")",
+                "- node `B`",
+                "+ node `C`",
+                "~ edge `e1` to: \"B\" -> \"C\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_equal_graphs_have_no_changes() {
+        let expected: Graph = graph(&[("A", None)], &[]);
+
+        assert_eq!(diff(&expected, &expected.clone()), vec![]);
+        assert_graph_eq(&expected, &expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "graphs differ (- expected, + actual):\n- node `A`")]
+    fn test_assert_panics_with_the_diff() {
+        assert_graph_eq(&graph(&[("A", None)], &[]), &Graph::default());
+    }
+}
@@ -1 +1,8 @@
+pub mod compose_graphs;
+pub mod emit_graph;
+pub mod layout_graph;
+pub mod load_document;
 pub mod load_graph;
+pub mod load_graphs;
+pub mod sanitize_graph;
+pub mod split_graph;
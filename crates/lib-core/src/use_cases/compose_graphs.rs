@@ -0,0 +1,353 @@
+use std::{collections::HashMap, sync::Arc};
+
+use rayon::prelude::*;
+
+use crate::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::{
+        edge::Edge,
+        graph::Graph,
+        group::Group,
+        id::{Id, IdAllocator},
+        node::{Node, NodeKind},
+        resolved::{self, ResolvedDiagram},
+    },
+};
+
+/// A source to compose, named after its file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NamedSource<'a> {
+    pub name: &'a str,
+    pub source: &'a str,
+}
+
+/// One diagram made of several sources sharing a namespace
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Composition {
+    pub graph: Graph,
+    /// Edge ends naming no node or group in any source
+    pub unresolved: Vec<Unresolved>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unresolved {
+    /// Name of the source the edge comes from
+    pub source: String,
+    pub edge: Id,
+    pub id: Id,
+}
+
+pub trait ComposeGraphsUseCase {
+    /// Reads every source and merges them, failing on the first source that
+    /// does not parse, reported under the source's name
+    fn execute(&self, sources: &[NamedSource]) -> Result<Composition, GraphGatewayError>;
+}
+
+/// Places each source under a group named after its title, or its file name
+/// when it has none. Element ids are shared: an edge may point at a node
+/// another source declares, and a node several sources mention is kept once,
+/// from the source describing it in most detail. Groups and edges whose ids
+/// clash across sources get a numeric suffix, as in `e1_2`.
+pub struct ComposeGraphs<T: GraphGateway + ?Sized> {
+    graph_gateway: Arc<T>,
+}
+
+impl<T: GraphGateway + ?Sized> ComposeGraphs<T> {
+    pub fn new(graph_gateway: Arc<T>) -> Self {
+        Self { graph_gateway }
+    }
+}
+
+impl<T: GraphGateway + Sync + Send + ?Sized + 'static> ComposeGraphsUseCase for ComposeGraphs<T> {
+    fn execute(&self, sources: &[NamedSource]) -> Result<Composition, GraphGatewayError> {
+        let graphs: Vec<Graph> = sources
+            .par_iter()
+            .map(|source: &NamedSource| {
+                futures_lite::future::block_on(
+                    self.graph_gateway.read_graph_from_raw_input(source.source),
+                )
+                .map_err(|error: GraphGatewayError| rename(error, source.name))
+            })
+            .collect::<Result<Vec<Graph>, GraphGatewayError>>()?;
+
+        let mut composed: Graph = Graph {
+            kind: graphs
+                .first()
+                .map(|graph: &Graph| graph.kind.clone())
+                .unwrap_or_default(),
+            direction: graphs
+                .first()
+                .map(|graph: &Graph| graph.direction)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        let mut origins: HashMap<Id, &str> = HashMap::new();
+        let (mut group_ids, mut edge_ids): (IdAllocator, IdAllocator) =
+            (IdAllocator::new(), IdAllocator::new());
+
+        for (source, mut graph) in sources.iter().zip(graphs) {
+            let cluster: Id =
+                group_ids.claim(graph.metadata.title.as_deref().unwrap_or(source.name));
+
+            let mut ids: Vec<Id> = graph.groups.keys().cloned().collect();
+            ids.sort();
+            let renamed: HashMap<Id, Id> = ids
+                .into_iter()
+                .filter_map(|id: Id| {
+                    let claimed: Id = group_ids.claim(&id);
+                    (claimed != id).then_some((id, claimed))
+                })
+                .collect();
+            rename_groups(&mut graph, &renamed);
+
+            let mut children: Vec<Id> = Vec::new();
+            for (id, mut group) in graph.groups {
+                if group.parent.is_none() {
+                    group.parent = Some(cluster.clone());
+                    children.push(id.clone());
+                }
+                composed.groups.insert(id, group);
+            }
+            for (id, mut node) in graph.nodes {
+                if node.parent.is_none() {
+                    node.parent = Some(cluster.clone());
+                }
+                let replaces: bool = composed
+                    .nodes
+                    .get(&id)
+                    .is_none_or(|known: &Node| detail(&node) > detail(known));
+                if replaces {
+                    let previous: Option<Id> = composed
+                        .nodes
+                        .get(&id)
+                        .and_then(|known: &Node| known.parent.clone());
+                    if let Some(group) =
+                        previous.and_then(|parent: Id| composed.groups.get_mut(&parent))
+                    {
+                        group.children.retain(|child: &Id| *child != id);
+                    }
+                    if node.parent.as_ref() == Some(&cluster) {
+                        children.push(id.clone());
+                    }
+                    composed.nodes.insert(id, node);
+                }
+            }
+            let mut edges: Vec<Edge> = graph.edges.into_values().collect();
+            edges.sort_by(|a: &Edge, b: &Edge| a.id.cmp(&b.id));
+            for mut edge in edges {
+                edge.id = edge_ids.claim(&edge.id);
+                origins.insert(edge.id.clone(), source.name);
+                composed.edges.insert(edge.id.clone(), edge);
+            }
+            composed.styles.extend(graph.styles);
+            composed.warnings.extend(graph.warnings);
+            composed.groups.insert(
+                cluster.clone(),
+                Group {
+                    id: cluster.clone(),
+                    label: Some(cluster),
+                    children,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let unresolved: Vec<Unresolved> = ResolvedDiagram::new(&composed)
+            .unresolved
+            .into_iter()
+            .map(|dangling: resolved::Unresolved| Unresolved {
+                source: origins[&dangling.edge].to_string(),
+                edge: dangling.edge,
+                id: dangling.id,
+            })
+            .collect();
+
+        Ok(Composition {
+            graph: composed,
+            unresolved,
+        })
+    }
+}
+
+fn rename(error: GraphGatewayError, name: &str) -> GraphGatewayError {
+    match error {
+        GraphGatewayError::Parse {
+            message,
+            line,
+            column,
+            ..
+        } => GraphGatewayError::Parse {
+            source: name.to_string(),
+            message,
+            line,
+            column,
+        },
+        GraphGatewayError::Semantic { message, .. } => GraphGatewayError::Semantic {
+            source: name.to_string(),
+            message,
+        },
+    }
+}
+
+/// Renames groups all at once, so a new name may be another group's old one
+fn rename_groups(graph: &mut Graph, renamed: &HashMap<Id, Id>) {
+    if renamed.is_empty() {
+        return;
+    }
+    let rename = |id: &mut Id| {
+        if let Some(to) = renamed.get(id) {
+            *id = to.clone();
+        }
+    };
+
+    graph.groups = std::mem::take(&mut graph.groups)
+        .into_values()
+        .map(|mut group: Group| {
+            rename(&mut group.id);
+            group.parent.iter_mut().for_each(rename);
+            group.children.iter_mut().for_each(rename);
+            (group.id.clone(), group)
+        })
+        .collect();
+    graph
+        .nodes
+        .values_mut()
+        .for_each(|node: &mut Node| node.parent.iter_mut().for_each(rename));
+    graph.edges.values_mut().for_each(|edge: &mut Edge| {
+        rename(&mut edge.from);
+        rename(&mut edge.to);
+    });
+}
+
+/// How much a source says about a node; a bare mention from a relation says
+/// the least
+fn detail(node: &Node) -> usize {
+    [
+        node.kind != NodeKind::default(),
+        node.label
+            .as_deref()
+            .is_some_and(|label: &str| label != node.id),
+        node.style.is_some(),
+    ]
+    .into_iter()
+    .filter(|known: &bool| *known)
+    .count()
+        + node.members.len()
+        + node.data.len()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        builder::GraphBuilder,
+        entities::{
+            edge::EdgeKind,
+            graph::{Graph, GraphKind},
+            id::Id,
+            member::{Member, MemberKind},
+            node::NodeKind,
+        },
+        use_cases::compose_graphs::{
+            ComposeGraphs, ComposeGraphsUseCase, Composition, NamedSource, Unresolved,
+        },
+    };
+
+    /// Reads `A->B` lines, marking `A` as an interface when a line is `A:`
+    /// and failing on anything else
+    struct ArrowGateway;
+
+    #[async_trait]
+    impl GraphGateway for ArrowGateway {
+        async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+            input
+                .lines()
+                .enumerate()
+                .try_fold(
+                    GraphBuilder::new(GraphKind::Class),
+                    |builder: GraphBuilder, (index, line): (usize, &str)| {
+                        if let Some((from, to)) = line.split_once("->") {
+                            Ok(builder.edge(from, to, EdgeKind::Association, None))
+                        } else if let Some(id) = line.strip_suffix(':') {
+                            Ok(builder.node(id, NodeKind::Interface, id).member(
+                                id,
+                                Member {
+                                    name: "run".to_string(),
+                                    kind: MemberKind::Method,
+                                    type_name: None,
+                                    data: Default::default(),
+                                },
+                            ))
+                        } else if let Some(id) = line.strip_prefix('.') {
+                            Ok(builder.node(id, NodeKind::default(), id))
+                        } else {
+                            Err(GraphGatewayError::Parse {
+                                source: "arrows".to_string(),
+                                message: format!("unexpected `{}`", line),
+                                line: index + 1,
+                                column: 1,
+                            })
+                        }
+                    },
+                )
+                .map(GraphBuilder::build)
+        }
+    }
+
+    fn compose(sources: &[(&str, &str)]) -> Result<Composition, GraphGatewayError> {
+        let sources: Vec<NamedSource> = sources
+            .iter()
+            .map(|(name, source): &(&str, &str)| NamedSource { name, source })
+            .collect();
+        ComposeGraphs::new(Arc::new(ArrowGateway)).execute(&sources)
+    }
+
+    #[test]
+    fn should_place_sources_in_clusters_sharing_ids() {
+        let composition: Composition = compose(&[
+            ("billing", ".Invoice\n.Order\nInvoice->Order"),
+            ("orders", "Order:\n.Line\nOrder->Line"),
+        ])
+        .unwrap();
+        let graph: Graph = composition.graph;
+
+        assert_eq!(graph.nodes["Invoice"].parent.as_deref(), Some("billing"));
+        assert_eq!(graph.nodes["Order"].kind, NodeKind::Interface);
+        assert_eq!(graph.nodes["Order"].parent.as_deref(), Some("orders"));
+        let mut orders: Vec<Id> = graph.groups["orders"].children.clone();
+        orders.sort();
+        assert_eq!(orders, vec!["Line", "Order"]);
+        assert_eq!(graph.groups["billing"].children, vec!["Invoice"]);
+        // Both sources number their edges from `e1`
+        assert_eq!(graph.edges["e1_2"].to, "Line");
+        assert_eq!(composition.unresolved, Vec::new());
+    }
+
+    #[test]
+    fn should_report_unresolved_references_and_failing_sources() {
+        let composition: Composition = compose(&[("billing", ".Invoice\nInvoice->Order")]).unwrap();
+
+        assert_eq!(
+            composition.unresolved,
+            vec![Unresolved {
+                source: "billing".to_string(),
+                edge: "e1".into(),
+                id: "Order".into(),
+            }]
+        );
+        assert_eq!(
+            compose(&[("billing", ".Invoice"), ("orders", "Order")]),
+            Err(GraphGatewayError::Parse {
+                source: "orders".to_string(),
+                message: "unexpected `Order`".to_string(),
+                line: 1,
+                column: 1,
+            })
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    adapters::graph_emitter_adapter::{GraphEmitterAdapter, GraphEmitterError},
+    entities::graph::Graph,
+};
+
+#[async_trait]
+pub trait EmitGraphUseCase {
+    async fn execute(&self, graph: &Graph) -> Result<String, String>;
+}
+
+pub struct EmitGraph<T: GraphEmitterAdapter + ?Sized> {
+    graph_emitter: Arc<T>,
+}
+
+impl<T: GraphEmitterAdapter + ?Sized> EmitGraph<T> {
+    pub fn new(graph_emitter: Arc<T>) -> Self {
+        Self { graph_emitter }
+    }
+}
+
+#[async_trait]
+impl<T: GraphEmitterAdapter + Sync + Send + ?Sized + 'static> EmitGraphUseCase for EmitGraph<T> {
+    async fn execute(&self, graph: &Graph) -> Result<String, String> {
+        self.graph_emitter
+            .emit_graph(graph)
+            .await
+            .map_err(String::from)
+    }
+}
+
+impl From<GraphEmitterError> for String {
+    fn from(value: GraphEmitterError) -> Self {
+        match value {
+            GraphEmitterError::Unsupported { target, message } => {
+                format!("[{}] Unsupported Output: {}", target, message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use crate::{
+        adapters::graph_emitter_adapter::{GraphEmitterAdapter, GraphEmitterError},
+        entities::graph::Graph,
+        use_cases::emit_graph::{EmitGraph, EmitGraphUseCase},
+    };
+
+    macro_rules! async_test {
+        ($body:expr) => {
+            smol::block_on(async { $body })
+        };
+    }
+
+    #[test]
+    fn should_return_emitted_output() {
+        async_test!({
+            let use_case: EmitGraph<FakeGraphEmitter> = EmitGraph::new(Arc::new(
+                FakeGraphEmitter::returning(Ok("graph TD".to_owned())),
+            ));
+
+            let result: Result<String, String> = use_case.execute(&Graph::default()).await;
+
+            assert_eq!(Ok("graph TD".to_owned()), result);
+        });
+    }
+
+    #[test]
+    fn should_parse_emitter_error() {
+        async_test!({
+            let use_case: EmitGraph<dyn GraphEmitterAdapter + Send + Sync> =
+                EmitGraph::new(Arc::new(FakeGraphEmitter::returning(Err(
+                    GraphEmitterError::Unsupported {
+                        target: "fake".to_owned(),
+                        message: "dummy error".to_owned(),
+                    },
+                ))));
+
+            let result: Result<String, String> = use_case.execute(&Graph::default()).await;
+
+            assert_eq!(
+                Err("[fake] Unsupported Output: dummy error".to_owned()),
+                result
+            );
+        });
+    }
+
+    struct FakeGraphEmitter {
+        result: Result<String, GraphEmitterError>,
+    }
+
+    impl FakeGraphEmitter {
+        fn returning(result: Result<String, GraphEmitterError>) -> Self {
+            Self { result }
+        }
+    }
+
+    #[async_trait]
+    impl GraphEmitterAdapter for FakeGraphEmitter {
+        async fn emit_graph(&self, _graph: &Graph) -> Result<String, GraphEmitterError> {
+            self.result.clone()
+        }
+    }
+}
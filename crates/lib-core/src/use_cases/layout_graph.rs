@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+    entities::graph::Graph,
+};
+
+#[async_trait]
+pub trait LayoutGraphUseCase {
+    async fn execute(&self, graph: Graph) -> Result<Graph, String>;
+}
+
+pub struct LayoutGraph<T: LayoutEngineAdapter + ?Sized> {
+    layout_engine: Arc<T>,
+}
+
+impl<T: LayoutEngineAdapter + ?Sized> LayoutGraph<T> {
+    pub fn new(layout_engine: Arc<T>) -> Self {
+        Self { layout_engine }
+    }
+}
+
+#[async_trait]
+impl<T: LayoutEngineAdapter + Sync + Send + ?Sized + 'static> LayoutGraphUseCase
+    for LayoutGraph<T>
+{
+    async fn execute(&self, mut graph: Graph) -> Result<Graph, String> {
+        self.layout_engine
+            .layout(&mut graph)
+            .await
+            .map_err(String::from)?;
+
+        Ok(graph)
+    }
+}
+
+impl From<LayoutEngineError> for String {
+    fn from(value: LayoutEngineError) -> Self {
+        match value {
+            LayoutEngineError::Unsupported { engine, message } => {
+                format!("[{}] Unsupported Layout: {}", engine, message)
+            }
+            LayoutEngineError::Failed { engine, message } => {
+                format!("[{}] Layout Error: {}", engine, message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use crate::{
+        adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+        entities::{geometry::Rect, graph::Graph, node::Node},
+        use_cases::layout_graph::{LayoutGraph, LayoutGraphUseCase},
+    };
+
+    macro_rules! async_test {
+        ($body:expr) => {
+            smol::block_on(async { $body })
+        };
+    }
+
+    #[test]
+    fn should_return_graph_with_geometry_from_engine() {
+        async_test!({
+            let mut graph: Graph = Graph::default();
+            graph.nodes.insert(
+                "A".into(),
+                Node {
+                    id: "A".into(),
+                    ..Default::default()
+                },
+            );
+
+            let use_case: LayoutGraph<FakeLayoutEngine> =
+                LayoutGraph::new(Arc::new(FakeLayoutEngine::returning(Ok(()))));
+
+            let result: Graph = use_case.execute(graph).await.unwrap();
+
+            assert_eq!(
+                result.nodes.get("A").and_then(|n: &Node| n.geometry),
+                Some(GEOMETRY)
+            );
+        });
+    }
+
+    #[test]
+    fn should_parse_engine_error() {
+        async_test!({
+            let use_case: LayoutGraph<FakeLayoutEngine> = LayoutGraph::new(Arc::new(
+                FakeLayoutEngine::returning(Err(LayoutEngineError::Unsupported {
+                    engine: "fake".to_owned(),
+                    message: "dummy error".to_owned(),
+                })),
+            ));
+
+            let result: Result<Graph, String> = use_case.execute(Graph::default()).await;
+
+            assert_eq!(
+                Err("[fake] Unsupported Layout: dummy error".to_owned()),
+                result
+            );
+        });
+    }
+
+    const GEOMETRY: Rect = Rect {
+        x: 1.0,
+        y: 2.0,
+        width: 3.0,
+        height: 4.0,
+    };
+
+    struct FakeLayoutEngine {
+        result: Result<(), LayoutEngineError>,
+    }
+
+    impl FakeLayoutEngine {
+        fn returning(result: Result<(), LayoutEngineError>) -> Self {
+            Self { result }
+        }
+    }
+
+    #[async_trait]
+    impl LayoutEngineAdapter for FakeLayoutEngine {
+        async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError> {
+            graph
+                .nodes
+                .values_mut()
+                .for_each(|node: &mut Node| node.geometry = Some(GEOMETRY));
+            self.result.clone()
+        }
+    }
+}
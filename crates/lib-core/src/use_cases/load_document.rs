@@ -0,0 +1,275 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    adapters::{
+        document_gateway::{DocumentGateway, DocumentGatewayError},
+        graph_gateway::{GraphGateway, GraphGatewayError},
+    },
+    entities::{
+        document::{DiagramBlock, Document, SourceBlock},
+        graph::Graph,
+    },
+};
+
+#[async_trait]
+pub trait LoadDocumentUseCase {
+    async fn execute(&self, source: &str) -> Result<Document, String>;
+}
+
+pub struct LoadDocument<T: DocumentGateway> {
+    document_gateway: Arc<T>,
+    graph_gateways: HashMap<String, Arc<dyn GraphGateway + Send + Sync>>,
+}
+
+impl<T: DocumentGateway> LoadDocument<T> {
+    pub fn new(document_gateway: Arc<T>) -> Self {
+        Self {
+            document_gateway,
+            graph_gateways: HashMap::new(),
+        }
+    }
+
+    /// Registers the gateway used to parse blocks tagged with the given language
+    pub fn with_graph_gateway(
+        mut self,
+        language: &str,
+        graph_gateway: Arc<dyn GraphGateway + Send + Sync>,
+    ) -> Self {
+        self.graph_gateways
+            .insert(language.to_lowercase(), graph_gateway);
+        self
+    }
+
+    async fn load_block(&self, block: SourceBlock) -> DiagramBlock {
+        let graph: Result<Graph, String> =
+            match self.graph_gateways.get(&block.language.to_lowercase()) {
+                Some(gateway) => gateway
+                    .read_graph_from_raw_input(&block.source)
+                    .await
+                    .map_err(|error: GraphGatewayError| {
+                        // Report positions relative to the host document rather than the block
+                        String::from(match error {
+                            GraphGatewayError::Parse {
+                                source,
+                                message,
+                                line,
+                                column,
+                            } => GraphGatewayError::Parse {
+                                source,
+                                message,
+                                line: line + block.span.start_line.saturating_sub(1),
+                                column,
+                            },
+                            semantic => semantic,
+                        })
+                    }),
+                None => Err(format!(
+                    "No graph gateway registered for language '{}'",
+                    block.language
+                )),
+            };
+
+        DiagramBlock {
+            language: block.language,
+            source: block.source,
+            span: block.span,
+            graph,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: DocumentGateway + Sync + Send + 'static> LoadDocumentUseCase for LoadDocument<T> {
+    async fn execute(&self, source: &str) -> Result<Document, String> {
+        let source_blocks: Vec<SourceBlock> = self
+            .document_gateway
+            .read_blocks_from_raw_input(source)
+            .await
+            .map_err(String::from)?;
+
+        let mut blocks: Vec<DiagramBlock> = Vec::with_capacity(source_blocks.len());
+        for block in source_blocks {
+            blocks.push(self.load_block(block).await);
+        }
+
+        Ok(Document { blocks })
+    }
+}
+
+impl From<DocumentGatewayError> for String {
+    fn from(value: DocumentGatewayError) -> Self {
+        match value {
+            DocumentGatewayError::Parse {
+                source,
+                message,
+                line,
+                column,
+            } => format!("[{}:{}:{}] Parse Error: {}", source, line, column, message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+
+    use crate::{
+        adapters::{
+            document_gateway::{DocumentGateway, DocumentGatewayError},
+            graph_gateway::{GraphGateway, GraphGatewayError},
+        },
+        entities::{
+            document::{Document, SourceBlock, SourceSpan},
+            graph::Graph,
+        },
+        use_cases::load_document::{LoadDocument, LoadDocumentUseCase},
+    };
+
+    macro_rules! async_test {
+        ($body:expr) => {
+            smol::block_on(async { $body })
+        };
+    }
+
+    #[test]
+    fn should_parse_each_block_with_matching_gateway() {
+        async_test!({
+            let diagram: Graph = Graph::default();
+            let graph_gateway: Arc<FakeGraphGateway> =
+                Arc::new(FakeGraphGateway::returning(Ok(diagram.clone())));
+            let document_gateway: Arc<FakeDocumentGateway> =
+                Arc::new(FakeDocumentGateway::returning(Ok(vec![
+                    block("plantuml", "A --> B", 3),
+                    block("mermaid", "graph TD", 9),
+                ])));
+
+            let use_case: LoadDocument<FakeDocumentGateway> = LoadDocument::new(document_gateway)
+                .with_graph_gateway("PlantUML", graph_gateway.clone());
+
+            let document: Document = use_case.execute("Some document").await.unwrap();
+
+            assert_eq!(document.blocks.len(), 2);
+            assert_eq!(document.blocks[0].graph, Ok(diagram));
+            assert_eq!(document.blocks[0].span.start_line, 3);
+            assert_eq!(
+                document.blocks[1].graph,
+                Err("No graph gateway registered for language 'mermaid'".to_owned())
+            );
+            assert_eq!(graph_gateway.received_inputs(), vec!["A --> B".to_owned()]);
+        });
+    }
+
+    #[test]
+    fn should_report_block_errors_relative_to_document() {
+        async_test!({
+            let graph_gateway: Arc<FakeGraphGateway> =
+                Arc::new(FakeGraphGateway::returning(Err(GraphGatewayError::Parse {
+                    source: "fake".to_owned(),
+                    message: "dummy error".to_owned(),
+                    line: 2,
+                    column: 5,
+                })));
+            let document_gateway: Arc<FakeDocumentGateway> = Arc::new(
+                FakeDocumentGateway::returning(Ok(vec![block("fake", "invalid", 10)])),
+            );
+
+            let use_case: LoadDocument<FakeDocumentGateway> =
+                LoadDocument::new(document_gateway).with_graph_gateway("fake", graph_gateway);
+
+            let document: Document = use_case.execute("Some document").await.unwrap();
+
+            assert_eq!(
+                document.blocks[0].graph,
+                Err("[fake:11:5] Parse Error: dummy error".to_owned())
+            );
+        });
+    }
+
+    #[test]
+    fn should_parse_document_gateway_error() {
+        async_test!({
+            let document_gateway: Arc<FakeDocumentGateway> = Arc::new(
+                FakeDocumentGateway::returning(Err(DocumentGatewayError::Parse {
+                    source: "fake".to_owned(),
+                    message: "dummy error".to_owned(),
+                    line: 4,
+                    column: 1,
+                })),
+            );
+
+            let use_case: LoadDocument<FakeDocumentGateway> = LoadDocument::new(document_gateway);
+
+            let result: Result<Document, String> = use_case.execute("Some document").await;
+
+            assert_eq!(
+                Err("[fake:4:1] Parse Error: dummy error".to_owned()),
+                result
+            );
+        });
+    }
+
+    fn block(language: &str, source: &str, start_line: usize) -> SourceBlock {
+        SourceBlock {
+            language: language.to_owned(),
+            source: source.to_owned(),
+            span: SourceSpan {
+                start_line,
+                end_line: start_line,
+                ..Default::default()
+            },
+        }
+    }
+
+    struct FakeDocumentGateway {
+        result: Result<Vec<SourceBlock>, DocumentGatewayError>,
+    }
+
+    impl FakeDocumentGateway {
+        fn returning(result: Result<Vec<SourceBlock>, DocumentGatewayError>) -> Self {
+            Self { result }
+        }
+    }
+
+    #[async_trait]
+    impl DocumentGateway for FakeDocumentGateway {
+        async fn read_blocks_from_raw_input(
+            &self,
+            _input: &str,
+        ) -> Result<Vec<SourceBlock>, DocumentGatewayError> {
+            self.result.clone()
+        }
+    }
+
+    struct FakeGraphGateway {
+        result: Result<Graph, GraphGatewayError>,
+        received_inputs: Mutex<Vec<String>>,
+    }
+
+    impl FakeGraphGateway {
+        fn returning(result: Result<Graph, GraphGatewayError>) -> Self {
+            Self {
+                result,
+                received_inputs: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn received_inputs(&self) -> Vec<String> {
+            self.received_inputs.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl GraphGateway for FakeGraphGateway {
+        async fn read_graph_from_raw_input(
+            &self,
+            source: &str,
+        ) -> Result<Graph, GraphGatewayError> {
+            self.received_inputs.lock().unwrap().push(source.to_owned());
+            self.result.clone()
+        }
+    }
+}
@@ -12,18 +12,18 @@ pub trait LoadGraphUseCase {
     async fn execute(&self, source: &str) -> Result<Graph, String>;
 }
 
-pub struct LoadGraph<T: GraphGateway> {
+pub struct LoadGraph<T: GraphGateway + ?Sized> {
     graph_gateway: Arc<T>,
 }
 
-impl<T: GraphGateway> LoadGraph<T> {
+impl<T: GraphGateway + ?Sized> LoadGraph<T> {
     pub fn new(graph_gateway: Arc<T>) -> Self {
         Self { graph_gateway }
     }
 }
 
 #[async_trait]
-impl<T: GraphGateway + Sync + Send + 'static> LoadGraphUseCase for LoadGraph<T> {
+impl<T: GraphGateway + Sync + Send + ?Sized + 'static> LoadGraphUseCase for LoadGraph<T> {
     async fn execute(&self, source: &str) -> Result<Graph, String> {
         self.graph_gateway
             .read_graph_from_raw_input(source)
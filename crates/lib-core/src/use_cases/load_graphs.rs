@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
+use crate::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+pub trait LoadGraphsUseCase {
+    /// Loads every source, keeping the results in the order of the sources.
+    /// Errors keep their location, so callers can point at it in the source
+    fn execute(&self, sources: &[&str]) -> Vec<Result<Graph, GraphGatewayError>>;
+}
+
+/// Loads independent sources across rayon's thread pool, blocking the caller
+/// until all of them are read
+pub struct LoadGraphs<T: GraphGateway + ?Sized> {
+    graph_gateway: Arc<T>,
+}
+
+impl<T: GraphGateway + ?Sized> LoadGraphs<T> {
+    pub fn new(graph_gateway: Arc<T>) -> Self {
+        Self { graph_gateway }
+    }
+}
+
+impl<T: GraphGateway + Sync + Send + ?Sized + 'static> LoadGraphsUseCase for LoadGraphs<T> {
+    fn execute(&self, sources: &[&str]) -> Vec<Result<Graph, GraphGatewayError>> {
+        sources
+            .par_iter()
+            .map(|source: &&str| {
+                futures_lite::future::block_on(self.graph_gateway.read_graph_from_raw_input(source))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{graph::Graph, id::Id},
+        use_cases::load_graphs::{LoadGraphs, LoadGraphsUseCase},
+    };
+
+    #[test]
+    fn should_keep_results_in_source_order() {
+        let sources: Vec<String> = (0..64).map(|i: usize| i.to_string()).collect();
+        let sources: Vec<&str> = sources.iter().map(String::as_str).collect();
+
+        let results: Vec<Result<Graph, GraphGatewayError>> =
+            LoadGraphs::new(Arc::new(NamingGateway)).execute(&sources);
+
+        let ids: Vec<Id> = results
+            .into_iter()
+            .map(|result: Result<Graph, GraphGatewayError>| result.unwrap().id)
+            .collect();
+        assert_eq!(
+            ids,
+            sources
+                .iter()
+                .map(|source: &&str| Id::from(*source))
+                .collect::<Vec<Id>>()
+        );
+    }
+
+    #[test]
+    fn should_report_each_failure_on_its_own() {
+        let results: Vec<Result<Graph, GraphGatewayError>> =
+            LoadGraphs::new(Arc::new(NamingGateway)).execute(&["ok", "", "fine"]);
+
+        assert!(results[0].is_ok());
+        assert_eq!(
+            results[1],
+            Err(GraphGatewayError::Semantic {
+                source: "fake".to_owned(),
+                message: "empty source".to_owned(),
+            })
+        );
+        assert!(results[2].is_ok());
+    }
+
+    /// Names each graph after its source, refusing empty ones
+    struct NamingGateway;
+
+    #[async_trait]
+    impl GraphGateway for NamingGateway {
+        async fn read_graph_from_raw_input(
+            &self,
+            source: &str,
+        ) -> Result<Graph, GraphGatewayError> {
+            if source.is_empty() {
+                return Err(GraphGatewayError::Semantic {
+                    source: "fake".to_owned(),
+                    message: "empty source".to_owned(),
+                });
+            }
+            Ok(Graph {
+                id: source.into(),
+                ..Default::default()
+            })
+        }
+    }
+}
@@ -0,0 +1,421 @@
+use std::{collections::HashMap, hash::Hash};
+
+use regex::Regex;
+
+use crate::entities::{
+    edge::Edge,
+    graph::{Graph, Legend},
+    group::Group,
+    id::{Id, Text},
+    member::Member,
+    node::Node,
+    rich_text::RichText,
+    sequence::{Activation, Fragment, Operand},
+    value::{LINK_KEYS, Value},
+    warning::Warning,
+};
+
+/// What becomes of a sensitive text
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Redaction {
+    /// Removed: labels and titles are cleared, data entries and members
+    /// dropped
+    #[default]
+    Strip,
+    /// Replaced by a short hash, so equal texts still look equal
+    Hash,
+}
+
+/// Which texts of a diagram are sensitive
+#[derive(Debug, Clone, Default)]
+pub struct SanitizePolicy {
+    /// A text matching any of these is redacted as a whole
+    pub patterns: Vec<Regex>,
+    /// Data and metadata property keys whose values are always redacted
+    pub keys: Vec<String>,
+    /// Whether every hyperlink is redacted, matching or not
+    pub links: bool,
+    pub redaction: Redaction,
+    /// Mixed into hashes, so short names cannot be guessed back from them
+    pub salt: String,
+}
+
+impl SanitizePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.keys.push(key.to_string());
+        self
+    }
+
+    pub fn with_links(mut self) -> Self {
+        self.links = true;
+        self
+    }
+
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    pub fn with_salt(mut self, salt: &str) -> Self {
+        self.salt = salt.to_string();
+        self
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern: &Regex| pattern.is_match(text))
+    }
+
+    /// `h` and eight hex digits of a salted FNV-1a hash, which stays the same
+    /// across runs and builds and is a valid id in every notation
+    fn hash(&self, text: &str) -> String {
+        let hash: u64 = self
+            .salt
+            .bytes()
+            .chain([0])
+            .chain(text.bytes())
+            .fold(0xcbf29ce484222325, |hash: u64, byte: u8| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            });
+        format!("h{:08x}", hash >> 32)
+    }
+}
+
+pub trait SanitizeGraphUseCase {
+    fn execute(&self, graph: &Graph, policy: &SanitizePolicy) -> Graph;
+}
+
+/// Redacts what a policy calls sensitive so a diagram can be shared: labels,
+/// titles, notes, members, data values, metadata properties, fragment
+/// conditions and warnings.
+/// Matching ids are always hashed rather than stripped, and renamed
+/// everywhere they are referenced, so the diagram keeps its shape.
+pub struct SanitizeGraph;
+
+impl SanitizeGraph {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SanitizeGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SanitizeGraphUseCase for SanitizeGraph {
+    fn execute(&self, graph: &Graph, policy: &SanitizePolicy) -> Graph {
+        let mut graph: Graph = graph.clone();
+        let renamed: HashMap<Id, Id> = graph
+            .nodes
+            .keys()
+            .chain(graph.edges.keys())
+            .chain(graph.groups.keys())
+            .filter(|id: &&Id| policy.matches(id))
+            .map(|id: &Id| (id.clone(), Id::from(policy.hash(id))))
+            .collect();
+        let rename = |id: &mut Id| {
+            if let Some(to) = renamed.get(id) {
+                *id = to.clone();
+            }
+        };
+
+        graph.metadata.title = text(policy, graph.metadata.title.take());
+        graph.metadata.description = text(policy, graph.metadata.description.take());
+        graph.metadata.legend = graph.metadata.legend.take().and_then(|legend: Legend| {
+            Some(Legend {
+                text: text(policy, Some(legend.text))?,
+                ..legend
+            })
+        });
+        graph.metadata.properties = std::mem::take(&mut graph.metadata.properties)
+            .into_iter()
+            .filter_map(|(key, value): (String, String)| {
+                match sensitive_key(policy, &key) || policy.matches(&value) {
+                    true => redact(policy, &value).map(|value: String| (key, value)),
+                    false => Some((key, value)),
+                }
+            })
+            .collect();
+
+        graph.nodes = std::mem::take(&mut graph.nodes)
+            .into_values()
+            .map(|mut node: Node| {
+                rename(&mut node.id);
+                node.parent.iter_mut().for_each(rename);
+                label(policy, &mut node.label, &mut node.rich_label);
+                node.members = std::mem::take(&mut node.members)
+                    .into_iter()
+                    .filter_map(|member: Member| self::member(policy, member))
+                    .collect();
+                node.data = data(policy, std::mem::take(&mut node.data));
+                (node.id.clone(), node)
+            })
+            .collect();
+        graph.edges = std::mem::take(&mut graph.edges)
+            .into_values()
+            .map(|mut edge: Edge| {
+                rename(&mut edge.from);
+                rename(&mut edge.to);
+                label(policy, &mut edge.label, &mut edge.rich_label);
+                edge.data = data(policy, std::mem::take(&mut edge.data));
+                (edge.id.clone(), edge)
+            })
+            .collect();
+        graph.groups = std::mem::take(&mut graph.groups)
+            .into_values()
+            .map(|mut group: Group| {
+                rename(&mut group.id);
+                group.parent.iter_mut().for_each(rename);
+                group.children.iter_mut().for_each(rename);
+                group.label = text(policy, group.label.take());
+                (group.id.clone(), group)
+            })
+            .collect();
+        graph.rename_edges(&renamed);
+        graph
+            .fragments
+            .values_mut()
+            .flat_map(|fragment: &mut Fragment| fragment.operands.iter_mut())
+            .for_each(|operand: &mut Operand| {
+                operand.condition = text(policy, operand.condition.take());
+            });
+        graph
+            .activations
+            .iter_mut()
+            .for_each(|activation: &mut Activation| rename(&mut activation.participant));
+
+        graph.warnings = std::mem::take(&mut graph.warnings)
+            .into_iter()
+            .filter_map(|mut warning: Warning| {
+                warning.message = text(policy, Some(warning.message))?;
+                Some(warning)
+            })
+            .collect();
+        graph
+    }
+}
+
+/// What a redacted text becomes: nothing, or its hash
+fn redact(policy: &SanitizePolicy, text: &str) -> Option<String> {
+    match policy.redaction {
+        Redaction::Strip => None,
+        Redaction::Hash => Some(policy.hash(text)),
+    }
+}
+
+/// A redacted label loses its formatting too, which holds the same text
+fn label(policy: &SanitizePolicy, label: &mut Option<Text>, rich_label: &mut Option<RichText>) {
+    let sanitized: Option<Text> = text(policy, label.clone());
+    if sanitized != *label {
+        *rich_label = None;
+    }
+    *label = sanitized;
+}
+
+fn text<T: AsRef<str> + From<String>>(policy: &SanitizePolicy, text: Option<T>) -> Option<T> {
+    match text {
+        Some(text) if policy.matches(text.as_ref()) => redact(policy, text.as_ref()).map(T::from),
+        text => text,
+    }
+}
+
+fn sensitive_key(policy: &SanitizePolicy, key: &str) -> bool {
+    policy
+        .keys
+        .iter()
+        .any(|sensitive: &String| sensitive == key)
+        || (policy.links && LINK_KEYS.contains(&key))
+}
+
+/// A member whose name matches is redacted whole; one whose type matches
+/// only loses its type
+fn member(policy: &SanitizePolicy, mut member: Member) -> Option<Member> {
+    if policy.matches(&member.name) {
+        member.name = redact(policy, &member.name)?;
+    }
+    member.type_name = text(policy, member.type_name.take());
+    member.data = data(policy, std::mem::take(&mut member.data));
+    Some(member)
+}
+
+fn data<K: AsRef<str> + Eq + Hash>(
+    policy: &SanitizePolicy,
+    data: HashMap<K, Value>,
+) -> HashMap<K, Value> {
+    data.into_iter()
+        .filter_map(|(key, value): (K, Value)| {
+            let redacted: Option<Value> = match sensitive_key(policy, key.as_ref()) {
+                true => redact(policy, &display(&value)).map(Value::String),
+                false => self::value(policy, value),
+            };
+            redacted.map(|value: Value| (key, value))
+        })
+        .collect()
+}
+
+/// Redacts the matching strings inside a value, keeping its structure
+fn value(policy: &SanitizePolicy, value: Value) -> Option<Value> {
+    match value {
+        Value::String(text) => self::text(policy, Some(text)).map(Value::String),
+        Value::List(items) => Some(Value::List(
+            items
+                .into_iter()
+                .filter_map(|item: Value| self::value(policy, item))
+                .collect(),
+        )),
+        Value::Object(entries) => Some(Value::Object(data(policy, entries))),
+        value => Some(value),
+    }
+}
+
+/// A value as text, for hashing a sensitive key's value of any type
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::List(items) => items.iter().map(display).collect::<Vec<String>>().join(","),
+        Value::Object(entries) => {
+            let mut pairs: Vec<String> = entries
+                .iter()
+                .map(|(key, value): (&String, &Value)| format!("{}:{}", key, display(value)))
+                .collect();
+            pairs.sort();
+            pairs.join(",")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        builder::GraphBuilder,
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            group::Group,
+            id::{Id, Text},
+            member::Member,
+            node::{Node, NodeKind},
+            value::Value,
+        },
+        use_cases::sanitize_graph::{
+            Redaction, SanitizeGraph, SanitizeGraphUseCase, SanitizePolicy,
+        },
+    };
+
+    fn graph() -> Graph {
+        let mut graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .title("Acme billing")
+            .node("AcmeGateway", NodeKind::Entity, "AcmeGateway")
+            .node("Invoice", NodeKind::Entity, "Invoice")
+            .node("note", NodeKind::Annotation, "Ask Acme about retries")
+            .group("acme", "Acme", &["AcmeGateway"])
+            .edge(
+                "Invoice",
+                "AcmeGateway",
+                EdgeKind::Association,
+                Some("pays"),
+            )
+            .build();
+        let invoice: &mut Node = graph.nodes.get_mut("Invoice").unwrap();
+        invoice.members = vec![
+            Member {
+                name: "total".to_string(),
+                type_name: Some("AcmeMoney".to_string()),
+                ..Default::default()
+            },
+            Member {
+                name: "acmeRef".to_string(),
+                ..Default::default()
+            },
+        ];
+        invoice.data = [
+            (
+                Text::from("href"),
+                Value::String("https://wiki/invoice".to_string()),
+            ),
+            (
+                Text::from("file"),
+                Value::String("/home/ada/billing/invoice.rs".to_string()),
+            ),
+            (Text::from("owner"), Value::String("ada".to_string())),
+            (Text::from("version"), Value::Number(2.0)),
+        ]
+        .into();
+        graph
+    }
+
+    #[test]
+    fn should_strip_matching_texts_and_sensitive_values() {
+        let policy: SanitizePolicy = SanitizePolicy::new()
+            .with_pattern("(?i)acme")
+            .unwrap()
+            .with_key("owner")
+            .with_links();
+
+        let sanitized: Graph = SanitizeGraph::new().execute(&graph(), &policy);
+
+        let gateway: String = policy.hash("AcmeGateway");
+        assert_eq!(sanitized.metadata.title, None);
+        assert_eq!(sanitized.nodes[gateway.as_str()].label, None);
+        assert_eq!(sanitized.nodes["note"].label, None);
+        assert_eq!(sanitized.nodes["Invoice"].label.as_deref(), Some("Invoice"));
+        let invoice: &Node = &sanitized.nodes["Invoice"];
+        assert_eq!(invoice.members.len(), 1);
+        assert_eq!(invoice.members[0].type_name, None);
+        assert_eq!(
+            invoice.data,
+            [(Text::from("version"), Value::Number(2.0))].into()
+        );
+        let edge: &Edge = sanitized.edges.values().next().unwrap();
+        assert_eq!(edge.to.as_str(), gateway);
+        assert_eq!(edge.label.as_deref(), Some("pays"));
+        let group: &Group = sanitized.groups.values().next().unwrap();
+        assert_eq!(group.children, vec![Id::from(gateway.as_str())]);
+        assert_eq!(group.label, None);
+    }
+
+    #[test]
+    fn should_hash_equal_texts_alike() {
+        let policy: SanitizePolicy = SanitizePolicy::new()
+            .with_pattern("Acme")
+            .unwrap()
+            .with_key("owner")
+            .with_redaction(Redaction::Hash)
+            .with_salt("pepper");
+
+        let sanitized: Graph = SanitizeGraph::new().execute(&graph(), &policy);
+
+        let gateway: String = policy.hash("AcmeGateway");
+        assert_eq!(
+            sanitized.nodes[gateway.as_str()].label.as_deref(),
+            Some(gateway.as_str())
+        );
+        assert_eq!(
+            sanitized.nodes["Invoice"].data["owner"],
+            Value::String(policy.hash("ada"))
+        );
+        assert_eq!(
+            sanitized.nodes["Invoice"].data["href"],
+            Value::String("https://wiki/invoice".to_string())
+        );
+        assert_eq!(gateway.len(), 9);
+        assert!(gateway.starts_with('h'));
+        assert!(SanitizePolicy::new().hash("AcmeGateway") != gateway);
+    }
+}
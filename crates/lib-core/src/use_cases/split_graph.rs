@@ -0,0 +1,211 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::entities::{
+    document::{DiagramBlock, Document, SourceSpan},
+    edge::Edge,
+    graph::{Graph, Metadata},
+    group::Group,
+    id::{Id, Text},
+    node::Node,
+    value::Value,
+};
+
+/// Data key marking a node copied in from another package; its value is the
+/// id of the package the node belongs to
+pub const EXTERNAL: &str = "external";
+
+/// Id of the view holding nodes outside every package
+pub const UNGROUPED: &str = "ungrouped";
+
+pub trait SplitGraphUseCase {
+    fn execute(&self, graph: &Graph) -> Document;
+}
+
+/// Splits a diagram into one view per top-level group, sorted by group id.
+/// An edge crossing packages shows up in both views, its far end copied in as
+/// a bare external node. The views are built rather than read, so their
+/// blocks carry no source.
+pub struct SplitGraph;
+
+impl SplitGraph {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SplitGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SplitGraphUseCase for SplitGraph {
+    fn execute(&self, graph: &Graph) -> Document {
+        let mut views: BTreeMap<Id, Graph> = BTreeMap::new();
+
+        graph.groups.values().for_each(|group: &Group| {
+            view(&mut views, graph, package(graph, &group.id))
+                .groups
+                .insert(group.id.clone(), group.clone());
+        });
+        graph.nodes.values().for_each(|node: &Node| {
+            view(&mut views, graph, package(graph, &node.id))
+                .nodes
+                .insert(node.id.clone(), node.clone());
+        });
+        graph.edges.values().for_each(|edge: &Edge| {
+            let (from, to): (Id, Id) = (package(graph, &edge.from), package(graph, &edge.to));
+            view(&mut views, graph, from.clone())
+                .edges
+                .insert(edge.id.clone(), edge.clone());
+            if from != to {
+                let target: &mut Graph = view(&mut views, graph, to.clone());
+                target.edges.insert(edge.id.clone(), edge.clone());
+                external(target, graph, &edge.from, &from);
+                external(view(&mut views, graph, from.clone()), graph, &edge.to, &to);
+            }
+        });
+
+        Document {
+            blocks: views
+                .into_values()
+                .map(|view: Graph| DiagramBlock {
+                    language: String::new(),
+                    source: String::new(),
+                    span: SourceSpan::default(),
+                    graph: Ok(view),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The view for a package, created the first time it is needed
+fn view<'a>(views: &'a mut BTreeMap<Id, Graph>, graph: &Graph, id: Id) -> &'a mut Graph {
+    views.entry(id.clone()).or_insert_with(|| Graph {
+        metadata: Metadata {
+            title: Some(
+                graph
+                    .groups
+                    .get(&id)
+                    .and_then(|group: &Group| group.label.as_ref())
+                    .unwrap_or(&id)
+                    .to_string(),
+            ),
+            ..graph.metadata.clone()
+        },
+        id,
+        kind: graph.kind.clone(),
+        direction: graph.direction,
+        styles: graph.styles.clone(),
+        ..Default::default()
+    })
+}
+
+/// Top-level group holding a node or group, or `UNGROUPED`
+fn package(graph: &Graph, id: &Id) -> Id {
+    let mut current: Option<Id> = graph
+        .nodes
+        .get(id)
+        .map_or(Some(id.clone()), |node: &Node| node.parent.clone());
+    let mut package: Id = Id::from(UNGROUPED);
+    // Bounded by the group count in case of a parent cycle
+    for _ in 0..=graph.groups.len() {
+        let Some(group) = current.and_then(|id: Id| graph.groups.get(&id)) else {
+            break;
+        };
+        package = group.id.clone();
+        current = group.parent.clone();
+    }
+    package
+}
+
+/// Copies the element at the far end of a crossing edge into `view`
+fn external(view: &mut Graph, graph: &Graph, id: &Id, package: &Id) {
+    let label: Option<Text> = graph
+        .nodes
+        .get(id)
+        .map(|node: &Node| node.label.clone())
+        .or_else(|| {
+            graph
+                .groups
+                .get(id)
+                .map(|group: &Group| group.label.clone())
+        })
+        .flatten();
+
+    view.nodes.entry(id.clone()).or_insert_with(|| Node {
+        id: id.clone(),
+        kind: graph
+            .nodes
+            .get(id)
+            .map(|node: &Node| node.kind.clone())
+            .unwrap_or_default(),
+        label,
+        data: HashMap::from([(Text::from(EXTERNAL), Value::String(package.to_string()))]),
+        ..Default::default()
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        builder::GraphBuilder,
+        entities::{
+            document::{DiagramBlock, Document},
+            edge::EdgeKind,
+            graph::{Graph, GraphKind},
+            id::Id,
+            node::NodeKind,
+            value::Value,
+        },
+        use_cases::split_graph::{SplitGraph, SplitGraphUseCase},
+    };
+
+    fn ids<'a>(ids: impl Iterator<Item = &'a Id>) -> Vec<&'a str> {
+        let mut ids: Vec<&str> = ids.map(Id::as_str).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn should_split_by_top_level_group_keeping_crossing_edges() {
+        let graph: Graph = GraphBuilder::new(GraphKind::Class)
+            .node("Order", NodeKind::Entity, "Order")
+            .node("Line", NodeKind::Entity, "Line")
+            .node("Invoice", NodeKind::Entity, "Invoice")
+            .node("Log", NodeKind::Entity, "Log")
+            .group("orders.model", "Model", &["Line"])
+            .group("orders", "Orders", &["Order", "orders.model"])
+            .group("billing", "Billing", &["Invoice"])
+            .edge("Order", "Line", EdgeKind::Composition, None)
+            .edge("Invoice", "Order", EdgeKind::Association, None)
+            .build();
+
+        let document: Document = SplitGraph::new().execute(&graph);
+        let views: Vec<Graph> = document
+            .blocks
+            .into_iter()
+            .map(|block: DiagramBlock| block.graph.unwrap())
+            .collect();
+
+        assert_eq!(
+            ids(views.iter().map(|view: &Graph| &view.id)),
+            vec!["billing", "orders", "ungrouped"]
+        );
+        let (billing, orders): (&Graph, &Graph) = (&views[0], &views[1]);
+        assert_eq!(billing.metadata.title.as_deref(), Some("Billing"));
+        assert_eq!(ids(billing.nodes.keys()), vec!["Invoice", "Order"]);
+        assert_eq!(
+            billing.nodes["Order"].data["external"],
+            Value::String("orders".to_string())
+        );
+        assert_eq!(ids(billing.edges.keys()), vec!["e2"]);
+        assert_eq!(ids(orders.nodes.keys()), vec!["Invoice", "Line", "Order"]);
+        assert_eq!(ids(orders.groups.keys()), vec!["orders", "orders.model"]);
+        assert_eq!(ids(orders.edges.keys()), vec!["e1", "e2"]);
+        assert_eq!(ids(views[2].nodes.keys()), vec!["Log"]);
+    }
+}
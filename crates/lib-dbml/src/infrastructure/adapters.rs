@@ -0,0 +1 @@
+pub mod dbml_graph_gateway;
@@ -0,0 +1,278 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, DbmlParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct DbmlGraphGateway;
+
+impl DbmlGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for DbmlGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_dbml(input)
+            .map_err(GraphGatewayError::from)
+            .map(|ast| transformer::GraphBuilder::new().build(ast))
+    }
+}
+
+impl From<DbmlParseError> for GraphGatewayError {
+    fn from(err: DbmlParseError) -> Self {
+        match err {
+            DbmlParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "dbml".into(),
+                message,
+                line,
+                column,
+            },
+            DbmlParseError::Internal(msg) => GraphGatewayError::Semantic {
+                source: "dbml".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::Edge,
+            graph::{Graph, GraphKind},
+            group::Group,
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            value::Value,
+            warning::Warning,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::dbml_graph_gateway::DbmlGraphGateway;
+
+    const SCHEMA: &str = r#"
+    Project shop {
+        database_type: 'PostgreSQL'
+        Note: 'Online { shop }'
+    }
+
+    // Core tables
+    Table users as U {
+        id integer [pk, increment]
+        email varchar(255) [not null, unique, note: 'login, lowercase']
+        status user_status
+        created_at timestamp [default: `now()`]
+        Note: 'Registered customers'
+    }
+
+    Table orders {
+        id integer [primary key]
+        user_id integer [ref: > U.id]
+        indexes {
+            (user_id, id) [unique]
+        }
+    }
+
+    Table "order items" {
+        order_id integer
+        sku varchar
+    }
+
+    Ref: "order items".order_id > orders.id
+
+    Ref order_user {
+        users.id < orders.user_id [delete: cascade]
+    }
+
+    Enum user_status {
+        active
+        "on hold" [note: 'pending review']
+    }
+
+    TableGroup sales {
+        orders
+        "order items"
+    }
+    "#;
+
+    async fn parse_schema() -> Graph {
+        DbmlGraphGateway::new()
+            .read_graph_from_raw_input(SCHEMA)
+            .await
+            .expect("Failed to parse valid DBML")
+    }
+
+    #[test]
+    fn test_parse_tables_and_columns() {
+        smol::block_on(async {
+            let graph: Graph = parse_schema().await;
+
+            assert_eq!(graph.kind, GraphKind::EntityRelationship);
+            assert_eq!(graph.metadata.title.as_deref(), Some("shop"));
+
+            let users: &Node = graph.nodes.get("users").expect("Missing users table");
+            assert_eq!(users.kind, NodeKind::Entity);
+            assert_eq!(
+                users.data.get("note"),
+                Some(&Value::String("Registered customers".to_string()))
+            );
+            assert_eq!(
+                users
+                    .members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), m.type_name.as_deref()))
+                    .collect::<Vec<(&str, Option<&str>)>>(),
+                vec![
+                    ("id", Some("integer")),
+                    ("email", Some("varchar(255)")),
+                    ("status", Some("user_status")),
+                    ("created_at", Some("timestamp")),
+                ]
+            );
+
+            let id: &Member = &users.members[0];
+            assert_eq!(id.data.get("primary_key"), Some(&Value::Bool(true)));
+            assert_eq!(id.data.get("increment"), Some(&Value::Bool(true)));
+
+            let email: &Member = &users.members[1];
+            assert_eq!(email.data.get("not_null"), Some(&Value::Bool(true)));
+            assert_eq!(
+                email.data.get("note"),
+                Some(&Value::String("login, lowercase".to_string()))
+            );
+
+            assert_eq!(
+                users.members[3].data.get("default"),
+                Some(&Value::String("now()".to_string()))
+            );
+            assert!(graph.nodes.contains_key("order items"));
+        });
+    }
+
+    #[test]
+    fn test_parse_standalone_and_inline_refs() {
+        smol::block_on(async {
+            let graph: Graph = parse_schema().await;
+
+            assert_eq!(graph.edges.len(), 3);
+
+            let inline: Vec<&Edge> = find_edges(&graph, "orders", "users");
+            assert_eq!(inline.len(), 1, "Inline ref should resolve the table alias");
+            assert_eq!(inline[0].from_cardinality.as_deref(), Some("*"));
+            assert_eq!(
+                inline[0].data.get("to_column"),
+                Some(&Value::String("id".to_string()))
+            );
+
+            let long_form: Vec<&Edge> = find_edges(&graph, "users", "orders");
+            assert_eq!(long_form.len(), 1);
+            assert_eq!(long_form[0].to_cardinality.as_deref(), Some("*"));
+
+            assert_eq!(find_edges(&graph, "order items", "orders").len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_parse_enums_and_table_groups() {
+        smol::block_on(async {
+            let graph: Graph = parse_schema().await;
+
+            let status: &Node = graph.nodes.get("user_status").expect("Missing enum");
+            assert_eq!(status.kind, NodeKind::Enum);
+            assert_eq!(
+                status
+                    .members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), &m.kind))
+                    .collect::<Vec<(&str, &MemberKind)>>(),
+                vec![
+                    ("active", &MemberKind::EnumValue),
+                    ("on hold", &MemberKind::EnumValue)
+                ]
+            );
+
+            let group: &Group = graph.groups.values().next().expect("Missing table group");
+            assert_eq!(group.label.as_deref(), Some("sales"));
+            assert_eq!(
+                group.children,
+                vec!["orders".to_string(), "order items".to_string()]
+            );
+            assert_eq!(
+                graph.nodes.get("orders").and_then(|n| n.parent.as_ref()),
+                Some(&group.id)
+            );
+        });
+    }
+
+    #[test]
+    fn test_groups_and_refs_resolve_tables_declared_later_and_skip_unknown_ones() {
+        smol::block_on(async {
+            let graph: Graph = DbmlGraphGateway::new()
+                .read_graph_from_raw_input(
+                    "TableGroup sales {\n  o\n  ghosts\n}\n\
+                     Ref: o.user_id > users.id\n\
+                     Ref: o.ghost_id > ghosts.id\n\
+                     Table users {\n  id integer\n}\n\
+                     Table orders as o {\n  user_id integer\n  ghost_id integer\n}\n",
+                )
+                .await
+                .unwrap();
+
+            let group: &Group = graph.groups.values().next().unwrap();
+            assert_eq!(group.children, vec!["orders".to_string()]);
+            assert_eq!(graph.nodes["orders"].parent.as_ref(), Some(&group.id));
+            assert_eq!(graph.edges.len(), 1);
+            assert_eq!(find_edges(&graph, "orders", "users").len(), 1);
+            assert_eq!(
+                graph
+                    .warnings
+                    .iter()
+                    .map(|warning: &Warning| warning.message.as_str())
+                    .collect::<Vec<&str>>(),
+                vec![
+                    "Table group names unknown table `ghosts`",
+                    "Ref `o.ghost_id` > `ghosts.id` names unknown table `ghosts`",
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_invalid_schema_is_reported_as_parse_error() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = DbmlGraphGateway::new()
+                .read_graph_from_raw_input("Table users {\n  id integer [pk\n}")
+                .await;
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Parse { ref source, .. }) if source == "dbml"),
+                "Expected a DBML parse error, got {:?}",
+                result
+            );
+        });
+    }
+
+    fn find_edges<'a>(graph: &'a Graph, from: &str, to: &str) -> Vec<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .filter(|e: &&Edge| e.from == from && e.to == to)
+            .collect()
+    }
+}
@@ -0,0 +1,45 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbmlAst {
+    Project {
+        name: String,
+    },
+    Table {
+        name: String,
+        alias: Option<String>,
+        columns: Vec<DbmlColumn>,
+        note: Option<String>,
+    },
+    Ref {
+        from: DbmlEndpoint,
+        operator: String,
+        to: DbmlEndpoint,
+    },
+    Enum {
+        name: String,
+        values: Vec<String>,
+    },
+    TableGroup {
+        name: String,
+        tables: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbmlColumn {
+    pub name: String,
+    pub column_type: String,
+    pub settings: Vec<DbmlSetting>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbmlSetting {
+    Flag(String),
+    Pair { key: String, value: String },
+    Ref { operator: String, to: DbmlEndpoint },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbmlEndpoint {
+    pub table: String,
+    pub column: String,
+}
@@ -0,0 +1,220 @@
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::infrastructure::models::dbml_ast::{DbmlAst, DbmlColumn, DbmlEndpoint, DbmlSetting};
+
+#[derive(Parser)]
+#[grammar = "infrastructure/dbml.pest"]
+pub struct DbmlParser;
+
+pub fn parse_dbml(input: &str) -> Result<Vec<DbmlAst>, DbmlParseError> {
+    let schema: pest::iterators::Pair<Rule> = DbmlParser::parse(Rule::schema, input)
+        .map_err(DbmlParseError::from)?
+        .next()
+        .ok_or_else(|| DbmlParseError::Internal("Missing schema root".to_string()))?;
+
+    Ok(schema.into_inner().filter_map(parse_statement).collect())
+}
+
+fn parse_statement(pair: pest::iterators::Pair<Rule>) -> Option<DbmlAst> {
+    match pair.as_rule() {
+        Rule::project => {
+            let name: String = unquote(pair.into_inner().next()?.as_str());
+
+            Some(DbmlAst::Project { name })
+        }
+        Rule::table => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let name: String = unquote_path(inner.next()?.as_str());
+            let mut alias: Option<String> = None;
+            let mut columns: Vec<DbmlColumn> = Vec::new();
+            let mut note: Option<String> = None;
+
+            inner.for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+                Rule::name => alias = Some(unquote(item.as_str())),
+                Rule::column => columns.push(parse_column(item)),
+                Rule::table_note => {
+                    note = item
+                        .into_inner()
+                        .next()
+                        .map(|s: pest::iterators::Pair<Rule>| unquote_string(s.as_str()))
+                }
+                _ => {}
+            });
+
+            Some(DbmlAst::Table {
+                name,
+                alias,
+                columns,
+                note,
+            })
+        }
+        Rule::reference => {
+            let mut endpoints: Vec<DbmlEndpoint> = Vec::new();
+            let mut operator: Option<String> = None;
+
+            pair.into_inner()
+                .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+                    Rule::endpoint => endpoints.push(parse_endpoint(item.as_str())),
+                    Rule::relation_op => operator = Some(item.as_str().to_string()),
+                    _ => {}
+                });
+
+            let to: DbmlEndpoint = endpoints.pop()?;
+            let from: DbmlEndpoint = endpoints.pop()?;
+
+            Some(DbmlAst::Ref {
+                from,
+                operator: operator?,
+                to,
+            })
+        }
+        Rule::enum_def => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let name: String = unquote_path(inner.next()?.as_str());
+            let values: Vec<String> = inner
+                .filter_map(|value: pest::iterators::Pair<Rule>| {
+                    value
+                        .into_inner()
+                        .next()
+                        .map(|n: pest::iterators::Pair<Rule>| unquote(n.as_str()))
+                })
+                .collect();
+
+            Some(DbmlAst::Enum { name, values })
+        }
+        Rule::table_group => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let name: String = unquote(inner.next()?.as_str());
+            let tables: Vec<String> = inner
+                .map(|t: pest::iterators::Pair<Rule>| unquote_path(t.as_str()))
+                .collect();
+
+            Some(DbmlAst::TableGroup { name, tables })
+        }
+        _ => None,
+    }
+}
+
+fn parse_column(pair: pest::iterators::Pair<Rule>) -> DbmlColumn {
+    let mut name: String = String::new();
+    let mut column_type: String = String::new();
+    let mut settings: Vec<DbmlSetting> = Vec::new();
+
+    pair.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::name => name = unquote(item.as_str()),
+            Rule::column_type => column_type = unquote(item.as_str()),
+            Rule::settings => settings = parse_settings(item),
+            _ => {}
+        });
+
+    DbmlColumn {
+        name,
+        column_type,
+        settings,
+    }
+}
+
+fn parse_settings(pair: pest::iterators::Pair<Rule>) -> Vec<DbmlSetting> {
+    pair.into_inner()
+        .filter_map(
+            |setting: pest::iterators::Pair<Rule>| match setting.as_rule() {
+                Rule::setting_flag => Some(DbmlSetting::Flag(
+                    setting
+                        .as_str()
+                        .split_whitespace()
+                        .collect::<Vec<&str>>()
+                        .join(" "),
+                )),
+                Rule::setting_pair => {
+                    let mut inner: pest::iterators::Pairs<Rule> = setting.into_inner();
+                    let key: String = inner.next()?.as_str().to_lowercase();
+                    let value: String = unquote_string(inner.next()?.as_str().trim());
+
+                    Some(DbmlSetting::Pair { key, value })
+                }
+                Rule::inline_ref => {
+                    let mut inner: pest::iterators::Pairs<Rule> = setting.into_inner();
+                    let operator: String = inner.next()?.as_str().to_string();
+                    let to: DbmlEndpoint = parse_endpoint(inner.next()?.as_str());
+
+                    Some(DbmlSetting::Ref { operator, to })
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+fn parse_endpoint(raw: &str) -> DbmlEndpoint {
+    // Composite endpoints look like `table.(a, b)`, so only split before the column list
+    let split_at: usize = raw
+        .find('(')
+        .map(|paren: usize| paren.saturating_sub(1))
+        .or_else(|| raw.rfind('.'))
+        .unwrap_or(raw.len());
+    let (table, column): (&str, &str) = raw.split_at(split_at);
+
+    DbmlEndpoint {
+        table: unquote_path(table),
+        column: column
+            .trim_start_matches('.')
+            .trim_matches(|c: char| c == '(' || c == ')')
+            .split(',')
+            .map(|c: &str| unquote(c.trim()))
+            .collect::<Vec<String>>()
+            .join(", "),
+    }
+}
+
+fn unquote(raw: &str) -> String {
+    raw.trim_matches('"').to_string()
+}
+
+fn unquote_path(raw: &str) -> String {
+    raw.split('.')
+        .map(unquote)
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+fn unquote_string(raw: &str) -> String {
+    raw.strip_prefix("'''")
+        .and_then(|s: &str| s.strip_suffix("'''"))
+        .or_else(|| {
+            raw.strip_prefix('\'')
+                .and_then(|s: &str| s.strip_suffix('\''))
+        })
+        .or_else(|| {
+            raw.strip_prefix('`')
+                .and_then(|s: &str| s.strip_suffix('`'))
+        })
+        .unwrap_or(raw)
+        .to_string()
+}
+
+#[derive(Debug)]
+pub enum DbmlParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Internal(String),
+}
+
+impl From<pest::error::Error<Rule>> for DbmlParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column): (usize, usize) = match err.line_col {
+            pest::error::LineColLocation::Pos((l, c)) => (l, c),
+            pest::error::LineColLocation::Span((l, c), _) => (l, c),
+        };
+
+        DbmlParseError::Syntax {
+            message: err.to_string(),
+            line,
+            column,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+    warning::Warning,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::dbml_ast::{DbmlAst, DbmlColumn, DbmlEndpoint, DbmlSetting};
+
+pub struct GraphBuilder {
+    graph: Graph,
+    alias_map: HashMap<String, Id>, // Maps DBML table aliases to actual Node IDs
+    pending_refs: Vec<(DbmlEndpoint, String, DbmlEndpoint)>,
+    pending_groups: Vec<(Id, Vec<String>)>, // Table groups and the tables they name
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::EntityRelationship,
+                ..Default::default()
+            },
+            alias_map: HashMap::new(),
+            pending_refs: Vec::new(),
+            pending_groups: Vec::new(),
+        }
+    }
+
+    pub fn build(mut self, ast: Vec<DbmlAst>) -> Graph {
+        ast.into_iter().for_each(|node: DbmlAst| {
+            self.process_ast_node(node);
+        });
+
+        // Groups and relationships may name tables declared further down, so
+        // resolve them last
+        std::mem::take(&mut self.pending_groups)
+            .into_iter()
+            .for_each(|(group_id, tables): (Id, Vec<String>)| self.add_members(&group_id, tables));
+        std::mem::take(&mut self.pending_refs).into_iter().for_each(
+            |(from, operator, to): (DbmlEndpoint, String, DbmlEndpoint)| {
+                self.add_relationship(from, &operator, to);
+            },
+        );
+
+        self.graph
+    }
+
+    fn process_ast_node(&mut self, node: DbmlAst) {
+        match node {
+            DbmlAst::Project { name } => {
+                self.graph.metadata.title = Some(name);
+            }
+            DbmlAst::Table {
+                name,
+                alias,
+                columns,
+                note,
+            } => {
+                if let Some(a) = alias {
+                    self.alias_map.insert(a, Id::from(name.as_str()));
+                }
+
+                columns.iter().for_each(|column: &DbmlColumn| {
+                    column.settings.iter().for_each(|setting: &DbmlSetting| {
+                        if let DbmlSetting::Ref { operator, to } = setting {
+                            self.pending_refs.push((
+                                DbmlEndpoint {
+                                    table: name.clone(),
+                                    column: column.name.clone(),
+                                },
+                                operator.clone(),
+                                to.clone(),
+                            ));
+                        }
+                    });
+                });
+
+                let mut data: HashMap<Text, Value> = HashMap::new();
+                if let Some(n) = note {
+                    data.insert("note".into(), Value::String(n));
+                }
+
+                self.graph.nodes.insert(
+                    Id::from(name.as_str()),
+                    Node {
+                        id: Id::from(name.as_str()),
+                        kind: NodeKind::Entity,
+                        label: Some(name.into()),
+                        members: columns.into_iter().map(map_column).collect(),
+                        data,
+                        ..Default::default()
+                    },
+                );
+            }
+            DbmlAst::Ref { from, operator, to } => {
+                self.pending_refs.push((from, operator, to));
+            }
+            DbmlAst::Enum { name, values } => {
+                self.graph.nodes.insert(
+                    Id::from(name.as_str()),
+                    Node {
+                        id: Id::from(name.as_str()),
+                        kind: NodeKind::Enum,
+                        label: Some(name.into()),
+                        members: values
+                            .into_iter()
+                            .map(|value: String| Member {
+                                name: value,
+                                kind: MemberKind::EnumValue,
+                                ..Default::default()
+                            })
+                            .collect(),
+                        ..Default::default()
+                    },
+                );
+            }
+            DbmlAst::TableGroup { name, tables } => {
+                let group_id: Id = Uuid::new_v4().to_string().into();
+                self.graph.groups.insert(
+                    group_id.clone(),
+                    Group {
+                        id: group_id.clone(),
+                        label: Some(name.into()),
+                        ..Default::default()
+                    },
+                );
+                self.pending_groups.push((group_id, tables));
+            }
+        }
+    }
+
+    /// Places the tables a group names in it, leaving out those never declared
+    fn add_members(&mut self, group_id: &Id, tables: Vec<String>) {
+        let children: Vec<Id> = tables
+            .into_iter()
+            .filter_map(|table: String| {
+                let id: Id = self.resolve_id(&table);
+                match self.graph.nodes.get_mut(&id) {
+                    Some(node) if node.kind == NodeKind::Entity => {
+                        node.parent = Some(group_id.clone());
+                        Some(id)
+                    }
+                    _ => {
+                        self.warn(format!("Table group names unknown table `{}`", table));
+                        None
+                    }
+                }
+            })
+            .collect();
+        if let Some(group) = self.graph.groups.get_mut(group_id) {
+            group.children = children;
+        }
+    }
+
+    /// An edge between two tables, or a warning when either was never
+    /// declared
+    fn add_relationship(&mut self, from: DbmlEndpoint, operator: &str, to: DbmlEndpoint) {
+        if let Some(unknown) = [&from.table, &to.table]
+            .into_iter()
+            .find(|table: &&String| !self.is_table(table))
+        {
+            self.warn(format!(
+                "Ref `{}.{}` {} `{}.{}` names unknown table `{}`",
+                from.table, from.column, operator, to.table, to.column, unknown
+            ));
+            return;
+        }
+        let (from_cardinality, to_cardinality): (&str, &str) = match operator {
+            ">" => ("*", "1"),
+            "<" => ("1", "*"),
+            "<>" => ("*", "*"),
+            _ => ("1", "1"),
+        };
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id,
+                from: self.resolve_id(&from.table),
+                to: self.resolve_id(&to.table),
+                directed: false,
+                kind: EdgeKind::Association,
+                data: HashMap::from([
+                    ("from_column".into(), Value::String(from.column)),
+                    ("to_column".into(), Value::String(to.column)),
+                ]),
+                from_cardinality: Some(from_cardinality.into()),
+                to_cardinality: Some(to_cardinality.into()),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn is_table(&self, identifier: &str) -> bool {
+        self.graph
+            .nodes
+            .get(&self.resolve_id(identifier))
+            .is_some_and(|node: &Node| node.kind == NodeKind::Entity)
+    }
+
+    fn warn(&mut self, message: String) {
+        self.graph.warnings.push(Warning {
+            source: "dbml".to_string(),
+            message,
+            line: None,
+        });
+    }
+
+    fn resolve_id(&self, identifier: &str) -> Id {
+        self.alias_map
+            .get(identifier)
+            .cloned()
+            .unwrap_or_else(|| Id::from(identifier))
+    }
+}
+
+fn map_column(column: DbmlColumn) -> Member {
+    let data: HashMap<Text, Value> = column
+        .settings
+        .into_iter()
+        .filter_map(|setting: DbmlSetting| match setting {
+            DbmlSetting::Flag(flag) => {
+                let key: String = match flag.to_lowercase().as_str() {
+                    "pk" | "primary key" => "primary_key".to_string(),
+                    other => other.replace(' ', "_"),
+                };
+                Some((Text::from(key), Value::Bool(true)))
+            }
+            DbmlSetting::Pair { key, value } => Some((Text::from(key), Value::String(value))),
+            DbmlSetting::Ref { .. } => None,
+        })
+        .collect();
+
+    Member {
+        name: column.name,
+        kind: MemberKind::Field,
+        type_name: Some(column.column_type),
+        data,
+    }
+}
@@ -0,0 +1 @@
+pub mod excalidraw_graph_gateway;
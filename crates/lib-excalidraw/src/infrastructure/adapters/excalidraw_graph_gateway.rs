@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, ExcalidrawParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct ExcalidrawGraphGateway;
+
+impl ExcalidrawGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for ExcalidrawGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_excalidraw(input)
+            .map_err(GraphGatewayError::from)
+            .map(|scene| transformer::GraphBuilder::new().build(scene))
+    }
+}
+
+impl From<ExcalidrawParseError> for GraphGatewayError {
+    fn from(err: ExcalidrawParseError) -> Self {
+        match err {
+            ExcalidrawParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "excalidraw".into(),
+                message,
+                line,
+                column,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            geometry::{Point, Rect},
+            graph::Graph,
+            group::Group,
+            node::Node,
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::excalidraw_graph_gateway::ExcalidrawGraphGateway;
+
+    const SCENE: &str = r#"
+    {
+        "type": "excalidraw",
+        "version": 2,
+        "elements": [
+            { "id": "frame1", "type": "frame", "name": "Backend", "x": 0, "y": 0, "width": 500, "height": 300 },
+            { "id": "rect1", "type": "rectangle", "x": 10, "y": 20, "width": 100, "height": 50, "frameId": "frame1" },
+            { "id": "text1", "type": "text", "text": "API", "containerId": "rect1" },
+            { "id": "ell1", "type": "ellipse", "x": 300, "y": 20, "width": 80, "height": 80 },
+            { "id": "text2", "type": "text", "text": "DB", "containerId": "ell1" },
+            {
+                "id": "arrow1", "type": "arrow", "x": 110, "y": 45,
+                "points": [[0, 0], [190, 15]],
+                "startBinding": { "elementId": "rect1" },
+                "endBinding": { "elementId": "ell1" },
+                "endArrowhead": "arrow"
+            },
+            { "id": "text3", "type": "text", "text": "queries", "containerId": "arrow1" },
+            { "id": "arrow2", "type": "arrow", "x": 0, "y": 0, "points": [[0, 0], [10, 10]] },
+            { "id": "rect2", "type": "rectangle", "x": 0, "y": 0, "width": 1, "height": 1, "isDeleted": true }
+        ]
+    }
+    "#;
+
+    #[test]
+    fn test_parse_labeled_shapes_into_nodes() {
+        smol::block_on(async {
+            let graph: Graph = ExcalidrawGraphGateway::new()
+                .read_graph_from_raw_input(SCENE)
+                .await
+                .expect("Failed to parse valid Excalidraw scene");
+
+            assert_eq!(graph.nodes.len(), 2, "Deleted shapes must be skipped");
+
+            let api: &Node = graph.nodes.get("rect1").expect("Missing API node");
+            assert_eq!(api.label.as_deref(), Some("API"));
+            assert_eq!(
+                api.data.get("shape"),
+                Some(&Value::String("rectangle".to_string()))
+            );
+            assert_eq!(
+                api.geometry,
+                Some(Rect {
+                    x: 10.0,
+                    y: 20.0,
+                    width: 100.0,
+                    height: 50.0
+                })
+            );
+
+            let db: &Node = graph.nodes.get("ell1").expect("Missing DB node");
+            assert_eq!(db.label.as_deref(), Some("DB"));
+            assert_eq!(db.parent, None);
+        });
+    }
+
+    #[test]
+    fn test_parse_bound_arrows_into_edges() {
+        smol::block_on(async {
+            let graph: Graph = ExcalidrawGraphGateway::new()
+                .read_graph_from_raw_input(SCENE)
+                .await
+                .expect("Failed to parse valid Excalidraw scene");
+
+            assert_eq!(graph.edges.len(), 1, "Unbound arrows must be skipped");
+
+            let edge: &Edge = graph.edges.get("arrow1").expect("Missing edge");
+            assert_eq!(edge.from, "rect1");
+            assert_eq!(edge.to, "ell1");
+            assert_eq!(edge.kind, EdgeKind::Association);
+            assert!(edge.directed);
+            assert_eq!(edge.label.as_deref(), Some("queries"));
+            assert_eq!(
+                edge.waypoints,
+                vec![Point { x: 110.0, y: 45.0 }, Point { x: 300.0, y: 60.0 }]
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_frames_into_groups() {
+        smol::block_on(async {
+            let graph: Graph = ExcalidrawGraphGateway::new()
+                .read_graph_from_raw_input(SCENE)
+                .await
+                .expect("Failed to parse valid Excalidraw scene");
+
+            let group: &Group = graph.groups.get("frame1").expect("Missing frame group");
+            assert_eq!(group.label.as_deref(), Some("Backend"));
+            assert_eq!(group.children, vec!["rect1".to_string()]);
+            assert_eq!(
+                graph.nodes.get("rect1").and_then(|n| n.parent.as_deref()),
+                Some("frame1")
+            );
+        });
+    }
+
+    #[test]
+    fn test_invalid_json_is_reported_with_location() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = ExcalidrawGraphGateway::new()
+                .read_graph_from_raw_input("{\n  \"elements\": [\n    oops\n  ]\n}")
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Parse {
+                    source,
+                    line,
+                    column,
+                    ..
+                }) => {
+                    assert_eq!(source, "excalidraw");
+                    assert_eq!(line, 3);
+                    assert_eq!(column, 5);
+                }
+                other => panic!("Expected GraphGatewayError::Parse, got {:?}", other),
+            }
+        });
+    }
+}
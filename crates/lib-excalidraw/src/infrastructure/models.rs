@@ -0,0 +1 @@
+pub(crate) mod excalidraw_element;
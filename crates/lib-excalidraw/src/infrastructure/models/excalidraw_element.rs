@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExcalidrawScene {
+    #[serde(default)]
+    pub elements: Vec<ExcalidrawElement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExcalidrawElement {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub element_type: String,
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+    #[serde(default)]
+    pub width: f64,
+    #[serde(default)]
+    pub height: f64,
+    #[serde(default)]
+    pub is_deleted: bool,
+    pub text: Option<String>,
+    pub name: Option<String>,
+    pub container_id: Option<String>,
+    pub frame_id: Option<String>,
+    pub start_binding: Option<ExcalidrawBinding>,
+    pub end_binding: Option<ExcalidrawBinding>,
+    pub end_arrowhead: Option<String>,
+    #[serde(default)]
+    pub points: Vec<[f64; 2]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExcalidrawBinding {
+    pub element_id: String,
+}
@@ -0,0 +1,24 @@
+use crate::infrastructure::models::excalidraw_element::ExcalidrawScene;
+
+pub fn parse_excalidraw(input: &str) -> Result<ExcalidrawScene, ExcalidrawParseError> {
+    serde_json::from_str(input).map_err(ExcalidrawParseError::from)
+}
+
+#[derive(Debug)]
+pub enum ExcalidrawParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+}
+
+impl From<serde_json::Error> for ExcalidrawParseError {
+    fn from(err: serde_json::Error) -> Self {
+        ExcalidrawParseError::Syntax {
+            message: err.to_string(),
+            line: err.line(),
+            column: err.column(),
+        }
+    }
+}
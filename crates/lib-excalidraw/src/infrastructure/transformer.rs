@@ -0,0 +1,162 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    geometry::{Point, Rect},
+    graph::Graph,
+    group::{Group, GroupKind},
+    id::{Id, Text},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::excalidraw_element::{ExcalidrawElement, ExcalidrawScene};
+
+const SHAPE_TYPES: [&str; 3] = ["rectangle", "ellipse", "diamond"];
+
+pub struct GraphBuilder {
+    graph: Graph,
+    labels: HashMap<String, String>, // Maps container element IDs to their bound text
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                ..Default::default()
+            },
+            labels: HashMap::new(),
+        }
+    }
+
+    pub fn build(mut self, scene: ExcalidrawScene) -> Graph {
+        let elements: Vec<&ExcalidrawElement> = scene
+            .elements
+            .iter()
+            .filter(|e: &&ExcalidrawElement| !e.is_deleted)
+            .collect();
+
+        elements.iter().for_each(|e: &&ExcalidrawElement| {
+            if let (Some(container_id), Some(text)) = (&e.container_id, &e.text) {
+                self.labels.insert(container_id.clone(), text.clone());
+            }
+        });
+
+        // Frames first, so shapes can be attached to them as children
+        elements
+            .iter()
+            .filter(|e: &&&ExcalidrawElement| e.element_type == "frame")
+            .for_each(|e: &&ExcalidrawElement| self.process_frame(e));
+
+        elements
+            .iter()
+            .filter(|e: &&&ExcalidrawElement| SHAPE_TYPES.contains(&e.element_type.as_str()))
+            .for_each(|e: &&ExcalidrawElement| self.process_shape(e));
+
+        elements
+            .iter()
+            .filter(|e: &&&ExcalidrawElement| e.element_type == "arrow")
+            .for_each(|e: &&ExcalidrawElement| self.process_arrow(e));
+
+        self.graph
+    }
+
+    fn process_frame(&mut self, element: &ExcalidrawElement) {
+        self.graph.groups.insert(
+            Id::from(element.id.as_str()),
+            Group {
+                id: Id::from(element.id.as_str()),
+                kind: GroupKind::Package,
+                label: element
+                    .name
+                    .clone()
+                    .or_else(|| self.labels.get(&element.id).cloned())
+                    .map(Text::from),
+                children: Vec::new(),
+                parent: None,
+                geometry: Some(bounds_of(element)),
+                hidden: false,
+            },
+        );
+    }
+
+    fn process_shape(&mut self, element: &ExcalidrawElement) {
+        let parent: Option<Id> = element
+            .frame_id
+            .as_deref()
+            .filter(|frame_id: &&str| self.graph.groups.contains_key(*frame_id))
+            .map(Id::from);
+
+        if let Some(group) = parent.as_ref().and_then(|p| self.graph.groups.get_mut(p)) {
+            group.children.push(Id::from(element.id.as_str()));
+        }
+
+        self.graph.nodes.insert(
+            Id::from(element.id.as_str()),
+            Node {
+                id: Id::from(element.id.as_str()),
+                kind: NodeKind::Entity,
+                label: self.labels.get(&element.id).map(Text::from),
+                data: HashMap::from([(
+                    "shape".into(),
+                    Value::String(element.element_type.clone()),
+                )]),
+                parent,
+                geometry: Some(bounds_of(element)),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn process_arrow(&mut self, element: &ExcalidrawElement) {
+        // Only arrows bound to nodes on both ends carry structural meaning
+        let (Some(start), Some(end)) = (&element.start_binding, &element.end_binding) else {
+            return;
+        };
+
+        if !self.graph.nodes.contains_key(start.element_id.as_str())
+            || !self.graph.nodes.contains_key(end.element_id.as_str())
+        {
+            return;
+        }
+
+        let directed: bool = element.end_arrowhead.is_some();
+        let kind: EdgeKind = if directed {
+            EdgeKind::Association
+        } else {
+            EdgeKind::Undirected
+        };
+
+        self.graph.edges.insert(
+            Id::from(element.id.as_str()),
+            Edge {
+                id: Id::from(element.id.as_str()),
+                from: Id::from(start.element_id.as_str()),
+                to: Id::from(end.element_id.as_str()),
+                directed,
+                kind,
+                label: self.labels.get(&element.id).map(Text::from),
+                waypoints: element
+                    .points
+                    .iter()
+                    .map(|[x, y]: &[f64; 2]| Point {
+                        x: element.x + x,
+                        y: element.y + y,
+                    })
+                    .collect(),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+fn bounds_of(element: &ExcalidrawElement) -> Rect {
+    Rect {
+        x: element.x,
+        y: element.y,
+        width: element.width,
+        height: element.height,
+    }
+}
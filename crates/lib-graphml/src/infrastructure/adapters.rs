@@ -0,0 +1 @@
+pub mod graphml_graph_gateway;
@@ -0,0 +1,248 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, GraphMlParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct GraphMlGraphGateway;
+
+impl GraphMlGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for GraphMlGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_graphml(input)
+            .map_err(GraphGatewayError::from)
+            .map(|document| transformer::GraphBuilder::new().build(document))
+    }
+}
+
+impl From<GraphMlParseError> for GraphGatewayError {
+    fn from(err: GraphMlParseError) -> Self {
+        match err {
+            GraphMlParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "graphml".into(),
+                message,
+                line,
+                column,
+            },
+            GraphMlParseError::Semantic(msg) => GraphGatewayError::Semantic {
+                source: "graphml".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            geometry::{Point, Rect},
+            graph::Graph,
+            group::Group,
+            node::Node,
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::graphml_graph_gateway::GraphMlGraphGateway;
+
+    const YED_GRAPH: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+    <graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:y="http://www.yworks.com/xml/graphml">
+      <key id="d0" for="node" attr.name="color" attr.type="string"><default>yellow</default></key>
+      <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+      <key id="d2" for="node" attr.name="critical" attr.type="boolean"/>
+      <key id="d3" for="graph" attr.name="author" attr.type="string"/>
+      <key id="d6" for="node" yfiles.type="nodegraphics"/>
+      <key id="d10" for="edge" yfiles.type="edgegraphics"/>
+      <graph id="G" edgedefault="directed">
+        <data key="d3">Jane</data>
+        <node id="n0">
+          <data key="d0">green</data>
+          <data key="d2">true</data>
+          <data key="d6">
+            <y:ShapeNode>
+              <y:Geometry x="10" y="20" width="30" height="40"/>
+              <y:NodeLabel>Gateway</y:NodeLabel>
+              <y:Shape type="roundrectangle"/>
+            </y:ShapeNode>
+          </data>
+        </node>
+        <node id="n1" yfiles.foldertype="group">
+          <data key="d6">
+            <y:ProxyAutoBoundsNode>
+              <y:Realizers active="0">
+                <y:GroupNode><y:NodeLabel>Services</y:NodeLabel></y:GroupNode>
+                <y:GroupNode><y:NodeLabel>Services (closed)</y:NodeLabel></y:GroupNode>
+              </y:Realizers>
+            </y:ProxyAutoBoundsNode>
+          </data>
+          <graph id="n1:" edgedefault="undirected">
+            <node id="n1::n0"/>
+            <node id="n1::n1"/>
+            <edge id="e1" source="n1::n0" target="n1::n1"/>
+          </graph>
+        </node>
+        <edge id="e0" source="n0" target="n1::n0">
+          <data key="d1">2.5</data>
+          <data key="d10">
+            <y:PolyLineEdge>
+              <y:Path sx="0" sy="0" tx="0" ty="0"><y:Point x="50" y="60"/></y:Path>
+              <y:EdgeLabel>routes</y:EdgeLabel>
+            </y:PolyLineEdge>
+          </data>
+        </edge>
+      </graph>
+    </graphml>
+    "#;
+
+    async fn parse_graph() -> Graph {
+        GraphMlGraphGateway::new()
+            .read_graph_from_raw_input(YED_GRAPH)
+            .await
+            .expect("Failed to parse valid GraphML")
+    }
+
+    #[test]
+    fn test_parse_nodes_with_data_and_graphics() {
+        smol::block_on(async {
+            let graph: Graph = parse_graph().await;
+
+            assert_eq!(graph.nodes.len(), 3);
+            assert_eq!(
+                graph.metadata.properties.get("author").map(String::as_str),
+                Some("Jane")
+            );
+
+            let gateway: &Node = graph.nodes.get("n0").expect("Missing n0");
+            assert_eq!(gateway.label.as_deref(), Some("Gateway"));
+            assert_eq!(
+                gateway.data.get("color"),
+                Some(&Value::String("green".to_string()))
+            );
+            assert_eq!(gateway.data.get("critical"), Some(&Value::Bool(true)));
+            assert_eq!(
+                gateway.data.get("shape"),
+                Some(&Value::String("roundrectangle".to_string()))
+            );
+            assert_eq!(
+                gateway.geometry,
+                Some(Rect {
+                    x: 10.0,
+                    y: 20.0,
+                    width: 30.0,
+                    height: 40.0
+                })
+            );
+
+            let nested: &Node = graph.nodes.get("n1::n0").expect("Missing nested node");
+            assert_eq!(
+                nested.data.get("color"),
+                Some(&Value::String("yellow".to_string())),
+                "Key defaults should apply to nodes without the entry"
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_nested_graphs_into_groups() {
+        smol::block_on(async {
+            let graph: Graph = parse_graph().await;
+
+            let group: &Group = graph.groups.get("n1").expect("Missing group node");
+            assert_eq!(group.label.as_deref(), Some("Services"));
+            assert_eq!(
+                group.children,
+                vec!["n1::n0".to_string(), "n1::n1".to_string()]
+            );
+            assert_eq!(
+                graph
+                    .nodes
+                    .get("n1::n1")
+                    .and_then(|n: &Node| n.parent.as_deref()),
+                Some("n1")
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_edges_with_direction_defaults() {
+        smol::block_on(async {
+            let graph: Graph = parse_graph().await;
+
+            let routes: &Edge = graph.edges.get("e0").expect("Missing e0");
+            assert!(routes.directed);
+            assert_eq!(routes.kind, EdgeKind::Association);
+            assert_eq!(routes.label.as_deref(), Some("routes"));
+            assert_eq!(routes.data.get("weight"), Some(&Value::Number(2.5)));
+            assert_eq!(routes.waypoints, vec![Point { x: 50.0, y: 60.0 }]);
+
+            let nested: &Edge = graph.edges.get("e1").expect("Missing e1");
+            assert!(!nested.directed, "Nested graph declares undirected edges");
+            assert_eq!(nested.kind, EdgeKind::Undirected);
+        });
+    }
+
+    #[test]
+    fn test_non_graphml_documents_are_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = GraphMlGraphGateway::new()
+                .read_graph_from_raw_input("<svg></svg>")
+                .await;
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Semantic { ref source, .. }) if source == "graphml")
+            );
+        });
+    }
+
+    #[test]
+    fn test_deep_subgraphs_are_rejected() {
+        smol::block_on(async {
+            let nested = |depth: usize| -> String {
+                format!(
+                    "<graphml>\n<graph>\n{}{}</graph>\n</graphml>",
+                    (0..depth)
+                        .map(|i: usize| format!("<node id=\"n{}\">\n<graph>\n", i))
+                        .collect::<String>(),
+                    "</graph></node>".repeat(depth)
+                )
+            };
+            assert!(
+                GraphMlGraphGateway::new()
+                    .read_graph_from_raw_input(&nested(31))
+                    .await
+                    .is_ok()
+            );
+
+            match GraphMlGraphGateway::new()
+                .read_graph_from_raw_input(&nested(20_000))
+                .await
+            {
+                Err(GraphGatewayError::Parse { message, line, .. }) => {
+                    assert_eq!(message, "Elements nest past the depth limit of 64");
+                    assert_eq!(line, 65);
+                }
+                other => panic!("Expected GraphGatewayError::Parse, got {:?}", other),
+            }
+        });
+    }
+}
@@ -0,0 +1,57 @@
+use lib_core::entities::geometry::{Point, Rect};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlDocument {
+    pub keys: Vec<GraphMlKey>,
+    pub graph: GraphMlGraph,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlKey {
+    pub id: String,
+    pub domain: String,
+    pub name: Option<String>,
+    pub value_type: Option<String>,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlGraph {
+    pub directed_by_default: bool,
+    pub data: Vec<GraphMlData>,
+    pub nodes: Vec<GraphMlNode>,
+    pub edges: Vec<GraphMlEdge>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlNode {
+    pub id: String,
+    pub data: Vec<GraphMlData>,
+    pub graphics: Option<GraphMlGraphics>,
+    pub subgraph: Option<GraphMlGraph>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlEdge {
+    pub id: Option<String>,
+    pub source: String,
+    pub target: String,
+    pub directed: Option<bool>,
+    pub data: Vec<GraphMlData>,
+    pub graphics: Option<GraphMlGraphics>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlData {
+    pub key: String,
+    pub value: String,
+}
+
+/// Visual information stored by yEd in `yfiles.type` data entries
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlGraphics {
+    pub label: Option<String>,
+    pub shape: Option<String>,
+    pub geometry: Option<Rect>,
+    pub points: Vec<Point>,
+}
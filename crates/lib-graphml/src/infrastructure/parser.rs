@@ -0,0 +1,192 @@
+use lib_core::{
+    entities::geometry::{Point, Rect},
+    nesting,
+};
+use roxmltree::{Document, Node};
+
+use crate::infrastructure::models::graphml_element::{
+    GraphMlData, GraphMlDocument, GraphMlEdge, GraphMlGraph, GraphMlGraphics, GraphMlKey,
+    GraphMlNode,
+};
+
+/// How deep elements may nest, graphs within nodes included
+const MAX_DEPTH: usize = 64;
+
+pub fn parse_graphml(input: &str) -> Result<GraphMlDocument, GraphMlParseError> {
+    if let Some((line, column)) = nesting::deepest_element(input, MAX_DEPTH) {
+        return Err(GraphMlParseError::Syntax {
+            message: format!("Elements nest past the depth limit of {}", MAX_DEPTH),
+            line,
+            column,
+        });
+    }
+    let document: Document = Document::parse(input).map_err(GraphMlParseError::from)?;
+    let root: Node = document.root_element();
+
+    if root.tag_name().name() != "graphml" {
+        return Err(GraphMlParseError::Semantic(format!(
+            "Expected a <graphml> root element, found <{}>",
+            root.tag_name().name()
+        )));
+    }
+
+    let graph: GraphMlGraph = children_named(root, "graph")
+        .next()
+        .map(parse_graph)
+        .ok_or_else(|| GraphMlParseError::Semantic("Document contains no <graph>".to_string()))?;
+
+    Ok(GraphMlDocument {
+        keys: children_named(root, "key").map(parse_key).collect(),
+        graph,
+    })
+}
+
+fn parse_key(node: Node) -> GraphMlKey {
+    GraphMlKey {
+        id: node.attribute("id").unwrap_or_default().to_string(),
+        domain: node.attribute("for").unwrap_or("all").to_string(),
+        name: node.attribute("attr.name").map(str::to_string),
+        value_type: node.attribute("attr.type").map(str::to_string),
+        default: children_named(node, "default")
+            .next()
+            .map(|default: Node| text_of(default)),
+    }
+}
+
+fn parse_graph(node: Node) -> GraphMlGraph {
+    let mut graph: GraphMlGraph = GraphMlGraph {
+        directed_by_default: node.attribute("edgedefault") != Some("undirected"),
+        ..Default::default()
+    };
+
+    node.children()
+        .filter(|child: &Node| child.is_element())
+        .for_each(|child: Node| match child.tag_name().name() {
+            "data" => graph.data.extend(parse_data(child)),
+            "node" => graph.nodes.push(parse_node(child)),
+            "edge" => graph.edges.push(parse_edge(child)),
+            _ => {}
+        });
+
+    graph
+}
+
+fn parse_node(node: Node) -> GraphMlNode {
+    GraphMlNode {
+        id: node.attribute("id").unwrap_or_default().to_string(),
+        data: children_named(node, "data")
+            .filter_map(parse_data)
+            .collect(),
+        graphics: children_named(node, "data").find_map(parse_graphics),
+        subgraph: children_named(node, "graph").next().map(parse_graph),
+    }
+}
+
+fn parse_edge(node: Node) -> GraphMlEdge {
+    GraphMlEdge {
+        id: node.attribute("id").map(str::to_string),
+        source: node.attribute("source").unwrap_or_default().to_string(),
+        target: node.attribute("target").unwrap_or_default().to_string(),
+        directed: node.attribute("directed").map(|d: &str| d == "true"),
+        data: children_named(node, "data")
+            .filter_map(parse_data)
+            .collect(),
+        graphics: children_named(node, "data").find_map(parse_graphics),
+    }
+}
+
+fn parse_data(node: Node) -> Option<GraphMlData> {
+    // Data entries holding nested markup are yEd graphics, handled by `parse_graphics`
+    if node.children().any(|child: Node| child.is_element()) {
+        return None;
+    }
+
+    Some(GraphMlData {
+        key: node.attribute("key").unwrap_or_default().to_string(),
+        value: text_of(node),
+    })
+}
+
+fn parse_graphics(node: Node) -> Option<GraphMlGraphics> {
+    let realizer: Node = node.children().find(|child: &Node| child.is_element())?;
+    // Group nodes carry one realizer per folding state; the first one is the open state
+    let realizer: Node = realizer
+        .descendants()
+        .find(|d: &Node| d.has_tag_name(("http://www.yworks.com/xml/graphml", "GroupNode")))
+        .unwrap_or(realizer);
+
+    let first = |name: &str| -> Option<Node> {
+        realizer
+            .descendants()
+            .find(|d: &Node| d.is_element() && d.tag_name().name() == name)
+    };
+
+    Some(GraphMlGraphics {
+        label: first("NodeLabel")
+            .or_else(|| first("EdgeLabel"))
+            .map(text_of)
+            .filter(|label: &String| !label.is_empty()),
+        shape: first("Shape").and_then(|s: Node| s.attribute("type").map(str::to_string)),
+        geometry: first("Geometry").map(|g: Node| Rect {
+            x: number(g, "x"),
+            y: number(g, "y"),
+            width: number(g, "width"),
+            height: number(g, "height"),
+        }),
+        points: first("Path")
+            .map(|path: Node| {
+                children_named(path, "Point")
+                    .map(|p: Node| Point {
+                        x: number(p, "x"),
+                        y: number(p, "y"),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+fn number(node: Node, attribute: &str) -> f64 {
+    node.attribute(attribute)
+        .and_then(|value: &str| value.parse::<f64>().ok())
+        .unwrap_or_default()
+}
+
+fn text_of(node: Node) -> String {
+    node.descendants()
+        .filter(|d: &Node| d.is_text())
+        .filter_map(|d: Node| d.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn children_named<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child: &Node| child.is_element() && child.tag_name().name() == name)
+}
+
+#[derive(Debug)]
+pub enum GraphMlParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Semantic(String),
+}
+
+impl From<roxmltree::Error> for GraphMlParseError {
+    fn from(err: roxmltree::Error) -> Self {
+        let position: roxmltree::TextPos = err.pos();
+
+        GraphMlParseError::Syntax {
+            message: err.to_string(),
+            line: position.row as usize,
+            column: position.col as usize,
+        }
+    }
+}
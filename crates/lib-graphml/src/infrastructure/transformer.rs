@@ -0,0 +1,209 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    geometry::Rect,
+    graph::Graph,
+    group::Group,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::graphml_element::{
+    GraphMlData, GraphMlDocument, GraphMlEdge, GraphMlGraph, GraphMlGraphics, GraphMlKey,
+    GraphMlNode,
+};
+
+pub struct GraphBuilder {
+    graph: Graph,
+    keys: HashMap<String, GraphMlKey>, // Maps GraphML key IDs to their declarations
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                ..Default::default()
+            },
+            keys: HashMap::new(),
+        }
+    }
+
+    pub fn build(mut self, document: GraphMlDocument) -> Graph {
+        self.keys = document
+            .keys
+            .into_iter()
+            .map(|key: GraphMlKey| (key.id.clone(), key))
+            .collect();
+
+        document.graph.data.iter().for_each(|data: &GraphMlData| {
+            self.graph
+                .metadata
+                .properties
+                .insert(self.property_name(&data.key), data.value.clone());
+        });
+
+        self.process_graph(&document.graph, None);
+        self.graph
+    }
+
+    fn process_graph(&mut self, graph: &GraphMlGraph, parent_id: Option<String>) {
+        graph.nodes.iter().for_each(|node: &GraphMlNode| {
+            if let Some(group) = parent_id
+                .as_ref()
+                .and_then(|p: &String| self.graph.groups.get_mut(p.as_str()))
+            {
+                group.children.push(Id::from(node.id.as_str()));
+            }
+
+            let data: HashMap<Text, Value> = self.properties_of("node", &node.data);
+            let label: Option<String> = node
+                .graphics
+                .as_ref()
+                .and_then(|g: &GraphMlGraphics| g.label.clone())
+                .or_else(|| label_from(&data));
+            let geometry: Option<Rect> = node
+                .graphics
+                .as_ref()
+                .and_then(|g: &GraphMlGraphics| g.geometry);
+
+            match &node.subgraph {
+                Some(subgraph) => {
+                    self.graph.groups.insert(
+                        Id::from(node.id.as_str()),
+                        Group {
+                            id: Id::from(node.id.as_str()),
+                            label: label.map(Text::from),
+                            parent: parent_id.as_deref().map(Id::from),
+                            geometry,
+                            ..Default::default()
+                        },
+                    );
+                    self.process_graph(subgraph, Some(node.id.clone()));
+                }
+                None => {
+                    let mut data: HashMap<Text, Value> = data;
+                    if let Some(shape) = node
+                        .graphics
+                        .as_ref()
+                        .and_then(|g: &GraphMlGraphics| g.shape.clone())
+                    {
+                        data.insert("shape".into(), Value::String(shape));
+                    }
+
+                    self.graph.nodes.insert(
+                        Id::from(node.id.as_str()),
+                        Node {
+                            id: Id::from(node.id.as_str()),
+                            kind: NodeKind::Entity,
+                            label: label.map(Text::from),
+                            data,
+                            parent: parent_id.as_deref().map(Id::from),
+                            geometry,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+        });
+
+        graph.edges.iter().for_each(|edge: &GraphMlEdge| {
+            let directed: bool = edge.directed.unwrap_or(graph.directed_by_default);
+            let data: HashMap<Text, Value> = self.properties_of("edge", &edge.data);
+            let edge_id: Id = Id::from(
+                edge.id
+                    .clone()
+                    .unwrap_or_else(|| Uuid::new_v4().to_string()),
+            );
+
+            self.graph.edges.insert(
+                edge_id.clone(),
+                Edge {
+                    id: edge_id,
+                    from: Id::from(edge.source.as_str()),
+                    to: Id::from(edge.target.as_str()),
+                    directed,
+                    kind: if directed {
+                        EdgeKind::Association
+                    } else {
+                        EdgeKind::Undirected
+                    },
+                    label: edge
+                        .graphics
+                        .as_ref()
+                        .and_then(|g: &GraphMlGraphics| g.label.clone())
+                        .or_else(|| label_from(&data))
+                        .map(Text::from),
+                    data,
+                    waypoints: edge
+                        .graphics
+                        .as_ref()
+                        .map(|g: &GraphMlGraphics| g.points.clone())
+                        .unwrap_or_default(),
+                    order: self.graph.edges.len(),
+                    ..Default::default()
+                },
+            );
+        });
+    }
+
+    /// Converts `<data>` entries into typed properties, filling in key defaults for the domain
+    fn properties_of(&self, domain: &str, data: &[GraphMlData]) -> HashMap<Text, Value> {
+        let mut properties: HashMap<Text, Value> = self
+            .keys
+            .values()
+            .filter(|key: &&GraphMlKey| key.domain == domain || key.domain == "all")
+            .filter_map(|key: &GraphMlKey| {
+                key.default.as_ref().map(|default: &String| {
+                    (
+                        self.property_name(&key.id).into(),
+                        self.typed(&key.id, default),
+                    )
+                })
+            })
+            .collect();
+
+        data.iter().for_each(|entry: &GraphMlData| {
+            properties.insert(
+                self.property_name(&entry.key).into(),
+                self.typed(&entry.key, &entry.value),
+            );
+        });
+
+        properties
+    }
+
+    fn property_name(&self, key_id: &str) -> String {
+        self.keys
+            .get(key_id)
+            .and_then(|key: &GraphMlKey| key.name.clone())
+            .unwrap_or_else(|| key_id.to_string())
+    }
+
+    fn typed(&self, key_id: &str, raw: &str) -> Value {
+        let value_type: Option<&str> = self
+            .keys
+            .get(key_id)
+            .and_then(|key: &GraphMlKey| key.value_type.as_deref());
+
+        match value_type {
+            Some("boolean") => Value::Bool(raw.eq_ignore_ascii_case("true")),
+            Some("int" | "long" | "float" | "double") => raw
+                .parse::<f64>()
+                .map(Value::Number)
+                .unwrap_or_else(|_| Value::String(raw.to_string())),
+            _ => Value::String(raw.to_string()),
+        }
+    }
+}
+
+fn label_from(data: &HashMap<Text, Value>) -> Option<String> {
+    ["label", "name"]
+        .iter()
+        .find_map(|key: &&str| match data.get(*key) {
+            Some(Value::String(label)) => Some(label.clone()),
+            _ => None,
+        })
+}
@@ -0,0 +1,179 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter, GraphEmitterError},
+    entities::graph::Graph,
+};
+use serde::Serialize;
+use serde_json::{Serializer, ser::PrettyFormatter};
+
+use crate::infrastructure::emitter;
+
+#[derive(Default)]
+pub struct JsonGraphEmitter {
+    options: EmitOptions,
+}
+
+impl JsonGraphEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: EmitOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+#[async_trait]
+impl GraphEmitterAdapter for JsonGraphEmitter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError> {
+        let indent: String = self.options.indent("  ");
+        let mut output: Vec<u8> = Vec::new();
+        let mut serializer: Serializer<&mut Vec<u8>, PrettyFormatter> = Serializer::with_formatter(
+            &mut output,
+            PrettyFormatter::with_indent(indent.as_bytes()),
+        );
+        emitter::graph_to_json_with(graph, &self.options)
+            .serialize(&mut serializer)
+            .map_err(|e: serde_json::Error| GraphEmitterError::Unsupported {
+                target: "json".into(),
+                message: e.to_string(),
+            })?;
+        Ok(String::from_utf8_lossy(&output).into_owned() + "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter, Indent},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            member::Member,
+            node::Node,
+            style::Style,
+        },
+        fuzzing,
+    };
+    use pretty_assertions::assert_eq;
+    use serde_json::{Value as Json, json};
+
+    use crate::infrastructure::adapters::json_graph_emitter::JsonGraphEmitter;
+
+    #[test]
+    fn test_emits_the_model_as_json() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph {
+                kind: GraphKind::Class,
+                ..Default::default()
+            };
+            graph.nodes.insert(
+                "A".into(),
+                Node {
+                    id: "A".into(),
+                    label: Some("Account".into()),
+                    members: vec![Member {
+                        name: "id".to_string(),
+                        type_name: Some("u32".to_string()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            );
+            let edge: Edge = Edge {
+                id: "e".into(),
+                from: "A".into(),
+                to: "B".into(),
+                kind: EdgeKind::Composition,
+                to_cardinality: Some("*".into()),
+                ..Default::default()
+            };
+            graph.edges.insert("e".into(), edge);
+
+            let output: String = JsonGraphEmitter::new().emit_graph(&graph).await.unwrap();
+            let json: Json = serde_json::from_str(&output).unwrap();
+
+            assert_eq!(json["kind"], json!("class"));
+            assert_eq!(
+                json["nodes"][0],
+                json!({
+                    "id": "A",
+                    "kind": "entity",
+                    "label": "Account",
+                    "members": [{"name": "id", "kind": "field", "type": "u32", "data": {}}],
+                    "data": {},
+                    "style": null,
+                    "colors": {"fill": null, "gradient": null, "stroke": null, "text": null},
+                    "parent": null,
+                    "geometry": null,
+                    "label_anchor": null,
+                    "hidden": false,
+                    "rich_label": null,
+                    "target_edge": null,
+                })
+            );
+            assert_eq!(json["edges"][0]["kind"], json!("composition"));
+            assert_eq!(json["edges"][0]["data"], json!({}));
+            assert_eq!(json["edges"][0]["to_cardinality"], json!("*"));
+            assert_eq!(json["edges"][0]["layout"]["hidden"], json!(false));
+        });
+    }
+
+    #[test]
+    fn test_emits_every_generated_graph_as_json() {
+        smol::block_on(async {
+            for seed in 0..32 {
+                let graph: Graph = fuzzing::sample(seed);
+                let text: String = JsonGraphEmitter::new().emit_graph(&graph).await.unwrap();
+                let json: Json = serde_json::from_str(&text).unwrap();
+
+                assert_eq!(
+                    json["nodes"].as_array().map(Vec::len),
+                    Some(graph.nodes.len())
+                );
+                assert_eq!(
+                    json["edges"].as_array().map(Vec::len),
+                    Some(graph.edges.len())
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_options_change_indentation_and_drop_styles() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            graph.nodes.insert(
+                "A".into(),
+                Node {
+                    id: "A".into(),
+                    style: Some("bold".into()),
+                    ..Default::default()
+                },
+            );
+            graph.styles.insert(
+                "bold".into(),
+                Style {
+                    id: "bold".into(),
+                    properties: Default::default(),
+                },
+            );
+
+            let output: String = JsonGraphEmitter::new()
+                .with_options(EmitOptions {
+                    indent: Indent::Spaces(4),
+                    styles: false,
+                    ..Default::default()
+                })
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let json: Json = serde_json::from_str(&output).unwrap();
+
+            assert!(output.starts_with("{\n    \"activations\""), "{}", output);
+            assert_eq!(json["styles"], json!([]));
+            assert_eq!(json["nodes"][0]["style"], json!(null));
+        });
+    }
+}
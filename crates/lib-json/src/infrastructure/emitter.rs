@@ -0,0 +1,325 @@
+use std::{collections::HashMap, hash::Hash};
+
+use lib_core::{
+    adapters::graph_emitter_adapter::EmitOptions,
+    entities::{
+        edge::{Edge, EdgeDirection, EdgeKind, EdgeLabel, EdgeLabelKind},
+        geometry::{Point, Rect},
+        graph::{Graph, GraphKind, HorizontalPosition, LayoutDirection, Legend, VerticalPosition},
+        group::{Group, GroupKind},
+        member::{Member, MemberKind},
+        node::{Node, NodeKind},
+        rich_text::{Format, Line, RichText, Span},
+        sequence::{Activation, Fragment, FragmentKind, Operand},
+        style::{Color, Colors, Style},
+        value::Value,
+    },
+};
+use serde_json::{Map, Value as Json, json};
+
+/// Converts a graph into plain JSON, with elements sorted by id so the
+/// output is stable. Used by the JSON output format and the language bindings.
+pub fn graph_to_json(graph: &Graph) -> Json {
+    graph_to_json_with(graph, &EmitOptions::default())
+}
+
+/// `graph_to_json` leaving out and ordering elements as the options say.
+/// Hidden elements are kept, with their flag, so the JSON holds the whole
+/// model.
+pub fn graph_to_json_with(graph: &Graph, options: &EmitOptions) -> Json {
+    let graph: &Graph = &options.prune(graph);
+    let mut nodes: Vec<&Node> = graph.nodes.values().collect();
+    options.sort_nodes(&mut nodes);
+    let mut edges: Vec<&Edge> = graph.edges.values().collect();
+    edges.sort_by(|a: &&Edge, b: &&Edge| a.id.cmp(&b.id));
+    let mut groups: Vec<&Group> = graph.groups.values().collect();
+    options.sort_groups(&mut groups);
+    let mut styles: Vec<&Style> = graph.styles.values().collect();
+    styles.sort_by(|a: &&Style, b: &&Style| a.id.cmp(&b.id));
+    let mut fragments: Vec<&Fragment> = graph.fragments.values().collect();
+    fragments.sort_by(|a: &&Fragment, b: &&Fragment| a.id.cmp(&b.id));
+
+    json!({
+        "id": graph.id,
+        "kind": graph_kind(&graph.kind),
+        "direction": layout_direction(graph.direction),
+        "metadata": {
+            "title": graph.metadata.title,
+            "description": graph.metadata.description,
+            "properties": graph.metadata.properties,
+            "legend": graph.metadata.legend.as_ref().map(|legend: &Legend| json!({
+                "text": legend.text,
+                "vertical": match legend.vertical {
+                    VerticalPosition::Top => "top",
+                    VerticalPosition::Bottom => "bottom",
+                },
+                "horizontal": match legend.horizontal {
+                    HorizontalPosition::Left => "left",
+                    HorizontalPosition::Center => "center",
+                    HorizontalPosition::Right => "right",
+                },
+            })),
+        },
+        "nodes": nodes.iter().map(|node: &&Node| node_to_json(node)).collect::<Vec<Json>>(),
+        "edges": edges.iter().map(|edge: &&Edge| edge_to_json(edge)).collect::<Vec<Json>>(),
+        "groups": groups.iter().map(|group: &&Group| json!({
+            "id": group.id,
+            "kind": group_kind(&group.kind),
+            "label": group.label,
+            "children": group.children,
+            "parent": group.parent,
+            "geometry": group.geometry.as_ref().map(rect),
+            "hidden": group.hidden,
+        })).collect::<Vec<Json>>(),
+        "styles": styles.iter().map(|style: &&Style| json!({
+            "id": style.id,
+            "properties": style.properties,
+        })).collect::<Vec<Json>>(),
+        "fragments": fragments.iter().map(|fragment: &&Fragment| json!({
+            "id": fragment.id,
+            "kind": fragment_kind(&fragment.kind),
+            "operands": fragment.operands.iter().map(|operand: &Operand| json!({
+                "condition": operand.condition,
+                "messages": operand.messages,
+                "fragments": operand.fragments,
+            })).collect::<Vec<Json>>(),
+            "parent": fragment.parent,
+        })).collect::<Vec<Json>>(),
+        "activations": graph.activations.iter().map(|activation: &Activation| json!({
+            "participant": activation.participant,
+            "start": activation.start,
+            "end": activation.end,
+        })).collect::<Vec<Json>>(),
+    })
+}
+
+fn node_to_json(node: &Node) -> Json {
+    json!({
+        "id": node.id,
+        "kind": node_kind(&node.kind),
+        "label": node.label,
+        "members": node.members.iter().map(|member: &Member| json!({
+            "name": member.name,
+            "kind": match member.kind {
+                MemberKind::Field => "field",
+                MemberKind::Method => "method",
+                MemberKind::EnumValue => "enum_value",
+                MemberKind::Separator => "separator",
+            },
+            "type": member.type_name,
+            "data": data(&member.data),
+        })).collect::<Vec<Json>>(),
+        "data": data(&node.data),
+        "style": node.style,
+        "colors": colors(&node.colors),
+        "parent": node.parent,
+        "geometry": node.geometry.as_ref().map(rect),
+        "label_anchor": node.label_anchor.as_ref().map(point),
+        "hidden": node.hidden,
+        "rich_label": node.rich_label.as_ref().map(rich_text),
+        "target_edge": node.target_edge,
+    })
+}
+
+fn edge_to_json(edge: &Edge) -> Json {
+    json!({
+        "id": edge.id,
+        "from": edge.from,
+        "to": edge.to,
+        "directed": edge.directed,
+        "kind": edge_kind(&edge.kind),
+        "label": edge.label,
+        "data": data(&edge.data),
+        "style": edge.style,
+        "colors": colors(&edge.colors),
+        "waypoints": edge.waypoints.iter().map(point).collect::<Vec<Json>>(),
+        "layout": {
+            "direction": edge.layout.direction.map(|direction: EdgeDirection| match direction {
+                EdgeDirection::Up => "up",
+                EdgeDirection::Down => "down",
+                EdgeDirection::Left => "left",
+                EdgeDirection::Right => "right",
+            }),
+            "length": edge.layout.length,
+            "hidden": edge.layout.hidden,
+            "labels": edge.layout.labels.iter().map(|label: &EdgeLabel| json!({
+                "kind": match label.kind {
+                    EdgeLabelKind::Label => "label",
+                    EdgeLabelKind::FromCardinality => "from_cardinality",
+                    EdgeLabelKind::ToCardinality => "to_cardinality",
+                    EdgeLabelKind::FromRole => "from_role",
+                    EdgeLabelKind::ToRole => "to_role",
+                },
+                "text": label.text,
+                "bounds": rect(&label.bounds),
+            })).collect::<Vec<Json>>(),
+        },
+        "hidden": edge.hidden,
+        "order": edge.order,
+        "from_port": edge.from_port,
+        "to_port": edge.to_port,
+        "from_cardinality": edge.from_cardinality,
+        "to_cardinality": edge.to_cardinality,
+        "rich_label": edge.rich_label.as_ref().map(rich_text),
+    })
+}
+
+/// A formatted label as its lines, each span naming the formats it is in
+fn rich_text(rich: &RichText) -> Json {
+    let spans = |spans: &[Span]| -> Json {
+        Json::Array(
+            spans
+                .iter()
+                .map(|span: &Span| {
+                    let Format {
+                        bold,
+                        italic,
+                        monospace,
+                        underline,
+                        strike,
+                    } = span.format;
+                    let format: Vec<&str> = [
+                        (bold, "bold"),
+                        (italic, "italic"),
+                        (monospace, "monospace"),
+                        (underline, "underline"),
+                        (strike, "strike"),
+                    ]
+                    .into_iter()
+                    .filter_map(|(on, name): (bool, &str)| on.then_some(name))
+                    .collect();
+                    json!({ "text": span.text, "format": format })
+                })
+                .collect(),
+        )
+    };
+
+    Json::Array(
+        rich.lines
+            .iter()
+            .map(|line: &Line| match line {
+                Line::Text(line) => json!({ "kind": "text", "spans": spans(line) }),
+                Line::Bullet { depth, spans: line } => {
+                    json!({ "kind": "bullet", "depth": depth, "spans": spans(line) })
+                }
+                Line::Row { header, cells } => json!({
+                    "kind": "row",
+                    "header": header,
+                    "cells": cells.iter().map(|cell: &Vec<Span>| spans(cell)).collect::<Vec<Json>>(),
+                }),
+            })
+            .collect(),
+    )
+}
+
+pub fn graph_kind(kind: &GraphKind) -> &'static str {
+    match kind {
+        GraphKind::Generic => "generic",
+        GraphKind::Class => "class",
+        GraphKind::EntityRelationship => "entity_relationship",
+        GraphKind::Sequence => "sequence",
+        GraphKind::Network => "network",
+        GraphKind::MindMap => "mind_map",
+        GraphKind::Wbs => "wbs",
+        GraphKind::Wireframe => "wireframe",
+        GraphKind::State => "state",
+        GraphKind::Flowchart => "flowchart",
+        GraphKind::UseCase => "use_case",
+    }
+}
+
+pub fn node_kind(kind: &NodeKind) -> &str {
+    match kind {
+        NodeKind::Entity => "entity",
+        NodeKind::Interface => "interface",
+        NodeKind::Actor => "actor",
+        NodeKind::Component => "component",
+        NodeKind::Database => "database",
+        NodeKind::Enum => "enum",
+        NodeKind::Group => "group",
+        NodeKind::Annotation => "annotation",
+        NodeKind::Deployment(kind) => kind.keyword(),
+        NodeKind::Custom(kind) => kind,
+    }
+}
+
+pub fn group_kind(kind: &GroupKind) -> &str {
+    match kind {
+        GroupKind::Package => "package",
+        GroupKind::Boundary => "boundary",
+        GroupKind::Deployment(kind) => kind.keyword(),
+    }
+}
+
+pub fn edge_kind(kind: &EdgeKind) -> &str {
+    match kind {
+        EdgeKind::Association => "association",
+        EdgeKind::Dependency => "dependency",
+        EdgeKind::Inheritance => "inheritance",
+        EdgeKind::Realization => "realization",
+        EdgeKind::Aggregation => "aggregation",
+        EdgeKind::Composition => "composition",
+        EdgeKind::Flow => "flow",
+        EdgeKind::Undirected => "undirected",
+        EdgeKind::Custom(kind) => kind,
+    }
+}
+
+pub fn fragment_kind(kind: &FragmentKind) -> &'static str {
+    match kind {
+        FragmentKind::Alt => "alt",
+        FragmentKind::Opt => "opt",
+        FragmentKind::Loop => "loop",
+        FragmentKind::Par => "par",
+        FragmentKind::Break => "break",
+        FragmentKind::Critical => "critical",
+        FragmentKind::Group => "group",
+    }
+}
+
+fn layout_direction(direction: LayoutDirection) -> &'static str {
+    match direction {
+        LayoutDirection::TopToBottom => "top_to_bottom",
+        LayoutDirection::LeftToRight => "left_to_right",
+        LayoutDirection::BottomToTop => "bottom_to_top",
+        LayoutDirection::RightToLeft => "right_to_left",
+    }
+}
+
+pub fn value_to_json(value: &Value) -> Json {
+    match value {
+        Value::String(text) => json!(text),
+        Value::Number(number) => json!(number),
+        Value::Bool(flag) => json!(flag),
+        Value::List(values) => Json::Array(values.iter().map(value_to_json).collect()),
+        Value::Object(entries) => data(entries),
+    }
+}
+
+fn data<K: AsRef<str> + Ord + Hash>(entries: &HashMap<K, Value>) -> Json {
+    let mut keys: Vec<&K> = entries.keys().collect();
+    keys.sort();
+    Json::Object(
+        keys.into_iter()
+            .map(|key: &K| (key.as_ref().to_string(), value_to_json(&entries[key])))
+            .collect::<Map<String, Json>>(),
+    )
+}
+
+fn colors(colors: &Colors) -> Json {
+    let color = |color: &Option<Color>| color.as_ref().map(Color::to_string);
+    json!({
+        "fill": color(&colors.fill),
+        "gradient": color(&colors.gradient),
+        "stroke": color(&colors.stroke),
+        "text": color(&colors.text),
+    })
+}
+
+fn rect(rect: &Rect) -> Json {
+    json!({"x": rect.x, "y": rect.y, "width": rect.width, "height": rect.height})
+}
+
+fn point(point: &Point) -> Json {
+    json!({"x": point.x, "y": point.y})
+}
@@ -0,0 +1,544 @@
+use std::{collections::HashMap, hash::Hash};
+
+use lib_core::entities::{
+    edge::{Edge, EdgeDirection, EdgeKind, EdgeLabel, EdgeLabelKind, EdgeLayout},
+    geometry::{Point, Rect},
+    graph::{
+        Graph, GraphKind, HorizontalPosition, LayoutDirection, Legend, Metadata, VerticalPosition,
+    },
+    group::{Group, GroupKind},
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{DeploymentKind, Node, NodeKind},
+    rich_text::{Format, Line, RichText, Span},
+    sequence::{Activation, Fragment, FragmentKind, Operand},
+    style::{Color, Colors, Style},
+    value::Value,
+};
+use serde_json::{Map, Value as Json};
+
+use crate::infrastructure::emitter;
+
+/// Reads back what `emitter::graph_to_json` writes. Missing fields take their
+/// defaults, so hand-written documents only need ids and the relations' ends.
+pub fn graph_from_json(json: &Json) -> Result<Graph, String> {
+    let metadata: &Json = &json["metadata"];
+
+    Ok(Graph {
+        id: Id::from(json["id"].as_str().unwrap_or_default()),
+        kind: match json["kind"].as_str() {
+            Some(kind) => GRAPH_KINDS
+                .into_iter()
+                .find(|known: &GraphKind| emitter::graph_kind(known) == kind)
+                .ok_or_else(|| format!("Unknown graph kind '{}'", kind))?,
+            None => GraphKind::default(),
+        },
+        direction: match json["direction"].as_str() {
+            None | Some("top_to_bottom") => LayoutDirection::TopToBottom,
+            Some("left_to_right") => LayoutDirection::LeftToRight,
+            Some("bottom_to_top") => LayoutDirection::BottomToTop,
+            Some("right_to_left") => LayoutDirection::RightToLeft,
+            Some(other) => return Err(format!("Unknown direction '{}'", other)),
+        },
+        metadata: Metadata {
+            title: text(&metadata["title"]),
+            description: text(&metadata["description"]),
+            properties: strings(&metadata["properties"]),
+            legend: legend(&metadata["legend"]),
+        },
+        nodes: keyed(&json["nodes"], node, |node: &Node| node.id.clone())?,
+        edges: keyed(&json["edges"], edge, |edge: &Edge| edge.id.clone())?,
+        groups: keyed(&json["groups"], group, |group: &Group| group.id.clone())?,
+        styles: keyed(&json["styles"], style, |style: &Style| style.id.clone())?,
+        fragments: keyed(&json["fragments"], fragment, |fragment: &Fragment| {
+            fragment.id.clone()
+        })?,
+        activations: list(&json["activations"])
+            .iter()
+            .map(activation)
+            .collect::<Result<Vec<Activation>, String>>()?,
+        warnings: Vec::new(),
+    })
+}
+
+const GRAPH_KINDS: [GraphKind; 11] = [
+    GraphKind::Generic,
+    GraphKind::Class,
+    GraphKind::EntityRelationship,
+    GraphKind::Sequence,
+    GraphKind::Network,
+    GraphKind::MindMap,
+    GraphKind::Wbs,
+    GraphKind::Wireframe,
+    GraphKind::State,
+    GraphKind::Flowchart,
+    GraphKind::UseCase,
+];
+
+const FRAGMENT_KINDS: [FragmentKind; 7] = [
+    FragmentKind::Alt,
+    FragmentKind::Opt,
+    FragmentKind::Loop,
+    FragmentKind::Par,
+    FragmentKind::Break,
+    FragmentKind::Critical,
+    FragmentKind::Group,
+];
+
+const NODE_KINDS: [NodeKind; 8] = [
+    NodeKind::Entity,
+    NodeKind::Interface,
+    NodeKind::Actor,
+    NodeKind::Component,
+    NodeKind::Database,
+    NodeKind::Enum,
+    NodeKind::Group,
+    NodeKind::Annotation,
+];
+
+const EDGE_KINDS: [EdgeKind; 8] = [
+    EdgeKind::Association,
+    EdgeKind::Dependency,
+    EdgeKind::Inheritance,
+    EdgeKind::Realization,
+    EdgeKind::Aggregation,
+    EdgeKind::Composition,
+    EdgeKind::Flow,
+    EdgeKind::Undirected,
+];
+
+fn node(json: &Json) -> Result<Node, String> {
+    Ok(Node {
+        id: id(json, "node")?,
+        kind: match json["kind"].as_str() {
+            Some(kind) => NODE_KINDS
+                .into_iter()
+                .find(|known: &NodeKind| emitter::node_kind(known) == kind)
+                .or_else(|| DeploymentKind::from_keyword(kind).map(NodeKind::Deployment))
+                .unwrap_or_else(|| NodeKind::Custom(kind.to_string())),
+            None => NodeKind::default(),
+        },
+        label: text(&json["label"]).map(Text::from),
+        members: list(&json["members"])
+            .iter()
+            .map(member)
+            .collect::<Result<Vec<Member>, String>>()?,
+        data: data(&json["data"]),
+        style: text(&json["style"]).map(Id::from),
+        colors: colors(&json["colors"]),
+        parent: text(&json["parent"]).map(Id::from),
+        geometry: rect(&json["geometry"]),
+        label_anchor: point(&json["label_anchor"]),
+        hidden: json["hidden"].as_bool().unwrap_or_default(),
+        rich_label: rich_text(&json["rich_label"]),
+        target_edge: text(&json["target_edge"]).map(Id::from),
+    })
+}
+
+fn member(json: &Json) -> Result<Member, String> {
+    Ok(Member {
+        name: text(&json["name"]).ok_or("A member has no name")?,
+        kind: match json["kind"].as_str() {
+            None | Some("field") => MemberKind::Field,
+            Some("method") => MemberKind::Method,
+            Some("enum_value") => MemberKind::EnumValue,
+            Some("separator") => MemberKind::Separator,
+            Some(other) => return Err(format!("Unknown member kind '{}'", other)),
+        },
+        type_name: text(&json["type"]),
+        data: data(&json["data"]),
+    })
+}
+
+fn edge(json: &Json) -> Result<Edge, String> {
+    let id: Id = id(json, "edge")?;
+    let end = |key: &str| -> Result<Id, String> {
+        text(&json[key])
+            .map(Id::from)
+            .ok_or_else(|| format!("Edge '{}' has no '{}'", id, key))
+    };
+    let layout: &Json = &json["layout"];
+
+    Ok(Edge {
+        from: end("from")?,
+        to: end("to")?,
+        directed: json["directed"].as_bool().unwrap_or(true),
+        kind: match json["kind"].as_str() {
+            Some(kind) => EDGE_KINDS
+                .into_iter()
+                .find(|known: &EdgeKind| emitter::edge_kind(known) == kind)
+                .unwrap_or_else(|| EdgeKind::Custom(kind.to_string())),
+            None => EdgeKind::default(),
+        },
+        label: text(&json["label"]).map(Text::from),
+        data: data(&json["data"]),
+        style: text(&json["style"]).map(Id::from),
+        colors: colors(&json["colors"]),
+        waypoints: list(&json["waypoints"]).iter().filter_map(point).collect(),
+        layout: EdgeLayout {
+            direction: match layout["direction"].as_str() {
+                None => None,
+                Some("up") => Some(EdgeDirection::Up),
+                Some("down") => Some(EdgeDirection::Down),
+                Some("left") => Some(EdgeDirection::Left),
+                Some("right") => Some(EdgeDirection::Right),
+                Some(other) => return Err(format!("Unknown edge direction '{}'", other)),
+            },
+            length: layout["length"]
+                .as_u64()
+                .and_then(|length: u64| usize::try_from(length).ok()),
+            hidden: layout["hidden"].as_bool().unwrap_or_default(),
+            labels: list(&layout["labels"])
+                .iter()
+                .filter_map(edge_label)
+                .collect(),
+        },
+        hidden: json["hidden"].as_bool().unwrap_or_default(),
+        order: json["order"]
+            .as_u64()
+            .and_then(|order: u64| usize::try_from(order).ok())
+            .unwrap_or_default(),
+        from_port: text(&json["from_port"]),
+        to_port: text(&json["to_port"]),
+        from_cardinality: text(&json["from_cardinality"]).map(Text::from),
+        to_cardinality: text(&json["to_cardinality"]).map(Text::from),
+        rich_label: rich_text(&json["rich_label"]),
+        id,
+    })
+}
+
+fn edge_label(json: &Json) -> Option<EdgeLabel> {
+    Some(EdgeLabel {
+        kind: match json["kind"].as_str()? {
+            "label" => EdgeLabelKind::Label,
+            "from_cardinality" => EdgeLabelKind::FromCardinality,
+            "to_cardinality" => EdgeLabelKind::ToCardinality,
+            "from_role" => EdgeLabelKind::FromRole,
+            "to_role" => EdgeLabelKind::ToRole,
+            _ => return None,
+        },
+        text: text(&json["text"])?,
+        bounds: rect(&json["bounds"])?,
+    })
+}
+
+fn rich_text(json: &Json) -> Option<RichText> {
+    let spans = |json: &Json| -> Vec<Span> {
+        list(json)
+            .iter()
+            .filter_map(|span: &Json| {
+                let format: Vec<&str> = list(&span["format"])
+                    .iter()
+                    .filter_map(Json::as_str)
+                    .collect();
+                Some(Span {
+                    text: text(&span["text"])?,
+                    format: Format {
+                        bold: format.contains(&"bold"),
+                        italic: format.contains(&"italic"),
+                        monospace: format.contains(&"monospace"),
+                        underline: format.contains(&"underline"),
+                        strike: format.contains(&"strike"),
+                    },
+                })
+            })
+            .collect()
+    };
+
+    Some(RichText {
+        lines: json
+            .as_array()?
+            .iter()
+            .filter_map(|line: &Json| match line["kind"].as_str()? {
+                "text" => Some(Line::Text(spans(&line["spans"]))),
+                "bullet" => Some(Line::Bullet {
+                    depth: line["depth"]
+                        .as_u64()
+                        .and_then(|depth: u64| usize::try_from(depth).ok())
+                        .unwrap_or(1),
+                    spans: spans(&line["spans"]),
+                }),
+                "row" => Some(Line::Row {
+                    header: line["header"].as_bool().unwrap_or_default(),
+                    cells: list(&line["cells"]).iter().map(spans).collect(),
+                }),
+                _ => None,
+            })
+            .collect(),
+    })
+}
+
+fn legend(json: &Json) -> Option<Legend> {
+    Some(Legend {
+        text: text(&json["text"])?,
+        vertical: match json["vertical"].as_str() {
+            Some("top") => VerticalPosition::Top,
+            _ => VerticalPosition::Bottom,
+        },
+        horizontal: match json["horizontal"].as_str() {
+            Some("left") => HorizontalPosition::Left,
+            Some("right") => HorizontalPosition::Right,
+            _ => HorizontalPosition::Center,
+        },
+    })
+}
+
+fn group(json: &Json) -> Result<Group, String> {
+    Ok(Group {
+        id: id(json, "group")?,
+        kind: match json["kind"].as_str() {
+            Some("boundary") => GroupKind::Boundary,
+            kind => kind
+                .and_then(DeploymentKind::from_keyword)
+                .map_or(GroupKind::Package, GroupKind::Deployment),
+        },
+        label: text(&json["label"]).map(Text::from),
+        children: list(&json["children"])
+            .iter()
+            .filter_map(text)
+            .map(Id::from)
+            .collect(),
+        parent: text(&json["parent"]).map(Id::from),
+        geometry: rect(&json["geometry"]),
+        hidden: json["hidden"].as_bool().unwrap_or_default(),
+    })
+}
+
+fn fragment(json: &Json) -> Result<Fragment, String> {
+    let ids =
+        |json: &Json| -> Vec<Id> { list(json).iter().filter_map(text).map(Id::from).collect() };
+
+    Ok(Fragment {
+        id: id(json, "fragment")?,
+        kind: match json["kind"].as_str() {
+            Some(kind) => FRAGMENT_KINDS
+                .into_iter()
+                .find(|known: &FragmentKind| emitter::fragment_kind(known) == kind)
+                .ok_or_else(|| format!("Unknown fragment kind '{}'", kind))?,
+            None => FragmentKind::default(),
+        },
+        operands: list(&json["operands"])
+            .iter()
+            .map(|operand: &Json| Operand {
+                condition: text(&operand["condition"]),
+                messages: ids(&operand["messages"]),
+                fragments: ids(&operand["fragments"]),
+            })
+            .collect(),
+        parent: text(&json["parent"]).map(Id::from),
+    })
+}
+
+fn activation(json: &Json) -> Result<Activation, String> {
+    Ok(Activation {
+        participant: text(&json["participant"])
+            .map(Id::from)
+            .ok_or("An activation has no participant")?,
+        start: text(&json["start"]).map(Id::from),
+        end: text(&json["end"]).map(Id::from),
+    })
+}
+
+fn style(json: &Json) -> Result<Style, String> {
+    Ok(Style {
+        id: id(json, "style")?,
+        properties: strings(&json["properties"]),
+    })
+}
+
+fn id(json: &Json, what: &str) -> Result<Id, String> {
+    text(&json["id"])
+        .map(Id::from)
+        .ok_or_else(|| format!("A {} has no id", what))
+}
+
+fn keyed<T>(
+    json: &Json,
+    read: impl Fn(&Json) -> Result<T, String>,
+    id: impl Fn(&T) -> Id,
+) -> Result<HashMap<Id, T>, String> {
+    list(json)
+        .iter()
+        .map(|item: &Json| read(item).map(|item: T| (id(&item), item)))
+        .collect()
+}
+
+fn list(json: &Json) -> &[Json] {
+    json.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+fn text(json: &Json) -> Option<String> {
+    json.as_str().map(str::to_string)
+}
+
+fn strings(json: &Json) -> HashMap<String, String> {
+    json.as_object()
+        .map(|entries: &Map<String, Json>| {
+            entries
+                .iter()
+                .filter_map(|(key, value): (&String, &Json)| Some((key.clone(), text(value)?)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn data<K: From<String> + Eq + Hash>(json: &Json) -> HashMap<K, Value> {
+    json.as_object()
+        .map(|entries: &Map<String, Json>| {
+            entries
+                .iter()
+                .filter_map(|(key, value): (&String, &Json)| {
+                    Some((K::from(key.clone()), self::value(value)?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn value(json: &Json) -> Option<Value> {
+    match json {
+        Json::Null => None,
+        Json::Bool(flag) => Some(Value::Bool(*flag)),
+        Json::Number(number) => number.as_f64().map(Value::Number),
+        Json::String(text) => Some(Value::String(text.clone())),
+        Json::Array(values) => Some(Value::List(values.iter().filter_map(value).collect())),
+        Json::Object(_) => Some(Value::Object(data(json))),
+    }
+}
+
+fn colors(json: &Json) -> Colors {
+    let color = |key: &str| json[key].as_str().and_then(Color::parse);
+    Colors {
+        fill: color("fill"),
+        gradient: color("gradient"),
+        stroke: color("stroke"),
+        text: color("text"),
+    }
+}
+
+fn rect(json: &Json) -> Option<Rect> {
+    Some(Rect {
+        x: json["x"].as_f64()?,
+        y: json["y"].as_f64()?,
+        width: json["width"].as_f64()?,
+        height: json["height"].as_f64()?,
+    })
+}
+
+fn point(json: &Json) -> Option<Point> {
+    Some(Point {
+        x: json["x"].as_f64()?,
+        y: json["y"].as_f64()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{entities::graph::Graph, fuzzing, testing};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_reads_back_what_the_emitter_writes() {
+        (0..64).map(fuzzing::sample).for_each(|graph: Graph| {
+            let json: Json = emitter::graph_to_json(&graph);
+
+            testing::assert_graph_eq(&graph, &graph_from_json(&json).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_reads_back_fragments_and_activations() {
+        let mut graph: Graph = fuzzing::sample(3);
+        graph.fragments.insert(
+            "f".into(),
+            Fragment {
+                id: "f".into(),
+                kind: FragmentKind::Loop,
+                operands: vec![Operand {
+                    condition: Some("retry".to_string()),
+                    messages: vec!["m1".into()],
+                    fragments: vec!["g".into()],
+                }],
+                parent: None,
+            },
+        );
+        graph.activations.push(Activation {
+            participant: "A".into(),
+            start: Some("m1".into()),
+            end: None,
+        });
+
+        let json: Json = emitter::graph_to_json(&graph);
+
+        assert_eq!(json["fragments"][0]["kind"], json!("loop"));
+        testing::assert_graph_eq(&graph, &graph_from_json(&json).unwrap());
+    }
+
+    #[test]
+    fn test_reads_back_formatted_labels() {
+        let mut graph: Graph = fuzzing::sample(5);
+        let bold: Span = Span {
+            text: "Order".to_string(),
+            format: Format {
+                bold: true,
+                ..Format::default()
+            },
+        };
+        graph.nodes.values_mut().for_each(|node: &mut Node| {
+            node.rich_label = Some(RichText {
+                lines: vec![
+                    Line::Text(vec![bold.clone()]),
+                    Line::Bullet {
+                        depth: 2,
+                        spans: vec![bold.clone()],
+                    },
+                    Line::Row {
+                        header: true,
+                        cells: vec![vec![bold.clone()], Vec::new()],
+                    },
+                ],
+            });
+        });
+
+        let json: Json = emitter::graph_to_json(&graph);
+
+        testing::assert_graph_eq(&graph, &graph_from_json(&json).unwrap());
+    }
+
+    #[test]
+    fn test_fills_in_defaults_and_rejects_unknown_kinds() {
+        let graph: Graph = graph_from_json(&json!({
+            "nodes": [{"id": "A"}, {"id": "B", "kind": "widget"}, {"id": "C", "kind": "queue"}],
+            "edges": [{"id": "e", "from": "A", "to": "B"}],
+            "groups": [{"id": "g", "kind": "cloud"}, {"id": "h"}, {"id": "i", "kind": "boundary"}],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            graph.nodes["B"].kind,
+            NodeKind::Custom("widget".to_string())
+        );
+        assert_eq!(
+            graph.nodes["C"].kind,
+            NodeKind::Deployment(DeploymentKind::Queue)
+        );
+        assert_eq!(
+            graph.groups["g"].kind,
+            GroupKind::Deployment(DeploymentKind::Cloud)
+        );
+        assert_eq!(graph.groups["h"].kind, GroupKind::Package);
+        assert_eq!(graph.groups["i"].kind, GroupKind::Boundary);
+        assert_eq!(graph.edges["e"].kind, EdgeKind::Association);
+        assert_eq!(
+            graph_from_json(&json!({"kind": "gantt"})),
+            Err("Unknown graph kind 'gantt'".to_string())
+        );
+        assert_eq!(
+            graph_from_json(&json!({"edges": [{"id": "e", "from": "A"}]})),
+            Err("Edge 'e' has no 'to'".to_string())
+        );
+    }
+}
@@ -0,0 +1,12 @@
+pub mod adapters;
+pub(crate) mod force;
+pub(crate) mod geometry;
+pub(crate) mod labels;
+pub(crate) mod layered;
+pub(crate) mod measure;
+pub(crate) mod nested;
+pub(crate) mod orientation;
+pub(crate) mod orthogonal;
+#[cfg(test)]
+pub(crate) mod testing;
+pub(crate) mod tree;
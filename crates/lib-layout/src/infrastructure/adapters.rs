@@ -0,0 +1,6 @@
+pub mod automatic_layout_engine;
+pub mod force_layout_engine;
+pub mod label_placement_engine;
+pub mod layered_layout_engine;
+pub mod orthogonal_edge_router;
+pub mod tree_layout_engine;
@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+    entities::{
+        edge::{Edge, EdgeKind},
+        graph::{Graph, GraphKind},
+    },
+};
+
+use crate::infrastructure::{
+    force::ForceLayout,
+    layered::LayeredLayout,
+    nested::NestedLayout,
+    tree::{Forest, TreeLayout},
+};
+
+/// Picks a layout from the graph kind and shape: trees for mindmaps, WBS and
+/// inheritance-only class hierarchies, force-directed for networks and mostly
+/// undirected graphs, and layered otherwise
+#[derive(Default)]
+pub struct AutomaticLayoutEngine {
+    layered: LayeredLayout,
+    force: ForceLayout,
+    tree: TreeLayout,
+    nested: NestedLayout,
+}
+
+impl AutomaticLayoutEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn apply(&self, graph: &mut Graph) {
+        let hierarchy_only: bool = !graph.edges.is_empty()
+            && graph
+                .edges
+                .values()
+                .all(|e: &Edge| matches!(e.kind, EdgeKind::Inheritance | EdgeKind::Realization));
+        let undirected: usize = graph.edges.values().filter(|e: &&Edge| !e.directed).count();
+
+        let forest: Option<Forest> = match graph.kind {
+            GraphKind::MindMap | GraphKind::Wbs => Forest::from_graph(graph),
+            GraphKind::Class if hierarchy_only => Forest::from_graph(graph),
+            _ => None,
+        };
+
+        match forest {
+            Some(forest) => self.tree.apply(graph, &forest),
+            None if graph.kind == GraphKind::Network || undirected * 2 > graph.edges.len() => {
+                self.force.apply(graph)
+            }
+            None => self.layered.apply(graph),
+        }
+    }
+}
+
+#[async_trait]
+impl LayoutEngineAdapter for AutomaticLayoutEngine {
+    async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError> {
+        // Each cluster gets its own layout choice
+        self.nested
+            .apply(graph, &|level: &mut Graph| self.apply(level));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::layout_engine_adapter::LayoutEngineAdapter,
+        entities::{
+            edge::EdgeKind,
+            graph::{Graph, GraphKind},
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::{
+        automatic_layout_engine::AutomaticLayoutEngine, force_layout_engine::ForceLayoutEngine,
+        layered_layout_engine::LayeredLayoutEngine, tree_layout_engine::TreeLayoutEngine,
+    };
+    use crate::infrastructure::testing::graph_of;
+
+    #[test]
+    fn test_mindmaps_use_tree_layout() {
+        smol::block_on(async {
+            let graph: Graph = graph(
+                GraphKind::MindMap,
+                &[
+                    ("Root", "A", EdgeKind::Association),
+                    ("Root", "B", EdgeKind::Association),
+                ],
+            );
+
+            assert_eq!(
+                layout(&AutomaticLayoutEngine::new(), &graph).await,
+                layout(&TreeLayoutEngine::new(), &graph).await
+            );
+        });
+    }
+
+    #[test]
+    fn test_inheritance_hierarchies_use_tree_layout() {
+        smol::block_on(async {
+            let graph: Graph = graph(
+                GraphKind::Class,
+                &[
+                    ("Cat", "Animal", EdgeKind::Inheritance),
+                    ("Dog", "Animal", EdgeKind::Inheritance),
+                ],
+            );
+
+            assert_eq!(
+                layout(&AutomaticLayoutEngine::new(), &graph).await,
+                layout(&TreeLayoutEngine::new(), &graph).await
+            );
+        });
+    }
+
+    #[test]
+    fn test_class_diagrams_with_associations_use_layered_layout() {
+        smol::block_on(async {
+            let graph: Graph = graph(
+                GraphKind::Class,
+                &[
+                    ("Cat", "Animal", EdgeKind::Inheritance),
+                    ("Cat", "Food", EdgeKind::Dependency),
+                ],
+            );
+
+            assert_eq!(
+                layout(&AutomaticLayoutEngine::new(), &graph).await,
+                layout(&LayeredLayoutEngine::new(), &graph).await
+            );
+        });
+    }
+
+    #[test]
+    fn test_networks_use_force_layout() {
+        smol::block_on(async {
+            let graph: Graph = graph(
+                GraphKind::Network,
+                &[
+                    ("Router", "Web", EdgeKind::Undirected),
+                    ("Router", "Db", EdgeKind::Undirected),
+                ],
+            );
+
+            assert_eq!(
+                layout(&AutomaticLayoutEngine::new(), &graph).await,
+                layout(&ForceLayoutEngine::new(), &graph).await
+            );
+        });
+    }
+
+    async fn layout(engine: &dyn LayoutEngineAdapter, graph: &Graph) -> Graph {
+        let mut graph: Graph = graph.clone();
+        engine.layout(&mut graph).await.unwrap();
+        graph
+    }
+
+    fn graph(kind: GraphKind, edges: &[(&str, &str, EdgeKind)]) -> Graph {
+        Graph {
+            kind,
+            ..graph_of(edges)
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{force::ForceLayout, nested::NestedLayout};
+
+#[derive(Default)]
+pub struct ForceLayoutEngine {
+    layout: ForceLayout,
+    nested: NestedLayout,
+}
+
+impl ForceLayoutEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LayoutEngineAdapter for ForceLayoutEngine {
+    async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError> {
+        self.nested
+            .apply(graph, &|level: &mut Graph| self.layout.apply(level));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::layout_engine_adapter::LayoutEngineAdapter,
+        entities::{
+            geometry::{Point, Rect},
+            graph::Graph,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::force_layout_engine::ForceLayoutEngine;
+    use crate::infrastructure::testing::{graph, rect};
+
+    #[test]
+    fn test_nodes_do_not_overlap() {
+        smol::block_on(async {
+            let ids: Vec<String> = (0..12).map(|i: usize| format!("N{}", i)).collect();
+            let edges: Vec<(String, String)> = (0..12)
+                .map(|i: usize| (ids[i].clone(), ids[(i + 1) % 12].clone()))
+                .chain([(ids[0].clone(), ids[6].clone())])
+                .collect();
+            let mut graph: Graph = graph(&ids, &edges);
+
+            ForceLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let rects: Vec<Rect> = ids.iter().map(|id: &String| rect(&graph, id)).collect();
+            for (i, a) in rects.iter().enumerate() {
+                for b in &rects[i + 1..] {
+                    let overlapping: bool = a.x < b.x + b.width
+                        && b.x < a.x + a.width
+                        && a.y < b.y + b.height
+                        && b.y < a.y + a.height;
+                    assert!(!overlapping, "{:?} overlaps {:?}", a, b);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_connected_nodes_are_kept_close() {
+        smol::block_on(async {
+            let ids: Vec<String> = ["A", "B", "C", "D", "E"].map(String::from).to_vec();
+            let edges: Vec<(String, String)> = ids
+                .windows(2)
+                .map(|pair: &[String]| (pair[0].clone(), pair[1].clone()))
+                .collect();
+            let mut graph: Graph = graph(&ids, &edges);
+
+            ForceLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let distance = |a: &str, b: &str| -> f64 {
+                let (a, b): (Point, Point) = (rect(&graph, a).center(), rect(&graph, b).center());
+                ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+            };
+            assert!(distance("A", "B") < distance("A", "E"));
+            assert!(distance("B", "C") < distance("B", "E"));
+        });
+    }
+
+    #[test]
+    fn test_edges_connect_node_borders_and_layout_is_deterministic() {
+        smol::block_on(async {
+            let ids: Vec<String> = ["A", "B", "C"].map(String::from).to_vec();
+            let edges: Vec<(String, String)> = vec![
+                ("A".to_string(), "B".to_string()),
+                ("B".to_string(), "C".to_string()),
+            ];
+            let mut first: Graph = graph(&ids, &edges);
+            let mut second: Graph = first.clone();
+
+            ForceLayoutEngine::new().layout(&mut first).await.unwrap();
+            ForceLayoutEngine::new().layout(&mut second).await.unwrap();
+
+            assert_eq!(first, second);
+
+            let a: Rect = rect(&first, "A");
+            let start: Point = first.edges["A-B"].waypoints[0];
+            assert_eq!(first.edges["A-B"].waypoints.len(), 2);
+            let on_vertical_border: bool =
+                (start.x - a.x).abs() < 1e-6 || (start.x - (a.x + a.width)).abs() < 1e-6;
+            let on_horizontal_border: bool =
+                (start.y - a.y).abs() < 1e-6 || (start.y - (a.y + a.height)).abs() < 1e-6;
+            assert!(on_vertical_border || on_horizontal_border);
+        });
+    }
+}
@@ -0,0 +1,256 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+    entities::{graph::Graph, node::Node},
+};
+
+use crate::infrastructure::labels::LabelPlacer;
+
+/// Places edge labels, cardinalities and roles on an already laid out and
+/// routed graph, and anchors node labels for renderers
+#[derive(Default)]
+pub struct LabelPlacementEngine {
+    placer: LabelPlacer,
+}
+
+impl LabelPlacementEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LayoutEngineAdapter for LabelPlacementEngine {
+    async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError> {
+        if let Some(node) = graph
+            .nodes
+            .values()
+            .find(|node: &&Node| node.geometry.is_none())
+        {
+            return Err(LayoutEngineError::Unsupported {
+                engine: "labels".into(),
+                message: format!(
+                    "Node '{}' has no geometry, labels can only be placed once nodes are laid out",
+                    node.id
+                ),
+            });
+        }
+
+        self.placer.apply(graph);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use lib_core::{
+        adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+        entities::{
+            edge::{Edge, EdgeLabel, EdgeLabelKind},
+            geometry::{Point, Rect},
+            graph::Graph,
+            id::{Id, Text},
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::label_placement_engine::LabelPlacementEngine;
+    use crate::infrastructure::testing::placed;
+
+    #[test]
+    fn test_edge_labels_are_placed_beside_the_middle_of_the_edge() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&[("A", 0.0, 0.0), ("B", 0.0, 200.0)], &[("A", "B")]);
+            graph.edges.get_mut("A-B").unwrap().label = Some("uses".into());
+
+            LabelPlacementEngine::new()
+                .layout(&mut graph)
+                .await
+                .unwrap();
+
+            let labels: &Vec<EdgeLabel> = &graph.edges["A-B"].layout.labels;
+            assert_eq!(labels.len(), 1);
+            assert_eq!(labels[0].kind, EdgeLabelKind::Label);
+            assert_eq!(labels[0].text, "uses");
+            let bounds: Rect = labels[0].bounds;
+            assert_eq!(bounds.center().y, 115.0);
+            assert!(
+                bounds.x > 40.0 || bounds.x + bounds.width < 40.0,
+                "Label should not cover the edge"
+            );
+        });
+    }
+
+    #[test]
+    fn test_cardinalities_and_roles_are_placed_near_their_end() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&[("A", 0.0, 0.0), ("B", 0.0, 200.0)], &[("A", "B")]);
+            let edge: &mut Edge = graph.edges.get_mut("A-B").unwrap();
+            edge.from_cardinality = Some("1".into());
+            edge.to_cardinality = Some("0..*".into());
+            [("from_role", "owner"), ("to_role", "items")]
+                .iter()
+                .for_each(|(key, value): &(&str, &str)| {
+                    edge.data
+                        .insert(Text::from(*key), Value::String(value.to_string()));
+                });
+
+            LabelPlacementEngine::new()
+                .layout(&mut graph)
+                .await
+                .unwrap();
+
+            let labels: &Vec<EdgeLabel> = &graph.edges["A-B"].layout.labels;
+            let bounds = |kind: EdgeLabelKind| -> Rect {
+                labels
+                    .iter()
+                    .find(|label: &&EdgeLabel| label.kind == kind)
+                    .map(|label: &EdgeLabel| label.bounds)
+                    .expect("Missing label")
+            };
+            let (from_cardinality, to_cardinality, from_role, to_role): (Rect, Rect, Rect, Rect) = (
+                bounds(EdgeLabelKind::FromCardinality),
+                bounds(EdgeLabelKind::ToCardinality),
+                bounds(EdgeLabelKind::FromRole),
+                bounds(EdgeLabelKind::ToRole),
+            );
+            assert!(from_cardinality.center().y < 100.0);
+            assert!(from_role.center().y < 100.0);
+            assert!(to_cardinality.center().y > 130.0);
+            assert!(to_role.center().y > 130.0);
+            assert!(
+                (from_cardinality.center().x - 40.0).signum()
+                    != (from_role.center().x - 40.0).signum(),
+                "Cardinality and role should sit on both sides of the edge"
+            );
+            labels
+                .iter()
+                .enumerate()
+                .for_each(|(i, a): (usize, &EdgeLabel)| {
+                    labels.iter().skip(i + 1).for_each(|b: &EdgeLabel| {
+                        assert!(!overlaps(&a.bounds, &b.bounds), "{:?} overlaps {:?}", a, b);
+                    });
+                    graph.nodes.values().for_each(|node: &Node| {
+                        assert!(!overlaps(&a.bounds, &node.geometry.unwrap()));
+                    });
+                });
+        });
+    }
+
+    #[test]
+    fn test_labels_of_neighbouring_edges_do_not_overlap() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(
+                &[
+                    ("A", 0.0, 0.0),
+                    ("B", 0.0, 200.0),
+                    ("C", 60.0, 0.0),
+                    ("D", 60.0, 200.0),
+                ],
+                &[("A", "B"), ("C", "D")],
+            );
+            graph.nodes.values_mut().for_each(|node: &mut Node| {
+                node.geometry.as_mut().unwrap().width = 20.0;
+            });
+            graph.edges.values_mut().for_each(|edge: &mut Edge| {
+                edge.label = Some("a long label".into());
+            });
+
+            LabelPlacementEngine::new()
+                .layout(&mut graph)
+                .await
+                .unwrap();
+
+            let (first, second): (Rect, Rect) = (
+                graph.edges["A-B"].layout.labels[0].bounds,
+                graph.edges["C-D"].layout.labels[0].bounds,
+            );
+            assert!(
+                !overlaps(&first, &second),
+                "{:?} overlaps {:?}",
+                first,
+                second
+            );
+        });
+    }
+
+    #[test]
+    fn test_note_connectors_are_straight_and_node_labels_anchored() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(
+                &[("N", 200.0, 0.0), ("A", 0.0, 100.0), ("Actor", 0.0, 300.0)],
+                &[("N", "A")],
+            );
+            graph.nodes.get_mut("N").unwrap().kind = NodeKind::Annotation;
+            graph.nodes.get_mut("Actor").unwrap().kind = NodeKind::Actor;
+            graph.edges.get_mut("N-A").unwrap().waypoints = vec![
+                Point { x: 240.0, y: 30.0 },
+                Point { x: 240.0, y: 115.0 },
+                Point { x: 80.0, y: 115.0 },
+            ];
+
+            LabelPlacementEngine::new()
+                .layout(&mut graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                graph.edges["N-A"].waypoints,
+                vec![Point { x: 210.0, y: 30.0 }, Point { x: 70.0, y: 100.0 }]
+            );
+            assert_eq!(
+                graph.nodes["A"].label_anchor,
+                Some(Point { x: 40.0, y: 115.0 })
+            );
+            assert_eq!(
+                graph.nodes["Actor"].label_anchor,
+                Some(Point { x: 40.0, y: 338.0 })
+            );
+        });
+    }
+
+    #[test]
+    fn test_requires_placed_nodes() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&[("A", 0.0, 0.0)], &[]);
+            graph.nodes.get_mut("A").unwrap().geometry = None;
+
+            let result: Result<(), LayoutEngineError> =
+                LabelPlacementEngine::new().layout(&mut graph).await;
+
+            assert!(matches!(result, Err(LayoutEngineError::Unsupported { .. })));
+        });
+    }
+
+    /// Edges run straight down between the borders of the placed nodes
+    fn graph(nodes: &[(&str, f64, f64)], edges: &[(&str, &str)]) -> Graph {
+        let mut graph: Graph = placed(nodes, edges);
+        let rects: HashMap<Id, Rect> = graph
+            .nodes
+            .values()
+            .filter_map(|node: &Node| Some((node.id.clone(), node.geometry?)))
+            .collect();
+        graph.edges.values_mut().for_each(|edge: &mut Edge| {
+            let (from, to): (Rect, Rect) = (rects[&edge.from], rects[&edge.to]);
+            edge.waypoints = vec![
+                Point {
+                    x: from.center().x,
+                    y: from.y + from.height,
+                },
+                Point {
+                    x: to.center().x,
+                    y: to.y,
+                },
+            ];
+        });
+        graph
+    }
+
+    fn overlaps(a: &Rect, b: &Rect) -> bool {
+        a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+    }
+}
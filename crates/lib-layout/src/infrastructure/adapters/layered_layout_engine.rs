@@ -0,0 +1,337 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{layered::LayeredLayout, nested::NestedLayout};
+
+#[derive(Default)]
+pub struct LayeredLayoutEngine {
+    layout: LayeredLayout,
+    nested: NestedLayout,
+}
+
+impl LayeredLayoutEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LayoutEngineAdapter for LayeredLayoutEngine {
+    async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError> {
+        self.nested
+            .apply(graph, &|level: &mut Graph| self.layout.apply(level));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::layout_engine_adapter::LayoutEngineAdapter,
+        entities::{
+            edge::{EdgeDirection, EdgeKind},
+            geometry::{Point, Rect},
+            graph::{Graph, LayoutDirection},
+            group::Group,
+            id::{Id, Text},
+            node::Node,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::layered_layout_engine::LayeredLayoutEngine;
+    use crate::infrastructure::testing::{add_edge, edge, edge_mut, graph, rect};
+
+    #[test]
+    fn test_ranks_flow_top_to_bottom() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B", "C"], &[("A", "B"), ("B", "C")]);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (a, b, c): (Rect, Rect, Rect) =
+                (rect(&graph, "A"), rect(&graph, "B"), rect(&graph, "C"));
+            assert!(a.y + a.height < b.y);
+            assert!(b.y + b.height < c.y);
+            assert_eq!(a.center().x, b.center().x);
+            assert_eq!(
+                edge(&graph, "A", "B").waypoints,
+                vec![
+                    Point {
+                        x: a.center().x,
+                        y: a.y + a.height
+                    },
+                    Point {
+                        x: b.center().x,
+                        y: b.y
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_parents_are_placed_above_children() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["Animal", "Cat", "Dog"], &[]);
+            add_edge(&mut graph, "Cat", "Animal", EdgeKind::Inheritance);
+            add_edge(&mut graph, "Dog", "Animal", EdgeKind::Inheritance);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (animal, cat, dog): (Rect, Rect, Rect) = (
+                rect(&graph, "Animal"),
+                rect(&graph, "Cat"),
+                rect(&graph, "Dog"),
+            );
+            assert!(animal.y < cat.y);
+            assert_eq!(cat.y, dog.y);
+            assert!(cat.x + cat.width <= dog.x || dog.x + dog.width <= cat.x);
+
+            let waypoints: &Vec<Point> = &edge(&graph, "Cat", "Animal").waypoints;
+            assert_eq!(waypoints.first().map(|p: &Point| p.y), Some(cat.y));
+            assert_eq!(
+                waypoints.last().map(|p: &Point| p.y),
+                Some(animal.y + animal.height)
+            );
+        });
+    }
+
+    #[test]
+    fn test_long_edges_are_routed_through_intermediate_ranks() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B", "C"], &[("A", "B"), ("B", "C"), ("A", "C")]);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            assert_eq!(edge(&graph, "A", "C").waypoints.len(), 3);
+            let (b, bend): (Rect, Point) = (rect(&graph, "B"), edge(&graph, "A", "C").waypoints[1]);
+            assert!(
+                bend.x < b.x || bend.x > b.x + b.width,
+                "Long edge should not cross node B"
+            );
+        });
+    }
+
+    #[test]
+    fn test_crossings_are_minimized() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B", "C", "D"], &[("A", "D"), ("B", "C")]);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            assert!(rect(&graph, "A").x < rect(&graph, "B").x);
+            assert!(rect(&graph, "D").x < rect(&graph, "C").x);
+        });
+    }
+
+    #[test]
+    fn test_cycles_are_laid_out() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(
+                &["A", "B", "C"],
+                &[("A", "B"), ("B", "C"), ("C", "A"), ("A", "A")],
+            );
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            assert!(graph.nodes.values().all(|n: &Node| n.geometry.is_some()));
+            let waypoints: &Vec<Point> = &edge(&graph, "C", "A").waypoints;
+            assert_eq!(
+                waypoints.first().map(|p: &Point| p.y),
+                Some(rect(&graph, "C").y),
+                "Reversed edges still start at their source"
+            );
+        });
+    }
+
+    #[test]
+    fn test_graph_direction_is_respected() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B", "C"], &[("A", "B"), ("B", "C")]);
+            graph.direction = LayoutDirection::LeftToRight;
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (a, b, c): (Rect, Rect, Rect) =
+                (rect(&graph, "A"), rect(&graph, "B"), rect(&graph, "C"));
+            assert!(a.x + a.width < b.x);
+            assert!(b.x + b.width < c.x);
+            assert_eq!(a.center().y, b.center().y);
+            assert_eq!(
+                edge(&graph, "A", "B").waypoints,
+                vec![
+                    Point {
+                        x: a.x + a.width,
+                        y: a.center().y
+                    },
+                    Point {
+                        x: b.x,
+                        y: b.center().y
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_bottom_to_top_direction_is_respected() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B"], &[("A", "B")]);
+            graph.direction = LayoutDirection::BottomToTop;
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (a, b): (Rect, Rect) = (rect(&graph, "A"), rect(&graph, "B"));
+            assert!(b.y + b.height < a.y);
+            assert!(b.y >= 0.0);
+        });
+    }
+
+    #[test]
+    fn test_edge_direction_hints_are_respected() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B", "C"], &[("A", "B"), ("A", "C")]);
+            edge_mut(&mut graph, "A", "B").layout.direction = Some(EdgeDirection::Up);
+            edge_mut(&mut graph, "A", "C").layout.direction = Some(EdgeDirection::Right);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (a, b, c): (Rect, Rect, Rect) =
+                (rect(&graph, "A"), rect(&graph, "B"), rect(&graph, "C"));
+            assert!(b.y + b.height < a.y, "B should be above A");
+            assert_eq!(a.center().y, c.center().y);
+            assert!(a.x + a.width < c.x, "C should be right of A");
+            assert_eq!(
+                edge(&graph, "A", "C").waypoints,
+                vec![
+                    Point {
+                        x: a.x + a.width,
+                        y: a.center().y
+                    },
+                    Point {
+                        x: c.x,
+                        y: c.center().y
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_edge_length_hints_add_ranks() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B"], &[("A", "B")]);
+            edge_mut(&mut graph, "A", "B").layout.length = Some(3);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            assert_eq!(edge(&graph, "A", "B").waypoints.len(), 4);
+            let (a, b): (Rect, Rect) = (rect(&graph, "A"), rect(&graph, "B"));
+            assert!(b.y - (a.y + a.height) >= 3.0 * 60.0);
+        });
+    }
+
+    #[test]
+    fn test_hidden_edges_shape_the_layout_without_being_drawn() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B"], &[("A", "B")]);
+            edge_mut(&mut graph, "A", "B").layout.hidden = true;
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            assert!(rect(&graph, "A").y + rect(&graph, "A").height < rect(&graph, "B").y);
+            assert_eq!(edge(&graph, "A", "B").waypoints, Vec::<Point>::new());
+        });
+    }
+
+    #[test]
+    fn test_parallel_edges_are_drawn_apart() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(&["A", "B"], &[("A", "B"), ("B", "A")]);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let center: f64 = rect(&graph, "A").center().x;
+            let there: &Vec<Point> = &edge(&graph, "A", "B").waypoints;
+            let back: &Vec<Point> = &edge(&graph, "B", "A").waypoints;
+            assert!(there.iter().all(|p: &Point| p.x == center - 6.0));
+            assert!(back.iter().all(|p: &Point| p.x == center + 6.0));
+        });
+    }
+
+    #[test]
+    fn test_clusters_are_laid_out_recursively() {
+        smol::block_on(async {
+            let mut graph: Graph = graph(
+                &["A", "B", "C", "D", "E"],
+                &[("D", "A"), ("A", "B"), ("B", "C"), ("C", "E")],
+            );
+            add_group(&mut graph, "Outer", Some("outer"), None, &["A", "B"]);
+            add_group(&mut graph, "Inner", Some("inner"), Some("Outer"), &["B"]);
+            add_group(&mut graph, "Other", None, None, &["C"]);
+
+            LayeredLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let outer: Rect = graph.groups["Outer"].geometry.expect("Missing Outer frame");
+            let inner: Rect = graph.groups["Inner"].geometry.expect("Missing Inner frame");
+            let other: Rect = graph.groups["Other"].geometry.expect("Missing Other frame");
+
+            assert!(contains(&outer, &rect(&graph, "A")));
+            assert!(contains(&outer, &inner));
+            assert!(contains(&inner, &rect(&graph, "B")));
+            assert!(contains(&other, &rect(&graph, "C")));
+            assert!(!contains(&outer, &rect(&graph, "D")));
+            assert!(!contains(&outer, &other) && !contains(&other, &outer));
+
+            // Labels get a header band above the content
+            assert!(rect(&graph, "B").y >= inner.y + 24.0);
+            assert_eq!(rect(&graph, "C").y, other.y + 15.0);
+
+            let waypoints: &Vec<Point> = &edge(&graph, "B", "C").waypoints;
+            let (b, c): (Rect, Rect) = (rect(&graph, "B"), rect(&graph, "C"));
+            assert!(contains(&b, &point_rect(waypoints[0])));
+            assert!(contains(&c, &point_rect(*waypoints.last().unwrap())));
+        });
+    }
+
+    fn add_group(
+        graph: &mut Graph,
+        id: &str,
+        label: Option<&str>,
+        parent: Option<&str>,
+        nodes: &[&str],
+    ) {
+        nodes.iter().for_each(|node: &&str| {
+            graph.nodes.get_mut(*node).unwrap().parent = Some(id.into());
+        });
+        graph.groups.insert(
+            id.into(),
+            Group {
+                id: id.into(),
+                label: label.map(Text::from),
+                parent: parent.map(Id::from),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn contains(outer: &Rect, inner: &Rect) -> bool {
+        outer.x <= inner.x
+            && outer.y <= inner.y
+            && inner.x + inner.width <= outer.x + outer.width
+            && inner.y + inner.height <= outer.y + outer.height
+    }
+
+    fn point_rect(p: Point) -> Rect {
+        Rect {
+            x: p.x,
+            y: p.y,
+            width: 0.0,
+            height: 0.0,
+        }
+    }
+}
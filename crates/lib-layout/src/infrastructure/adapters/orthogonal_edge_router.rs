@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+    entities::{edge::Edge, graph::Graph, id::Id},
+};
+
+use crate::infrastructure::orthogonal::OrthogonalRouter;
+
+/// Reroutes the edges of an already positioned graph, e.g. after the layered
+/// or force-directed engines
+#[derive(Default)]
+pub struct OrthogonalEdgeRouter {
+    router: OrthogonalRouter,
+}
+
+impl OrthogonalEdgeRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LayoutEngineAdapter for OrthogonalEdgeRouter {
+    async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError> {
+        let unplaced: Option<&Id> = graph
+            .edges
+            .values()
+            .flat_map(|edge: &Edge| [&edge.from, &edge.to])
+            .find(|id: &&Id| {
+                graph
+                    .nodes
+                    .get(*id)
+                    .is_some_and(|node| node.geometry.is_none())
+            });
+
+        if let Some(id) = unplaced {
+            return Err(LayoutEngineError::Unsupported {
+                engine: "orthogonal".into(),
+                message: format!(
+                    "Node '{}' has no geometry, edges can only be routed once nodes are placed",
+                    id
+                ),
+            });
+        }
+
+        self.router.apply(graph);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+        entities::{
+            geometry::{Point, Rect},
+            graph::Graph,
+            group::Group,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::orthogonal_edge_router::OrthogonalEdgeRouter;
+    use crate::infrastructure::testing::{placed, rect};
+
+    #[test]
+    fn test_routes_are_axis_aligned_between_node_borders() {
+        smol::block_on(async {
+            let mut graph: Graph = placed(&[("A", 0.0, 0.0), ("B", 300.0, 200.0)], &[("A", "B")]);
+
+            OrthogonalEdgeRouter::new()
+                .layout(&mut graph)
+                .await
+                .unwrap();
+
+            let waypoints: &Vec<Point> = &graph.edges["A-B"].waypoints;
+            assert_axis_aligned(waypoints);
+            assert!(on_border(&rect(&graph, "A"), &waypoints[0]));
+            assert!(on_border(&rect(&graph, "B"), waypoints.last().unwrap()));
+            assert_eq!(
+                waypoints.len(),
+                3,
+                "Expected a single bend, got {:?}",
+                waypoints
+            );
+        });
+    }
+
+    #[test]
+    fn test_routes_avoid_nodes_in_the_way() {
+        smol::block_on(async {
+            let mut graph: Graph = placed(
+                &[("A", 0.0, 0.0), ("B", 200.0, 0.0), ("C", 400.0, 0.0)],
+                &[("A", "C")],
+            );
+
+            OrthogonalEdgeRouter::new()
+                .layout(&mut graph)
+                .await
+                .unwrap();
+
+            let waypoints: &Vec<Point> = &graph.edges["A-C"].waypoints;
+            assert_axis_aligned(waypoints);
+            assert!(!crosses(&rect(&graph, "B"), waypoints), "{:?}", waypoints);
+        });
+    }
+
+    #[test]
+    fn test_routes_avoid_foreign_clusters_only() {
+        smol::block_on(async {
+            let mut graph: Graph = placed(
+                &[("A", 0.0, 0.0), ("B", 400.0, 0.0), ("X", 200.0, 0.0)],
+                &[("A", "B"), ("A", "X")],
+            );
+            graph.nodes.get_mut("X").unwrap().parent = Some("G".into());
+            let cluster: Rect = Rect {
+                x: 160.0,
+                y: -40.0,
+                width: 160.0,
+                height: 110.0,
+            };
+            graph.groups.insert(
+                "G".into(),
+                Group {
+                    id: "G".into(),
+                    children: vec!["X".into()],
+                    geometry: Some(cluster),
+                    ..Default::default()
+                },
+            );
+
+            OrthogonalEdgeRouter::new()
+                .layout(&mut graph)
+                .await
+                .unwrap();
+
+            assert!(!crosses(&cluster, &graph.edges["A-B"].waypoints));
+            assert!(on_border(
+                &rect(&graph, "X"),
+                graph.edges["A-X"].waypoints.last().unwrap()
+            ));
+        });
+    }
+
+    #[test]
+    fn test_requires_placed_nodes() {
+        smol::block_on(async {
+            let mut graph: Graph = placed(&[("A", 0.0, 0.0), ("B", 100.0, 0.0)], &[("A", "B")]);
+            graph.nodes.get_mut("B").unwrap().geometry = None;
+
+            let result: Result<(), LayoutEngineError> =
+                OrthogonalEdgeRouter::new().layout(&mut graph).await;
+
+            assert!(matches!(result, Err(LayoutEngineError::Unsupported { .. })));
+        });
+    }
+
+    fn assert_axis_aligned(waypoints: &[Point]) {
+        waypoints.windows(2).for_each(|pair: &[Point]| {
+            assert!(
+                pair[0].x == pair[1].x || pair[0].y == pair[1].y,
+                "Segment {:?} is not axis-aligned",
+                pair
+            );
+        });
+    }
+
+    fn on_border(rect: &Rect, p: &Point) -> bool {
+        let within_x: bool = p.x >= rect.x && p.x <= rect.x + rect.width;
+        let within_y: bool = p.y >= rect.y && p.y <= rect.y + rect.height;
+        (within_x && (p.y == rect.y || p.y == rect.y + rect.height))
+            || (within_y && (p.x == rect.x || p.x == rect.x + rect.width))
+    }
+
+    /// Whether an axis-aligned polyline passes through the rectangle's interior
+    fn crosses(rect: &Rect, waypoints: &[Point]) -> bool {
+        waypoints.windows(2).any(|pair: &[Point]| {
+            let (min_x, max_x): (f64, f64) = (pair[0].x.min(pair[1].x), pair[0].x.max(pair[1].x));
+            let (min_y, max_y): (f64, f64) = (pair[0].y.min(pair[1].y), pair[0].y.max(pair[1].y));
+            min_x < rect.x + rect.width
+                && max_x > rect.x
+                && min_y < rect.y + rect.height
+                && max_y > rect.y
+        })
+    }
+}
@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::tree::{Forest, TreeLayout};
+
+#[derive(Default)]
+pub struct TreeLayoutEngine {
+    layout: TreeLayout,
+}
+
+impl TreeLayoutEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LayoutEngineAdapter for TreeLayoutEngine {
+    async fn layout(&self, graph: &mut Graph) -> Result<(), LayoutEngineError> {
+        let forest: Forest = Forest::from_graph(graph).ok_or(LayoutEngineError::Unsupported {
+            engine: "tree".into(),
+            message: "Graph is not a tree, some node has several parents or edges form a cycle"
+                .into(),
+        })?;
+
+        self.layout.apply(graph, &forest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::layout_engine_adapter::{LayoutEngineAdapter, LayoutEngineError},
+        entities::{
+            edge::EdgeKind,
+            geometry::{Point, Rect},
+            graph::Graph,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::tree_layout_engine::TreeLayoutEngine;
+    use crate::infrastructure::testing::{graph_of, rect};
+
+    #[test]
+    fn test_parents_are_centered_above_children() {
+        smol::block_on(async {
+            let mut graph: Graph = graph_of(&[
+                ("Root", "A", EdgeKind::Association),
+                ("Root", "B", EdgeKind::Association),
+                ("Root", "C", EdgeKind::Association),
+                ("A", "A1", EdgeKind::Association),
+            ]);
+
+            TreeLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (root, a, b, c, a1): (Rect, Rect, Rect, Rect, Rect) = (
+                rect(&graph, "Root"),
+                rect(&graph, "A"),
+                rect(&graph, "B"),
+                rect(&graph, "C"),
+                rect(&graph, "A1"),
+            );
+            assert!(root.y + root.height < a.y);
+            assert_eq!(a.y, b.y);
+            assert_eq!(b.y, c.y);
+            assert!(a.x + a.width < b.x && b.x + b.width < c.x);
+            assert_eq!(root.center().x, (a.center().x + c.center().x) / 2.0);
+            assert_eq!(a1.center().x, a.center().x);
+
+            let waypoints: &Vec<Point> = &graph.edges["Root-A"].waypoints;
+            assert_eq!(
+                waypoints.first().map(|p: &Point| p.y),
+                Some(root.y + root.height)
+            );
+            assert_eq!(waypoints.last().map(|p: &Point| p.y), Some(a.y));
+        });
+    }
+
+    #[test]
+    fn test_subtrees_are_packed_without_overlapping() {
+        smol::block_on(async {
+            let mut graph: Graph = graph_of(&[
+                ("Root", "A", EdgeKind::Association),
+                ("Root", "B", EdgeKind::Association),
+                ("A", "A1", EdgeKind::Association),
+                ("A", "A2", EdgeKind::Association),
+                ("B", "B1", EdgeKind::Association),
+                ("B", "B2", EdgeKind::Association),
+            ]);
+
+            TreeLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (a2, b1): (Rect, Rect) = (rect(&graph, "A2"), rect(&graph, "B1"));
+            assert_eq!(a2.y, b1.y);
+            assert!(a2.x + a2.width < b1.x);
+        });
+    }
+
+    #[test]
+    fn test_inheritance_places_parents_on_top() {
+        smol::block_on(async {
+            let mut graph: Graph = graph_of(&[
+                ("Cat", "Animal", EdgeKind::Inheritance),
+                ("Dog", "Animal", EdgeKind::Inheritance),
+            ]);
+
+            TreeLayoutEngine::new().layout(&mut graph).await.unwrap();
+
+            let (animal, cat): (Rect, Rect) = (rect(&graph, "Animal"), rect(&graph, "Cat"));
+            assert!(animal.y < cat.y);
+            assert_eq!(
+                graph.edges["Cat-Animal"]
+                    .waypoints
+                    .first()
+                    .map(|p: &Point| p.y),
+                Some(cat.y)
+            );
+        });
+    }
+
+    #[test]
+    fn test_rejects_graphs_that_are_not_trees() {
+        smol::block_on(async {
+            let mut graph: Graph = graph_of(&[
+                ("A", "C", EdgeKind::Association),
+                ("B", "C", EdgeKind::Association),
+            ]);
+
+            let result: Result<(), LayoutEngineError> =
+                TreeLayoutEngine::new().layout(&mut graph).await;
+
+            assert!(matches!(result, Err(LayoutEngineError::Unsupported { .. })));
+        });
+    }
+}
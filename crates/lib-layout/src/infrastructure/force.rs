@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use lib_core::entities::{
+    edge::Edge,
+    geometry::{Point, Rect},
+    graph::Graph,
+    id::Id,
+};
+
+use crate::infrastructure::{geometry, measure};
+
+/// Force-directed (Fruchterman-Reingold) layout: edges pull their ends
+/// together while every node repels its neighbours, which suits undirected
+/// and densely connected graphs. Repulsion is only computed between nodes in
+/// neighbouring grid cells so large graphs stay tractable.
+pub struct ForceLayout {
+    pub iterations: usize,
+    pub node_spacing: f64,
+    pub margin: f64,
+    /// Distance between edges joining the same two nodes
+    pub edge_spacing: f64,
+}
+
+impl Default for ForceLayout {
+    fn default() -> Self {
+        Self {
+            iterations: 300,
+            node_spacing: 40.0,
+            margin: 20.0,
+            edge_spacing: 12.0,
+        }
+    }
+}
+
+impl ForceLayout {
+    pub fn apply(&self, graph: &mut Graph) {
+        let mut ids: Vec<Id> = graph.nodes.keys().cloned().collect();
+        ids.sort();
+        if ids.is_empty() {
+            return;
+        }
+
+        let index: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id): (usize, &Id)| (id.as_str(), i))
+            .collect();
+        let sizes: Vec<(f64, f64)> = ids
+            .iter()
+            .map(|id: &Id| measure::node_size(&graph.nodes[id]))
+            .collect();
+        let mut links: Vec<(usize, usize)> = graph
+            .edges
+            .values()
+            .filter_map(|edge: &Edge| {
+                let from: usize = *index.get(edge.from.as_str())?;
+                let to: usize = *index.get(edge.to.as_str())?;
+                (from != to).then_some((from, to))
+            })
+            .collect();
+        links.sort();
+
+        // Ideal distance between connected nodes
+        let k: f64 = sizes
+            .iter()
+            .map(|(w, h): &(f64, f64)| w.max(*h))
+            .fold(0.0, f64::max)
+            + self.node_spacing;
+
+        let mut positions: Vec<Point> = initial_positions(ids.len(), k);
+        let mut temperature: f64 = k * (ids.len() as f64).sqrt();
+        let cooling: f64 = temperature / self.iterations.max(1) as f64;
+
+        for _ in 0..self.iterations {
+            let mut displacement: Vec<Point> = vec![Point::default(); ids.len()];
+
+            let cells: HashMap<(i64, i64), Vec<usize>> = grid(&positions, 2.0 * k);
+            positions
+                .iter()
+                .enumerate()
+                .for_each(|(v, p): (usize, &Point)| {
+                    let (cx, cy): (i64, i64) = cell(p, 2.0 * k);
+                    for nx in cx - 1..=cx + 1 {
+                        for ny in cy - 1..=cy + 1 {
+                            cells
+                                .get(&(nx, ny))
+                                .into_iter()
+                                .flatten()
+                                .for_each(|u: &usize| {
+                                    if *u == v {
+                                        return;
+                                    }
+                                    let (dx, dy, distance): (f64, f64, f64) =
+                                        delta(p, &positions[*u], v, *u);
+                                    if distance < 2.0 * k {
+                                        let force: f64 = k * k / distance;
+                                        displacement[v].x += dx / distance * force;
+                                        displacement[v].y += dy / distance * force;
+                                    }
+                                });
+                        }
+                    }
+                });
+
+            links.iter().for_each(|(from, to): &(usize, usize)| {
+                let (dx, dy, distance): (f64, f64, f64) =
+                    delta(&positions[*from], &positions[*to], *from, *to);
+                let force: f64 = distance * distance / k;
+                displacement[*from].x -= dx / distance * force;
+                displacement[*from].y -= dy / distance * force;
+                displacement[*to].x += dx / distance * force;
+                displacement[*to].y += dy / distance * force;
+            });
+
+            positions.iter_mut().zip(displacement.iter()).for_each(
+                |(p, d): (&mut Point, &Point)| {
+                    let length: f64 = (d.x * d.x + d.y * d.y).sqrt();
+                    if length > 0.0 {
+                        let step: f64 = length.min(temperature);
+                        p.x += d.x / length * step;
+                        p.y += d.y / length * step;
+                    }
+                },
+            );
+
+            temperature = (temperature - cooling).max(1.0);
+        }
+
+        self.remove_overlaps(&mut positions, &sizes);
+
+        let (left, top): (f64, f64) = positions.iter().zip(sizes.iter()).fold(
+            (f64::INFINITY, f64::INFINITY),
+            |(left, top): (f64, f64), (p, (w, h)): (&Point, &(f64, f64))| {
+                (left.min(p.x - w / 2.0), top.min(p.y - h / 2.0))
+            },
+        );
+
+        let rects: HashMap<&str, Rect> = ids
+            .iter()
+            .zip(positions.iter().zip(sizes.iter()))
+            .map(|(id, (p, (w, h))): (&Id, (&Point, &(f64, f64)))| {
+                (
+                    id.as_str(),
+                    Rect {
+                        x: p.x - w / 2.0 - left + self.margin,
+                        y: p.y - h / 2.0 - top + self.margin,
+                        width: *w,
+                        height: *h,
+                    },
+                )
+            })
+            .collect();
+
+        graph.edges.values_mut().for_each(|edge: &mut Edge| {
+            edge.waypoints = match (rects.get(edge.from.as_str()), rects.get(edge.to.as_str())) {
+                (Some(from), Some(to)) if edge.from != edge.to && !edge.layout.hidden => vec![
+                    geometry::border_point(from, &to.center()),
+                    geometry::border_point(to, &from.center()),
+                ],
+                _ => Vec::new(),
+            };
+        });
+        rects.into_iter().for_each(|(id, rect): (&str, Rect)| {
+            if let Some(node) = graph.nodes.get_mut(id) {
+                node.geometry = Some(rect);
+            }
+        });
+        geometry::spread_parallel_edges(graph, self.edge_spacing);
+    }
+
+    /// Pushes overlapping nodes apart along the axis where they overlap least
+    fn remove_overlaps(&self, positions: &mut [Point], sizes: &[(f64, f64)]) {
+        let cell_size: f64 = sizes
+            .iter()
+            .map(|(w, h): &(f64, f64)| w.max(*h))
+            .fold(0.0, f64::max)
+            + self.node_spacing;
+
+        for _ in 0..positions.len().min(50) {
+            let cells: HashMap<(i64, i64), Vec<usize>> = grid(positions, cell_size);
+            let mut moved: bool = false;
+
+            for v in 0..positions.len() {
+                let (cx, cy): (i64, i64) = cell(&positions[v], cell_size);
+                for nx in cx - 1..=cx + 1 {
+                    for ny in cy - 1..=cy + 1 {
+                        for u in cells.get(&(nx, ny)).into_iter().flatten() {
+                            if *u <= v {
+                                continue;
+                            }
+                            let overlap_x: f64 = (sizes[v].0 + sizes[*u].0) / 2.0
+                                + self.node_spacing / 2.0
+                                - (positions[v].x - positions[*u].x).abs();
+                            let overlap_y: f64 = (sizes[v].1 + sizes[*u].1) / 2.0
+                                + self.node_spacing / 2.0
+                                - (positions[v].y - positions[*u].y).abs();
+                            if overlap_x <= 0.0 || overlap_y <= 0.0 {
+                                continue;
+                            }
+
+                            moved = true;
+                            let (dx, dy, _): (f64, f64, f64) =
+                                delta(&positions[v], &positions[*u], v, *u);
+                            if overlap_x < overlap_y {
+                                let shift: f64 = overlap_x / 2.0 * dx.signum();
+                                positions[v].x += shift;
+                                positions[*u].x -= shift;
+                            } else {
+                                let shift: f64 = overlap_y / 2.0 * dy.signum();
+                                positions[v].y += shift;
+                                positions[*u].y -= shift;
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !moved {
+                break;
+            }
+        }
+    }
+}
+
+/// Nodes start evenly spread on a circle, keeping the result deterministic
+fn initial_positions(count: usize, k: f64) -> Vec<Point> {
+    let radius: f64 = k * count as f64 / std::f64::consts::TAU;
+    (0..count)
+        .map(|i: usize| {
+            let angle: f64 = std::f64::consts::TAU * i as f64 / count as f64;
+            Point {
+                x: radius * angle.cos(),
+                y: radius * angle.sin(),
+            }
+        })
+        .collect()
+}
+
+fn grid(positions: &[Point], size: f64) -> HashMap<(i64, i64), Vec<usize>> {
+    let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    positions
+        .iter()
+        .enumerate()
+        .for_each(|(i, p): (usize, &Point)| cells.entry(cell(p, size)).or_default().push(i));
+    cells
+}
+
+fn cell(p: &Point, size: f64) -> (i64, i64) {
+    ((p.x / size).floor() as i64, (p.y / size).floor() as i64)
+}
+
+/// Vector from `b` to `a`, nudged apart deterministically when both coincide
+fn delta(a: &Point, b: &Point, ia: usize, ib: usize) -> (f64, f64, f64) {
+    let (mut dx, dy): (f64, f64) = (a.x - b.x, a.y - b.y);
+    if dx == 0.0 && dy == 0.0 {
+        dx = if ia < ib { -0.01 } else { 0.01 };
+    }
+    (dx, dy, (dx * dx + dy * dy).sqrt())
+}
@@ -0,0 +1,66 @@
+use lib_core::entities::{
+    edge::Parallel,
+    geometry::{Point, Rect},
+    graph::Graph,
+    id::Id,
+};
+
+/// Point where the segment from the rectangle's center towards `target` leaves the rectangle
+pub fn border_point(rect: &Rect, target: &Point) -> Point {
+    let center: Point = rect.center();
+    let (dx, dy): (f64, f64) = (target.x - center.x, target.y - center.y);
+    if dx == 0.0 && dy == 0.0 {
+        return center;
+    }
+
+    let scale_x: f64 = if dx == 0.0 {
+        f64::INFINITY
+    } else {
+        rect.width / 2.0 / dx.abs()
+    };
+    let scale_y: f64 = if dy == 0.0 {
+        f64::INFINITY
+    } else {
+        rect.height / 2.0 / dy.abs()
+    };
+    let scale: f64 = scale_x.min(scale_y);
+
+    Point {
+        x: center.x + dx * scale,
+        y: center.y + dy * scale,
+    }
+}
+
+/// Moves each edge sharing its ends with others sideways, off the line
+/// joining its ends, so parallel edges run `spacing` apart instead of on top
+/// of each other
+pub fn spread_parallel_edges(graph: &mut Graph, spacing: f64) {
+    graph
+        .parallel_edges()
+        .into_iter()
+        .for_each(|(id, parallel): (Id, Parallel)| {
+            let Some(edge) = graph.edges.get_mut(&id) else {
+                return;
+            };
+            let (Some(first), Some(last)) = (edge.waypoints.first(), edge.waypoints.last()) else {
+                return;
+            };
+            // Measured from the end with the lower id, so edges pointing
+            // either way share a side
+            let (dx, dy): (f64, f64) = match edge.from <= edge.to {
+                true => (last.x - first.x, last.y - first.y),
+                false => (first.x - last.x, first.y - last.y),
+            };
+            let length: f64 = dx.hypot(dy);
+            if length == 0.0 {
+                return;
+            }
+
+            let offset: f64 = parallel.offset(spacing);
+            let (x, y): (f64, f64) = (dy / length * offset, -dx / length * offset);
+            edge.waypoints.iter_mut().for_each(|point: &mut Point| {
+                point.x += x;
+                point.y += y;
+            });
+        });
+}
@@ -0,0 +1,334 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeLabel, EdgeLabelKind},
+    geometry::{Point, Rect},
+    graph::Graph,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+    value::Value,
+};
+
+use crate::infrastructure::{geometry, measure};
+
+const LABEL_FRACTIONS: [f64; 5] = [0.5, 0.35, 0.65, 0.2, 0.8];
+const END_DISTANCES: [f64; 3] = [6.0, 26.0, 46.0];
+// Cost of a label lying across an edge, compared with overlapping areas
+const CROSSING_COST: f64 = 100.0;
+
+/// Places edge texts (label, cardinalities and roles) beside their edge,
+/// trying a few spots along it and keeping the first one that covers no node,
+/// label or edge, or else the least cluttered one. Node label anchors are
+/// filled in and note connectors are straightened when nothing is in the way.
+pub struct LabelPlacer {
+    pub gap: f64,
+}
+
+impl Default for LabelPlacer {
+    fn default() -> Self {
+        Self { gap: 4.0 }
+    }
+}
+
+impl LabelPlacer {
+    pub fn apply(&self, graph: &mut Graph) {
+        let mut obstacles: Vec<Rect> = Vec::new();
+        graph.nodes.values_mut().for_each(|node: &mut Node| {
+            let Some(rect) = node.geometry else {
+                return;
+            };
+            let anchor: Point = measure::label_anchor(node, &rect);
+            if node.kind == NodeKind::Actor {
+                let (width, height): (f64, f64) =
+                    measure::text_size(node.label.as_deref().unwrap_or(&node.id));
+                obstacles.push(centered(&anchor, (width, height)));
+            }
+            node.label_anchor = Some(anchor);
+            obstacles.push(rect);
+        });
+
+        self.connect_notes(graph);
+
+        let segments: Vec<(Point, Point)> = graph
+            .edges
+            .values()
+            .filter(|edge: &&Edge| !edge.layout.hidden)
+            .flat_map(|edge: &Edge| {
+                edge.waypoints
+                    .windows(2)
+                    .map(|pair: &[Point]| (pair[0], pair[1]))
+            })
+            .collect();
+
+        let mut ids: Vec<Id> = graph.edges.keys().cloned().collect();
+        ids.sort();
+        ids.iter().for_each(|id: &Id| {
+            let edge: &Edge = &graph.edges[id];
+            let labels: Vec<EdgeLabel> = if edge.layout.hidden || edge.waypoints.len() < 2 {
+                Vec::new()
+            } else {
+                texts(edge)
+                    .into_iter()
+                    .map(|(kind, text): (EdgeLabelKind, String)| {
+                        let bounds: Rect = self.place(
+                            kind,
+                            &edge.waypoints,
+                            measure::text_size(&text),
+                            &obstacles,
+                            &segments,
+                        );
+                        obstacles.push(bounds);
+                        EdgeLabel { kind, text, bounds }
+                    })
+                    .collect()
+            };
+
+            if let Some(edge) = graph.edges.get_mut(id) {
+                edge.layout.labels = labels;
+            }
+        });
+    }
+
+    /// Draws note connectors as a straight line between the note and the
+    /// element it annotates, unless that line would cross another node
+    fn connect_notes(&self, graph: &mut Graph) {
+        let rects: Vec<(Id, Rect)> = graph
+            .nodes
+            .values()
+            .filter_map(|node: &Node| node.geometry.map(|rect: Rect| (node.id.clone(), rect)))
+            .collect();
+
+        let notes: Vec<(Id, Vec<Point>)> = graph
+            .edges
+            .values()
+            .filter_map(|edge: &Edge| {
+                let (from, to): (&Node, &Node) =
+                    (graph.nodes.get(&edge.from)?, graph.nodes.get(&edge.to)?);
+                if edge.from == edge.to
+                    || (from.kind != NodeKind::Annotation && to.kind != NodeKind::Annotation)
+                {
+                    return None;
+                }
+
+                let (from_rect, to_rect): (Rect, Rect) = (from.geometry?, to.geometry?);
+                let line: (Point, Point) = (
+                    geometry::border_point(&from_rect, &to_rect.center()),
+                    geometry::border_point(&to_rect, &from_rect.center()),
+                );
+                let blocked: bool = rects.iter().any(|(id, rect): &(Id, Rect)| {
+                    *id != edge.from && *id != edge.to && crosses(rect, &line)
+                });
+                (!blocked).then(|| (edge.id.clone(), vec![line.0, line.1]))
+            })
+            .collect();
+
+        notes
+            .into_iter()
+            .for_each(|(id, waypoints): (Id, Vec<Point>)| {
+                if let Some(edge) = graph.edges.get_mut(&id) {
+                    edge.waypoints = waypoints;
+                }
+            });
+    }
+
+    fn place(
+        &self,
+        kind: EdgeLabelKind,
+        waypoints: &[Point],
+        size: (f64, f64),
+        obstacles: &[Rect],
+        segments: &[(Point, Point)],
+    ) -> Rect {
+        let candidates: Vec<Rect> = match kind {
+            EdgeLabelKind::Label => {
+                let length: f64 = path_length(waypoints);
+                LABEL_FRACTIONS
+                    .iter()
+                    .flat_map(|fraction: &f64| {
+                        let (point, direction): (Point, Point) =
+                            along(waypoints, length * fraction);
+                        [1.0, -1.0].map(|side: f64| self.beside(&point, &direction, side, size))
+                    })
+                    .collect()
+            }
+            _ => {
+                let path: Vec<Point> = match kind {
+                    EdgeLabelKind::ToCardinality | EdgeLabelKind::ToRole => {
+                        waypoints.iter().rev().copied().collect()
+                    }
+                    _ => waypoints.to_vec(),
+                };
+                // Cardinalities and roles start on opposite sides of the edge
+                let sides: [f64; 2] = match kind {
+                    EdgeLabelKind::FromCardinality | EdgeLabelKind::ToCardinality => [1.0, -1.0],
+                    _ => [-1.0, 1.0],
+                };
+                END_DISTANCES
+                    .iter()
+                    .flat_map(|distance: &f64| {
+                        let (point, direction): (Point, Point) = along(&path, *distance);
+                        let start: Point = Point {
+                            x: point.x + direction.x * size.0.max(size.1) / 2.0,
+                            y: point.y + direction.y * size.0.max(size.1) / 2.0,
+                        };
+                        sides.map(|side: f64| self.beside(&start, &direction, side, size))
+                    })
+                    .collect()
+            }
+        };
+
+        let mut best: (f64, Rect) = (f64::INFINITY, candidates[0]);
+        for candidate in candidates {
+            let cost: f64 = cost(&candidate, obstacles, segments);
+            if cost < best.0 {
+                best = (cost, candidate);
+            }
+            if cost == 0.0 {
+                break;
+            }
+        }
+        best.1
+    }
+
+    /// Box of the given size just beside `point`, on the left (`side` = 1) or
+    /// right (`side` = -1) of a segment running along `direction`
+    fn beside(&self, point: &Point, direction: &Point, side: f64, size: (f64, f64)) -> Rect {
+        let normal: Point = Point {
+            x: -direction.y * side,
+            y: direction.x * side,
+        };
+        let extent: f64 = normal.x.abs() * size.0 / 2.0 + normal.y.abs() * size.1 / 2.0;
+        centered(
+            &Point {
+                x: point.x + normal.x * (self.gap + extent),
+                y: point.y + normal.y * (self.gap + extent),
+            },
+            size,
+        )
+    }
+}
+
+fn texts(edge: &Edge) -> Vec<(EdgeLabelKind, String)> {
+    let data = |key: &str| match edge.data.get(key) {
+        Some(Value::String(text)) if !text.is_empty() => Some(text.clone()),
+        _ => None,
+    };
+    let cardinality = |text: &Option<Text>| {
+        text.as_deref()
+            .filter(|text: &&str| !text.is_empty())
+            .map(str::to_string)
+    };
+
+    [
+        (
+            EdgeLabelKind::Label,
+            edge.label.as_deref().map(str::to_string),
+        ),
+        (
+            EdgeLabelKind::FromCardinality,
+            cardinality(&edge.from_cardinality),
+        ),
+        (
+            EdgeLabelKind::ToCardinality,
+            cardinality(&edge.to_cardinality),
+        ),
+        (EdgeLabelKind::FromRole, data("from_role")),
+        (EdgeLabelKind::ToRole, data("to_role")),
+    ]
+    .into_iter()
+    .filter_map(|(kind, text): (EdgeLabelKind, Option<String>)| Some((kind, text?)))
+    .collect()
+}
+
+fn cost(rect: &Rect, obstacles: &[Rect], segments: &[(Point, Point)]) -> f64 {
+    obstacles
+        .iter()
+        .map(|obstacle: &Rect| overlap(rect, obstacle))
+        .sum::<f64>()
+        + segments
+            .iter()
+            .filter(|segment: &&(Point, Point)| crosses(rect, segment))
+            .count() as f64
+            * CROSSING_COST
+}
+
+fn centered(center: &Point, (width, height): (f64, f64)) -> Rect {
+    Rect {
+        x: center.x - width / 2.0,
+        y: center.y - height / 2.0,
+        width,
+        height,
+    }
+}
+
+fn overlap(a: &Rect, b: &Rect) -> f64 {
+    let width: f64 = (a.x + a.width).min(b.x + b.width) - a.x.max(b.x);
+    let height: f64 = (a.y + a.height).min(b.y + b.height) - a.y.max(b.y);
+    width.max(0.0) * height.max(0.0)
+}
+
+/// Whether a segment passes through the inside of a rectangle (Liang-Barsky clipping)
+fn crosses(rect: &Rect, (a, b): &(Point, Point)) -> bool {
+    let (dx, dy): (f64, f64) = (b.x - a.x, b.y - a.y);
+    let (mut low, mut high): (f64, f64) = (0.0, 1.0);
+    let bounds: [(f64, f64); 4] = [
+        (-dx, a.x - rect.x),
+        (dx, rect.x + rect.width - a.x),
+        (-dy, a.y - rect.y),
+        (dy, rect.y + rect.height - a.y),
+    ];
+    for (p, q) in bounds {
+        if p == 0.0 {
+            if q <= 0.0 {
+                return false;
+            }
+            continue;
+        }
+        let t: f64 = q / p;
+        if p < 0.0 {
+            low = low.max(t);
+        } else {
+            high = high.min(t);
+        }
+    }
+    low < high
+}
+
+fn path_length(points: &[Point]) -> f64 {
+    points
+        .windows(2)
+        .map(|pair: &[Point]| distance(&pair[0], &pair[1]))
+        .sum()
+}
+
+/// Point at the given distance along a polyline, with the unit direction of
+/// the segment it lies on
+fn along(points: &[Point], mut remaining: f64) -> (Point, Point) {
+    let segments: Vec<&[Point]> = points
+        .windows(2)
+        .filter(|pair: &&[Point]| distance(&pair[0], &pair[1]) > 0.0)
+        .collect();
+
+    for (i, pair) in segments.iter().enumerate() {
+        let length: f64 = distance(&pair[0], &pair[1]);
+        // Distances past the end stay on the last segment
+        if remaining <= length || i == segments.len() - 1 {
+            let t: f64 = (remaining / length).clamp(0.0, 1.0);
+            return (
+                Point {
+                    x: pair[0].x + (pair[1].x - pair[0].x) * t,
+                    y: pair[0].y + (pair[1].y - pair[0].y) * t,
+                },
+                Point {
+                    x: (pair[1].x - pair[0].x) / length,
+                    y: (pair[1].y - pair[0].y) / length,
+                },
+            );
+        }
+        remaining -= length;
+    }
+
+    (points[0], Point { x: 1.0, y: 0.0 })
+}
+
+fn distance(a: &Point, b: &Point) -> f64 {
+    ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt()
+}
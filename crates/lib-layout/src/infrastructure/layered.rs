@@ -0,0 +1,567 @@
+use std::collections::HashMap;
+
+use lib_core::entities::{
+    edge::{Edge, EdgeDirection, EdgeKind},
+    geometry::Point,
+    graph::Graph,
+    id::Id,
+};
+
+use crate::infrastructure::{geometry, measure, orientation::Orientation};
+
+const CROSSING_SWEEPS: usize = 24;
+const ALIGNMENT_SWEEPS: usize = 8;
+
+const UNVISITED: u8 = 0;
+const VISITING: u8 = 1;
+const VISITED: u8 = 2;
+
+/// Hierarchical (Sugiyama-style) layout: ranks flow top to bottom, long edges
+/// are split with dummy vertices, layers are reordered with barycenter sweeps
+/// and vertices are then aligned with their neighbours. The result is turned
+/// towards the graph direction, and edge direction and length hints are
+/// honoured while ranking.
+pub struct LayeredLayout {
+    pub node_spacing: f64,
+    pub rank_spacing: f64,
+    pub margin: f64,
+    /// Distance between edges joining the same two nodes
+    pub edge_spacing: f64,
+}
+
+impl Default for LayeredLayout {
+    fn default() -> Self {
+        Self {
+            node_spacing: 40.0,
+            rank_spacing: 60.0,
+            margin: 20.0,
+            edge_spacing: 12.0,
+        }
+    }
+}
+
+/// A vertex of the layered graph, either a node or a dummy along a long edge
+struct Vertex {
+    node: Option<Id>,
+    width: f64,
+    height: f64,
+    rank: usize,
+}
+
+/// An edge in ranking orientation, `reversed` when it runs against the original edge
+struct Link {
+    edge: Id,
+    from: usize,
+    to: usize,
+    // Minimum rank difference between both ends, 0 for side by side nodes
+    length: usize,
+    reversed: bool,
+}
+
+/// The chain of vertices an edge goes through, from its upper to its lower end
+struct Route {
+    edge: Id,
+    reversed: bool,
+    vertices: Vec<usize>,
+}
+
+impl LayeredLayout {
+    pub fn apply(&self, graph: &mut Graph) {
+        let mut ids: Vec<Id> = graph.nodes.keys().cloned().collect();
+        ids.sort();
+        if ids.is_empty() {
+            return;
+        }
+
+        let index: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id): (usize, &Id)| (id.as_str(), i))
+            .collect();
+
+        let mut edges: Vec<&Edge> = graph.edges.values().collect();
+        edges.sort_by(|a: &&Edge, b: &&Edge| a.id.cmp(&b.id));
+
+        let mut links: Vec<Link> = Vec::new();
+        // Pairs of nodes to keep side by side, left one first
+        let mut sideways: Vec<(usize, usize)> = Vec::new();
+        edges.iter().for_each(|edge: &&Edge| {
+            let (Some(from), Some(to)) = (
+                index.get(edge.from.as_str()).copied(),
+                index.get(edge.to.as_str()).copied(),
+            ) else {
+                return;
+            };
+            if from == to {
+                return;
+            }
+
+            // Parents sit above their children in class hierarchies unless told otherwise
+            let points_up: bool = match edge.layout.direction {
+                Some(EdgeDirection::Up) => true,
+                Some(EdgeDirection::Down) => false,
+                _ => matches!(edge.kind, EdgeKind::Inheritance | EdgeKind::Realization),
+            };
+            let length: usize = match edge.layout.direction {
+                Some(EdgeDirection::Left) => {
+                    sideways.push((to, from));
+                    0
+                }
+                Some(EdgeDirection::Right) => {
+                    sideways.push((from, to));
+                    0
+                }
+                _ => edge.layout.length.unwrap_or(1).max(1),
+            };
+
+            links.push(Link {
+                edge: edge.id.clone(),
+                from: if points_up { to } else { from },
+                to: if points_up { from } else { to },
+                length,
+                reversed: points_up,
+            });
+        });
+
+        remove_cycles(ids.len(), &mut links);
+        let ranks: Vec<usize> = assign_ranks(ids.len(), &links);
+
+        let mut vertices: Vec<Vertex> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id): (usize, &Id)| {
+                let (width, height): (f64, f64) =
+                    Orientation::flow_size(graph.direction, measure::node_size(&graph.nodes[id]));
+                Vertex {
+                    node: Some(id.clone()),
+                    width,
+                    height,
+                    rank: ranks[i],
+                }
+            })
+            .collect();
+
+        let routes: Vec<Route> = links
+            .iter()
+            .map(|link: &Link| {
+                let mut chain: Vec<usize> = vec![link.from];
+                for rank in ranks[link.from] + 1..ranks[link.to] {
+                    vertices.push(Vertex {
+                        node: None,
+                        width: 0.0,
+                        height: 0.0,
+                        rank,
+                    });
+                    chain.push(vertices.len() - 1);
+                }
+                chain.push(link.to);
+
+                Route {
+                    edge: link.edge.clone(),
+                    reversed: link.reversed,
+                    vertices: chain,
+                }
+            })
+            .collect();
+
+        let mut up: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+        let mut down: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+        routes.iter().for_each(|route: &Route| {
+            route.vertices.windows(2).for_each(|pair: &[usize]| {
+                if vertices[pair[0]].rank != vertices[pair[1]].rank {
+                    down[pair[0]].push(pair[1]);
+                    up[pair[1]].push(pair[0]);
+                }
+            });
+        });
+
+        let mut layers: Vec<Vec<usize>> = order_layers(&vertices, &up, &down);
+        keep_side_by_side(&mut layers, &vertices, &sideways);
+        let centers: Vec<Point> = self.assign_coordinates(&vertices, &layers, &up, &down);
+
+        let orientation: Orientation = Orientation {
+            direction: graph.direction,
+            extent: vertices
+                .iter()
+                .zip(centers.iter())
+                .map(|(vertex, center): (&Vertex, &Point)| center.y + vertex.height / 2.0)
+                .fold(0.0, f64::max)
+                + self.margin,
+        };
+
+        vertices
+            .iter()
+            .zip(centers.iter())
+            .for_each(|(vertex, center): (&Vertex, &Point)| {
+                if let Some(node) = vertex
+                    .node
+                    .as_ref()
+                    .and_then(|id: &Id| graph.nodes.get_mut(id))
+                {
+                    node.geometry = Some(orientation.rect(
+                        center,
+                        Orientation::flow_size(graph.direction, (vertex.width, vertex.height)),
+                    ));
+                }
+            });
+
+        routes.iter().for_each(|route: &Route| {
+            let Some(edge) = graph.edges.get_mut(&route.edge) else {
+                return;
+            };
+            if edge.layout.hidden {
+                edge.waypoints = Vec::new();
+                return;
+            }
+
+            let (first, last): (usize, usize) =
+                (route.vertices[0], route.vertices[route.vertices.len() - 1]);
+            let mut points: Vec<Point> = if vertices[first].rank == vertices[last].rank {
+                // Side by side nodes are joined from side to side
+                let direction: f64 = (centers[last].x - centers[first].x).signum();
+                vec![
+                    Point {
+                        x: centers[first].x + direction * vertices[first].width / 2.0,
+                        y: centers[first].y,
+                    },
+                    Point {
+                        x: centers[last].x - direction * vertices[last].width / 2.0,
+                        y: centers[last].y,
+                    },
+                ]
+            } else {
+                let end: usize = route.vertices.len() - 1;
+                route
+                    .vertices
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v): (usize, &usize)| {
+                        // Edges leave the bottom of their upper node and enter the top of the lower one
+                        let offset: f64 = match i {
+                            0 => vertices[*v].height / 2.0,
+                            i if i == end => -vertices[*v].height / 2.0,
+                            _ => 0.0,
+                        };
+                        Point {
+                            x: centers[*v].x,
+                            y: centers[*v].y + offset,
+                        }
+                    })
+                    .collect()
+            };
+
+            if route.reversed {
+                points.reverse();
+            }
+            edge.waypoints = points
+                .iter()
+                .map(|p: &Point| orientation.point(p))
+                .collect();
+        });
+        geometry::spread_parallel_edges(graph, self.edge_spacing);
+    }
+
+    fn assign_coordinates(
+        &self,
+        vertices: &[Vertex],
+        layers: &[Vec<usize>],
+        up: &[Vec<usize>],
+        down: &[Vec<usize>],
+    ) -> Vec<Point> {
+        let mut x: Vec<f64> = vec![0.0; vertices.len()];
+        layers.iter().for_each(|layer: &Vec<usize>| {
+            let mut cursor: f64 = 0.0;
+            layer.windows(2).for_each(|pair: &[usize]| {
+                cursor += self.gap(&vertices[pair[0]], &vertices[pair[1]]);
+                x[pair[1]] = cursor;
+            });
+        });
+
+        for sweep in 0..ALIGNMENT_SWEEPS {
+            if sweep % 2 == 0 {
+                (1..layers.len()).for_each(|r: usize| self.align(vertices, &layers[r], up, &mut x));
+            } else {
+                (0..layers.len().saturating_sub(1))
+                    .rev()
+                    .for_each(|r: usize| self.align(vertices, &layers[r], down, &mut x));
+            }
+        }
+
+        let left: f64 = vertices
+            .iter()
+            .zip(x.iter())
+            .map(|(vertex, x): (&Vertex, &f64)| x - vertex.width / 2.0)
+            .fold(f64::INFINITY, f64::min);
+
+        let mut layer_centers: Vec<f64> = Vec::with_capacity(layers.len());
+        let mut top: f64 = self.margin;
+        layers.iter().for_each(|layer: &Vec<usize>| {
+            let height: f64 = layer
+                .iter()
+                .map(|v: &usize| vertices[*v].height)
+                .fold(0.0, f64::max);
+            layer_centers.push(top + height / 2.0);
+            top += height + self.rank_spacing;
+        });
+
+        vertices
+            .iter()
+            .zip(x.iter())
+            .map(|(vertex, x): (&Vertex, &f64)| Point {
+                x: x - left + self.margin,
+                y: layer_centers[vertex.rank],
+            })
+            .collect()
+    }
+
+    /// Moves a layer's vertices towards the average of their neighbours while
+    /// keeping their order, balancing a left-to-right and a right-to-left pass
+    fn align(
+        &self,
+        vertices: &[Vertex],
+        layer: &[usize],
+        neighbours: &[Vec<usize>],
+        x: &mut [f64],
+    ) {
+        let desired: Vec<f64> = layer
+            .iter()
+            .map(|v: &usize| average(neighbours[*v].iter().map(|n: &usize| x[*n])).unwrap_or(x[*v]))
+            .collect();
+
+        let mut left: Vec<f64> = desired.clone();
+        let mut right: Vec<f64> = desired;
+        for i in 1..layer.len() {
+            left[i] =
+                left[i].max(left[i - 1] + self.gap(&vertices[layer[i - 1]], &vertices[layer[i]]));
+        }
+        for i in (0..layer.len().saturating_sub(1)).rev() {
+            right[i] =
+                right[i].min(right[i + 1] - self.gap(&vertices[layer[i]], &vertices[layer[i + 1]]));
+        }
+
+        layer
+            .iter()
+            .enumerate()
+            .for_each(|(i, v): (usize, &usize)| {
+                x[*v] = (left[i] + right[i]) / 2.0;
+            });
+    }
+
+    /// Minimum distance between the centers of two neighbouring vertices
+    fn gap(&self, a: &Vertex, b: &Vertex) -> f64 {
+        let spacing: f64 = if a.node.is_some() && b.node.is_some() {
+            self.node_spacing
+        } else {
+            self.node_spacing / 2.0
+        };
+        (a.width + b.width) / 2.0 + spacing
+    }
+}
+
+/// Reverses the links closing a cycle (found through a depth-first search) so
+/// the remaining graph can be ranked
+fn remove_cycles(count: usize, links: &mut [Link]) {
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); count];
+    links
+        .iter()
+        .enumerate()
+        .for_each(|(i, link): (usize, &Link)| outgoing[link.from].push(i));
+
+    let mut state: Vec<u8> = vec![UNVISITED; count];
+    let mut back_links: Vec<usize> = Vec::new();
+
+    for root in 0..count {
+        if state[root] != UNVISITED {
+            continue;
+        }
+
+        state[root] = VISITING;
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some(top) = stack.last_mut() {
+            let (vertex, next): (usize, usize) = *top;
+            match outgoing[vertex].get(next) {
+                Some(link) => {
+                    top.1 += 1;
+                    let target: usize = links[*link].to;
+                    match state[target] {
+                        VISITING => back_links.push(*link),
+                        UNVISITED => {
+                            state[target] = VISITING;
+                            stack.push((target, 0));
+                        }
+                        _ => {}
+                    }
+                }
+                None => {
+                    state[vertex] = VISITED;
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    back_links.into_iter().for_each(|i: usize| {
+        let link: &mut Link = &mut links[i];
+        std::mem::swap(&mut link.from, &mut link.to);
+        link.reversed = !link.reversed;
+    });
+}
+
+/// Longest-path ranking, with sources pulled down next to their successors
+fn assign_ranks(count: usize, links: &[Link]) -> Vec<usize> {
+    let mut incoming: Vec<usize> = vec![0; count];
+    let mut outgoing: Vec<Vec<(usize, usize)>> = vec![Vec::new(); count];
+    links.iter().for_each(|link: &Link| {
+        incoming[link.to] += 1;
+        outgoing[link.from].push((link.to, link.length));
+    });
+
+    let mut remaining: Vec<usize> = incoming.clone();
+    let mut order: Vec<usize> = (0..count).filter(|v: &usize| incoming[*v] == 0).collect();
+    let mut ranks: Vec<usize> = vec![0; count];
+    let mut i: usize = 0;
+    while i < order.len() {
+        let vertex: usize = order[i];
+        outgoing[vertex]
+            .iter()
+            .for_each(|(target, length): &(usize, usize)| {
+                ranks[*target] = ranks[*target].max(ranks[vertex] + length);
+                remaining[*target] -= 1;
+                if remaining[*target] == 0 {
+                    order.push(*target);
+                }
+            });
+        i += 1;
+    }
+
+    order.iter().rev().for_each(|vertex: &usize| {
+        if incoming[*vertex] == 0
+            && let Some(closest) = outgoing[*vertex]
+                .iter()
+                .map(|(target, length): &(usize, usize)| ranks[*target] - length)
+                .min()
+        {
+            ranks[*vertex] = closest;
+        }
+    });
+
+    ranks
+}
+
+fn order_layers(vertices: &[Vertex], up: &[Vec<usize>], down: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let rank_count: usize = vertices
+        .iter()
+        .map(|v: &Vertex| v.rank + 1)
+        .max()
+        .unwrap_or_default();
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); rank_count];
+    vertices
+        .iter()
+        .enumerate()
+        .for_each(|(i, vertex): (usize, &Vertex)| layers[vertex.rank].push(i));
+
+    let mut best: Vec<Vec<usize>> = layers.clone();
+    let mut best_crossings: usize = count_crossings(&layers, down);
+
+    for sweep in 0..CROSSING_SWEEPS {
+        if best_crossings == 0 {
+            break;
+        }
+
+        if sweep % 2 == 0 {
+            (1..rank_count).for_each(|r: usize| reorder(&mut layers, r, r - 1, up));
+        } else {
+            (0..rank_count.saturating_sub(1))
+                .rev()
+                .for_each(|r: usize| reorder(&mut layers, r, r + 1, down));
+        }
+
+        let crossings: usize = count_crossings(&layers, down);
+        if crossings < best_crossings {
+            best = layers.clone();
+            best_crossings = crossings;
+        }
+    }
+
+    best
+}
+
+/// Moves the right-hand node of each sideways pair next to its left-hand one
+/// when both ended up on the same rank
+fn keep_side_by_side(layers: &mut [Vec<usize>], vertices: &[Vertex], pairs: &[(usize, usize)]) {
+    pairs.iter().for_each(|(left, right): &(usize, usize)| {
+        if vertices[*left].rank != vertices[*right].rank {
+            return;
+        }
+        let layer: &mut Vec<usize> = &mut layers[vertices[*left].rank];
+        layer.retain(|v: &usize| v != right);
+        if let Some(position) = layer.iter().position(|v: &usize| v == left) {
+            layer.insert(position + 1, *right);
+        }
+    });
+}
+
+/// Sorts a layer by the barycenter of each vertex's neighbours in the fixed layer
+fn reorder(layers: &mut [Vec<usize>], layer: usize, fixed: usize, neighbours: &[Vec<usize>]) {
+    let positions: HashMap<usize, f64> = positions(&layers[fixed]);
+
+    let mut keyed: Vec<(f64, usize)> = layers[layer]
+        .iter()
+        .enumerate()
+        .map(|(i, v): (usize, &usize)| {
+            let barycenter: Option<f64> = average(
+                neighbours[*v]
+                    .iter()
+                    .filter_map(|n: &usize| positions.get(n).copied()),
+            );
+            (barycenter.unwrap_or(i as f64), *v)
+        })
+        .collect();
+    keyed.sort_by(|a: &(f64, usize), b: &(f64, usize)| a.0.total_cmp(&b.0));
+
+    layers[layer] = keyed.into_iter().map(|(_, v): (f64, usize)| v).collect();
+}
+
+fn count_crossings(layers: &[Vec<usize>], down: &[Vec<usize>]) -> usize {
+    layers
+        .windows(2)
+        .map(|pair: &[Vec<usize>]| {
+            let lower: HashMap<usize, f64> = positions(&pair[1]);
+            let segments: Vec<(usize, f64)> = pair[0]
+                .iter()
+                .enumerate()
+                .flat_map(|(i, v): (usize, &usize)| {
+                    down[*v]
+                        .iter()
+                        .filter_map(|n: &usize| lower.get(n).map(|p: &f64| (i, *p)))
+                        .collect::<Vec<(usize, f64)>>()
+                })
+                .collect();
+
+            let mut crossings: usize = 0;
+            for (i, a) in segments.iter().enumerate() {
+                for b in &segments[i + 1..] {
+                    if (a.0 < b.0 && a.1 > b.1) || (a.0 > b.0 && a.1 < b.1) {
+                        crossings += 1;
+                    }
+                }
+            }
+            crossings
+        })
+        .sum()
+}
+
+fn positions(layer: &[usize]) -> HashMap<usize, f64> {
+    layer
+        .iter()
+        .enumerate()
+        .map(|(i, v): (usize, &usize)| (*v, i as f64))
+        .collect()
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count): (f64, usize) = values.fold((0.0, 0), |(sum, count): (f64, usize), v: f64| {
+        (sum + v, count + 1)
+    });
+    (count > 0).then(|| sum / count as f64)
+}
@@ -0,0 +1,76 @@
+use lib_core::entities::{
+    geometry::{Point, Rect},
+    member::Member,
+    node::{Node, NodeKind},
+};
+
+const CHAR_WIDTH: f64 = 8.0;
+const PADDING: f64 = 10.0;
+const HEADER_HEIGHT: f64 = 30.0;
+const MEMBER_HEIGHT: f64 = 18.0;
+const MIN_WIDTH: f64 = 80.0;
+const TEXT_HEIGHT: f64 = 16.0;
+
+/// Estimates the box needed to draw a node: a header with its label and one
+/// line per member, using a fixed-width character approximation. Nodes that
+/// already have a size (imported geometry, nested clusters) keep it.
+pub fn node_size(node: &Node) -> (f64, f64) {
+    if let Some(rect) = node
+        .geometry
+        .filter(|r: &Rect| r.width > 0.0 && r.height > 0.0)
+    {
+        return (rect.width, rect.height);
+    }
+
+    let label_length: usize = node.label.as_deref().unwrap_or(&node.id).chars().count();
+    let member_length: usize = node
+        .members
+        .iter()
+        .map(|member: &Member| member_text_length(member))
+        .max()
+        .unwrap_or_default();
+
+    let width: f64 =
+        (label_length.max(member_length) as f64 * CHAR_WIDTH + 2.0 * PADDING).max(MIN_WIDTH);
+    let height: f64 = if node.members.is_empty() {
+        HEADER_HEIGHT
+    } else {
+        HEADER_HEIGHT + node.members.len() as f64 * MEMBER_HEIGHT + PADDING
+    };
+
+    (width, height)
+}
+
+/// Box needed to draw a single line of text, e.g. an edge label
+pub fn text_size(text: &str) -> (f64, f64) {
+    (
+        text.chars().count() as f64 * CHAR_WIDTH + PADDING / 2.0,
+        TEXT_HEIGHT,
+    )
+}
+
+/// Center of a node's label: inside its header, or below the shape for nodes
+/// drawn as figures
+pub fn label_anchor(node: &Node, rect: &Rect) -> Point {
+    match node.kind {
+        NodeKind::Actor => Point {
+            x: rect.center().x,
+            y: rect.y + rect.height + TEXT_HEIGHT / 2.0,
+        },
+        _ if node.members.is_empty() => rect.center(),
+        _ => Point {
+            x: rect.center().x,
+            y: rect.y + HEADER_HEIGHT / 2.0,
+        },
+    }
+}
+
+fn member_text_length(member: &Member) -> usize {
+    // Rendered as "name: Type"
+    member.name.chars().count()
+        + member
+            .type_name
+            .as_ref()
+            .map(|t: &String| t.chars().count() + 2)
+            .unwrap_or_default()
+}
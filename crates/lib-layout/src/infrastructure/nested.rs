@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+
+use lib_core::entities::{
+    edge::Edge,
+    geometry::{Point, Rect},
+    graph::Graph,
+    group::Group,
+    id::Id,
+    node::{Node, NodeKind},
+};
+
+use crate::infrastructure::{geometry, measure};
+
+/// Lays out clusters bottom-up: the content of each group is laid out on its
+/// own, the group then takes part in its parent's layout as a single node
+/// sized to fit its content, a margin and a header band holding its label.
+pub struct NestedLayout {
+    pub padding: f64,
+    pub label_height: f64,
+}
+
+impl Default for NestedLayout {
+    fn default() -> Self {
+        Self {
+            padding: 15.0,
+            label_height: 24.0,
+        }
+    }
+}
+
+/// Direct content of a group (or of the top level)
+#[derive(Default)]
+struct Level {
+    nodes: Vec<Id>,
+    groups: Vec<Id>,
+    graph: Graph,
+}
+
+impl NestedLayout {
+    pub fn apply(&self, graph: &mut Graph, layout: &dyn Fn(&mut Graph)) {
+        if graph.groups.is_empty() {
+            layout(graph);
+            return;
+        }
+
+        let containers: HashMap<Id, Option<Id>> = containers(graph);
+        let depth = |id: &Id| -> usize { chain(id, &containers).len() };
+
+        let mut levels: HashMap<Option<Id>, Level> = HashMap::new();
+        levels.insert(None, Level::default());
+        graph.groups.keys().for_each(|id: &Id| {
+            levels.insert(Some(id.clone()), Level::default());
+        });
+        let mut node_ids: Vec<&Id> = graph.nodes.keys().collect();
+        node_ids.sort();
+        node_ids.into_iter().for_each(|id: &Id| {
+            if let Some(level) = levels.get_mut(&containers[id]) {
+                level.nodes.push(id.clone());
+            }
+        });
+        let mut group_ids: Vec<&Id> = graph.groups.keys().collect();
+        group_ids.sort();
+        group_ids.iter().for_each(|id: &&Id| {
+            if let Some(level) = levels.get_mut(&containers[*id]) {
+                level.groups.push((*id).clone());
+            }
+        });
+
+        // Innermost groups first, so every group is sized before its parent
+        let mut order: Vec<Option<Id>> = group_ids
+            .iter()
+            .map(|id: &&Id| Some((*id).clone()))
+            .collect();
+        order.sort_by_key(|id: &Option<Id>| std::cmp::Reverse(id.as_ref().map(depth)));
+        order.push(None);
+
+        let mut sizes: HashMap<Id, (f64, f64)> = HashMap::new();
+        for container in &order {
+            let mut level: Level = levels.remove(container).unwrap_or_default();
+            level.graph = self.level_graph(graph, container, &level, &containers, &sizes);
+            layout(&mut level.graph);
+
+            if let Some(group) = container {
+                let label: Option<&str> = graph.groups[group].label.as_deref();
+                sizes.insert(group.clone(), self.frame(&mut level.graph, label));
+            }
+            levels.insert(container.clone(), level);
+        }
+
+        // Place every level at the position its group got in the enclosing level
+        order.reverse();
+        let mut offsets: HashMap<Option<Id>, Point> = HashMap::new();
+        offsets.insert(None, Point::default());
+        for container in &order {
+            let offset: Point = offsets.get(container).copied().unwrap_or_default();
+            let level: &Level = &levels[container];
+
+            level.graph.nodes.values().for_each(|node: &Node| {
+                let Some(rect) = node.geometry.map(|r: Rect| translate(&r, &offset)) else {
+                    return;
+                };
+                if let Some(group) = graph
+                    .groups
+                    .get_mut(&node.id)
+                    .filter(|_| level.groups.contains(&node.id))
+                {
+                    group.geometry = Some(rect);
+                    offsets.insert(
+                        Some(node.id.clone()),
+                        Point {
+                            x: rect.x,
+                            y: rect.y,
+                        },
+                    );
+                } else if let Some(original) = graph.nodes.get_mut(&node.id) {
+                    original.geometry = Some(rect);
+                }
+            });
+        }
+
+        // Edges keep the route of the level where both of their ends are siblings
+        let routes: Vec<(Id, Vec<Point>)> = levels
+            .iter()
+            .flat_map(|(container, level): (&Option<Id>, &Level)| {
+                let offset: Point = offsets.get(container).copied().unwrap_or_default();
+                level.graph.edges.values().map(move |edge: &Edge| {
+                    let points: Vec<Point> = edge
+                        .waypoints
+                        .iter()
+                        .map(|p: &Point| Point {
+                            x: p.x + offset.x,
+                            y: p.y + offset.y,
+                        })
+                        .collect();
+                    (edge.id.clone(), points)
+                })
+            })
+            .collect();
+
+        routes
+            .into_iter()
+            .for_each(|(id, mut points): (Id, Vec<Point>)| {
+                let (from, to): (Option<Rect>, Option<Rect>) = match graph.edges.get(&id) {
+                    Some(edge) => (bounds(graph, &edge.from), bounds(graph, &edge.to)),
+                    None => return,
+                };
+
+                // Ends inside a cluster are pulled from the cluster frame to the actual node
+                if let (Some(from), Some(to), true) = (from, to, points.len() >= 2) {
+                    let last: usize = points.len() - 1;
+                    let start: Point = if last == 1 { to.center() } else { points[1] };
+                    let end: Point = if last == 1 {
+                        from.center()
+                    } else {
+                        points[last - 1]
+                    };
+                    if !on_border(&from, &points[0]) {
+                        points[0] = geometry::border_point(&from, &start);
+                    }
+                    if !on_border(&to, &points[last]) {
+                        points[last] = geometry::border_point(&to, &end);
+                    }
+                }
+                if let Some(edge) = graph.edges.get_mut(&id) {
+                    edge.waypoints = points;
+                }
+            });
+    }
+
+    /// A graph with the level's nodes, its groups as fixed-size nodes and the
+    /// edges connecting them, edges to nested content being attached to the group
+    fn level_graph(
+        &self,
+        graph: &Graph,
+        container: &Option<Id>,
+        level: &Level,
+        containers: &HashMap<Id, Option<Id>>,
+        sizes: &HashMap<Id, (f64, f64)>,
+    ) -> Graph {
+        let mut nodes: HashMap<Id, Node> = level
+            .nodes
+            .iter()
+            .map(|id: &Id| (id.clone(), graph.nodes[id].clone()))
+            .collect();
+        level.groups.iter().for_each(|id: &Id| {
+            let (width, height): (f64, f64) = sizes.get(id).copied().unwrap_or_default();
+            nodes.insert(
+                id.clone(),
+                Node {
+                    id: id.clone(),
+                    kind: NodeKind::Group,
+                    label: graph.groups[id].label.clone(),
+                    geometry: Some(Rect {
+                        x: 0.0,
+                        y: 0.0,
+                        width,
+                        height,
+                    }),
+                    ..Default::default()
+                },
+            );
+        });
+
+        let edges: HashMap<Id, Edge> = graph
+            .edges
+            .values()
+            .filter_map(|edge: &Edge| {
+                let from: Id = representative(&edge.from, container, containers)?;
+                let to: Id = representative(&edge.to, container, containers)?;
+                // Edges within a single group were handled by its own level
+                if from == to && edge.from != edge.to {
+                    return None;
+                }
+                Some((
+                    edge.id.clone(),
+                    Edge {
+                        from,
+                        to,
+                        waypoints: Vec::new(),
+                        ..edge.clone()
+                    },
+                ))
+            })
+            .collect();
+
+        Graph {
+            kind: graph.kind.clone(),
+            direction: graph.direction,
+            nodes,
+            edges,
+            ..Default::default()
+        }
+    }
+
+    /// Moves a laid out level inside its group frame and returns the frame size
+    fn frame(&self, level: &mut Graph, label: Option<&str>) -> (f64, f64) {
+        let header: f64 = if label.is_some() {
+            self.label_height
+        } else {
+            0.0
+        };
+        let label_width: f64 = label
+            .map(|l: &str| {
+                measure::node_size(&Node {
+                    label: Some(l.into()),
+                    ..Default::default()
+                })
+                .0
+            })
+            .unwrap_or_default();
+
+        let content: Option<Rect> = level
+            .nodes
+            .values()
+            .filter_map(|n: &Node| n.geometry)
+            .reduce(|a: Rect, b: Rect| union(&a, &b));
+        let Some(content) = content else {
+            return (
+                label_width.max(2.0 * self.padding),
+                header + 2.0 * self.padding,
+            );
+        };
+
+        let offset: Point = Point {
+            x: self.padding - content.x,
+            y: self.padding + header - content.y,
+        };
+        level.nodes.values_mut().for_each(|node: &mut Node| {
+            node.geometry = node.geometry.map(|r: Rect| translate(&r, &offset));
+        });
+        level.edges.values_mut().for_each(|edge: &mut Edge| {
+            edge.waypoints.iter_mut().for_each(|p: &mut Point| {
+                p.x += offset.x;
+                p.y += offset.y;
+            });
+        });
+
+        (
+            (content.width + 2.0 * self.padding).max(label_width),
+            content.height + 2.0 * self.padding + header,
+        )
+    }
+}
+
+/// The group directly containing each node and group, None for the top level
+fn containers(graph: &Graph) -> HashMap<Id, Option<Id>> {
+    let mut listed: HashMap<&Id, &Id> = HashMap::new();
+    graph.groups.values().for_each(|group: &Group| {
+        group.children.iter().for_each(|child: &Id| {
+            listed.entry(child).or_insert(&group.id);
+        });
+    });
+
+    let parent_of = |id: &Id, parent: &Option<Id>| -> Option<Id> {
+        parent
+            .as_ref()
+            .or(listed.get(id).copied())
+            .filter(|p: &&Id| graph.groups.contains_key(*p) && *p != id)
+            .cloned()
+    };
+
+    let mut containers: HashMap<Id, Option<Id>> = HashMap::new();
+    graph.nodes.values().for_each(|node: &Node| {
+        containers.insert(node.id.clone(), parent_of(&node.id, &node.parent));
+    });
+    graph.groups.values().for_each(|group: &Group| {
+        containers.insert(group.id.clone(), parent_of(&group.id, &group.parent));
+    });
+
+    // Groups nested in a cycle are moved to the top level
+    let cyclic: Vec<Id> = graph
+        .groups
+        .keys()
+        .filter(|id: &&Id| {
+            let mut seen: Vec<&Id> = vec![*id];
+            let mut current: Option<&Id> = containers[*id].as_ref();
+            while let Some(group) = current {
+                if seen.contains(&group) {
+                    return true;
+                }
+                seen.push(group);
+                current = containers.get(group).and_then(|c: &Option<Id>| c.as_ref());
+            }
+            false
+        })
+        .cloned()
+        .collect();
+    cyclic.into_iter().for_each(|id: Id| {
+        containers.insert(id, None);
+    });
+
+    containers
+}
+
+/// Groups enclosing an element, innermost first
+fn chain(id: &Id, containers: &HashMap<Id, Option<Id>>) -> Vec<Id> {
+    let mut chain: Vec<Id> = Vec::new();
+    let mut current: Option<&Id> = containers.get(id).and_then(|c: &Option<Id>| c.as_ref());
+    while let Some(group) = current {
+        if chain.contains(group) {
+            break;
+        }
+        chain.push(group.clone());
+        current = containers.get(group).and_then(|c: &Option<Id>| c.as_ref());
+    }
+    chain
+}
+
+/// The element standing for `id` among the direct content of `container`
+fn representative(
+    id: &Id,
+    container: &Option<Id>,
+    containers: &HashMap<Id, Option<Id>>,
+) -> Option<Id> {
+    let mut current: &Id = id;
+    let mut steps: usize = 0;
+    loop {
+        let parent: &Option<Id> = containers.get(current)?;
+        if parent == container {
+            return Some(current.clone());
+        }
+        current = parent.as_ref()?;
+
+        steps += 1;
+        if steps > containers.len() {
+            return None;
+        }
+    }
+}
+
+fn bounds(graph: &Graph, id: &Id) -> Option<Rect> {
+    graph
+        .nodes
+        .get(id)
+        .and_then(|n: &Node| n.geometry)
+        .or_else(|| graph.groups.get(id).and_then(|g: &Group| g.geometry))
+}
+
+fn on_border(rect: &Rect, p: &Point) -> bool {
+    const EPSILON: f64 = 1e-6;
+    let within_x: bool = p.x >= rect.x - EPSILON && p.x <= rect.x + rect.width + EPSILON;
+    let within_y: bool = p.y >= rect.y - EPSILON && p.y <= rect.y + rect.height + EPSILON;
+    (within_x && ((p.y - rect.y).abs() < EPSILON || (p.y - rect.y - rect.height).abs() < EPSILON))
+        || (within_y
+            && ((p.x - rect.x).abs() < EPSILON || (p.x - rect.x - rect.width).abs() < EPSILON))
+}
+
+fn translate(rect: &Rect, offset: &Point) -> Rect {
+    Rect {
+        x: rect.x + offset.x,
+        y: rect.y + offset.y,
+        ..*rect
+    }
+}
+
+fn union(a: &Rect, b: &Rect) -> Rect {
+    let (x, y): (f64, f64) = (a.x.min(b.x), a.y.min(b.y));
+    Rect {
+        x,
+        y,
+        width: (a.x + a.width).max(b.x + b.width) - x,
+        height: (a.y + a.height).max(b.y + b.height) - y,
+    }
+}
@@ -0,0 +1,55 @@
+use lib_core::entities::{
+    geometry::{Point, Rect},
+    graph::LayoutDirection,
+};
+
+/// Layouts are computed flowing top to bottom and then turned towards the
+/// graph direction, `extent` being the size of the layout along its flow
+pub struct Orientation {
+    pub direction: LayoutDirection,
+    pub extent: f64,
+}
+
+impl Orientation {
+    pub fn is_horizontal(direction: LayoutDirection) -> bool {
+        matches!(
+            direction,
+            LayoutDirection::LeftToRight | LayoutDirection::RightToLeft
+        )
+    }
+
+    /// Size of a node as seen by a top to bottom layout
+    pub fn flow_size(direction: LayoutDirection, (width, height): (f64, f64)) -> (f64, f64) {
+        if Self::is_horizontal(direction) {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    pub fn point(&self, p: &Point) -> Point {
+        match self.direction {
+            LayoutDirection::TopToBottom => *p,
+            LayoutDirection::BottomToTop => Point {
+                x: p.x,
+                y: self.extent - p.y,
+            },
+            LayoutDirection::LeftToRight => Point { x: p.y, y: p.x },
+            LayoutDirection::RightToLeft => Point {
+                x: self.extent - p.y,
+                y: p.x,
+            },
+        }
+    }
+
+    /// Places a node of the given (unturned) size around a turned center
+    pub fn rect(&self, center: &Point, (width, height): (f64, f64)) -> Rect {
+        let center: Point = self.point(center);
+        Rect {
+            x: center.x - width / 2.0,
+            y: center.y - height / 2.0,
+            width,
+            height,
+        }
+    }
+}
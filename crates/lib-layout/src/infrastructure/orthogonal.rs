@@ -0,0 +1,481 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use lib_core::entities::{
+    edge::Edge,
+    geometry::{Point, Rect},
+    graph::Graph,
+    group::Group,
+    id::Id,
+    node::Node,
+};
+
+use crate::infrastructure::geometry;
+
+const UP: usize = 0;
+const RIGHT: usize = 1;
+const DOWN: usize = 2;
+const LEFT: usize = 3;
+// Pseudo direction of states that entered the target
+const ARRIVED: usize = 4;
+
+/// Routes edges as axis-aligned polylines around node boxes and around the
+/// clusters that contain neither end of the edge. Routes follow a sparse grid
+/// built from the obstacle borders and are searched with A*, penalising bends.
+pub struct OrthogonalRouter {
+    pub margin: f64,
+    pub bend_penalty: f64,
+}
+
+impl Default for OrthogonalRouter {
+    fn default() -> Self {
+        Self {
+            margin: 10.0,
+            bend_penalty: 40.0,
+        }
+    }
+}
+
+struct Obstacle {
+    rect: Rect,
+    // Set for clusters, which an edge may enter when one of its ends is inside
+    group: Option<Id>,
+}
+
+struct Grid {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+}
+
+#[derive(Clone, Copy)]
+struct Port {
+    point: Point,
+    // Direction pointing away from the node
+    direction: usize,
+}
+
+#[derive(PartialEq)]
+struct State {
+    estimate: f64,
+    cost: f64,
+    x: usize,
+    y: usize,
+    direction: usize,
+}
+
+impl Eq for State {}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so the heap pops the cheapest estimate first
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl OrthogonalRouter {
+    /// Routes every edge whose ends have geometry, leaving the others untouched
+    pub fn apply(&self, graph: &mut Graph) {
+        let rects: HashMap<Id, Rect> = graph
+            .nodes
+            .values()
+            .filter_map(|node: &Node| node.geometry.map(|rect: Rect| (node.id.clone(), rect)))
+            .collect();
+
+        let mut obstacles: Vec<Obstacle> = rects
+            .values()
+            .map(|rect: &Rect| Obstacle {
+                rect: self.inflate(rect),
+                group: None,
+            })
+            .collect();
+        obstacles.extend(graph.groups.values().filter_map(|group: &Group| {
+            group.geometry.map(|rect: Rect| Obstacle {
+                rect: self.inflate(&rect),
+                group: Some(group.id.clone()),
+            })
+        }));
+
+        let grid: Grid = self.grid(&obstacles);
+
+        let routes: Vec<(Id, Vec<Point>)> = graph
+            .edges
+            .values()
+            .filter_map(|edge: &Edge| {
+                let from: &Rect = rects.get(&edge.from)?;
+                let to: &Rect = rects.get(&edge.to)?;
+                if edge.layout.hidden {
+                    return Some((edge.id.clone(), Vec::new()));
+                }
+                if edge.from == edge.to {
+                    return Some((edge.id.clone(), self.self_loop(from)));
+                }
+
+                let ancestors: Vec<Id> = [&edge.from, &edge.to]
+                    .iter()
+                    .flat_map(|id: &&Id| ancestors(graph, id))
+                    .collect();
+                let blocking: Vec<&Obstacle> = obstacles
+                    .iter()
+                    .filter(|o: &&Obstacle| {
+                        o.group
+                            .as_ref()
+                            .is_none_or(|group: &Id| !ancestors.contains(group))
+                    })
+                    .collect();
+
+                let route: Vec<Point> =
+                    self.route(&grid, &blocking, from, to).unwrap_or_else(|| {
+                        vec![
+                            geometry::border_point(from, &to.center()),
+                            geometry::border_point(to, &from.center()),
+                        ]
+                    });
+                Some((edge.id.clone(), route))
+            })
+            .collect();
+
+        routes
+            .into_iter()
+            .for_each(|(id, waypoints): (Id, Vec<Point>)| {
+                if let Some(edge) = graph.edges.get_mut(&id) {
+                    edge.waypoints = waypoints;
+                }
+            });
+    }
+
+    fn route(
+        &self,
+        grid: &Grid,
+        obstacles: &[&Obstacle],
+        from: &Rect,
+        to: &Rect,
+    ) -> Option<Vec<Point>> {
+        let starts: Vec<Port> = self.ports(from);
+        let goals: Vec<Port> = self.ports(to);
+
+        let locate = |p: &Point| -> Option<(usize, usize)> {
+            Some((position(&grid.xs, p.x)?, position(&grid.ys, p.y)?))
+        };
+        let heuristic = |x: usize, y: usize| -> f64 {
+            goals
+                .iter()
+                .map(|g: &Port| (grid.xs[x] - g.point.x).abs() + (grid.ys[y] - g.point.y).abs())
+                .fold(f64::INFINITY, f64::min)
+        };
+        let goal_at: HashMap<(usize, usize), Port> = goals
+            .iter()
+            .filter_map(|g: &Port| Some((locate(&g.point)?, *g)))
+            .collect();
+
+        let mut best: HashMap<(usize, usize, usize), f64> = HashMap::new();
+        let mut previous: HashMap<(usize, usize, usize), (usize, usize, usize)> = HashMap::new();
+        let mut heap: BinaryHeap<State> = BinaryHeap::new();
+        let mut origins: HashMap<(usize, usize, usize), Port> = HashMap::new();
+
+        starts.iter().for_each(|start: &Port| {
+            if let Some((x, y)) = locate(&start.point) {
+                best.insert((x, y, start.direction), self.margin);
+                origins.insert((x, y, start.direction), *start);
+                heap.push(State {
+                    estimate: self.margin + heuristic(x, y),
+                    cost: self.margin,
+                    x,
+                    y,
+                    direction: start.direction,
+                });
+            }
+        });
+
+        while let Some(state) = heap.pop() {
+            let key: (usize, usize, usize) = (state.x, state.y, state.direction);
+            if best.get(&key).is_some_and(|cost: &f64| *cost < state.cost) {
+                continue;
+            }
+
+            if let Some(goal) = goal_at.get(&(state.x, state.y)) {
+                if state.direction == ARRIVED {
+                    return Some(rebuild(grid, &previous, &origins, key, from, to, goal));
+                }
+
+                // Entering the target sideways costs one more bend
+                let entering: usize = (goal.direction + 2) % 4;
+                let bend: f64 = if state.direction == entering {
+                    0.0
+                } else {
+                    self.bend_penalty
+                };
+                let arrived: (usize, usize, usize) = (state.x, state.y, ARRIVED);
+                let cost: f64 = state.cost + self.margin + bend;
+                if best.get(&arrived).is_none_or(|known: &f64| cost < *known) {
+                    best.insert(arrived, cost);
+                    previous.insert(arrived, key);
+                    heap.push(State {
+                        estimate: cost,
+                        cost,
+                        x: state.x,
+                        y: state.y,
+                        direction: ARRIVED,
+                    });
+                }
+            }
+
+            for direction in [UP, RIGHT, DOWN, LEFT] {
+                if direction == (state.direction + 2) % 4 {
+                    continue;
+                }
+                let Some((x, y)) = step(grid, state.x, state.y, direction) else {
+                    continue;
+                };
+
+                let (a, b): (Point, Point) = (
+                    Point {
+                        x: grid.xs[state.x],
+                        y: grid.ys[state.y],
+                    },
+                    Point {
+                        x: grid.xs[x],
+                        y: grid.ys[y],
+                    },
+                );
+                let midpoint: Point = Point {
+                    x: (a.x + b.x) / 2.0,
+                    y: (a.y + b.y) / 2.0,
+                };
+                if obstacles.iter().any(|o: &&Obstacle| {
+                    strictly_inside(&o.rect, &b) || strictly_inside(&o.rect, &midpoint)
+                }) {
+                    continue;
+                }
+
+                let bend: f64 = if direction == state.direction {
+                    0.0
+                } else {
+                    self.bend_penalty
+                };
+                let cost: f64 = state.cost + (a.x - b.x).abs() + (a.y - b.y).abs() + bend;
+                let next: (usize, usize, usize) = (x, y, direction);
+                if best.get(&next).is_none_or(|known: &f64| cost < *known) {
+                    best.insert(next, cost);
+                    previous.insert(next, key);
+                    heap.push(State {
+                        estimate: cost + heuristic(x, y),
+                        cost,
+                        x,
+                        y,
+                        direction,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The four side midpoints of a node, pushed out by the margin
+    fn ports(&self, rect: &Rect) -> Vec<Port> {
+        let center: Point = rect.center();
+        vec![
+            Port {
+                point: Point {
+                    x: center.x,
+                    y: rect.y - self.margin,
+                },
+                direction: UP,
+            },
+            Port {
+                point: Point {
+                    x: rect.x + rect.width + self.margin,
+                    y: center.y,
+                },
+                direction: RIGHT,
+            },
+            Port {
+                point: Point {
+                    x: center.x,
+                    y: rect.y + rect.height + self.margin,
+                },
+                direction: DOWN,
+            },
+            Port {
+                point: Point {
+                    x: rect.x - self.margin,
+                    y: center.y,
+                },
+                direction: LEFT,
+            },
+        ]
+    }
+
+    /// Grid lines along every obstacle border and through every port
+    fn grid(&self, obstacles: &[Obstacle]) -> Grid {
+        let mut xs: Vec<f64> = Vec::new();
+        let mut ys: Vec<f64> = Vec::new();
+        obstacles.iter().for_each(|o: &Obstacle| {
+            xs.extend([o.rect.x, o.rect.x + o.rect.width, o.rect.center().x]);
+            ys.extend([o.rect.y, o.rect.y + o.rect.height, o.rect.center().y]);
+        });
+
+        Grid {
+            xs: sorted(xs),
+            ys: sorted(ys),
+        }
+    }
+
+    fn self_loop(&self, rect: &Rect) -> Vec<Point> {
+        let right: f64 = rect.x + rect.width;
+        let quarter: f64 = rect.height / 4.0;
+        let center: Point = rect.center();
+        vec![
+            Point {
+                x: right,
+                y: center.y - quarter,
+            },
+            Point {
+                x: right + 2.0 * self.margin,
+                y: center.y - quarter,
+            },
+            Point {
+                x: right + 2.0 * self.margin,
+                y: center.y + quarter,
+            },
+            Point {
+                x: right,
+                y: center.y + quarter,
+            },
+        ]
+    }
+
+    fn inflate(&self, rect: &Rect) -> Rect {
+        Rect {
+            x: rect.x - self.margin,
+            y: rect.y - self.margin,
+            width: rect.width + 2.0 * self.margin,
+            height: rect.height + 2.0 * self.margin,
+        }
+    }
+}
+
+/// Point on the node side a port sits in front of
+fn on_border(rect: &Rect, port: &Port) -> Point {
+    match port.direction {
+        UP => Point {
+            x: port.point.x,
+            y: rect.y,
+        },
+        DOWN => Point {
+            x: port.point.x,
+            y: rect.y + rect.height,
+        },
+        LEFT => Point {
+            x: rect.x,
+            y: port.point.y,
+        },
+        _ => Point {
+            x: rect.x + rect.width,
+            y: port.point.y,
+        },
+    }
+}
+
+fn rebuild(
+    grid: &Grid,
+    previous: &HashMap<(usize, usize, usize), (usize, usize, usize)>,
+    origins: &HashMap<(usize, usize, usize), Port>,
+    last: (usize, usize, usize),
+    from: &Rect,
+    to: &Rect,
+    goal: &Port,
+) -> Vec<Point> {
+    let mut points: Vec<Point> = vec![on_border(to, goal)];
+    let mut key: (usize, usize, usize) = last;
+    loop {
+        points.push(Point {
+            x: grid.xs[key.0],
+            y: grid.ys[key.1],
+        });
+        match previous.get(&key) {
+            Some(prev) => key = *prev,
+            None => break,
+        }
+    }
+    if let Some(origin) = origins.get(&key) {
+        points.push(on_border(from, origin));
+    }
+    points.reverse();
+
+    simplify(points)
+}
+
+fn step(grid: &Grid, x: usize, y: usize, direction: usize) -> Option<(usize, usize)> {
+    match direction {
+        UP => Some((x, y.checked_sub(1)?)),
+        DOWN => (y + 1 < grid.ys.len()).then_some((x, y + 1)),
+        LEFT => Some((x.checked_sub(1)?, y)),
+        _ => (x + 1 < grid.xs.len()).then_some((x + 1, y)),
+    }
+}
+
+fn ancestors(graph: &Graph, node: &Id) -> Vec<Id> {
+    let mut ancestors: Vec<Id> = Vec::new();
+    let mut current: Option<&Id> = graph.nodes.get(node).and_then(|n: &Node| n.parent.as_ref());
+    while let Some(group) = current {
+        if ancestors.contains(group) {
+            break;
+        }
+        ancestors.push(group.clone());
+        current = graph
+            .groups
+            .get(group)
+            .and_then(|g: &Group| g.parent.as_ref());
+    }
+    ancestors
+}
+
+fn strictly_inside(rect: &Rect, p: &Point) -> bool {
+    const EPSILON: f64 = 1e-9;
+    p.x > rect.x + EPSILON
+        && p.x < rect.x + rect.width - EPSILON
+        && p.y > rect.y + EPSILON
+        && p.y < rect.y + rect.height - EPSILON
+}
+
+fn sorted(mut values: Vec<f64>) -> Vec<f64> {
+    values.sort_by(f64::total_cmp);
+    values.dedup_by(|a: &mut f64, b: &mut f64| (*a - *b).abs() < 1e-9);
+    values
+}
+
+fn position(values: &[f64], value: f64) -> Option<usize> {
+    values.iter().position(|v: &f64| (v - value).abs() < 1e-9)
+}
+
+/// Drops points lying on a straight line between their neighbours
+fn simplify(points: Vec<Point>) -> Vec<Point> {
+    let mut simplified: Vec<Point> = Vec::with_capacity(points.len());
+    for point in points {
+        if simplified.last().is_some_and(|last: &Point| last == &point) {
+            continue;
+        }
+        if simplified.len() >= 2 {
+            let (a, b): (Point, Point) = (
+                simplified[simplified.len() - 2],
+                simplified[simplified.len() - 1],
+            );
+            let collinear: bool = ((a.x - b.x).abs() < 1e-9 && (b.x - point.x).abs() < 1e-9)
+                || ((a.y - b.y).abs() < 1e-9 && (b.y - point.y).abs() < 1e-9);
+            if collinear {
+                simplified.pop();
+            }
+        }
+        simplified.push(point);
+    }
+    simplified
+}
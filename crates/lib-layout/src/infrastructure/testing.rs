@@ -0,0 +1,97 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    geometry::Rect,
+    graph::Graph,
+    id::{Id, Text},
+    node::Node,
+};
+
+/// Nodes labelled with their ids, joined by associations
+pub(crate) fn graph<S: AsRef<str>>(nodes: &[S], edges: &[(S, S)]) -> Graph {
+    let mut graph: Graph = Graph::default();
+    nodes
+        .iter()
+        .for_each(|id: &S| add_node(&mut graph, id.as_ref()));
+    edges.iter().for_each(|(from, to): &(S, S)| {
+        add_edge(
+            &mut graph,
+            from.as_ref(),
+            to.as_ref(),
+            EdgeKind::Association,
+        );
+    });
+    graph
+}
+
+/// The nodes the edges join and the edges themselves
+pub(crate) fn graph_of(edges: &[(&str, &str, EdgeKind)]) -> Graph {
+    let mut graph: Graph = Graph::default();
+    edges
+        .iter()
+        .for_each(|(from, to, kind): &(&str, &str, EdgeKind)| {
+            add_node(&mut graph, from);
+            add_node(&mut graph, to);
+            add_edge(&mut graph, from, to, kind.clone());
+        });
+    graph
+}
+
+/// Nodes already placed as 80x30 boxes at the given corners
+pub(crate) fn placed(nodes: &[(&str, f64, f64)], edges: &[(&str, &str)]) -> Graph {
+    let mut graph: Graph = graph(&[] as &[&str], edges);
+    nodes.iter().for_each(|(id, x, y): &(&str, f64, f64)| {
+        add_node(&mut graph, id);
+        graph.nodes.get_mut(*id).unwrap().geometry = Some(Rect {
+            x: *x,
+            y: *y,
+            width: 80.0,
+            height: 30.0,
+        });
+    });
+    graph
+}
+
+pub(crate) fn add_node(graph: &mut Graph, id: &str) {
+    graph.nodes.insert(
+        Id::from(id),
+        Node {
+            id: Id::from(id),
+            label: Some(Text::from(id)),
+            ..Default::default()
+        },
+    );
+}
+
+/// An edge with the id `from-to`, directed unless undirected
+pub(crate) fn add_edge(graph: &mut Graph, from: &str, to: &str, kind: EdgeKind) {
+    let id: Id = format!("{}-{}", from, to).into();
+    graph.edges.insert(
+        id.clone(),
+        Edge {
+            id,
+            from: from.into(),
+            to: to.into(),
+            directed: kind != EdgeKind::Undirected,
+            kind,
+            ..Default::default()
+        },
+    );
+}
+
+pub(crate) fn rect(graph: &Graph, id: &str) -> Rect {
+    graph.nodes[id].geometry.expect("Missing node geometry")
+}
+
+pub(crate) fn edge<'a>(graph: &'a Graph, from: &str, to: &str) -> &'a Edge {
+    graph
+        .edges
+        .get(format!("{}-{}", from, to).as_str())
+        .expect("Missing edge")
+}
+
+pub(crate) fn edge_mut<'a>(graph: &'a mut Graph, from: &str, to: &str) -> &'a mut Edge {
+    graph
+        .edges
+        .get_mut(format!("{}-{}", from, to).as_str())
+        .expect("Missing edge")
+}
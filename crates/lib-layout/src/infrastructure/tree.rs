@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    geometry::{Point, Rect},
+    graph::Graph,
+    id::Id,
+};
+
+use crate::infrastructure::{measure, orientation::Orientation};
+
+/// Tidy tree layout: every subtree is placed as close as possible to its left
+/// sibling without their contours overlapping and parents are centered above
+/// their children.
+pub struct TreeLayout {
+    pub node_spacing: f64,
+    pub level_spacing: f64,
+    pub margin: f64,
+}
+
+impl Default for TreeLayout {
+    fn default() -> Self {
+        Self {
+            node_spacing: 30.0,
+            level_spacing: 50.0,
+            margin: 20.0,
+        }
+    }
+}
+
+/// The graph seen as a set of trees, parents being the sources of edges
+/// (or the targets of inheritance and realization edges)
+pub struct Forest {
+    ids: Vec<Id>,
+    roots: Vec<usize>,
+    children: Vec<Vec<usize>>,
+    // Edge id with its parent and child
+    links: Vec<(Id, usize, usize)>,
+}
+
+/// Horizontal extent of a subtree for each of its levels
+type Contour = Vec<(f64, f64)>;
+
+impl Forest {
+    /// Returns None when a node has several parents or the edges form a cycle
+    pub fn from_graph(graph: &Graph) -> Option<Self> {
+        let mut ids: Vec<Id> = graph.nodes.keys().cloned().collect();
+        ids.sort();
+        let index: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id): (usize, &Id)| (id.as_str(), i))
+            .collect();
+
+        let mut edges: Vec<&Edge> = graph.edges.values().collect();
+        edges.sort_by(|a: &&Edge, b: &&Edge| a.id.cmp(&b.id));
+
+        let mut parents: Vec<Option<usize>> = vec![None; ids.len()];
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+        let mut links: Vec<(Id, usize, usize)> = Vec::new();
+        for edge in edges {
+            let (Some(from), Some(to)) =
+                (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+            else {
+                continue;
+            };
+            let (parent, child): (usize, usize) =
+                if matches!(edge.kind, EdgeKind::Inheritance | EdgeKind::Realization) {
+                    (*to, *from)
+                } else {
+                    (*from, *to)
+                };
+            if parent == child || parents[child].is_some() {
+                return None;
+            }
+
+            parents[child] = Some(parent);
+            children[parent].push(child);
+            links.push((edge.id.clone(), parent, child));
+        }
+        children.iter_mut().for_each(|c: &mut Vec<usize>| c.sort());
+
+        let roots: Vec<usize> = (0..ids.len())
+            .filter(|v: &usize| parents[*v].is_none())
+            .collect();
+
+        // Every node must hang from a root, otherwise some edges form a cycle
+        let mut reached: usize = 0;
+        let mut stack: Vec<usize> = roots.clone();
+        while let Some(v) = stack.pop() {
+            reached += 1;
+            stack.extend(children[v].iter().copied());
+        }
+        (reached == ids.len()).then_some(Self {
+            ids,
+            roots,
+            children,
+            links,
+        })
+    }
+}
+
+impl TreeLayout {
+    pub fn apply(&self, graph: &mut Graph, forest: &Forest) {
+        if forest.ids.is_empty() {
+            return;
+        }
+
+        let sizes: Vec<(f64, f64)> = forest
+            .ids
+            .iter()
+            .map(|id: &Id| {
+                Orientation::flow_size(graph.direction, measure::node_size(&graph.nodes[id]))
+            })
+            .collect();
+        let mut x: Vec<f64> = vec![0.0; forest.ids.len()];
+        let mut depth: Vec<usize> = vec![0; forest.ids.len()];
+
+        let mut contour: Contour = Vec::new();
+        for root in &forest.roots {
+            let subtree: Contour = self.place(*root, 0, forest, &sizes, &mut x, &mut depth);
+            self.append(&mut contour, subtree, *root, forest, &mut x);
+        }
+
+        let mut level_heights: Vec<f64> = Vec::new();
+        depth
+            .iter()
+            .zip(sizes.iter())
+            .for_each(|(d, (_, h)): (&usize, &(f64, f64))| {
+                if level_heights.len() <= *d {
+                    level_heights.resize(d + 1, 0.0);
+                }
+                level_heights[*d] = level_heights[*d].max(*h);
+            });
+        let mut level_tops: Vec<f64> = Vec::with_capacity(level_heights.len());
+        let mut top: f64 = self.margin;
+        level_heights.iter().for_each(|h: &f64| {
+            level_tops.push(top);
+            top += h + self.level_spacing;
+        });
+
+        let left: f64 = contour
+            .iter()
+            .map(|(l, _): &(f64, f64)| *l)
+            .fold(f64::INFINITY, f64::min);
+
+        let rects: Vec<Rect> = (0..forest.ids.len())
+            .map(|v: usize| Rect {
+                x: x[v] - sizes[v].0 / 2.0 - left + self.margin,
+                y: level_tops[depth[v]],
+                width: sizes[v].0,
+                height: sizes[v].1,
+            })
+            .collect();
+        let orientation: Orientation = Orientation {
+            direction: graph.direction,
+            extent: rects
+                .iter()
+                .map(|rect: &Rect| rect.y + rect.height)
+                .fold(0.0, f64::max)
+                + self.margin,
+        };
+
+        forest
+            .links
+            .iter()
+            .for_each(|(edge, parent, child): &(Id, usize, usize)| {
+                let Some(edge) = graph.edges.get_mut(edge) else {
+                    return;
+                };
+                if edge.layout.hidden {
+                    edge.waypoints = Vec::new();
+                    return;
+                }
+                let (from, to): (Point, Point) = (rects[*parent].center(), rects[*child].center());
+                let bottom: f64 = rects[*parent].y + rects[*parent].height;
+                let elbow: f64 = bottom + self.level_spacing / 2.0;
+
+                let mut points: Vec<Point> = vec![Point {
+                    x: from.x,
+                    y: bottom,
+                }];
+                if from.x != to.x {
+                    points.push(Point {
+                        x: from.x,
+                        y: elbow,
+                    });
+                    points.push(Point { x: to.x, y: elbow });
+                }
+                points.push(Point {
+                    x: to.x,
+                    y: rects[*child].y,
+                });
+
+                // Waypoints always run from the edge's source
+                if edge.to == forest.ids[*parent] {
+                    points.reverse();
+                }
+                edge.waypoints = points
+                    .iter()
+                    .map(|p: &Point| orientation.point(p))
+                    .collect();
+            });
+
+        forest
+            .ids
+            .iter()
+            .zip(rects)
+            .for_each(|(id, rect): (&Id, Rect)| {
+                if let Some(node) = graph.nodes.get_mut(id) {
+                    node.geometry = Some(orientation.rect(
+                        &rect.center(),
+                        Orientation::flow_size(graph.direction, (rect.width, rect.height)),
+                    ));
+                }
+            });
+    }
+
+    /// Lays out a subtree with its root at x = 0, returning its contour
+    fn place(
+        &self,
+        v: usize,
+        level: usize,
+        forest: &Forest,
+        sizes: &[(f64, f64)],
+        x: &mut [f64],
+        depth: &mut [usize],
+    ) -> Contour {
+        depth[v] = level;
+
+        let mut children: Contour = Vec::new();
+        for child in &forest.children[v] {
+            let subtree: Contour = self.place(*child, level + 1, forest, sizes, x, depth);
+            self.append(&mut children, subtree, *child, forest, x);
+        }
+
+        if let (Some(first), Some(last)) = (forest.children[v].first(), forest.children[v].last()) {
+            let center: f64 = (x[*first] + x[*last]) / 2.0;
+            forest.children[v]
+                .iter()
+                .for_each(|child: &usize| shift(*child, -center, forest, x));
+            children.iter_mut().for_each(|(l, r): &mut (f64, f64)| {
+                *l -= center;
+                *r -= center;
+            });
+        }
+        x[v] = 0.0;
+
+        let mut contour: Contour = vec![(-sizes[v].0 / 2.0, sizes[v].0 / 2.0)];
+        contour.extend(children);
+        contour
+    }
+
+    /// Places a subtree right of the ones already in `contour`, merging its contour in
+    fn append(
+        &self,
+        contour: &mut Contour,
+        mut subtree: Contour,
+        root: usize,
+        forest: &Forest,
+        x: &mut [f64],
+    ) {
+        if !contour.is_empty() {
+            let offset: f64 = contour
+                .iter()
+                .zip(subtree.iter())
+                .map(|((_, right), (left, _)): (&(f64, f64), &(f64, f64))| {
+                    right - left + self.node_spacing
+                })
+                .fold(f64::NEG_INFINITY, f64::max);
+
+            shift(root, offset, forest, x);
+            subtree.iter_mut().for_each(|(l, r): &mut (f64, f64)| {
+                *l += offset;
+                *r += offset;
+            });
+        }
+
+        subtree
+            .into_iter()
+            .enumerate()
+            .for_each(
+                |(i, (l, r)): (usize, (f64, f64))| match contour.get_mut(i) {
+                    Some(level) => *level = (level.0.min(l), level.1.max(r)),
+                    None => contour.push((l, r)),
+                },
+            );
+    }
+}
+
+fn shift(v: usize, offset: f64, forest: &Forest, x: &mut [f64]) {
+    let mut stack: Vec<usize> = vec![v];
+    while let Some(v) = stack.pop() {
+        x[v] += offset;
+        stack.extend(forest.children[v].iter().copied());
+    }
+}
@@ -0,0 +1 @@
+pub mod markdown_document_gateway;
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::document_gateway::{DocumentGateway, DocumentGatewayError},
+    entities::document::SourceBlock,
+};
+
+use crate::infrastructure::parser::{self, MarkdownParseError};
+
+pub struct MarkdownDocumentGateway {
+    languages: Vec<String>,
+}
+
+impl MarkdownDocumentGateway {
+    pub fn new() -> Self {
+        Self::with_languages(&["plantuml", "puml", "mermaid"])
+    }
+
+    /// Only fenced blocks whose info string starts with one of these languages are extracted
+    pub fn with_languages(languages: &[&str]) -> Self {
+        Self {
+            languages: languages
+                .iter()
+                .map(|language: &&str| language.to_lowercase())
+                .collect(),
+        }
+    }
+}
+
+impl Default for MarkdownDocumentGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DocumentGateway for MarkdownDocumentGateway {
+    async fn read_blocks_from_raw_input(
+        &self,
+        input: &str,
+    ) -> Result<Vec<SourceBlock>, DocumentGatewayError> {
+        parser::parse_markdown(input, &self.languages).map_err(DocumentGatewayError::from)
+    }
+}
+
+impl From<MarkdownParseError> for DocumentGatewayError {
+    fn from(err: MarkdownParseError) -> Self {
+        match err {
+            MarkdownParseError::UnterminatedBlock {
+                language,
+                line,
+                column,
+            } => DocumentGatewayError::Parse {
+                source: "markdown".into(),
+                message: format!("Unterminated '{}' fenced block", language),
+                line,
+                column,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lib_core::{
+        adapters::document_gateway::{DocumentGateway, DocumentGatewayError},
+        entities::document::{Document, SourceBlock, SourceSpan},
+        use_cases::load_document::{LoadDocument, LoadDocumentUseCase},
+    };
+    use lib_plantuml::infrastructure::adapters::plant_uml_graph_gateway::PlantUmlGraphGateway;
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::markdown_document_gateway::MarkdownDocumentGateway;
+
+    #[test]
+    fn test_extract_fenced_diagram_blocks() {
+        smol::block_on(async {
+            let input: &str = "# Title\n\n```plantuml\n@startuml\nA --> B\n@enduml\n```\n\nText\n\n~~~ Mermaid\ngraph TD\n~~~\n\n```rust\nfn main() {}\n```\n";
+
+            let blocks: Vec<SourceBlock> = MarkdownDocumentGateway::new()
+                .read_blocks_from_raw_input(input)
+                .await
+                .expect("Failed to extract blocks");
+
+            assert_eq!(
+                blocks,
+                vec![
+                    SourceBlock {
+                        language: "plantuml".to_string(),
+                        source: "@startuml\nA --> B\n@enduml".to_string(),
+                        span: SourceSpan {
+                            start: 21,
+                            end: 47,
+                            start_line: 4,
+                            end_line: 6,
+                        },
+                    },
+                    SourceBlock {
+                        language: "mermaid".to_string(),
+                        source: "graph TD".to_string(),
+                        span: SourceSpan {
+                            start: 70,
+                            end: 79,
+                            start_line: 12,
+                            end_line: 12,
+                        },
+                    },
+                ]
+            );
+            assert_eq!(
+                &input[blocks[0].span.start..blocks[0].span.end],
+                "@startuml\nA --> B\n@enduml\n"
+            );
+        });
+    }
+
+    #[test]
+    fn test_ignores_blocks_nested_in_other_fences() {
+        smol::block_on(async {
+            let input: &str =
+                "````markdown\n```plantuml\nA --> B\n```\n````\n  ```puml\n  A --> B\n  ```\n";
+
+            let blocks: Vec<SourceBlock> = MarkdownDocumentGateway::new()
+                .read_blocks_from_raw_input(input)
+                .await
+                .expect("Failed to extract blocks");
+
+            assert_eq!(blocks.len(), 1);
+            assert_eq!(blocks[0].language, "puml");
+            assert_eq!(blocks[0].source, "A --> B");
+            assert_eq!(blocks[0].span.start_line, 7);
+        });
+    }
+
+    #[test]
+    fn test_unterminated_block_is_reported() {
+        smol::block_on(async {
+            let result: Result<Vec<SourceBlock>, DocumentGatewayError> =
+                MarkdownDocumentGateway::new()
+                    .read_blocks_from_raw_input("Intro\n\n```plantuml\nA --> B\n")
+                    .await;
+
+            assert_eq!(
+                result,
+                Err(DocumentGatewayError::Parse {
+                    source: "markdown".to_string(),
+                    message: "Unterminated 'plantuml' fenced block".to_string(),
+                    line: 3,
+                    column: 1,
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_document_parses_blocks_with_registered_gateways() {
+        smol::block_on(async {
+            let input: &str = "```plantuml\n@startuml\nA --> B\n@enduml\n```\n\n```plantuml\n@startuml\nA -->\n@enduml\n```\n";
+
+            let use_case: LoadDocument<MarkdownDocumentGateway> =
+                LoadDocument::new(Arc::new(MarkdownDocumentGateway::new()))
+                    .with_graph_gateway("plantuml", Arc::new(PlantUmlGraphGateway::new()));
+
+            let document: Document = use_case
+                .execute(input)
+                .await
+                .expect("Failed to load document");
+
+            assert_eq!(document.blocks.len(), 2);
+            assert_eq!(
+                document.blocks[0].graph.as_ref().map(|g| g.edges.len()),
+                Ok(1)
+            );
+
+            let error: &String = document.blocks[1].graph.as_ref().unwrap_err();
+            assert!(
+                error.starts_with("[plantuml:10:"),
+                "Expected error on document line 10, got {}",
+                error
+            );
+        });
+    }
+}
@@ -0,0 +1,133 @@
+use lib_core::entities::document::{SourceBlock, SourceSpan};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkdownParseError {
+    UnterminatedBlock {
+        language: String,
+        line: usize,
+        column: usize,
+    },
+}
+
+struct Fence {
+    marker: char,
+    length: usize,
+    indent: usize,
+    language: String,
+    line: usize,
+    column: usize,
+    content_start: usize,
+}
+
+/// Extracts the fenced code blocks tagged with one of the given languages.
+/// Blocks nested inside other fences (e.g. a ````markdown example) are ignored.
+pub fn parse_markdown(
+    input: &str,
+    languages: &[String],
+) -> Result<Vec<SourceBlock>, MarkdownParseError> {
+    let mut blocks: Vec<SourceBlock> = Vec::new();
+    let mut open: Option<Fence> = None;
+    let mut content: Vec<&str> = Vec::new();
+    let mut offset: usize = 0;
+
+    for (index, raw_line) in input.split_inclusive('\n').enumerate() {
+        let line_number: usize = index + 1;
+        let line: &str = raw_line.trim_end_matches(['\n', '\r']);
+        let line_start: usize = offset;
+        offset += raw_line.len();
+
+        match &open {
+            None => {
+                if let Some(fence) = opening_fence(line, line_number, offset) {
+                    open = Some(fence);
+                    content.clear();
+                }
+            }
+            Some(fence) if is_closing_fence(line, fence) => {
+                if languages.contains(&fence.language) {
+                    blocks.push(SourceBlock {
+                        language: fence.language.clone(),
+                        source: content.join("\n"),
+                        span: SourceSpan {
+                            start: fence.content_start,
+                            end: line_start,
+                            start_line: fence.line + 1,
+                            end_line: (line_number - 1).max(fence.line + 1),
+                        },
+                    });
+                }
+                open = None;
+            }
+            Some(fence) => content.push(strip_indent(line, fence.indent)),
+        }
+    }
+
+    match open {
+        Some(fence) if languages.contains(&fence.language) => {
+            Err(MarkdownParseError::UnterminatedBlock {
+                language: fence.language,
+                line: fence.line,
+                column: fence.column,
+            })
+        }
+        _ => Ok(blocks),
+    }
+}
+
+fn opening_fence(line: &str, line_number: usize, content_start: usize) -> Option<Fence> {
+    let indent: usize = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+
+    let rest: &str = &line[indent..];
+    let marker: char = rest
+        .chars()
+        .next()
+        .filter(|c: &char| matches!(c, '`' | '~'))?;
+    let length: usize = rest.len() - rest.trim_start_matches(marker).len();
+    if length < 3 {
+        return None;
+    }
+
+    let info: &str = rest[length..].trim();
+    // Backtick fences cannot have backticks in their info string
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+
+    // Accept both ```plantuml and pandoc-style ```{.plantuml}
+    let language: String = info
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('{')
+        .trim_start_matches('.')
+        .trim_end_matches('}')
+        .to_lowercase();
+
+    Some(Fence {
+        marker,
+        length,
+        indent,
+        language,
+        line: line_number,
+        column: indent + 1,
+        content_start,
+    })
+}
+
+fn is_closing_fence(line: &str, fence: &Fence) -> bool {
+    let trimmed: &str = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return false;
+    }
+
+    let length: usize = trimmed.len() - trimmed.trim_start_matches(fence.marker).len();
+    length >= fence.length && trimmed[length..].trim().is_empty()
+}
+
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let spaces: usize = line.len() - line.trim_start_matches(' ').len();
+    &line[spaces.min(indent)..]
+}
@@ -0,0 +1,2 @@
+pub mod adapters;
+pub(crate) mod emitter;
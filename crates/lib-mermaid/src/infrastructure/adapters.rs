@@ -0,0 +1 @@
+pub mod mermaid_graph_emitter;
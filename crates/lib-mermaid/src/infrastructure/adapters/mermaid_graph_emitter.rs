@@ -0,0 +1,357 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter, GraphEmitterError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::emitter;
+
+#[derive(Default)]
+pub struct MermaidGraphEmitter {
+    options: EmitOptions,
+}
+
+impl MermaidGraphEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: EmitOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+#[async_trait]
+impl GraphEmitterAdapter for MermaidGraphEmitter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError> {
+        Ok(emitter::emit_mermaid(graph, &self.options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use lib_core::{
+        adapters::graph_emitter_adapter::{
+            ElementOrder, EmitOptions, GraphEmitterAdapter, Indent, Quoting,
+        },
+        builder::GraphBuilder,
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind, LayoutDirection},
+            group::Group,
+            id::Id,
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            sequence::{Activation, Fragment, FragmentKind, Operand},
+            style::{Color, Colors},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::mermaid_graph_emitter::MermaidGraphEmitter;
+
+    #[test]
+    fn test_class_graphs_are_emitted_as_class_diagrams() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph {
+                kind: GraphKind::Class,
+                ..Default::default()
+            };
+            add_node(&mut graph, "Animal", NodeKind::Entity, None);
+            add_node(&mut graph, "Cat", NodeKind::Entity, Some("pets"));
+            add_node(&mut graph, "Walker", NodeKind::Interface, None);
+            graph.nodes.get_mut("Animal").unwrap().members = vec![
+                Member {
+                    name: "name".to_string(),
+                    type_name: Some("String".to_string()),
+                    data: HashMap::from([("visibility".into(), Value::String("-".to_string()))]),
+                    ..Default::default()
+                },
+                Member {
+                    name: "speak".to_string(),
+                    kind: MemberKind::Method,
+                    type_name: Some("String".to_string()),
+                    data: HashMap::from([("abstract".into(), Value::Bool(true))]),
+                },
+            ];
+            graph.groups.insert(
+                "pets".into(),
+                Group {
+                    id: "pets".into(),
+                    children: vec!["Cat".into()],
+                    ..Default::default()
+                },
+            );
+            add_edge(&mut graph, "Cat", "Animal", EdgeKind::Inheritance);
+            let walks: &mut Edge = add_edge(&mut graph, "Cat", "Walker", EdgeKind::Association);
+            walks.directed = true;
+            walks.label = Some("walks".into());
+            walks.from_cardinality = Some("1".into());
+
+            let output: String = MermaidGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "classDiagram",
+                    "    direction TB",
+                    "    class Animal[\"Animal\"] {",
+                    "        -String name",
+                    "        speak() String*",
+                    "    }",
+                    "    namespace pets {",
+                    "        class Cat[\"Cat\"]",
+                    "    }",
+                    "    class Walker[\"Walker\"] {",
+                    "        <<interface>>",
+                    "    }",
+                    "    Cat --|> Animal",
+                    "    Cat \"1\" --> Walker : walks",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_other_graphs_are_emitted_as_flowcharts() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph {
+                direction: LayoutDirection::LeftToRight,
+                ..Default::default()
+            };
+            graph.metadata.title = Some("Deploy".to_string());
+            add_node(&mut graph, "web", NodeKind::Component, Some("dmz"));
+            add_node(&mut graph, "db", NodeKind::Database, None);
+            graph.groups.insert(
+                "dmz".into(),
+                Group {
+                    id: "dmz".into(),
+                    label: Some("DMZ".into()),
+                    children: vec!["web".into()],
+                    ..Default::default()
+                },
+            );
+            let queries: &mut Edge = add_edge(&mut graph, "web", "db", EdgeKind::Flow);
+            queries.directed = true;
+            queries.label = Some("queries".into());
+            queries.colors.stroke = Some(Color::Named("red".into()));
+            graph.nodes.get_mut("db").unwrap().colors = Colors {
+                fill: Some(Color::Rgba([255, 204, 153, 255])),
+                gradient: Some(Color::Named("white".into())),
+                text: Some(Color::Named("blue".into())),
+                ..Default::default()
+            };
+
+            let output: String = MermaidGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "---",
+                    "title: Deploy",
+                    "---",
+                    "flowchart LR",
+                    "    db[(\"db\")]",
+                    "    subgraph dmz[\"DMZ\"]",
+                    "        web[\"web\"]",
+                    "    end",
+                    "    web -->|\"queries\"| db",
+                    "    style db fill:#ffcc99,color:blue",
+                    "    linkStyle 0 stroke:red",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_sequences_are_emitted_as_sequence_diagrams_in_message_order() {
+        smol::block_on(async {
+            let mut graph: Graph = GraphBuilder::new(GraphKind::Sequence)
+                .node("User", NodeKind::Actor, "User")
+                .node("Shop", NodeKind::Component, "Shop")
+                .edge("Shop", "User", EdgeKind::Flow, Some("refused"))
+                .edge("Shop", "User", EdgeKind::Flow, Some("confirmed"))
+                .edge("User", "Shop", EdgeKind::Flow, Some("order"))
+                .edge("Shop", "Shop", EdgeKind::Flow, Some("restock"))
+                .build();
+            [("e1", 3.0), ("e2", 2.0), ("e3", 1.0)]
+                .into_iter()
+                .for_each(|(id, sequence): (&str, f64)| {
+                    let edge: &mut Edge = graph.edges.get_mut(id).unwrap();
+                    edge.data.insert("sequence".into(), Value::Number(sequence));
+                    if id != "e3" {
+                        edge.data
+                            .insert("line_style".into(), Value::String("dashed".to_string()));
+                    }
+                });
+            graph
+                .edges
+                .get_mut("e4")
+                .unwrap()
+                .data
+                .insert("async".into(), Value::Bool(true));
+            graph.fragments.insert(
+                "f".into(),
+                Fragment {
+                    id: "f".into(),
+                    kind: FragmentKind::Alt,
+                    operands: vec![
+                        Operand {
+                            condition: Some("in stock".to_string()),
+                            messages: vec!["e2".into()],
+                            ..Default::default()
+                        },
+                        Operand {
+                            condition: Some("sold out".to_string()),
+                            messages: vec!["e1".into()],
+                            ..Default::default()
+                        },
+                    ],
+                    parent: None,
+                },
+            );
+            graph.activations.push(Activation {
+                participant: "Shop".into(),
+                start: Some("e3".into()),
+                end: Some("e4".into()),
+            });
+
+            let output: String = MermaidGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "sequenceDiagram",
+                    "    participant Shop as Shop",
+                    "    actor User as User",
+                    "    User->>Shop: order",
+                    "    activate Shop",
+                    "    alt in stock",
+                    "        Shop-->>User: confirmed",
+                    "    else sold out",
+                    "        Shop-->>User: refused",
+                    "    end",
+                    "    Shop-)Shop: restock",
+                    "    deactivate Shop",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_ids_that_are_keywords_are_escaped() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph {
+                kind: GraphKind::State,
+                ..Default::default()
+            };
+            add_node(
+                &mut graph,
+                "start",
+                NodeKind::Custom("start".to_string()),
+                None,
+            );
+            add_node(&mut graph, "end", NodeKind::Custom("end".to_string()), None);
+            add_edge(&mut graph, "start", "end", EdgeKind::Flow).directed = true;
+
+            let output: String = MermaidGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "flowchart TB",
+                    "    end_[\"end\"]",
+                    "    start[\"start\"]",
+                    "    start --> end_",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_options_change_indentation_quoting_order_and_notes() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "a", NodeKind::Component, Some("box"));
+            add_node(&mut graph, "b", NodeKind::Database, Some("box"));
+            add_node(&mut graph, "tip", NodeKind::Annotation, None);
+            graph.nodes.get_mut("a").unwrap().label = Some("Web app".into());
+            graph.nodes.get_mut("b").unwrap().label = Some("Cache (hot)".into());
+            graph.groups.insert(
+                "box".into(),
+                Group {
+                    id: "box".into(),
+                    label: Some("Box".into()),
+                    children: vec!["a".into(), "b".into()],
+                    ..Default::default()
+                },
+            );
+            add_edge(&mut graph, "tip", "a", EdgeKind::Undirected);
+
+            let output: String = MermaidGraphEmitter::new()
+                .with_options(EmitOptions {
+                    indent: Indent::Tab,
+                    quoting: Quoting::AsNeeded,
+                    order: ElementOrder::Label,
+                    notes: false,
+                    ..Default::default()
+                })
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "flowchart TB",
+                    "\tsubgraph box[Box]",
+                    "\t\tb[(\"Cache (hot)\")]",
+                    "\t\ta[Web app]",
+                    "\tend",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    fn add_node(graph: &mut Graph, id: &str, kind: NodeKind, parent: Option<&str>) {
+        graph.nodes.insert(
+            id.into(),
+            Node {
+                id: id.into(),
+                kind,
+                label: Some(id.into()),
+                parent: parent.map(Id::from),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn add_edge<'a>(graph: &'a mut Graph, from: &str, to: &str, kind: EdgeKind) -> &'a mut Edge {
+        let id: Id = format!("{}-{}", from, to).into();
+        graph.edges.insert(
+            id.clone(),
+            Edge {
+                id: id.clone(),
+                from: from.into(),
+                to: to.into(),
+                kind,
+                ..Default::default()
+            },
+        );
+        graph.edges.get_mut(id.as_str()).unwrap()
+    }
+}
@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, Quoting},
+    entities::{
+        edge::{Edge, EdgeKey, EdgeKind},
+        graph::{Graph, GraphKind, LayoutDirection},
+        group::Group,
+        id::{Id, Text},
+        member::{Member, MemberKind},
+        node::{Node, NodeKind},
+        sequence::{self, FragmentKind, Step},
+        style::Colors,
+        value::Value,
+    },
+};
+
+const INDENT: &str = "    ";
+/// Words the diagram grammars read as keywords wherever an id may stand
+const RESERVED: [&str; 31] = [
+    "end",
+    "graph",
+    "flowchart",
+    "subgraph",
+    "direction",
+    "style",
+    "classDef",
+    "class",
+    "linkStyle",
+    "click",
+    "call",
+    "href",
+    "default",
+    "namespace",
+    "classDiagram",
+    "sequenceDiagram",
+    "participant",
+    "actor",
+    "activate",
+    "deactivate",
+    "alt",
+    "else",
+    "opt",
+    "loop",
+    "par",
+    "and",
+    "critical",
+    "option",
+    "break",
+    "rect",
+    "note",
+];
+
+/// Writes a graph as Mermaid source: class-like graphs become a
+/// `classDiagram`, sequences a `sequenceDiagram`, everything else a
+/// `flowchart` with nested subgraphs
+pub fn emit_mermaid(graph: &Graph, options: &EmitOptions) -> String {
+    let graph: &Graph = &options.prepare(graph);
+    let mut lines: Vec<String> = Vec::new();
+    if let Some(title) = &graph.metadata.title {
+        lines.extend([
+            "---".to_string(),
+            format!("title: {}", title),
+            "---".to_string(),
+        ]);
+    }
+
+    match graph.kind {
+        GraphKind::Class | GraphKind::EntityRelationship => {
+            emit_class_diagram(graph, options, &mut lines)
+        }
+        GraphKind::Sequence => emit_sequence_diagram(graph, options, &mut lines),
+        _ => emit_flowchart(graph, options, &mut lines),
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn emit_class_diagram(graph: &Graph, options: &EmitOptions, lines: &mut Vec<String>) {
+    let step: String = options.indent(INDENT);
+    lines.push("classDiagram".to_string());
+    lines.push(format!("{}direction {}", step, direction(graph.direction)));
+
+    // Mermaid namespaces cannot be nested, nodes go into their closest group
+    sorted_nodes(graph, options, |_| true)
+        .iter()
+        .for_each(|node: &&Node| {
+            let namespace: Option<&Group> = node
+                .parent
+                .as_ref()
+                .and_then(|parent: &Id| graph.groups.get(parent));
+            let indent: String = match namespace {
+                Some(group) => {
+                    lines.push(format!("{}namespace {} {{", step, id(&group.id)));
+                    step.repeat(2)
+                }
+                None => step.clone(),
+            };
+
+            emit_class(node, &indent, &step, options, lines);
+
+            if namespace.is_some() {
+                lines.push(format!("{}}}", step));
+            }
+        });
+
+    sorted_edges(graph).iter().for_each(|edge: &&Edge| {
+        let arrow: &str = match &edge.kind {
+            EdgeKind::Inheritance => "--|>",
+            EdgeKind::Realization => "..|>",
+            EdgeKind::Composition => "*--",
+            EdgeKind::Aggregation => "o--",
+            EdgeKind::Dependency => "..>",
+            EdgeKind::Undirected => "--",
+            _ if edge.directed => "-->",
+            _ => "--",
+        };
+        let cardinality = |text: &Option<Text>| match text {
+            Some(text) if !text.is_empty() => format!(" \"{}\"", text),
+            _ => String::new(),
+        };
+        lines.push(format!(
+            "{}{}{} {}{} {}{}",
+            step,
+            id(&edge.from),
+            cardinality(&edge.from_cardinality),
+            arrow,
+            cardinality(&edge.to_cardinality),
+            id(&edge.to),
+            label(edge)
+        ));
+    });
+
+    // Class diagrams have no `linkStyle`, edges keep the default color
+    emit_colors(&sorted_nodes(graph, options, |_| true), &[], &step, lines);
+}
+
+fn emit_class(
+    node: &Node,
+    indent: &str,
+    step: &str,
+    options: &EmitOptions,
+    lines: &mut Vec<String>,
+) {
+    let annotation: Option<&str> = match &node.kind {
+        NodeKind::Interface => Some("interface"),
+        NodeKind::Enum => Some("enumeration"),
+        NodeKind::Deployment(kind) => Some(kind.keyword()),
+        NodeKind::Custom(kind) => Some(kind),
+        _ if matches!(node.data.get("abstract"), Some(Value::Bool(true))) => Some("abstract"),
+        _ => match node.data.get("stereotype") {
+            Some(Value::String(stereotype)) => Some(stereotype),
+            _ => None,
+        },
+    };
+
+    let label: &str = node.label.as_deref().unwrap_or(&node.id);
+    let declaration: String = match options.quoting {
+        Quoting::AsNeeded if label == id(&node.id) => format!("{}class {}", indent, label),
+        _ => format!("{}class {}[\"{}\"]", indent, id(&node.id), escape(label)),
+    };
+    if annotation.is_none() && node.members.is_empty() {
+        lines.push(declaration);
+        return;
+    }
+
+    lines.push(declaration + " {");
+    if let Some(annotation) = annotation {
+        lines.push(format!("{}{}<<{}>>", indent, step, annotation));
+    }
+    // Mermaid has no separator lines
+    node.members
+        .iter()
+        .filter(|member: &&Member| member.kind != MemberKind::Separator)
+        .for_each(|member: &Member| {
+            lines.push(format!("{}{}{}", indent, step, member_text(member)));
+        });
+    lines.push(format!("{}}}", indent));
+}
+
+/// A member as Mermaid writes it, with `$` after static members and `*`
+/// after abstract ones
+fn member_text(member: &Member) -> String {
+    let visibility: &str = match member.data.get("visibility") {
+        Some(Value::String(symbol)) => symbol,
+        _ => "",
+    };
+    let classifier: &str = match (member.is_static(), member.is_abstract()) {
+        (true, _) => "$",
+        (false, true) => "*",
+        (false, false) => "",
+    };
+
+    match member.kind {
+        MemberKind::Method => {
+            let parameters: Vec<&str> = match member.data.get("parameters") {
+                Some(Value::List(parameters)) => parameters
+                    .iter()
+                    .filter_map(|p: &Value| match p {
+                        Value::String(p) => Some(p.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            let returns: String = member
+                .type_name
+                .as_ref()
+                .map(|t: &String| format!(" {}", t))
+                .unwrap_or_default();
+            format!(
+                "{}{}({}){}{}",
+                visibility,
+                member.name,
+                parameters.join(", "),
+                returns,
+                classifier
+            )
+        }
+        _ => match &member.type_name {
+            Some(type_name) => format!("{}{} {}{}", visibility, type_name, member.name, classifier),
+            None => format!("{}{}{}", visibility, member.name, classifier),
+        },
+    }
+}
+
+/// Participants, then messages by sequence number inside their fragments
+fn emit_sequence_diagram(graph: &Graph, options: &EmitOptions, lines: &mut Vec<String>) {
+    let step: String = options.indent(INDENT);
+    lines.push("sequenceDiagram".to_string());
+    sorted_nodes(graph, options, |node: &Node| {
+        node.kind != NodeKind::Annotation
+    })
+    .iter()
+    .for_each(|node: &&Node| {
+        let keyword: &str = match node.kind {
+            NodeKind::Actor => "actor",
+            _ => "participant",
+        };
+        let label: &str = node.label.as_deref().unwrap_or(&node.id);
+        lines.push(match options.quoting {
+            Quoting::AsNeeded if label == id(&node.id) => {
+                format!("{}{} {}", step, keyword, label)
+            }
+            _ => format!("{}{} {} as {}", step, keyword, id(&node.id), label),
+        });
+    });
+
+    // Mermaid has no plain labelled box, so groups become uncoloured
+    // rectangles with their label as a comment
+    let mut depth: usize = 1;
+    sequence::steps(graph).iter().for_each(|item: &Step| {
+        let indent: String = step.repeat(depth);
+        let line: String = match item {
+            Step::Message(edge) => {
+                let arrow: &str = match (edge.data.get("line_style"), edge.data.get("async")) {
+                    (Some(Value::String(style)), Some(Value::Bool(true))) if style == "dashed" => {
+                        "--)"
+                    }
+                    (Some(Value::String(style)), _) if style == "dashed" => "-->>",
+                    (_, Some(Value::Bool(true))) => "-)",
+                    _ => "->>",
+                };
+                format!(
+                    "{}{}{}: {}",
+                    id(&edge.from),
+                    arrow,
+                    id(&edge.to),
+                    edge.label.as_deref().unwrap_or_default()
+                )
+                .trim_end()
+                .to_string()
+            }
+            Step::Activate(participant) => format!("activate {}", id(participant)),
+            Step::Deactivate(participant) => format!("deactivate {}", id(participant)),
+            Step::Open(fragment) => {
+                depth += 1;
+                let condition: &str = fragment
+                    .operands
+                    .first()
+                    .and_then(|operand: &sequence::Operand| operand.condition.as_deref())
+                    .unwrap_or_default();
+                let keyword: &str = match fragment.kind {
+                    FragmentKind::Alt => "alt",
+                    FragmentKind::Opt => "opt",
+                    FragmentKind::Loop => "loop",
+                    FragmentKind::Par => "par",
+                    FragmentKind::Break => "break",
+                    FragmentKind::Critical => "critical",
+                    FragmentKind::Group => {
+                        lines.push(format!("{}rect rgba(0, 0, 0, 0)", indent));
+                        lines.push(format!("{}{}%% {}", indent, step, condition));
+                        return;
+                    }
+                };
+                format!("{} {}", keyword, condition).trim_end().to_string()
+            }
+            Step::Operand(fragment, operand) => {
+                let keyword: &str = match fragment.kind {
+                    FragmentKind::Par => "and",
+                    FragmentKind::Critical => "option",
+                    _ => "else",
+                };
+                let line: String = format!(
+                    "{} {}",
+                    keyword,
+                    operand.condition.as_deref().unwrap_or_default()
+                );
+                lines.push(step.repeat(depth - 1) + line.trim_end());
+                return;
+            }
+            Step::Close(_) => {
+                depth -= 1;
+                lines.push(step.repeat(depth) + "end");
+                return;
+            }
+        };
+        lines.push(indent + &line);
+    });
+}
+
+fn emit_flowchart(graph: &Graph, options: &EmitOptions, lines: &mut Vec<String>) {
+    lines.push(format!("flowchart {}", direction(graph.direction)));
+    emit_subgraph(graph, options, None, 1, lines);
+
+    let edges: Vec<&Edge> = sorted_edges(graph);
+    edges.iter().for_each(|edge: &&Edge| {
+        let arrow: &str = match &edge.kind {
+            _ if edge.layout.hidden => "~~~",
+            EdgeKind::Dependency | EdgeKind::Realization => "-.->",
+            EdgeKind::Undirected => "---",
+            _ if edge.directed => "-->",
+            _ => "---",
+        };
+        let label: String = match &edge.label {
+            Some(label) if !edge.layout.hidden => format!("|{}|", text(label, options)),
+            _ => String::new(),
+        };
+        lines.push(format!(
+            "{}{} {}{} {}",
+            options.indent(INDENT),
+            id(&edge.from),
+            arrow,
+            label,
+            id(&edge.to)
+        ));
+    });
+
+    emit_colors(
+        &sorted_nodes(graph, options, |_| true),
+        &edges,
+        &options.indent(INDENT),
+        lines,
+    );
+}
+
+/// `style` lines for the nodes with colors of their own, then `linkStyle`
+/// lines for such edges, numbered in the order the edges were written
+fn emit_colors(nodes: &[&Node], edges: &[&Edge], indent: &str, lines: &mut Vec<String>) {
+    nodes
+        .iter()
+        .filter_map(|node: &&Node| Some((node, paint(&node.colors)?)))
+        .for_each(|(node, paint): (&&Node, String)| {
+            lines.push(format!("{}style {} {}", indent, id(&node.id), paint))
+        });
+    edges
+        .iter()
+        .enumerate()
+        .filter_map(|(index, edge): (usize, &&Edge)| Some((index, paint(&edge.colors)?)))
+        .for_each(|(index, paint): (usize, String)| {
+            lines.push(format!("{}linkStyle {} {}", indent, index, paint))
+        });
+}
+
+/// Colors as a Mermaid style; Mermaid has no gradients, so a gradient fill
+/// keeps only its starting color
+fn paint(colors: &Colors) -> Option<String> {
+    let pairs: Vec<String> = colors
+        .properties()
+        .into_iter()
+        .filter(|(key, _): &(&str, String)| *key != "gradient")
+        .map(|(key, value): (&str, String)| format!("{}:{}", key, value))
+        .collect();
+    (!pairs.is_empty()).then(|| pairs.join(","))
+}
+
+/// Emits the nodes and subgraphs directly inside `parent`
+fn emit_subgraph(
+    graph: &Graph,
+    options: &EmitOptions,
+    parent: Option<&Id>,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    let indent: String = options.indent(INDENT).repeat(depth);
+
+    sorted_nodes(graph, options, |node: &Node| node.parent.as_ref() == parent)
+        .iter()
+        .for_each(|node: &&Node| {
+            let label: String = text(node.label.as_deref().unwrap_or(&node.id), options);
+            let shape: String = match node.kind {
+                NodeKind::Database => format!("[({})]", label),
+                NodeKind::Actor => format!("(({}))", label),
+                NodeKind::Annotation => format!(">{}]", label),
+                NodeKind::Interface => format!("([{}])", label),
+                _ => format!("[{}]", label),
+            };
+            lines.push(format!("{}{}{}", indent, id(&node.id), shape));
+        });
+
+    let mut groups: Vec<&Group> = graph
+        .groups
+        .values()
+        .filter(|group: &&Group| group.parent.as_ref() == parent)
+        .collect();
+    options.sort_groups(&mut groups);
+    groups.iter().for_each(|group: &&Group| {
+        lines.push(format!(
+            "{}subgraph {}[{}]",
+            indent,
+            id(&group.id),
+            text(group.label.as_deref().unwrap_or(&group.id), options)
+        ));
+        emit_subgraph(graph, options, Some(&group.id), depth + 1, lines);
+        lines.push(format!("{}end", indent));
+    });
+}
+
+fn sorted_nodes<'a>(
+    graph: &'a Graph,
+    options: &EmitOptions,
+    filter: impl Fn(&Node) -> bool,
+) -> Vec<&'a Node> {
+    let mut nodes: Vec<&Node> = graph.nodes.values().filter(|n: &&Node| filter(n)).collect();
+    options.sort_nodes(&mut nodes);
+    nodes
+}
+
+fn sorted_edges(graph: &Graph) -> Vec<&Edge> {
+    let mut edges: Vec<&Edge> = graph.edges.values().collect();
+    // Edges go by their ends, and parallel ones in the order of their source
+    let keys: HashMap<Id, EdgeKey> = graph.edge_keys();
+    edges.sort_by(|a: &&Edge, b: &&Edge| keys[&a.id].cmp(&keys[&b.id]));
+    edges
+}
+
+fn direction(direction: LayoutDirection) -> &'static str {
+    match direction {
+        LayoutDirection::TopToBottom => "TB",
+        LayoutDirection::LeftToRight => "LR",
+        LayoutDirection::BottomToTop => "BT",
+        LayoutDirection::RightToLeft => "RL",
+    }
+}
+
+fn label(edge: &Edge) -> String {
+    edge.label
+        .as_ref()
+        .map(|label: &Text| format!(" : {}", label))
+        .unwrap_or_default()
+}
+
+/// Mermaid ids only allow letters, digits, dashes and underscores, and
+/// keywords such as `end` are taken for ids with an underscore after them
+fn id(id: &str) -> String {
+    let id: String = id
+        .chars()
+        .map(|c: char| {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match RESERVED.contains(&id.as_str()) {
+        true => id + "_",
+        false => id,
+    }
+}
+
+/// A node, group or edge label, quoted unless the options allow plain text
+/// and the label is only words
+fn text(label: &str, options: &EmitOptions) -> String {
+    let plain: bool = !label.trim().is_empty()
+        && label
+            .chars()
+            .all(|c: char| c.is_alphanumeric() || c == ' ' || c == '_');
+    match options.quoting {
+        Quoting::AsNeeded if plain => label.to_string(),
+        _ => format!("\"{}\"", escape(label)),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
@@ -0,0 +1,2 @@
+pub mod json_schema_graph_gateway;
+pub mod openapi_graph_gateway;
@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, OpenApiParseError},
+    transformer,
+};
+
+/// Reads standalone JSON Schema documents, drawing their root and the
+/// schemas under `$defs` or `definitions` as classes
+#[derive(Default)]
+pub struct JsonSchemaGraphGateway;
+
+impl JsonSchemaGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for JsonSchemaGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_json_schema(input)
+            .map_err(|err: OpenApiParseError| match err {
+                OpenApiParseError::Syntax {
+                    message,
+                    line,
+                    column,
+                } => GraphGatewayError::Parse {
+                    source: "jsonschema".into(),
+                    message,
+                    line,
+                    column,
+                },
+                OpenApiParseError::Semantic(msg) => GraphGatewayError::Semantic {
+                    source: "jsonschema".into(),
+                    message: msg,
+                },
+            })
+            .map(|document| transformer::GraphBuilder::new().build(document))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            member::Member,
+            node::Node,
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::json_schema_graph_gateway::JsonSchemaGraphGateway;
+
+    const SCHEMA: &str = r##"{
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Invoice",
+        "type": "object",
+        "required": ["number", "customer"],
+        "properties": {
+            "number": {"type": "string"},
+            "customer": {"$ref": "#/$defs/Customer"},
+            "lines": {"type": "array", "items": {"$ref": "#/$defs/Line"}},
+            "notes": {"type": ["string", "null"]}
+        },
+        "$defs": {
+            "Customer": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {"type": "string"},
+                    "email": {"type": "string", "format": "email"}
+                }
+            },
+            "Line": {
+                "type": "object",
+                "properties": {
+                    "amount": {"type": "number"}
+                }
+            }
+        }
+    }"##;
+
+    fn edge<'a>(graph: &'a Graph, from: &str, to: &str) -> &'a Edge {
+        graph
+            .edges
+            .values()
+            .find(|edge: &&Edge| edge.from == from && edge.to == to)
+            .unwrap_or_else(|| panic!("Missing edge {} -> {}", from, to))
+    }
+
+    #[test]
+    fn test_root_and_definitions_become_classes() {
+        smol::block_on(async {
+            let graph: Graph = JsonSchemaGraphGateway::new()
+                .read_graph_from_raw_input(SCHEMA)
+                .await
+                .unwrap();
+
+            assert_eq!(graph.kind, GraphKind::Class);
+            assert_eq!(graph.nodes.len(), 3);
+
+            let invoice: &Node = graph.nodes.get("Invoice").expect("Missing root");
+            assert_eq!(
+                invoice
+                    .members
+                    .iter()
+                    .map(|m: &Member| (
+                        m.name.as_str(),
+                        m.type_name.as_deref().unwrap(),
+                        m.data.get("required") == Some(&Value::Bool(true))
+                    ))
+                    .collect::<Vec<(&str, &str, bool)>>(),
+                vec![
+                    ("number", "string", true),
+                    ("customer", "Customer", true),
+                    ("lines", "Line[]", false),
+                    ("notes", "string", false),
+                ]
+            );
+            assert_eq!(
+                graph.nodes["Customer"].members[1].type_name.as_deref(),
+                Some("email")
+            );
+        });
+    }
+
+    #[test]
+    fn test_references_carry_whether_they_are_required() {
+        smol::block_on(async {
+            let graph: Graph = JsonSchemaGraphGateway::new()
+                .read_graph_from_raw_input(SCHEMA)
+                .await
+                .unwrap();
+
+            let customer: &Edge = edge(&graph, "Invoice", "Customer");
+            assert_eq!(customer.kind, EdgeKind::Association);
+            assert_eq!(customer.to_cardinality.as_deref(), Some("1"));
+            assert_eq!(
+                edge(&graph, "Invoice", "Line").to_cardinality.as_deref(),
+                Some("*")
+            );
+        });
+    }
+
+    #[test]
+    fn test_draft_07_definitions_are_read() {
+        smol::block_on(async {
+            let graph: Graph = JsonSchemaGraphGateway::new()
+                .read_graph_from_raw_input(
+                    "$ref: '#/definitions/Pet'\ndefinitions:\n  Pet:\n    allOf:\n      - $ref: '#/definitions/Animal'\n  Animal:\n    properties:\n      name:\n        type: string\n",
+                )
+                .await
+                .unwrap();
+
+            // A root that only refers to a definition is not drawn
+            assert_eq!(graph.nodes.len(), 2);
+            assert_eq!(edge(&graph, "Pet", "Animal").kind, EdgeKind::Inheritance);
+        });
+    }
+
+    #[test]
+    fn test_schemas_without_properties_are_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = JsonSchemaGraphGateway::new()
+                .read_graph_from_raw_input(r#"{"type": "string"}"#)
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Semantic { source, message }) => {
+                    assert_eq!(source, "jsonschema");
+                    assert_eq!(message, "Schema defines no properties or definitions");
+                }
+                other => panic!("Expected a semantic error, got {:?}", other),
+            }
+        });
+    }
+}
@@ -0,0 +1,335 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, OpenApiParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct OpenApiGraphGateway;
+
+impl OpenApiGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for OpenApiGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_openapi(input)
+            .map_err(GraphGatewayError::from)
+            .map(|document| transformer::GraphBuilder::new().build(document))
+    }
+}
+
+impl From<OpenApiParseError> for GraphGatewayError {
+    fn from(err: OpenApiParseError) -> Self {
+        match err {
+            OpenApiParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "openapi".into(),
+                message,
+                line,
+                column,
+            },
+            OpenApiParseError::Semantic(msg) => GraphGatewayError::Semantic {
+                source: "openapi".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::openapi_graph_gateway::OpenApiGraphGateway;
+
+    const DOCUMENT: &str = r##"
+openapi: 3.0.3
+info:
+  title: Pet store
+  version: 1.0.0
+paths:
+  /pets/{petId}:
+    parameters:
+      - $ref: "#/components/parameters/PetId"
+    get:
+      operationId: showPet
+      responses:
+        "200":
+          description: The pet
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+        default:
+          $ref: "#/components/responses/Failure"
+    put:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/Pet"
+      responses:
+        "204":
+          description: Updated
+components:
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema:
+        type: integer
+        format: int64
+  responses:
+    Failure:
+      description: Something went wrong
+      content:
+        application/json:
+          schema:
+            $ref: "#/components/schemas/Error"
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: "#/components/schemas/Cat"
+        - $ref: "#/components/schemas/Dog"
+      discriminator:
+        propertyName: kind
+    Animal:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+        tags:
+          type: array
+          items:
+            $ref: "#/components/schemas/Tag"
+        owner:
+          $ref: "#/components/schemas/Owner"
+    Cat:
+      allOf:
+        - $ref: "#/components/schemas/Animal"
+        - type: object
+          properties:
+            lives:
+              type: integer
+    Dog:
+      allOf:
+        - $ref: "#/components/schemas/Animal"
+    Tag:
+      type: object
+      properties:
+        label:
+          type: string
+        meta:
+          type: object
+          additionalProperties:
+            type: string
+    Owner:
+      type: object
+      properties:
+        since:
+          type: [string, "null"]
+          format: date-time
+    Status:
+      type: string
+      enum: [available, sold]
+    Error:
+      type: object
+      properties:
+        message:
+          type: string
+"##;
+
+    fn edge<'a>(graph: &'a Graph, from: &str, to: &str) -> &'a Edge {
+        graph
+            .edges
+            .values()
+            .find(|edge: &&Edge| edge.from == from && edge.to == to)
+            .unwrap_or_else(|| panic!("Missing edge {} -> {}", from, to))
+    }
+
+    #[test]
+    fn test_schemas_become_classes_with_typed_properties() {
+        smol::block_on(async {
+            let graph: Graph = OpenApiGraphGateway::new()
+                .read_graph_from_raw_input(DOCUMENT)
+                .await
+                .unwrap();
+
+            assert_eq!(graph.kind, GraphKind::Class);
+            assert_eq!(graph.nodes.len(), 9);
+
+            let animal: &Node = graph.nodes.get("Animal").expect("Missing Animal");
+            assert_eq!(
+                animal
+                    .members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), m.type_name.as_deref().unwrap()))
+                    .collect::<Vec<(&str, &str)>>(),
+                vec![("name", "string"), ("tags", "Tag[]"), ("owner", "Owner")]
+            );
+            assert_eq!(
+                animal.members[0].data.get("required"),
+                Some(&Value::Bool(true))
+            );
+
+            let cat: &Node = graph.nodes.get("Cat").expect("Missing Cat");
+            assert_eq!(cat.members[0].name, "lives");
+            assert_eq!(
+                graph.nodes["Tag"].members[1].type_name.as_deref(),
+                Some("Map<string, string>")
+            );
+            assert_eq!(
+                graph.nodes["Owner"].members[0].type_name.as_deref(),
+                Some("date-time")
+            );
+
+            let status: &Node = graph.nodes.get("Status").expect("Missing Status");
+            assert_eq!(status.kind, NodeKind::Enum);
+            assert_eq!(status.members[1].name, "sold");
+            assert_eq!(status.members[1].kind, MemberKind::EnumValue);
+            assert_eq!(
+                graph.nodes["Pet"].data.get("discriminator"),
+                Some(&Value::String("kind".to_string()))
+            );
+        });
+    }
+
+    #[test]
+    fn test_references_and_compositions_become_edges() {
+        smol::block_on(async {
+            let graph: Graph = OpenApiGraphGateway::new()
+                .read_graph_from_raw_input(DOCUMENT)
+                .await
+                .unwrap();
+
+            let tags: &Edge = edge(&graph, "Animal", "Tag");
+            assert_eq!(tags.kind, EdgeKind::Association);
+            assert_eq!(tags.label.as_deref(), Some("tags"));
+            assert_eq!(tags.to_cardinality.as_deref(), Some("*"));
+            assert_eq!(
+                edge(&graph, "Animal", "Owner").to_cardinality.as_deref(),
+                Some("0..1")
+            );
+
+            assert_eq!(edge(&graph, "Cat", "Animal").kind, EdgeKind::Inheritance);
+            assert_eq!(edge(&graph, "Dog", "Animal").kind, EdgeKind::Inheritance);
+
+            let variant: &Edge = edge(&graph, "Cat", "Pet");
+            assert_eq!(variant.kind, EdgeKind::Inheritance);
+            assert_eq!(
+                variant.data.get("composition"),
+                Some(&Value::String("oneOf".to_string()))
+            );
+        });
+    }
+
+    #[test]
+    fn test_paths_become_resources_depending_on_schemas() {
+        smol::block_on(async {
+            let graph: Graph = OpenApiGraphGateway::new()
+                .read_graph_from_raw_input(DOCUMENT)
+                .await
+                .unwrap();
+
+            let resource: &Node = graph.nodes.get("/pets/{petId}").expect("Missing resource");
+            assert_eq!(resource.kind, NodeKind::Custom("resource".to_string()));
+            assert_eq!(
+                resource.members[0],
+                Member {
+                    name: "showPet".to_string(),
+                    kind: MemberKind::Method,
+                    type_name: Some("Pet".to_string()),
+                    data: [
+                        ("method".into(), Value::String("GET".to_string())),
+                        (
+                            "parameters".into(),
+                            Value::List(vec![Value::String("petId: int64".to_string())])
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                }
+            );
+            assert_eq!(resource.members[1].name, "put");
+            assert_eq!(resource.members[1].type_name, None);
+
+            assert_eq!(
+                edge(&graph, "/pets/{petId}", "Pet").kind,
+                EdgeKind::Dependency
+            );
+            assert_eq!(
+                edge(&graph, "/pets/{petId}", "Error").kind,
+                EdgeKind::Dependency
+            );
+            assert_eq!(
+                graph
+                    .edges
+                    .values()
+                    .filter(|edge: &&Edge| edge.from == "/pets/{petId}")
+                    .count(),
+                2
+            );
+        });
+    }
+
+    #[test]
+    fn test_json_documents_are_read() {
+        smol::block_on(async {
+            let graph: Graph = OpenApiGraphGateway::new()
+                .read_graph_from_raw_input(
+                    r#"{
+                        "openapi": "3.1.0",
+                        "components": {"schemas": {"User": {"properties": {"id": {"type": "string", "format": "uuid"}}}}}
+                    }"#,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                graph.nodes["User"].members[0].type_name.as_deref(),
+                Some("uuid")
+            );
+        });
+    }
+
+    #[test]
+    fn test_errors_are_attributed_to_openapi() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = OpenApiGraphGateway::new()
+                .read_graph_from_raw_input("openapi: 3.0.0\ninfo:\n  title: Empty\n")
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Semantic { source, message }) => {
+                    assert_eq!(source, "openapi");
+                    assert_eq!(message, "Document defines no schemas or paths");
+                }
+                other => panic!("Expected a semantic error, got {:?}", other),
+            }
+        });
+    }
+}
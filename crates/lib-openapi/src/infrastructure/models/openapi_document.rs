@@ -0,0 +1,155 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+/// The parts of an OpenAPI document a class diagram is drawn from
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct OpenApiDocument {
+    pub openapi: Option<String>,
+    pub swagger: Option<String>,
+    pub paths: IndexMap<String, OpenApiPathItem>,
+    pub components: OpenApiComponents,
+}
+
+/// A standalone JSON Schema document, whose root is a schema of its own
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct JsonSchemaDocument {
+    pub title: Option<String>,
+    #[serde(rename = "$defs")]
+    pub defs: IndexMap<String, OpenApiSchema>,
+    /// Where drafts before 2019-09 keep their definitions
+    pub definitions: IndexMap<String, OpenApiSchema>,
+    #[serde(flatten)]
+    pub root: OpenApiSchema,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OpenApiComponents {
+    pub schemas: IndexMap<String, OpenApiSchema>,
+    pub parameters: IndexMap<String, OpenApiParameter>,
+    pub request_bodies: IndexMap<String, OpenApiBody>,
+    pub responses: IndexMap<String, OpenApiBody>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OpenApiSchema {
+    #[serde(rename = "$ref")]
+    pub reference: Option<String>,
+    #[serde(rename = "type")]
+    pub schema_type: Option<OpenApiType>,
+    pub format: Option<String>,
+    pub description: Option<String>,
+    pub properties: IndexMap<String, OpenApiSchema>,
+    pub required: Vec<String>,
+    pub items: Option<Box<OpenApiSchema>>,
+    pub additional_properties: Option<OpenApiAdditionalProperties>,
+    pub all_of: Vec<OpenApiSchema>,
+    pub one_of: Vec<OpenApiSchema>,
+    pub any_of: Vec<OpenApiSchema>,
+    #[serde(rename = "enum")]
+    pub values: Vec<serde_json::Value>,
+    pub discriminator: Option<OpenApiDiscriminator>,
+}
+
+/// A single type, or the list of them OpenAPI 3.1 allows
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum OpenApiType {
+    Single(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum OpenApiAdditionalProperties {
+    Allowed(bool),
+    Schema(Box<OpenApiSchema>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OpenApiDiscriminator {
+    pub property_name: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct OpenApiPathItem {
+    pub parameters: Vec<OpenApiParameter>,
+    pub get: Option<OpenApiOperation>,
+    pub put: Option<OpenApiOperation>,
+    pub post: Option<OpenApiOperation>,
+    pub delete: Option<OpenApiOperation>,
+    pub options: Option<OpenApiOperation>,
+    pub head: Option<OpenApiOperation>,
+    pub patch: Option<OpenApiOperation>,
+    pub trace: Option<OpenApiOperation>,
+}
+
+impl OpenApiPathItem {
+    /// Operations with their HTTP method, in the order the specification lists them
+    pub fn operations(&self) -> Vec<(&'static str, &OpenApiOperation)> {
+        [
+            ("GET", &self.get),
+            ("PUT", &self.put),
+            ("POST", &self.post),
+            ("DELETE", &self.delete),
+            ("OPTIONS", &self.options),
+            ("HEAD", &self.head),
+            ("PATCH", &self.patch),
+            ("TRACE", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(
+            |(method, operation): (&'static str, &Option<OpenApiOperation>)| {
+                operation.as_ref().map(|operation| (method, operation))
+            },
+        )
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OpenApiOperation {
+    pub operation_id: Option<String>,
+    pub parameters: Vec<OpenApiParameter>,
+    pub request_body: Option<OpenApiBody>,
+    pub responses: IndexMap<String, OpenApiBody>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct OpenApiParameter {
+    #[serde(rename = "$ref")]
+    pub reference: Option<String>,
+    pub name: String,
+    pub schema: Option<OpenApiSchema>,
+}
+
+/// A request body or response, both holding their schemas by media type
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct OpenApiBody {
+    #[serde(rename = "$ref")]
+    pub reference: Option<String>,
+    pub content: IndexMap<String, OpenApiMediaType>,
+}
+
+impl OpenApiBody {
+    /// Schema of the first media type declaring one, usually the JSON one
+    pub fn schema(&self) -> Option<&OpenApiSchema> {
+        self.content
+            .values()
+            .find_map(|media_type: &OpenApiMediaType| media_type.schema.as_ref())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct OpenApiMediaType {
+    pub schema: Option<OpenApiSchema>,
+}
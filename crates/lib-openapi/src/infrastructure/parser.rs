@@ -0,0 +1,159 @@
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+
+use crate::infrastructure::models::openapi_document::{
+    JsonSchemaDocument, OpenApiComponents, OpenApiDocument, OpenApiSchema,
+};
+
+pub fn parse_openapi(input: &str) -> Result<OpenApiDocument, OpenApiParseError> {
+    let document: OpenApiDocument = deserialize(input)?;
+
+    match (&document.openapi, &document.swagger) {
+        (Some(version), _) if version.starts_with("3.") => {}
+        (Some(version), _) => {
+            return Err(OpenApiParseError::Semantic(format!(
+                "Unsupported OpenAPI version {}, expected 3.x",
+                version
+            )));
+        }
+        (None, Some(_)) => {
+            return Err(OpenApiParseError::Semantic(
+                "Swagger 2.0 documents are not supported, expected OpenAPI 3.x".to_string(),
+            ));
+        }
+        (None, None) => {
+            return Err(OpenApiParseError::Semantic(
+                "Expected an OpenAPI 3.x document".to_string(),
+            ));
+        }
+    }
+
+    if document.components.schemas.is_empty() && document.paths.is_empty() {
+        return Err(OpenApiParseError::Semantic(
+            "Document defines no schemas or paths".to_string(),
+        ));
+    }
+
+    Ok(document)
+}
+
+/// Reads a JSON Schema document as an OpenAPI one holding its definitions,
+/// as OpenAPI schemas are JSON Schema
+pub fn parse_json_schema(input: &str) -> Result<OpenApiDocument, OpenApiParseError> {
+    let document: JsonSchemaDocument = deserialize(input)?;
+    let root: OpenApiSchema = document.root;
+
+    // The root is drawn too when it describes more than a reference
+    let mut schemas: IndexMap<String, OpenApiSchema> = IndexMap::new();
+    if !root.properties.is_empty()
+        || !root.all_of.is_empty()
+        || !root.one_of.is_empty()
+        || !root.any_of.is_empty()
+        || !root.values.is_empty()
+    {
+        schemas.insert(document.title.unwrap_or_else(|| "Root".to_string()), root);
+    }
+    schemas.extend(document.defs);
+    schemas.extend(document.definitions);
+
+    if schemas.is_empty() {
+        return Err(OpenApiParseError::Semantic(
+            "Schema defines no properties or definitions".to_string(),
+        ));
+    }
+
+    Ok(OpenApiDocument {
+        components: OpenApiComponents {
+            schemas,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn deserialize<T: DeserializeOwned>(input: &str) -> Result<T, OpenApiParseError> {
+    // JSON documents are YAML too, but serde_json reports their errors better
+    if input.trim_start().starts_with('{') {
+        serde_json::from_str(input).map_err(OpenApiParseError::from)
+    } else {
+        serde_yaml_ng::from_str(input).map_err(OpenApiParseError::from)
+    }
+}
+
+#[derive(Debug)]
+pub enum OpenApiParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Semantic(String),
+}
+
+impl From<serde_json::Error> for OpenApiParseError {
+    fn from(err: serde_json::Error) -> Self {
+        OpenApiParseError::Syntax {
+            message: err.to_string(),
+            line: err.line(),
+            column: err.column(),
+        }
+    }
+}
+
+impl From<serde_yaml_ng::Error> for OpenApiParseError {
+    fn from(err: serde_yaml_ng::Error) -> Self {
+        let (line, column): (usize, usize) = err
+            .location()
+            .map_or((0, 0), |location: serde_yaml_ng::Location| {
+                (location.line(), location.column())
+            });
+
+        OpenApiParseError::Syntax {
+            message: err.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_reads_json_and_yaml_alike() {
+        let json: OpenApiDocument = parse_openapi(
+            r#"{"openapi": "3.0.3", "components": {"schemas": {"Pet": {"type": "object"}}}}"#,
+        )
+        .unwrap();
+        let yaml: OpenApiDocument = parse_openapi(
+            "openapi: 3.0.3\ncomponents:\n  schemas:\n    Pet:\n      type: object\n",
+        )
+        .unwrap();
+
+        assert_eq!(json, yaml);
+    }
+
+    #[test]
+    fn test_rejects_swagger_documents() {
+        match parse_openapi("swagger: '2.0'\npaths: {}\n") {
+            Err(OpenApiParseError::Semantic(message)) => assert_eq!(
+                message,
+                "Swagger 2.0 documents are not supported, expected OpenAPI 3.x"
+            ),
+            other => panic!("Expected a semantic error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reports_where_the_document_is_malformed() {
+        match parse_openapi("openapi: 3.1.0\npaths: 5\n") {
+            Err(OpenApiParseError::Syntax { line, column, .. }) => {
+                assert_eq!((line, column), (2, 8))
+            }
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+    }
+}
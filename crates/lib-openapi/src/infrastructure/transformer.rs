@@ -0,0 +1,439 @@
+use indexmap::IndexMap;
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::openapi_document::{
+    OpenApiAdditionalProperties, OpenApiBody, OpenApiComponents, OpenApiDocument, OpenApiOperation,
+    OpenApiParameter, OpenApiPathItem, OpenApiSchema, OpenApiType,
+};
+
+pub struct GraphBuilder {
+    graph: Graph,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::Class,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn build(mut self, document: OpenApiDocument) -> Graph {
+        let schemas: &IndexMap<String, OpenApiSchema> = &document.components.schemas;
+
+        // Schemas may reference ones declared after them, so all nodes go first
+        schemas
+            .iter()
+            .for_each(|(name, schema): (&String, &OpenApiSchema)| self.add_schema(name, schema));
+        schemas
+            .iter()
+            .for_each(|(name, schema): (&String, &OpenApiSchema)| {
+                self.add_relationships(name, schema)
+            });
+        document
+            .paths
+            .iter()
+            .for_each(|(path, item): (&String, &OpenApiPathItem)| {
+                self.add_resource(path, item, &document.components)
+            });
+        self.graph
+    }
+
+    fn add_schema(&mut self, name: &str, schema: &OpenApiSchema) {
+        let (kind, members): (NodeKind, Vec<Member>) = if schema.values.is_empty() {
+            let required: Vec<&String> = required(schema);
+            let members: Vec<Member> = properties(schema)
+                .into_iter()
+                .map(|(property, definition): (&String, &OpenApiSchema)| {
+                    let mut data: HashMap<Text, Value> = HashMap::new();
+                    if required.contains(&property) {
+                        data.insert("required".into(), Value::Bool(true));
+                    }
+                    Member {
+                        name: property.clone(),
+                        kind: MemberKind::Field,
+                        type_name: Some(type_text(definition)),
+                        data,
+                    }
+                })
+                .collect();
+            (NodeKind::Entity, members)
+        } else {
+            let members: Vec<Member> = schema
+                .values
+                .iter()
+                .map(|value: &serde_json::Value| Member {
+                    name: match value {
+                        serde_json::Value::String(text) => text.clone(),
+                        other => other.to_string(),
+                    },
+                    kind: MemberKind::EnumValue,
+                    ..Default::default()
+                })
+                .collect();
+            (NodeKind::Enum, members)
+        };
+
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        if let Some(description) = &schema.description {
+            data.insert("description".into(), Value::String(description.clone()));
+        }
+        if let Some(discriminator) = &schema.discriminator {
+            data.insert(
+                "discriminator".into(),
+                Value::String(discriminator.property_name.clone()),
+            );
+        }
+
+        self.graph.nodes.insert(
+            Id::from(name),
+            Node {
+                id: Id::from(name),
+                kind,
+                label: Some(name.into()),
+                members,
+                data,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn add_relationships(&mut self, name: &str, schema: &OpenApiSchema) {
+        let required: Vec<&String> = required(schema);
+        properties(schema).into_iter().for_each(
+            |(property, definition): (&String, &OpenApiSchema)| {
+                let cardinality: &str = if is_collection(definition) {
+                    "*"
+                } else if required.contains(&property) {
+                    "1"
+                } else {
+                    "0..1"
+                };
+                references(definition).iter().for_each(|target: &String| {
+                    self.add_edge(
+                        name,
+                        target,
+                        EdgeKind::Association,
+                        Some(property.clone()),
+                        Some(cardinality.into()),
+                        HashMap::new(),
+                    );
+                });
+            },
+        );
+
+        // `allOf` extends the schemas it lists, while `oneOf` and `anyOf`
+        // generalize theirs
+        schema
+            .all_of
+            .iter()
+            .filter_map(|part: &OpenApiSchema| part.reference.as_deref())
+            .for_each(|reference: &str| {
+                self.add_edge(
+                    name,
+                    schema_name(reference),
+                    EdgeKind::Inheritance,
+                    None,
+                    None,
+                    HashMap::new(),
+                );
+            });
+        [("oneOf", &schema.one_of), ("anyOf", &schema.any_of)]
+            .into_iter()
+            .for_each(|(keyword, variants): (&str, &Vec<OpenApiSchema>)| {
+                variants
+                    .iter()
+                    .filter_map(|variant: &OpenApiSchema| variant.reference.as_deref())
+                    .map(schema_name)
+                    .filter(|variant: &&str| *variant != name)
+                    .for_each(|variant: &str| {
+                        self.add_edge(
+                            variant,
+                            name,
+                            EdgeKind::Inheritance,
+                            None,
+                            None,
+                            HashMap::from([(
+                                "composition".into(),
+                                Value::String(keyword.to_string()),
+                            )]),
+                        );
+                    });
+            });
+    }
+
+    /// Draws a path as a class whose methods are its operations, depending
+    /// on every schema they take or return
+    fn add_resource(&mut self, path: &str, item: &OpenApiPathItem, components: &OpenApiComponents) {
+        let mut dependencies: Vec<String> = Vec::new();
+        let members: Vec<Member> = item
+            .operations()
+            .into_iter()
+            .map(|(method, operation): (&str, &OpenApiOperation)| {
+                let parameters: Vec<&OpenApiParameter> = item
+                    .parameters
+                    .iter()
+                    .chain(&operation.parameters)
+                    .map(|parameter: &OpenApiParameter| {
+                        resolve(&parameter.reference, parameter, &components.parameters)
+                    })
+                    .collect();
+                let body: Option<&OpenApiSchema> =
+                    operation
+                        .request_body
+                        .as_ref()
+                        .and_then(|body: &OpenApiBody| {
+                            resolve(&body.reference, body, &components.request_bodies).schema()
+                        });
+                let responses: Vec<(&String, &OpenApiSchema)> = operation
+                    .responses
+                    .iter()
+                    .filter_map(|(status, response): (&String, &OpenApiBody)| {
+                        resolve(&response.reference, response, &components.responses)
+                            .schema()
+                            .map(|schema: &OpenApiSchema| (status, schema))
+                    })
+                    .collect();
+
+                parameters
+                    .iter()
+                    .filter_map(|parameter: &&OpenApiParameter| parameter.schema.as_ref())
+                    .chain(body)
+                    .chain(responses.iter().map(|(_, schema)| *schema))
+                    .flat_map(references)
+                    .for_each(|reference: String| {
+                        if !dependencies.contains(&reference) {
+                            dependencies.push(reference);
+                        }
+                    });
+
+                let mut signature: Vec<Value> = parameters
+                    .iter()
+                    .map(|parameter: &&OpenApiParameter| {
+                        Value::String(match &parameter.schema {
+                            Some(schema) => format!("{}: {}", parameter.name, type_text(schema)),
+                            None => parameter.name.clone(),
+                        })
+                    })
+                    .collect();
+                if let Some(body) = body {
+                    signature.push(Value::String(format!("body: {}", type_text(body))));
+                }
+
+                Member {
+                    name: operation
+                        .operation_id
+                        .clone()
+                        .unwrap_or_else(|| method.to_lowercase()),
+                    kind: MemberKind::Method,
+                    // The success response is what the operation returns
+                    type_name: responses
+                        .iter()
+                        .find(|(status, _)| status.starts_with('2'))
+                        .map(|(_, schema)| type_text(schema)),
+                    data: HashMap::from([
+                        ("method".into(), Value::String(method.to_string())),
+                        ("parameters".into(), Value::List(signature)),
+                    ]),
+                }
+            })
+            .collect();
+
+        self.graph.nodes.insert(
+            Id::from(path),
+            Node {
+                id: Id::from(path),
+                kind: NodeKind::Custom("resource".to_string()),
+                label: Some(path.into()),
+                members,
+                ..Default::default()
+            },
+        );
+        dependencies.iter().for_each(|target: &String| {
+            self.add_edge(
+                path,
+                target,
+                EdgeKind::Dependency,
+                None,
+                None,
+                HashMap::new(),
+            );
+        });
+    }
+
+    /// Adds an edge unless its target is no schema of the document
+    fn add_edge(
+        &mut self,
+        from: &str,
+        to: &str,
+        kind: EdgeKind,
+        label: Option<String>,
+        to_cardinality: Option<Text>,
+        data: HashMap<Text, Value>,
+    ) {
+        if !self.graph.nodes.contains_key(to) {
+            return;
+        }
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id,
+                from: from.into(),
+                to: to.into(),
+                directed: true,
+                kind,
+                label: label.map(Text::from),
+                to_cardinality,
+                data,
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Properties of a schema, including those of the inline parts of its `allOf`
+fn properties(schema: &OpenApiSchema) -> Vec<(&String, &OpenApiSchema)> {
+    schema
+        .properties
+        .iter()
+        .chain(
+            schema
+                .all_of
+                .iter()
+                .filter(|part: &&OpenApiSchema| part.reference.is_none())
+                .flat_map(|part: &OpenApiSchema| &part.properties),
+        )
+        .collect()
+}
+
+fn required(schema: &OpenApiSchema) -> Vec<&String> {
+    schema
+        .required
+        .iter()
+        .chain(
+            schema
+                .all_of
+                .iter()
+                .flat_map(|part: &OpenApiSchema| &part.required),
+        )
+        .collect()
+}
+
+/// Names of the component schemas a schema mentions, in order and once each
+fn references(schema: &OpenApiSchema) -> Vec<String> {
+    fn visit(schema: &OpenApiSchema, output: &mut Vec<String>) {
+        if let Some(reference) = &schema.reference {
+            let name: String = schema_name(reference).to_string();
+            if !output.contains(&name) {
+                output.push(name);
+            }
+        }
+        schema
+            .properties
+            .values()
+            .chain(schema.items.as_deref())
+            .chain(match &schema.additional_properties {
+                Some(OpenApiAdditionalProperties::Schema(values)) => Some(values.as_ref()),
+                _ => None,
+            })
+            .chain(&schema.all_of)
+            .chain(&schema.one_of)
+            .chain(&schema.any_of)
+            .for_each(|nested: &OpenApiSchema| visit(nested, output));
+    }
+
+    let mut output: Vec<String> = Vec::new();
+    visit(schema, &mut output);
+    output
+}
+
+fn is_collection(schema: &OpenApiSchema) -> bool {
+    schema.items.is_some()
+        || matches!(
+            schema.additional_properties,
+            Some(OpenApiAdditionalProperties::Schema(_))
+        )
+}
+
+/// A schema as a type name, as in `Pet[]`, `date-time` or `Cat | Dog`
+fn type_text(schema: &OpenApiSchema) -> String {
+    if let Some(reference) = &schema.reference {
+        return schema_name(reference).to_string();
+    }
+
+    let alternatives: Option<(&Vec<OpenApiSchema>, &str)> = [
+        (&schema.one_of, " | "),
+        (&schema.any_of, " | "),
+        (&schema.all_of, " & "),
+    ]
+    .into_iter()
+    .find(|(parts, _): &(&Vec<OpenApiSchema>, &str)| !parts.is_empty());
+    if let Some((parts, separator)) = alternatives {
+        return parts
+            .iter()
+            .map(type_text)
+            .collect::<Vec<String>>()
+            .join(separator);
+    }
+
+    let types: Vec<&str> = match &schema.schema_type {
+        Some(OpenApiType::Single(name)) => vec![name.as_str()],
+        Some(OpenApiType::Many(names)) => names
+            .iter()
+            .map(String::as_str)
+            .filter(|name: &&str| *name != "null")
+            .collect(),
+        None if schema.items.is_some() => vec!["array"],
+        None if !schema.properties.is_empty() => vec!["object"],
+        None => vec!["any"],
+    };
+    types
+        .into_iter()
+        .map(|name: &str| match name {
+            "array" => format!(
+                "{}[]",
+                schema.items.as_deref().map_or("any".to_string(), type_text)
+            ),
+            "object" => match &schema.additional_properties {
+                Some(OpenApiAdditionalProperties::Schema(values)) => {
+                    format!("Map<string, {}>", type_text(values))
+                }
+                _ => "object".to_string(),
+            },
+            _ => schema.format.clone().unwrap_or_else(|| name.to_string()),
+        })
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+/// Last segment of a reference such as `#/components/schemas/Pet`
+fn schema_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap_or(reference)
+}
+
+/// Follows a reference into the components of its kind, when it has one
+fn resolve<'a, T>(
+    reference: &Option<String>,
+    value: &'a T,
+    components: &'a IndexMap<String, T>,
+) -> &'a T {
+    reference
+        .as_deref()
+        .and_then(|reference: &str| components.get(schema_name(reference)))
+        .unwrap_or(value)
+}
@@ -1,4 +1,26 @@
+pub(crate) mod activity;
 pub mod adapters;
+pub(crate) mod archimate;
+pub(crate) mod color;
+pub(crate) mod creole;
+pub(crate) mod emitter;
+pub(crate) mod erd;
+pub mod formatter;
+pub mod incremental;
+pub mod limits;
 pub(crate) mod models;
 pub(crate) mod parser;
+pub mod preprocessor;
+pub mod profile;
+pub mod refactor;
+pub(crate) mod salt;
+pub(crate) mod sequence;
+pub(crate) mod skinparam;
+pub(crate) mod state;
+pub mod streaming;
+pub mod syntax;
+pub(crate) mod theme;
+pub mod tokens;
 pub(crate) mod transformer;
+pub(crate) mod usecase;
+pub(crate) mod visibility;
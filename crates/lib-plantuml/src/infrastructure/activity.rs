@@ -0,0 +1,379 @@
+//! The vocabulary of activity diagrams: `start` and `stop`, `:action;` steps,
+//! and `if`, `while` and `fork` blocks. A diagram using any of them is read
+//! as a flowchart whose steps are linked in the order they are written, and
+//! written back by finding those blocks in the flowchart's shape.
+
+use std::collections::{HashMap, HashSet};
+
+use lib_core::entities::{
+    edge::{Edge, EdgeKey},
+    graph::Graph,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+};
+
+use crate::infrastructure::models::{
+    ast_arena::{ArenaNode, AstArena},
+    ast_node::AstNode,
+};
+
+/// Node kinds the steps of an activity diagram are read as
+const STEPS: [&str; 6] = ["start", "end", "action", "decision", "fork", "join"];
+
+/// The kind of node a step is, such as `action` or `decision`, also the
+/// prefix of its id
+pub(crate) fn step_kind(keyword: &str) -> &str {
+    match keyword {
+        "stop" => "end",
+        "if" | "while" => "decision",
+        keyword => keyword,
+    }
+}
+
+/// An activity block being read: the node its branches leave from and the
+/// steps the flow leaves the block from
+pub(crate) struct Block {
+    pub keyword: String,
+    pub node: Id,
+    pub exits: Vec<(Id, Option<Text>)>,
+    /// Whether an `else` branch was read, so an `if` needs no flow around
+    /// its branches
+    pub otherwise: bool,
+}
+
+/// Whether a statement only belongs in an activity diagram
+pub(crate) fn is_activity(node: &AstNode) -> bool {
+    match node {
+        AstNode::Terminal { .. } | AstNode::Action { .. } | AstNode::Control { .. } => true,
+        AstNode::Package { children, .. } | AstNode::Together { children } => {
+            children.iter().any(is_activity)
+        }
+        _ => false,
+    }
+}
+
+/// Whether any statement of an arena only belongs in an activity diagram
+pub(crate) fn is_activity_arena(arena: &AstArena) -> bool {
+    (0..arena.len()).any(|index: usize| {
+        matches!(
+            arena.get(index),
+            ArenaNode::Terminal { .. } | ArenaNode::Action { .. } | ArenaNode::Control { .. }
+        )
+    })
+}
+
+/// Whether a node is an activity step
+pub(crate) fn is_step(node: &Node) -> bool {
+    matches!(&node.kind, NodeKind::Custom(kind) if STEPS.contains(&kind.as_str()))
+}
+
+/// The statements of an activity diagram, or why its flowchart cannot be
+/// written as one: it holds other elements, or flows that no block draws
+pub(crate) fn write(graph: &Graph, step: &str) -> Result<Vec<String>, String> {
+    if let Some(node) = graph.nodes.values().find(|node: &&Node| !is_step(node)) {
+        return Err(format!(
+            "'{}' is not an activity step, which an activity diagram cannot hold",
+            node.id
+        ));
+    }
+    if let Some(group) = graph.groups.keys().min() {
+        return Err(format!(
+            "Activity steps are written without groups, but '{}' is one",
+            group
+        ));
+    }
+
+    let mut writer: Writer = Writer::new(graph, step);
+    let mut roots: Vec<&Id> = graph
+        .nodes
+        .keys()
+        .filter(|id: &&Id| writer.predecessors[id].is_empty())
+        .collect();
+    roots.sort();
+    let mut lines: Vec<String> = Vec::new();
+    roots
+        .into_iter()
+        .try_for_each(|root: &Id| writer.write(Some(root), None, 0, &mut lines))?;
+    let mut missed: Vec<&Id> = graph
+        .nodes
+        .keys()
+        .filter(|id: &&Id| !writer.written.contains(id))
+        .collect();
+    missed.sort();
+    match missed.first() {
+        Some(id) => Err(format!("'{}' is not reached from the start of a flow", id)),
+        None => Ok(lines),
+    }
+}
+
+/// Finds the blocks of a flowchart: a decision with a flow back to it from
+/// a step it dominates is a `while`, any other joins its branches again at
+/// its closest post-dominator, as a fork does at its join
+struct Writer<'a> {
+    graph: &'a Graph,
+    step: &'a str,
+    successors: HashMap<&'a Id, Vec<&'a Edge>>,
+    predecessors: HashMap<&'a Id, Vec<&'a Id>>,
+    /// The steps inside each `while`, its decision included
+    loops: HashMap<&'a Id, HashSet<&'a Id>>,
+    post_dominators: HashMap<&'a Id, HashSet<&'a Id>>,
+    written: HashSet<&'a Id>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(graph: &'a Graph, step: &'a str) -> Self {
+        let keys: HashMap<Id, EdgeKey> = graph.edge_keys();
+        let mut successors: HashMap<&Id, Vec<&Edge>> =
+            graph.nodes.keys().map(|id: &Id| (id, Vec::new())).collect();
+        let mut predecessors: HashMap<&Id, Vec<&Id>> =
+            graph.nodes.keys().map(|id: &Id| (id, Vec::new())).collect();
+        let mut edges: Vec<&Edge> = graph.edges.values().collect();
+        edges.sort_by(|a: &&Edge, b: &&Edge| keys[&a.id].cmp(&keys[&b.id]));
+        edges.into_iter().for_each(|edge: &Edge| {
+            successors.entry(&edge.from).or_default().push(edge);
+            predecessors.entry(&edge.to).or_default().push(&edge.from);
+        });
+
+        let targets: HashMap<&Id, Vec<&Id>> = successors
+            .iter()
+            .map(|(id, edges): (&&Id, &Vec<&Edge>)| {
+                (*id, edges.iter().map(|edge: &&Edge| &edge.to).collect())
+            })
+            .collect();
+        let roots: HashSet<&Id> = predecessors
+            .iter()
+            .filter(|(_, from): &(&&Id, &Vec<&Id>)| from.is_empty())
+            .map(|(id, _): (&&Id, &Vec<&Id>)| *id)
+            .collect();
+        let dominating: HashMap<&Id, HashSet<&Id>> = dominators(&predecessors, &roots);
+
+        // The steps that flow back to a decision without passing it
+        let loops: HashMap<&Id, HashSet<&Id>> = predecessors
+            .iter()
+            .filter_map(|(id, from): (&&Id, &Vec<&Id>)| {
+                let sources: Vec<&Id> = from
+                    .iter()
+                    .filter(|source: &&&Id| {
+                        dominating
+                            .get(**source)
+                            .is_some_and(|dominators: &HashSet<&Id>| dominators.contains(*id))
+                    })
+                    .copied()
+                    .collect();
+                if sources.is_empty() {
+                    return None;
+                }
+                let mut body: HashSet<&Id> = HashSet::from([*id]);
+                let mut pending: Vec<&Id> = sources;
+                while let Some(step) = pending.pop() {
+                    if body.insert(step) {
+                        pending.extend(&predecessors[step]);
+                    }
+                }
+                Some((*id, body))
+            })
+            .collect();
+
+        // A `while` without a way out ends the flow as a `stop` does
+        let ends: HashSet<&Id> = targets
+            .iter()
+            .filter(|(id, to): &(&&Id, &Vec<&Id>)| {
+                to.is_empty()
+                    || loops.get(**id).is_some_and(|body: &HashSet<&Id>| {
+                        to.iter().all(|to: &&Id| body.contains(*to))
+                    })
+            })
+            .map(|(id, _): (&&Id, &Vec<&Id>)| *id)
+            .collect();
+        let post_dominators: HashMap<&Id, HashSet<&Id>> = dominators(&targets, &ends);
+
+        Self {
+            graph,
+            step,
+            successors,
+            predecessors,
+            loops,
+            post_dominators,
+            written: HashSet::new(),
+        }
+    }
+
+    /// Writes the flow from `at` until it reaches `until` or ends
+    fn write(
+        &mut self,
+        mut at: Option<&'a Id>,
+        until: Option<&'a Id>,
+        depth: usize,
+        lines: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let indent: String = self.step.repeat(depth);
+        while let Some(id) = at {
+            if Some(id) == until {
+                return Ok(());
+            }
+            if !self.written.insert(id) {
+                return Err(format!("'{}' is reached by flows that no block joins", id));
+            }
+            let node: &Node = &self.graph.nodes[id];
+            let label: &str = node.label.as_deref().unwrap_or_default();
+            let edges: Vec<&'a Edge> = self.successors[id].clone();
+            let kind: &str = match &node.kind {
+                NodeKind::Custom(kind) => kind,
+                _ => "",
+            };
+            at = match kind {
+                "start" => {
+                    lines.push(format!("{}start", indent));
+                    self.next(id, &edges)?
+                }
+                "end" => {
+                    lines.push(format!("{}stop", indent));
+                    match edges.is_empty() {
+                        true => None,
+                        false => return Err(format!("'{}' stops a flow that goes on", id)),
+                    }
+                }
+                "action" => {
+                    lines.push(format!("{}:{};", indent, label));
+                    self.next(id, &edges)?
+                }
+                "decision" if self.loops.contains_key(id) => {
+                    let body: &HashSet<&Id> = &self.loops[id];
+                    let (inside, outside): (Vec<&Edge>, Vec<&Edge>) = edges
+                        .iter()
+                        .partition(|edge: &&&Edge| body.contains(&edge.to));
+                    let ([inside], [] | [_]) = (&inside[..], &outside[..]) else {
+                        return Err(format!("'{}' loops in a way no `while` draws", id));
+                    };
+                    lines.push(format!(
+                        "{}while ({}){}",
+                        indent,
+                        label,
+                        guard(" is", inside)
+                    ));
+                    self.write(Some(&inside.to), Some(id), depth + 1, lines)?;
+                    let exit: Option<&Edge> = outside.first().copied();
+                    lines.push(format!(
+                        "{}endwhile{}",
+                        indent,
+                        exit.map(|exit: &Edge| guard("", exit)).unwrap_or_default()
+                    ));
+                    exit.map(|exit: &Edge| &exit.to)
+                }
+                "decision" => {
+                    let mut branches: Vec<&Edge> = edges.clone();
+                    // An `if` without an `else` leaves by an unlabelled flow
+                    branches.sort_by_key(|edge: &&Edge| edge.label.is_none());
+                    let [then, otherwise] = branches[..] else {
+                        return Err(format!(
+                            "'{}' has {} branches, where an `if` has two",
+                            id,
+                            branches.len()
+                        ));
+                    };
+                    let merge: Option<&Id> = self.post_dominator(id);
+                    lines.push(format!("{}if ({}) then{}", indent, label, guard("", then)));
+                    self.write(Some(&then.to), merge, depth + 1, lines)?;
+                    lines.push(format!("{}else{}", indent, guard("", otherwise)));
+                    self.write(Some(&otherwise.to), merge, depth + 1, lines)?;
+                    lines.push(format!("{}endif", indent));
+                    merge
+                }
+                "fork" => {
+                    let join: &Id = self
+                        .post_dominator(id)
+                        .filter(|join: &&Id| {
+                            self.graph.nodes[*join].kind == NodeKind::Custom("join".to_string())
+                        })
+                        .ok_or_else(|| format!("'{}' forks flows that no join ends", id))?;
+                    edges
+                        .iter()
+                        .enumerate()
+                        .try_for_each(|(i, edge): (usize, &&Edge)| {
+                            lines.push(match i {
+                                0 => format!("{}fork", indent),
+                                _ => format!("{}fork again", indent),
+                            });
+                            self.write(Some(&edge.to), Some(join), depth + 1, lines)
+                        })?;
+                    lines.push(format!("{}end fork", indent));
+                    self.written.insert(join);
+                    let edges: Vec<&'a Edge> = self.successors[join].clone();
+                    self.next(join, &edges)?
+                }
+                _ => return Err(format!("'{}' joins flows that no fork split", id)),
+            };
+        }
+        Ok(())
+    }
+
+    /// The step after one that leads straight on
+    fn next(&self, id: &Id, edges: &[&'a Edge]) -> Result<Option<&'a Id>, String> {
+        match edges {
+            [] => Ok(None),
+            [edge] => Ok(Some(&edge.to)),
+            _ => Err(format!(
+                "'{}' leads to {} steps, where only a decision or fork branches",
+                id,
+                edges.len()
+            )),
+        }
+    }
+
+    /// The closest step every flow from a decision or fork goes through
+    fn post_dominator(&self, id: &Id) -> Option<&'a Id> {
+        self.post_dominators
+            .get(id)?
+            .iter()
+            .filter(|step: &&&Id| **step != id)
+            .max_by_key(|step: &&&Id| (self.post_dominators[**step].len(), **step))
+            .copied()
+    }
+}
+
+/// A branch's label in brackets, after a keyword
+fn guard(keyword: &str, edge: &Edge) -> String {
+    edge.label
+        .as_ref()
+        .map(|label: &Text| format!("{} ({})", keyword, label))
+        .unwrap_or_default()
+}
+
+/// The steps every flow from an entry to each step passes through, given
+/// where each step's flows come from; steps no entry reaches are left out
+fn dominators<'a>(
+    from: &HashMap<&'a Id, Vec<&'a Id>>,
+    entries: &HashSet<&'a Id>,
+) -> HashMap<&'a Id, HashSet<&'a Id>> {
+    let mut order: Vec<&Id> = from.keys().copied().collect();
+    order.sort();
+    let mut dominators: HashMap<&Id, HashSet<&Id>> = entries
+        .iter()
+        .map(|entry: &&Id| (*entry, HashSet::from([*entry])))
+        .collect();
+    let mut changed: bool = true;
+    while changed {
+        changed = false;
+        order
+            .iter()
+            .filter(|id: &&&Id| !entries.contains(**id))
+            .for_each(|id: &&Id| {
+                let mut sets = from[*id]
+                    .iter()
+                    .filter_map(|source: &&Id| dominators.get(*source));
+                let Some(first) = sets.next() else {
+                    return;
+                };
+                let mut set: HashSet<&Id> =
+                    sets.fold(first.clone(), |set: HashSet<&Id>, other: &HashSet<&Id>| {
+                        set.intersection(other).copied().collect()
+                    });
+                set.insert(*id);
+                if dominators.get(*id) != Some(&set) {
+                    dominators.insert(*id, set);
+                    changed = true;
+                }
+            });
+    }
+    dominators
+}
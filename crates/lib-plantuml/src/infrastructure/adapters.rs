@@ -1 +1,2 @@
+pub mod plant_uml_graph_emitter;
 pub mod plant_uml_graph_gateway;
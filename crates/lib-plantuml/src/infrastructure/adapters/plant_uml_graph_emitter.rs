@@ -0,0 +1,982 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter, GraphEmitterError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::emitter;
+
+#[derive(Default)]
+pub struct PlantUmlGraphEmitter {
+    options: EmitOptions,
+}
+
+impl PlantUmlGraphEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: EmitOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+#[async_trait]
+impl GraphEmitterAdapter for PlantUmlGraphEmitter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError> {
+        emitter::emit_plantuml(graph, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use lib_core::{
+        adapters::{
+            graph_emitter_adapter::{
+                EmitOptions, GraphEmitterAdapter, GraphEmitterError, Indent, Quoting,
+            },
+            graph_gateway::GraphGateway,
+        },
+        entities::{
+            edge::{Edge, EdgeDirection, EdgeKind},
+            graph::{
+                Graph, GraphKind, HorizontalPosition, LayoutDirection, Legend, VerticalPosition,
+            },
+            group::{Group, GroupKind},
+            id::{Id, Text},
+            member::{Member, MemberKind},
+            node::{DeploymentKind, Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::{
+        plant_uml_graph_emitter::PlantUmlGraphEmitter,
+        plant_uml_graph_gateway::PlantUmlGraphGateway,
+    };
+
+    #[test]
+    fn test_emits_classes_packages_and_relations() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph {
+                direction: LayoutDirection::LeftToRight,
+                ..Default::default()
+            };
+            graph.metadata.title = Some("Zoo".to_string());
+            add_node(&mut graph, "Animal", NodeKind::Entity, None);
+            add_node(&mut graph, "Cat", NodeKind::Entity, Some("pets"));
+            add_node(&mut graph, "Walker", NodeKind::Interface, None);
+            graph.nodes.get_mut("Animal").unwrap().members = vec![
+                Member {
+                    name: "name".to_string(),
+                    type_name: Some("String".to_string()),
+                    data: HashMap::from([("visibility".into(), Value::String("-".to_string()))]),
+                    ..Default::default()
+                },
+                Member {
+                    name: "speak".to_string(),
+                    kind: MemberKind::Method,
+                    data: HashMap::from([("abstract".into(), Value::Bool(true))]),
+                    ..Default::default()
+                },
+            ];
+            graph.nodes.get_mut("Animal").unwrap().data =
+                HashMap::from([("abstract".into(), Value::Bool(true))]);
+            graph.groups.insert(
+                "pets".into(),
+                Group {
+                    id: "pets".into(),
+                    label: Some("Pets".into()),
+                    children: vec!["Cat".into()],
+                    ..Default::default()
+                },
+            );
+            add_edge(&mut graph, "Cat", "Animal", EdgeKind::Inheritance);
+            add_edge(&mut graph, "Cat", "Walker", EdgeKind::Realization);
+            let owns: &mut Edge = add_edge(&mut graph, "Animal", "Walker", EdgeKind::Association);
+            owns.label = Some("walks".into());
+            owns.directed = true;
+            owns.layout.direction = Some(EdgeDirection::Up);
+            owns.to_cardinality = Some("*".into());
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "@startuml",
+                    "title Zoo",
+                    "left to right direction",
+                    "abstract class \"Animal\" as Animal {",
+                    "  -name : String",
+                    "  {abstract} speak()",
+                    "}",
+                    "interface \"Walker\" as Walker",
+                    "package \"Pets\" {",
+                    "  class \"Cat\" as Cat",
+                    "}",
+                    "Animal -up-> \"*\" Walker : walks",
+                    "Cat --|> Animal",
+                    "Cat ..|> Walker",
+                    "@enduml",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_emitted_source_can_be_read_back() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "User", NodeKind::Entity, None);
+            add_node(&mut graph, "Profile", NodeKind::Entity, None);
+            let owns: &mut Edge = add_edge(&mut graph, "User", "Profile", EdgeKind::Composition);
+            owns.layout.length = Some(2);
+            owns.layout.direction = Some(EdgeDirection::Down);
+            owns.from_port = Some("profile".to_string());
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+
+            assert_eq!(read.nodes.len(), 2);
+            let edge: &Edge = read.edges.values().next().unwrap();
+            assert_eq!(
+                (edge.from.as_str(), edge.to.as_str(), &edge.kind),
+                ("User", "Profile", &EdgeKind::Composition)
+            );
+            assert_eq!(edge.layout.length, Some(2));
+            assert_eq!(edge.layout.direction, Some(EdgeDirection::Down));
+            assert_eq!(
+                (edge.from_port.as_deref(), edge.to_port.as_deref()),
+                (Some("profile"), None)
+            );
+        });
+    }
+
+    #[test]
+    fn test_every_edge_kind_can_be_read_back() {
+        smol::block_on(async {
+            let kinds: [EdgeKind; 9] = [
+                EdgeKind::Association,
+                EdgeKind::Dependency,
+                EdgeKind::Inheritance,
+                EdgeKind::Realization,
+                EdgeKind::Aggregation,
+                EdgeKind::Composition,
+                EdgeKind::Flow,
+                EdgeKind::Undirected,
+                EdgeKind::Custom("<-->".to_string()),
+            ];
+            let mut graph: Graph = Graph::default();
+            kinds
+                .iter()
+                .enumerate()
+                .for_each(|(i, kind): (usize, &EdgeKind)| {
+                    let (from, to): (String, String) = (format!("A{}", i), format!("B{}", i));
+                    add_node(&mut graph, &from, NodeKind::Entity, None);
+                    add_node(&mut graph, &to, NodeKind::Entity, None);
+                    add_edge(&mut graph, &from, &to, kind.clone()).directed =
+                        *kind != EdgeKind::Undirected;
+                });
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+            let mut edges: Vec<(String, String, EdgeKind)> = read
+                .edges
+                .values()
+                .map(|edge: &Edge| {
+                    (
+                        edge.from.to_string(),
+                        edge.to.to_string(),
+                        edge.kind.clone(),
+                    )
+                })
+                .collect();
+            edges.sort_by(
+                |a: &(String, String, EdgeKind), b: &(String, String, EdgeKind)| a.0.cmp(&b.0),
+            );
+
+            assert_eq!(
+                edges,
+                kinds
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, kind): (usize, EdgeKind)| (
+                        format!("A{}", i),
+                        format!("B{}", i),
+                        kind
+                    ))
+                    .collect::<Vec<(String, String, EdgeKind)>>(),
+                "{}",
+                output
+            );
+        });
+    }
+
+    #[test]
+    fn test_parallel_relations_are_written_in_source_order() {
+        smol::block_on(async {
+            let source: &str =
+                "@startuml\nclass A\nclass B\nA --> B : writes\nA --> B : reads\n@enduml\n";
+            let graph: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(source)
+                .await
+                .unwrap();
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert!(
+                output.ends_with("A --> B : writes\nA --> B : reads\n@enduml\n"),
+                "{}",
+                output
+            );
+        });
+    }
+
+    #[test]
+    fn test_sequences_are_written_in_message_order() {
+        smol::block_on(async {
+            let source: &str = "@startuml
+actor User
+participant Shop
+User -> Shop : order
+activate Shop
+alt in stock
+  Shop --> User : confirmed
+  loop each item
+    Shop ->> Shop : reserve
+  end
+else sold out
+  Shop --> User : refused
+end
+deactivate Shop
+@enduml
+";
+            let graph: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(source)
+                .await
+                .unwrap();
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                "@startuml
+participant \"Shop\" as Shop
+actor \"User\" as User
+User -> Shop : order
+activate Shop
+alt in stock
+  Shop --> User : confirmed
+  loop each item
+    Shop ->> Shop : reserve
+  end
+else sold out
+  Shop --> User : refused
+  deactivate Shop
+end
+@enduml
+"
+            );
+            assert_eq!(read.kind, GraphKind::Sequence);
+            assert_eq!(read.fragments.len(), 2);
+            assert_eq!(read.activations.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_states_are_written_as_states_and_read_back() {
+        smol::block_on(async {
+            let source: &str = "@startuml
+[*] --> Idle
+state \"Working hard\" as Busy {
+  [*] --> Loading
+  Loading --> Saving : loaded
+  Saving --> [*]
+}
+Idle --> Busy : start
+Busy : entry / lock
+Busy --> [*]
+@enduml
+";
+            let graph: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(source)
+                .await
+                .unwrap();
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+
+            assert!(!output.contains("class"), "{}", output);
+            assert_eq!(read.kind, GraphKind::State);
+            assert_eq!(
+                read.groups["Busy"].label.as_deref(),
+                Some("Working hard"),
+                "{}",
+                output
+            );
+            let mut nodes: Vec<(&str, Option<&str>)> = read
+                .nodes
+                .values()
+                .map(|node: &Node| (node.id.as_str(), node.parent.as_deref()))
+                .collect();
+            nodes.sort();
+            assert_eq!(
+                nodes,
+                [
+                    ("Busy_end", Some("Busy")),
+                    ("Busy_start", Some("Busy")),
+                    ("Idle", None),
+                    ("Loading", Some("Busy")),
+                    ("Saving", Some("Busy")),
+                    ("end", None),
+                    ("start", None),
+                ],
+                "{}",
+                output
+            );
+            let mut edges: Vec<(&str, &str, Option<&str>)> = read
+                .edges
+                .values()
+                .map(|edge: &Edge| (edge.from.as_str(), edge.to.as_str(), edge.label.as_deref()))
+                .collect();
+            edges.sort();
+            assert_eq!(
+                edges,
+                [
+                    ("Busy", "end", None),
+                    ("Busy_start", "Loading", None),
+                    ("Idle", "Busy", Some("start")),
+                    ("Loading", "Saving", Some("loaded")),
+                    ("Saving", "Busy_end", None),
+                    ("start", "Idle", None),
+                ],
+                "{}",
+                output
+            );
+        });
+    }
+
+    #[test]
+    fn test_activities_are_written_as_blocks_and_read_back() {
+        smol::block_on(async {
+            let source: &str = "@startuml
+start
+:Take order;
+if (paid?) then (yes)
+  while (items left?) is (yes)
+    :Pack item;
+  endwhile (no)
+  fork
+    :Ship;
+  fork again
+    :Send invoice;
+  end fork
+else (no)
+  :Cancel;
+  stop
+endif
+:Archive;
+stop
+@enduml
+";
+            let graph: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(source)
+                .await
+                .unwrap();
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+
+            // A branch that stops never joins the other, which goes on alone
+            assert_eq!(
+                output,
+                "@startuml
+start
+:Take order;
+if (paid?) then (no)
+  :Cancel;
+  stop
+else (yes)
+  while (items left?) is (yes)
+    :Pack item;
+  endwhile (no)
+  fork
+    :Ship;
+  fork again
+    :Send invoice;
+  end fork
+  :Archive;
+  stop
+endif
+@enduml
+"
+            );
+            let flows = |graph: &Graph| -> Vec<(String, String, Option<Text>)> {
+                let name = |id: &Id| -> String {
+                    let node: &Node = &graph.nodes[id];
+                    format!("{:?} {:?}", node.kind, node.label)
+                };
+                let mut flows: Vec<(String, String, Option<Text>)> = graph
+                    .edges
+                    .values()
+                    .map(|edge: &Edge| (name(&edge.from), name(&edge.to), edge.label.clone()))
+                    .collect();
+                flows.sort();
+                flows
+            };
+            assert_eq!(read.kind, GraphKind::Flowchart);
+            assert_eq!(flows(&read), flows(&graph));
+        });
+    }
+
+    #[test]
+    fn test_flows_no_activity_block_draws_are_rejected() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph {
+                kind: GraphKind::Flowchart,
+                ..Default::default()
+            };
+            ["a", "b", "c"].into_iter().for_each(|id: &str| {
+                add_node(&mut graph, id, NodeKind::Custom("action".to_string()), None)
+            });
+            add_edge(&mut graph, "a", "b", EdgeKind::Flow);
+            add_edge(&mut graph, "a", "c", EdgeKind::Flow);
+
+            let error: GraphEmitterError = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap_err();
+
+            assert_eq!(
+                error,
+                GraphEmitterError::Unsupported {
+                    target: "plantuml".to_string(),
+                    message: "'a' leads to 2 steps, where only a decision or fork branches"
+                        .to_string(),
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_emitted_aliases_can_be_read_back() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            ["order_items", "Outer.Inner", "Straße", "v1."]
+                .into_iter()
+                .for_each(|id: &str| add_node(&mut graph, id, NodeKind::Entity, None));
+            add_edge(
+                &mut graph,
+                "order_items",
+                "Outer.Inner",
+                EdgeKind::Association,
+            );
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+            let mut labels: Vec<(&str, Option<&str>)> = read
+                .nodes
+                .values()
+                .map(|node: &Node| (node.id.as_str(), node.label.as_deref()))
+                .collect();
+            labels.sort();
+
+            assert_eq!(
+                labels,
+                vec![
+                    ("Outer.Inner", Some("Outer.Inner")),
+                    ("Stra_e", Some("Straße")),
+                    ("order_items", Some("order_items")),
+                    ("v1_", Some("v1.")),
+                ]
+            );
+            assert_eq!(read.edges.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_colliding_aliases_get_distinct_suffixes() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            ["a-b", "a b", "a_b_2"]
+                .into_iter()
+                .for_each(|id: &str| add_node(&mut graph, id, NodeKind::Entity, None));
+            add_edge(&mut graph, "a-b", "a b", EdgeKind::Association);
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+            let mut labels: Vec<(&str, Option<&str>)> = read
+                .nodes
+                .values()
+                .map(|node: &Node| (node.id.as_str(), node.label.as_deref()))
+                .collect();
+            labels.sort();
+
+            assert_eq!(
+                labels,
+                vec![
+                    ("a_b", Some("a b")),
+                    ("a_b_2", Some("a_b_2")),
+                    ("a_b_3", Some("a-b")),
+                ]
+            );
+            assert!(output.contains("a_b_3 -- a_b\n"), "{}", output);
+        });
+    }
+
+    #[test]
+    fn test_emitted_groups_can_be_read_back() {
+        smol::block_on(async {
+            let source: &str = "@startuml\npackage \"Core\" {\n  class A\n  package \"Inner parts\" {\n    class B\n  }\n}\nA --> B\n@enduml";
+            for gateway in [
+                PlantUmlGraphGateway::new(),
+                PlantUmlGraphGateway::with_arena(),
+            ] {
+                let graph: Graph = gateway.read_graph_from_raw_input(source).await.unwrap();
+                let output: String = PlantUmlGraphEmitter::new()
+                    .emit_graph(&graph)
+                    .await
+                    .unwrap();
+                let read: Graph = gateway.read_graph_from_raw_input(&output).await.unwrap();
+                let package = |graph: &Graph, id: &str| -> Option<Text> {
+                    let parent: &Id = graph.nodes[id].parent.as_ref()?;
+                    graph.groups[parent].label.clone()
+                };
+
+                assert!(output.contains("package \"Core\" {\n"));
+                assert_eq!(read.groups.len(), 2);
+                assert_eq!(package(&read, "A").as_deref(), Some("Core"));
+                assert_eq!(package(&read, "B").as_deref(), Some("Inner parts"));
+                assert_eq!(read.edges.len(), 1);
+            }
+        });
+    }
+
+    #[test]
+    fn test_emitted_deployment_keywords_can_be_read_back() {
+        smol::block_on(async {
+            let source: &str = "@startuml\ncloud \"AWS\" {\n  node Server\n  storage Backups\n}\nqueue Jobs\nServer --> Jobs\n@enduml";
+            for gateway in [
+                PlantUmlGraphGateway::new(),
+                PlantUmlGraphGateway::with_arena(),
+            ] {
+                let graph: Graph = gateway.read_graph_from_raw_input(source).await.unwrap();
+                let output: String = PlantUmlGraphEmitter::new()
+                    .emit_graph(&graph)
+                    .await
+                    .unwrap();
+                let read: Graph = gateway.read_graph_from_raw_input(&output).await.unwrap();
+                let cloud: &Group = read.groups.values().next().unwrap();
+
+                assert!(output.contains("cloud \"AWS\" {\n"));
+                assert!(output.contains("storage \"Backups\" as Backups\n"));
+                assert_eq!(cloud.kind, GroupKind::Deployment(DeploymentKind::Cloud));
+                assert_eq!(
+                    read.nodes["Server"].kind,
+                    NodeKind::Deployment(DeploymentKind::Node)
+                );
+                assert_eq!(
+                    read.nodes["Jobs"].kind,
+                    NodeKind::Deployment(DeploymentKind::Queue)
+                );
+                assert_eq!(read.nodes["Backups"].parent, Some(cloud.id.clone()));
+                assert_eq!(read.edges.len(), 1);
+            }
+        });
+    }
+
+    #[test]
+    fn test_emitted_use_cases_can_be_read_back() {
+        smol::block_on(async {
+            let source: &str = "@startuml\nactor :Customer: as C\nrectangle \"Shop\" {\n  usecase (Log in) as UC1\n  (Check out)\n}\nC -> (Log in)\nC --> (Check out) : pays\n@enduml";
+            for gateway in [
+                PlantUmlGraphGateway::new(),
+                PlantUmlGraphGateway::with_arena(),
+            ] {
+                let graph: Graph = gateway.read_graph_from_raw_input(source).await.unwrap();
+                let output: String = PlantUmlGraphEmitter::new()
+                    .emit_graph(&graph)
+                    .await
+                    .unwrap();
+                let read: Graph = gateway.read_graph_from_raw_input(&output).await.unwrap();
+                let shop: &Group = read.groups.values().next().unwrap();
+
+                assert!(output.contains("rectangle \"Shop\" {\n"));
+                assert!(output.contains("  usecase \"Log in\" as UC1\n"));
+                assert!(output.contains("actor \"Customer\" as C\n"));
+                assert_eq!(read.kind, GraphKind::UseCase);
+                assert_eq!(shop.kind, GroupKind::Boundary);
+                assert_eq!(read.nodes["C"].kind, NodeKind::Actor);
+                assert_eq!(
+                    read.nodes["UC1"].kind,
+                    NodeKind::Custom("usecase".to_string())
+                );
+                assert_eq!(read.nodes["UC1"].parent, Some(shop.id.clone()));
+                assert_eq!(read.edges.len(), 2);
+            }
+        });
+    }
+
+    #[test]
+    fn test_emits_objects_with_their_values() {
+        smol::block_on(async {
+            let source: &str = "@startuml\nobject user {\n  name = \"Bob\"\n}\nmap Capitals {\n  UK => London\n}\n@enduml";
+            let graph: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(source)
+                .await
+                .unwrap();
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "@startuml",
+                    "map \"Capitals\" as Capitals {",
+                    "  UK => London",
+                    "}",
+                    "object \"user\" as user {",
+                    "  name = Bob",
+                    "}",
+                    "@enduml",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_emits_stereotypes_with_their_spot() {
+        smol::block_on(async {
+            let source: &str = "@startuml\nclass Billing << (S,#FF7700) Service >>\nclass Cache <<(C)>>\nclass Order <<Entity>>\n@enduml";
+            let graph: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(source)
+                .await
+                .unwrap();
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "@startuml",
+                    "class \"Billing\" as Billing << (S,#FF7700) Service >>",
+                    "class \"Cache\" as Cache << (C) >>",
+                    "class \"Order\" as Order <<Entity>>",
+                    "@enduml",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_emits_headings() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            graph.metadata.title = Some("Orders\nby region".to_string());
+            graph.metadata.properties = HashMap::from([
+                ("footer".to_string(), "Page 1".to_string()),
+                ("source".to_string(), "orders.sql".to_string()),
+            ]);
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "@startuml",
+                    "title",
+                    "Orders",
+                    "by region",
+                    "end title",
+                    "footer Page 1",
+                    "@enduml",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_emits_legends_that_read_back() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            graph.metadata.legend = Some(Legend {
+                text: "Draft\nv2".to_string(),
+                vertical: VerticalPosition::Top,
+                horizontal: HorizontalPosition::Left,
+            });
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                "@startuml\nlegend top left\nDraft\nv2\nendlegend\n@enduml\n"
+            );
+            assert_eq!(read.metadata.legend, graph.metadata.legend);
+        });
+    }
+
+    #[test]
+    fn test_emits_notes_on_links_after_their_relation() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "A", NodeKind::Entity, None);
+            add_node(&mut graph, "B", NodeKind::Entity, None);
+            add_edge(&mut graph, "A", "B", EdgeKind::Association);
+            add_node(&mut graph, "N", NodeKind::Annotation, None);
+            let note: &mut Node = graph.nodes.get_mut("N").unwrap();
+            note.label = Some("cached\nfor a day".into());
+            note.target_edge = Some("A-B".into());
+            note.data
+                .insert("position".into(), Value::String("left".to_string()));
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+            let edge: &Edge = read.edges.values().next().unwrap();
+            let note: &Node = &read.nodes["note_1"];
+
+            assert!(
+                output
+                    .ends_with("A -- B\nnote left on link\ncached\nfor a day\nend note\n@enduml\n")
+            );
+            assert_eq!(note.label.as_deref(), Some("cached\nfor a day"));
+            assert_eq!(note.target_edge.as_ref(), Some(&edge.id));
+        });
+    }
+
+    #[test]
+    fn test_notes_on_links_to_hidden_elements_are_left_out() {
+        smol::block_on(async {
+            let graph: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(
+                    "@startuml\nclass A\nclass B\nhide B\nA --> B\nnote on link : cached\n@enduml",
+                )
+                .await
+                .unwrap();
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(output, "@startuml\nclass \"A\" as A\n@enduml\n");
+        });
+    }
+
+    #[test]
+    fn test_emits_enum_values_that_read_back() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "Color", NodeKind::Enum, None);
+            graph.nodes.get_mut("Color").unwrap().members = vec![
+                Member {
+                    name: "RED".to_string(),
+                    kind: MemberKind::EnumValue,
+                    data: HashMap::from([("value".into(), Value::String("\"#f00\"".to_string()))]),
+                    ..Default::default()
+                },
+                Member {
+                    name: "BLACK".to_string(),
+                    kind: MemberKind::EnumValue,
+                    ..Default::default()
+                },
+            ];
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+
+            assert!(output.contains("enum \"Color\" as Color {\n  RED(\"#f00\")\n  BLACK\n}"));
+            assert_eq!(read.nodes["Color"].members, graph.nodes["Color"].members);
+        });
+    }
+
+    #[test]
+    fn test_emits_class_like_keywords() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(
+                &mut graph,
+                "Point",
+                NodeKind::Custom("struct".to_string()),
+                None,
+            );
+            add_node(
+                &mut graph,
+                "Drawable",
+                NodeKind::Custom("protocol".to_string()),
+                None,
+            );
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+            let read: Graph = PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input(&output)
+                .await
+                .unwrap();
+
+            assert!(output.contains("struct \"Point\" as Point\n"));
+            assert!(output.contains("protocol \"Drawable\" as Drawable\n"));
+            assert_eq!(read.nodes["Point"].kind, graph.nodes["Point"].kind);
+            assert_eq!(read.nodes["Drawable"].kind, graph.nodes["Drawable"].kind);
+        });
+    }
+
+    #[test]
+    fn test_options_change_indentation_and_quoting() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "User", NodeKind::Entity, Some("auth"));
+            add_node(&mut graph, "Token", NodeKind::Entity, Some("auth"));
+            graph.nodes.get_mut("Token").unwrap().label = Some("Access token".into());
+            graph.groups.insert(
+                "auth".into(),
+                Group {
+                    id: "auth".into(),
+                    label: Some("auth".into()),
+                    children: vec!["Token".into(), "User".into()],
+                    ..Default::default()
+                },
+            );
+
+            let output: String = PlantUmlGraphEmitter::new()
+                .with_options(EmitOptions {
+                    indent: Indent::Spaces(4),
+                    quoting: Quoting::AsNeeded,
+                    ..Default::default()
+                })
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                [
+                    "@startuml",
+                    "package \"auth\" {",
+                    "    class \"Access token\" as Token",
+                    "    class User",
+                    "}",
+                    "@enduml",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    fn add_node(graph: &mut Graph, id: &str, kind: NodeKind, parent: Option<&str>) {
+        graph.nodes.insert(
+            id.into(),
+            Node {
+                id: id.into(),
+                kind,
+                label: Some(id.into()),
+                parent: parent.map(Id::from),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn add_edge<'a>(graph: &'a mut Graph, from: &str, to: &str, kind: EdgeKind) -> &'a mut Edge {
+        let id: Id = format!("{}-{}", from, to).into();
+        graph.edges.insert(
+            id.clone(),
+            Edge {
+                id: id.clone(),
+                from: from.into(),
+                to: to.into(),
+                kind,
+                ..Default::default()
+            },
+        );
+        graph.edges.get_mut(&id).unwrap()
+    }
+}
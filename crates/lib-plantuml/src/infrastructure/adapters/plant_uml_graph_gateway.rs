@@ -1,29 +1,192 @@
 use async_trait::async_trait;
 use lib_core::{
     adapters::graph_gateway::{GraphGateway, GraphGatewayError},
-    entities::graph::Graph,
+    entities::{graph::Graph, warning::Warning},
 };
+use rayon::prelude::*;
 
 use crate::infrastructure::{
+    limits::Limits,
+    models::{ast_arena::AstArena, ast_node::AstNode},
     parser::{self, PlantUmlParseError},
-    transformer,
+    preprocessor::{Expanded, IncludeLoader, Preprocessor},
+    profile::Profile,
+    salt, transformer,
 };
 
 #[derive(Default)]
-pub struct PlantUmlGraphGateway;
+pub struct PlantUmlGraphGateway {
+    arena: bool,
+    limits: Limits,
+    profile: Profile,
+    preprocessor: Preprocessor,
+}
 
 impl PlantUmlGraphGateway {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Parses into a flat arena instead of a tree of statements, which
+    /// allocates far less on very large diagrams. The graph is the same.
+    pub fn with_arena() -> Self {
+        Self {
+            arena: true,
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the default bounds on input size, package nesting and
+    /// statement count
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Holds sources to a dialect; the default one is permissive
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Resolves `!include` lines with files the loader reads. Without one,
+    /// they are skipped with a warning like other directives.
+    pub fn with_loader(mut self, loader: impl IncludeLoader + 'static) -> Self {
+        self.preprocessor = self.preprocessor.with_loader(loader);
+        self
+    }
+
+    /// Replaces the pass run over sources before parsing, such as to set
+    /// variables up front
+    pub fn with_preprocessor(mut self, preprocessor: Preprocessor) -> Self {
+        self.preprocessor = preprocessor;
+        self
+    }
+
+    /// Reads every `@startuml`/`@enduml` and `@startsalt`/`@endsalt` block of
+    /// a file as its own graph, parsing the blocks in parallel. Error lines
+    /// count from the top of the file rather than the block.
+    pub fn read_graphs_from_blocks(&self, input: &str) -> Vec<Result<Graph, GraphGatewayError>> {
+        diagram_blocks(input)
+            .par_iter()
+            .map(|(offset, block): &(usize, &str)| {
+                self.read(block)
+                    .map(|mut graph: Graph| {
+                        graph.warnings.iter_mut().for_each(|warning: &mut Warning| {
+                            warning.line = warning.line.map(|line: usize| line + offset)
+                        });
+                        graph
+                    })
+                    .map_err(|e: GraphGatewayError| match e {
+                        GraphGatewayError::Parse {
+                            source,
+                            message,
+                            line,
+                            column,
+                        } => GraphGatewayError::Parse {
+                            source,
+                            message,
+                            line: line + offset,
+                            column,
+                        },
+                        e => e,
+                    })
+            })
+            .collect()
+    }
+
+    fn read(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        if !self.preprocessor.changes(input) {
+            return self.parse(input);
+        }
+        let expanded: Expanded = self
+            .preprocessor
+            .expand(input)
+            .map_err(PlantUmlParseError::from)?;
+
+        // Lines count in the diagram itself, those of an included file at its `!include`
+        match self.parse(&expanded.text) {
+            Ok(mut graph) => {
+                graph.warnings.iter_mut().for_each(|warning: &mut Warning| {
+                    if let Some(line) = warning.line {
+                        let (line, message): (usize, String) =
+                            expanded.locate(line, warning.message.clone());
+                        warning.line = Some(line);
+                        warning.message = message;
+                    }
+                });
+                Ok(graph)
+            }
+            Err(GraphGatewayError::Parse {
+                source,
+                message,
+                line,
+                column,
+            }) => {
+                let (line, message): (usize, String) = expanded.locate(line, message);
+                Err(GraphGatewayError::Parse {
+                    source,
+                    message,
+                    line,
+                    column,
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        self.limits.check_source(input)?;
+
+        if salt::is_salt(input) {
+            Ok(salt::parse_salt(input, &self.limits)?)
+        } else if self.arena {
+            let arena: AstArena = parser::parse_plantuml_arena(input)?;
+            self.limits.check_statements(arena.len())?;
+            Ok(self
+                .profile
+                .check(transformer::GraphBuilder::new().build_arena(&arena), input)?)
+        } else {
+            let ast: Vec<AstNode> = parser::parse_plantuml(input)?;
+            self.limits
+                .check_statements(ast.iter().map(AstNode::count).sum())?;
+            Ok(self
+                .profile
+                .check(transformer::GraphBuilder::new().build(ast), input)?)
+        }
+    }
+}
+
+/// Splits a file into its diagram blocks, each with the number of lines that
+/// precede it. An unterminated block runs to the end of the file.
+fn diagram_blocks(input: &str) -> Vec<(usize, &str)> {
+    let mut blocks: Vec<(usize, &str)> = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut position: usize = 0;
+
+    for (number, line) in input.split_inclusive('\n').enumerate() {
+        let keyword: &str = line.trim_start();
+        if (keyword.starts_with("@startuml") || keyword.starts_with("@startsalt"))
+            && start.is_none()
+        {
+            start = Some((number, position));
+        } else if (keyword.starts_with("@enduml") || keyword.starts_with("@endsalt"))
+            && let Some((offset, from)) = start.take()
+        {
+            blocks.push((offset, &input[from..position + line.len()]));
+        }
+        position += line.len();
+    }
+    if let Some((offset, from)) = start {
+        blocks.push((offset, &input[from..]));
     }
+    blocks
 }
 
 #[async_trait]
 impl GraphGateway for PlantUmlGraphGateway {
     async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
-        parser::parse_plantuml(input)
-            .map_err(GraphGatewayError::from)
-            .map(|ast| transformer::GraphBuilder::new().build(ast))
+        self.read(input)
     }
 }
 
@@ -40,7 +203,9 @@ impl From<PlantUmlParseError> for GraphGatewayError {
                 line,
                 column,
             },
-            PlantUmlParseError::Internal(msg) => GraphGatewayError::Semantic {
+            PlantUmlParseError::Internal(msg)
+            | PlantUmlParseError::LimitExceeded(msg)
+            | PlantUmlParseError::Disallowed(msg) => GraphGatewayError::Semantic {
                 source: "plantuml".into(),
                 message: msg,
             },
@@ -61,18 +226,31 @@ impl From<PlantUmlParseError> for GraphGatewayError {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use lib_core::{
         adapters::graph_gateway::{GraphGateway, GraphGatewayError},
         entities::{
-            edge::{Edge, EdgeKind},
-            graph::Graph,
-            group::Group,
-            node::{Node, NodeKind},
+            edge::{Edge, EdgeDirection, EdgeKind},
+            graph::{
+                Graph, GraphKind, HorizontalPosition, LayoutDirection, Legend, VerticalPosition,
+            },
+            group::{Group, GroupKind},
+            id::{Id, Text},
+            member::{Member, MemberKind},
+            node::{DeploymentKind, Node, NodeKind},
+            rich_text::{Line, RichText},
+            sequence::{Activation, Fragment, FragmentKind},
+            style::{Color, Colors},
+            value::Value,
+            warning::Warning,
         },
     };
 
     use crate::infrastructure::{
-        adapters::plant_uml_graph_gateway::PlantUmlGraphGateway, parser::PlantUmlParseError,
+        adapters::plant_uml_graph_gateway::PlantUmlGraphGateway, incremental::ParsedSource,
+        limits::Limits, parser::PlantUmlParseError, preprocessor::Preprocessor, profile::Profile,
+        streaming::stream_elements, syntax,
     };
 
     #[test]
@@ -301,6 +479,1861 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_ignored_directives_and_implicit_nodes_are_warned_about() {
+        let block: &str = "@startuml\nscale 2\nskinparam class {\n  BackgroundColor White\n}\nhide @unlinked\nclass User\nUser --> Order\n@enduml\n";
+        let expected: Vec<Warning> = vec![
+            Warning {
+                source: "plantuml".to_string(),
+                message: "`scale 2` is not supported".to_string(),
+                line: Some(2),
+            },
+            Warning {
+                source: "plantuml".to_string(),
+                message: "`hide @unlinked` is not supported".to_string(),
+                line: Some(6),
+            },
+            Warning {
+                source: "plantuml".to_string(),
+                message: "`Order` is not declared".to_string(),
+                line: None,
+            },
+        ];
+
+        [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ]
+        .iter()
+        .for_each(|gateway: &PlantUmlGraphGateway| {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(block))
+                .expect("Directives should not stop the diagram from being read");
+            assert_eq!(graph.warnings, expected);
+            assert_eq!(graph.nodes.len(), 2);
+        });
+
+        // Lines count from the top of the file when it holds several diagrams
+        let graphs: Vec<Result<Graph, GraphGatewayError>> = PlantUmlGraphGateway::new()
+            .read_graphs_from_blocks(&format!("@startuml\nclass A\n@enduml\n{}", block));
+        assert_eq!(graphs[1].as_ref().unwrap().warnings[0].line, Some(5));
+    }
+
+    #[test]
+    fn test_strict_profile_rejects_what_permissive_warns_about() {
+        let gateway: PlantUmlGraphGateway =
+            PlantUmlGraphGateway::with_arena().with_profile(Profile::Strict);
+
+        match smol::block_on(
+            gateway
+                .read_graph_from_raw_input("@startuml\nclass A\n  scale max 1024 width\n@enduml\n"),
+        ) {
+            Err(GraphGatewayError::Parse {
+                message,
+                line,
+                column,
+                ..
+            }) => {
+                assert_eq!(message, "`scale max 1024 width` is not supported");
+                assert_eq!((line, column), (3, 3));
+            }
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        assert!(matches!(
+            smol::block_on(gateway.read_graph_from_raw_input("@startuml\nA --> B\n@enduml\n")),
+            Err(GraphGatewayError::Semantic { .. })
+        ));
+        assert!(
+            smol::block_on(
+                gateway
+                    .read_graph_from_raw_input("@startuml\nclass A\nclass B\nA --> B\n@enduml\n")
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_class_body_members() {
+        smol::block_on(async {
+            let parser: PlantUmlGraphGateway = PlantUmlGraphGateway::new();
+            let source: &str = r#"
+            @startuml
+            class User {
+              -id : Uuid
+              ' comments are skipped
+              {static} count : int
+              +rename(from, to) : bool
+            }
+            @enduml
+        "#;
+
+            let graph: Graph = parser
+                .read_graph_from_raw_input(source)
+                .await
+                .expect("Failed to parse class body");
+
+            let user: &Node = find_node_by_label(&graph, "User").expect("Missing User node");
+            assert_eq!(
+                user.members,
+                vec![
+                    Member {
+                        name: "id".to_string(),
+                        kind: MemberKind::Field,
+                        type_name: Some("Uuid".to_string()),
+                        data: HashMap::from([(
+                            "visibility".into(),
+                            Value::String("-".to_string())
+                        )]),
+                    },
+                    Member {
+                        name: "count".to_string(),
+                        kind: MemberKind::Field,
+                        type_name: Some("int".to_string()),
+                        data: HashMap::from([("static".into(), Value::Bool(true))]),
+                    },
+                    Member {
+                        name: "rename".to_string(),
+                        kind: MemberKind::Method,
+                        type_name: Some("bool".to_string()),
+                        data: HashMap::from([
+                            ("visibility".into(), Value::String("+".to_string())),
+                            (
+                                "parameters".into(),
+                                Value::List(vec![
+                                    Value::String("from".to_string()),
+                                    Value::String("to".to_string()),
+                                ])
+                            ),
+                        ]),
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_reads_each_diagram_block_as_its_own_graph() {
+        let source: &str =
+            "' models\n@startuml\nclass A\n@enduml\n\n@startuml\nclass B\nclass C\n@enduml\n";
+
+        let graphs: Vec<Result<Graph, GraphGatewayError>> =
+            PlantUmlGraphGateway::new().read_graphs_from_blocks(source);
+
+        let sizes: Vec<usize> = graphs
+            .into_iter()
+            .map(|graph: Result<Graph, GraphGatewayError>| graph.unwrap().nodes.len())
+            .collect();
+        assert_eq!(sizes, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_block_errors_are_located_in_the_file() {
+        let block: &str = "@startuml\nclass A -->\n@enduml\n";
+        let gateway: PlantUmlGraphGateway = PlantUmlGraphGateway::new();
+
+        let alone: Result<Graph, GraphGatewayError> =
+            smol::block_on(gateway.read_graph_from_raw_input(block));
+        let graphs: Vec<Result<Graph, GraphGatewayError>> =
+            gateway.read_graphs_from_blocks(&format!("@startuml\nclass B\n@enduml\n{}", block));
+
+        assert!(graphs[0].is_ok());
+        match (alone, &graphs[1]) {
+            (
+                Err(GraphGatewayError::Parse { line: expected, .. }),
+                Err(GraphGatewayError::Parse { line, .. }),
+            ) => assert_eq!(*line, expected + 3),
+            other => panic!("Expected parse errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reads_salt_wireframes() {
+        let source: &str = "@startsalt\n{+\n  Login    | \"MyName   \"\n  Password | \"****     \"\n  [] Remember me | ^English^\n  ..\n  [Cancel] | [  OK   ]\n  {T\n   + Settings\n   ++ Theme\n   ++ Keys\n  }\n}\n@endsalt\n";
+
+        let graph: Graph =
+            smol::block_on(PlantUmlGraphGateway::new().read_graph_from_raw_input(source)).unwrap();
+
+        assert_eq!(graph.kind, GraphKind::Wireframe);
+        let widget = |label: &str| -> (NodeKind, Option<&Value>, Option<&Value>) {
+            let node: &Node = find_node_by_label(&graph, label).unwrap();
+            (
+                node.kind.clone(),
+                node.data.get("row"),
+                node.data.get("column"),
+            )
+        };
+        assert_eq!(
+            widget("MyName"),
+            (
+                NodeKind::Custom("text_field".to_string()),
+                Some(&Value::Number(0.0)),
+                Some(&Value::Number(1.0))
+            )
+        );
+        assert_eq!(
+            widget("OK"),
+            (
+                NodeKind::Custom("button".to_string()),
+                Some(&Value::Number(4.0)),
+                Some(&Value::Number(1.0))
+            )
+        );
+        assert_eq!(
+            find_node_by_label(&graph, "Remember me").unwrap().data["checked"],
+            Value::Bool(false)
+        );
+        assert_eq!(
+            widget("English").0,
+            NodeKind::Custom("droplist".to_string())
+        );
+        assert_eq!(graph.groups["grid"].children.len(), 10);
+        assert_eq!(graph.groups["tree"].parent.as_deref(), Some("grid"));
+        assert!(find_edge_between_labels(&graph, "Settings", "Keys").is_some());
+        assert!(find_edge_between_labels(&graph, "Theme", "Keys").is_none());
+    }
+
+    #[test]
+    fn test_salt_blocks_mix_with_uml_ones() {
+        let source: &str = "@startuml\nclass A\n@enduml\n@startsalt\n{\n  [OK]\n@endsalt\n@startuml\nsalt\n{\n  Name | \"Ada\"\n}\n@enduml\n";
+
+        let graphs: Vec<Result<Graph, GraphGatewayError>> =
+            PlantUmlGraphGateway::new().read_graphs_from_blocks(source);
+
+        assert_eq!(graphs[0].as_ref().unwrap().kind, GraphKind::Generic);
+        assert_eq!(
+            graphs[1],
+            Err(GraphGatewayError::Parse {
+                source: "plantuml".to_string(),
+                message: "Unclosed `{`".to_string(),
+                line: 5,
+                column: 1,
+            })
+        );
+        assert_eq!(graphs[2].as_ref().unwrap().kind, GraphKind::Wireframe);
+        assert_eq!(graphs[2].as_ref().unwrap().nodes.len(), 2);
+    }
+
+    #[test]
+    fn test_reads_archimate_macros() {
+        let source: &str = "@startuml\n!include <archimate/Archimate>\nBusiness_Actor(customer, \"Customer\")\nBusiness_Process(order_intake, \"Take order\")\nApplication_Component(shop, \"Web shop\")\nRel_Triggering_Down(customer, order_intake, \"places\")\nRel_Serving(shop, order_intake)\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert!(graph.warnings.is_empty());
+            assert_eq!(graph.nodes["customer"].kind, NodeKind::Actor);
+            assert_eq!(graph.nodes["shop"].kind, NodeKind::Component);
+            let process: &Node = &graph.nodes["order_intake"];
+            assert_eq!(
+                process.kind,
+                NodeKind::Custom("business_process".to_string())
+            );
+            assert_eq!(process.label.as_deref(), Some("Take order"));
+            assert_eq!(
+                process.data,
+                HashMap::from([
+                    (
+                        "archimate_layer".into(),
+                        Value::String("business".to_string())
+                    ),
+                    (
+                        "archimate_element".into(),
+                        Value::String("process".to_string())
+                    ),
+                ])
+            );
+
+            let places: &Edge = find_edge_between_labels(&graph, "Customer", "Take order").unwrap();
+            assert_eq!(places.kind, EdgeKind::Flow);
+            assert_eq!(places.label.as_deref(), Some("places"));
+            assert_eq!(places.layout.direction, Some(EdgeDirection::Down));
+            assert_eq!(
+                places.data["archimate_relation"],
+                Value::String("triggering".to_string())
+            );
+            let serves: &Edge = find_edge_between_labels(&graph, "Web shop", "Take order").unwrap();
+            assert_eq!(serves.kind, EdgeKind::Association);
+            assert_eq!(serves.layout.direction, None);
+        }
+    }
+
+    #[test]
+    fn test_reads_sequence_diagrams() {
+        let source: &str = "@startuml\nactor Alice\nparticipant \"Web shop\" as Shop\nAlice -> Shop : order\nactivate Shop\nalt in stock\n  Shop --> Alice : it's on its way\n  loop every day\n    Shop ->> Bank : poll\n  end\nelse\n  Alice <- Shop : sorry\nend\ndeactivate Shop\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.kind, GraphKind::Sequence);
+            assert!(graph.warnings.is_empty());
+            assert_eq!(graph.nodes["Shop"].kind, NodeKind::Component);
+            assert_eq!(graph.nodes["Bank"].kind, NodeKind::Component);
+            let mut messages: Vec<&Edge> = graph.edges.values().collect();
+            messages.sort_by_key(|edge: &&Edge| match edge.data["sequence"] {
+                Value::Number(sequence) => sequence as usize,
+                _ => 0,
+            });
+            assert_eq!(
+                messages
+                    .iter()
+                    .map(|edge: &&Edge| (
+                        edge.from.as_str(),
+                        edge.to.as_str(),
+                        edge.label.as_deref()
+                    ))
+                    .collect::<Vec<(&str, &str, Option<&str>)>>(),
+                vec![
+                    ("Alice", "Shop", Some("order")),
+                    ("Shop", "Alice", Some("it's on its way")),
+                    ("Shop", "Bank", Some("poll")),
+                    ("Shop", "Alice", Some("sorry")),
+                ]
+            );
+            assert!(
+                messages
+                    .iter()
+                    .all(|edge: &&Edge| edge.kind == EdgeKind::Flow)
+            );
+            assert_eq!(
+                messages[1].data["line_style"],
+                Value::String("dashed".to_string())
+            );
+            assert_eq!(messages[2].data["async"], Value::Bool(true));
+
+            let alt: &Fragment = graph
+                .fragments
+                .values()
+                .find(|fragment: &&Fragment| fragment.kind == FragmentKind::Alt)
+                .unwrap();
+            assert_eq!(alt.parent, None);
+            assert_eq!(alt.operands.len(), 2);
+            assert_eq!(alt.operands[0].condition.as_deref(), Some("in stock"));
+            assert_eq!(alt.operands[0].messages, vec![messages[1].id.clone()]);
+            assert_eq!(alt.operands[1].condition, None);
+            assert_eq!(alt.operands[1].messages, vec![messages[3].id.clone()]);
+            let repeat: &Fragment = &graph.fragments[&alt.operands[0].fragments[0]];
+            assert_eq!(repeat.kind, FragmentKind::Loop);
+            assert_eq!(repeat.parent.as_ref(), Some(&alt.id));
+            assert_eq!(repeat.operands[0].messages, vec![messages[2].id.clone()]);
+
+            assert_eq!(
+                graph.activations,
+                vec![Activation {
+                    participant: "Shop".into(),
+                    start: Some(messages[0].id.clone()),
+                    end: Some(messages[3].id.clone()),
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn test_reads_state_diagrams() {
+        let source: &str = "@startuml\n[*] --> Idle\nstate \"Processing order\" as Busy {\n  [*] --> Checking\n  Checking --> [*] : valid\n}\nIdle --> Busy : submit\nBusy : entry / start timer\nstate Idle : waiting\nIdle : for input\nBusy -> [*]\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.kind, GraphKind::State);
+            assert!(graph.warnings.is_empty());
+            assert_eq!(
+                graph.nodes["start"].kind,
+                NodeKind::Custom("start".to_string())
+            );
+            assert_eq!(graph.nodes["end"].kind, NodeKind::Custom("end".to_string()));
+            assert_eq!(
+                graph.nodes["Idle"].kind,
+                NodeKind::Custom("state".to_string())
+            );
+            assert_eq!(
+                graph.nodes["Idle"].data["description"],
+                Value::String("waiting\nfor input".to_string())
+            );
+
+            let busy: &Group = &graph.groups["Busy"];
+            assert_eq!(busy.label.as_deref(), Some("Processing order"));
+            assert_eq!(
+                busy.children,
+                vec![
+                    Id::from("Busy_start"),
+                    Id::from("Checking"),
+                    Id::from("Busy_end")
+                ]
+            );
+            assert!(!graph.nodes.contains_key("Busy"));
+            assert_eq!(graph.nodes["Checking"].parent.as_ref(), Some(&busy.id));
+
+            let mut transitions: Vec<(&str, &str, Option<&str>)> = graph
+                .edges
+                .values()
+                .map(|edge: &Edge| (edge.from.as_str(), edge.to.as_str(), edge.label.as_deref()))
+                .collect();
+            transitions.sort();
+            assert_eq!(
+                transitions,
+                vec![
+                    ("Busy", "end", None),
+                    ("Busy_start", "Checking", None),
+                    ("Checking", "Busy_end", Some("valid")),
+                    ("Idle", "Busy", Some("submit")),
+                    ("start", "Idle", None),
+                ]
+            );
+            assert!(
+                graph
+                    .edges
+                    .values()
+                    .all(|edge: &Edge| edge.kind == EdgeKind::Flow && edge.directed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reads_activity_diagrams() {
+        let source: &str = "@startuml\nstart\n:Take order;\nif (paid?) then (yes)\n  fork\n    :Pack;\n  fork again\n    :Invoice;\n  end fork\nelseif (credit?) then (yes)\n  :Bill later;\nelse (no)\n  stop\nendif\nwhile (items left?) is (yes)\n  :Ship item;\nendwhile (done)\nstop\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.kind, GraphKind::Flowchart);
+            assert!(graph.warnings.is_empty());
+            assert_eq!(graph.nodes["action_2"].label.as_deref(), Some("Take order"));
+            assert_eq!(
+                graph.nodes["decision_3"].kind,
+                NodeKind::Custom("decision".to_string())
+            );
+            assert_eq!(graph.nodes["decision_3"].label.as_deref(), Some("paid?"));
+
+            let mut flows: Vec<(&str, &str, Option<&str>)> = graph
+                .edges
+                .values()
+                .map(|edge: &Edge| (edge.from.as_str(), edge.to.as_str(), edge.label.as_deref()))
+                .collect();
+            flows.sort();
+            assert_eq!(
+                flows,
+                vec![
+                    ("action_12", "decision_11", None),
+                    ("action_2", "decision_3", None),
+                    ("action_5", "join_7", None),
+                    ("action_6", "join_7", None),
+                    ("action_9", "decision_11", None),
+                    ("decision_11", "action_12", Some("yes")),
+                    ("decision_11", "end_13", Some("done")),
+                    ("decision_3", "decision_8", None),
+                    ("decision_3", "fork_4", Some("yes")),
+                    ("decision_8", "action_9", Some("yes")),
+                    ("decision_8", "end_10", Some("no")),
+                    ("fork_4", "action_5", None),
+                    ("fork_4", "action_6", None),
+                    ("join_7", "decision_11", None),
+                    ("start_1", "action_2", None),
+                ]
+            );
+            assert!(
+                graph
+                    .edges
+                    .values()
+                    .all(|edge: &Edge| edge.kind == EdgeKind::Flow && edge.directed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reads_use_case_diagrams() {
+        let source: &str = "@startuml\nactor :Customer: as C\nrectangle \"Shop\" {\n  usecase (Log in) as UC1\n  (Check out)\n}\nC -> (Log in)\nC --> (Check out) : pays\n:Clerk: -> (Restock)\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.kind, GraphKind::UseCase);
+            assert!(graph.warnings.is_empty());
+            assert_eq!(graph.nodes["C"].kind, NodeKind::Actor);
+            assert_eq!(graph.nodes["C"].label.as_deref(), Some("Customer"));
+            assert_eq!(graph.nodes["Clerk"].kind, NodeKind::Actor);
+            assert_eq!(
+                graph.nodes["UC1"].kind,
+                NodeKind::Custom("usecase".to_string())
+            );
+            assert_eq!(graph.nodes["UC1"].label.as_deref(), Some("Log in"));
+            assert_eq!(
+                graph.nodes["Restock"].kind,
+                NodeKind::Custom("usecase".to_string())
+            );
+
+            let shop: &Group = graph.groups.values().next().unwrap();
+            assert_eq!(shop.label.as_deref(), Some("Shop"));
+            assert_eq!(shop.kind, GroupKind::Boundary);
+            assert_eq!(shop.children, vec![Id::from("UC1"), Id::from("Check out")]);
+
+            let mut links: Vec<(&str, &str, Option<&str>)> = graph
+                .edges
+                .values()
+                .map(|edge: &Edge| (edge.from.as_str(), edge.to.as_str(), edge.label.as_deref()))
+                .collect();
+            links.sort();
+            assert_eq!(
+                links,
+                vec![
+                    ("C", "Check out", Some("pays")),
+                    ("C", "UC1", None),
+                    ("Clerk", "Restock", None),
+                ]
+            );
+            assert!(
+                graph
+                    .edges
+                    .values()
+                    .all(|edge: &Edge| edge.kind == EdgeKind::Association)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reads_crow_foot_relationships() {
+        let source: &str = "@startuml\nentity Customer {\n  * id : number\n  --\n  * name : text\n  email : text\n}\nentity Order {\n  * id : number\n  * customer_id : number\n}\nCustomer ||--o{ Order : places\nOrder }|..|| Invoice\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.kind, GraphKind::EntityRelationship);
+            assert_eq!(
+                graph.nodes["Customer"]
+                    .members
+                    .iter()
+                    .map(|member: &Member| (
+                        member.name.as_str(),
+                        member.data.contains_key("primary_key"),
+                        member.data.contains_key("mandatory")
+                    ))
+                    .collect::<Vec<(&str, bool, bool)>>(),
+                vec![
+                    ("id", true, true),
+                    ("name", false, true),
+                    ("email", false, false),
+                ]
+            );
+            // Without a separator no attribute is known to be the key
+            assert!(
+                graph.nodes["Order"]
+                    .members
+                    .iter()
+                    .all(|member: &Member| !member.data.contains_key("primary_key"))
+            );
+
+            let mut edges: Vec<&Edge> = graph.edges.values().collect();
+            edges.sort_by_key(|edge: &&Edge| edge.from.clone());
+            let cardinalities = |edge: &Edge| -> (Option<Text>, Option<Text>) {
+                (edge.from_cardinality.clone(), edge.to_cardinality.clone())
+            };
+            assert_eq!(
+                cardinalities(edges[0]),
+                (Some("1".into()), Some("0..*".into()))
+            );
+            assert_eq!(edges[0].label.as_deref(), Some("places"));
+            assert_eq!(
+                cardinalities(edges[1]),
+                (Some("1..*".into()), Some("1".into()))
+            );
+            assert_eq!(
+                edges[1].data["line_style"],
+                Value::String("dashed".to_string())
+            );
+            assert!(
+                edges
+                    .iter()
+                    .all(|edge: &&Edge| edge.kind == EdgeKind::Association && !edge.directed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reads_objects_and_maps() {
+        let source: &str = "@startuml\nobject user1\nobject \"Bob\" as user2 {\n  name = \"Bob\"\n  site = \"http://bob.example\"\n}\nmap Capitals {\n  UK => London\n}\nuser1 --> user2\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let values = |id: &str| -> Vec<(String, Option<Value>)> {
+                graph.nodes[id]
+                    .members
+                    .iter()
+                    .map(|member: &Member| (member.name.clone(), member.data.get("value").cloned()))
+                    .collect()
+            };
+
+            assert_eq!(
+                graph.nodes["user1"].kind,
+                NodeKind::Custom("object".to_string())
+            );
+            assert!(graph.nodes["user1"].members.is_empty());
+            assert_eq!(
+                values("user2"),
+                vec![
+                    ("name".to_string(), Some(Value::String("Bob".to_string()))),
+                    (
+                        "site".to_string(),
+                        Some(Value::String("http://bob.example".to_string()))
+                    ),
+                ]
+            );
+            assert_eq!(
+                graph.nodes["Capitals"].kind,
+                NodeKind::Custom("map".to_string())
+            );
+            assert_eq!(
+                values("Capitals"),
+                vec![("UK".to_string(), Some(Value::String("London".to_string())))]
+            );
+            assert_eq!(graph.edges.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_reads_deployment_elements() {
+        let source: &str = "@startuml\ncloud \"AWS\" {\n  storage Backups\n  queue Jobs\n}\nartifact \"app.jar\" as app\ncard Notes\nfile Config\ncollections Logs\nnode Server\napp --> Jobs\napp --> Backups\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.kind, GraphKind::Generic);
+            let mut kinds: Vec<(&str, &NodeKind)> = graph
+                .nodes
+                .values()
+                .map(|node: &Node| (node.id.as_str(), &node.kind))
+                .collect();
+            kinds.sort_by_key(|(id, _): &(&str, &NodeKind)| *id);
+            assert_eq!(
+                kinds,
+                vec![
+                    ("Backups", &NodeKind::Deployment(DeploymentKind::Storage)),
+                    ("Config", &NodeKind::Deployment(DeploymentKind::File)),
+                    ("Jobs", &NodeKind::Deployment(DeploymentKind::Queue)),
+                    ("Logs", &NodeKind::Deployment(DeploymentKind::Collections)),
+                    ("Notes", &NodeKind::Deployment(DeploymentKind::Card)),
+                    ("Server", &NodeKind::Deployment(DeploymentKind::Node)),
+                    ("app", &NodeKind::Deployment(DeploymentKind::Artifact)),
+                ]
+            );
+            let cloud: &Group = graph.groups.values().next().unwrap();
+            assert_eq!(cloud.kind, GroupKind::Deployment(DeploymentKind::Cloud));
+            assert_eq!(cloud.label.as_deref(), Some("AWS"));
+            assert_eq!(cloud.children, vec![Id::from("Backups"), Id::from("Jobs")]);
+            assert_eq!(graph.edges.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_reads_class_like_keywords() {
+        let source: &str = "@startuml\nannotation Override\nprotocol Drawable {\n  +draw()\n}\nstruct Point {\n  x : int\n}\nexception NotFound\nmetaclass Meta\nhide exception\nPoint --|> Drawable\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let mut kinds: Vec<(&str, &NodeKind)> = graph
+                .nodes
+                .values()
+                .map(|node: &Node| (node.id.as_str(), &node.kind))
+                .collect();
+            kinds.sort_by_key(|(id, _): &(&str, &NodeKind)| *id);
+
+            assert_eq!(
+                kinds,
+                vec![
+                    ("Drawable", &NodeKind::Custom("protocol".to_string())),
+                    ("Meta", &NodeKind::Custom("metaclass".to_string())),
+                    ("NotFound", &NodeKind::Custom("exception".to_string())),
+                    ("Override", &NodeKind::Custom("annotation".to_string())),
+                    ("Point", &NodeKind::Custom("struct".to_string())),
+                ]
+            );
+            assert_eq!(graph.nodes["Drawable"].members[0].kind, MemberKind::Method);
+            assert_eq!(graph.nodes["Point"].members[0].name, "x");
+            assert!(graph.nodes["NotFound"].hidden);
+            assert!(!graph.nodes["Meta"].hidden);
+            assert_eq!(graph.edges.len(), 1);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_headings_into_metadata() {
+        let source: &str = "@startuml\ntitle Orders\nheader\n  Draft\n\n  v2\nend header\nfooter Page 1\ncaption Figure 1\nclass Order\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.metadata.title.as_deref(), Some("Orders"));
+            assert_eq!(
+                graph.metadata.properties,
+                HashMap::from([
+                    ("header".to_string(), "Draft\nv2".to_string()),
+                    ("footer".to_string(), "Page 1".to_string()),
+                    ("caption".to_string(), "Figure 1".to_string()),
+                ])
+            );
+            assert!(graph.warnings.is_empty());
+            assert_eq!(graph.nodes.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_reads_legends() {
+        for (source, legend) in [
+            (
+                "@startuml\nclass Order\nlegend top right\n  Orders in **bold**\n\n  are paid\nendlegend\n@enduml",
+                Legend {
+                    text: "Orders in **bold**\nare paid".to_string(),
+                    vertical: VerticalPosition::Top,
+                    horizontal: HorizontalPosition::Right,
+                },
+            ),
+            (
+                "@startuml\nlegend\nDraft\nend legend\nclass Order\n@enduml",
+                Legend {
+                    text: "Draft".to_string(),
+                    ..Legend::default()
+                },
+            ),
+        ] {
+            for gateway in [
+                PlantUmlGraphGateway::new(),
+                PlantUmlGraphGateway::with_arena(),
+            ] {
+                let graph: Graph =
+                    smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+                assert_eq!(graph.metadata.legend.as_ref(), Some(&legend));
+                assert_eq!(graph.nodes.len(), 1);
+                assert!(graph.warnings.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_reads_creole_markup_in_labels() {
+        let source: &str = "@startuml\nclass \"**Order**\\n//service//\" as Order\nclass Invoice\nOrder --> Invoice : * pays\\n* ~**twice~**\n@enduml";
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let order: &Node = &graph.nodes["Order"];
+            let edge: &Edge = graph.edges.values().next().unwrap();
+
+            assert_eq!(order.label.as_deref(), Some("Order\nservice"));
+            assert_eq!(
+                order
+                    .rich_label
+                    .as_ref()
+                    .map(|rich: &RichText| rich.lines.len()),
+                Some(2)
+            );
+            assert_eq!(edge.label.as_deref(), Some("pays\n**twice**"));
+            assert!(matches!(
+                edge.rich_label
+                    .as_ref()
+                    .map(|rich: &RichText| &rich.lines[1]),
+                Some(Line::Bullet { depth: 1, .. })
+            ));
+            assert_eq!(graph.nodes["Invoice"].rich_label, None);
+        }
+    }
+
+    #[test]
+    fn test_reads_notes_on_links() {
+        let source: &str = "@startuml\nnote on link : orphan\nclass Order\nOrder --> Invoice : pays\nnote on link : once\nOrder --> Customer\nnote bottom on link\n  checked\n  daily\nend note\n@enduml";
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let target = |note: &str| -> &str {
+                let edge: &Id = graph.nodes[note].target_edge.as_ref().unwrap();
+                graph.edges[edge].to.as_str()
+            };
+
+            assert_eq!(graph.nodes["note_1"].kind, NodeKind::Annotation);
+            assert_eq!(graph.nodes["note_1"].label.as_deref(), Some("once"));
+            assert_eq!(target("note_1"), "Invoice");
+            assert_eq!(
+                graph.nodes["note_2"].label.as_deref(),
+                Some("checked\ndaily")
+            );
+            assert_eq!(
+                graph.nodes["note_2"].data.get("position"),
+                Some(&Value::String("bottom".to_string()))
+            );
+            assert_eq!(target("note_2"), "Customer");
+            assert_eq!(
+                graph
+                    .warnings
+                    .iter()
+                    .map(|warning: &Warning| (warning.message.as_str(), warning.line))
+                    .collect::<Vec<(&str, Option<usize>)>>(),
+                vec![
+                    ("`note on link` follows no relation", Some(2)),
+                    ("`Invoice` is not declared", None),
+                    ("`Customer` is not declared", None),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_reads_the_layout_direction() {
+        for (source, direction) in [
+            (
+                "@startuml\nleft to right direction\nA --> B\n@enduml",
+                LayoutDirection::LeftToRight,
+            ),
+            (
+                "@startuml\nleft to right direction\ntop  to  bottom direction\n@enduml",
+                LayoutDirection::TopToBottom,
+            ),
+            (
+                "@startuml\nleft --> right\n@enduml",
+                LayoutDirection::TopToBottom,
+            ),
+        ] {
+            for gateway in [
+                PlantUmlGraphGateway::new(),
+                PlantUmlGraphGateway::with_arena(),
+            ] {
+                let graph: Graph =
+                    smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+                assert_eq!(graph.direction, direction);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reads_skinparams_into_styles() {
+        let source: &str = "@startuml\nskinparam shadowing false\nskinparam defaultFontName Courier New\nskinparam class {\n  BackgroundColor #fff\n  ' the border\n  BorderColor Black\n}\nskinparam class { BackgroundColor White }\nskinparam\nclass A\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let properties =
+                |id: &str| -> HashMap<String, String> { graph.styles[id].properties.clone() };
+
+            assert_eq!(graph.styles.len(), 2);
+            assert_eq!(
+                properties("skinparam"),
+                HashMap::from([
+                    ("shadowing".to_string(), "false".to_string()),
+                    ("defaultFontName".to_string(), "Courier New".to_string()),
+                ])
+            );
+            assert_eq!(
+                properties("class"),
+                HashMap::from([
+                    ("BackgroundColor".to_string(), "White".to_string()),
+                    ("BorderColor".to_string(), "Black".to_string()),
+                ])
+            );
+            // A bare `skinparam` sets nothing
+            assert_eq!(graph.warnings.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_expands_bundled_themes_into_styles() {
+        let source: &str = "@startuml\n!theme cerulean from https://example.com/themes\nskinparam class {\n  BorderColor Black\n}\nclass A\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert!(graph.warnings.is_empty());
+            assert_eq!(
+                graph.styles["skinparam"].properties["defaultFontName"],
+                "Verdana"
+            );
+            // Settings after the theme override its own
+            assert_eq!(
+                (
+                    graph.styles["class"].properties["BackgroundColor"].as_str(),
+                    graph.styles["class"].properties["BorderColor"].as_str()
+                ),
+                ("#2FA4E7", "Black")
+            );
+        }
+
+        let graph: Graph = smol::block_on(
+            PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input("@startuml\n!theme unheard-of\nclass A\n@enduml"),
+        )
+        .unwrap();
+        assert!(graph.styles.is_empty());
+        assert_eq!(
+            graph.warnings[0].message,
+            "`!theme unheard-of` is not supported"
+        );
+    }
+
+    #[test]
+    fn test_resolves_includes_with_the_loader() {
+        let loader: HashMap<String, String> = HashMap::from([(
+            "model.puml".to_string(),
+            "class User\nclass Order\nhide @unlinked\n".to_string(),
+        )]);
+        let source: &str = "@startuml\n!include model.puml\nUser --> Order\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new().with_loader(loader.clone()),
+            PlantUmlGraphGateway::with_arena().with_loader(loader.clone()),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.nodes.len(), 2);
+            assert_eq!(graph.edges.len(), 1);
+            assert_eq!(
+                graph.warnings,
+                vec![Warning {
+                    source: "plantuml".to_string(),
+                    message: "`hide @unlinked` is not supported (in `model.puml` at line 3)"
+                        .to_string(),
+                    line: Some(2),
+                }]
+            );
+        }
+
+        // A syntax error in the diagram itself keeps its own line
+        match smol::block_on(
+            PlantUmlGraphGateway::new()
+                .with_loader(loader)
+                .read_graph_from_raw_input("@startuml\n!include model.puml\nclass {\n@enduml"),
+        ) {
+            Err(GraphGatewayError::Parse { line, .. }) => assert_eq!(line, 3),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        // Without a loader an include is skipped like other directives
+        let graph: Graph =
+            smol::block_on(PlantUmlGraphGateway::new().read_graph_from_raw_input(source)).unwrap();
+        assert_eq!(
+            graph.warnings[0].message,
+            "`!include model.puml` is not supported"
+        );
+    }
+
+    #[test]
+    fn test_preprocesses_variables_and_conditions() {
+        let source: &str = "@startuml\n!ifndef $entity\n!$entity = \"Order\"\n!endif\nclass $entity\n!if $entity == \"Invoice\"\nclass Payment\n!endif\nclass Line$entity\n@enduml";
+        let names = |gateway: PlantUmlGraphGateway| -> Vec<String> {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let mut names: Vec<String> = graph.nodes.keys().map(|id: &Id| id.to_string()).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            names(PlantUmlGraphGateway::with_arena()),
+            vec!["LineOrder", "Order"]
+        );
+        assert_eq!(
+            names(
+                PlantUmlGraphGateway::new()
+                    .with_preprocessor(Preprocessor::new().with_variable("$entity", "Invoice"))
+            ),
+            vec!["Invoice", "LineInvoice", "Payment"]
+        );
+        match smol::block_on(
+            PlantUmlGraphGateway::new()
+                .read_graph_from_raw_input("@startuml\nclass A\n!else\n@enduml"),
+        ) {
+            Err(GraphGatewayError::Parse { message, line, .. }) => {
+                assert_eq!(message, "`!else` has no `!if` to belong to");
+                assert_eq!(line, 3);
+            }
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reads_generic_type_parameters() {
+        let source: &str = "@startuml\nclass List<T>\nclass Cache<K, Map<K, V>> as C {\n  +get(key : K) : V\n}\nC --> List\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let generics =
+                |id: &str| -> Option<Value> { graph.nodes[id].data.get("generics").cloned() };
+
+            assert_eq!(graph.nodes.len(), 2);
+            assert_eq!(graph.nodes["List"].label.as_deref(), Some("List"));
+            assert_eq!(
+                generics("List"),
+                Some(Value::List(vec![Value::String("T".to_string())]))
+            );
+            assert_eq!(graph.nodes["C"].label.as_deref(), Some("Cache"));
+            assert_eq!(
+                generics("C"),
+                Some(Value::List(vec![
+                    Value::String("K".to_string()),
+                    Value::String("Map<K, V>".to_string()),
+                ]))
+            );
+            assert_eq!(graph.nodes["C"].members.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_reads_stereotypes_and_their_spot() {
+        let source: &str = "@startuml\nclass Billing << (S,#FF7700) Service >> #pink\nclass \"Order\" as O <<Entity>> {\n  +id : int\n}\ninterface Cache << ( C ) >>\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let data =
+                |id: &str, key: &str| -> Option<Value> { graph.nodes[id].data.get(key).cloned() };
+            let text = |value: &str| -> Option<Value> { Some(Value::String(value.to_string())) };
+
+            assert_eq!(data("Billing", "stereotype"), text("Service"));
+            assert_eq!(data("Billing", "spot"), text("S"));
+            assert_eq!(data("Billing", "spot_color"), text("#FF7700"));
+            assert_eq!(
+                graph.nodes["Billing"].colors.fill,
+                Some(Color::Named("pink".into()))
+            );
+            assert_eq!(data("O", "stereotype"), text("Entity"));
+            assert_eq!(data("O", "spot"), None);
+            assert_eq!(graph.nodes["O"].members.len(), 1);
+            assert_eq!(data("Cache", "stereotype"), None);
+            assert_eq!(data("Cache", "spot"), text("C"));
+            assert_eq!(data("Cache", "spot_color"), None);
+        }
+    }
+
+    #[test]
+    fn test_reads_hidden_and_unranked_links() {
+        let source: &str = "@startuml\nclass A\nclass B\nclass C\nA -[hidden]-> B\nB -[#red,norank]- C\nA --> C\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let edge = |from: &str, to: &str| -> &Edge {
+                graph
+                    .edges
+                    .values()
+                    .find(|edge: &&Edge| edge.from.as_str() == from && edge.to.as_str() == to)
+                    .unwrap()
+            };
+
+            assert_eq!(graph.edges.len(), 3);
+            assert!(edge("A", "B").layout.hidden);
+            assert_eq!(edge("A", "B").kind, EdgeKind::Association);
+            assert!(!edge("B", "C").layout.hidden);
+            assert_eq!(edge("B", "C").kind, EdgeKind::Undirected);
+            assert_eq!(edge("B", "C").data.get("norank"), Some(&Value::Bool(true)));
+            assert!(!edge("A", "C").layout.hidden);
+            assert_eq!(edge("A", "C").data.get("norank"), None);
+        }
+    }
+
+    #[test]
+    fn test_reads_together_blocks() {
+        let source: &str = "@startuml\npackage \"Core\" {\n  together {\n    class A\n    class B\n  }\n  class C\n}\ntogether {\n  class D\n}\nA --> D\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let together =
+                |id: &str| -> Option<Value> { graph.nodes[id].data.get("together").cloned() };
+            let core: &Group = graph.groups.values().next().unwrap();
+
+            assert_eq!(graph.groups.len(), 1);
+            assert_eq!(
+                core.children,
+                vec![Id::from("A"), Id::from("B"), Id::from("C")]
+            );
+            assert_eq!(graph.nodes["A"].parent, Some(core.id.clone()));
+            assert_eq!(together("A"), Some(Value::Number(1.0)));
+            assert_eq!(together("B"), Some(Value::Number(1.0)));
+            assert_eq!(together("C"), None);
+            assert_eq!(together("D"), Some(Value::Number(2.0)));
+            assert_eq!(graph.nodes["D"].parent, None);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_arrow_direction_hints() {
+        let source: &str = "@startuml\nclass A\nA -up-> B\nA -d-|> C\nA -left[#red]- D\nA -[hidden]r-> E\nA --> F\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let edge = |to: &str| -> &Edge {
+                graph
+                    .edges
+                    .values()
+                    .find(|edge: &&Edge| edge.to.as_str() == to)
+                    .unwrap()
+            };
+
+            assert_eq!(edge("B").layout.direction, Some(EdgeDirection::Up));
+            assert_eq!(edge("B").kind, EdgeKind::Association);
+            assert_eq!(edge("C").layout.direction, Some(EdgeDirection::Down));
+            assert_eq!(edge("C").kind, EdgeKind::Inheritance);
+            assert_eq!(edge("D").layout.direction, Some(EdgeDirection::Left));
+            assert_eq!(edge("D").kind, EdgeKind::Undirected);
+            assert_eq!(edge("D").colors.stroke, Some(Color::Named("red".into())));
+            assert_eq!(edge("E").layout.direction, Some(EdgeDirection::Right));
+            assert!(edge("E").layout.hidden);
+            assert_eq!(edge("F").layout.direction, None);
+        }
+    }
+
+    #[test]
+    fn test_reads_arrow_lengths() {
+        let source: &str = "@startuml\nclass A\nA --> B\nA ---> C\nA <|---- D\nA -[#red]---o E\nA -up---> F\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let edge = |to: &str| -> &Edge {
+                graph
+                    .edges
+                    .values()
+                    .find(|edge: &&Edge| edge.to.as_str() == to)
+                    .unwrap()
+            };
+
+            assert_eq!(edge("B").layout.length, None);
+            assert_eq!(edge("C").layout.length, Some(2));
+            assert_eq!(edge("C").kind, EdgeKind::Association);
+            assert_eq!(edge("D").layout.length, Some(3));
+            assert_eq!(edge("D").kind, EdgeKind::Inheritance);
+            assert_eq!(edge("E").layout.length, Some(3));
+            assert_eq!(edge("E").kind, EdgeKind::Aggregation);
+            assert_eq!(edge("F").layout.length, Some(3));
+            assert_eq!(edge("F").layout.direction, Some(EdgeDirection::Up));
+        }
+    }
+
+    #[test]
+    fn test_reads_dotted_arrows() {
+        let source: &str = "@startuml\nclass A\nA .. B\nA ..> C\nD <.. A\nA ..|> E\nF <|.. A\nA .[#red].> G\nA .up.|> H\nA ....> I\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let edge = |end: &str| -> &Edge {
+                graph
+                    .edges
+                    .values()
+                    .find(|edge: &&Edge| edge.to.as_str() == end || edge.from.as_str() == end)
+                    .unwrap()
+            };
+
+            assert_eq!(
+                (&edge("B").kind, edge("B").directed),
+                (&EdgeKind::Dependency, false)
+            );
+            assert_eq!(
+                (&edge("C").kind, edge("C").directed),
+                (&EdgeKind::Dependency, true)
+            );
+            assert_eq!(edge("D").kind, EdgeKind::Dependency);
+            assert_eq!(edge("E").kind, EdgeKind::Realization);
+            assert_eq!(edge("F").kind, EdgeKind::Realization);
+            assert_eq!(edge("G").kind, EdgeKind::Dependency);
+            assert_eq!(edge("G").colors.stroke, Some(Color::Named("red".into())));
+            assert_eq!(edge("H").kind, EdgeKind::Realization);
+            assert_eq!(edge("H").layout.direction, Some(EdgeDirection::Up));
+            assert_eq!(edge("I").kind, EdgeKind::Dependency);
+            assert_eq!(edge("I").layout.length, Some(3));
+        }
+    }
+
+    #[test]
+    fn test_reads_association_classes() {
+        let source: &str = "@startuml\nclass Student\nclass Course\nclass Enrollment {\n  +grade : int\n}\nclass Room\nclass Booking\nStudent \"*\" -- \"*\" Course\n(Student, Course) .. Enrollment\nBooking .. (Course, Room)\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let attached = |class: &str| -> Vec<&Edge> {
+                graph
+                    .edges
+                    .values()
+                    .filter(|edge: &&Edge| {
+                        edge.data.get("association_class")
+                            == Some(&Value::String(class.to_string()))
+                    })
+                    .collect()
+            };
+
+            assert_eq!(graph.edges.len(), 2);
+            let enrollment: Vec<&Edge> = attached("Enrollment");
+            assert_eq!(enrollment.len(), 1);
+            assert_eq!(
+                (enrollment[0].from.as_str(), enrollment[0].to.as_str()),
+                ("Student", "Course")
+            );
+            assert_eq!(enrollment[0].from_cardinality.as_deref(), Some("*"));
+            let booking: Vec<&Edge> = attached("Booking");
+            assert_eq!(booking.len(), 1);
+            assert_eq!(
+                (booking[0].from.as_str(), booking[0].to.as_str()),
+                ("Course", "Room")
+            );
+            assert_eq!(booking[0].kind, EdgeKind::Undirected);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_classes_nested_in_bodies() {
+        let source: &str = "@startuml\nclass Outer {\n  +name : String\n  +class Inner {\n    -id : int\n  }\n  interface Listener\n}\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            let mut ids: Vec<&str> = graph.nodes.keys().map(Id::as_str).collect();
+            ids.sort();
+            assert_eq!(ids, vec!["Outer", "Outer.Inner", "Outer.Listener"]);
+            assert_eq!(graph.nodes["Outer"].members.len(), 1);
+            assert_eq!(graph.nodes["Outer.Inner"].label.as_deref(), Some("Inner"));
+            assert_eq!(graph.nodes["Outer.Inner"].members[0].name, "id");
+            assert_eq!(graph.nodes["Outer.Listener"].kind, NodeKind::Interface);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_enum_values() {
+        let source: &str = "@startuml\nenum Status {\n  ACTIVE\n  INACTIVE\n}\nenum Color <<palette>> {\n  RED(\"#f00\"), GREEN(\"#0f0\");\n  --\n  +hex() : String\n  name : String\n}\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let members = |id: &str| -> Vec<(MemberKind, &str, Option<&Value>)> {
+                graph.nodes[id]
+                    .members
+                    .iter()
+                    .map(|member: &Member| {
+                        (
+                            member.kind.clone(),
+                            member.name.as_str(),
+                            member.data.get("value"),
+                        )
+                    })
+                    .collect()
+            };
+            let value = |text: &str| -> Option<Value> { Some(Value::String(text.to_string())) };
+
+            assert_eq!(graph.nodes["Status"].kind, NodeKind::Enum);
+            assert_eq!(
+                members("Status"),
+                vec![
+                    (MemberKind::EnumValue, "ACTIVE", None),
+                    (MemberKind::EnumValue, "INACTIVE", None),
+                ]
+            );
+            assert_eq!(graph.nodes["Color"].kind, NodeKind::Enum);
+            assert_eq!(
+                members("Color"),
+                vec![
+                    (MemberKind::EnumValue, "RED", value("\"#f00\"").as_ref()),
+                    (MemberKind::EnumValue, "GREEN", value("\"#0f0\"").as_ref()),
+                    (MemberKind::Separator, "", None),
+                    (MemberKind::Method, "hex", None),
+                    (MemberKind::Field, "name", None),
+                ]
+            );
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_abstract_classes_and_member_modifiers() {
+        let source: &str = "@startuml\nabstract class Shape {\n  {abstract} +area() : double\n  {classifier} count : int\n  {static} {method} reset\n  {field} handler(x)\n}\nabstract Base\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let shape: &Node = &graph.nodes["Shape"];
+            let members: Vec<(&str, MemberKind, bool, bool)> = shape
+                .members
+                .iter()
+                .map(|member: &Member| {
+                    (
+                        member.name.as_str(),
+                        member.kind.clone(),
+                        member.is_static(),
+                        member.is_abstract(),
+                    )
+                })
+                .collect();
+
+            assert_eq!(shape.kind, NodeKind::Entity);
+            assert_eq!(shape.data.get("abstract"), Some(&Value::Bool(true)));
+            assert_eq!(graph.nodes["Base"].kind, NodeKind::Entity);
+            assert_eq!(
+                graph.nodes["Base"].data.get("abstract"),
+                Some(&Value::Bool(true))
+            );
+            assert_eq!(
+                members,
+                vec![
+                    ("area", MemberKind::Method, false, true),
+                    ("count", MemberKind::Field, true, false),
+                    ("reset", MemberKind::Method, true, false),
+                    ("handler(x)", MemberKind::Field, false, false),
+                ]
+            );
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_separators_and_section_titles() {
+        let source: &str = "@startuml\nclass User {\n  -id : int\n  -- Getters --\n  +getId() : int\n  ..\n  == Setters ==\n  __init__()\n  __ internal __\n}\nentity Order {\n  * id : number\n  --\n  total : number\n  .. audit ..\n  created : date\n}\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let members = |id: &str| -> Vec<(MemberKind, &str, Option<&Value>)> {
+                graph.nodes[id]
+                    .members
+                    .iter()
+                    .map(|member: &Member| {
+                        (
+                            member.kind.clone(),
+                            member.name.as_str(),
+                            member.data.get("line"),
+                        )
+                    })
+                    .collect()
+            };
+            let line = |line: &str| -> Option<Value> { Some(Value::String(line.to_string())) };
+
+            assert_eq!(
+                members("User"),
+                vec![
+                    (MemberKind::Field, "id", None),
+                    (MemberKind::Separator, "Getters", None),
+                    (MemberKind::Method, "getId", None),
+                    (MemberKind::Separator, "", line("dotted").as_ref()),
+                    (MemberKind::Separator, "Setters", line("double").as_ref()),
+                    (MemberKind::Method, "__init__", None),
+                    (MemberKind::Separator, "internal", line("thick").as_ref()),
+                ]
+            );
+            assert_eq!(
+                members("Order"),
+                vec![
+                    (MemberKind::Field, "id", None),
+                    (MemberKind::Field, "total", None),
+                    (MemberKind::Separator, "audit", line("dotted").as_ref()),
+                    (MemberKind::Field, "created", None),
+                ]
+            );
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_namespace_qualified_class_names() {
+        let source: &str =
+            "@startuml\nclass Outer\nclass Outer::Inner\nclass Outer::Inner::Deep as D\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert_eq!(graph.nodes["Outer.Inner"].label.as_deref(), Some("Inner"));
+            assert_eq!(graph.nodes["D"].label.as_deref(), Some("Deep"));
+            assert_eq!(graph.nodes.len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_reads_deeply_nested_classes() {
+        let source: &str = "@startuml\npackage \"Model\" {\n  class A {\n    class B {\n      class C {\n        class D\n      }\n    }\n    class B::E\n  }\n}\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let package: &Group = graph.groups.values().next().unwrap();
+
+            assert_eq!(
+                package.children,
+                vec![
+                    Id::from("A"),
+                    Id::from("A.B"),
+                    Id::from("A.B.C"),
+                    Id::from("A.B.C.D"),
+                    Id::from("A.B.E"),
+                ]
+            );
+            package.children.iter().for_each(|id: &Id| {
+                assert_eq!(graph.nodes[id].parent.as_ref(), Some(&package.id));
+            });
+            assert_eq!(graph.nodes["A.B.C.D"].label.as_deref(), Some("D"));
+        }
+    }
+
+    #[test]
+    fn test_relates_nested_classes_by_qualified_name() {
+        let source: &str = "@startuml\nclass Outer {\n  class Inner {\n    class Deep\n  }\n  +name : String\n}\nclass Other\nOuter::Inner --> Other\nOther --> Outer::Inner::Deep\nOther --> Outer::name\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let mut edges: Vec<(&str, &str, Option<&str>)> = graph
+                .edges
+                .values()
+                .map(|edge: &Edge| {
+                    (
+                        edge.from.as_str(),
+                        edge.to.as_str(),
+                        edge.to_port.as_deref(),
+                    )
+                })
+                .collect();
+            edges.sort();
+
+            assert_eq!(
+                edges,
+                vec![
+                    ("Other", "Outer", Some("name")),
+                    ("Other", "Outer.Inner.Deep", None),
+                    ("Outer.Inner", "Other", None),
+                ]
+            );
+            assert_eq!(graph.nodes.len(), 4);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_member_qualified_relation_ends() {
+        let source: &str = "@startuml\nclass Order {\n  customer : Customer\n}\nclass Customer\nOrder::customer --> Customer\nCustomer \"1\" -- \"*\" Order::lines_2 : owns\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let ports = |from: &str| -> (&str, Option<&str>, Option<&str>) {
+                let edge: &Edge = graph
+                    .edges
+                    .values()
+                    .find(|edge: &&Edge| edge.from.as_str() == from)
+                    .unwrap();
+                (
+                    edge.to.as_str(),
+                    edge.from_port.as_deref(),
+                    edge.to_port.as_deref(),
+                )
+            };
+
+            assert_eq!(graph.nodes.len(), 2);
+            assert_eq!(ports("Order"), ("Customer", Some("customer"), None));
+            assert_eq!(ports("Customer"), ("Order", None, Some("lines_2")));
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reads_relation_cardinalities() {
+        let source: &str = "@startuml\nclass User\nclass Order\nUser \"1\" -- \"0..*\" Order : places\nOrder --> \"many\" User\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let edge = |from: &str| -> &Edge {
+                graph
+                    .edges
+                    .values()
+                    .find(|edge: &&Edge| edge.from.as_str() == from)
+                    .unwrap()
+            };
+
+            assert_eq!(edge("User").label.as_deref(), Some("places"));
+            assert_eq!(edge("User").from_cardinality.as_deref(), Some("1"));
+            assert_eq!(edge("User").to_cardinality.as_deref(), Some("0..*"));
+            assert_eq!(edge("Order").from_cardinality, None);
+            assert_eq!(edge("Order").to_cardinality.as_deref(), Some("many"));
+        }
+    }
+
+    #[test]
+    fn test_reads_element_and_arrow_colors() {
+        let source: &str = "@startuml\nclass Foo #lightblue\nclass \"Bar\" as B #FF0000/white ##[dashed]blue {\n  +id : int\n}\nFoo -[#red,bold]-> B\nB -[#00ff00]-> Foo : back\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let style = |data: &HashMap<Text, Value>| data.get("style").cloned();
+            let edge = |from: &str| -> &Edge {
+                graph
+                    .edges
+                    .values()
+                    .find(|edge: &&Edge| edge.from.as_str() == from)
+                    .unwrap()
+            };
+            let stroke = |color: Color| -> Colors {
+                Colors {
+                    stroke: Some(color),
+                    ..Default::default()
+                }
+            };
+
+            assert_eq!(
+                graph.nodes["Foo"].colors,
+                Colors {
+                    fill: Some(Color::Named("lightblue".into())),
+                    ..Default::default()
+                }
+            );
+            assert_eq!(style(&graph.nodes["Foo"].data), None);
+            assert_eq!(
+                graph.nodes["B"].colors,
+                Colors {
+                    fill: Some(Color::Rgba([255, 0, 0, 255])),
+                    gradient: Some(Color::Named("white".into())),
+                    stroke: Some(Color::Named("blue".into())),
+                    text: None,
+                }
+            );
+            assert_eq!(
+                style(&graph.nodes["B"].data),
+                Some(Value::String("stroke-dasharray:6 4".to_string()))
+            );
+            assert_eq!(graph.nodes["B"].members.len(), 1);
+            assert_eq!(edge("Foo").kind, EdgeKind::Association);
+            assert_eq!(edge("Foo").colors, stroke(Color::Named("red".into())));
+            assert_eq!(
+                style(&edge("Foo").data),
+                Some(Value::String("stroke-width:2".to_string()))
+            );
+            assert_eq!(edge("B").label.as_deref(), Some("back"));
+            assert_eq!(edge("B").colors, stroke(Color::Rgba([0, 255, 0, 255])));
+            assert_eq!(style(&edge("B").data), None);
+        }
+    }
+
+    #[test]
+    fn test_reads_hide_directives() {
+        let source: &str = "@startuml\nclass A\nclass \"Billing\" as B\nhide B\nhide Legacy\nhide empty members\npackage \"Legacy\" {\n  class C\n}\nA --> B\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+
+            assert!(!graph.nodes["A"].hidden);
+            assert!(graph.nodes["B"].hidden);
+            assert!(graph.groups.values().all(|group: &Group| group.hidden));
+            assert_eq!(graph.hidden().len(), 3);
+            assert_eq!(graph.edges.len(), 1);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_hides_and_shows_members_again() {
+        let source: &str = "@startuml\nclass User {\n  +name : String\n  +login()\n}\ninterface Repository {\n  +find()\n}\nhide members\nshow User methods\nclass Order {\n  +total : int\n}\nshow Order fields\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let names = |id: &str| -> Vec<&str> {
+                graph.nodes[id]
+                    .members
+                    .iter()
+                    .map(|member: &Member| member.name.as_str())
+                    .collect()
+            };
+
+            assert_eq!(names("User"), vec!["login"]);
+            assert_eq!(names("Repository"), Vec::<&str>::new());
+            assert_eq!(names("Order"), vec!["total"]);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_hides_circles_stereotypes_and_kinds() {
+        let source: &str = "@startuml\nhide circle\nclass A << Service >>\nclass B << Internal >>\ninterface I\nhide <<Service>> stereotype\nhide interface\nshow B circle\nhide empty members\n@enduml";
+
+        for gateway in [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ] {
+            let graph: Graph = smol::block_on(gateway.read_graph_from_raw_input(source)).unwrap();
+            let flag = |id: &str, key: &str| -> bool {
+                graph.nodes[id].data.get(key) == Some(&Value::Bool(true))
+            };
+
+            assert!(flag("A", "hide_circle"));
+            assert!(!flag("B", "hide_circle"));
+            assert!(flag("A", "hide_stereotype"));
+            assert!(!flag("B", "hide_stereotype"));
+            assert!(graph.nodes["I"].hidden);
+            assert!(!graph.nodes["A"].hidden);
+            assert!(graph.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_arena_mode_builds_the_same_graph() {
+        let source: &str = "@startuml\npackage \"Core\" {\n  class \"User Account\" as U {\n    +name : String\n  }\n  package \"Inner\" {\n    interface Repo\n  }\n  U --> Repo : uses\n}\nOrder --|> U\n@enduml";
+
+        let tree: Graph =
+            smol::block_on(PlantUmlGraphGateway::new().read_graph_from_raw_input(source)).unwrap();
+        let arena: Graph =
+            smol::block_on(PlantUmlGraphGateway::with_arena().read_graph_from_raw_input(source))
+                .unwrap();
+
+        let group_labels = |graph: &Graph, id: &Option<Id>| -> Option<Text> {
+            id.as_ref()
+                .and_then(|id: &Id| graph.groups.get(id))
+                .and_then(|group: &Group| group.label.clone())
+        };
+        assert_eq!(arena.nodes.len(), tree.nodes.len());
+        tree.nodes.values().for_each(|node: &Node| {
+            let other: &Node = &arena.nodes[&node.id];
+            assert_eq!(other.kind, node.kind);
+            assert_eq!(other.label, node.label);
+            assert_eq!(other.members, node.members);
+            assert_eq!(
+                group_labels(&arena, &other.parent),
+                group_labels(&tree, &node.parent)
+            );
+        });
+        let edges = |graph: &Graph| -> Vec<(String, String, EdgeKind, Option<Text>)> {
+            let mut edges: Vec<(String, String, EdgeKind, Option<Text>)> = graph
+                .edges
+                .values()
+                .map(|e: &Edge| {
+                    (
+                        e.from.to_string(),
+                        e.to.to_string(),
+                        e.kind.clone(),
+                        e.label.clone(),
+                    )
+                })
+                .collect();
+            edges.sort_by_key(|edge: &(String, String, EdgeKind, Option<Text>)| {
+                (edge.0.clone(), edge.1.clone())
+            });
+            edges
+        };
+        assert_eq!(edges(&arena), edges(&tree));
+        let groups = |graph: &Graph| -> Vec<(Option<Text>, Vec<String>, Option<Text>)> {
+            let mut groups: Vec<(Option<Text>, Vec<String>, Option<Text>)> = graph
+                .groups
+                .values()
+                .map(|g: &Group| {
+                    (
+                        g.label.clone(),
+                        g.children.iter().map(Id::to_string).collect(),
+                        group_labels(graph, &g.parent),
+                    )
+                })
+                .collect();
+            groups.sort();
+            groups
+        };
+        assert_eq!(groups(&arena), groups(&tree));
+    }
+
+    #[test]
+    fn test_enforces_limits_in_both_modes() {
+        let limits: Limits = Limits {
+            max_depth: 1,
+            max_statements: 2,
+            ..Default::default()
+        };
+        let nested: &str = "@startuml\npackage \"A\" {\n  package \"B\" {\n  }\n}\n@enduml";
+        let long: &str = "@startuml\nclass A\nclass B\nA --> B\n@enduml";
+
+        [
+            PlantUmlGraphGateway::new(),
+            PlantUmlGraphGateway::with_arena(),
+        ]
+        .into_iter()
+        .map(|gateway: PlantUmlGraphGateway| gateway.with_limits(limits))
+        .for_each(|gateway: PlantUmlGraphGateway| {
+            assert_eq!(
+                smol::block_on(gateway.read_graph_from_raw_input(nested)),
+                Err(GraphGatewayError::Semantic {
+                    source: "plantuml".into(),
+                    message: "Blocks nest past the depth limit of 1 at line 3".into(),
+                })
+            );
+            assert_eq!(
+                smol::block_on(gateway.read_graph_from_raw_input(long)),
+                Err(GraphGatewayError::Semantic {
+                    source: "plantuml".into(),
+                    message: "Diagram has 3 statements, more than the 2 allowed".into(),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_deep_nesting_fails_before_parsing() {
+        let depth: usize = 100_000;
+        let input: String = format!(
+            "@startuml\n{}{}@enduml",
+            "package \"P\" {\n".repeat(depth),
+            "}\n".repeat(depth)
+        );
+
+        let result: Result<Graph, GraphGatewayError> =
+            smol::block_on(PlantUmlGraphGateway::new().read_graph_from_raw_input(&input));
+
+        assert!(matches!(result, Err(GraphGatewayError::Semantic { .. })));
+    }
+
+    #[test]
+    fn test_deep_blocks_of_every_kind_fail_before_parsing() {
+        let depth: usize = 20_000;
+        [
+            ("together {\n", "}\n"),
+            ("state S {\n", "}\n"),
+            ("class C {\n", "}\n"),
+            ("alt x\n", "end\n"),
+            ("if (x)\n", "endif\n"),
+            ("while (x)\n", "endwhile\n"),
+            ("fork\n", "end fork\n"),
+        ]
+        .into_iter()
+        .for_each(|(open, close): (&str, &str)| {
+            let input: String = format!(
+                "@startuml\n{}{}@enduml",
+                open.repeat(depth),
+                close.repeat(depth)
+            );
+
+            let result: Result<Graph, GraphGatewayError> =
+                smol::block_on(PlantUmlGraphGateway::new().read_graph_from_raw_input(&input));
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Semantic { .. })),
+                "{open:?} nested {depth} deep"
+            );
+        });
+        assert!(
+            syntax::parse_diagram(&format!("@startuml\n{}@enduml", "(".repeat(depth))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_mangled_input_never_panics() {
+        let source: &str = "@startuml\nskinparam x {\n}\npackage \"P\" {\n  class \"A b\" as A <<E>> {\n    {static} +run(a, b) : int\n    -x\n  }\n}\nA \"1\" *-up-> \"*\" B : has >\nenum C {\n  X\n}\n@enduml\n";
+        let boundaries: Vec<usize> = source
+            .char_indices()
+            .map(|(i, _): (usize, char)| i)
+            .chain([source.len()])
+            .collect();
+        let mangled: Vec<String> = boundaries
+            .iter()
+            .flat_map(|&i: &usize| {
+                let next: usize = source[i..]
+                    .chars()
+                    .next()
+                    .map_or(i, |c: char| i + c.len_utf8());
+                [
+                    source[..i].to_string(),
+                    format!("{}{}", &source[..i], &source[next..]),
+                    format!("{}\"{}", &source[..i], &source[i..]),
+                    format!("{}{{{}", &source[..i], &source[i..]),
+                ]
+            })
+            .collect();
+
+        mangled.iter().for_each(|input: &String| {
+            [
+                PlantUmlGraphGateway::new(),
+                PlantUmlGraphGateway::with_arena(),
+                PlantUmlGraphGateway::new().with_profile(Profile::Strict),
+            ]
+            .iter()
+            .for_each(|gateway: &PlantUmlGraphGateway| {
+                let _ = smol::block_on(gateway.read_graph_from_raw_input(input));
+            });
+            let _ = stream_elements(input).count();
+            let _ = ParsedSource::new(input.clone()).graph();
+        });
+    }
+
     fn find_node_by_label<'a>(graph: &'a Graph, label: &str) -> Option<&'a Node> {
         graph
             .nodes
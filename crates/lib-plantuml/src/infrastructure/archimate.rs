@@ -0,0 +1,104 @@
+//! The vocabulary of PlantUML's Archimate library: element macros such as
+//! `Business_Process(order, "Handle order")` and relation macros such as
+//! `Rel_Serving_Up(api, portal, "serves")`. Elements keep their layer and
+//! element type as data, and relations the Archimate relationship.
+
+use std::collections::HashMap;
+
+use lib_core::entities::{
+    edge::{EdgeDirection, EdgeKind},
+    id::Text,
+    node::NodeKind,
+    value::Value,
+};
+
+/// Data key holding an element's layer (`business`, `application`,
+/// `technology`…)
+pub(crate) const LAYER: &str = "archimate_layer";
+/// Data key holding an element's type within its layer (`process`,
+/// `data_object`…)
+pub(crate) const ELEMENT: &str = "archimate_element";
+/// Data key holding a relation's Archimate relationship (`serving`,
+/// `triggering`…)
+pub(crate) const RELATION: &str = "archimate_relation";
+
+/// What an element macro such as `Application_DataObject` declares
+pub(crate) fn element(keyword: &str) -> Option<(NodeKind, HashMap<Text, Value>)> {
+    let (layer, name): (&str, &str) = keyword.split_once('_')?;
+    let layer: String = layer.to_lowercase();
+    let name: String = snake_case(name);
+
+    let kind: NodeKind = match name.as_str() {
+        "actor" => NodeKind::Actor,
+        "component" | "node" | "device" | "system_software" | "equipment" => NodeKind::Component,
+        "interface" => NodeKind::Interface,
+        "object" | "data_object" | "artifact" | "material" => NodeKind::Entity,
+        _ => NodeKind::Custom(format!("{}_{}", layer, name)),
+    };
+    let data: HashMap<Text, Value> = [
+        (LAYER.into(), Value::String(layer)),
+        (ELEMENT.into(), Value::String(name)),
+    ]
+    .into();
+    Some((kind, data))
+}
+
+/// How a relation macro is drawn
+pub(crate) struct Relation {
+    pub kind: EdgeKind,
+    pub directed: bool,
+    /// Where the target goes
+    pub direction: Option<EdgeDirection>,
+    pub data: HashMap<Text, Value>,
+}
+
+/// What a relation macro such as `Rel_Triggering_Down` draws. A trailing
+/// `Up`, `Down`, `Left` or `Right` places the target; other suffixes, such
+/// as the `r`/`w` of access relations, are ignored.
+pub(crate) fn relation(arrow: &str) -> Option<Relation> {
+    let mut parts: Vec<&str> = arrow.strip_prefix("Rel_")?.split('_').collect();
+    let direction: Option<EdgeDirection> = match parts.last() {
+        Some(&"Up") => Some(EdgeDirection::Up),
+        Some(&"Down") => Some(EdgeDirection::Down),
+        Some(&"Left") => Some(EdgeDirection::Left),
+        Some(&"Right") => Some(EdgeDirection::Right),
+        _ => None,
+    };
+    if direction.is_some() {
+        parts.pop();
+    }
+    let name: String = parts.first()?.to_lowercase();
+
+    let (kind, directed): (EdgeKind, bool) = match name.as_str() {
+        "composition" => (EdgeKind::Composition, true),
+        "aggregation" => (EdgeKind::Aggregation, true),
+        "assignment" | "serving" => (EdgeKind::Association, true),
+        "realization" => (EdgeKind::Realization, true),
+        "specialization" => (EdgeKind::Inheritance, true),
+        "access" | "influence" => (EdgeKind::Dependency, true),
+        "triggering" | "flow" => (EdgeKind::Flow, true),
+        "association" if parts.contains(&"dir") => (EdgeKind::Association, true),
+        "association" => (EdgeKind::Undirected, false),
+        _ => (EdgeKind::Custom(name.clone()), true),
+    };
+    Some(Relation {
+        kind,
+        directed,
+        direction,
+        data: [(RELATION.into(), Value::String(name))].into(),
+    })
+}
+
+/// `DataObject` as `data_object`
+fn snake_case(name: &str) -> String {
+    name.chars().enumerate().fold(
+        String::new(),
+        |mut snake: String, (index, c): (usize, char)| {
+            if c.is_uppercase() && index > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+            snake
+        },
+    )
+}
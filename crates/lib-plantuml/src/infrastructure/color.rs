@@ -0,0 +1,185 @@
+//! Colors given in the source, after an element's name (`class Foo #pink
+//! ##red`) or in an arrow's brackets (`-[#red,dashed]->`), turned into the
+//! element's colors and the `key:value` pairs of the rest of its own style.
+
+use lib_core::entities::style::{Color, Colors};
+
+/// An element's colors and line: `#color` fills it, `#from/to` (or `|`, `-`,
+/// `\`) fills it with a gradient, `##color` draws its border, and
+/// `#back:pink;line:red;text:blue` names each part
+pub(crate) fn element(specs: &str) -> (Colors, Option<String>) {
+    let mut colors: Colors = Colors::default();
+    let pairs: Vec<String> = specs
+        .split_whitespace()
+        .flat_map(|spec: &str| match spec.strip_prefix("##") {
+            Some(border) => border_style(border, &mut colors),
+            None => fill_style(spec.trim_start_matches('#'), &mut colors),
+        })
+        .collect();
+
+    (colors, style(pairs))
+}
+
+/// The color and line of an arrow's brackets, leaving out the parts that are
+/// not about its look
+pub(crate) fn arrow(spec: &str) -> (Colors, Option<String>) {
+    let mut colors: Colors = Colors::default();
+    let mut pairs: Vec<String> = Vec::new();
+    spec.split([',', ';'])
+        .map(str::trim)
+        .for_each(|part: &str| match part.strip_prefix('#') {
+            Some(color) if colors.stroke.is_none() => colors.stroke = Color::parse(color),
+            Some(_) => {}
+            None => pairs.extend(line(part)),
+        });
+
+    (colors, style(pairs))
+}
+
+fn style(pairs: Vec<String>) -> Option<String> {
+    (!pairs.is_empty()).then(|| pairs.join(", "))
+}
+
+fn fill_style(spec: &str, colors: &mut Colors) -> Vec<String> {
+    if spec.contains(':') || spec.starts_with("line.") {
+        return spec
+            .split(';')
+            .filter_map(|part: &str| match part.split_once(':') {
+                Some(("back", color)) => {
+                    colors.fill = Color::parse(color);
+                    None
+                }
+                Some(("line", color)) => {
+                    colors.stroke = Color::parse(color);
+                    None
+                }
+                Some(("text", color)) => {
+                    colors.text = Color::parse(color);
+                    None
+                }
+                Some(_) => None,
+                None => part.strip_prefix("line.").and_then(line),
+            })
+            .collect();
+    }
+    match spec.split_once(['/', '|', '\\', '-']) {
+        Some((from, to)) => {
+            colors.fill = Color::parse(from);
+            colors.gradient = Color::parse(to);
+        }
+        None => colors.fill = Color::parse(spec),
+    }
+    Vec::new()
+}
+
+/// The line of a `##color` border, which may be drawn `##[dashed]color`
+fn border_style(spec: &str, colors: &mut Colors) -> Vec<String> {
+    let (pattern, color): (Option<&str>, &str) = match spec
+        .strip_prefix('[')
+        .and_then(|rest: &str| rest.split_once(']'))
+    {
+        Some((pattern, color)) => (Some(pattern), color),
+        None => (None, spec),
+    };
+    if !color.is_empty() {
+        colors.stroke = Color::parse(color);
+    }
+    pattern.and_then(line).into_iter().collect()
+}
+
+/// The pair a line pattern such as `dashed` stands for
+fn line(pattern: &str) -> Option<String> {
+    match pattern {
+        "dashed" => Some("stroke-dasharray:6 4".to_string()),
+        "dotted" => Some("stroke-dasharray:2 2".to_string()),
+        "bold" => Some("stroke-width:2".to_string()),
+        _ => pattern
+            .strip_prefix("thickness=")
+            .map(|width: &str| format!("stroke-width:{}", width)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn named(name: &str) -> Option<Color> {
+        Some(Color::Named(name.into()))
+    }
+
+    #[test]
+    fn test_reads_element_colors() {
+        assert_eq!(
+            element("#lightblue"),
+            (
+                Colors {
+                    fill: named("lightblue"),
+                    ..Default::default()
+                },
+                None
+            )
+        );
+        assert_eq!(
+            element("#FF0000").0.fill,
+            Some(Color::Rgba([255, 0, 0, 255]))
+        );
+        assert_eq!(
+            element("#red/white").0,
+            Colors {
+                fill: named("red"),
+                gradient: named("white"),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            element("#back:pink;line:red;text:blue;line.dashed"),
+            (
+                Colors {
+                    fill: named("pink"),
+                    stroke: named("red"),
+                    text: named("blue"),
+                    ..Default::default()
+                },
+                Some("stroke-dasharray:6 4".to_string())
+            )
+        );
+        assert_eq!(
+            element("#ccc ##[bold]blue"),
+            (
+                Colors {
+                    fill: Some(Color::Rgba([204, 204, 204, 255])),
+                    stroke: named("blue"),
+                    ..Default::default()
+                },
+                Some("stroke-width:2".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_reads_arrow_styles() {
+        assert_eq!(
+            arrow("#red"),
+            (
+                Colors {
+                    stroke: named("red"),
+                    ..Default::default()
+                },
+                None
+            )
+        );
+        assert_eq!(
+            arrow("#00ff00,dashed,thickness=3"),
+            (
+                Colors {
+                    stroke: Some(Color::Rgba([0, 255, 0, 255])),
+                    ..Default::default()
+                },
+                Some("stroke-dasharray:6 4, stroke-width:3".to_string())
+            )
+        );
+        assert_eq!(arrow("hidden"), (Colors::default(), None));
+    }
+}
@@ -0,0 +1,301 @@
+//! Creole, the markup of labels and notes: `**bold**`, `//italic//`,
+//! `""monospace""`, `__underlined__`, `--struck--` and their `<b>`, `<i>`,
+//! `<u>` and `<s>` tags, lines apart by `\n`, `* item` bullets and
+//! `|= head | cell |` tables. `~` escapes the character after it.
+
+use lib_core::entities::rich_text::{Format, Line, RichText, Span};
+
+/// Where a format keeps whether it is on
+type Flag = fn(&mut Format) -> &mut bool;
+
+/// The markers of each format, written in pairs around the text
+const MARKERS: [(&str, Flag); 5] = [
+    ("**", |format: &mut Format| &mut format.bold),
+    ("//", |format: &mut Format| &mut format.italic),
+    ("\"\"", |format: &mut Format| &mut format.monospace),
+    ("__", |format: &mut Format| &mut format.underline),
+    ("--", |format: &mut Format| &mut format.strike),
+];
+
+/// The tags of each format, opening and closing it
+const TAGS: [(&str, &str, Flag); 4] = [
+    ("<b>", "</b>", |format: &mut Format| &mut format.bold),
+    ("<i>", "</i>", |format: &mut Format| &mut format.italic),
+    ("<u>", "</u>", |format: &mut Format| &mut format.underline),
+    ("<s>", "</s>", |format: &mut Format| &mut format.strike),
+];
+
+/// The text `source` marks up, if it marks anything up
+pub(crate) fn read(source: &str) -> Option<RichText> {
+    let rich: RichText = RichText {
+        lines: source.split("\\n").flat_map(str::lines).map(line).collect(),
+    };
+    let marked: bool = rich.plain() != source
+        || rich.lines.iter().any(|line: &Line| match line {
+            Line::Text(spans) => spans
+                .iter()
+                .any(|span: &Span| span.format != Format::default()),
+            _ => true,
+        });
+
+    marked.then_some(rich)
+}
+
+/// The source marking up `rich`, on one line
+pub(crate) fn write(rich: &RichText) -> String {
+    rich.lines
+        .iter()
+        .map(|line: &Line| match line {
+            Line::Text(spans) => write_spans(spans),
+            Line::Bullet { depth, spans } => {
+                format!("{} {}", "*".repeat(*depth), write_spans(spans))
+            }
+            Line::Row { header, cells } => {
+                let bar: &str = if *header { "|=" } else { "|" };
+                let cells: String = cells
+                    .iter()
+                    .map(|cell: &Vec<Span>| format!("{} {} ", bar, write_spans(cell)))
+                    .collect();
+                format!("{}|", cells)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\\n")
+}
+
+fn line(source: &str) -> Line {
+    let trimmed: &str = source.trim();
+    let stars: usize = trimmed.chars().take_while(|c: &char| *c == '*').count();
+    if stars > 0 && trimmed[stars..].starts_with(' ') {
+        return Line::Bullet {
+            depth: stars,
+            spans: spans(trimmed[stars..].trim_start()),
+        };
+    }
+    match trimmed
+        .strip_prefix('|')
+        .and_then(|row: &str| row.strip_suffix('|'))
+    {
+        Some(row) => {
+            let cells: Vec<&str> = row.split('|').collect();
+            Line::Row {
+                header: cells.iter().any(|cell: &&str| cell.starts_with('=')),
+                cells: cells
+                    .iter()
+                    .map(|cell: &&str| spans(cell.trim_start_matches('=').trim()))
+                    .collect(),
+            }
+        }
+        None => Line::Text(spans(source)),
+    }
+}
+
+/// The runs of text a line's markers and tags format
+fn spans(source: &str) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut format: Format = Format::default();
+    let mut text: String = String::new();
+    let mut rest: &str = source;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(escaped) = rest
+            .strip_prefix('~')
+            .and_then(|after: &str| after.chars().next())
+        {
+            text.push(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+            continue;
+        }
+        let toggle: Option<(usize, Flag)> = MARKERS
+            .iter()
+            .find(|(marker, flag): &&(&str, Flag)| {
+                rest.starts_with(marker)
+                    // A marker opens only if it closes later on the line,
+                    // and `//` right after `:` is part of a URL
+                    && (*flag(&mut format.clone())
+                        || rest[marker.len()..].contains(marker))
+                    && !(*marker == "//" && text.ends_with(':'))
+            })
+            .map(|(marker, flag): &(&str, Flag)| (marker.len(), *flag))
+            .or_else(|| {
+                TAGS.iter()
+                    .find_map(|(open, close, flag): &(&str, &str, Flag)| {
+                        [open, close]
+                            .into_iter()
+                            .find(|tag: &&&str| rest.starts_with(**tag))
+                            .map(|tag: &&str| (tag.len(), *flag))
+                    })
+            });
+
+        match toggle {
+            Some((length, flag)) => {
+                push(&mut spans, &mut text, format);
+                let closing: bool = rest.starts_with("</");
+                let on: &mut bool = flag(&mut format);
+                *on = match rest.starts_with('<') {
+                    true => !closing,
+                    false => !*on,
+                };
+                rest = &rest[length..];
+            }
+            None => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    push(&mut spans, &mut text, format);
+    spans
+}
+
+/// Ends the run of text read so far, if there is any
+fn push(spans: &mut Vec<Span>, text: &mut String, format: Format) {
+    if !text.is_empty() {
+        spans.push(Span {
+            text: std::mem::take(text),
+            format,
+        });
+    }
+}
+
+fn write_spans(spans: &[Span]) -> String {
+    spans
+        .iter()
+        .map(|span: &Span| {
+            let text: String = escape(&span.text);
+            MARKERS
+                .iter()
+                .rev()
+                .fold(
+                    text,
+                    |text: String, (marker, flag): &(&str, Flag)| match *flag(
+                        &mut span.format.clone(),
+                    ) {
+                        true => format!("{}{}{}", marker, text, marker),
+                        false => text,
+                    },
+                )
+        })
+        .collect()
+}
+
+/// Text with `~` before whatever would read as markup
+fn escape(text: &str) -> String {
+    let mut escaped: String = String::new();
+    let mut rest: &str = text;
+    while let Some(c) = rest.chars().next() {
+        let markup: bool = c == '~'
+            || c == '<'
+            || c == '|'
+            || MARKERS
+                .iter()
+                .any(|(marker, _): &(&str, Flag)| rest.starts_with(marker));
+        if markup {
+            escaped.push('~');
+        }
+        escaped.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn span(text: &str, format: Format) -> Span {
+        Span {
+            text: text.to_string(),
+            format,
+        }
+    }
+
+    #[test]
+    fn test_reads_inline_formats() {
+        let bold: Format = Format {
+            bold: true,
+            ..Format::default()
+        };
+        let italic: Format = Format {
+            italic: true,
+            ..Format::default()
+        };
+
+        assert_eq!(
+            read("**Order** //service// <u>now</u>"),
+            Some(RichText {
+                lines: vec![Line::Text(vec![
+                    span("Order", bold),
+                    span(" ", Format::default()),
+                    span("service", italic),
+                    span(" ", Format::default()),
+                    span(
+                        "now",
+                        Format {
+                            underline: true,
+                            ..Format::default()
+                        }
+                    ),
+                ])],
+            })
+        );
+        assert_eq!(read("Order service"), None);
+        assert_eq!(read("a -- b"), None);
+        assert_eq!(read("see http://example.com"), None);
+        assert_eq!(
+            read("~**not bold~**").map(|rich: RichText| rich.plain()),
+            Some("**not bold**".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reads_lines_bullets_and_tables() {
+        let rich: RichText = read("Steps\\n* pay\\n** twice\\n|= a |= b |\\n| 1 | 2 |").unwrap();
+
+        assert_eq!(
+            rich.lines,
+            vec![
+                Line::Text(vec![span("Steps", Format::default())]),
+                Line::Bullet {
+                    depth: 1,
+                    spans: vec![span("pay", Format::default())],
+                },
+                Line::Bullet {
+                    depth: 2,
+                    spans: vec![span("twice", Format::default())],
+                },
+                Line::Row {
+                    header: true,
+                    cells: vec![
+                        vec![span("a", Format::default())],
+                        vec![span("b", Format::default())],
+                    ],
+                },
+                Line::Row {
+                    header: false,
+                    cells: vec![
+                        vec![span("1", Format::default())],
+                        vec![span("2", Format::default())],
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_writes_what_it_reads() {
+        [
+            "**Order** //service//",
+            "Steps\\n* pay\\n** twice",
+            "|= a |= b |\\n| 1 | 2 |",
+            "~**x~** and \"\"code\"\"",
+        ]
+        .into_iter()
+        .for_each(|source: &str| {
+            let rich: RichText = read(source).unwrap();
+            assert_eq!(read(&write(&rich)), Some(rich));
+        });
+    }
+}
@@ -0,0 +1,718 @@
+use std::collections::HashMap;
+
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterError, Quoting},
+    entities::{
+        edge::{Edge, EdgeDirection, EdgeKey, EdgeKind},
+        graph::{Graph, GraphKind, HorizontalPosition, LayoutDirection, VerticalPosition},
+        group::{Group, GroupKind},
+        id::{Id, IdAllocator, Text},
+        member::{Member, MemberKind},
+        node::{Node, NodeKind},
+        sequence::{self, FragmentKind, Operand, Step},
+        value::Value,
+    },
+};
+
+use crate::infrastructure::{activity, creole};
+
+const INDENT: &str = "  ";
+/// Node kinds declared with their own keyword rather than as stereotyped
+/// classes
+const KEYWORDS: [&str; 8] = [
+    "usecase",
+    "object",
+    "map",
+    "annotation",
+    "protocol",
+    "struct",
+    "exception",
+    "metaclass",
+];
+
+/// Writes a graph as PlantUML source, nesting groups as packages and
+/// sorting elements by id so the output is stable; sequence diagrams are
+/// written as participants and messages in order, state diagrams as states
+/// and transitions, and activity steps as the blocks their flows form
+pub fn emit_plantuml(graph: &Graph, options: &EmitOptions) -> Result<String, GraphEmitterError> {
+    let graph: &Graph = &options.prepare(graph);
+    let aliases: HashMap<Id, String> = aliases(graph);
+    let mut lines: Vec<String> = vec!["@startuml".to_string()];
+
+    if let Some(title) = &graph.metadata.title {
+        heading("title", title, &mut lines);
+    }
+    ["header", "footer", "caption"]
+        .into_iter()
+        .for_each(|keyword: &str| {
+            if let Some(text) = graph.metadata.properties.get(keyword) {
+                heading(keyword, text, &mut lines);
+            }
+        });
+    if let Some(legend) = &graph.metadata.legend {
+        let vertical: Option<&str> = match legend.vertical {
+            VerticalPosition::Top => Some("top"),
+            VerticalPosition::Bottom => None,
+        };
+        let horizontal: Option<&str> = match legend.horizontal {
+            HorizontalPosition::Left => Some("left"),
+            HorizontalPosition::Center => None,
+            HorizontalPosition::Right => Some("right"),
+        };
+        lines.push(
+            ["legend"]
+                .into_iter()
+                .chain(vertical)
+                .chain(horizontal)
+                .collect::<Vec<&str>>()
+                .join(" "),
+        );
+        lines.extend(legend.text.lines().map(str::to_string));
+        lines.push("endlegend".to_string());
+    }
+    if graph.kind == GraphKind::Sequence {
+        emit_sequence(graph, options, &aliases, &mut lines);
+        lines.push("@enduml".to_string());
+        return Ok(lines.join("\n") + "\n");
+    }
+    if graph.kind == GraphKind::Flowchart && graph.nodes.values().any(activity::is_step) {
+        let steps: Vec<String> =
+            activity::write(graph, &options.indent(INDENT)).map_err(|message: String| {
+                GraphEmitterError::Unsupported {
+                    target: "plantuml".into(),
+                    message,
+                }
+            })?;
+        lines.extend(steps);
+        lines.push("@enduml".to_string());
+        return Ok(lines.join("\n") + "\n");
+    }
+    match graph.direction {
+        LayoutDirection::LeftToRight | LayoutDirection::RightToLeft => {
+            lines.push("left to right direction".to_string());
+        }
+        LayoutDirection::TopToBottom | LayoutDirection::BottomToTop => {}
+    }
+
+    if graph.kind == GraphKind::State {
+        emit_states(graph, options, &aliases, None, 0, &mut lines);
+        lines.push("@enduml".to_string());
+        return Ok(lines.join("\n") + "\n");
+    }
+
+    emit_scope(graph, options, &aliases, None, 0, &mut lines);
+
+    let mut edges: Vec<&Edge> = graph.edges.values().collect();
+    // Edges go by their ends, and parallel ones in the order of their source
+    let keys: HashMap<Id, EdgeKey> = graph.edge_keys();
+    edges.sort_by(|a: &&Edge, b: &&Edge| keys[&a.id].cmp(&keys[&b.id]));
+    edges.iter().for_each(|edge: &&Edge| {
+        lines.push(relation(edge, &aliases));
+        let mut notes: Vec<&Node> = graph
+            .nodes
+            .values()
+            .filter(|node: &&Node| node.target_edge.as_ref() == Some(&edge.id))
+            .collect();
+        notes.sort_by(|a: &&Node, b: &&Node| a.id.cmp(&b.id));
+        notes
+            .iter()
+            .for_each(|note: &&Node| link_note(note, &mut lines));
+    });
+
+    lines.push("@enduml".to_string());
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Emits the nodes and groups directly inside `parent`
+fn emit_scope(
+    graph: &Graph,
+    options: &EmitOptions,
+    aliases: &HashMap<Id, String>,
+    parent: Option<&Id>,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    let step: String = options.indent(INDENT);
+    let indent: String = step.repeat(depth);
+
+    // Notes on links follow their relation instead
+    let mut nodes: Vec<&Node> = graph
+        .nodes
+        .values()
+        .filter(|node: &&Node| node.parent.as_ref() == parent)
+        .filter(|node: &&Node| {
+            node.target_edge
+                .as_ref()
+                .is_none_or(|edge: &Id| !graph.edges.contains_key(edge))
+        })
+        .collect();
+    options.sort_nodes(&mut nodes);
+    nodes
+        .iter()
+        .for_each(|node: &&Node| emit_node(node, &indent, &step, options, aliases, lines));
+
+    let mut groups: Vec<&Group> = graph
+        .groups
+        .values()
+        .filter(|group: &&Group| group.parent.as_ref() == parent)
+        .collect();
+    options.sort_groups(&mut groups);
+    // The grammar gives packages no alias, so they are named by label alone
+    groups.iter().for_each(|group: &&Group| {
+        let keyword: &str = match group.kind {
+            GroupKind::Package => "package",
+            GroupKind::Boundary => "rectangle",
+            GroupKind::Deployment(kind) => kind.keyword(),
+        };
+        lines.push(format!(
+            "{}{} {} {{",
+            indent,
+            keyword,
+            quote(group.label.as_deref().unwrap_or(&group.id))
+        ));
+        emit_scope(graph, options, aliases, Some(&group.id), depth + 1, lines);
+        lines.push(format!("{}}}", indent));
+    });
+}
+
+fn emit_node(
+    node: &Node,
+    indent: &str,
+    step: &str,
+    options: &EmitOptions,
+    aliases: &HashMap<Id, String>,
+    lines: &mut Vec<String>,
+) {
+    let label: String = match &node.rich_label {
+        Some(rich) => creole::write(rich),
+        None => node.label.as_ref().unwrap_or(&node.id).to_string(),
+    };
+
+    if node.kind == NodeKind::Annotation {
+        lines.push(format!(
+            "{}note {} as {}",
+            indent,
+            quote(&label),
+            alias(aliases, &node.id)
+        ));
+        return;
+    }
+
+    let is_abstract: bool = matches!(node.data.get("abstract"), Some(Value::Bool(true)));
+    let keyword: &str = match &node.kind {
+        NodeKind::Entity if is_abstract => "abstract class",
+        NodeKind::Interface => "interface",
+        NodeKind::Actor => "actor",
+        NodeKind::Component => "component",
+        NodeKind::Database => "database",
+        NodeKind::Enum => "enum",
+        NodeKind::Group => "package",
+        NodeKind::Deployment(kind) => kind.keyword(),
+        NodeKind::Custom(kind) if KEYWORDS.contains(&kind.as_str()) => kind,
+        _ => "class",
+    };
+    let stereotype: Option<&str> = match (&node.kind, node.data.get("stereotype")) {
+        (NodeKind::Custom(kind), _) if KEYWORDS.contains(&kind.as_str()) => None,
+        (NodeKind::Custom(kind), _) => Some(kind),
+        (_, Some(Value::String(stereotype))) => Some(stereotype),
+        _ => None,
+    };
+    let spot: Option<String> = match (node.data.get("spot"), node.data.get("spot_color")) {
+        (Some(Value::String(spot)), Some(Value::String(color))) => {
+            Some(format!("({},{})", spot, color))
+        }
+        (Some(Value::String(spot)), _) => Some(format!("({})", spot)),
+        _ => None,
+    };
+    let stereotype: String = match (spot, stereotype) {
+        (Some(spot), Some(stereotype)) => format!(" << {} {} >>", spot, stereotype),
+        (Some(spot), None) => format!(" << {} >>", spot),
+        (None, Some(stereotype)) => format!(" <<{}>>", stereotype),
+        (None, None) => String::new(),
+    };
+
+    let declaration: String = format!(
+        "{}{} {}{}",
+        indent,
+        keyword,
+        name(&label, &node.id, options, aliases),
+        stereotype
+    );
+    if node.members.is_empty() {
+        lines.push(declaration);
+        return;
+    }
+
+    lines.push(declaration + " {");
+    let assignment: &str = if keyword == "map" { "=>" } else { "=" };
+    node.members.iter().for_each(|member: &Member| {
+        lines.push(format!(
+            "{}{}{}",
+            indent,
+            step,
+            member_text(member, assignment)
+        ));
+    });
+    lines.push(format!("{}}}", indent));
+}
+
+/// The states and composite states directly inside `parent`, then the
+/// transitions whose `[*]` belongs to it, or all the others at the top
+fn emit_states(
+    graph: &Graph,
+    options: &EmitOptions,
+    aliases: &HashMap<Id, String>,
+    parent: Option<&Id>,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    let step: String = options.indent(INDENT);
+    let indent: String = step.repeat(depth);
+
+    let mut nodes: Vec<&Node> = graph
+        .nodes
+        .values()
+        .filter(|node: &&Node| node.parent.as_ref() == parent && !is_pseudo_state(node))
+        .filter(|node: &&Node| {
+            node.target_edge
+                .as_ref()
+                .is_none_or(|edge: &Id| !graph.edges.contains_key(edge))
+        })
+        .collect();
+    options.sort_nodes(&mut nodes);
+    nodes.iter().for_each(|node: &&Node| {
+        if node.kind != NodeKind::Custom("state".to_string()) {
+            return emit_node(node, &indent, &step, options, aliases, lines);
+        }
+        let label: &str = node.label.as_deref().unwrap_or(&node.id);
+        lines.push(format!(
+            "{}state {}",
+            indent,
+            name(label, &node.id, options, aliases)
+        ));
+        ["description", "entry", "exit", "do"]
+            .into_iter()
+            .for_each(|key: &str| {
+                let Some(Value::String(text)) = node.data.get(key) else {
+                    return;
+                };
+                text.lines().for_each(|line: &str| {
+                    lines.push(match key {
+                        "description" => {
+                            format!("{}{} : {}", indent, alias(aliases, &node.id), line)
+                        }
+                        action => format!(
+                            "{}{} : {} / {}",
+                            indent,
+                            alias(aliases, &node.id),
+                            action,
+                            line
+                        ),
+                    });
+                });
+            });
+    });
+
+    let mut groups: Vec<&Group> = graph
+        .groups
+        .values()
+        .filter(|group: &&Group| group.parent.as_ref() == parent)
+        .collect();
+    options.sort_groups(&mut groups);
+    groups.iter().for_each(|group: &&Group| {
+        let label: &str = group.label.as_deref().unwrap_or(&group.id);
+        lines.push(format!(
+            "{}state {} {{",
+            indent,
+            name(label, &group.id, options, aliases)
+        ));
+        emit_states(graph, options, aliases, Some(&group.id), depth + 1, lines);
+        lines.push(format!("{}}}", indent));
+    });
+
+    // A `[*]` is the pseudo-state of the composite state it is written in
+    let scope = |id: &Id| -> Option<&Id> {
+        graph
+            .nodes
+            .get(id)
+            .filter(|node: &&Node| is_pseudo_state(node))
+            .and_then(|node: &Node| node.parent.as_ref())
+    };
+    let mut edges: Vec<&Edge> = graph
+        .edges
+        .values()
+        .filter(|edge: &&Edge| scope(&edge.from).or(scope(&edge.to)) == parent)
+        .collect();
+    let keys: HashMap<Id, EdgeKey> = graph.edge_keys();
+    edges.sort_by(|a: &&Edge, b: &&Edge| keys[&a.id].cmp(&keys[&b.id]));
+    edges.iter().for_each(|edge: &&Edge| {
+        let end = |id: &Id| match graph.nodes.get(id) {
+            Some(node) if is_pseudo_state(node) => "[*]".to_string(),
+            _ => alias(aliases, id),
+        };
+        let arrow: &str = if edge.directed { "-->" } else { "--" };
+        let label: String = edge
+            .label
+            .as_deref()
+            .map(|label: &str| format!(" : {}", label))
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}{} {} {}{}",
+            indent,
+            end(&edge.from),
+            arrow,
+            end(&edge.to),
+            label
+        ));
+    });
+}
+
+/// The `[*]` a transition starts or ends at
+fn is_pseudo_state(node: &Node) -> bool {
+    matches!(&node.kind, NodeKind::Custom(kind) if kind == "start" || kind == "end")
+}
+
+/// Participants, then messages by sequence number inside their fragments
+fn emit_sequence(
+    graph: &Graph,
+    options: &EmitOptions,
+    aliases: &HashMap<Id, String>,
+    lines: &mut Vec<String>,
+) {
+    let mut nodes: Vec<&Node> = graph.nodes.values().collect();
+    options.sort_nodes(&mut nodes);
+    nodes.iter().for_each(|node: &&Node| {
+        let keyword: &str = match &node.kind {
+            NodeKind::Annotation => return emit_node(node, "", "", options, aliases, lines),
+            NodeKind::Actor => "actor",
+            NodeKind::Database => "database",
+            NodeKind::Entity => "entity",
+            NodeKind::Deployment(kind) => kind.keyword(),
+            NodeKind::Custom(kind) if ["boundary", "control"].contains(&kind.as_str()) => kind,
+            _ => "participant",
+        };
+        let label: &str = node.label.as_deref().unwrap_or(&node.id);
+        lines.push(format!(
+            "{} {}",
+            keyword,
+            name(label, &node.id, options, aliases)
+        ));
+    });
+
+    let step: String = options.indent(INDENT);
+    let mut depth: usize = 0;
+    sequence::steps(graph).iter().for_each(|item: &Step| {
+        let indent: String = step.repeat(depth);
+        let line: String = match item {
+            Step::Message(edge) => message(edge, aliases),
+            Step::Activate(participant) => format!("activate {}", alias(aliases, participant)),
+            Step::Deactivate(participant) => format!("deactivate {}", alias(aliases, participant)),
+            Step::Open(fragment) => {
+                depth += 1;
+                let keyword: &str = match fragment.kind {
+                    FragmentKind::Alt => "alt",
+                    FragmentKind::Opt => "opt",
+                    FragmentKind::Loop => "loop",
+                    FragmentKind::Par => "par",
+                    FragmentKind::Break => "break",
+                    FragmentKind::Critical => "critical",
+                    FragmentKind::Group => "group",
+                };
+                let condition: Option<&str> = fragment
+                    .operands
+                    .first()
+                    .and_then(|operand: &Operand| operand.condition.as_deref());
+                condition_line(keyword, condition)
+            }
+            Step::Operand(_, operand) => {
+                let indent: String = step.repeat(depth - 1);
+                lines.push(indent + &condition_line("else", operand.condition.as_deref()));
+                return;
+            }
+            Step::Close(_) => {
+                depth -= 1;
+                lines.push(step.repeat(depth) + "end");
+                return;
+            }
+        };
+        lines.push(indent + &line);
+    });
+}
+
+fn condition_line(keyword: &str, condition: Option<&str>) -> String {
+    match condition {
+        Some(condition) => format!("{} {}", keyword, condition),
+        None => keyword.to_string(),
+    }
+}
+
+/// A message arrow: dashed for replies, with an open head when asynchronous
+fn message(edge: &Edge, aliases: &HashMap<Id, String>) -> String {
+    let line: &str = match edge.data.get("line_style") {
+        Some(Value::String(style)) if style == "dashed" => "--",
+        _ => "-",
+    };
+    let head: &str = match edge.data.get("async") {
+        Some(Value::Bool(true)) => ">>",
+        _ => ">",
+    };
+    let label: String = edge
+        .label
+        .as_deref()
+        .map(|label: &str| format!(" : {}", label))
+        .unwrap_or_default();
+    format!(
+        "{} {}{} {}{}",
+        alias(aliases, &edge.from),
+        line,
+        head,
+        alias(aliases, &edge.to),
+        label
+    )
+}
+
+/// A heading on one line, or on several up to its `end`
+fn heading(keyword: &str, text: &str, lines: &mut Vec<String>) {
+    if !text.contains('\n') {
+        lines.push(format!("{} {}", keyword, text));
+        return;
+    }
+    lines.push(keyword.to_string());
+    lines.extend(text.lines().map(str::to_string));
+    lines.push(format!("end {}", keyword));
+}
+
+fn member_text(member: &Member, assignment: &str) -> String {
+    if member.kind == MemberKind::Separator {
+        let bar: &str = match member.data.get("line") {
+            Some(Value::String(line)) if line == "dotted" => "..",
+            Some(Value::String(line)) if line == "double" => "==",
+            Some(Value::String(line)) if line == "thick" => "__",
+            _ => "--",
+        };
+        return match member.name.is_empty() {
+            true => bar.to_string(),
+            false => format!("{} {} {}", bar, member.name, bar),
+        };
+    }
+    if let Some(Value::String(value)) = member.data.get("value") {
+        return match member.kind {
+            MemberKind::EnumValue => format!("{}({})", member.name, value),
+            _ => format!("{} {} {}", member.name, assignment, value),
+        };
+    }
+
+    let modifiers: String = [
+        (member.is_static(), "{static} "),
+        (member.is_abstract(), "{abstract} "),
+        // A field named with parentheses would read back as a method
+        (
+            member.kind == MemberKind::Field && member.name.contains('('),
+            "{field} ",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(on, modifier): (bool, &str)| on.then_some(modifier))
+    .collect();
+    let visibility: &str = match member.data.get("visibility") {
+        Some(Value::String(symbol)) => symbol,
+        _ => "",
+    };
+    let name: String = match member.kind {
+        MemberKind::Method if !member.name.contains('(') => {
+            let parameters: Vec<&str> = match member.data.get("parameters") {
+                Some(Value::List(parameters)) => parameters
+                    .iter()
+                    .filter_map(|p: &Value| match p {
+                        Value::String(p) => Some(p.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            format!("{}({})", member.name, parameters.join(", "))
+        }
+        _ => member.name.clone(),
+    };
+
+    match &member.type_name {
+        Some(type_name) => format!("{}{}{} : {}", modifiers, visibility, name, type_name),
+        None => format!("{}{}{}", modifiers, visibility, name),
+    }
+}
+
+/// A `note on link`, on one line unless its plain text spans several
+fn link_note(note: &Node, lines: &mut Vec<String>) {
+    let keyword: String = match note.data.get("position") {
+        Some(Value::String(position)) => format!("note {} on link", position),
+        _ => "note on link".to_string(),
+    };
+    let text: String = match &note.rich_label {
+        Some(rich) => creole::write(rich),
+        None => note.label.as_deref().unwrap_or_default().to_string(),
+    };
+    match text.is_empty() || text.contains('\n') {
+        true => {
+            lines.push(keyword);
+            lines.extend(text.lines().map(str::to_string));
+            lines.push("end note".to_string());
+        }
+        false => lines.push(format!("{} : {}", keyword, text)),
+    }
+}
+
+fn relation(edge: &Edge, aliases: &HashMap<Id, String>) -> String {
+    if edge.kind == EdgeKind::Flow {
+        return flow(edge, aliases);
+    }
+    let dashed: bool = matches!(edge.kind, EdgeKind::Dependency | EdgeKind::Realization);
+    let line: char = if dashed { '.' } else { '-' };
+    let (head, tail): (&str, &str) = match &edge.kind {
+        EdgeKind::Inheritance | EdgeKind::Realization => ("", "|>"),
+        EdgeKind::Composition => ("*", ""),
+        EdgeKind::Aggregation => ("o", ""),
+        EdgeKind::Undirected => ("", ""),
+        _ if edge.directed => ("", ">"),
+        _ => ("", ""),
+    };
+
+    let hint: &str = match (edge.layout.hidden, edge.layout.direction) {
+        (true, _) => "[hidden]",
+        (false, Some(EdgeDirection::Up)) => "up",
+        (false, Some(EdgeDirection::Down)) => "down",
+        (false, Some(EdgeDirection::Left)) => "left",
+        (false, Some(EdgeDirection::Right)) => "right",
+        (false, None) => "",
+    };
+    let length: usize = edge.layout.length.unwrap_or(1).max(1) + 1;
+    let arrow: String = if hint.is_empty() {
+        format!("{}{}{}", head, line.to_string().repeat(length), tail)
+    } else {
+        format!(
+            "{}{}{}{}{}",
+            head,
+            line,
+            hint,
+            line.to_string().repeat(length - 1),
+            tail
+        )
+    };
+    // Arrows without a kind of their own, such as `->>`, are kept as written
+    let arrow: String = match &edge.kind {
+        EdgeKind::Custom(text)
+            if hint.is_empty()
+                && text.contains(['-', '.'])
+                && text.chars().all(|c: char| "<>-.|*o".contains(c)) =>
+        {
+            text.clone()
+        }
+        _ => arrow,
+    };
+
+    let cardinality = |text: &Option<Text>| match text {
+        Some(text) if !text.is_empty() => format!(" {}", quote(text)),
+        _ => String::new(),
+    };
+    let label: String = edge
+        .rich_label
+        .as_ref()
+        .map(creole::write)
+        .or_else(|| edge.label.as_deref().map(str::to_string))
+        .map(|label: String| format!(" : {}", label))
+        .unwrap_or_default();
+
+    let end = |id: &str, port: &Option<String>| match port {
+        Some(port) => format!("{}::{}", alias(aliases, id), port),
+        None => alias(aliases, id),
+    };
+
+    format!(
+        "{}{} {}{} {}{}",
+        end(&edge.from, &edge.from_port),
+        cardinality(&edge.from_cardinality),
+        arrow,
+        cardinality(&edge.to_cardinality),
+        end(&edge.to, &edge.to_port),
+        label
+    )
+}
+
+/// A flow, which no arrow spells, as the ArchiMate macro reading back as one
+fn flow(edge: &Edge, aliases: &HashMap<Id, String>) -> String {
+    let direction: &str = match edge.layout.direction {
+        Some(EdgeDirection::Up) => "_Up",
+        Some(EdgeDirection::Down) => "_Down",
+        Some(EdgeDirection::Left) => "_Left",
+        Some(EdgeDirection::Right) => "_Right",
+        None => "",
+    };
+    let label: String = edge
+        .label
+        .as_deref()
+        .map(|label: &str| format!(", {}", quote(label)))
+        .unwrap_or_default();
+    format!(
+        "Rel_Flow{}({}, {}{})",
+        direction,
+        alias(aliases, &edge.from),
+        alias(aliases, &edge.to),
+        label
+    )
+}
+
+/// Each element's alias, where ids spelling the same alias, as `a-b` and
+/// `a b` do, take the first free `_2`, `_3`… suffix in id order so they are
+/// not merged on reading back; ids already valid as aliases keep theirs
+fn aliases(graph: &Graph) -> HashMap<Id, String> {
+    let mut ids: Vec<&Id> = graph.nodes.keys().chain(graph.groups.keys()).collect();
+    ids.sort();
+    let (valid, others): (Vec<&Id>, Vec<&Id>) = ids
+        .into_iter()
+        .partition(|id: &&Id| sanitize(id) == id.as_str());
+    let mut allocator: IdAllocator = IdAllocator::with_taken(valid.iter().copied());
+    valid
+        .iter()
+        .map(|id: &&Id| ((*id).clone(), id.to_string()))
+        .chain(
+            others
+                .iter()
+                .map(|id: &&Id| ((*id).clone(), allocator.claim(&sanitize(id)).to_string())),
+        )
+        .collect()
+}
+
+fn alias(aliases: &HashMap<Id, String>, id: &str) -> String {
+    aliases.get(id).cloned().unwrap_or_else(|| sanitize(id))
+}
+
+/// PlantUML aliases only allow ASCII letters, digits and underscores, with
+/// dots between them
+fn sanitize(id: &str) -> String {
+    let chars: Vec<char> = id.chars().collect();
+    let word = |c: Option<&char>| -> bool {
+        c.is_some_and(|c: &char| c.is_ascii_alphanumeric() || *c == '_')
+    };
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, c): (usize, &char)| match c {
+            '.' if i > 0 && word(chars.get(i - 1)) && word(chars.get(i + 1)) => '.',
+            c if word(Some(c)) => *c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// How a declaration names an element: `"Label" as Alias`, or just the alias
+/// when the options allow it and it reads the same
+fn name(label: &str, id: &str, options: &EmitOptions, aliases: &HashMap<Id, String>) -> String {
+    let alias: String = alias(aliases, id);
+    match options.quoting {
+        Quoting::AsNeeded if label == alias => alias,
+        _ => format!("{} as {}", quote(label), alias),
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\""))
+}
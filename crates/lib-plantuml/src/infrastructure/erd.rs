@@ -0,0 +1,80 @@
+//! The vocabulary of entity relationship diagrams: crow's foot arrows such as
+//! `||--o{`, whose ends tell how many entities of each side take part. A
+//! diagram using any of them is read as an entity relationship diagram.
+
+use std::collections::HashMap;
+
+use lib_core::entities::{id::Text, value::Value};
+
+use crate::infrastructure::models::{
+    ast_arena::{ArenaNode, AstArena},
+    ast_node::AstNode,
+};
+
+/// How many entities of each side a crow's foot arrow relates
+pub(crate) struct Relationship {
+    pub from: &'static str,
+    pub to: &'static str,
+    /// Drawn dotted, for a relationship that is not identifying
+    pub dashed: bool,
+}
+
+impl Relationship {
+    /// The edge data drawing the relationship's line
+    pub(crate) fn data(&self) -> HashMap<Text, Value> {
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        if self.dashed {
+            data.insert("line_style".into(), Value::String("dashed".to_string()));
+        }
+        data
+    }
+}
+
+/// What a crow's foot arrow such as `}o..||` relates, if it is one
+pub(crate) fn relationship(arrow: &str) -> Option<Relationship> {
+    if arrow.len() != 6 || !arrow.is_ascii() {
+        return None;
+    }
+    let (from, line, to): (&str, &str, &str) = (&arrow[..2], &arrow[2..4], &arrow[4..]);
+    Some(Relationship {
+        from: cardinality(from)?,
+        to: cardinality(&to.chars().rev().collect::<String>())?,
+        dashed: match line {
+            "--" => false,
+            ".." => true,
+            _ => return None,
+        },
+    })
+}
+
+/// The cardinality an end such as `}o` stands for, read from the side of
+/// the entity it touches
+fn cardinality(end: &str) -> Option<&'static str> {
+    match end.replace('{', "}").as_str() {
+        "||" => Some("1"),
+        "|o" => Some("0..1"),
+        "}o" => Some("0..*"),
+        "}|" => Some("1..*"),
+        _ => None,
+    }
+}
+
+/// Whether a statement only belongs in an entity relationship diagram
+pub(crate) fn is_entity_relationship(node: &AstNode) -> bool {
+    match node {
+        AstNode::Relation { arrow, .. } => relationship(arrow).is_some(),
+        AstNode::Package { children, .. } | AstNode::Together { children } => {
+            children.iter().any(is_entity_relationship)
+        }
+        _ => false,
+    }
+}
+
+/// Whether any statement of an arena only belongs in an entity relationship
+/// diagram
+pub(crate) fn is_entity_relationship_arena(arena: &AstArena) -> bool {
+    (0..arena.len()).any(|index: usize| match arena.get(index) {
+        ArenaNode::Relation { arrow, .. } => relationship(arrow).is_some(),
+        _ => false,
+    })
+}
@@ -0,0 +1,594 @@
+use lib_core::adapters::graph_gateway::GraphGatewayError;
+use pest::iterators::{Pair, Pairs};
+
+use crate::infrastructure::{
+    parser::Rule,
+    syntax::{self, Span},
+};
+
+const INDENT: &str = "  ";
+const VISIBILITY: [char; 4] = ['+', '-', '#', '~'];
+/// First words of the lines opening a fragment operand or activity branch
+const OPERANDS: [&str; 12] = [
+    "alt", "opt", "loop", "par", "break", "critical", "group", "else", "if", "elseif", "while",
+    "fork",
+];
+
+/// Reprints a PlantUML source with one statement per line, packages, class
+/// bodies, composite states, fragments and activity blocks indented and single spaces around arrows and keywords. Comments and single blank
+/// lines between statements are kept.
+pub fn format_source(input: &str) -> Result<String, GraphGatewayError> {
+    let diagram: Pair<Rule> = syntax::parse_diagram(input)?;
+    let mut printer: Printer = Printer {
+        input,
+        lines: Vec::new(),
+        cursor: 0,
+    };
+
+    printer.trivia(syntax::skip_trivia(input, 0), 0);
+    printer.lines.push("@startuml".to_string());
+    printer.cursor += "@startuml".len();
+
+    printer.statements(diagram.into_inner(), 0);
+
+    printer.trivia(syntax::skip_trivia(input, printer.cursor), 0);
+    printer.lines.push("@enduml".to_string());
+    printer.cursor += "@enduml".len();
+    printer.trivia(input.len(), 0);
+
+    Ok(printer.lines.join("\n") + "\n")
+}
+
+struct Printer<'a> {
+    input: &'a str,
+    lines: Vec<String>,
+    /// End of the last printed token
+    cursor: usize,
+}
+
+impl Printer<'_> {
+    fn statements<'p>(&mut self, pairs: impl Iterator<Item = Pair<'p, Rule>>, depth: usize) {
+        pairs.for_each(|pair: Pair<Rule>| {
+            let Span { start, end } = syntax::statement_span(&pair);
+            let indent: String = INDENT.repeat(depth);
+
+            match pair.as_rule() {
+                Rule::definition => {
+                    self.trivia(start, depth);
+                    let (header, body): (Vec<Pair<Rule>>, Vec<Pair<Rule>>) =
+                        pair.into_inner().partition(|p: &Pair<Rule>| {
+                            !matches!(p.as_rule(), Rule::class_body | Rule::enum_body)
+                        });
+                    // After the keyword and name, an identifier is the alias;
+                    // type parameters stay next to the name
+                    let line: String = header.iter().enumerate().fold(
+                        String::new(),
+                        |line: String, (index, p): (usize, &Pair<Rule>)| match p.as_rule() {
+                            Rule::generics => line + p.as_str(),
+                            Rule::identifier if index > 1 => line + " as " + p.as_str(),
+                            _ if index == 0 => p.as_str().to_string(),
+                            _ => line + " " + p.as_str(),
+                        },
+                    );
+
+                    match body.into_iter().next() {
+                        Some(body) => {
+                            self.lines.push(format!("{}{} {{", indent, line));
+                            self.cursor = body.as_span().start() + 1;
+                            self.members(body.into_inner(), depth + 1);
+                            self.trivia(end - 1, depth + 1);
+                            self.lines.push(format!("{}}}", indent));
+                        }
+                        None => self.lines.push(indent + &line),
+                    }
+                    self.cursor = end;
+                }
+                Rule::relation => {
+                    self.trivia(start, depth);
+                    let line: String = pair
+                        .into_inner()
+                        .map(|p: Pair<Rule>| match p.as_rule() {
+                            Rule::label => format!(": {}", p.as_str()),
+                            _ => p.as_str().to_string(),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" ");
+                    self.lines.push(indent + &line);
+                    self.cursor = end;
+                }
+                Rule::archimate_element | Rule::archimate_relation => {
+                    self.trivia(start, depth);
+                    let parts: Vec<&str> =
+                        pair.into_inner().map(|p: Pair<Rule>| p.as_str()).collect();
+                    let line: String = match parts.split_first() {
+                        Some((name, arguments)) => format!("{}({})", name, arguments.join(", ")),
+                        None => String::new(),
+                    };
+                    self.lines.push(indent + &line);
+                    self.cursor = end;
+                }
+                Rule::package => {
+                    self.trivia(start, depth);
+                    let mut inner: Pairs<Rule> = pair.into_inner();
+                    let (Some(keyword), Some(name)) = (inner.next(), inner.next()) else {
+                        return;
+                    };
+                    self.lines.push(format!(
+                        "{}{} {} {{",
+                        indent,
+                        keyword.as_str(),
+                        name.as_str()
+                    ));
+                    // Past the opening brace
+                    self.cursor = syntax::skip_trivia(self.input, name.as_span().end()) + 1;
+
+                    self.statements(inner, depth + 1);
+
+                    self.trivia(end - 1, depth + 1);
+                    self.lines.push(format!("{}}}", indent));
+                    self.cursor = end;
+                }
+                Rule::together => {
+                    self.trivia(start, depth);
+                    let mut inner: Pairs<Rule> = pair.into_inner();
+                    let Some(keyword) = inner.next() else {
+                        return;
+                    };
+                    self.lines
+                        .push(format!("{}{} {{", indent, keyword.as_str()));
+                    // Past the opening brace
+                    self.cursor = syntax::skip_trivia(self.input, keyword.as_span().end()) + 1;
+
+                    self.statements(inner, depth + 1);
+
+                    self.trivia(end - 1, depth + 1);
+                    self.lines.push(format!("{}}}", indent));
+                    self.cursor = end;
+                }
+                Rule::state => {
+                    self.trivia(start, depth);
+                    let (header, body): (Vec<Pair<Rule>>, Vec<Pair<Rule>>) = pair
+                        .into_inner()
+                        .partition(|p: &Pair<Rule>| p.as_rule() != Rule::state_body);
+                    let line: String = header.iter().enumerate().fold(
+                        String::new(),
+                        |line: String, (position, p): (usize, &Pair<Rule>)| match position {
+                            0 => p.as_str().to_string(),
+                            1 => format!("{} {}", line, p.as_str()),
+                            _ if p.as_rule() == Rule::label => format!("{} : {}", line, p.as_str()),
+                            _ => format!("{} as {}", line, p.as_str()),
+                        },
+                    );
+
+                    match body.into_iter().next() {
+                        Some(body) => {
+                            self.lines.push(format!("{}{} {{", indent, line));
+                            self.cursor = body.as_span().start() + 1;
+                            self.statements(body.into_inner(), depth + 1);
+                            self.trivia(end - 1, depth + 1);
+                            self.lines.push(format!("{}}}", indent));
+                        }
+                        None => self.lines.push(indent + &line),
+                    }
+                    self.cursor = end;
+                }
+                Rule::state_description => {
+                    self.trivia(start, depth);
+                    let parts: Vec<&str> =
+                        pair.into_inner().map(|p: Pair<Rule>| p.as_str()).collect();
+                    self.lines.push(indent + &parts.join(" : "));
+                    self.cursor = end;
+                }
+                Rule::fragment => {
+                    self.trivia(start, depth);
+                    pair.into_inner()
+                        .for_each(|part: Pair<Rule>| match part.as_rule() {
+                            Rule::fragment_header | Rule::fragment_else | Rule::fragment_end => {
+                                if part.as_rule() != Rule::fragment_header {
+                                    self.trivia(part.as_span().start(), depth + 1);
+                                }
+                                let words: Vec<&str> = part.as_str().split_whitespace().collect();
+                                self.lines.push(INDENT.repeat(depth) + &words.join(" "));
+                                self.cursor = part.as_span().end();
+                            }
+                            _ => self.statements(std::iter::once(part), depth + 1),
+                        });
+                    self.cursor = end;
+                }
+                Rule::activity_if | Rule::activity_while | Rule::activity_fork => {
+                    self.trivia(start, depth);
+                    pair.into_inner()
+                        .for_each(|part: Pair<Rule>| match part.as_rule() {
+                            Rule::if_header
+                            | Rule::elseif_header
+                            | Rule::else_header
+                            | Rule::endif
+                            | Rule::while_header
+                            | Rule::endwhile
+                            | Rule::fork_header
+                            | Rule::fork_again
+                            | Rule::end_fork => {
+                                self.trivia(part.as_span().start(), depth + 1);
+                                let words: Vec<&str> = part.as_str().split_whitespace().collect();
+                                self.lines.push(INDENT.repeat(depth) + &words.join(" "));
+                                self.cursor = part.as_span().end();
+                            }
+                            _ => self.statements(std::iter::once(part), depth + 1),
+                        });
+                    self.cursor = end;
+                }
+                Rule::shorthand => {
+                    self.trivia(start, depth);
+                    let parts: Vec<&str> =
+                        pair.into_inner().map(|p: Pair<Rule>| p.as_str()).collect();
+                    self.lines.push(indent + &parts.join(" as "));
+                    self.cursor = end;
+                }
+                Rule::terminal | Rule::action => {
+                    self.trivia(start, depth);
+                    self.lines.push(indent + pair.as_str().trim());
+                    self.cursor = end;
+                }
+                Rule::association_class => {
+                    self.trivia(start, depth);
+                    let names: Vec<&str> = pair
+                        .clone()
+                        .into_inner()
+                        .map(|p: Pair<Rule>| p.as_str())
+                        .collect();
+                    let line: String = match (pair.as_str().starts_with('('), names.as_slice()) {
+                        (true, [left, right, class]) => {
+                            format!("({}, {}) .. {}", left, right, class)
+                        }
+                        (false, [class, left, right]) => {
+                            format!("{} .. ({}, {})", class, left, right)
+                        }
+                        _ => pair.as_str().to_string(),
+                    };
+                    self.lines.push(indent + &line);
+                    self.cursor = end;
+                }
+                Rule::activation => {
+                    self.trivia(start, depth);
+                    let parts: Vec<&str> =
+                        pair.into_inner().map(|p: Pair<Rule>| p.as_str()).collect();
+                    self.lines.push(indent + &parts.join(" "));
+                    self.cursor = end;
+                }
+                Rule::direction => {
+                    self.trivia(start, depth);
+                    let words: Vec<&str> = pair.as_str().split_whitespace().collect();
+                    self.lines.push(indent + &words.join(" "));
+                    self.cursor = end;
+                }
+                Rule::heading
+                | Rule::legend
+                | Rule::link_note
+                | Rule::visibility_command
+                | Rule::directive => {
+                    self.trivia(start, depth);
+                    let text: &str = pair.as_str().trim_end();
+                    self.lines.push(indent + text);
+                    self.cursor = start + text.len();
+                }
+                _ => {}
+            }
+        });
+    }
+
+    /// One member per line; when some members show a visibility marker the
+    /// others are padded so all names line up
+    fn members(&mut self, pairs: Pairs<Rule>, depth: usize) {
+        let members: Vec<Pair<Rule>> = pairs.collect();
+        let aligned: bool = members.iter().any(|member: &Pair<Rule>| {
+            member.as_rule() == Rule::member && member.as_str().starts_with(VISIBILITY)
+        });
+
+        members.iter().for_each(|member: &Pair<Rule>| {
+            let text: &str = member.as_str().trim_end();
+            self.trivia(member.as_span().start(), depth);
+
+            if member.as_rule() == Rule::inner_class {
+                // The class is printed as any other, its visibility kept
+                // in front of its keyword
+                let first: usize = self.lines.len();
+                let mut inner: Pairs<Rule> = member.clone().into_inner();
+                let visibility: Option<&str> = text.starts_with(VISIBILITY).then(|| &text[..1]);
+                if visibility.is_some() {
+                    inner.next();
+                }
+                self.statements(inner, depth);
+                if let (Some(visibility), Some(line)) = (visibility, self.lines.get_mut(first)) {
+                    line.insert_str(INDENT.len() * depth, visibility);
+                }
+                return;
+            }
+
+            let line: String = match text.strip_prefix(VISIBILITY) {
+                _ if member.as_rule() == Rule::separator => text.to_string(),
+                Some(rest) => format!("{}{}", &text[..1], rest.trim_start()),
+                None if aligned => format!(" {}", text),
+                None => text.to_string(),
+            };
+            self.lines.push(INDENT.repeat(depth) + &line);
+            self.cursor = member.as_span().start() + text.len();
+        });
+    }
+
+    /// Prints the comments between the cursor and `until`. A comment on the
+    /// line of the previous token stays on that line, and any run of blank
+    /// lines becomes a single one.
+    fn trivia(&mut self, until: usize, depth: usize) {
+        let gap: &str = &self.input[self.cursor..until];
+        self.cursor = until;
+        let parts: Vec<&str> = gap.split('\n').collect();
+        // Only whitespace can follow the last line break
+        let Some((first, lines)) = parts[..parts.len() - 1].split_first() else {
+            return;
+        };
+
+        let trailing: &str = first.trim();
+        if !trailing.is_empty() {
+            match self.lines.last_mut() {
+                Some(line) => {
+                    line.push(' ');
+                    line.push_str(trailing);
+                }
+                None => self.lines.push(trailing.to_string()),
+            }
+        }
+
+        let mut blank: bool = false;
+        lines.iter().for_each(|line: &&str| {
+            let comment: &str = line.trim();
+            if comment.is_empty() {
+                blank = true;
+                return;
+            }
+            self.blank_line(blank);
+            blank = false;
+            self.lines.push(INDENT.repeat(depth) + comment);
+        });
+        // No blank line before the end of a block
+        if !closes_block(&self.input[until..]) {
+            self.blank_line(blank);
+        }
+    }
+
+    /// Blank lines are dropped at the start of a block
+    fn blank_line(&mut self, blank: bool) {
+        let opens_block: bool = self.lines.last().is_none_or(|line: &String| {
+            line.is_empty()
+                || line.ends_with('{')
+                || line == "@startuml"
+                || line
+                    .split_whitespace()
+                    .next()
+                    .is_some_and(|word: &str| OPERANDS.contains(&word))
+        });
+        if blank && !opens_block {
+            self.lines.push(String::new());
+        }
+    }
+}
+
+/// Whether a block, or a fragment operand or activity branch, ends where
+/// `rest` starts
+fn closes_block(rest: &str) -> bool {
+    let keyword = |keyword: &str| -> bool {
+        rest.strip_prefix(keyword)
+            .is_some_and(|after: &str| !after.starts_with(|c: char| c.is_ascii_alphanumeric()))
+    };
+    rest.starts_with(['}', '@'])
+        || rest.starts_with("fork again")
+        || ["end", "else", "elseif", "endif", "endwhile"]
+            .into_iter()
+            .any(keyword)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_normalizes_spacing_and_indentation() {
+        let input: &str = "' header\n@startuml\n  class   \"User\"   as U   ' the user\npackage \"Core\"{\nclass Profile\n\n\nU-->Profile:owns\n\n}\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "' header\n@startuml\nclass \"User\" as U ' the user\npackage \"Core\" {\n  class Profile\n\n  U --> Profile : owns\n}\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_aligns_member_visibility_markers() {
+        let input: &str = "@startuml\nclass User{\n+  name : String\n  id\n\n\n     ' actions\n  #rename(to)   \n}\nclass Empty {\n}\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nclass User {\n  +name : String\n   id\n\n  ' actions\n  #rename(to)\n}\nclass Empty {\n}\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_formats_enum_bodies() {
+        let input: &str = "@startuml\nenum  Status{\nACTIVE,  INACTIVE\n    PAUSED\n}\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nenum Status {\n  ACTIVE,  INACTIVE\n  PAUSED\n}\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_comment_lines_and_is_stable() {
+        let input: &str = "@startuml\n\n' the actors\nactor Admin\n  ' who owns what\nAdmin -- User\n' trailing\n@enduml\n";
+        let formatted: String = format_source(input).unwrap();
+
+        assert_eq!(
+            formatted,
+            "@startuml\n' the actors\nactor Admin\n' who owns what\nAdmin -- User\n' trailing\n@enduml\n"
+        );
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_keeps_directives_as_written() {
+        let input: &str = "@startuml\n  skinparam  shadowing false\nskinparam class {\n  BackgroundColor White\n}\npackage \"Core\" {\nhide empty members\n}\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nskinparam  shadowing false\nskinparam class {\n  BackgroundColor White\n}\npackage \"Core\" {\n  hide empty members\n}\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_indents_sequence_fragments() {
+        let input: &str = "@startuml\nparticipant  Shop\nAlice->Shop : order\nactivate   Shop\nalt   in stock\n\nShop-->Alice : ok\nloop retries\nShop->>Bank\nend\n' nothing left\nelse\nShop-->Alice : sorry\n\nend\ndeactivate Shop\n@enduml";
+        let formatted: String = format_source(input).unwrap();
+
+        assert_eq!(
+            formatted,
+            "@startuml\nparticipant Shop\nAlice -> Shop : order\nactivate Shop\nalt in stock\n  Shop --> Alice : ok\n  loop retries\n    Shop ->> Bank\n  end\n  ' nothing left\nelse\n  Shop --> Alice : sorry\nend\ndeactivate Shop\n@enduml\n"
+        );
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_indents_composite_states() {
+        let input: &str = "@startuml\n[*]-->Idle\nstate   \"Processing order\"   as Busy{\n[*] --> Checking\n\nChecking-->[*]:valid\n}\nstate Idle:waiting\nBusy  :  entry / start timer\n@enduml";
+        let formatted: String = format_source(input).unwrap();
+
+        assert_eq!(
+            formatted,
+            "@startuml\n[*] --> Idle\nstate \"Processing order\" as Busy {\n  [*] --> Checking\n\n  Checking --> [*] : valid\n}\nstate Idle : waiting\nBusy : entry / start timer\n@enduml\n"
+        );
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_indents_activity_blocks() {
+        let input: &str = "@startuml\nstart\n:Take order;\nif (paid?)   then (yes)\n\nfork\n:Pack;\nfork   again\n:Invoice;\nend fork\nelse (no)\n' give up\nstop\nendif\nwhile (items left?) is (yes)\n:Ship;\n\nendwhile (done)\nstop\n@enduml";
+        let formatted: String = format_source(input).unwrap();
+
+        assert_eq!(
+            formatted,
+            "@startuml\nstart\n:Take order;\nif (paid?) then (yes)\n  fork\n    :Pack;\n  fork again\n    :Invoice;\n  end fork\nelse (no)\n  ' give up\n  stop\nendif\nwhile (items left?) is (yes)\n  :Ship;\nendwhile (done)\nstop\n@enduml\n"
+        );
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_keeps_use_case_names_and_boundaries() {
+        let input: &str = "@startuml\nactor   :Customer:   as C\nrectangle \"Shop\"{\n(Log in)  as  UC1\n}\nC->(Log in)\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nactor :Customer: as C\nrectangle \"Shop\" {\n  (Log in) as UC1\n}\nC -> (Log in)\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_normalizes_directions() {
+        let input: &str = "@startuml\n  left   to right\tdirection\nA-->B\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nleft to right direction\nA --> B\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_colors_and_arrow_styles() {
+        let input: &str =
+            "@startuml\nclass  \"Foo\"   as F  #pink ##red\nF  -[#blue,dashed]->B\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nclass \"Foo\" as F #pink ##red\nF -[#blue,dashed]-> B\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_spaces_relation_cardinalities() {
+        let input: &str = "@startuml\nUser\"1\"--  \"0..*\" Order:places\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nUser \"1\" -- \"0..*\" Order : places\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_type_parameters_next_to_the_name() {
+        let input: &str = "@startuml\nclass   Map<K, V>   as M {\n+get(key : K) : V\n}\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nclass Map<K, V> as M {\n  +get(key : K) : V\n}\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_indents_together_blocks() {
+        let input: &str = "@startuml\ntogether{\nclass A\n' side by side\nclass B\n}\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\ntogether {\n  class A\n  ' side by side\n  class B\n}\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_spaces_association_classes() {
+        let input: &str =
+            "@startuml\n(Student,Course)..Enrollment\nGrade ..( Student , Course )\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\n(Student, Course) .. Enrollment\nGrade .. (Student, Course)\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_headings_as_written() {
+        let input: &str = "@startuml\ntitle   Orders ' shown on top\n  header\nDraft\n  end header\nclass A\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\ntitle   Orders ' shown on top\nheader\nDraft\n  end header\nclass A\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_entity_key_separators() {
+        let input: &str = "@startuml\nentity Customer {\n* id : number\n   --\n+name : text\n}\nCustomer||--o{Order\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nentity Customer {\n   * id : number\n  --\n  +name : text\n}\nCustomer ||--o{ Order\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_indents_nested_classes() {
+        let input: &str = "@startuml\nclass Outer {\n+ name : String\n+class   Inner {\n-id : int\n}\nclass Outer::Other\n}\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nclass Outer {\n  +name : String\n  +class Inner {\n    -id : int\n  }\n  class Outer::Other\n}\n@enduml\n"
+        );
+    }
+
+    #[test]
+    fn test_normalizes_archimate_macros() {
+        let input: &str = "@startuml\nBusiness_Role( clerk ,\"Clerk\")\nRel_Assignment_Up(clerk,desk , \"staffs\")\n@enduml";
+
+        assert_eq!(
+            format_source(input).unwrap(),
+            "@startuml\nBusiness_Role(clerk, \"Clerk\")\nRel_Assignment_Up(clerk, desk, \"staffs\")\n@enduml\n"
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use std::iter;
+
+use lib_core::{adapters::graph_gateway::GraphGatewayError, entities::graph::Graph};
+use pest::{Parser, iterators::Pair};
+
+use crate::infrastructure::{
+    limits::Limits,
+    models::ast_node::AstNode,
+    parser::{self, PlantUmlParseError, PlantUmlParser, Rule},
+    syntax::{self, Reference, SourceIndex, Span, Symbol},
+    transformer::GraphBuilder,
+};
+
+/// Replaces the text in `span` (byte offsets into the current text)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub text: String,
+}
+
+/// Why an edit could not be applied, leaving the text as it was
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextEditError {
+    /// The span ends before it starts or past the end of the text
+    OutOfBounds { span: Span, len: usize },
+    /// An end of the span falls inside a character
+    NotCharBoundary { offset: usize },
+}
+
+/// A top-level statement with everything derived from it
+#[derive(Debug, Clone)]
+struct Statement {
+    span: Span,
+    node: Option<AstNode<'static>>,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+}
+
+impl Statement {
+    fn shift(&mut self, delta: isize) {
+        self.span = self.span.shifted(delta);
+        self.symbols
+            .iter_mut()
+            .for_each(|symbol: &mut Symbol| symbol.shift(delta));
+        self.references
+            .iter_mut()
+            .for_each(|reference: &mut Reference| reference.span = reference.span.shifted(delta));
+    }
+}
+
+/// A PlantUML source kept parsed across edits. Only the top-level statements
+/// an edit touches are parsed again; the rest are moved along. While the text
+/// does not parse, the statements of the last version that did are kept.
+#[derive(Debug, Clone)]
+pub struct ParsedSource {
+    text: String,
+    statements: Vec<Statement>,
+    /// Between `@startuml` and `@enduml`
+    body: Span,
+    error: Option<GraphGatewayError>,
+}
+
+impl ParsedSource {
+    pub fn new(text: String) -> Self {
+        let mut source: ParsedSource = Self {
+            text,
+            statements: Vec::new(),
+            body: Span { start: 0, end: 0 },
+            error: None,
+        };
+        source.parse_all();
+        source
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn error(&self) -> Option<&GraphGatewayError> {
+        self.error.as_ref()
+    }
+
+    pub fn apply(&mut self, edit: &TextEdit) -> Result<(), TextEditError> {
+        let Span { start, end } = edit.span;
+        if start > end || end > self.text.len() {
+            return Err(TextEditError::OutOfBounds {
+                span: edit.span,
+                len: self.text.len(),
+            });
+        }
+        if let Some(offset) = [start, end]
+            .into_iter()
+            .find(|offset: &usize| !self.text.is_char_boundary(*offset))
+        {
+            return Err(TextEditError::NotCharBoundary { offset });
+        }
+        self.text.replace_range(start..end, &edit.text);
+
+        let reparsed: bool = self.error.is_none()
+            && self.body.start <= start
+            && end <= self.body.end
+            && self.reparse(edit);
+        if !reparsed {
+            self.parse_all();
+        }
+        Ok(())
+    }
+
+    pub fn index(&self) -> SourceIndex {
+        SourceIndex {
+            symbols: self
+                .statements
+                .iter()
+                .flat_map(|statement: &Statement| statement.symbols.iter().cloned())
+                .collect(),
+            references: self
+                .statements
+                .iter()
+                .flat_map(|statement: &Statement| statement.references.iter().cloned())
+                .collect(),
+        }
+    }
+
+    /// Builds the graph from the parsed statements, without parsing again
+    pub fn graph(&self) -> Graph {
+        GraphBuilder::new().build(
+            self.statements
+                .iter()
+                .filter_map(|statement: &Statement| statement.node.clone())
+                .collect(),
+        )
+    }
+
+    fn parse_all(&mut self) {
+        let diagram: Pair<Rule> = match syntax::parse_diagram(&self.text) {
+            Ok(diagram) => diagram,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+
+        self.statements = match diagram
+            .into_inner()
+            .filter(|pair: &Pair<Rule>| pair.as_rule() != Rule::EOI)
+            .map(statement)
+            .collect()
+        {
+            Ok(statements) => statements,
+            Err(e) => {
+                self.error = Some(e.into());
+                return;
+            }
+        };
+        let start: usize = syntax::skip_trivia(&self.text, 0) + "@startuml".len();
+        let last: usize = self
+            .statements
+            .last()
+            .map_or(start, |statement: &Statement| statement.span.end);
+        self.body = Span {
+            start,
+            end: syntax::skip_trivia(&self.text, last),
+        };
+        self.error = None;
+    }
+
+    /// Parses the statements touching the edit, from the end of the statement
+    /// before them to the start of the one after, and splices them in. Returns
+    /// false when that stretch does not parse on its own.
+    fn reparse(&mut self, edit: &TextEdit) -> bool {
+        let Span { start, end } = edit.span;
+        let delta: isize = edit.text.len() as isize - (end - start) as isize;
+
+        // Statements touching the edited range are replaced
+        let first: usize = self
+            .statements
+            .partition_point(|statement: &Statement| statement.span.end < start);
+        let last: usize = self
+            .statements
+            .partition_point(|statement: &Statement| statement.span.start <= end);
+        let from: usize = match first {
+            0 => self.body.start,
+            i => self.statements[i - 1].span.end,
+        };
+        let to: usize = self
+            .statements
+            .get(last)
+            .map_or(self.body.end, |statement: &Statement| statement.span.start)
+            .saturating_add_signed(delta);
+
+        if Limits::default()
+            .check_source(&self.text[from..to])
+            .is_err()
+        {
+            return false;
+        }
+        let Ok(mut pairs) = PlantUmlParser::parse(Rule::statements, &self.text[from..to]) else {
+            return false;
+        };
+        let Ok(replacement) = pairs
+            .next()
+            .map(|run: Pair<Rule>| {
+                run.into_inner()
+                    .filter(|pair: &Pair<Rule>| pair.as_rule() != Rule::EOI)
+                    .map(|pair: Pair<Rule>| {
+                        let mut statement: Statement = statement(pair)?;
+                        statement.shift(from as isize);
+                        Ok(statement)
+                    })
+                    .collect::<Result<Vec<Statement>, PlantUmlParseError>>()
+            })
+            .unwrap_or(Ok(Vec::new()))
+        else {
+            return false;
+        };
+
+        self.statements[last..]
+            .iter_mut()
+            .for_each(|statement: &mut Statement| statement.shift(delta));
+        self.statements.splice(first..last, replacement);
+        self.body.end = self.body.end.saturating_add_signed(delta);
+        true
+    }
+}
+
+fn statement(pair: Pair<Rule>) -> Result<Statement, PlantUmlParseError> {
+    let mut references: Vec<Reference> = Vec::new();
+    Ok(Statement {
+        span: syntax::statement_span(&pair),
+        symbols: syntax::statements(iter::once(pair.clone()), &mut references),
+        node: parser::parse_element(pair)?.map(AstNode::into_owned),
+        references,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::entities::{graph::Graph, node::Node};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const SOURCE: &str =
+        "@startuml\nclass User\npackage \"Core\" {\n  class Order\n}\nUser --> Order\n@enduml\n";
+
+    fn edit(source: &mut ParsedSource, from: &str, to: &str) {
+        let start: usize = source.text().find(from).unwrap();
+        source
+            .apply(&TextEdit {
+                span: Span {
+                    start,
+                    end: start + from.len(),
+                },
+                text: to.to_string(),
+            })
+            .unwrap();
+    }
+
+    /// Whatever was reparsed, the result matches parsing the new text afresh
+    fn assert_matches_full_parse(source: &ParsedSource) {
+        assert_eq!(source.index(), syntax::index_source(source.text()).unwrap());
+    }
+
+    #[test]
+    fn test_reparses_an_edited_statement() {
+        let mut source: ParsedSource = ParsedSource::new(SOURCE.to_string());
+
+        edit(&mut source, "class User", "interface Account as User");
+
+        assert!(source.error().is_none());
+        assert_eq!(source.index().definition("User").unwrap().name, "Account");
+        assert_matches_full_parse(&source);
+    }
+
+    #[test]
+    fn test_inserts_and_removes_statements() {
+        let mut source: ParsedSource = ParsedSource::new(SOURCE.to_string());
+
+        edit(
+            &mut source,
+            "User --> Order\n",
+            "User --> Order\nOrder --> Item\n",
+        );
+        assert_matches_full_parse(&source);
+        assert_eq!(source.index().references.len(), 4);
+
+        edit(&mut source, "class User\n", "");
+        assert_matches_full_parse(&source);
+
+        let graph: Graph = source.graph();
+        let mut ids: Vec<&str> = graph
+            .nodes
+            .values()
+            .map(|node: &Node| node.id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["Item", "Order", "User"]);
+    }
+
+    #[test]
+    fn test_edits_inside_packages_and_around_the_body() {
+        let mut source: ParsedSource = ParsedSource::new(SOURCE.to_string());
+
+        edit(
+            &mut source,
+            "class Order",
+            "class Order {\n    +total : Money\n  }",
+        );
+        assert_matches_full_parse(&source);
+        assert_eq!(source.index().symbols[1].children[0].children.len(), 1);
+
+        edit(&mut source, "@startuml\n", "' generated\n@startuml\n\n");
+        assert_matches_full_parse(&source);
+    }
+
+    #[test]
+    fn test_keeps_the_last_statements_until_the_text_parses_again() {
+        let mut source: ParsedSource = ParsedSource::new(SOURCE.to_string());
+
+        edit(&mut source, "User --> Order", "User -->");
+        assert!(source.error().is_some());
+        assert!(source.index().definition("User").is_some());
+
+        edit(&mut source, "User -->\n", "User --> Invoice\n");
+        assert!(source.error().is_none());
+        assert_matches_full_parse(&source);
+    }
+
+    #[test]
+    fn test_rejects_edits_outside_the_text_or_inside_a_character() {
+        let text: String = SOURCE.replace("class User", "class \"Größe\" as User");
+        let mut source: ParsedSource = ParsedSource::new(text.clone());
+        let umlaut: usize = text.find('ö').unwrap();
+        let replace = |start: usize, end: usize| -> TextEdit {
+            TextEdit {
+                span: Span { start, end },
+                text: "o".to_string(),
+            }
+        };
+
+        assert_eq!(
+            source.apply(&replace(umlaut + 1, umlaut + 2)),
+            Err(TextEditError::NotCharBoundary { offset: umlaut + 1 })
+        );
+        assert_eq!(
+            source.apply(&replace(umlaut, umlaut + 1)),
+            Err(TextEditError::NotCharBoundary { offset: umlaut + 1 })
+        );
+        assert_eq!(
+            source.apply(&replace(text.len(), text.len() + 1)),
+            Err(TextEditError::OutOfBounds {
+                span: Span {
+                    start: text.len(),
+                    end: text.len() + 1
+                },
+                len: text.len(),
+            })
+        );
+        assert_eq!(
+            source.apply(&replace(umlaut + 2, umlaut)),
+            Err(TextEditError::OutOfBounds {
+                span: Span {
+                    start: umlaut + 2,
+                    end: umlaut
+                },
+                len: text.len(),
+            })
+        );
+        assert_eq!(source.text(), text);
+
+        source.apply(&replace(umlaut, umlaut + 2)).unwrap();
+        assert_eq!(source.index().definition("User").unwrap().name, "Große");
+        assert_matches_full_parse(&source);
+    }
+}
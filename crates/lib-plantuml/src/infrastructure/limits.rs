@@ -0,0 +1,312 @@
+use crate::infrastructure::parser::PlantUmlParseError;
+
+/// Bounds on what a source may hold, so that adversarial input fails with an
+/// error instead of exhausting the stack or memory of whoever parses it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub max_input_bytes: usize,
+    /// How deep blocks such as packages, bodies, fragments and branches may
+    /// nest inside each other
+    pub max_depth: usize,
+    /// Definitions, relations and packages, nested ones included
+    pub max_statements: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_input_bytes: 32 * 1024 * 1024,
+            max_depth: 64,
+            max_statements: 1_000_000,
+        }
+    }
+}
+
+impl Limits {
+    /// Checks the size and nesting of a source. Nesting is read from the raw
+    /// text, as the parser itself recurses once per block.
+    pub(crate) fn check_source(&self, input: &str) -> Result<(), PlantUmlParseError> {
+        if input.len() > self.max_input_bytes {
+            return Err(PlantUmlParseError::LimitExceeded(format!(
+                "Input is {} bytes, more than the {} allowed",
+                input.len(),
+                self.max_input_bytes
+            )));
+        }
+
+        match deepest_block(input, self.max_depth) {
+            Some(offset) => Err(PlantUmlParseError::LimitExceeded(format!(
+                "Blocks nest past the depth limit of {} at line {}",
+                self.max_depth,
+                input[..offset].matches('\n').count() + 1
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_statements(&self, count: usize) -> Result<(), PlantUmlParseError> {
+        if count > self.max_statements {
+            return Err(PlantUmlParseError::LimitExceeded(format!(
+                "Diagram has {} statements, more than the {} allowed",
+                count, self.max_statements
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Offset of the first block nested deeper than `max_depth`, if any. Every
+/// construct the parser recurses into counts: braces that stay open past the
+/// end of their line (packages, `together`, states, class bodies…), lines
+/// opening a fragment or an activity branch up to their `end`, and brackets
+/// within a line. Braces closed on their own line, such as `{static}`, hold
+/// nothing that nests.
+fn deepest_block(input: &str, max_depth: usize) -> Option<usize> {
+    let bytes: &[u8] = input.as_bytes();
+    let mut depth: usize = 0;
+    let mut brackets: usize = 0;
+    let mut line_start: bool = true;
+    let mut offset: usize = 0;
+
+    while offset < bytes.len() {
+        let opened: bool = match bytes[offset] {
+            b'\'' => {
+                offset += input[offset..].find('\n').unwrap_or(input.len() - offset);
+                continue;
+            }
+            b'\n' => {
+                brackets = 0;
+                line_start = true;
+                offset += 1;
+                continue;
+            }
+            b'"' => {
+                offset += 1;
+                while offset < bytes.len() && !matches!(bytes[offset], b'"' | b'\n') {
+                    offset += if bytes[offset] == b'\\' { 2 } else { 1 };
+                }
+                false
+            }
+            b'{' => match closing_brace(&bytes[offset..]) {
+                Some(len) => {
+                    offset += len;
+                    false
+                }
+                None => {
+                    depth += 1;
+                    true
+                }
+            },
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            b'(' | b'[' | b'<' => {
+                brackets += 1;
+                true
+            }
+            b')' | b']' | b'>' => {
+                brackets = brackets.saturating_sub(1);
+                false
+            }
+            byte if byte.is_ascii_whitespace() => {
+                offset += 1;
+                continue;
+            }
+            byte if byte.is_ascii_alphanumeric() => {
+                let end: usize = input[offset..]
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .map_or(input.len(), |len: usize| offset + len);
+                let word: &str = &input[offset..end];
+                let rest: &str = input[end..].trim_start_matches([' ', '\t']);
+                let opened: bool = line_start && opens(word, rest);
+                if opened {
+                    depth += 1;
+                } else if line_start && closes(word, rest) {
+                    depth = depth.saturating_sub(1);
+                }
+                line_start = false;
+                if opened && depth.max(brackets) > max_depth {
+                    return Some(offset);
+                }
+                offset = end;
+                continue;
+            }
+            _ => false,
+        };
+        line_start = false;
+        if opened && depth.max(brackets) > max_depth {
+            return Some(offset);
+        }
+        offset += 1;
+    }
+    None
+}
+
+/// Length up to the brace closing the one `block` starts with, when that
+/// happens on the same line
+fn closing_brace(block: &[u8]) -> Option<usize> {
+    let mut open: usize = 0;
+    for (len, byte) in block.iter().enumerate() {
+        match byte {
+            b'{' => open += 1,
+            b'}' if open == 1 => return Some(len),
+            b'}' => open -= 1,
+            b'\n' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Whether a line starting with `word` opens a fragment or activity branch
+fn opens(word: &str, rest: &str) -> bool {
+    match word {
+        "alt" | "opt" | "loop" | "par" | "break" | "critical" | "group" => {
+            !rest.starts_with([':', '('])
+        }
+        "if" | "while" => rest.starts_with('('),
+        "fork" => !rest.starts_with("again"),
+        _ => false,
+    }
+}
+
+/// Whether a line starting with `word` closes a fragment or activity branch
+fn closes(word: &str, rest: &str) -> bool {
+    match word {
+        "endif" | "endwhile" => true,
+        "end" => {
+            rest.is_empty() || rest.starts_with(['\n', '\r', '\'']) || rest.starts_with("fork")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn nested(open: &str, inner: &str, close: &str, depth: usize) -> String {
+        format!(
+            "@startuml\n{}{}\n{}@enduml",
+            open.repeat(depth),
+            inner,
+            close.repeat(depth)
+        )
+    }
+
+    fn assert_too_deep(open: &str, inner: &str, close: &str) {
+        let limits: Limits = Limits {
+            max_depth: 2,
+            ..Default::default()
+        };
+
+        assert!(limits.check_source(&nested(open, inner, close, 2)).is_ok());
+        match limits.check_source(&nested(open, inner, close, 3)) {
+            Err(PlantUmlParseError::LimitExceeded(message)) => {
+                assert_eq!(message, "Blocks nest past the depth limit of 2 at line 4")
+            }
+            other => panic!("Expected a limit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_bounds_packages() {
+        assert_too_deep("package \"P\" {\n", "class User { {static} count }", "}\n");
+    }
+
+    #[test]
+    fn test_bounds_together() {
+        assert_too_deep("together {\n", "class User", "}\n");
+    }
+
+    #[test]
+    fn test_bounds_states() {
+        assert_too_deep("state Busy {\n", "[*] --> Idle", "}\n");
+    }
+
+    #[test]
+    fn test_bounds_inner_classes() {
+        assert_too_deep("class Outer {\n", "+name : String", "}\n");
+    }
+
+    #[test]
+    fn test_bounds_fragments() {
+        assert_too_deep("alt found\n", "Alice -> Bob : hello", "end\n");
+    }
+
+    #[test]
+    fn test_bounds_if_branches() {
+        assert_too_deep("if (paid?) then (yes)\n", ":Ship;", "endif\n");
+    }
+
+    #[test]
+    fn test_bounds_while_loops() {
+        assert_too_deep("while (more?)\n", ":Pick;", "endwhile\n");
+    }
+
+    #[test]
+    fn test_bounds_forks() {
+        assert_too_deep("fork\n", ":Pack;", "end fork\n");
+    }
+
+    #[test]
+    fn test_bounds_brackets_within_a_line() {
+        let limits: Limits = Limits {
+            max_depth: 2,
+            ..Default::default()
+        };
+
+        assert!(
+            limits
+                .check_source("@startuml\nclass Box<List<T>>\n@enduml")
+                .is_ok()
+        );
+        assert!(matches!(
+            limits.check_source("@startuml\nclass Box<List<Map<T>>>\n@enduml"),
+            Err(PlantUmlParseError::LimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_closed_blocks_free_their_depth() {
+        let limits: Limits = Limits {
+            max_depth: 1,
+            ..Default::default()
+        };
+        let input: String = format!(
+            "@startuml\n{}@enduml",
+            "alt x\nA -> B\nend\nfork\n:a;\nfork again\n:b;\nend fork\ntogether {\nclass A\n}\n"
+                .repeat(4)
+        );
+
+        assert!(limits.check_source(&input).is_ok());
+    }
+
+    #[test]
+    fn test_ignores_braces_in_strings_and_comments() {
+        let limits: Limits = Limits {
+            max_depth: 1,
+            ..Default::default()
+        };
+        let input: &str =
+            "@startuml\npackage \"{\" {\n' package \"x\" {\nUser --> Order : \"{\"\n}\n@enduml";
+
+        assert!(limits.check_source(input).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_oversized_input() {
+        let limits: Limits = Limits {
+            max_input_bytes: 8,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            limits.check_source("@startuml\n@enduml"),
+            Err(PlantUmlParseError::LimitExceeded(_))
+        ));
+    }
+}
@@ -1 +1,2 @@
+pub(crate) mod ast_arena;
 pub(crate) mod ast_node;
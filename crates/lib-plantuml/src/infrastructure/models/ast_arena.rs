@@ -0,0 +1,294 @@
+use std::{borrow::Cow, ops::Range};
+
+use lib_core::entities::{
+    edge::EdgeDirection,
+    graph::{LayoutDirection, Legend},
+};
+
+use crate::infrastructure::models::ast_node::{AstMember, AstStereotype};
+
+/// Statements stored flat in source order instead of packages owning their
+/// children: a package's contents follow it up to the end of its subtree, and
+/// all class members share one buffer. Text is borrowed from the source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AstArena<'a> {
+    nodes: Vec<ArenaNode<'a>>,
+    members: Vec<AstMember<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArenaNode<'a> {
+    Definition {
+        keyword: &'a str,
+        name: &'a str,
+        alias: Option<&'a str>,
+        generics: Option<&'a str>,
+        stereotype: Option<AstStereotype<'a>>,
+        colors: Option<&'a str>,
+        members: Range<usize>,
+        /// End of the classes declared in its body
+        end: usize,
+    },
+    Relation {
+        left: &'a str,
+        right: &'a str,
+        /// Spelled out again when the source gives it with brackets
+        arrow: Cow<'a, str>,
+        style: Option<&'a str>,
+        direction: Option<EdgeDirection>,
+        length: Option<usize>,
+        label: Option<&'a str>,
+        left_cardinality: Option<&'a str>,
+        right_cardinality: Option<&'a str>,
+        left_port: Option<&'a str>,
+        right_port: Option<&'a str>,
+    },
+    Package {
+        keyword: &'a str,
+        name: &'a str,
+        /// Index after the package's last descendant
+        end: usize,
+    },
+    Together {
+        end: usize,
+    },
+    /// A state declaration or description line; a composite state's
+    /// children follow it up to `end`
+    State {
+        name: &'a str,
+        alias: Option<&'a str>,
+        description: Option<&'a str>,
+        composite: bool,
+        end: usize,
+    },
+    AssociationClass {
+        left: &'a str,
+        right: &'a str,
+        class: &'a str,
+    },
+    /// `activate` (active) or `deactivate` of a participant's lifeline
+    Activation {
+        participant: &'a str,
+        active: bool,
+    },
+    /// A sequence fragment, its operands following it up to `end`
+    Fragment {
+        keyword: &'a str,
+        end: usize,
+    },
+    /// One condition of a fragment, its statements following it up to `end`
+    Operand {
+        condition: Option<&'a str>,
+        end: usize,
+    },
+    /// `start` or `stop` of an activity diagram
+    Terminal {
+        keyword: &'a str,
+    },
+    /// An `:action;` of an activity diagram
+    Action {
+        text: &'a str,
+    },
+    /// An `if`, `while` or `fork` block, its branches following it up to
+    /// `end`
+    Control {
+        keyword: &'a str,
+        exit: Option<&'a str>,
+        end: usize,
+    },
+    /// One branch of an activity block, its statements following it up to
+    /// `end`
+    Branch {
+        guard: Option<&'a str>,
+        label: Option<&'a str>,
+        end: usize,
+    },
+    /// A `title`, `header`, `footer` or `caption`, its lines joined
+    Heading {
+        keyword: &'a str,
+        text: Cow<'a, str>,
+    },
+    Legend(Legend),
+    LinkNote {
+        position: Option<&'a str>,
+        text: Cow<'a, str>,
+        line: usize,
+    },
+    Direction(LayoutDirection),
+    Visibility {
+        show: bool,
+        target: Option<&'a str>,
+        part: Option<&'a str>,
+        empty: bool,
+        line: usize,
+    },
+    Directive {
+        text: &'a str,
+        line: usize,
+    },
+}
+
+impl<'a> AstArena<'a> {
+    pub fn push(&mut self, node: ArenaNode<'a>) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Marks everything pushed since the block (package, together, definition, state,
+    /// fragment, activity block) or branch at `index` as its contents
+    pub fn close(&mut self, index: usize) {
+        let len: usize = self.nodes.len();
+        if let ArenaNode::Package { end, .. }
+        | ArenaNode::Definition { end, .. }
+        | ArenaNode::Together { end }
+        | ArenaNode::State { end, .. }
+        | ArenaNode::Fragment { end, .. }
+        | ArenaNode::Operand { end, .. }
+        | ArenaNode::Control { end, .. }
+        | ArenaNode::Branch { end, .. } = &mut self.nodes[index]
+        {
+            *end = len;
+        }
+    }
+
+    pub fn push_members(&mut self, members: impl Iterator<Item = AstMember<'a>>) -> Range<usize> {
+        let start: usize = self.members.len();
+        self.members.extend(members);
+        start..self.members.len()
+    }
+
+    pub fn get(&self, index: usize) -> &ArenaNode<'a> {
+        &self.nodes[index]
+    }
+
+    /// Every statement, nested ones included
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn members(&self, range: Range<usize>) -> &[AstMember<'a>] {
+        &self.members[range]
+    }
+
+    /// Top-level statements
+    pub fn roots(&self) -> Siblings<'_, 'a> {
+        Siblings {
+            arena: self,
+            next: 0,
+            end: self.nodes.len(),
+        }
+    }
+
+    /// Direct children of the block or branch at `index`
+    pub fn children(&self, index: usize) -> Siblings<'_, 'a> {
+        Siblings {
+            arena: self,
+            next: index + 1,
+            end: self.subtree_end(index),
+        }
+    }
+
+    fn subtree_end(&self, index: usize) -> usize {
+        match &self.nodes[index] {
+            ArenaNode::Package { end, .. }
+            | ArenaNode::Definition { end, .. }
+            | ArenaNode::Together { end }
+            | ArenaNode::State { end, .. }
+            | ArenaNode::Fragment { end, .. }
+            | ArenaNode::Operand { end, .. }
+            | ArenaNode::Control { end, .. }
+            | ArenaNode::Branch { end, .. } => *end,
+            _ => index + 1,
+        }
+    }
+}
+
+/// Indices of consecutive siblings, skipping over their descendants
+pub struct Siblings<'s, 'a> {
+    arena: &'s AstArena<'a>,
+    next: usize,
+    end: usize,
+}
+
+impl Iterator for Siblings<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.next >= self.end {
+            return None;
+        }
+        let index: usize = self.next;
+        self.next = self.arena.subtree_end(index);
+        Some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_children_skip_over_nested_packages() {
+        let mut arena: AstArena = AstArena::default();
+        let outer: usize = arena.push(ArenaNode::Package {
+            keyword: "package",
+            name: "Outer",
+            end: 0,
+        });
+        let inner: usize = arena.push(ArenaNode::Package {
+            keyword: "package",
+            name: "Inner",
+            end: 0,
+        });
+        arena.push(ArenaNode::Relation {
+            left: "A",
+            right: "B",
+            arrow: Cow::Borrowed("-->"),
+            style: None,
+            direction: None,
+            length: None,
+            label: None,
+            left_cardinality: None,
+            right_cardinality: None,
+            left_port: None,
+            right_port: None,
+        });
+        arena.close(inner);
+        let last: usize = arena.push(ArenaNode::Relation {
+            left: "B",
+            right: "C",
+            arrow: Cow::Borrowed("--"),
+            style: None,
+            direction: None,
+            length: None,
+            label: None,
+            left_cardinality: None,
+            right_cardinality: None,
+            left_port: None,
+            right_port: None,
+        });
+        arena.close(outer);
+        let after: usize = arena.push(ArenaNode::Relation {
+            left: "C",
+            right: "D",
+            arrow: Cow::Borrowed("--"),
+            style: None,
+            direction: None,
+            length: None,
+            label: None,
+            left_cardinality: None,
+            right_cardinality: None,
+            left_port: None,
+            right_port: None,
+        });
+
+        assert_eq!(arena.roots().collect::<Vec<usize>>(), vec![outer, after]);
+        assert_eq!(
+            arena.children(outer).collect::<Vec<usize>>(),
+            vec![inner, last]
+        );
+        assert_eq!(arena.children(inner).count(), 1);
+    }
+}
@@ -1,18 +1,414 @@
+use std::borrow::Cow;
+
+use lib_core::entities::{
+    edge::EdgeDirection,
+    graph::{LayoutDirection, Legend},
+    member::MemberKind,
+};
+
+/// Statement tree borrowing its text from the parsed source
 #[derive(Debug, Clone, PartialEq)]
-pub enum AstNode {
+pub enum AstNode<'a> {
     Definition {
-        keyword: String,
-        name: String,
-        alias: Option<String>,
+        keyword: Cow<'a, str>,
+        name: Cow<'a, str>,
+        alias: Option<Cow<'a, str>>,
+        /// Type parameters between the brackets after the name (e.g., `K, V`)
+        generics: Option<Cow<'a, str>>,
+        stereotype: Option<AstStereotype<'a>>,
+        /// Fill and border colors written after the name (e.g., `#pink ##red`)
+        colors: Option<Cow<'a, str>>,
+        members: Vec<AstMember<'a>>,
+        /// Classes declared in its body
+        children: Vec<AstNode<'a>>,
     },
     Relation {
-        left: String,
-        right: String,
-        arrow: String,
-        label: Option<String>,
+        left: Cow<'a, str>,
+        right: Cow<'a, str>,
+        /// The arrow without its brackets, `-[#red]->` being `-->`
+        arrow: Cow<'a, str>,
+        /// What the arrow's brackets held (e.g., `#red,dashed`)
+        style: Option<Cow<'a, str>>,
+        /// Where the arrow places its target (e.g., `-up->`)
+        direction: Option<EdgeDirection>,
+        /// Ranks between both ends of an arrow longer than `--` (e.g., 2
+        /// for `--->`)
+        length: Option<usize>,
+        label: Option<Cow<'a, str>>,
+        /// Multiplicities quoted next to each end (e.g., `"0..*"`)
+        left_cardinality: Option<Cow<'a, str>>,
+        right_cardinality: Option<Cow<'a, str>>,
+        /// Members qualifying each end (e.g., `customer` of `Order::customer`)
+        left_port: Option<Cow<'a, str>>,
+        right_port: Option<Cow<'a, str>>,
     },
+    /// A package, or another grouping element such as `cloud`, named by
+    /// its keyword
     Package {
-        name: String,
-        children: Vec<AstNode>,
+        keyword: Cow<'a, str>,
+        name: Cow<'a, str>,
+        children: Vec<AstNode<'a>>,
     },
+    /// Statements whose elements are laid out next to each other
+    Together {
+        children: Vec<AstNode<'a>>,
+    },
+    /// A state declaration or description line; a composite state has
+    /// children, even if none
+    State {
+        name: Cow<'a, str>,
+        alias: Option<Cow<'a, str>>,
+        description: Option<Cow<'a, str>>,
+        children: Option<Vec<AstNode<'a>>>,
+    },
+    /// A class attached to the relation between two others
+    AssociationClass {
+        left: Cow<'a, str>,
+        right: Cow<'a, str>,
+        class: Cow<'a, str>,
+    },
+    /// `activate` (active) or `deactivate` of a participant's lifeline
+    Activation {
+        participant: Cow<'a, str>,
+        active: bool,
+    },
+    /// A sequence fragment such as `alt` or `loop`, with one operand per
+    /// condition
+    Fragment {
+        keyword: Cow<'a, str>,
+        operands: Vec<AstOperand<'a>>,
+    },
+    /// `start` or `stop` of an activity diagram
+    Terminal {
+        keyword: Cow<'a, str>,
+    },
+    /// An `:action;` of an activity diagram
+    Action {
+        text: Cow<'a, str>,
+    },
+    /// An `if`, `while` or `fork` block of an activity diagram, with one
+    /// branch per `elseif`, `else` or `fork again`
+    Control {
+        keyword: Cow<'a, str>,
+        branches: Vec<AstBranch<'a>>,
+        /// Label of the flow leaving a `while` (`endwhile (done)`)
+        exit: Option<Cow<'a, str>>,
+    },
+    /// A `title`, `header`, `footer` or `caption`, its lines joined
+    Heading {
+        keyword: Cow<'a, str>,
+        text: Cow<'a, str>,
+    },
+    Legend(Legend),
+    /// A `note on link`, on the side of the relation before it its position
+    /// word gives
+    LinkNote {
+        position: Option<Cow<'a, str>>,
+        text: Cow<'a, str>,
+        line: usize,
+    },
+    /// `left to right direction` or `top to bottom direction`
+    Direction(LayoutDirection),
+    /// A `hide` or `show` command: the elements it applies to, the part of
+    /// them it names and whether only empty ones (`hide empty members`)
+    Visibility {
+        show: bool,
+        target: Option<Cow<'a, str>>,
+        part: Option<Cow<'a, str>>,
+        empty: bool,
+        line: usize,
+    },
+    /// A setting such as `skinparam` that the graph has no place for
+    Directive {
+        text: Cow<'a, str>,
+        line: usize,
+    },
+}
+
+/// The statements of an activity block taken under one guard: an `if`'s or
+/// `elseif`'s condition, none for an `else` or a `fork` branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstBranch<'a> {
+    pub guard: Option<Cow<'a, str>>,
+    /// Label of the flow into the branch (`then (yes)`, `else (no)`)
+    pub label: Option<Cow<'a, str>>,
+    pub children: Vec<AstNode<'a>>,
+}
+
+/// The statements of a fragment under one condition: the header's, or an
+/// `else`'s
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstOperand<'a> {
+    pub condition: Option<Cow<'a, str>>,
+    pub children: Vec<AstNode<'a>>,
+}
+
+/// A class body line, split up by the grammar
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstMember<'a> {
+    /// Words in braces, such as `static` or `abstract`
+    pub modifiers: Vec<Cow<'a, str>>,
+    pub visibility: Option<char>,
+    pub name: Cow<'a, str>,
+    /// Present for methods, even without parameters
+    pub parameters: Option<Vec<Cow<'a, str>>>,
+    pub type_name: Option<Cow<'a, str>>,
+    /// What an object field or map key is set to, such as `"Bob"` in
+    /// `name = "Bob"`, or an enum value's arguments, such as `"#f00"` in
+    /// `RED("#f00")`
+    pub value: Option<Cow<'a, str>>,
+    /// The bar of a line splitting the body into sections (`--`, `..`, `==`
+    /// or `__`), whose title, if any, is the name
+    pub separator: Option<Cow<'a, str>>,
+    /// Whether the line is one of an enum's values
+    pub enum_value: bool,
+}
+
+impl AstMember<'_> {
+    pub fn kind(&self) -> MemberKind {
+        if self.separator.is_some() {
+            MemberKind::Separator
+        } else if self.enum_value {
+            MemberKind::EnumValue
+        } else if self.parameters.is_some() || self.has_modifier("method") {
+            MemberKind::Method
+        } else {
+            MemberKind::Field
+        }
+    }
+
+    pub fn has_modifier(&self, modifier: &str) -> bool {
+        self.modifiers
+            .iter()
+            .any(|m: &Cow<str>| m.as_ref() == modifier)
+    }
+
+    pub fn into_owned(self) -> AstMember<'static> {
+        AstMember {
+            modifiers: self.modifiers.into_iter().map(owned).collect(),
+            visibility: self.visibility,
+            name: owned(self.name),
+            parameters: self
+                .parameters
+                .map(|parameters: Vec<Cow<str>>| parameters.into_iter().map(owned).collect()),
+            type_name: self.type_name.map(owned),
+            value: self.value.map(owned),
+            separator: self.separator.map(owned),
+            enum_value: self.enum_value,
+        }
+    }
+}
+
+/// What a definition's `<< ... >>` holds: a name, a letter drawn in a spot
+/// and the spot's color, each of which may be left out
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AstStereotype<'a> {
+    pub name: Option<Cow<'a, str>>,
+    pub spot: Option<Cow<'a, str>>,
+    pub spot_color: Option<Cow<'a, str>>,
+}
+
+impl AstStereotype<'_> {
+    pub fn into_owned(self) -> AstStereotype<'static> {
+        AstStereotype {
+            name: self.name.map(owned),
+            spot: self.spot.map(owned),
+            spot_color: self.spot_color.map(owned),
+        }
+    }
+}
+
+fn owned(text: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(text.into_owned())
+}
+
+impl AstNode<'_> {
+    /// This statement and every one nested in it
+    pub fn count(&self) -> usize {
+        match self {
+            AstNode::Package { children, .. }
+            | AstNode::Together { children }
+            | AstNode::Definition { children, .. } => {
+                1 + children.iter().map(AstNode::count).sum::<usize>()
+            }
+            AstNode::State {
+                children: Some(children),
+                ..
+            } => 1 + children.iter().map(AstNode::count).sum::<usize>(),
+            AstNode::Control { branches, .. } => {
+                1 + branches
+                    .iter()
+                    .map(|branch: &AstBranch| {
+                        1 + branch.children.iter().map(AstNode::count).sum::<usize>()
+                    })
+                    .sum::<usize>()
+            }
+            AstNode::Fragment { operands, .. } => {
+                1 + operands
+                    .iter()
+                    .map(|operand: &AstOperand| {
+                        1 + operand.children.iter().map(AstNode::count).sum::<usize>()
+                    })
+                    .sum::<usize>()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Copies the borrowed text, for nodes that outlive their source
+    pub fn into_owned(self) -> AstNode<'static> {
+        match self {
+            AstNode::Definition {
+                keyword,
+                name,
+                alias,
+                generics,
+                stereotype,
+                colors,
+                members,
+                children,
+            } => AstNode::Definition {
+                keyword: owned(keyword),
+                name: owned(name),
+                alias: alias.map(owned),
+                generics: generics.map(owned),
+                stereotype: stereotype.map(AstStereotype::into_owned),
+                colors: colors.map(owned),
+                members: members.into_iter().map(AstMember::into_owned).collect(),
+                children: children.into_iter().map(AstNode::into_owned).collect(),
+            },
+            AstNode::Relation {
+                left,
+                right,
+                arrow,
+                style,
+                direction,
+                length,
+                label,
+                left_cardinality,
+                right_cardinality,
+                left_port,
+                right_port,
+            } => AstNode::Relation {
+                left: owned(left),
+                right: owned(right),
+                arrow: owned(arrow),
+                style: style.map(owned),
+                direction,
+                length,
+                label: label.map(owned),
+                left_cardinality: left_cardinality.map(owned),
+                right_cardinality: right_cardinality.map(owned),
+                left_port: left_port.map(owned),
+                right_port: right_port.map(owned),
+            },
+            AstNode::Package {
+                keyword,
+                name,
+                children,
+            } => AstNode::Package {
+                keyword: owned(keyword),
+                name: owned(name),
+                children: children.into_iter().map(AstNode::into_owned).collect(),
+            },
+            AstNode::Together { children } => AstNode::Together {
+                children: children.into_iter().map(AstNode::into_owned).collect(),
+            },
+            AstNode::State {
+                name,
+                alias,
+                description,
+                children,
+            } => AstNode::State {
+                name: owned(name),
+                alias: alias.map(owned),
+                description: description.map(owned),
+                children: children.map(|children: Vec<AstNode>| {
+                    children.into_iter().map(AstNode::into_owned).collect()
+                }),
+            },
+            AstNode::AssociationClass { left, right, class } => AstNode::AssociationClass {
+                left: owned(left),
+                right: owned(right),
+                class: owned(class),
+            },
+            AstNode::Activation {
+                participant,
+                active,
+            } => AstNode::Activation {
+                participant: owned(participant),
+                active,
+            },
+            AstNode::Fragment { keyword, operands } => AstNode::Fragment {
+                keyword: owned(keyword),
+                operands: operands
+                    .into_iter()
+                    .map(|operand: AstOperand| AstOperand {
+                        condition: operand.condition.map(owned),
+                        children: operand
+                            .children
+                            .into_iter()
+                            .map(AstNode::into_owned)
+                            .collect(),
+                    })
+                    .collect(),
+            },
+            AstNode::Terminal { keyword } => AstNode::Terminal {
+                keyword: owned(keyword),
+            },
+            AstNode::Action { text } => AstNode::Action { text: owned(text) },
+            AstNode::Control {
+                keyword,
+                branches,
+                exit,
+            } => AstNode::Control {
+                keyword: owned(keyword),
+                branches: branches
+                    .into_iter()
+                    .map(|branch: AstBranch| AstBranch {
+                        guard: branch.guard.map(owned),
+                        label: branch.label.map(owned),
+                        children: branch
+                            .children
+                            .into_iter()
+                            .map(AstNode::into_owned)
+                            .collect(),
+                    })
+                    .collect(),
+                exit: exit.map(owned),
+            },
+            AstNode::Heading { keyword, text } => AstNode::Heading {
+                keyword: owned(keyword),
+                text: owned(text),
+            },
+            AstNode::Legend(legend) => AstNode::Legend(legend),
+            AstNode::LinkNote {
+                position,
+                text,
+                line,
+            } => AstNode::LinkNote {
+                position: position.map(owned),
+                text: owned(text),
+                line,
+            },
+            AstNode::Direction(direction) => AstNode::Direction(direction),
+            AstNode::Visibility {
+                show,
+                target,
+                part,
+                empty,
+                line,
+            } => AstNode::Visibility {
+                show,
+                target: target.map(owned),
+                part: part.map(owned),
+                empty,
+                line,
+            },
+            AstNode::Directive { text, line } => AstNode::Directive {
+                text: owned(text),
+                line,
+            },
+        }
+    }
 }
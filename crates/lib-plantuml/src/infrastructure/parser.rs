@@ -1,78 +1,1173 @@
+use std::{borrow::Cow, ops::Range};
+
+use lib_core::entities::{
+    edge::EdgeDirection,
+    graph::{HorizontalPosition, LayoutDirection, Legend, VerticalPosition},
+};
 use pest::Parser;
 use pest_derive::Parser;
 
-use crate::infrastructure::models::ast_node::AstNode;
+use crate::infrastructure::{
+    models::{
+        ast_arena::{ArenaNode, AstArena},
+        ast_node::{AstBranch, AstMember, AstNode, AstOperand, AstStereotype},
+    },
+    usecase, visibility,
+};
 
 #[derive(Parser)]
 #[grammar = "infrastructure/plantuml.pest"]
 pub struct PlantUmlParser;
 
-pub fn parse_plantuml(input: &str) -> Result<Vec<AstNode>, PlantUmlParseError> {
+pub fn parse_plantuml(input: &str) -> Result<Vec<AstNode<'_>>, PlantUmlParseError> {
     let mut ast: Vec<AstNode> = Vec::new();
-    let diagram: pest::iterators::Pair<Rule> = PlantUmlParser::parse(Rule::diagram, input)
-        .map_err(PlantUmlParseError::from)?
-        .next()
-        .unwrap();
+    let diagram: pest::iterators::Pair<Rule> = first(PlantUmlParser::parse(Rule::diagram, input)?)?;
 
-    diagram
-        .into_inner()
-        .for_each(|pair: pest::iterators::Pair<Rule>| {
-            if let Some(node) = parse_element(pair) {
-                ast.push(node);
-            }
-        });
+    for pair in diagram.into_inner() {
+        if let Some(node) = parse_element(pair)? {
+            ast.push(node);
+        }
+    }
 
     Ok(ast)
 }
 
-fn parse_element(pair: pest::iterators::Pair<Rule>) -> Option<AstNode> {
+/// Parses into one flat arena, avoiding an allocation per package and per
+/// definition's members
+pub fn parse_plantuml_arena(input: &str) -> Result<AstArena<'_>, PlantUmlParseError> {
+    let mut arena: AstArena = AstArena::default();
+    first(PlantUmlParser::parse(Rule::diagram, input)?)?
+        .into_inner()
+        .try_for_each(|pair: pest::iterators::Pair<Rule>| parse_into_arena(pair, &mut arena))?;
+
+    Ok(arena)
+}
+
+fn parse_into_arena<'a>(
+    pair: pest::iterators::Pair<'a, Rule>,
+    arena: &mut AstArena<'a>,
+) -> Result<(), PlantUmlParseError> {
+    let at: (usize, usize) = pair.line_col();
     match pair.as_rule() {
         Rule::definition => {
             let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
-            let keyword: String = inner.next().unwrap().as_str().to_string();
-            let name: String = inner.next().unwrap().as_str().trim_matches('"').to_string();
-            let alias: Option<String> = inner
+            let keyword: &str = part(&mut inner, "a keyword", at)?.as_str();
+            let name: &str = usecase::name(part(&mut inner, "a name", at)?.as_str());
+            let mut alias: Option<&str> = None;
+            let mut generics: Option<&str> = None;
+            let mut stereotype: Option<AstStereotype> = None;
+            let mut colors: Option<&str> = None;
+            let mut members: Range<usize> = 0..0;
+            let mut body: Option<pest::iterators::Pair<Rule>> = None;
+
+            inner.for_each(|p: pest::iterators::Pair<Rule>| match p.as_rule() {
+                Rule::identifier => alias = Some(p.as_str()),
+                Rule::generics => generics = Some(type_parameters(p.as_str())),
+                Rule::stereotype => stereotype = Some(parse_stereotype(p)),
+                Rule::colors => colors = Some(p.as_str()),
+                Rule::class_body | Rule::enum_body => {
+                    members = arena.push_members(parse_body(p.clone(), keyword).into_iter());
+                    body = Some(p);
+                }
+                _ => {}
+            });
+
+            let definition: usize = arena.push(ArenaNode::Definition {
+                keyword,
+                name,
+                alias,
+                generics,
+                stereotype,
+                colors,
+                members,
+                end: 0,
+            });
+            if let Some(body) = body {
+                inner_classes(body).try_for_each(|child_pair: pest::iterators::Pair<Rule>| {
+                    parse_into_arena(child_pair, arena)
+                })?;
+            }
+            arena.close(definition);
+        }
+        Rule::relation => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let (left, left_port): (&str, Option<&str>) =
+                endpoint(part(&mut inner, "the left side of a relation", at)?);
+            let left_cardinality: Option<&str> = cardinality(&mut inner);
+            let (arrow, style, direction): (Cow<str>, Option<&str>, Option<EdgeDirection>) =
+                arrow(part(&mut inner, "an arrow", at)?);
+            let (arrow, length): (Cow<str>, Option<usize>) = shorten(arrow);
+            let right_cardinality: Option<&str> = cardinality(&mut inner);
+            let (right, right_port): (&str, Option<&str>) =
+                endpoint(part(&mut inner, "the right side of a relation", at)?);
+            let label: Option<&str> = inner
+                .next()
+                .map(|p: pest::iterators::Pair<Rule>| p.as_str().trim_matches('"'));
+
+            arena.push(ArenaNode::Relation {
+                left,
+                right,
+                arrow,
+                style,
+                direction,
+                length,
+                label,
+                left_cardinality,
+                right_cardinality,
+                left_port,
+                right_port,
+            });
+        }
+        Rule::shorthand => {
+            let (keyword, name, alias): (&str, &str, Option<&str>) = shorthand(pair, at)?;
+            let definition: usize = arena.push(ArenaNode::Definition {
+                keyword,
+                name,
+                alias,
+                generics: None,
+                stereotype: None,
+                colors: None,
+                members: 0..0,
+                end: 0,
+            });
+            arena.close(definition);
+        }
+        Rule::archimate_element => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let keyword: &str = part(&mut inner, "a macro", at)?.as_str();
+            let alias: &str = part(&mut inner, "an id", at)?.as_str();
+            let name: &str = part(&mut inner, "a name", at)?.as_str().trim_matches('"');
+
+            let definition: usize = arena.push(ArenaNode::Definition {
+                keyword,
+                name,
+                alias: Some(alias),
+                generics: None,
+                stereotype: None,
+                colors: None,
+                members: 0..0,
+                end: 0,
+            });
+            arena.close(definition);
+        }
+        Rule::archimate_relation => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let arrow: &str = part(&mut inner, "a macro", at)?.as_str();
+            let left: &str = part(&mut inner, "the source of a relation", at)?.as_str();
+            let right: &str = part(&mut inner, "the target of a relation", at)?.as_str();
+            let label: Option<&str> = inner
                 .next()
-                .map(|p: pest::iterators::Pair<Rule>| p.as_str().to_string());
+                .map(|p: pest::iterators::Pair<Rule>| p.as_str().trim_matches('"'));
+
+            arena.push(ArenaNode::Relation {
+                left,
+                right,
+                arrow: Cow::Borrowed(arrow),
+                style: None,
+                direction: None,
+                length: None,
+                label,
+                left_cardinality: None,
+                right_cardinality: None,
+                left_port: None,
+                right_port: None,
+            });
+        }
+        Rule::package => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let keyword: &str = part(&mut inner, "a keyword", at)?.as_str();
+            let name: &str = part(&mut inner, "a package name", at)?
+                .as_str()
+                .trim_matches('"');
+            let package: usize = arena.push(ArenaNode::Package {
+                keyword,
+                name,
+                end: 0,
+            });
+
+            inner.try_for_each(|child_pair: pest::iterators::Pair<Rule>| {
+                parse_into_arena(child_pair, arena)
+            })?;
+            arena.close(package);
+        }
+        Rule::together => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            part(&mut inner, "a keyword", at)?;
+            let together: usize = arena.push(ArenaNode::Together { end: 0 });
+
+            inner.try_for_each(|child_pair: pest::iterators::Pair<Rule>| {
+                parse_into_arena(child_pair, arena)
+            })?;
+            arena.close(together);
+        }
+        Rule::state => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            part(&mut inner, "a keyword", at)?;
+            let name: &str = part(&mut inner, "a state name", at)?
+                .as_str()
+                .trim_matches('"');
+            let mut alias: Option<&str> = None;
+            let mut description: Option<&str> = None;
+            let mut body: Option<pest::iterators::Pair<Rule>> = None;
+
+            inner.for_each(|p: pest::iterators::Pair<Rule>| match p.as_rule() {
+                Rule::identifier => alias = Some(p.as_str()),
+                Rule::label => description = Some(p.as_str()),
+                Rule::state_body => body = Some(p),
+                _ => {}
+            });
+
+            let state: usize = arena.push(ArenaNode::State {
+                name,
+                alias,
+                description,
+                composite: body.is_some(),
+                end: 0,
+            });
+            if let Some(body) = body {
+                body.into_inner()
+                    .try_for_each(|child_pair: pest::iterators::Pair<Rule>| {
+                        parse_into_arena(child_pair, arena)
+                    })?;
+            }
+            arena.close(state);
+        }
+        Rule::state_description => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let name: &str = part(&mut inner, "a state name", at)?.as_str();
+            let description: &str = part(&mut inner, "a description", at)?.as_str();
+
+            let state: usize = arena.push(ArenaNode::State {
+                name,
+                alias: None,
+                description: Some(description),
+                composite: false,
+                end: 0,
+            });
+            arena.close(state);
+        }
+        Rule::fragment => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let header: pest::iterators::Pair<Rule> = part(&mut inner, "a fragment", at)?;
+            let (keyword, condition): (&str, Option<&str>) = fragment_header(header, at)?;
+            let fragment: usize = arena.push(ArenaNode::Fragment { keyword, end: 0 });
+            let mut operand: usize = arena.push(ArenaNode::Operand { condition, end: 0 });
+
+            inner.try_for_each(|child_pair: pest::iterators::Pair<Rule>| {
+                match child_pair.as_rule() {
+                    Rule::fragment_else | Rule::fragment_end => {
+                        arena.close(operand);
+                        if child_pair.as_rule() == Rule::fragment_else {
+                            operand = arena.push(ArenaNode::Operand {
+                                condition: condition_of(child_pair),
+                                end: 0,
+                            });
+                        }
+                        Ok(())
+                    }
+                    _ => parse_into_arena(child_pair, arena),
+                }
+            })?;
+            arena.close(fragment);
+        }
+        Rule::association_class => {
+            let (left, right, class): (&str, &str, &str) = association_class(pair, at)?;
+            arena.push(ArenaNode::AssociationClass { left, right, class });
+        }
+        Rule::activation => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let keyword: &str = part(&mut inner, "a keyword", at)?.as_str();
+            let participant: &str = part(&mut inner, "a participant", at)?.as_str();
+
+            arena.push(ArenaNode::Activation {
+                participant,
+                active: keyword == "activate",
+            });
+        }
+        Rule::terminal => {
+            arena.push(ArenaNode::Terminal {
+                keyword: pair.as_str(),
+            });
+        }
+        Rule::action => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let text: &str = part(&mut inner, "an action", at)?.as_str().trim();
+            arena.push(ArenaNode::Action { text });
+        }
+        Rule::activity_if | Rule::activity_while | Rule::activity_fork => {
+            // The closing line carries the label of the flow leaving
+            let exit: Option<&str> = pair
+                .clone()
+                .into_inner()
+                .last()
+                .and_then(|last: pest::iterators::Pair<Rule>| branch_header(last).1);
+            let control: usize = arena.push(ArenaNode::Control {
+                keyword: control_keyword(&pair),
+                exit,
+                end: 0,
+            });
+            let mut branch: Option<usize> = None;
+
+            pair.into_inner()
+                .try_for_each(|child_pair: pest::iterators::Pair<Rule>| {
+                    match child_pair.as_rule() {
+                        Rule::if_header
+                        | Rule::elseif_header
+                        | Rule::else_header
+                        | Rule::while_header
+                        | Rule::fork_header
+                        | Rule::fork_again => {
+                            if let Some(branch) = branch {
+                                arena.close(branch);
+                            }
+                            let (guard, label): (Option<&str>, Option<&str>) =
+                                branch_header(child_pair);
+                            branch = Some(arena.push(ArenaNode::Branch {
+                                guard,
+                                label,
+                                end: 0,
+                            }));
+                            Ok(())
+                        }
+                        Rule::endif | Rule::endwhile | Rule::end_fork => {
+                            if let Some(branch) = branch {
+                                arena.close(branch);
+                            }
+                            Ok(())
+                        }
+                        _ => parse_into_arena(child_pair, arena),
+                    }
+                })?;
+            arena.close(control);
+        }
+        Rule::heading => {
+            let (keyword, text): (&str, Cow<str>) = heading(pair);
+            arena.push(ArenaNode::Heading { keyword, text });
+        }
+        Rule::legend => {
+            arena.push(ArenaNode::Legend(legend(pair)));
+        }
+        Rule::link_note => {
+            let (position, text): (Option<&str>, Cow<str>) = link_note(pair);
+            arena.push(ArenaNode::LinkNote {
+                position,
+                text,
+                line: at.0,
+            });
+        }
+        Rule::direction => {
+            arena.push(ArenaNode::Direction(direction(pair)));
+        }
+        Rule::visibility_command => {
+            if let Some((show, target, part, empty)) = visibility::words(pair.as_str().trim()) {
+                arena.push(ArenaNode::Visibility {
+                    show,
+                    target,
+                    part,
+                    empty,
+                    line: at.0,
+                });
+            }
+        }
+        Rule::directive => {
+            arena.push(ArenaNode::Directive {
+                text: pair.as_str().trim(),
+                line: at.0,
+            });
+        }
+        _ => {}
+    }
+    Ok(())
+}
 
-            Some(AstNode::Definition {
+pub(crate) fn parse_element(
+    pair: pest::iterators::Pair<'_, Rule>,
+) -> Result<Option<AstNode<'_>>, PlantUmlParseError> {
+    let at: (usize, usize) = pair.line_col();
+    match pair.as_rule() {
+        Rule::definition => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let keyword: Cow<str> = Cow::Borrowed(part(&mut inner, "a keyword", at)?.as_str());
+            let name: Cow<str> =
+                Cow::Borrowed(usecase::name(part(&mut inner, "a name", at)?.as_str()));
+            let mut alias: Option<Cow<str>> = None;
+            let mut generics: Option<Cow<str>> = None;
+            let mut stereotype: Option<AstStereotype> = None;
+            let mut colors: Option<Cow<str>> = None;
+            let mut members: Vec<AstMember> = Vec::new();
+            let mut children: Vec<AstNode> = Vec::new();
+
+            for p in inner {
+                match p.as_rule() {
+                    Rule::identifier => alias = Some(Cow::Borrowed(p.as_str())),
+                    Rule::generics => generics = Some(Cow::Borrowed(type_parameters(p.as_str()))),
+                    Rule::stereotype => stereotype = Some(parse_stereotype(p)),
+                    Rule::colors => colors = Some(Cow::Borrowed(p.as_str())),
+                    Rule::class_body | Rule::enum_body => {
+                        members = parse_body(p.clone(), &keyword);
+                        for child_pair in inner_classes(p) {
+                            if let Some(child) = parse_element(child_pair)? {
+                                children.push(child);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            Ok(Some(AstNode::Definition {
                 keyword,
                 name,
                 alias,
-            })
+                generics,
+                stereotype,
+                colors,
+                members,
+                children,
+            }))
         }
         Rule::relation => {
             let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
-            let left: String = inner.next().unwrap().as_str().to_string();
-            let arrow: String = inner.next().unwrap().as_str().to_string();
-            let right: String = inner.next().unwrap().as_str().to_string();
-            let label: Option<String> = inner
+            let (left, left_port): (&str, Option<&str>) =
+                endpoint(part(&mut inner, "the left side of a relation", at)?);
+            let left_cardinality: Option<&str> = cardinality(&mut inner);
+            let (arrow, style, direction): (Cow<str>, Option<&str>, Option<EdgeDirection>) =
+                arrow(part(&mut inner, "an arrow", at)?);
+            let (arrow, length): (Cow<str>, Option<usize>) = shorten(arrow);
+            let right_cardinality: Option<&str> = cardinality(&mut inner);
+            let (right, right_port): (&str, Option<&str>) =
+                endpoint(part(&mut inner, "the right side of a relation", at)?);
+            let label: Option<Cow<str>> = inner
                 .next()
-                .map(|p: pest::iterators::Pair<Rule>| p.as_str().trim_matches('"').to_string());
+                .map(|p: pest::iterators::Pair<Rule>| Cow::Borrowed(p.as_str().trim_matches('"')));
+
+            Ok(Some(AstNode::Relation {
+                left: Cow::Borrowed(left),
+                right: Cow::Borrowed(right),
+                arrow,
+                style: style.map(Cow::Borrowed),
+                direction,
+                length,
+                label,
+                left_cardinality: left_cardinality.map(Cow::Borrowed),
+                right_cardinality: right_cardinality.map(Cow::Borrowed),
+                left_port: left_port.map(Cow::Borrowed),
+                right_port: right_port.map(Cow::Borrowed),
+            }))
+        }
+        Rule::shorthand => {
+            let (keyword, name, alias): (&str, &str, Option<&str>) = shorthand(pair, at)?;
+            Ok(Some(AstNode::Definition {
+                keyword: Cow::Borrowed(keyword),
+                name: Cow::Borrowed(name),
+                alias: alias.map(Cow::Borrowed),
+                generics: None,
+                stereotype: None,
+                colors: None,
+                members: Vec::new(),
+                children: Vec::new(),
+            }))
+        }
+        Rule::archimate_element => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let keyword: Cow<str> = Cow::Borrowed(part(&mut inner, "a macro", at)?.as_str());
+            let alias: Cow<str> = Cow::Borrowed(part(&mut inner, "an id", at)?.as_str());
+            let name: Cow<str> =
+                Cow::Borrowed(part(&mut inner, "a name", at)?.as_str().trim_matches('"'));
 
-            Some(AstNode::Relation {
+            Ok(Some(AstNode::Definition {
+                keyword,
+                name,
+                alias: Some(alias),
+                generics: None,
+                stereotype: None,
+                colors: None,
+                members: Vec::new(),
+                children: Vec::new(),
+            }))
+        }
+        Rule::archimate_relation => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let arrow: Cow<str> = Cow::Borrowed(part(&mut inner, "a macro", at)?.as_str());
+            let left: Cow<str> =
+                Cow::Borrowed(part(&mut inner, "the source of a relation", at)?.as_str());
+            let right: Cow<str> =
+                Cow::Borrowed(part(&mut inner, "the target of a relation", at)?.as_str());
+            let label: Option<Cow<str>> = inner
+                .next()
+                .map(|p: pest::iterators::Pair<Rule>| Cow::Borrowed(p.as_str().trim_matches('"')));
+
+            Ok(Some(AstNode::Relation {
                 left,
                 right,
                 arrow,
+                style: None,
+                direction: None,
+                length: None,
                 label,
-            })
+                left_cardinality: None,
+                right_cardinality: None,
+                left_port: None,
+                right_port: None,
+            }))
         }
         Rule::package => {
             let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
-            let name: String = inner.next().unwrap().as_str().trim_matches('"').to_string();
+            let keyword: Cow<str> = Cow::Borrowed(part(&mut inner, "a keyword", at)?.as_str());
+            let name: Cow<str> = Cow::Borrowed(
+                part(&mut inner, "a package name", at)?
+                    .as_str()
+                    .trim_matches('"'),
+            );
             let mut children: Vec<AstNode> = Vec::new();
 
-            inner.for_each(|child_pair: pest::iterators::Pair<Rule>| {
-                if let Some(child) = parse_element(child_pair) {
+            for child_pair in inner {
+                if let Some(child) = parse_element(child_pair)? {
                     children.push(child);
                 }
-            });
-            Some(AstNode::Package { name, children })
+            }
+            Ok(Some(AstNode::Package {
+                keyword,
+                name,
+                children,
+            }))
+        }
+        Rule::together => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            part(&mut inner, "a keyword", at)?;
+            let mut children: Vec<AstNode> = Vec::new();
+
+            for child_pair in inner {
+                if let Some(child) = parse_element(child_pair)? {
+                    children.push(child);
+                }
+            }
+            Ok(Some(AstNode::Together { children }))
+        }
+        Rule::state => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            part(&mut inner, "a keyword", at)?;
+            let name: Cow<str> = Cow::Borrowed(
+                part(&mut inner, "a state name", at)?
+                    .as_str()
+                    .trim_matches('"'),
+            );
+            let mut alias: Option<Cow<str>> = None;
+            let mut description: Option<Cow<str>> = None;
+            let mut children: Option<Vec<AstNode>> = None;
+
+            for p in inner {
+                match p.as_rule() {
+                    Rule::identifier => alias = Some(Cow::Borrowed(p.as_str())),
+                    Rule::label => description = Some(Cow::Borrowed(p.as_str())),
+                    Rule::state_body => {
+                        let mut body: Vec<AstNode> = Vec::new();
+                        for child_pair in p.into_inner() {
+                            if let Some(child) = parse_element(child_pair)? {
+                                body.push(child);
+                            }
+                        }
+                        children = Some(body);
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Some(AstNode::State {
+                name,
+                alias,
+                description,
+                children,
+            }))
+        }
+        Rule::state_description => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let name: Cow<str> = Cow::Borrowed(part(&mut inner, "a state name", at)?.as_str());
+            let description: Cow<str> =
+                Cow::Borrowed(part(&mut inner, "a description", at)?.as_str());
+
+            Ok(Some(AstNode::State {
+                name,
+                alias: None,
+                description: Some(description),
+                children: None,
+            }))
+        }
+        Rule::fragment => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let header: pest::iterators::Pair<Rule> = part(&mut inner, "a fragment", at)?;
+            let (keyword, condition): (&str, Option<&str>) = fragment_header(header, at)?;
+            let mut operands: Vec<AstOperand> = vec![AstOperand {
+                condition: condition.map(Cow::Borrowed),
+                children: Vec::new(),
+            }];
+
+            for child_pair in inner {
+                match child_pair.as_rule() {
+                    Rule::fragment_else => operands.push(AstOperand {
+                        condition: condition_of(child_pair).map(Cow::Borrowed),
+                        children: Vec::new(),
+                    }),
+                    Rule::fragment_end => {}
+                    _ => {
+                        if let (Some(child), Some(operand)) =
+                            (parse_element(child_pair)?, operands.last_mut())
+                        {
+                            operand.children.push(child);
+                        }
+                    }
+                }
+            }
+            Ok(Some(AstNode::Fragment {
+                keyword: Cow::Borrowed(keyword),
+                operands,
+            }))
+        }
+        Rule::association_class => {
+            let (left, right, class): (&str, &str, &str) = association_class(pair, at)?;
+            Ok(Some(AstNode::AssociationClass {
+                left: Cow::Borrowed(left),
+                right: Cow::Borrowed(right),
+                class: Cow::Borrowed(class),
+            }))
+        }
+        Rule::activation => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let keyword: &str = part(&mut inner, "a keyword", at)?.as_str();
+            let participant: Cow<str> =
+                Cow::Borrowed(part(&mut inner, "a participant", at)?.as_str());
+
+            Ok(Some(AstNode::Activation {
+                participant,
+                active: keyword == "activate",
+            }))
+        }
+        Rule::terminal => Ok(Some(AstNode::Terminal {
+            keyword: Cow::Borrowed(pair.as_str()),
+        })),
+        Rule::action => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let text: &str = part(&mut inner, "an action", at)?.as_str().trim();
+            Ok(Some(AstNode::Action {
+                text: Cow::Borrowed(text),
+            }))
+        }
+        Rule::activity_if | Rule::activity_while | Rule::activity_fork => {
+            let keyword: &str = control_keyword(&pair);
+            let mut branches: Vec<AstBranch> = Vec::new();
+            let mut exit: Option<Cow<str>> = None;
+
+            for child_pair in pair.into_inner() {
+                match child_pair.as_rule() {
+                    Rule::if_header
+                    | Rule::elseif_header
+                    | Rule::else_header
+                    | Rule::while_header
+                    | Rule::fork_header
+                    | Rule::fork_again => {
+                        let (guard, label): (Option<&str>, Option<&str>) =
+                            branch_header(child_pair);
+                        branches.push(AstBranch {
+                            guard: guard.map(Cow::Borrowed),
+                            label: label.map(Cow::Borrowed),
+                            children: Vec::new(),
+                        });
+                    }
+                    Rule::endif | Rule::endwhile | Rule::end_fork => {
+                        exit = branch_header(child_pair).1.map(Cow::Borrowed);
+                    }
+                    _ => {
+                        if let (Some(child), Some(branch)) =
+                            (parse_element(child_pair)?, branches.last_mut())
+                        {
+                            branch.children.push(child);
+                        }
+                    }
+                }
+            }
+            Ok(Some(AstNode::Control {
+                keyword: Cow::Borrowed(keyword),
+                branches,
+                exit,
+            }))
+        }
+        Rule::heading => {
+            let (keyword, text): (&str, Cow<str>) = heading(pair);
+            Ok(Some(AstNode::Heading {
+                keyword: Cow::Borrowed(keyword),
+                text,
+            }))
+        }
+        Rule::legend => Ok(Some(AstNode::Legend(legend(pair)))),
+        Rule::link_note => {
+            let (position, text): (Option<&str>, Cow<str>) = link_note(pair);
+            Ok(Some(AstNode::LinkNote {
+                position: position.map(Cow::Borrowed),
+                text,
+                line: at.0,
+            }))
+        }
+        Rule::direction => Ok(Some(AstNode::Direction(direction(pair)))),
+        Rule::visibility_command => Ok(visibility::words(pair.as_str().trim()).map(
+            |(show, target, part, empty): (bool, Option<&str>, Option<&str>, bool)| {
+                AstNode::Visibility {
+                    show,
+                    target: target.map(Cow::Borrowed),
+                    part: part.map(Cow::Borrowed),
+                    empty,
+                    line: at.0,
+                }
+            },
+        )),
+        Rule::directive => Ok(Some(AstNode::Directive {
+            text: Cow::Borrowed(pair.as_str().trim()),
+            line: at.0,
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// The keyword and text of a heading such as `title Orders`. The lines of a
+/// heading spanning several are trimmed, and blank ones dropped.
+fn heading(pair: pest::iterators::Pair<'_, Rule>) -> (&str, Cow<'_, str>) {
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    let keyword: &str = inner
+        .next()
+        .map_or("title", |p: pest::iterators::Pair<Rule>| p.as_str());
+    let text: Cow<str> = match inner.next() {
+        Some(p) if p.as_rule() == Rule::heading_lines => Cow::Owned(
+            p.as_str()
+                .lines()
+                .map(str::trim)
+                .filter(|line: &&str| !line.is_empty())
+                .collect::<Vec<&str>>()
+                .join("\n"),
+        ),
+        Some(p) => Cow::Borrowed(p.as_str()),
+        None => Cow::Borrowed(""),
+    };
+    (keyword, text)
+}
+
+/// The position word and text of a `note on link`, the lines of one spanning
+/// several trimmed and blank ones dropped
+fn link_note(pair: pest::iterators::Pair<'_, Rule>) -> (Option<&str>, Cow<'_, str>) {
+    pair.into_inner().fold(
+        (None, Cow::Borrowed("")),
+        |(position, text): (Option<&str>, Cow<str>), p: pest::iterators::Pair<Rule>| match p
+            .as_rule()
+        {
+            Rule::note_position => (Some(p.as_str()), text),
+            Rule::note_lines => (
+                position,
+                Cow::Owned(
+                    p.as_str()
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line: &&str| !line.is_empty())
+                        .collect::<Vec<&str>>()
+                        .join("\n"),
+                ),
+            ),
+            _ => (position, Cow::Borrowed(p.as_str())),
+        },
+    )
+}
+
+/// A legend's text, its lines trimmed, and where its position words place it
+fn legend(pair: pest::iterators::Pair<'_, Rule>) -> Legend {
+    pair.into_inner().fold(
+        Legend::default(),
+        |mut legend: Legend, p: pest::iterators::Pair<Rule>| {
+            match (p.as_rule(), p.as_str()) {
+                (Rule::legend_position, "top") => legend.vertical = VerticalPosition::Top,
+                (Rule::legend_position, "bottom") => legend.vertical = VerticalPosition::Bottom,
+                (Rule::legend_position, "left") => legend.horizontal = HorizontalPosition::Left,
+                (Rule::legend_position, "right") => legend.horizontal = HorizontalPosition::Right,
+                (Rule::legend_position, _) => legend.horizontal = HorizontalPosition::Center,
+                (_, text) => {
+                    legend.text = text
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line: &&str| !line.is_empty())
+                        .collect::<Vec<&str>>()
+                        .join("\n")
+                }
+            }
+            legend
+        },
+    )
+}
+
+/// The flow a direction statement sets
+fn direction(pair: pest::iterators::Pair<'_, Rule>) -> LayoutDirection {
+    match pair.as_str().starts_with("left") {
+        true => LayoutDirection::LeftToRight,
+        false => LayoutDirection::TopToBottom,
+    }
+}
+
+/// An arrow as plain text, with what its brackets held and the direction
+/// it gives when it has any
+fn arrow(
+    pair: pest::iterators::Pair<'_, Rule>,
+) -> (Cow<'_, str>, Option<&str>, Option<EdgeDirection>) {
+    if pair.as_rule() != Rule::styled_arrow {
+        return (Cow::Borrowed(pair.as_str()), None, None);
+    }
+    let mut plain: String = String::new();
+    let (mut style, mut direction): (Option<&str>, Option<EdgeDirection>) = (None, None);
+    pair.into_inner()
+        .for_each(|p: pest::iterators::Pair<Rule>| match p.as_rule() {
+            Rule::arrow_style => style = Some(p.as_str().trim()),
+            Rule::arrow_hint => {
+                direction = Some(match &p.as_str()[..1] {
+                    "u" => EdgeDirection::Up,
+                    "d" => EdgeDirection::Down,
+                    "l" => EdgeDirection::Left,
+                    _ => EdgeDirection::Right,
+                })
+            }
+            _ => plain.push_str(p.as_str()),
+        });
+    (Cow::Owned(plain), style, direction)
+}
+
+/// What the brackets of a generic class hold, `Map<K, V>` giving `K, V`
+fn type_parameters(generics: &str) -> &str {
+    generics
+        .strip_prefix('<')
+        .and_then(|rest: &str| rest.strip_suffix('>'))
+        .unwrap_or(generics)
+        .trim()
+}
+
+fn parse_stereotype(pair: pest::iterators::Pair<'_, Rule>) -> AstStereotype<'_> {
+    let mut stereotype: AstStereotype = AstStereotype::default();
+    pair.into_inner()
+        .flat_map(|p: pest::iterators::Pair<Rule>| match p.as_rule() {
+            Rule::spot => p.into_inner().collect(),
+            _ => vec![p],
+        })
+        .for_each(|p: pest::iterators::Pair<Rule>| {
+            let text: Option<Cow<str>> = Some(Cow::Borrowed(p.as_str().trim()));
+            match p.as_rule() {
+                Rule::spot_char => stereotype.spot = text,
+                Rule::spot_color => stereotype.spot_color = text,
+                _ => stereotype.name = text,
+            }
+        });
+    stereotype
+}
+
+/// An arrow whose line is longer than `--` or `..` as the one it draws
+/// longer, with how many ranks it spans: `--->` is `-->` spanning 2
+fn shorten(arrow: Cow<'_, str>) -> (Cow<'_, str>, Option<usize>) {
+    let line: char = if arrow.contains('.') { '.' } else { '-' };
+    let dashes: usize = arrow.matches(line).count();
+    if dashes < 3 {
+        return (arrow, None);
+    }
+    (
+        Cow::Owned(arrow.replacen(
+            &line.to_string().repeat(dashes),
+            &line.to_string().repeat(2),
+            1,
+        )),
+        Some(dashes - 1),
+    )
+}
+
+/// A relation's end, and the member qualifying it in `Order::customer`
+fn endpoint(pair: pest::iterators::Pair<'_, Rule>) -> (&str, Option<&str>) {
+    match pair.as_rule() {
+        Rule::qualified => match pair.as_str().split_once("::") {
+            Some((class, port)) => (class, Some(port)),
+            None => (pair.as_str(), None),
+        },
+        _ => (pair.as_str(), None),
+    }
+}
+
+/// The quoted multiplicity next to a relation's end, if the next part is one
+fn cardinality<'a>(inner: &mut pest::iterators::Pairs<'a, Rule>) -> Option<&'a str> {
+    inner
+        .peek()
+        .filter(|p: &pest::iterators::Pair<Rule>| p.as_rule() == Rule::cardinality)?;
+    inner
+        .next()
+        .map(|p: pest::iterators::Pair<Rule>| p.as_str().trim_matches('"'))
+}
+
+/// The single pair a successful parse yields
+fn first(
+    mut pairs: pest::iterators::Pairs<'_, Rule>,
+) -> Result<pest::iterators::Pair<'_, Rule>, PlantUmlParseError> {
+    pairs
+        .next()
+        .ok_or_else(|| PlantUmlParseError::Internal("empty parse".into()))
+}
+
+/// The next part of a statement, which the grammar always provides; should
+/// the two ever disagree, the statement is reported instead of panicking
+fn part<'a>(
+    inner: &mut pest::iterators::Pairs<'a, Rule>,
+    what: &str,
+    (line, column): (usize, usize),
+) -> Result<pest::iterators::Pair<'a, Rule>, PlantUmlParseError> {
+    inner.next().ok_or_else(|| PlantUmlParseError::Syntax {
+        message: format!("expected {}", what),
+        line,
+        column,
+    })
+}
+
+/// The keyword and condition of a fragment's first line, such as
+/// `alt found`
+fn fragment_header<'a>(
+    header: pest::iterators::Pair<'a, Rule>,
+    at: (usize, usize),
+) -> Result<(&'a str, Option<&'a str>), PlantUmlParseError> {
+    let mut inner: pest::iterators::Pairs<Rule> = header.clone().into_inner();
+    let keyword: &str = part(&mut inner, "a fragment keyword", at)?.as_str();
+    Ok((keyword, condition_of(header)))
+}
+
+/// The condition a fragment header or `else` line carries, if any
+fn condition_of(pair: pest::iterators::Pair<'_, Rule>) -> Option<&str> {
+    pair.into_inner()
+        .find(|p: &pest::iterators::Pair<Rule>| p.as_rule() == Rule::condition)
+        .map(|p: pest::iterators::Pair<Rule>| p.as_str())
+}
+
+/// The keyword, name and alias of a use case or actor declared by its name
+/// alone, such as `(Log in) as UC1`
+fn shorthand(
+    pair: pest::iterators::Pair<'_, Rule>,
+    at: (usize, usize),
+) -> Result<(&'static str, &str, Option<&str>), PlantUmlParseError> {
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    let name: pest::iterators::Pair<Rule> = part(&mut inner, "a name", at)?;
+    let keyword: &str = match name.as_rule() {
+        Rule::actor_name => "actor",
+        _ => "usecase",
+    };
+    let alias: Option<&str> = inner
+        .next()
+        .map(|p: pest::iterators::Pair<Rule>| p.as_str());
+    Ok((keyword, usecase::name(name.as_str()), alias))
+}
+
+/// The keyword an activity block is read by: `if`, `while` or `fork`
+/// The two classes of `(A, B) .. C` and the one attached to their relation,
+/// which may also come first
+fn association_class(
+    pair: pest::iterators::Pair<'_, Rule>,
+    at: (usize, usize),
+) -> Result<(&str, &str, &str), PlantUmlParseError> {
+    let class_first: bool = !pair.as_str().starts_with('(');
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    let first: &str = part(&mut inner, "a class", at)?.as_str();
+    let second: &str = part(&mut inner, "a class", at)?.as_str();
+    let third: &str = part(&mut inner, "a class", at)?.as_str();
+
+    Ok(match class_first {
+        true => (second, third, first),
+        false => (first, second, third),
+    })
+}
+
+fn control_keyword(pair: &pest::iterators::Pair<'_, Rule>) -> &'static str {
+    match pair.as_rule() {
+        Rule::activity_while => "while",
+        Rule::activity_fork => "fork",
+        _ => "if",
+    }
+}
+
+/// The guard and flow label of a line opening or closing an activity
+/// branch: `if (paid?) then (yes)` has both, `else (no)` and
+/// `endwhile (done)` only a label
+fn branch_header(pair: pest::iterators::Pair<'_, Rule>) -> (Option<&str>, Option<&str>) {
+    let rule: Rule = pair.as_rule();
+    let guards: Vec<&str> = pair
+        .into_inner()
+        .filter(|p: &pest::iterators::Pair<Rule>| p.as_rule() == Rule::guard)
+        .map(|p: pest::iterators::Pair<Rule>| p.as_str().trim())
+        .collect();
+    match rule {
+        Rule::if_header | Rule::elseif_header | Rule::while_header => {
+            (guards.first().copied(), guards.get(1).copied())
         }
+        _ => (None, guards.first().copied()),
+    }
+}
+
+/// The definitions of the classes declared in a class body
+fn inner_classes<'a>(
+    body: pest::iterators::Pair<'a, Rule>,
+) -> impl Iterator<Item = pest::iterators::Pair<'a, Rule>> {
+    body.into_inner()
+        .filter(|p: &pest::iterators::Pair<Rule>| p.as_rule() == Rule::inner_class)
+        .filter_map(|p: pest::iterators::Pair<Rule>| {
+            p.into_inner()
+                .find(|p: &pest::iterators::Pair<Rule>| p.as_rule() == Rule::definition)
+        })
+}
+
+/// The members of a class body, separator lines included; in an entity,
+/// those above the first separator form its primary key, which stands for
+/// that line, and in an object or map, fields and keys are split from their
+/// values
+fn parse_body<'a>(body: pest::iterators::Pair<'a, Rule>, keyword: &str) -> Vec<AstMember<'a>> {
+    let pairs: Vec<pest::iterators::Pair<Rule>> = body.into_inner().collect();
+    let assignment: Option<&str> = match keyword {
+        "object" => Some("="),
+        "map" => Some("=>"),
+        _ => None,
+    };
+    let key: Option<usize> = match keyword {
+        "entity" => pairs
+            .iter()
+            .position(|p: &pest::iterators::Pair<Rule>| p.as_rule() == Rule::separator),
         _ => None,
+    };
+
+    pairs
+        .into_iter()
+        .enumerate()
+        .flat_map(
+            |(position, p): (usize, pest::iterators::Pair<Rule>)| match p.as_rule() {
+                Rule::member => vec![(position, p)],
+                Rule::separator if Some(position) != key => vec![(position, p)],
+                Rule::enum_values => p
+                    .into_inner()
+                    .map(|value: pest::iterators::Pair<Rule>| (position, value))
+                    .collect(),
+                _ => Vec::new(),
+            },
+        )
+        .map(|(position, p): (usize, pest::iterators::Pair<Rule>)| {
+            match p.as_rule() {
+                Rule::separator => return separator(p),
+                Rule::enum_value => return enum_value(p),
+                _ => {}
+            }
+            let text: &str = p.as_str();
+            let mut member: AstMember = parse_member(p);
+            if key.is_some_and(|key: usize| position < key) {
+                member.modifiers.push(Cow::Borrowed("primary_key"));
+            }
+            // A value may hold colons or braces, so the whole line is split
+            if let Some((name, value)) =
+                assignment.and_then(|operator: &str| text.split_once(operator))
+            {
+                member.name = Cow::Borrowed(name.trim());
+                member.type_name = None;
+                member.value = Some(Cow::Borrowed(value.trim().trim_matches('"')));
+            }
+            member
+        })
+        .collect()
+}
+
+/// One of an enum's values, its arguments kept as written
+pub(crate) fn enum_value(pair: pest::iterators::Pair<'_, Rule>) -> AstMember<'_> {
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    AstMember {
+        modifiers: Vec::new(),
+        visibility: None,
+        name: inner
+            .next()
+            .map_or(Cow::Borrowed(""), |p: pest::iterators::Pair<Rule>| {
+                Cow::Borrowed(p.as_str())
+            }),
+        parameters: None,
+        type_name: None,
+        value: inner
+            .next()
+            .map(|p: pest::iterators::Pair<Rule>| Cow::Borrowed(p.as_str().trim())),
+        separator: None,
+        enum_value: true,
     }
 }
 
+/// A separator line, as a member whose name is its title
+fn separator(pair: pest::iterators::Pair<'_, Rule>) -> AstMember<'_> {
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    AstMember {
+        modifiers: Vec::new(),
+        visibility: None,
+        separator: inner
+            .next()
+            .map(|p: pest::iterators::Pair<Rule>| Cow::Borrowed(p.as_str())),
+        name: inner
+            .next()
+            .map_or(Cow::Borrowed(""), |p: pest::iterators::Pair<Rule>| {
+                Cow::Borrowed(p.as_str().trim())
+            }),
+        parameters: None,
+        type_name: None,
+        value: None,
+        enum_value: false,
+    }
+}
+
+/// Reads a class body line such as `+name : String`, `{static} count` or
+/// `-rename(from, to) : bool`. A `{field}` modifier keeps a name with
+/// parentheses from being read as a method.
+pub(crate) fn parse_member(pair: pest::iterators::Pair<'_, Rule>) -> AstMember<'_> {
+    let mut member: AstMember = AstMember {
+        modifiers: Vec::new(),
+        visibility: None,
+        name: Cow::Borrowed(""),
+        parameters: None,
+        type_name: None,
+        value: None,
+        separator: None,
+        enum_value: false,
+    };
+    let mut signature: Option<pest::iterators::Pair<Rule>> = None;
+
+    pair.into_inner()
+        .for_each(|p: pest::iterators::Pair<Rule>| match p.as_rule() {
+            Rule::modifier => member.modifiers.extend(
+                p.into_inner()
+                    .map(|m: pest::iterators::Pair<Rule>| Cow::Borrowed(m.as_str().trim())),
+            ),
+            Rule::visibility => member.visibility = p.as_str().chars().next(),
+            Rule::mandatory => member.modifiers.push(Cow::Borrowed("mandatory")),
+            Rule::member_name => member.name = Cow::Borrowed(p.as_str().trim()),
+            Rule::signature => signature = Some(p),
+            Rule::type_name => {
+                member.type_name = Some(p.as_str().trim())
+                    .filter(|t: &&str| !t.is_empty())
+                    .map(Cow::Borrowed)
+            }
+            _ => {}
+        });
+
+    if let Some(signature) = signature {
+        if member.has_modifier("field") {
+            member.name = Cow::Borrowed(signature.as_str().trim());
+        } else {
+            let text: &str = signature.as_str();
+            let mut inner: pest::iterators::Pairs<Rule> = signature.into_inner();
+            member.name = Cow::Borrowed(
+                inner
+                    .next()
+                    .map_or(text, |p: pest::iterators::Pair<Rule>| p.as_str())
+                    .trim(),
+            );
+            member.parameters = Some(
+                inner
+                    .map(|p: pest::iterators::Pair<Rule>| Cow::Borrowed(p.as_str().trim()))
+                    .collect(),
+            );
+        }
+    }
+    member
+}
+
 #[derive(Debug)]
 pub enum PlantUmlParseError {
     Syntax {
@@ -87,6 +1182,10 @@ pub enum PlantUmlParseError {
         column: usize,
     },
     Internal(String),
+    /// The source is bigger than the configured `Limits` allow
+    LimitExceeded(String),
+    /// The source uses what the chosen `Profile` does not allow
+    Disallowed(String),
 }
 
 impl From<pest::error::Error<Rule>> for PlantUmlParseError {
@@ -105,3 +1204,93 @@ impl From<pest::error::Error<Rule>> for PlantUmlParseError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use lib_core::entities::member::MemberKind;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_borrows_text_from_the_input() {
+        let ast: Vec<AstNode> =
+            parse_plantuml("@startuml\nclass \"User\" as U {\n  +name\n}\n@enduml").unwrap();
+
+        let AstNode::Definition {
+            name,
+            alias,
+            members,
+            ..
+        } = &ast[0]
+        else {
+            panic!("expected a definition");
+        };
+        assert!(matches!(name, Cow::Borrowed("User")));
+        assert!(matches!(alias, Some(Cow::Borrowed("U"))));
+        assert!(matches!(members[0].name, Cow::Borrowed("name")));
+    }
+
+    #[test]
+    fn test_arena_keeps_packages_flat() {
+        let arena: AstArena = parse_plantuml_arena(
+            "@startuml\npackage \"Core\" {\n  class User {\n    +name\n  }\n  User --> Order\n}\nclass Order\n@enduml",
+        )
+        .unwrap();
+
+        let roots: Vec<usize> = arena.roots().collect();
+        assert_eq!(roots, vec![0, 3]);
+        assert_eq!(arena.children(0).collect::<Vec<usize>>(), vec![1, 2]);
+        let ArenaNode::Definition { members, .. } = arena.get(1) else {
+            panic!("expected a definition");
+        };
+        assert_eq!(arena.members(members.clone())[0].name, "name");
+    }
+
+    fn members(body: &str) -> Vec<AstMember<'_>> {
+        let ast: Vec<AstNode> = parse_plantuml(body).unwrap();
+        let AstNode::Definition { members, .. } = ast.into_iter().next().unwrap() else {
+            panic!("expected a definition");
+        };
+        members
+    }
+
+    #[test]
+    fn test_parameters_keep_their_commas_and_parentheses() {
+        let parsed: Vec<AstMember> = members(
+            "@startuml\nclass Cache {\n  +put(key : Map<K, V>, ttl = max(1, 2), f : Fn(A, B)) : Option<V>\n}\n@enduml",
+        );
+
+        assert_eq!(parsed[0].name, "put");
+        assert_eq!(parsed[0].visibility, Some('+'));
+        assert_eq!(
+            parsed[0].parameters,
+            Some(vec![
+                Cow::Borrowed("key : Map<K, V>"),
+                Cow::Borrowed("ttl = max(1, 2)"),
+                Cow::Borrowed("f : Fn(A, B)")
+            ])
+        );
+        assert_eq!(parsed[0].type_name.as_deref(), Some("Option<V>"));
+    }
+
+    #[test]
+    fn test_reads_modifiers_and_field_types_with_parentheses() {
+        let parsed: Vec<AstMember> = members(
+            "@startuml\nclass Job {\n  {static} {abstract} -run()\n  callback : Fn(Job)\n  {field} handler(x)\n}\n@enduml",
+        );
+
+        assert_eq!(
+            parsed[0].modifiers,
+            vec![Cow::Borrowed("static"), Cow::Borrowed("abstract")]
+        );
+        assert_eq!(parsed[0].parameters, Some(Vec::new()));
+        assert_eq!(parsed[1].name, "callback");
+        assert_eq!(parsed[1].parameters, None);
+        assert_eq!(parsed[1].type_name.as_deref(), Some("Fn(Job)"));
+        assert_eq!(parsed[2].name, "handler(x)");
+        assert_eq!(parsed[2].kind(), MemberKind::Field);
+    }
+}
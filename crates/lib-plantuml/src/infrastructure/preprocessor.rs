@@ -0,0 +1,575 @@
+//! The preprocessing pass run before parsing: `!include` and
+//! `!include_many` lines are replaced by the files they name, `!define` and
+//! `!$name = value` set variables substituted into later lines, and
+//! `!if`/`!ifdef`/`!ifndef` ... `!else` ... `!endif` blocks keep only the
+//! branch that holds. Files come from an [`IncludeLoader`], so tests and
+//! hosts without a file system can serve them from memory.
+
+use std::{borrow::Cow, collections::HashMap, fs, io, path::PathBuf, sync::Arc};
+
+use crate::infrastructure::parser::PlantUmlParseError;
+
+/// How deep included files may include others, which also stops cycles
+const MAX_DEPTH: usize = 16;
+
+/// Reads the files a diagram includes
+pub trait IncludeLoader: Send + Sync {
+    fn load(&self, path: &str) -> io::Result<String>;
+}
+
+/// Reads included files from disk, relative to a directory
+#[derive(Debug, Clone, Default)]
+pub struct FileLoader {
+    root: PathBuf,
+}
+
+impl FileLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl IncludeLoader for FileLoader {
+    fn load(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(self.root.join(path))
+    }
+}
+
+/// Serves included files by path, for tests and hosts without a file system
+impl IncludeLoader for HashMap<String, String> {
+    fn load(&self, path: &str) -> io::Result<String> {
+        self.get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no file `{}`", path)))
+    }
+}
+
+/// Where a line of the expanded source was written
+#[derive(Debug, Clone, PartialEq)]
+pub struct Origin {
+    /// The included file, none for the diagram itself
+    pub file: Option<String>,
+    pub line: usize,
+    /// The diagram's own line, that of the `!include` for an included one
+    pub root: usize,
+}
+
+impl Origin {
+    /// A message told where it applies when that is in an included file
+    fn describe(&self, message: String) -> String {
+        match &self.file {
+            Some(file) => format!("{} (in `{}` at line {})", message, file, self.line),
+            None => message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreprocessError {
+    /// An included file could not be read
+    Include {
+        path: String,
+        reason: String,
+        at: Origin,
+    },
+    /// Files include each other deeper than the preprocessor allows
+    TooDeep { path: String, at: Origin },
+    /// An `!elseif`, `!else` or `!endif` without its `!if`, or an `!if`
+    /// its file does not close
+    Unbalanced { directive: String, at: Origin },
+}
+
+impl From<PreprocessError> for PlantUmlParseError {
+    fn from(err: PreprocessError) -> Self {
+        let (message, at): (String, Origin) = match err {
+            PreprocessError::Include { path, reason, at } => {
+                (format!("Could not include `{}`: {}", path, reason), at)
+            }
+            PreprocessError::TooDeep { path, at } => {
+                return PlantUmlParseError::LimitExceeded(at.describe(format!(
+                    "`{}` is included more than {} files deep",
+                    path, MAX_DEPTH
+                )));
+            }
+            PreprocessError::Unbalanced { directive, at } => {
+                let message: String = match directive.as_str() {
+                    "if" | "ifdef" | "ifndef" => {
+                        format!("`!{}` is never closed by `!endif`", directive)
+                    }
+                    _ => format!("`!{}` has no `!if` to belong to", directive),
+                };
+                (message, at)
+            }
+        };
+        PlantUmlParseError::Syntax {
+            message: at.describe(message),
+            line: at.root,
+            column: 1,
+        }
+    }
+}
+
+/// A source with its includes replaced by what they name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Expanded {
+    pub text: String,
+    origins: Vec<Origin>,
+}
+
+impl Expanded {
+    /// Where a line of the expanded text, counted from 1, comes from
+    fn origin(&self, line: usize) -> Option<&Origin> {
+        self.origins.get(line.checked_sub(1)?)
+    }
+
+    /// The diagram's line for an expanded one, and a message naming the
+    /// included file it came from, if it did
+    pub(crate) fn locate(&self, line: usize, message: String) -> (usize, String) {
+        match self.origin(line) {
+            Some(origin) => (origin.root, origin.describe(message)),
+            None => (line, message),
+        }
+    }
+}
+
+/// Runs the preprocessing pass over a source. Variables given up front act
+/// as if the source set them first. Without a loader, `!include` lines are
+/// kept for the reader, which skips them with a warning.
+#[derive(Clone, Default)]
+pub struct Preprocessor {
+    loader: Option<Arc<dyn IncludeLoader>>,
+    variables: HashMap<String, String>,
+}
+
+impl Preprocessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_loader(mut self, loader: impl IncludeLoader + 'static) -> Self {
+        self.loader = Some(Arc::new(loader));
+        self
+    }
+
+    /// Sets a variable, named as the source refers to it (`$version`, or
+    /// `VERSION` for one `!define` would set)
+    pub fn with_variable(mut self, name: &str, value: &str) -> Self {
+        self.variables.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Whether the pass can change a source at all: it holds a directive,
+    /// all of which start with `!`, or variables are set up front
+    pub(crate) fn changes(&self, input: &str) -> bool {
+        !self.variables.is_empty() || input.contains('!')
+    }
+
+    /// The source as the parser should read it
+    pub fn process(&self, input: &str) -> Result<String, PreprocessError> {
+        self.expand(input).map(|expanded: Expanded| expanded.text)
+    }
+
+    /// The processed source, with where each of its lines comes from
+    pub(crate) fn expand(&self, input: &str) -> Result<Expanded, PreprocessError> {
+        let mut run: Run = Run {
+            loader: self.loader.as_deref(),
+            variables: self.variables.clone(),
+            included: Vec::new(),
+            conditions: Vec::new(),
+            expanded: Expanded::default(),
+        };
+        run.file(input, None, None, 0)?;
+        Ok(run.expanded)
+    }
+}
+
+/// A conditional block being read
+struct Condition {
+    /// Whether the branch being read holds
+    active: bool,
+    /// Whether any branch so far held, so later ones are skipped
+    taken: bool,
+    directive: String,
+    at: Origin,
+}
+
+/// The state of one pass over a source and the files it includes
+struct Run<'a> {
+    loader: Option<&'a dyn IncludeLoader>,
+    variables: HashMap<String, String>,
+    included: Vec<String>,
+    conditions: Vec<Condition>,
+    expanded: Expanded,
+}
+
+impl Run<'_> {
+    fn file(
+        &mut self,
+        input: &str,
+        file: Option<&str>,
+        root: Option<usize>,
+        depth: usize,
+    ) -> Result<(), PreprocessError> {
+        let open: usize = self.conditions.len();
+
+        for (index, line) in input.lines().enumerate() {
+            let at: Origin = Origin {
+                file: file.map(str::to_string),
+                line: index + 1,
+                root: root.unwrap_or(index + 1),
+            };
+            let trimmed: &str = line.trim();
+            // An included file's own diagram markers would end the diagram early
+            if file.is_some()
+                && (trimmed.starts_with("@startuml") || trimmed.starts_with("@enduml"))
+            {
+                continue;
+            }
+            match trimmed.strip_prefix('!') {
+                Some(directive) => self.directive(directive, line, at, depth)?,
+                None if self.active() => self.keep(line, at),
+                None => {}
+            }
+        }
+
+        match self.conditions.len() > open {
+            true => {
+                let condition: Condition = self.conditions.swap_remove(open);
+                Err(PreprocessError::Unbalanced {
+                    directive: condition.directive,
+                    at: condition.at,
+                })
+            }
+            false => Ok(()),
+        }
+    }
+
+    /// Whether every conditional block being read is in a branch that holds
+    fn active(&self) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition: &Condition| condition.active)
+    }
+
+    fn keep(&mut self, line: &str, at: Origin) {
+        self.expanded
+            .text
+            .push_str(&substitute(line, &self.variables));
+        self.expanded.text.push('\n');
+        self.expanded.origins.push(at);
+    }
+
+    fn directive(
+        &mut self,
+        directive: &str,
+        line: &str,
+        at: Origin,
+        depth: usize,
+    ) -> Result<(), PreprocessError> {
+        let (keyword, rest): (&str, &str) = directive
+            .split_once(char::is_whitespace)
+            .map_or((directive, ""), |(keyword, rest): (&str, &str)| {
+                (keyword, rest.trim())
+            });
+
+        match keyword {
+            "if" | "ifdef" | "ifndef" => {
+                let holds: bool = self.active() && self.holds(keyword, rest);
+                self.conditions.push(Condition {
+                    active: holds,
+                    taken: holds,
+                    directive: keyword.to_string(),
+                    at,
+                });
+            }
+            "elseif" | "else" => {
+                let holds: bool = keyword == "else" || self.holds("if", rest);
+                let Some(condition) = self.conditions.last_mut() else {
+                    return Err(PreprocessError::Unbalanced {
+                        directive: keyword.to_string(),
+                        at,
+                    });
+                };
+                condition.active = !condition.taken && holds;
+                condition.taken |= holds;
+            }
+            "endif" => {
+                if self.conditions.pop().is_none() {
+                    return Err(PreprocessError::Unbalanced {
+                        directive: keyword.to_string(),
+                        at,
+                    });
+                }
+            }
+            _ if !self.active() => {}
+            "define" => {
+                let (name, value): (&str, &str) =
+                    rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                if is_name(name) {
+                    let value: String = substitute(value.trim(), &self.variables).into_owned();
+                    self.variables.insert(name.to_string(), value);
+                } else {
+                    self.keep(line, at);
+                }
+            }
+            "undef" => {
+                self.variables.remove(rest);
+            }
+            "include" | "include_many" if !rest.starts_with('<') => match self.loader {
+                Some(loader) => self.include(loader, keyword == "include_many", rest, at, depth)?,
+                None => self.keep(line, at),
+            },
+            _ => match assignment(directive) {
+                Some((name, value, fallback)) => {
+                    if !(fallback && self.variables.contains_key(name)) {
+                        let value: String = substitute(value, &self.variables).into_owned();
+                        self.variables.insert(name.to_string(), value);
+                    }
+                }
+                None => self.keep(line, at),
+            },
+        }
+        Ok(())
+    }
+
+    /// Reads the file an include names in place of the include. A file is
+    /// included once however often `!include` names it, as many times as
+    /// `!include_many` does.
+    fn include(
+        &mut self,
+        loader: &dyn IncludeLoader,
+        many: bool,
+        path: &str,
+        at: Origin,
+        depth: usize,
+    ) -> Result<(), PreprocessError> {
+        let path: Cow<str> = substitute(path, &self.variables);
+        if !many && self.included.iter().any(|done: &String| *done == path) {
+            return Ok(());
+        }
+        if depth >= MAX_DEPTH {
+            return Err(PreprocessError::TooDeep {
+                path: path.into_owned(),
+                at,
+            });
+        }
+
+        let content: String =
+            loader
+                .load(&path)
+                .map_err(|e: io::Error| PreprocessError::Include {
+                    path: path.to_string(),
+                    reason: e.to_string(),
+                    at: at.clone(),
+                })?;
+        self.included.push(path.to_string());
+        self.file(&content, Some(&path), Some(at.root), depth + 1)
+    }
+
+    /// Whether the condition of an `!if`, `!ifdef` or `!ifndef` holds
+    fn holds(&self, keyword: &str, condition: &str) -> bool {
+        match keyword {
+            "ifdef" => self.variables.contains_key(condition),
+            "ifndef" => !self.variables.contains_key(condition),
+            _ => {
+                let condition: Cow<str> = substitute(condition, &self.variables);
+                let condition: &str = condition.trim();
+                let condition: &str = condition
+                    .strip_prefix('(')
+                    .and_then(|inner: &str| inner.strip_suffix(')'))
+                    .unwrap_or(condition);
+                if let Some(name) = condition
+                    .strip_prefix("%variable_exists(")
+                    .and_then(|rest: &str| rest.strip_suffix(')'))
+                {
+                    return self.variables.contains_key(unquote(name));
+                }
+                match (condition.split_once("=="), condition.split_once("!=")) {
+                    (Some((left, right)), _) => unquote(left) == unquote(right),
+                    (_, Some((left, right))) => unquote(left) != unquote(right),
+                    _ => !matches!(unquote(condition), "" | "0" | "false"),
+                }
+            }
+        }
+    }
+}
+
+/// The name, value and whether it only sets an unset variable, of an
+/// assignment such as `$name = "Bob"` or `$name ?= "Bob"`
+fn assignment(directive: &str) -> Option<(&str, &str, bool)> {
+    let (name, value): (&str, &str) = directive.split_once('=')?;
+    let (name, fallback): (&str, bool) = match name.trim_end().strip_suffix('?') {
+        Some(name) => (name.trim_end(), true),
+        None => (name.trim_end(), false),
+    };
+    (name.starts_with('$') && is_name(&name[1..])).then(|| (name, unquote(value), fallback))
+}
+
+/// Whether a word can name a variable
+fn is_name(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c: char| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A value without the spaces and double quotes around it
+fn unquote(value: &str) -> &str {
+    let value: &str = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|inner: &str| inner.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// A line with the variables it names replaced by their values: `$name`
+/// wherever it stands, a name `!define` set as a whole word
+fn substitute<'a>(line: &'a str, variables: &HashMap<String, String>) -> Cow<'a, str> {
+    if variables.is_empty() {
+        return Cow::Borrowed(line);
+    }
+    let word = |c: char| -> bool { c.is_ascii_alphanumeric() || c == '_' };
+    let mut output: String = String::with_capacity(line.len());
+    let mut rest: &str = line;
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = rest.chars().next() {
+        // `$name` may follow a word, a name `!define` set may not
+        let starts: bool = c == '$' || word(c) && !previous.is_some_and(word);
+        if !starts {
+            output.push(c);
+            previous = Some(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let len: usize = c.len_utf8()
+            + rest[c.len_utf8()..]
+                .find(|c: char| !word(c))
+                .unwrap_or(rest.len() - c.len_utf8());
+        let token: &str = &rest[..len];
+        output.push_str(variables.get(token).map_or(token, String::as_str));
+        previous = token.chars().last();
+        rest = &rest[len..];
+    }
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn files(files: &[(&str, &str)]) -> HashMap<String, String> {
+        files
+            .iter()
+            .map(|(path, text): &(&str, &str)| (path.to_string(), text.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_replaces_includes_with_their_files() {
+        let loader: HashMap<String, String> = files(&[
+            (
+                "common.puml",
+                "@startuml\nclass Base\n!include types.puml\n@enduml\n",
+            ),
+            ("types.puml", "class Id\n"),
+        ]);
+        let input: &str = "@startuml\n!include common.puml\n!include types.puml\n!include_many types.puml\n!include <archimate/Archimate>\nclass User\n@enduml\n";
+
+        let expanded: Expanded = Preprocessor::new()
+            .with_loader(loader)
+            .expand(input)
+            .unwrap();
+
+        assert_eq!(
+            expanded.text,
+            "@startuml\nclass Base\nclass Id\nclass Id\n!include <archimate/Archimate>\nclass User\n@enduml\n"
+        );
+        assert_eq!(
+            expanded.locate(3, "oops".to_string()),
+            (2, "oops (in `types.puml` at line 1)".to_string())
+        );
+        assert_eq!(
+            expanded.locate(4, "oops".to_string()),
+            (4, "oops (in `types.puml` at line 1)".to_string())
+        );
+        assert_eq!(
+            expanded.locate(6, "oops".to_string()),
+            (6, "oops".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reports_missing_and_endless_includes() {
+        let preprocessor: Preprocessor =
+            Preprocessor::new().with_loader(files(&[("loop.puml", "!include_many loop.puml\n")]));
+
+        assert_eq!(
+            preprocessor.process("@startuml\n  !include missing.puml\n@enduml\n"),
+            Err(PreprocessError::Include {
+                path: "missing.puml".to_string(),
+                reason: "no file `missing.puml`".to_string(),
+                at: Origin {
+                    file: None,
+                    line: 2,
+                    root: 2
+                },
+            })
+        );
+        assert!(matches!(
+            preprocessor.process("@startuml\n!include loop.puml\n@enduml\n"),
+            Err(PreprocessError::TooDeep { .. })
+        ));
+    }
+
+    #[test]
+    fn test_substitutes_variables() {
+        let input: &str = "@startuml\n!define PREFIX Shop\n!$entity = \"Order\"\n!$entity ?= \"Ignored\"\n!$table = $entity\nclass PREFIX_$entity\nclass PREFIX$table\n' $version stays\n!undef PREFIX\nclass PREFIX\n@enduml\n";
+
+        assert_eq!(
+            Preprocessor::new().process(input),
+            Ok("@startuml\nclass PREFIX_Order\nclass ShopOrder\n' $version stays\nclass PREFIX\n@enduml\n".to_string())
+        );
+        assert_eq!(
+            Preprocessor::new()
+                .with_variable("$version", "2")
+                .process("@startuml\nclass V$version\n@enduml"),
+            Ok("@startuml\nclass V2\n@enduml\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_keeps_the_branch_that_holds() {
+        let input: &str = "@startuml\n!$mode = \"full\"\n!if $mode == \"full\"\nclass Detail\n!ifdef DEBUG\nclass Trace\n!else\nclass Release\n!endif\n!elseif $mode == \"short\"\nclass Short\n!else\n!define NEVER 1\n!endif\n!ifndef NEVER\nclass Always\n!endif\n!if 0\nclass Off\n!endif\n@enduml\n";
+
+        assert_eq!(
+            Preprocessor::new().process(input),
+            Ok("@startuml\nclass Detail\nclass Release\nclass Always\n@enduml\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reports_unbalanced_conditions() {
+        let unclosed: Result<String, PreprocessError> =
+            Preprocessor::new().process("@startuml\n!if 1\nclass A\n@enduml\n");
+        let stray: Result<String, PreprocessError> =
+            Preprocessor::new().process("@startuml\nclass A\n!endif\n@enduml\n");
+
+        match PlantUmlParseError::from(unclosed.unwrap_err()) {
+            PlantUmlParseError::Syntax { message, line, .. } => {
+                assert_eq!(message, "`!if` is never closed by `!endif`");
+                assert_eq!(line, 2);
+            }
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+        match PlantUmlParseError::from(stray.unwrap_err()) {
+            PlantUmlParseError::Syntax { message, line, .. } => {
+                assert_eq!(message, "`!endif` has no `!if` to belong to");
+                assert_eq!(line, 3);
+            }
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use lib_core::entities::graph::Graph;
+
+use crate::infrastructure::parser::PlantUmlParseError;
+
+/// The PlantUML dialect a source is held to, as real files target different
+/// PlantUML versions and tools
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Profile {
+    /// Classic class diagram syntax only: directives such as `skinparam` and
+    /// relations to undeclared elements are errors
+    Strict,
+    /// Also accepts directives and undeclared elements, warning about them
+    #[default]
+    Permissive,
+}
+
+impl Profile {
+    /// Fails on the first warning of a built graph when the profile is strict
+    pub(crate) fn check(&self, graph: Graph, input: &str) -> Result<Graph, PlantUmlParseError> {
+        let (Profile::Strict, Some(warning)) = (self, graph.warnings.first()) else {
+            return Ok(graph);
+        };
+
+        Err(match warning.line {
+            Some(line) => PlantUmlParseError::Syntax {
+                message: warning.message.clone(),
+                line,
+                column: column(input, line),
+            },
+            None => PlantUmlParseError::Disallowed(warning.message.clone()),
+        })
+    }
+}
+
+/// Column of the first character of a line, past its indentation
+fn column(input: &str, line: usize) -> usize {
+    let text: &str = input
+        .lines()
+        .nth(line.saturating_sub(1))
+        .unwrap_or_default();
+    text.len() - text.trim_start().len() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::entities::{graph::Graph, warning::Warning};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn warned(line: Option<usize>) -> Graph {
+        Graph {
+            warnings: vec![Warning {
+                source: "plantuml".to_string(),
+                message: "`hide empty members` is not supported".to_string(),
+                line,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_only_the_strict_profile_fails_on_warnings() {
+        let input: &str = "@startuml\n  hide empty members\n@enduml\n";
+
+        assert!(Profile::Permissive.check(warned(Some(2)), input).is_ok());
+        match Profile::Strict.check(warned(Some(2)), input) {
+            Err(PlantUmlParseError::Syntax {
+                message,
+                line,
+                column,
+            }) => {
+                assert_eq!(message, "`hide empty members` is not supported");
+                assert_eq!((line, column), (2, 3));
+            }
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+        assert!(matches!(
+            Profile::Strict.check(warned(None), input),
+            Err(PlantUmlParseError::Disallowed(_))
+        ));
+        assert!(Profile::Strict.check(Graph::default(), input).is_ok());
+    }
+}
@@ -0,0 +1,156 @@
+use lib_core::adapters::graph_gateway::GraphGatewayError;
+
+use crate::infrastructure::{
+    incremental::TextEdit,
+    syntax::{self, SourceIndex, Span},
+};
+
+/// Edits renaming an element id at its declaration and wherever a relation
+/// or a `hide` or `show` command uses it. When the element is declared with
+/// an alias, the alias is what gets renamed; its display name stays. The new
+/// id must be one the grammar reads as an identifier, words of letters,
+/// digits and underscores joined by dots, and not already taken.
+pub fn rename(input: &str, id: &str, new_id: &str) -> Result<Vec<TextEdit>, GraphGatewayError> {
+    let identifier: bool = new_id.split('.').all(|word: &str| {
+        !word.is_empty()
+            && word
+                .chars()
+                .all(|c: char| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if !identifier {
+        return Err(GraphGatewayError::Semantic {
+            source: "plantuml".into(),
+            message: format!("'{}' is not a valid element id", new_id),
+        });
+    }
+
+    let index: SourceIndex = syntax::index_source(input)?;
+    if new_id != id && index.ids().contains(&new_id) {
+        return Err(GraphGatewayError::Semantic {
+            source: "plantuml".into(),
+            message: format!("'{}' is already an element id", new_id),
+        });
+    }
+
+    Ok(index
+        .find_references(id)
+        .into_iter()
+        .map(|span: Span| TextEdit {
+            span,
+            text: if input[span.start..span.end].starts_with('"') {
+                format!("\"{}\"", new_id)
+            } else {
+                new_id.to_string()
+            },
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn apply(input: &str, mut edits: Vec<TextEdit>) -> String {
+        let mut output: String = input.to_string();
+        edits.sort_by_key(|edit: &TextEdit| edit.span.start);
+        edits.iter().rev().for_each(|edit: &TextEdit| {
+            output.replace_range(edit.span.start..edit.span.end, &edit.text)
+        });
+        output
+    }
+
+    #[test]
+    fn test_renames_the_declaration_and_every_reference() {
+        let input: &str =
+            "@startuml\nclass User\nclass Order\nUser --> Order\nOrder --> User : owner\n@enduml";
+
+        let edits: Vec<TextEdit> = rename(input, "User", "Account").unwrap();
+
+        assert_eq!(edits.len(), 3);
+        assert_eq!(
+            apply(input, edits),
+            "@startuml\nclass Account\nclass Order\nAccount --> Order\nOrder --> Account : owner\n@enduml"
+        );
+    }
+
+    #[test]
+    fn test_renames_the_alias_and_keeps_the_display_name() {
+        let input: &str = "@startuml\npackage \"Core\" {\n  class \"User Account\" as U\n}\nU --> Profile\n@enduml";
+
+        let edits: Vec<TextEdit> = rename(input, "U", "User").unwrap();
+
+        assert_eq!(
+            apply(input, edits),
+            "@startuml\npackage \"Core\" {\n  class \"User Account\" as User\n}\nUser --> Profile\n@enduml"
+        );
+    }
+
+    #[test]
+    fn test_keeps_quotes_around_a_quoted_name() {
+        let input: &str = "@startuml\nclass \"User\"\n@enduml";
+
+        let edits: Vec<TextEdit> = rename(input, "User", "Account").unwrap();
+
+        assert_eq!(apply(input, edits), "@startuml\nclass \"Account\"\n@enduml");
+    }
+
+    #[test]
+    fn test_rejects_ids_relations_cannot_use() {
+        ["User Account", "", "User.", "a..b", "Straße"]
+            .into_iter()
+            .for_each(|new_id: &str| {
+                let error: GraphGatewayError =
+                    rename("@startuml\nclass User\n@enduml", "User", new_id).unwrap_err();
+
+                assert!(
+                    matches!(error, GraphGatewayError::Semantic { .. }),
+                    "{}",
+                    new_id
+                );
+            });
+    }
+
+    #[test]
+    fn test_accepts_underscores_and_dots() {
+        let input: &str = "@startuml\nclass User\n@enduml";
+
+        let edits: Vec<TextEdit> = rename(input, "User", "auth.user_account").unwrap();
+
+        assert_eq!(
+            apply(input, edits),
+            "@startuml\nclass auth.user_account\n@enduml"
+        );
+    }
+
+    #[test]
+    fn test_rejects_an_id_already_taken() {
+        let error: GraphGatewayError = rename(
+            "@startuml\nclass User\nclass Order\nUser --> Order\n@enduml",
+            "User",
+            "Order",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            GraphGatewayError::Semantic {
+                source: "plantuml".into(),
+                message: "'Order' is already an element id".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_renames_hide_and_show_targets() {
+        let input: &str = "@startuml\nclass User\nclass Order\nhide User\nshow \"User\" methods\nhide Order\nhide members\n@enduml";
+
+        let edits: Vec<TextEdit> = rename(input, "User", "Account").unwrap();
+
+        assert_eq!(
+            apply(input, edits),
+            "@startuml\nclass Account\nclass Order\nhide Account\nshow \"Account\" methods\nhide Order\nhide members\n@enduml"
+        );
+    }
+}
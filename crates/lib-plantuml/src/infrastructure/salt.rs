@@ -0,0 +1,341 @@
+//! PlantUML Salt wireframes, read into a `Wireframe` graph: every widget is a
+//! node whose kind names the widget (`button`, `text_field`, `checkbox`…) and
+//! whose `row` and `column` data place it in its container. Containers (grids,
+//! trees, tabs, menus, scroll panes and group boxes) are groups, and tree
+//! items are linked to their parent item by an edge.
+
+use std::collections::HashMap;
+
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, IdAllocator, Text},
+    node::{Node, NodeKind},
+    value::Value,
+};
+
+use crate::infrastructure::{limits::Limits, parser::PlantUmlParseError};
+
+/// Whether a source is a Salt wireframe: a `@startsalt` block, or a
+/// `@startuml` one whose first statement is `salt`
+pub(crate) fn is_salt(input: &str) -> bool {
+    let mut lines = input
+        .lines()
+        .map(str::trim)
+        .filter(|line: &&str| !line.is_empty() && !line.starts_with('\''));
+    match lines.next() {
+        Some(line) if line.starts_with("@startsalt") => true,
+        Some(line) if line.starts_with("@startuml") => lines.next() == Some("salt"),
+        _ => false,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    /// `{`, with the marker naming the kind of container
+    Open(&'a str, Option<&'a str>),
+    Close,
+    Separator,
+    Cell(&'a str),
+}
+
+struct Container {
+    id: Id,
+    tree: bool,
+    line: usize,
+    row: usize,
+    column: usize,
+    /// Whether the current line put anything in the container
+    used: bool,
+    /// Latest tree item at each depth, for linking the next deeper one
+    items: Vec<Id>,
+}
+
+pub(crate) fn parse_salt(input: &str, limits: &Limits) -> Result<Graph, PlantUmlParseError> {
+    let mut graph: Graph = Graph {
+        kind: GraphKind::Wireframe,
+        ..Default::default()
+    };
+    let mut ids: IdAllocator = IdAllocator::new();
+    let mut stack: Vec<Container> = Vec::new();
+    // Widgets outside every container are placed in rows of their own
+    let mut root: Container = Container {
+        id: Id::default(),
+        tree: false,
+        line: 0,
+        row: 0,
+        column: 0,
+        used: false,
+        items: Vec::new(),
+    };
+    let mut started: bool = false;
+
+    for (index, raw) in input.lines().enumerate() {
+        let line: &str = raw.trim();
+        if line.is_empty() || line.starts_with('\'') {
+            continue;
+        }
+        if !started {
+            started = line.starts_with("@startsalt") || line == "salt";
+            continue;
+        }
+        if line.starts_with("@endsalt") || line.starts_with("@enduml") {
+            break;
+        }
+
+        for token in tokens(line) {
+            let parent: Option<Id> = stack
+                .last()
+                .map(|container: &Container| container.id.clone());
+            match token {
+                Token::Open(marker, title) => {
+                    current(&mut stack, &mut root).used = true;
+                    if stack.len() >= limits.max_depth {
+                        return Err(PlantUmlParseError::LimitExceeded(format!(
+                            "Salt containers nest past the depth limit of {} at line {}",
+                            limits.max_depth,
+                            index + 1
+                        )));
+                    }
+                    let kind: &str = container_kind(marker);
+                    let id: Id = ids.for_name(kind);
+                    graph.groups.insert(
+                        id.clone(),
+                        Group {
+                            id: id.clone(),
+                            label: Some(title.unwrap_or(kind).into()),
+                            parent: parent.clone(),
+                            ..Default::default()
+                        },
+                    );
+                    if let Some(group) = parent.and_then(|parent: Id| graph.groups.get_mut(&parent))
+                    {
+                        group.children.push(id.clone());
+                    }
+                    stack.push(Container {
+                        id,
+                        tree: kind == "tree",
+                        line: index + 1,
+                        row: 0,
+                        column: 0,
+                        used: false,
+                        items: Vec::new(),
+                    });
+                }
+                Token::Close => {
+                    if stack.pop().is_none() {
+                        return Err(PlantUmlParseError::Syntax {
+                            message: "Unmatched `}`".to_string(),
+                            line: index + 1,
+                            column: raw.find('}').map_or(1, |column: usize| column + 1),
+                        });
+                    }
+                }
+                Token::Separator => current(&mut stack, &mut root).column += 1,
+                Token::Cell(text) => {
+                    let current: &mut Container = current(&mut stack, &mut root);
+                    let Some((kind, label, checked)) = widget(text, current.tree) else {
+                        continue;
+                    };
+                    current.used = true;
+                    let id: Id = ids.for_name(if label.is_empty() { kind } else { &label });
+                    let mut data: HashMap<Text, Value> = [
+                        ("row".into(), Value::Number(current.row as f64)),
+                        ("column".into(), Value::Number(current.column as f64)),
+                    ]
+                    .into();
+                    if let Some(checked) = checked {
+                        data.insert("checked".into(), Value::Bool(checked));
+                    }
+
+                    if kind == "tree_item" {
+                        let depth: usize = text.chars().take_while(|c: &char| *c == '+').count();
+                        current.items.truncate(depth - 1);
+                        if let Some(branch) = current.items.last() {
+                            let edge: Id = Id::from(format!("e{}", graph.edges.len() + 1));
+                            graph.edges.insert(
+                                edge.clone(),
+                                Edge {
+                                    id: edge,
+                                    from: branch.clone(),
+                                    to: id.clone(),
+                                    directed: false,
+                                    kind: EdgeKind::Undirected,
+                                    order: graph.edges.len(),
+                                    ..Default::default()
+                                },
+                            );
+                        }
+                        current.items.push(id.clone());
+                    }
+
+                    if let Some(group) = parent
+                        .as_ref()
+                        .and_then(|parent: &Id| graph.groups.get_mut(parent))
+                    {
+                        group.children.push(id.clone());
+                    }
+                    graph.nodes.insert(
+                        id.clone(),
+                        Node {
+                            id,
+                            kind: NodeKind::Custom(kind.to_string()),
+                            label: Some(label.into()),
+                            data,
+                            parent,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+        }
+
+        for container in stack.iter_mut().chain(std::iter::once(&mut root)) {
+            if container.used {
+                container.row += 1;
+            }
+            container.column = 0;
+            container.used = false;
+        }
+        limits.check_statements(graph.nodes.len() + graph.groups.len())?;
+    }
+
+    match stack.last() {
+        Some(container) => Err(PlantUmlParseError::Syntax {
+            message: "Unclosed `{`".to_string(),
+            line: container.line,
+            column: 1,
+        }),
+        None => Ok(graph),
+    }
+}
+
+/// The innermost open container
+fn current<'a>(stack: &'a mut [Container], root: &'a mut Container) -> &'a mut Container {
+    stack.last_mut().unwrap_or(root)
+}
+
+/// Splits a line into braces, `|` separators and cells. Quoted text is kept
+/// whole, and `{` only opens a container at the start of a cell.
+fn tokens(line: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut rest: &str = line.trim_start();
+
+    while let Some(first) = rest.chars().next() {
+        match first {
+            '{' => {
+                let after: &str = &rest[1..];
+                let marker: &str = ["SI", "S-", "S", "T", "#", "!", "-", "+", "/", "*", "^"]
+                    .into_iter()
+                    .find(|marker: &&str| after.starts_with(marker))
+                    .unwrap_or("");
+                rest = after[marker.len()..].trim_start();
+                let mut title: Option<&str> = None;
+                if marker == "^"
+                    && let Some(quoted) = rest.strip_prefix('"')
+                    && let Some(end) = quoted.find('"')
+                {
+                    title = Some(&quoted[..end]);
+                    rest = quoted[end + 1..].trim_start();
+                }
+                tokens.push(Token::Open(marker, title));
+            }
+            '}' => {
+                tokens.push(Token::Close);
+                rest = rest[1..].trim_start();
+            }
+            '|' => {
+                tokens.push(Token::Separator);
+                rest = rest[1..].trim_start();
+            }
+            _ => {
+                let mut quoted: bool = false;
+                let end: usize = rest
+                    .char_indices()
+                    .find(|(_, c): &(usize, char)| {
+                        if *c == '"' {
+                            quoted = !quoted;
+                        }
+                        !quoted && (*c == '|' || *c == '}')
+                    })
+                    .map_or(rest.len(), |(end, _): (usize, char)| end);
+                tokens.push(Token::Cell(rest[..end].trim_end()));
+                rest = &rest[end..];
+            }
+        }
+    }
+    tokens
+}
+
+fn container_kind(marker: &str) -> &'static str {
+    match marker {
+        "T" => "tree",
+        "/" => "tabs",
+        "*" => "menu",
+        "S" | "SI" | "S-" => "scroll",
+        "^" => "group_box",
+        _ => "grid",
+    }
+}
+
+/// The widget a cell holds, its label and, for checkboxes and radio
+/// buttons, whether it is checked. Empty (`.`) and spanned (`*`) cells hold
+/// none.
+fn widget(text: &str, tree: bool) -> Option<(&'static str, String, Option<bool>)> {
+    let inner = |open: char, close: char| -> Option<&str> {
+        text.strip_prefix(open)?.strip_suffix(close).map(str::trim)
+    };
+    let toggle = |open: char, close: char| -> Option<(bool, String)> {
+        let rest: &str = text.strip_prefix(open)?;
+        let (state, label): (&str, &str) = rest.split_once(close)?;
+        let checked: bool = match state.trim() {
+            "" => false,
+            "X" | "x" => true,
+            _ => return None,
+        };
+        (!label.trim().is_empty()).then(|| (checked, label.trim().to_string()))
+    };
+
+    if text.is_empty() || text == "." || text == "*" {
+        return None;
+    }
+    if text.len() >= 2 {
+        let first: char = text.chars().next().unwrap_or_default();
+        if ".=~-".contains(first) && text.chars().all(|c: char| c == first) {
+            return Some(("separator", String::new(), None));
+        }
+    }
+    if tree && text.starts_with('+') {
+        return Some((
+            "tree_item",
+            text.trim_start_matches('+').trim().to_string(),
+            None,
+        ));
+    }
+    if let Some((checked, label)) = toggle('[', ']') {
+        return Some(("checkbox", label, Some(checked)));
+    }
+    if let Some((checked, label)) = toggle('(', ')') {
+        return Some(("radio", label, Some(checked)));
+    }
+    if let Some(label) = inner('[', ']') {
+        return Some(("button", label.to_string(), None));
+    }
+    if let Some(label) = inner('"', '"') {
+        return Some(("text_field", label.to_string(), None));
+    }
+    if let Some(label) = inner('^', '^') {
+        return Some((
+            "droplist",
+            label
+                .split('^')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            None,
+        ));
+    }
+    Some(("label", text.to_string(), None))
+}
@@ -0,0 +1,98 @@
+//! The vocabulary of sequence diagrams: participant keywords, message arrows
+//! such as `->` or `-->>`, and fragment keywords such as `alt` or `loop`. A
+//! diagram using any of them is read as a sequence diagram, where `-->` is a
+//! reply message rather than an association.
+
+use lib_core::entities::{
+    node::{DeploymentKind, NodeKind},
+    sequence::FragmentKind,
+};
+
+use crate::infrastructure::models::{
+    ast_arena::{ArenaNode, AstArena},
+    ast_node::AstNode,
+};
+
+/// Keywords that only declare sequence participants
+const PARTICIPANTS: [&str; 3] = ["participant", "boundary", "control"];
+/// Participant keywords deployment diagrams use too, which alone do not make
+/// a sequence diagram
+const SHARED: [&str; 2] = ["collections", "queue"];
+/// Arrows that only draw messages
+const ARROWS: [&str; 6] = ["->", "->>", "<-", "<<-", "-->>", "<<--"];
+
+/// The kind of node a participant keyword declares
+pub(crate) fn participant(keyword: &str) -> Option<NodeKind> {
+    match keyword {
+        "participant" => Some(NodeKind::Component),
+        keyword if PARTICIPANTS.contains(&keyword) => Some(NodeKind::Custom(keyword.to_string())),
+        keyword if SHARED.contains(&keyword) => {
+            DeploymentKind::from_keyword(keyword).map(NodeKind::Deployment)
+        }
+        _ => None,
+    }
+}
+
+/// How a message arrow is drawn
+pub(crate) struct Message {
+    /// Pointing from right to left, as `<-` does
+    pub reversed: bool,
+    /// A dashed reply line, as `-->` draws
+    pub dashed: bool,
+    /// An open arrowhead, as `->>` draws
+    pub asynchronous: bool,
+}
+
+/// What an arrow draws in a sequence diagram, if it is a message
+pub(crate) fn message(arrow: &str) -> Option<Message> {
+    (ARROWS.contains(&arrow) || arrow == "-->" || arrow == "<--").then(|| Message {
+        reversed: arrow.starts_with('<'),
+        dashed: arrow.contains("--"),
+        asynchronous: arrow.contains(">>") || arrow.contains("<<"),
+    })
+}
+
+pub(crate) fn fragment_kind(keyword: &str) -> FragmentKind {
+    match keyword {
+        "opt" => FragmentKind::Opt,
+        "loop" => FragmentKind::Loop,
+        "par" => FragmentKind::Par,
+        "break" => FragmentKind::Break,
+        "critical" => FragmentKind::Critical,
+        "group" => FragmentKind::Group,
+        _ => FragmentKind::Alt,
+    }
+}
+
+/// Whether a statement only belongs in a sequence diagram
+pub(crate) fn is_sequence(node: &AstNode) -> bool {
+    match node {
+        AstNode::Definition { keyword, .. } => PARTICIPANTS.contains(&keyword.as_ref()),
+        AstNode::Relation { arrow, .. } => ARROWS.contains(&arrow.as_ref()),
+        AstNode::Package { children, .. } | AstNode::Together { children } => {
+            children.iter().any(is_sequence)
+        }
+        AstNode::Activation { .. } | AstNode::Fragment { .. } => true,
+        AstNode::State { .. }
+        | AstNode::AssociationClass { .. }
+        | AstNode::Terminal { .. }
+        | AstNode::Action { .. }
+        | AstNode::Control { .. }
+        | AstNode::Heading { .. }
+        | AstNode::Legend(_)
+        | AstNode::LinkNote { .. }
+        | AstNode::Direction(_)
+        | AstNode::Visibility { .. }
+        | AstNode::Directive { .. } => false,
+    }
+}
+
+/// Whether any statement of an arena only belongs in a sequence diagram
+pub(crate) fn is_sequence_arena(arena: &AstArena) -> bool {
+    (0..arena.len()).any(|index: usize| match arena.get(index) {
+        ArenaNode::Definition { keyword, .. } => PARTICIPANTS.contains(keyword),
+        ArenaNode::Relation { arrow, .. } => ARROWS.contains(&arrow.as_ref()),
+        ArenaNode::Activation { .. } | ArenaNode::Fragment { .. } => true,
+        _ => false,
+    })
+}
@@ -0,0 +1,39 @@
+//! The settings of `skinparam` directives, such as `skinparam shadowing
+//! false` or a `skinparam class { BackgroundColor #fff }` block, kept as
+//! graph styles: one per element the settings apply to.
+
+/// Id of the style holding settings given without an element
+pub(crate) const GLOBAL: &str = "skinparam";
+
+/// The style a `skinparam` directive adds to, and its settings as key and
+/// value pairs; none when it sets nothing
+pub(crate) fn declarations(directive: &str) -> Option<(&str, Vec<(&str, &str)>)> {
+    let rest: &str = directive.strip_prefix("skinparam")?.trim();
+    let (element, settings): (&str, Vec<(&str, &str)>) = match rest.split_once('{') {
+        Some((element, block)) => (
+            element.trim(),
+            block
+                .trim_end()
+                .trim_end_matches('}')
+                .lines()
+                .filter_map(setting)
+                .collect(),
+        ),
+        None => (GLOBAL, setting(rest).into_iter().collect()),
+    };
+    let element: &str = if element.is_empty() { GLOBAL } else { element };
+
+    (!settings.is_empty()).then_some((element, settings))
+}
+
+/// A `key value` line, the value being the rest of it; comments and lines
+/// without a value are skipped
+fn setting(line: &str) -> Option<(&str, &str)> {
+    let line: &str = line.trim();
+    if line.starts_with('\'') {
+        return None;
+    }
+    line.split_once(char::is_whitespace)
+        .map(|(key, value): (&str, &str)| (key, value.trim()))
+        .filter(|(_, value): &(&str, &str)| !value.is_empty())
+}
@@ -0,0 +1,45 @@
+//! The vocabulary of state diagrams: `state` declarations, description lines
+//! such as `Busy : entry / start timer`, and the `[*]` pseudo-state. A
+//! diagram using any of them is read as a state diagram, where every arrow is
+//! a transition, even those a sequence diagram would read as messages.
+
+use crate::infrastructure::models::{
+    ast_arena::{ArenaNode, AstArena},
+    ast_node::AstNode,
+};
+
+/// The initial pseudo-state as a source, the final one as a target
+pub(crate) const PSEUDO_STATE: &str = "[*]";
+/// Description prefixes kept apart as the state's actions, such as
+/// `entry / start timer`
+const ACTIONS: [&str; 3] = ["entry", "exit", "do"];
+
+/// The data key and text of a description line: an action's name and what
+/// follows its `/`, or `description`
+pub(crate) fn description(text: &str) -> (&str, &str) {
+    text.split_once('/')
+        .map(|(action, rest): (&str, &str)| (action.trim(), rest.trim()))
+        .filter(|(action, _): &(&str, &str)| ACTIONS.contains(action))
+        .unwrap_or(("description", text))
+}
+
+/// Whether a statement only belongs in a state diagram
+pub(crate) fn is_state(node: &AstNode) -> bool {
+    match node {
+        AstNode::State { .. } => true,
+        AstNode::Relation { left, right, .. } => left == PSEUDO_STATE || right == PSEUDO_STATE,
+        AstNode::Package { children, .. } | AstNode::Together { children } => {
+            children.iter().any(is_state)
+        }
+        _ => false,
+    }
+}
+
+/// Whether any statement of an arena only belongs in a state diagram
+pub(crate) fn is_state_arena(arena: &AstArena) -> bool {
+    (0..arena.len()).any(|index: usize| match arena.get(index) {
+        ArenaNode::State { .. } => true,
+        ArenaNode::Relation { left, right, .. } => *left == PSEUDO_STATE || *right == PSEUDO_STATE,
+        _ => false,
+    })
+}
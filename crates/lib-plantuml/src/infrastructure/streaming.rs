@@ -0,0 +1,235 @@
+use std::collections::VecDeque;
+
+use futures_lite::{Stream, stream};
+use lib_core::{
+    adapters::graph_gateway::GraphGatewayError,
+    entities::{edge::Edge, graph::Graph, group::Group, node::Node},
+};
+use pest::{
+    Parser,
+    iterators::{Pair, Pairs},
+};
+
+use crate::infrastructure::{
+    limits::Limits,
+    parser::{self, PlantUmlParser, Rule},
+    syntax,
+    transformer::{Change, GraphBuilder},
+};
+
+/// A graph element, available as soon as the statement declaring it is parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Element {
+    Node(Node),
+    Edge(Edge),
+    Group(Group),
+}
+
+/// Reads a diagram one top-level statement at a time, as an iterator or an
+/// async stream. A node comes again when a later statement replaces it, such
+/// as a definition after a relation created it implicitly, and the contents
+/// of a package come before its group. Nothing follows an error.
+pub struct ElementStream<'a> {
+    input: &'a str,
+    /// Where the next statement starts, once `@startuml` was read
+    position: Option<usize>,
+    builder: GraphBuilder,
+    pending: VecDeque<Element>,
+    finished: bool,
+}
+
+pub fn stream_elements(input: &str) -> ElementStream<'_> {
+    ElementStream {
+        input,
+        position: None,
+        builder: GraphBuilder::new(),
+        pending: VecDeque::new(),
+        finished: false,
+    }
+}
+
+impl<'a> ElementStream<'a> {
+    /// The same elements as an async stream, parsing a statement per poll
+    pub fn into_stream(self) -> impl Stream<Item = Result<Element, GraphGatewayError>> + 'a {
+        stream::iter(self)
+    }
+
+    fn advance(&mut self) -> Result<(), GraphGatewayError> {
+        let Some(position) = self.position else {
+            let from: usize = syntax::skip_trivia(self.input, 0);
+            if !self.input[from..].starts_with("@startuml") {
+                return Err(self.error());
+            }
+            Limits::default().check_source(self.input)?;
+            self.position = Some(from + "@startuml".len());
+            return Ok(());
+        };
+
+        let from: usize = syntax::skip_trivia(self.input, position);
+        let rest: &str = &self.input[from..];
+        if rest.starts_with("@enduml")
+            && syntax::skip_trivia(self.input, from + "@enduml".len()) == self.input.len()
+        {
+            self.finished = true;
+            return Ok(());
+        }
+
+        let statement: Pair<Rule> = PlantUmlParser::parse(Rule::statement, rest)
+            .ok()
+            .and_then(|mut pairs: Pairs<Rule>| pairs.next())
+            .ok_or_else(|| self.error())?;
+        self.position = Some(from + statement.as_span().end());
+
+        let inner: Option<Pair<Rule>> = statement.into_inner().next();
+        if let Some(node) = inner
+            .map(parser::parse_element)
+            .transpose()
+            .map_err(|_| self.error())?
+            .flatten()
+        {
+            let changes: Vec<Change> = self.builder.add(&node);
+            let graph: &Graph = self.builder.graph();
+            self.pending.extend(
+                changes
+                    .into_iter()
+                    .filter_map(|change: Change| element(graph, change)),
+            );
+        }
+        Ok(())
+    }
+
+    /// The error parsing the whole diagram reports, so both agree on it
+    fn error(&self) -> GraphGatewayError {
+        syntax::parse_diagram(self.input)
+            .err()
+            .unwrap_or_else(|| GraphGatewayError::Semantic {
+                source: "plantuml".into(),
+                message: "A statement could not be read on its own".to_string(),
+            })
+    }
+}
+
+impl Iterator for ElementStream<'_> {
+    type Item = Result<Element, GraphGatewayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(element) = self.pending.pop_front() {
+                return Some(Ok(element));
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(e) = self.advance() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+fn element(graph: &Graph, change: Change) -> Option<Element> {
+    match change {
+        Change::Node(id) => graph.nodes.get(&id).cloned().map(Element::Node),
+        Change::Edge(id) => graph.edges.get(&id).cloned().map(Element::Edge),
+        Change::Group(id) => graph.groups.get(&id).cloned().map(Element::Group),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_lite::StreamExt;
+    use lib_core::{
+        adapters::graph_gateway::GraphGateway,
+        entities::{id::Id, node::NodeKind},
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::infrastructure::adapters::plant_uml_graph_gateway::PlantUmlGraphGateway;
+
+    const SOURCE: &str =
+        "@startuml\n' orders\nOrder --> User\npackage \"Core\" {\n  class User\n}\n@enduml\n";
+
+    #[test]
+    fn test_yields_elements_in_statement_order() {
+        let elements: Vec<Element> = stream_elements(SOURCE).map(Result::unwrap).collect();
+
+        let names: Vec<String> = elements
+            .iter()
+            .map(|element: &Element| match element {
+                Element::Node(node) => format!("node {}", node.id),
+                Element::Edge(edge) => format!("edge {}->{}", edge.from, edge.to),
+                Element::Group(group) => format!("group {}", group.label.as_deref().unwrap()),
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "node Order",
+                "node User",
+                "edge Order->User",
+                "node User",
+                "group Core"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_later_elements_replace_earlier_ones() {
+        let graph: Graph =
+            smol::block_on(PlantUmlGraphGateway::new().read_graph_from_raw_input(SOURCE)).unwrap();
+
+        let mut streamed: Graph = Graph::default();
+        smol::block_on(stream_elements(SOURCE).into_stream().for_each(
+            |element: Result<Element, GraphGatewayError>| match element.unwrap() {
+                Element::Node(node) => {
+                    streamed.nodes.insert(node.id.clone(), node);
+                }
+                Element::Edge(edge) => {
+                    streamed.edges.insert(edge.id.clone(), edge);
+                }
+                Element::Group(group) => {
+                    streamed.groups.insert(group.id.clone(), group);
+                }
+            },
+        ));
+
+        let user: Id = Id::from("User");
+        assert_eq!(streamed.nodes.len(), graph.nodes.len());
+        assert_eq!(streamed.edges.len(), graph.edges.len());
+        assert_eq!(streamed.groups.len(), graph.groups.len());
+        assert_eq!(streamed.nodes[&user].kind, NodeKind::Entity);
+        assert!(streamed.nodes[&user].parent.is_some());
+    }
+
+    #[test]
+    fn test_yields_what_came_before_an_error() {
+        let input: &str = "@startuml\nclass User\nclass\n@enduml\n";
+
+        let elements: Vec<Result<Element, GraphGatewayError>> = stream_elements(input).collect();
+
+        assert_eq!(elements.len(), 2);
+        assert!(matches!(elements[0], Ok(Element::Node(_))));
+        assert_eq!(
+            elements[1].as_ref().unwrap_err(),
+            &syntax::parse_diagram(input).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_requires_the_diagram_markers() {
+        assert!(
+            stream_elements("class User\n@enduml")
+                .next()
+                .unwrap()
+                .is_err()
+        );
+        assert!(
+            stream_elements("@startuml\nclass User\n")
+                .last()
+                .unwrap()
+                .is_err()
+        );
+    }
+}
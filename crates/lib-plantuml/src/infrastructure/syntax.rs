@@ -0,0 +1,630 @@
+use lib_core::{adapters::graph_gateway::GraphGatewayError, entities::member::MemberKind};
+use pest::{
+    Parser,
+    iterators::{Pair, Pairs},
+};
+
+use crate::infrastructure::{
+    limits::Limits,
+    models::ast_node::AstMember,
+    parser::{self, PlantUmlParseError, PlantUmlParser, Rule},
+    usecase, visibility,
+};
+
+/// Words that can start a statement or follow a declared name
+pub const KEYWORDS: [&str; 56] = [
+    "@startuml",
+    "@enduml",
+    "package",
+    "abstract",
+    "class",
+    "interface",
+    "enum",
+    "actor",
+    "component",
+    "database",
+    "as",
+    "participant",
+    "boundary",
+    "control",
+    "entity",
+    "collections",
+    "queue",
+    "activate",
+    "deactivate",
+    "alt",
+    "else",
+    "opt",
+    "loop",
+    "par",
+    "break",
+    "critical",
+    "group",
+    "end",
+    "state",
+    "start",
+    "stop",
+    "if",
+    "then",
+    "elseif",
+    "endif",
+    "while",
+    "is",
+    "endwhile",
+    "fork",
+    "again",
+    "usecase",
+    "rectangle",
+    "object",
+    "map",
+    "cloud",
+    "artifact",
+    "storage",
+    "card",
+    "file",
+    "direction",
+    "together",
+    "annotation",
+    "protocol",
+    "struct",
+    "exception",
+    "metaclass",
+];
+
+/// Byte range in the source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset <= self.end
+    }
+
+    pub(crate) fn shifted(&self, delta: isize) -> Self {
+        Self {
+            start: self.start.saturating_add_signed(delta),
+            end: self.end.saturating_add_signed(delta),
+        }
+    }
+}
+
+impl From<pest::Span<'_>> for Span {
+    fn from(span: pest::Span<'_>) -> Self {
+        Self {
+            start: span.start(),
+            end: span.end(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolKind {
+    /// Declared element, with the keyword that declared it
+    Element(String),
+    /// Line of a class body
+    Member(MemberKind),
+    Package,
+}
+
+/// A declaration in the source: an element with its members, or a package
+/// with its contents
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    /// Id the element is referred to by (its alias, or else its name)
+    pub id: String,
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Whole statement
+    pub span: Span,
+    /// Where the id is written
+    pub selection: Span,
+    pub children: Vec<Symbol>,
+}
+
+impl Symbol {
+    /// Moves the symbol and its children by `delta` bytes
+    pub(crate) fn shift(&mut self, delta: isize) {
+        self.span = self.span.shifted(delta);
+        self.selection = self.selection.shifted(delta);
+        self.children
+            .iter_mut()
+            .for_each(|child: &mut Symbol| child.shift(delta));
+    }
+}
+
+/// An element id used as a relation endpoint or named by `hide` or `show`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub id: String,
+    pub span: Span,
+}
+
+/// Where things are declared and referenced in a PlantUML source, for
+/// editor features that need positions rather than the graph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceIndex {
+    pub symbols: Vec<Symbol>,
+    pub references: Vec<Reference>,
+}
+
+impl SourceIndex {
+    /// Element declaration for an id, looking inside packages
+    pub fn definition(&self, id: &str) -> Option<&Symbol> {
+        fn find<'a>(symbols: &'a [Symbol], id: &str) -> Option<&'a Symbol> {
+            symbols
+                .iter()
+                .find_map(|symbol: &Symbol| match symbol.kind {
+                    SymbolKind::Element(_) if symbol.id == id => Some(symbol),
+                    _ => find(&symbol.children, id),
+                })
+        }
+        find(&self.symbols, id)
+    }
+
+    /// Where an element id is written: its declaration first, then every
+    /// relation endpoint using it
+    pub fn find_references(&self, id: &str) -> Vec<Span> {
+        self.definition(id)
+            .map(|symbol: &Symbol| symbol.selection)
+            .into_iter()
+            .chain(
+                self.references
+                    .iter()
+                    .filter(|reference: &&Reference| reference.id == id)
+                    .map(|reference: &Reference| reference.span),
+            )
+            .collect()
+    }
+
+    /// Id of the element declared or referenced at the given offset
+    pub fn id_at(&self, offset: usize) -> Option<&str> {
+        fn find(symbols: &[Symbol], offset: usize) -> Option<&str> {
+            symbols
+                .iter()
+                .find_map(|symbol: &Symbol| match symbol.kind {
+                    SymbolKind::Element(_) if symbol.selection.contains(offset) => {
+                        Some(symbol.id.as_str())
+                    }
+                    _ => find(&symbol.children, offset),
+                })
+        }
+        self.references
+            .iter()
+            .find(|reference: &&Reference| reference.span.contains(offset))
+            .map(|reference: &Reference| reference.id.as_str())
+            .or_else(|| find(&self.symbols, offset))
+    }
+
+    /// Every element id, declared or only used in relations, sorted
+    pub fn ids(&self) -> Vec<&str> {
+        fn collect<'a>(symbols: &'a [Symbol], ids: &mut Vec<&'a str>) {
+            symbols.iter().for_each(|symbol: &Symbol| {
+                if let SymbolKind::Element(_) = symbol.kind {
+                    ids.push(&symbol.id);
+                }
+                collect(&symbol.children, ids);
+            });
+        }
+
+        let mut ids: Vec<&str> = Vec::new();
+        collect(&self.symbols, &mut ids);
+        self.references
+            .iter()
+            .for_each(|reference: &Reference| ids.push(&reference.id));
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
+/// Packages, elements and their members as nested symbols, for structure
+/// views in editors
+pub fn outline(input: &str) -> Result<Vec<Symbol>, GraphGatewayError> {
+    index_source(input).map(|index: SourceIndex| index.symbols)
+}
+
+/// Parses a source and records where every element is declared and referenced
+pub fn index_source(input: &str) -> Result<SourceIndex, GraphGatewayError> {
+    let diagram: Pair<Rule> = parse_diagram(input)?;
+
+    let mut index: SourceIndex = SourceIndex::default();
+    index.symbols = statements(diagram.into_inner(), &mut index.references);
+    Ok(index)
+}
+
+/// Parses a whole diagram, once its nesting is known to fit the stack
+pub(crate) fn parse_diagram(input: &str) -> Result<Pair<'_, Rule>, GraphGatewayError> {
+    Limits::default().check_source(input)?;
+    PlantUmlParser::parse(Rule::diagram, input)
+        .map_err(|e: pest::error::Error<Rule>| {
+            GraphGatewayError::from(PlantUmlParseError::from(e))
+        })?
+        .next()
+        .ok_or_else(|| GraphGatewayError::from(PlantUmlParseError::Internal("empty parse".into())))
+}
+
+/// Span of a statement without the whitespace and comments pest leaves at
+/// the end of an optional part that did not match
+pub(crate) fn statement_span(pair: &Pair<Rule>) -> Span {
+    let start: usize = pair.as_span().start();
+    match pair.as_rule() {
+        Rule::package
+        | Rule::together
+        | Rule::fragment
+        | Rule::archimate_element
+        | Rule::archimate_relation
+        | Rule::action
+        | Rule::heading
+        | Rule::legend
+        | Rule::link_note
+        | Rule::direction
+        | Rule::activity_if
+        | Rule::activity_while
+        | Rule::activity_fork => pair.as_span().into(),
+        _ => Span {
+            start,
+            end: pair
+                .clone()
+                .into_inner()
+                .last()
+                .map_or(pair.as_span().end(), |last: Pair<Rule>| {
+                    last.as_span().end()
+                }),
+        },
+    }
+}
+
+/// Offset of the first token at or after `from`
+pub(crate) fn skip_trivia(input: &str, mut from: usize) -> usize {
+    loop {
+        let rest: &str = &input[from..];
+        let trimmed: &str = rest.trim_start();
+        from += rest.len() - trimmed.len();
+        if !trimmed.starts_with('\'') {
+            return from;
+        }
+        from += trimmed.find('\n').unwrap_or(trimmed.len());
+    }
+}
+
+pub(crate) fn statements<'a>(
+    pairs: impl Iterator<Item = Pair<'a, Rule>>,
+    references: &mut Vec<Reference>,
+) -> Vec<Symbol> {
+    pairs
+        .flat_map(|pair: Pair<Rule>| match pair.as_rule() {
+            // Fragments, activity blocks and `together` only group statements,
+            // so what they declare is listed alongside them
+            Rule::together
+            | Rule::fragment
+            | Rule::activity_if
+            | Rule::activity_while
+            | Rule::activity_fork => statements(pair.into_inner(), references),
+            _ => statement(pair, references).into_iter().collect(),
+        })
+        .collect()
+}
+
+fn statement(pair: Pair<Rule>, references: &mut Vec<Reference>) -> Option<Symbol> {
+    let span: Span = statement_span(&pair);
+    match pair.as_rule() {
+        Rule::definition => {
+            let mut inner: Pairs<Rule> = pair.into_inner();
+            let keyword: String = inner.next()?.as_str().to_string();
+            let name: Pair<Rule> = inner.next()?;
+            let mut selection: Pair<Rule> = name.clone();
+            let mut children: Vec<Symbol> = Vec::new();
+
+            inner.for_each(|p: Pair<Rule>| match p.as_rule() {
+                Rule::identifier => selection = p,
+                Rule::class_body | Rule::enum_body => {
+                    children = p
+                        .into_inner()
+                        .flat_map(|p: Pair<Rule>| match p.as_rule() {
+                            Rule::enum_values => p.into_inner().collect(),
+                            _ => vec![p],
+                        })
+                        .filter_map(|p: Pair<Rule>| match p.as_rule() {
+                            Rule::member | Rule::enum_value => Some(member(p)),
+                            Rule::inner_class => p
+                                .into_inner()
+                                .find(|p: &Pair<Rule>| p.as_rule() == Rule::definition)
+                                .and_then(|p: Pair<Rule>| statement(p, references)),
+                            _ => None,
+                        })
+                        .collect()
+                }
+                _ => {}
+            });
+
+            Some(Symbol {
+                id: usecase::name(selection.as_str()).to_string(),
+                name: usecase::name(name.as_str()).to_string(),
+                kind: SymbolKind::Element(keyword),
+                span,
+                selection: selection.as_span().into(),
+                children,
+            })
+        }
+        Rule::state => {
+            let mut inner: Pairs<Rule> = pair.into_inner();
+            let keyword: String = inner.next()?.as_str().to_string();
+            let name: Pair<Rule> = inner.next()?;
+            let mut selection: Pair<Rule> = name.clone();
+            let mut children: Vec<Symbol> = Vec::new();
+
+            inner.for_each(|p: Pair<Rule>| match p.as_rule() {
+                Rule::identifier => selection = p,
+                Rule::state_body => children = statements(p.into_inner(), references),
+                _ => {}
+            });
+
+            Some(Symbol {
+                id: usecase::name(selection.as_str()).to_string(),
+                name: usecase::name(name.as_str()).to_string(),
+                kind: SymbolKind::Element(keyword),
+                span,
+                selection: selection.as_span().into(),
+                children,
+            })
+        }
+        Rule::relation | Rule::association_class | Rule::activation | Rule::state_description => {
+            pair.into_inner()
+                // A member-qualified end refers to its class
+                .map(|p: Pair<Rule>| match p.as_rule() {
+                    Rule::qualified => p.clone().into_inner().next().unwrap_or(p),
+                    _ => p,
+                })
+                .filter(|p: &Pair<Rule>| {
+                    matches!(
+                        p.as_rule(),
+                        Rule::identifier | Rule::usecase_name | Rule::actor_name
+                    )
+                })
+                .take(3)
+                .for_each(|p: Pair<Rule>| {
+                    references.push(Reference {
+                        id: usecase::name(p.as_str()).to_string(),
+                        span: p.as_span().into(),
+                    })
+                });
+            None
+        }
+        Rule::shorthand => {
+            let mut inner: Pairs<Rule> = pair.into_inner();
+            let name: Pair<Rule> = inner.next()?;
+            let keyword: &str = match name.as_rule() {
+                Rule::actor_name => "actor",
+                _ => "usecase",
+            };
+            let selection: Pair<Rule> = inner.next().unwrap_or_else(|| name.clone());
+
+            Some(Symbol {
+                id: usecase::name(selection.as_str()).to_string(),
+                name: usecase::name(name.as_str()).to_string(),
+                kind: SymbolKind::Element(keyword.to_string()),
+                span,
+                selection: selection.as_span().into(),
+                children: Vec::new(),
+            })
+        }
+        Rule::archimate_element => {
+            let mut inner: Pairs<Rule> = pair.into_inner();
+            let keyword: String = inner.next()?.as_str().to_string();
+            let id: Pair<Rule> = inner.next()?;
+            let name: Pair<Rule> = inner.next()?;
+
+            Some(Symbol {
+                id: id.as_str().to_string(),
+                name: name.as_str().trim_matches('"').to_string(),
+                kind: SymbolKind::Element(keyword),
+                span,
+                selection: id.as_span().into(),
+                children: Vec::new(),
+            })
+        }
+        // `hide Foo` and `show Foo methods` name the element they apply to
+        Rule::visibility_command => {
+            let command: &str = pair.as_str();
+            let (_, target, _, _) = visibility::words(command.trim())?;
+            let target: &str = target?;
+            let id: &str = visibility::hidden_target(target)?;
+            let start: usize = pair.as_span().start() + command.find(target)?;
+            references.push(Reference {
+                id: id.to_string(),
+                span: Span {
+                    start,
+                    end: start + target.len(),
+                },
+            });
+            None
+        }
+        Rule::archimate_relation => {
+            pair.into_inner()
+                .filter(|p: &Pair<Rule>| p.as_rule() == Rule::archimate_id)
+                .for_each(|p: Pair<Rule>| {
+                    references.push(Reference {
+                        id: p.as_str().to_string(),
+                        span: p.as_span().into(),
+                    })
+                });
+            None
+        }
+        Rule::package => {
+            let mut inner: Pairs<Rule> = pair.into_inner();
+            inner.next()?;
+            let name: Pair<Rule> = inner.next()?;
+
+            Some(Symbol {
+                id: name.as_str().trim_matches('"').to_string(),
+                name: name.as_str().trim_matches('"').to_string(),
+                kind: SymbolKind::Package,
+                span,
+                selection: name.as_span().into(),
+                children: statements(inner, references),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn member(pair: Pair<Rule>) -> Symbol {
+    let text: &str = pair.as_str().trim_end();
+    let start: usize = pair.as_span().start();
+    let member: AstMember = match pair.as_rule() {
+        Rule::enum_value => parser::enum_value(pair),
+        _ => parser::parse_member(pair),
+    };
+    let name_start: usize = start + text.find(member.name.as_ref()).unwrap_or_default();
+
+    Symbol {
+        id: member.name.to_string(),
+        kind: SymbolKind::Member(member.kind()),
+        span: Span {
+            start,
+            end: start + text.len(),
+        },
+        selection: Span {
+            start: name_start,
+            end: name_start + member.name.len(),
+        },
+        name: member.name.to_string(),
+        children: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const SOURCE: &str = "@startuml\npackage \"Core\" {\n  class \"User Account\" as User\n}\nUser --> Profile\n@enduml\n";
+
+    #[test]
+    fn test_indexes_declarations_and_references() {
+        let index: SourceIndex = index_source(SOURCE).unwrap();
+
+        let user: &Symbol = index.definition("User").unwrap();
+        assert_eq!(user.name, "User Account");
+        assert_eq!(&SOURCE[user.selection.start..user.selection.end], "User");
+        assert_eq!(index.symbols[0].kind, SymbolKind::Package);
+        assert_eq!(
+            index
+                .references
+                .iter()
+                .map(|reference: &Reference| reference.id.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["User", "Profile"]
+        );
+        assert_eq!(index.ids(), vec!["Profile", "User"]);
+    }
+
+    #[test]
+    fn test_outlines_members_under_their_element() {
+        let source: &str =
+            "@startuml\nclass User {\n  -id : Uuid\n  +rename(to) : bool  \n}\n@enduml";
+
+        let symbols: Vec<Symbol> = outline(source).unwrap();
+
+        let members: Vec<(&str, &SymbolKind, &str)> = symbols[0]
+            .children
+            .iter()
+            .map(|member: &Symbol| {
+                (
+                    &source[member.selection.start..member.selection.end],
+                    &member.kind,
+                    &source[member.span.start..member.span.end],
+                )
+            })
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("id", &SymbolKind::Member(MemberKind::Field), "-id : Uuid"),
+                (
+                    "rename",
+                    &SymbolKind::Member(MemberKind::Method),
+                    "+rename(to) : bool"
+                ),
+            ]
+        );
+        assert_eq!(symbols[0].span.end, source.find("\n@enduml").unwrap());
+    }
+
+    #[test]
+    fn test_finds_references_starting_with_the_declaration() {
+        let source: &str = "@startuml\nclass User\nUser --> Profile\nProfile --> User\n@enduml";
+        let index: SourceIndex = index_source(source).unwrap();
+
+        let spans: Vec<usize> = index
+            .find_references("User")
+            .iter()
+            .map(|span: &Span| span.start)
+            .collect();
+
+        assert_eq!(spans, vec![16, 21, 50]);
+        assert_eq!(index.find_references("Profile").len(), 2);
+        assert!(index.find_references("Order").is_empty());
+    }
+
+    #[test]
+    fn test_indexes_archimate_macros() {
+        let source: &str = "@startuml\nBusiness_Role(desk_clerk, \"Clerk\")\nRel_Assignment(desk_clerk, desk)\n@enduml";
+        let index: SourceIndex = index_source(source).unwrap();
+
+        assert_eq!(
+            index
+                .definition("desk_clerk")
+                .map(|symbol: &Symbol| &symbol.kind),
+            Some(&SymbolKind::Element("Business_Role".to_string()))
+        );
+        assert_eq!(index.find_references("desk_clerk").len(), 2);
+        assert_eq!(index.ids(), vec!["desk", "desk_clerk"]);
+    }
+
+    #[test]
+    fn test_indexes_inside_sequence_fragments() {
+        let source: &str = "@startuml\nparticipant Shop\nalt paid\n  actor Clerk\n  Shop -> Clerk : ship\nend\nactivate Shop\n@enduml";
+        let index: SourceIndex = index_source(source).unwrap();
+
+        assert_eq!(index.ids(), vec!["Clerk", "Shop"]);
+        assert_eq!(index.find_references("Shop").len(), 3);
+        assert_eq!(index.find_references("Clerk").len(), 2);
+    }
+
+    #[test]
+    fn test_nests_the_states_of_composite_states() {
+        let source: &str = "@startuml\nstate \"Processing order\" as Busy {\n  state Checking\n  [*] --> Checking\n}\nBusy : entry / start timer\n@enduml";
+        let index: SourceIndex = index_source(source).unwrap();
+
+        assert_eq!(index.symbols[0].id, "Busy");
+        assert_eq!(index.symbols[0].children[0].id, "Checking");
+        assert_eq!(index.ids(), vec!["Busy", "Checking"]);
+        assert_eq!(index.find_references("Busy").len(), 2);
+        assert_eq!(index.find_references("Checking").len(), 2);
+    }
+
+    #[test]
+    fn test_finds_the_id_at_an_offset() {
+        let index: SourceIndex = index_source(SOURCE).unwrap();
+        let relation: usize = SOURCE.find("User -->").unwrap();
+
+        assert_eq!(index.id_at(relation + 2), Some("User"));
+        assert_eq!(
+            index.id_at(SOURCE.find("Profile").unwrap()),
+            Some("Profile")
+        );
+        assert_eq!(index.id_at(0), None);
+    }
+
+    #[test]
+    fn test_reports_syntax_errors_with_position() {
+        let error: GraphGatewayError = index_source("@startuml\nclass\n@enduml").unwrap_err();
+
+        assert!(matches!(error, GraphGatewayError::Parse { line: 3, .. }));
+    }
+}
@@ -0,0 +1,89 @@
+//! Themes bundled with PlantUML that `!theme <name>` picks, each spelled out
+//! as the `skinparam` settings it stands for so a themed diagram reads like
+//! one styling itself.
+
+use crate::infrastructure::skinparam::GLOBAL;
+
+/// Settings of one element, as `skinparam <element> { ... }` gives them
+type Settings = (&'static str, &'static [(&'static str, &'static str)]);
+
+const PLAIN: &[Settings] = &[
+    (
+        GLOBAL,
+        &[
+            ("shadowing", "false"),
+            ("defaultFontName", "Helvetica"),
+            ("backgroundColor", "#FFFFFF"),
+        ],
+    ),
+    (
+        "class",
+        &[("BackgroundColor", "#FFFFFF"), ("BorderColor", "#000000")],
+    ),
+    ("arrow", &[("Color", "#000000")]),
+];
+
+const CERULEAN: &[Settings] = &[
+    (
+        GLOBAL,
+        &[
+            ("shadowing", "false"),
+            ("defaultFontName", "Verdana"),
+            ("backgroundColor", "#FFFFFF"),
+        ],
+    ),
+    (
+        "class",
+        &[("BackgroundColor", "#2FA4E7"), ("BorderColor", "#178ACC")],
+    ),
+    ("arrow", &[("Color", "#178ACC")]),
+];
+
+const BLUEPRINT: &[Settings] = &[
+    (
+        GLOBAL,
+        &[
+            ("shadowing", "false"),
+            ("defaultFontName", "Courier"),
+            ("backgroundColor", "#1E4B8F"),
+            ("defaultFontColor", "#FFFFFF"),
+        ],
+    ),
+    (
+        "class",
+        &[("BackgroundColor", "#1E4B8F"), ("BorderColor", "#FFFFFF")],
+    ),
+    ("arrow", &[("Color", "#FFFFFF")]),
+];
+
+const SKETCHY: &[Settings] = &[
+    (
+        GLOBAL,
+        &[
+            ("handwritten", "true"),
+            ("shadowing", "false"),
+            ("backgroundColor", "#FFFFFF"),
+        ],
+    ),
+    (
+        "class",
+        &[("BackgroundColor", "#FFFFFF"), ("BorderColor", "#333333")],
+    ),
+    ("arrow", &[("Color", "#333333")]),
+];
+
+/// The settings a theme stands for, if it is one of the bundled ones. A
+/// `from <url>` after the name, naming where to fetch it, is left out.
+pub(crate) fn settings(directive: &str) -> Option<&'static [Settings]> {
+    let name: &str = directive
+        .strip_prefix("!theme")?
+        .split_whitespace()
+        .next()?;
+    match name {
+        "plain" => Some(PLAIN),
+        "cerulean" => Some(CERULEAN),
+        "blueprint" => Some(BLUEPRINT),
+        "sketchy" => Some(SKETCHY),
+        _ => None,
+    }
+}
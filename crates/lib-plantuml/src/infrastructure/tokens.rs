@@ -0,0 +1,256 @@
+use crate::infrastructure::{
+    erd,
+    syntax::{KEYWORDS, Span},
+};
+
+/// Characters that make up relation arrows such as `<|--`, `*..` or `--o`
+const ARROW_CHARS: &[char] = &['-', '.', '<', '>', '|', '*', '#', '+', '^'];
+const DIRECTIONS: [&str; 4] = ["up", "down", "left", "right"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    Name,
+    Arrow,
+    Stereotype,
+    String,
+    Comment,
+}
+
+impl TokenKind {
+    pub const ALL: [TokenKind; 6] = [
+        TokenKind::Keyword,
+        TokenKind::Name,
+        TokenKind::Arrow,
+        TokenKind::Stereotype,
+        TokenKind::String,
+        TokenKind::Comment,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenKind::Keyword => "keyword",
+            TokenKind::Name => "name",
+            TokenKind::Arrow => "arrow",
+            TokenKind::Stereotype => "stereotype",
+            TokenKind::String => "string",
+            TokenKind::Comment => "comment",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+/// Splits a PlantUML source into classified spans for syntax highlighting.
+/// The raw text is scanned rather than parsed, so sources with errors are
+/// still highlighted; whitespace and punctuation get no token.
+pub fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut offset: usize = 0;
+
+    while let Some(c) = input[offset..].chars().next() {
+        let rest: &str = &input[offset..];
+        let (kind, len): (Option<TokenKind>, usize) = if rest.starts_with("/'") {
+            (
+                Some(TokenKind::Comment),
+                rest.find("'/").map_or(rest.len(), |end: usize| end + 2),
+            )
+        } else if c == '\'' {
+            (Some(TokenKind::Comment), line_len(rest))
+        } else if c == '"' {
+            (Some(TokenKind::String), string_len(rest))
+        } else if rest.starts_with("<<") {
+            (
+                Some(TokenKind::Stereotype),
+                rest[..line_len(rest)]
+                    .find(">>")
+                    .map_or(line_len(rest), |end: usize| end + 2),
+            )
+        } else if c == ':' {
+            // Relation labels run to the end of the line
+            let label: &str = &rest[1..line_len(rest)];
+            let start: usize = offset + 1 + label.len() - label.trim_start().len();
+            let text: &str = label.trim();
+            if !text.is_empty() {
+                tokens.push(Token {
+                    kind: TokenKind::String,
+                    span: Span {
+                        start,
+                        end: start + text.len(),
+                    },
+                });
+            }
+            (None, 1 + label.len())
+        } else if let Some(len) = arrow_len(rest) {
+            (Some(TokenKind::Arrow), len)
+        } else if c == '@' || is_word_char(c) {
+            let len: usize = c.len_utf8() + word_len(&rest[c.len_utf8()..]);
+            let kind: TokenKind = if KEYWORDS.contains(&&rest[..len]) {
+                TokenKind::Keyword
+            } else {
+                TokenKind::Name
+            };
+            (Some(kind), len)
+        } else {
+            (None, c.len_utf8())
+        };
+
+        if let Some(kind) = kind {
+            tokens.push(Token {
+                kind,
+                span: Span {
+                    start: offset,
+                    end: offset + len,
+                },
+            });
+        }
+        offset += len;
+    }
+
+    tokens
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !is_word_char(c)).unwrap_or(text.len())
+}
+
+fn line_len(text: &str) -> usize {
+    text.find('\n').unwrap_or(text.len())
+}
+
+/// Length of a quoted string, up to the end of the line when unterminated
+fn string_len(text: &str) -> usize {
+    let mut escaped: bool = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            '\n' => return i,
+            '"' if !escaped => return i + 1,
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    text.len()
+}
+
+/// Length of an arrow at the start of `text`, including `o` heads and
+/// direction hints such as `-up->` or `-[hidden]-`
+fn arrow_len(text: &str) -> Option<usize> {
+    // Crow's foot ends such as `}o` are not made of arrow characters
+    if let Some(crow_foot) = text
+        .get(..6)
+        .filter(|arrow: &&str| erd::relationship(arrow).is_some())
+    {
+        return Some(crow_foot.len());
+    }
+    let run = |text: &str| {
+        text.find(|c: char| !ARROW_CHARS.contains(&c))
+            .unwrap_or(text.len())
+    };
+    let is_line = |c: char| c == '-' || c == '.';
+
+    let head: usize = usize::from(text.starts_with("o-") || text.starts_with("o."));
+    let mut len: usize = head + run(&text[head..]);
+
+    if text[..len].ends_with(is_line) {
+        let tail: &str = &text[len..];
+        let hint: Option<usize> = DIRECTIONS
+            .iter()
+            .find(|direction: &&&str| tail.starts_with(**direction))
+            .map(|direction: &&str| direction.len())
+            .or_else(|| {
+                tail.starts_with('[')
+                    .then(|| tail.find(']').map(|end: usize| end + 1))
+                    .flatten()
+            });
+        if let Some(hint) = hint.filter(|hint: &usize| run(&tail[*hint..]) > 0) {
+            len += hint + run(&tail[hint..]);
+        }
+    }
+    // A trailing `o` is an aggregation head unless it starts a word
+    if text[..len].ends_with(is_line)
+        && text[len..].starts_with('o')
+        && !text[len + 1..].starts_with(is_word_char)
+    {
+        len += 1;
+    }
+
+    (len >= 2 && text[..len].contains(is_line)).then_some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn classify(input: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(input)
+            .iter()
+            .map(|token: &Token| (token.kind, &input[token.span.start..token.span.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_classifies_declarations() {
+        assert_eq!(
+            classify("class \"User Account\" as User <<entity>> ' main type"),
+            vec![
+                (TokenKind::Keyword, "class"),
+                (TokenKind::String, "\"User Account\""),
+                (TokenKind::Keyword, "as"),
+                (TokenKind::Name, "User"),
+                (TokenKind::Stereotype, "<<entity>>"),
+                (TokenKind::Comment, "' main type"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classifies_arrows_and_labels() {
+        assert_eq!(
+            classify("User o-- Order\nA -up-> B : owns many\nC <|.. D"),
+            vec![
+                (TokenKind::Name, "User"),
+                (TokenKind::Arrow, "o--"),
+                (TokenKind::Name, "Order"),
+                (TokenKind::Name, "A"),
+                (TokenKind::Arrow, "-up->"),
+                (TokenKind::Name, "B"),
+                (TokenKind::String, "owns many"),
+                (TokenKind::Name, "C"),
+                (TokenKind::Arrow, "<|.."),
+                (TokenKind::Name, "D"),
+            ]
+        );
+        assert_eq!(
+            classify("Customer ||--o{ Order"),
+            vec![
+                (TokenKind::Name, "Customer"),
+                (TokenKind::Arrow, "||--o{"),
+                (TokenKind::Name, "Order"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlights_sources_that_do_not_parse() {
+        assert_eq!(
+            classify("@startuml\n/' block\ncomment '/ class \"unterminated\n@enduml"),
+            vec![
+                (TokenKind::Keyword, "@startuml"),
+                (TokenKind::Comment, "/' block\ncomment '/"),
+                (TokenKind::Keyword, "class"),
+                (TokenKind::String, "\"unterminated"),
+                (TokenKind::Keyword, "@enduml"),
+            ]
+        );
+    }
+}
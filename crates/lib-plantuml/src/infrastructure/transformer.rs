@@ -1,152 +1,1482 @@
 use lib_core::entities::{
-    edge::{Edge, EdgeKind},
-    graph::Graph,
-    group::Group,
-    id::Id,
-    node::{Node, NodeKind},
+    computed_style::STYLE,
+    edge::{Edge, EdgeDirection, EdgeKind, EdgeLayout},
+    graph::{Graph, GraphKind},
+    group::{Group, GroupKind},
+    id::{Id, Text},
+    member::Member,
+    node::{DeploymentKind, Node, NodeKind},
+    rich_text::RichText,
+    sequence::{Activation, Fragment, Operand},
+    style::{Colors, Style},
+    value::Value,
+    warning::Warning,
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
 };
-use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::infrastructure::models::ast_node::AstNode;
+use crate::infrastructure::{
+    activity::{self, Block},
+    archimate, color, creole, erd,
+    models::{
+        ast_arena::{ArenaNode, AstArena},
+        ast_node::{AstBranch, AstMember, AstNode, AstOperand, AstStereotype},
+    },
+    sequence, skinparam, state, theme, usecase,
+    visibility::{self, Command, Part, Target},
+};
 
 pub struct GraphBuilder {
     graph: Graph,
-    alias_map: HashMap<String, String>, // Maps PlantUML aliases to actual Node IDs
+    alias_map: HashMap<String, Id>, // Maps PlantUML aliases to actual Node IDs
+    changes: Option<Vec<Change>>,   // Only recorded while adding statements one by one
+    hidden: HashSet<String>,        // Names of `hide` directives, also hiding later elements
+    messages: usize,                // Numbers sequence messages as they appear in the source
+    last_message: Option<Id>,       // Where activations start and end
+    operands: Vec<(Id, usize)>,     // Fragments being read, with the operand each is at
+    active: HashMap<Id, Vec<usize>>, // Open activations of each participant
+    scope: Option<Id>,              // The composite state being read
+    steps: usize,                   // Numbers activity steps as they appear in the source
+    tails: Vec<(Id, Option<Text>)>, // Activity steps the next one follows, with their flow's label
+    together: Option<usize>,        // The `together` block being read, numbered from 1
+    togethers: usize,               // `together` blocks read so far
+    namespace: Option<Id>,          // The class whose body's classes are being read
+    commands: Vec<Command>,         // `hide` and `show` commands of parts and kinds, in order
+    declared: HashMap<Id, Vec<Member>>, // Members of the nodes before commands hid any
+    last_link: Option<Id>,          // The relation a `note on link` annotates
+    notes: usize,                   // Numbers notes as they appear in the source
+}
+
+/// An element a statement inserted or replaced
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Change {
+    Node(Id),
+    Edge(Id),
+    Group(Id),
+}
+
+/// What a definition says of its element besides its name and members
+#[derive(Default)]
+struct Details<'s> {
+    generics: Option<&'s str>,
+    stereotype: Option<&'s AstStereotype<'s>>,
+    colors: Option<&'s str>,
+}
+
+/// A relation statement as either parse gives it
+struct Link<'s> {
+    left: &'s str,
+    right: &'s str,
+    arrow: &'s str,
+    style: Option<&'s str>,
+    direction: Option<EdgeDirection>,
+    length: Option<usize>,
+    label: Option<&'s str>,
+    left_cardinality: Option<&'s str>,
+    right_cardinality: Option<&'s str>,
+    left_port: Option<&'s str>,
+    right_port: Option<&'s str>,
 }
 
 impl GraphBuilder {
     pub fn new() -> Self {
         Self {
             graph: Graph {
-                id: Uuid::new_v4().to_string(),
+                id: Uuid::new_v4().to_string().into(),
                 ..Default::default()
             },
             alias_map: HashMap::new(),
+            changes: None,
+            hidden: HashSet::new(),
+            messages: 0,
+            last_message: None,
+            operands: Vec::new(),
+            active: HashMap::new(),
+            scope: None,
+            steps: 0,
+            tails: Vec::new(),
+            together: None,
+            togethers: 0,
+            namespace: None,
+            commands: Vec::new(),
+            declared: HashMap::new(),
+            last_link: None,
+            notes: 0,
         }
     }
 
     pub fn build(mut self, ast: Vec<AstNode>) -> Graph {
+        if ast.iter().any(state::is_state) {
+            self.graph.kind = GraphKind::State;
+        } else if ast.iter().any(activity::is_activity) {
+            self.graph.kind = GraphKind::Flowchart;
+        } else if ast.iter().any(usecase::is_use_case) {
+            self.graph.kind = GraphKind::UseCase;
+        } else if ast.iter().any(erd::is_entity_relationship) {
+            self.graph.kind = GraphKind::EntityRelationship;
+        } else if ast.iter().any(sequence::is_sequence) {
+            self.graph.kind = GraphKind::Sequence;
+        }
         ast.iter().for_each(|node: &AstNode| {
             self.process_ast_node(node, None);
         });
+        self.read_markup(&self.everything());
+        self.graph
+    }
+
+    /// Builds from statements parsed into an arena; the graph is the same as
+    /// from the equivalent tree
+    pub fn build_arena(mut self, arena: &AstArena) -> Graph {
+        if state::is_state_arena(arena) {
+            self.graph.kind = GraphKind::State;
+        } else if activity::is_activity_arena(arena) {
+            self.graph.kind = GraphKind::Flowchart;
+        } else if usecase::is_use_case_arena(arena) {
+            self.graph.kind = GraphKind::UseCase;
+        } else if erd::is_entity_relationship_arena(arena) {
+            self.graph.kind = GraphKind::EntityRelationship;
+        } else if sequence::is_sequence_arena(arena) {
+            self.graph.kind = GraphKind::Sequence;
+        }
+        arena.roots().for_each(|index: usize| {
+            self.process_arena_node(arena, index, None);
+        });
+        self.read_markup(&self.everything());
+        self.graph
+    }
+
+    /// Adds one top-level statement, returning what it changed in order. A
+    /// diagram becomes a sequence, state, activity, use case or entity
+    /// relationship diagram from its first such statement on, so earlier
+    /// `-->` relations stay associations; only a sequence diagram may still
+    /// become another.
+    pub(crate) fn add(&mut self, node: &AstNode) -> Vec<Change> {
+        let settled: bool = matches!(
+            self.graph.kind,
+            GraphKind::State
+                | GraphKind::Flowchart
+                | GraphKind::UseCase
+                | GraphKind::EntityRelationship
+        );
+        if state::is_state(node) {
+            self.graph.kind = GraphKind::State;
+        } else if !settled && activity::is_activity(node) {
+            self.graph.kind = GraphKind::Flowchart;
+        } else if !settled && usecase::is_use_case(node) {
+            self.graph.kind = GraphKind::UseCase;
+        } else if !settled && erd::is_entity_relationship(node) {
+            self.graph.kind = GraphKind::EntityRelationship;
+        } else if !settled && sequence::is_sequence(node) {
+            self.graph.kind = GraphKind::Sequence;
+        }
+        self.changes = Some(Vec::new());
+        self.process_ast_node(node, None);
+        let changes: Vec<Change> = self.changes.take().unwrap_or_default();
+        self.read_markup(&changes);
+        changes
+    }
+
+    pub(crate) fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    fn everything(&self) -> Vec<Change> {
         self.graph
+            .nodes
+            .keys()
+            .cloned()
+            .map(Change::Node)
+            .chain(self.graph.edges.keys().cloned().map(Change::Edge))
+            .collect()
     }
 
-    fn process_ast_node(&mut self, node: &AstNode, parent_id: Option<String>) {
+    /// Reads the Creole markup of the labels of the elements `changes` name,
+    /// keeping their plain text as the label. Labels read already are left as
+    /// they are.
+    fn read_markup(&mut self, changes: &[Change]) {
+        changes.iter().for_each(|change: &Change| {
+            let (label, rich_label): (&mut Option<Text>, &mut Option<RichText>) = match change {
+                Change::Node(id) => match self.graph.nodes.get_mut(id) {
+                    Some(node) => (&mut node.label, &mut node.rich_label),
+                    None => return,
+                },
+                Change::Edge(id) => match self.graph.edges.get_mut(id) {
+                    Some(edge) => (&mut edge.label, &mut edge.rich_label),
+                    None => return,
+                },
+                Change::Group(_) => return,
+            };
+            if rich_label.is_some() {
+                return;
+            }
+            if let Some(rich) = label.as_deref().and_then(creole::read) {
+                *label = Some(rich.plain().into());
+                *rich_label = Some(rich);
+            }
+        });
+    }
+
+    fn record(&mut self, change: Change) {
+        if let Some(changes) = &mut self.changes {
+            changes.push(change);
+        }
+    }
+
+    fn process_ast_node(&mut self, node: &AstNode, parent_id: Option<Id>) {
         match node {
             AstNode::Definition {
                 keyword,
                 name,
                 alias,
+                generics,
+                stereotype,
+                colors,
+                members,
+                children,
             } => {
-                let id: String = alias.clone().unwrap_or_else(|| name.clone());
-
-                if let Some(a) = alias {
-                    self.alias_map.insert(a.clone(), id.clone());
-                }
-
-                let kind: NodeKind = match keyword.as_str() {
-                    "class" => NodeKind::Entity,
-                    "interface" => NodeKind::Interface,
-                    "actor" => NodeKind::Actor,
-                    "component" => NodeKind::Component,
-                    "database" => NodeKind::Database,
-                    _ => NodeKind::Custom(keyword.clone()),
-                };
-
-                self.graph.nodes.insert(
-                    id.clone(),
-                    Node {
-                        id: id.clone(),
-                        kind,
-                        label: Some(name.clone()),
-                        data: HashMap::new(),
-                        style: None,
-                        parent: parent_id,
+                let id: Id = self.define(
+                    keyword,
+                    name,
+                    alias.as_deref(),
+                    Details {
+                        generics: generics.as_deref(),
+                        stereotype: stereotype.as_ref(),
+                        colors: colors.as_deref(),
                     },
+                    members.iter(),
+                    parent_id.clone(),
                 );
+                let outer: Option<Id> = self.namespace.replace(id);
+                children.iter().for_each(|child: &AstNode| {
+                    self.process_ast_node(child, parent_id.clone());
+                });
+                self.namespace = outer;
             }
             AstNode::Relation {
                 left,
                 right,
                 arrow,
+                style,
+                direction,
+                length,
                 label,
+                left_cardinality,
+                right_cardinality,
+                left_port,
+                right_port,
+            } => self.relate(Link {
+                left,
+                right,
+                arrow,
+                style: style.as_deref(),
+                direction: *direction,
+                length: *length,
+                label: label.as_deref(),
+                left_cardinality: left_cardinality.as_deref(),
+                right_cardinality: right_cardinality.as_deref(),
+                left_port: left_port.as_deref(),
+                right_port: right_port.as_deref(),
+            }),
+            AstNode::Package {
+                keyword,
+                name,
+                children,
             } => {
-                let left_id: String = self.resolve_id(&left);
-                let right_id: String = self.resolve_id(&right);
-
-                // Ensure implicit nodes exist
-                self.ensure_node_exists(&left_id);
-                self.ensure_node_exists(&right_id);
-
-                let (kind, directed): (EdgeKind, bool) = self.map_arrow(&arrow);
-
-                let edge_id: String = Uuid::new_v4().to_string();
-                self.graph.edges.insert(
-                    edge_id.clone(),
-                    Edge {
-                        id: edge_id,
-                        from: left_id,
-                        to: right_id,
-                        directed,
-                        kind,
-                        label: label.clone(),
-                        data: HashMap::new(),
-                        style: None,
-                    },
-                );
-            }
-            AstNode::Package { name, children } => {
-                let group_id: String = Uuid::new_v4().to_string();
+                let group_id: Id = Uuid::new_v4().to_string().into();
                 let mut child_ids: Vec<Id> = Vec::new();
+                declared_ids(children, None, &mut child_ids);
 
                 children.iter().for_each(|child: &AstNode| {
-                    // Quick peek to grab IDs for the group's child list
-                    if let AstNode::Definition {
-                        alias,
-                        name: child_name,
-                        ..
-                    } = &child
-                    {
-                        child_ids.push(alias.clone().unwrap_or_else(|| child_name.clone()));
-                    }
                     self.process_ast_node(child, Some(group_id.clone()));
                 });
 
-                self.graph.groups.insert(
-                    group_id.clone(),
-                    Group {
-                        id: group_id,
-                        label: Some(name.clone()),
-                        children: child_ids,
-                        parent: parent_id,
+                self.group(group_id, keyword, name, child_ids, parent_id);
+            }
+            AstNode::Together { children } => {
+                self.togethers += 1;
+                let outer: Option<usize> = self.together.replace(self.togethers);
+                children.iter().for_each(|child: &AstNode| {
+                    self.process_ast_node(child, parent_id.clone());
+                });
+                self.together = outer;
+            }
+            AstNode::State {
+                name,
+                alias,
+                description,
+                children,
+            } => {
+                let id: Id = self.state(
+                    name,
+                    alias.as_deref(),
+                    description.as_deref(),
+                    children.is_some(),
+                    parent_id,
+                );
+                if let Some(children) = children {
+                    let scope: Option<Id> = self.scope.replace(id.clone());
+                    children.iter().for_each(|child: &AstNode| {
+                        self.process_ast_node(child, Some(id.clone()));
+                    });
+                    self.scope = scope;
+                }
+            }
+            AstNode::AssociationClass { left, right, class } => self.associate(left, right, class),
+            AstNode::Activation {
+                participant,
+                active,
+            } => self.activate(participant, *active),
+            AstNode::Fragment { keyword, operands } => {
+                let fragment: Id = self.fragment(
+                    keyword,
+                    operands
+                        .iter()
+                        .map(|operand: &AstOperand| operand.condition.as_deref()),
+                );
+                operands.iter().enumerate().for_each(
+                    |(position, operand): (usize, &AstOperand)| {
+                        self.operands.push((fragment.clone(), position));
+                        operand.children.iter().for_each(|child: &AstNode| {
+                            self.process_ast_node(child, parent_id.clone());
+                        });
+                        self.operands.pop();
                     },
                 );
             }
+            AstNode::Terminal { keyword } => self.terminal(keyword, parent_id),
+            AstNode::Action { text } => {
+                self.step("action", Some(text), parent_id);
+            }
+            AstNode::Control {
+                keyword,
+                branches,
+                exit,
+            } => {
+                let guard: Option<&str> = branches
+                    .first()
+                    .and_then(|branch: &AstBranch| branch.guard.as_deref());
+                let mut block: Block = self.open(keyword, guard, parent_id.clone());
+                branches
+                    .iter()
+                    .enumerate()
+                    .for_each(|(position, branch): (usize, &AstBranch)| {
+                        self.branch(
+                            &mut block,
+                            position,
+                            branch.guard.as_deref(),
+                            branch.label.as_deref(),
+                            parent_id.clone(),
+                        );
+                        branch.children.iter().for_each(|child: &AstNode| {
+                            self.process_ast_node(child, parent_id.clone());
+                        });
+                    });
+                self.close(block, exit.as_deref(), parent_id);
+            }
+            AstNode::Heading { keyword, text } => self.heading(keyword, text),
+            AstNode::Direction(direction) => self.graph.direction = *direction,
+            AstNode::Legend(legend) => self.graph.metadata.legend = Some(legend.clone()),
+            AstNode::LinkNote {
+                position,
+                text,
+                line,
+            } => self.link_note(position.as_deref(), text, *line, parent_id),
+            AstNode::Visibility {
+                show,
+                target,
+                part,
+                empty,
+                line,
+            } => self.visibility(*show, target.as_deref(), part.as_deref(), *empty, *line),
+            AstNode::Directive { text, line } => self.ignore(text, *line),
         }
     }
 
-    fn resolve_id(&self, identifier: &str) -> String {
+    fn process_arena_node(&mut self, arena: &AstArena, index: usize, parent_id: Option<Id>) {
+        match arena.get(index) {
+            ArenaNode::Definition {
+                keyword,
+                name,
+                alias,
+                generics,
+                stereotype,
+                colors,
+                members,
+                ..
+            } => {
+                let id: Id = self.define(
+                    keyword,
+                    name,
+                    *alias,
+                    Details {
+                        generics: *generics,
+                        stereotype: stereotype.as_ref(),
+                        colors: *colors,
+                    },
+                    arena.members(members.clone()).iter(),
+                    parent_id.clone(),
+                );
+                let outer: Option<Id> = self.namespace.replace(id);
+                arena.children(index).for_each(|child: usize| {
+                    self.process_arena_node(arena, child, parent_id.clone());
+                });
+                self.namespace = outer;
+            }
+            ArenaNode::Relation {
+                left,
+                right,
+                arrow,
+                style,
+                direction,
+                length,
+                label,
+                left_cardinality,
+                right_cardinality,
+                left_port,
+                right_port,
+            } => self.relate(Link {
+                left,
+                right,
+                arrow,
+                style: *style,
+                direction: *direction,
+                length: *length,
+                label: *label,
+                left_cardinality: *left_cardinality,
+                right_cardinality: *right_cardinality,
+                left_port: *left_port,
+                right_port: *right_port,
+            }),
+            ArenaNode::Package { keyword, name, .. } => {
+                let group_id: Id = Uuid::new_v4().to_string().into();
+                let mut child_ids: Vec<Id> = Vec::new();
+                declared_arena_ids(arena, index, None, &mut child_ids);
+
+                arena.children(index).for_each(|child: usize| {
+                    self.process_arena_node(arena, child, Some(group_id.clone()));
+                });
+
+                self.group(group_id, keyword, name, child_ids, parent_id);
+            }
+            ArenaNode::Together { .. } => {
+                self.togethers += 1;
+                let outer: Option<usize> = self.together.replace(self.togethers);
+                arena.children(index).for_each(|child: usize| {
+                    self.process_arena_node(arena, child, parent_id.clone());
+                });
+                self.together = outer;
+            }
+            ArenaNode::State {
+                name,
+                alias,
+                description,
+                composite,
+                ..
+            } => {
+                let id: Id = self.state(name, *alias, *description, *composite, parent_id);
+                if *composite {
+                    let scope: Option<Id> = self.scope.replace(id.clone());
+                    arena.children(index).for_each(|child: usize| {
+                        self.process_arena_node(arena, child, Some(id.clone()));
+                    });
+                    self.scope = scope;
+                }
+            }
+            ArenaNode::AssociationClass { left, right, class } => {
+                self.associate(left, right, class)
+            }
+            ArenaNode::Activation {
+                participant,
+                active,
+            } => self.activate(participant, *active),
+            ArenaNode::Fragment { keyword, .. } => {
+                let operands: Vec<usize> = arena.children(index).collect();
+                let fragment: Id = self.fragment(
+                    keyword,
+                    operands
+                        .iter()
+                        .map(|operand: &usize| match arena.get(*operand) {
+                            ArenaNode::Operand { condition, .. } => *condition,
+                            _ => None,
+                        }),
+                );
+                operands
+                    .iter()
+                    .enumerate()
+                    .for_each(|(position, operand): (usize, &usize)| {
+                        self.operands.push((fragment.clone(), position));
+                        arena.children(*operand).for_each(|child: usize| {
+                            self.process_arena_node(arena, child, parent_id.clone());
+                        });
+                        self.operands.pop();
+                    });
+            }
+            ArenaNode::Terminal { keyword } => self.terminal(keyword, parent_id),
+            ArenaNode::Action { text } => {
+                self.step("action", Some(text), parent_id);
+            }
+            ArenaNode::Control { keyword, exit, .. } => {
+                let branches: Vec<usize> = arena.children(index).collect();
+                let guard: Option<&str> =
+                    branches
+                        .first()
+                        .and_then(|branch: &usize| match arena.get(*branch) {
+                            ArenaNode::Branch { guard, .. } => *guard,
+                            _ => None,
+                        });
+                let mut block: Block = self.open(keyword, guard, parent_id.clone());
+                branches
+                    .iter()
+                    .enumerate()
+                    .for_each(|(position, branch): (usize, &usize)| {
+                        if let ArenaNode::Branch { guard, label, .. } = arena.get(*branch) {
+                            self.branch(&mut block, position, *guard, *label, parent_id.clone());
+                        }
+                        arena.children(*branch).for_each(|child: usize| {
+                            self.process_arena_node(arena, child, parent_id.clone());
+                        });
+                    });
+                self.close(block, *exit, parent_id);
+            }
+            // Read along with their fragment or activity block
+            ArenaNode::Operand { .. } | ArenaNode::Branch { .. } => {}
+            ArenaNode::Heading { keyword, text } => self.heading(keyword, text),
+            ArenaNode::Direction(direction) => self.graph.direction = *direction,
+            ArenaNode::Legend(legend) => self.graph.metadata.legend = Some(legend.clone()),
+            ArenaNode::LinkNote {
+                position,
+                text,
+                line,
+            } => self.link_note(*position, text, *line, parent_id),
+            ArenaNode::Visibility {
+                show,
+                target,
+                part,
+                empty,
+                line,
+            } => self.visibility(*show, *target, *part, *empty, *line),
+            ArenaNode::Directive { text, line } => self.ignore(text, *line),
+        }
+    }
+
+    fn define<'a>(
+        &mut self,
+        keyword: &str,
+        name: &str,
+        alias: Option<&str>,
+        details: Details,
+        members: impl Iterator<Item = &'a AstMember<'a>>,
+        parent_id: Option<Id>,
+    ) -> Id {
+        let id: Id = definition_id(self.namespace.as_ref(), name, alias);
+
+        if let Some(a) = alias {
+            self.alias_map.insert(a.to_string(), id.clone());
+            // Use cases and actors are also referred to by name, as `(Log in)`
+            if self.graph.kind == GraphKind::UseCase {
+                self.alias_map
+                    .entry(name.to_string())
+                    .or_insert_with(|| id.clone());
+            }
+        }
+
+        let (kind, mut data): (NodeKind, HashMap<Text, Value>) = match keyword {
+            "class" | "entity" => (NodeKind::Entity, HashMap::new()),
+            _ if keyword.starts_with("abstract") => (
+                NodeKind::Entity,
+                HashMap::from([("abstract".into(), Value::Bool(true))]),
+            ),
+            "interface" => (NodeKind::Interface, HashMap::new()),
+            "enum" => (NodeKind::Enum, HashMap::new()),
+            "actor" => (NodeKind::Actor, HashMap::new()),
+            "component" => (NodeKind::Component, HashMap::new()),
+            "database" => (NodeKind::Database, HashMap::new()),
+            _ => sequence::participant(keyword)
+                .or_else(|| DeploymentKind::from_keyword(keyword).map(NodeKind::Deployment))
+                .map(|kind: NodeKind| (kind, HashMap::new()))
+                .or_else(|| archimate::element(keyword))
+                .unwrap_or_else(|| (NodeKind::Custom(keyword.to_string()), HashMap::new())),
+        };
+        if let Some(together) = self.together {
+            data.insert("together".into(), Value::Number(together as f64));
+        }
+        if let Some(generics) = details.generics {
+            data.insert("generics".into(), Value::List(type_parameters(generics)));
+        }
+        if let Some(stereotype) = details.stereotype {
+            [
+                ("stereotype", &stereotype.name),
+                ("spot", &stereotype.spot),
+                ("spot_color", &stereotype.spot_color),
+            ]
+            .into_iter()
+            .filter_map(|(key, text): (&str, &Option<Cow<str>>)| Some((key, text.as_ref()?)))
+            .for_each(|(key, text): (&str, &Cow<str>)| {
+                data.insert(key.into(), Value::String(text.to_string()));
+            });
+        }
+        let (colors, style): (Colors, Option<String>) =
+            details.colors.map(color::element).unwrap_or_default();
+        if let Some(style) = style {
+            data.insert(STYLE.into(), Value::String(style));
+        }
+
+        self.graph.nodes.insert(
+            id.clone(),
+            Node {
+                id: id.clone(),
+                kind,
+                label: Some(name.rsplit("::").next().unwrap_or(name).into()),
+                members: members.map(member).collect(),
+                data,
+                style: None,
+                colors,
+                parent: parent_id,
+                geometry: None,
+                label_anchor: None,
+                hidden: self.hidden.contains(name)
+                    || alias.is_some_and(|alias: &str| self.hidden.contains(alias)),
+                rich_label: None,
+                target_edge: None,
+            },
+        );
+        if !self.commands.is_empty() {
+            self.declared.remove(&id);
+            self.apply_commands(&id);
+        }
+        self.record(Change::Node(id.clone()));
+        id
+    }
+
+    /// A state, or a group with the state's id when it is composite. A
+    /// state declared again, or by a description line, is updated.
+    fn state(
+        &mut self,
+        name: &str,
+        alias: Option<&str>,
+        description: Option<&str>,
+        composite: bool,
+        parent_id: Option<Id>,
+    ) -> Id {
+        let id: Id = Id::from(alias.unwrap_or(name));
+        if let Some(a) = alias {
+            self.alias_map.insert(a.to_string(), id.clone());
+        }
+        let hidden: bool = self.hidden.contains(name)
+            || alias.is_some_and(|alias: &str| self.hidden.contains(alias));
+        self.adopt(parent_id.as_ref(), &id);
+
+        if composite {
+            // A state first met in a transition is now a group
+            self.graph.nodes.remove(&id);
+            let children: Vec<Id> = self
+                .graph
+                .groups
+                .remove(&id)
+                .map(|group: Group| group.children)
+                .unwrap_or_default();
+            self.graph.groups.insert(
+                id.clone(),
+                Group {
+                    id: id.clone(),
+                    kind: GroupKind::Package,
+                    label: Some(name.into()),
+                    children,
+                    parent: parent_id,
+                    geometry: None,
+                    hidden,
+                },
+            );
+            self.record(Change::Group(id.clone()));
+            return id;
+        }
+        if self.graph.groups.contains_key(&id) {
+            return id;
+        }
+
+        let node: &mut Node = self.graph.nodes.entry(id.clone()).or_insert_with(|| Node {
+            id: id.clone(),
+            kind: NodeKind::Custom("state".to_string()),
+            parent: parent_id,
+            hidden,
+            ..Default::default()
+        });
+        if alias.is_some() || node.label.is_none() {
+            node.label = Some(name.into());
+            node.rich_label = None;
+        }
+        if let Some(text) = description {
+            let (key, text): (&str, &str) = state::description(text);
+            let text: String = match node.data.get(key) {
+                Some(Value::String(previous)) => format!("{}\n{}", previous, text),
+                _ => text.to_string(),
+            };
+            node.data.insert(key.into(), Value::String(text));
+        }
+        self.record(Change::Node(id.clone()));
+        id
+    }
+
+    /// Lists `id` among the children of the composite state `parent`
+    fn adopt(&mut self, parent: Option<&Id>, id: &Id) {
+        if let Some(group) = parent.and_then(|parent: &Id| self.graph.groups.get_mut(parent))
+            && !group.children.contains(id)
+        {
+            group.children.push(id.clone());
+        }
+    }
+
+    /// A transition of a state diagram; `[*]` is the initial pseudo-state of
+    /// the composite state being read as a source, and its final one as a
+    /// target
+    fn transition(&mut self, left: &str, arrow: &str, right: &str, label: Option<&str>) -> Id {
+        let (left, right): (&str, &str) = match arrow.starts_with('<') {
+            true => (right, left),
+            false => (left, right),
+        };
+        let from: Id = self.pseudo_state(left, "start");
+        let to: Id = self.pseudo_state(right, "end");
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id.clone(),
+                from,
+                to,
+                directed: arrow != "--",
+                kind: EdgeKind::Flow,
+                label: label.map(Text::from),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+        self.record(Change::Edge(edge_id.clone()));
+        edge_id
+    }
+
+    /// The node a transition end stands for: a state, or a `start` or `end`
+    /// pseudo-state scoped to the composite state being read
+    fn pseudo_state(&mut self, end: &str, kind: &str) -> Id {
+        if end != state::PSEUDO_STATE {
+            let id: Id = self.resolve_id(end);
+            self.ensure_node_exists(&id);
+            return id;
+        }
+        let id: Id = match &self.scope {
+            Some(scope) => Id::from(format!("{}_{}", scope, kind)),
+            None => Id::from(kind),
+        };
+        if !self.graph.nodes.contains_key(&id) {
+            self.graph.nodes.insert(
+                id.clone(),
+                Node {
+                    id: id.clone(),
+                    kind: NodeKind::Custom(kind.to_string()),
+                    parent: self.scope.clone(),
+                    ..Default::default()
+                },
+            );
+            self.adopt(self.scope.clone().as_ref(), &id);
+            self.record(Change::Node(id.clone()));
+        }
+        id
+    }
+
+    /// A relation, drawn with the style its arrow's brackets give and
+    /// keeping the multiplicities and members of its ends and the direction and length
+    /// it gives. `hidden` in the brackets leaves the edge undrawn, `norank`
+    /// keeps it from placing its ends.
+    fn relate(&mut self, link: Link) {
+        let (left, left_port): (Cow<str>, Option<&str>) = self.qualify(link.left, link.left_port);
+        let (right, right_port): (Cow<str>, Option<&str>) =
+            self.qualify(link.right, link.right_port);
+        let edge_id: Id = self.connect(&left, link.arrow, &right, link.label);
+        let Some(edge) = self.graph.edges.get_mut(&edge_id) else {
+            return;
+        };
+        self.last_link = Some(edge_id);
+        let (colors, style): (Colors, Option<String>) =
+            link.style.map(color::arrow).unwrap_or_default();
+        edge.colors = colors;
+        if let Some(style) = style {
+            edge.data.insert(STYLE.into(), Value::String(style));
+        }
+        let words: Vec<&str> = link
+            .style
+            .map(|style: &str| style.split([',', ';']).map(str::trim).collect())
+            .unwrap_or_default();
+        edge.layout.hidden = words.contains(&"hidden");
+        if link.direction.is_some() {
+            edge.layout.direction = link.direction;
+        }
+        edge.layout.length = link.length;
+        edge.from_port = left_port.map(str::to_string);
+        edge.to_port = right_port.map(str::to_string);
+        if words.contains(&"norank") {
+            edge.data.insert("norank".into(), Value::Bool(true));
+        }
+        if let Some(cardinality) = link.left_cardinality {
+            edge.from_cardinality = Some(cardinality.into());
+        }
+        if let Some(cardinality) = link.right_cardinality {
+            edge.to_cardinality = Some(cardinality.into());
+        }
+    }
+
+    /// A relation end written `Outer::Inner` names the class nested in
+    /// `Outer` when one is declared, and a member of `Outer` otherwise
+    fn qualify<'s>(
+        &self,
+        class: &'s str,
+        port: Option<&'s str>,
+    ) -> (Cow<'s, str>, Option<&'s str>) {
+        let Some(port) = port else {
+            return (Cow::Borrowed(class), None);
+        };
+        let nested: String = format!("{}::{}", class, port);
+        match self.graph.nodes.contains_key(&self.resolve_id(&nested)) {
+            true => (Cow::Owned(nested), None),
+            false => (Cow::Borrowed(class), Some(port)),
+        }
+    }
+
+    /// The edge a relation stands for: a transition, a message or a plain
+    /// edge depending on the diagram
+    fn connect(&mut self, left: &str, arrow: &str, right: &str, label: Option<&str>) -> Id {
+        if self.graph.kind == GraphKind::State {
+            return self.transition(left, arrow, right, label);
+        }
+        let left_id: Id = self.endpoint(left);
+        let right_id: Id = self.endpoint(right);
+        let arrow: &str = match self.graph.kind {
+            GraphKind::UseCase => usecase::arrow(arrow),
+            _ => arrow,
+        };
+
+        // Ensure implicit nodes exist
+        self.ensure_node_exists(&left_id);
+        self.ensure_node_exists(&right_id);
+
+        if self.graph.kind == GraphKind::Sequence
+            && let Some(message) = sequence::message(arrow)
+        {
+            let (from, to): (Id, Id) = match message.reversed {
+                true => (right_id, left_id),
+                false => (left_id, right_id),
+            };
+            return self.message(from, to, &message, label);
+        }
+
+        let relationship: Option<erd::Relationship> = erd::relationship(arrow);
+        let relation: archimate::Relation = archimate::relation(arrow).unwrap_or_else(|| {
+            if let Some(relationship) = &relationship {
+                return archimate::Relation {
+                    kind: EdgeKind::Association,
+                    directed: false,
+                    direction: None,
+                    data: relationship.data(),
+                };
+            }
+            let (kind, directed): (EdgeKind, bool) = self.map_arrow(arrow);
+            archimate::Relation {
+                kind,
+                directed,
+                direction: None,
+                data: HashMap::new(),
+            }
+        });
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id.clone(),
+                from: left_id,
+                to: right_id,
+                directed: relation.directed,
+                kind: relation.kind,
+                label: label.map(Text::from),
+                data: relation.data,
+                style: None,
+                colors: Colors::default(),
+                waypoints: Vec::new(),
+                layout: EdgeLayout {
+                    direction: relation.direction,
+                    ..Default::default()
+                },
+                hidden: false,
+                order: self.graph.edges.len(),
+                from_port: None,
+                to_port: None,
+                from_cardinality: relationship
+                    .as_ref()
+                    .map(|relationship: &erd::Relationship| relationship.from.into()),
+                to_cardinality: relationship
+                    .as_ref()
+                    .map(|relationship: &erd::Relationship| relationship.to.into()),
+                rich_label: None,
+            },
+        );
+        self.record(Change::Edge(edge_id.clone()));
+        edge_id
+    }
+
+    /// Attaches `class` to the relation between `left` and `right`, either
+    /// way round, as the edge's `association_class` data; without one, an
+    /// undirected edge is drawn for it
+    fn associate(&mut self, left: &str, right: &str, class: &str) {
+        let (left, right, class): (Id, Id, Id) = (
+            self.resolve_id(left),
+            self.resolve_id(right),
+            self.resolve_id(class),
+        );
+        [&left, &right, &class]
+            .into_iter()
+            .for_each(|id: &Id| self.ensure_node_exists(id));
+
+        let relation: Option<Id> = self
+            .graph
+            .edges
+            .values()
+            .find(|edge: &&Edge| {
+                (edge.from == left && edge.to == right) || (edge.from == right && edge.to == left)
+            })
+            .map(|edge: &Edge| edge.id.clone());
+        let edge_id: Id = relation.unwrap_or_else(|| self.connect(&left, "--", &right, None));
+        if let Some(edge) = self.graph.edges.get_mut(&edge_id) {
+            edge.data
+                .insert("association_class".into(), Value::String(class.to_string()));
+            self.record(Change::Edge(edge_id));
+        }
+    }
+
+    /// The id a relation end refers to, declaring the use case `(Log in)` or
+    /// actor `:Customer:` it spells out unless already declared
+    fn endpoint(&mut self, text: &str) -> Id {
+        let Some((keyword, name)) = usecase::endpoint(text) else {
+            return self.resolve_id(text);
+        };
+        let id: Id = self.resolve_id(name);
+        if !self.graph.nodes.contains_key(&id) {
+            self.define(
+                keyword,
+                name,
+                None,
+                Details::default(),
+                std::iter::empty(),
+                None,
+            );
+        }
+        id
+    }
+
+    /// A message of a sequence diagram, numbered in order and placed in the
+    /// fragment operand being read
+    fn message(
+        &mut self,
+        from: Id,
+        to: Id,
+        message: &sequence::Message,
+        label: Option<&str>,
+    ) -> Id {
+        self.messages += 1;
+        let mut data: HashMap<Text, Value> =
+            HashMap::from([("sequence".into(), Value::Number(self.messages as f64))]);
+        if message.dashed {
+            data.insert("line_style".into(), Value::String("dashed".to_string()));
+        }
+        if message.asynchronous {
+            data.insert("async".into(), Value::Bool(true));
+        }
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id.clone(),
+                from,
+                to,
+                directed: true,
+                kind: EdgeKind::Flow,
+                label: label.map(Text::from),
+                data,
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+        if let Some(operand) = self.operand() {
+            operand.messages.push(edge_id.clone());
+        }
+        self.last_message = Some(edge_id.clone());
+        self.record(Change::Edge(edge_id.clone()));
+        edge_id
+    }
+
+    /// A fragment with one operand per condition, inside the operand being
+    /// read
+    fn fragment<'a>(
+        &mut self,
+        keyword: &str,
+        conditions: impl Iterator<Item = Option<&'a str>>,
+    ) -> Id {
+        let id: Id = Uuid::new_v4().to_string().into();
+        if let Some(operand) = self.operand() {
+            operand.fragments.push(id.clone());
+        }
+        let parent: Option<Id> = self
+            .operands
+            .last()
+            .map(|(fragment, _): &(Id, usize)| fragment.clone());
+
+        self.graph.fragments.insert(
+            id.clone(),
+            Fragment {
+                id: id.clone(),
+                kind: sequence::fragment_kind(keyword),
+                operands: conditions
+                    .map(|condition: Option<&str>| Operand {
+                        condition: condition.map(str::to_string),
+                        ..Default::default()
+                    })
+                    .collect(),
+                parent,
+            },
+        );
+        id
+    }
+
+    fn operand(&mut self) -> Option<&mut Operand> {
+        let (fragment, position): &(Id, usize) = self.operands.last()?;
+        self.graph
+            .fragments
+            .get_mut(fragment)?
+            .operands
+            .get_mut(*position)
+    }
+
+    /// Opens an activation after the latest message, or closes the latest
+    /// open one of the participant
+    fn activate(&mut self, participant: &str, active: bool) {
+        let id: Id = self.resolve_id(participant);
+        self.ensure_node_exists(&id);
+
+        if active {
+            self.active
+                .entry(id.clone())
+                .or_default()
+                .push(self.graph.activations.len());
+            self.graph.activations.push(Activation {
+                participant: id,
+                start: self.last_message.clone(),
+                end: None,
+            });
+            return;
+        }
+        match self.active.get_mut(&id).and_then(Vec::pop) {
+            Some(index) => self.graph.activations[index].end = self.last_message.clone(),
+            None => self.warn(format!("`{}` is not active", id), None),
+        }
+    }
+
+    /// `start` begins a new flow, `stop` ends the current one
+    fn terminal(&mut self, keyword: &str, parent_id: Option<Id>) {
+        if keyword == "start" {
+            self.tails.clear();
+        }
+        self.step(activity::step_kind(keyword), None, parent_id);
+        if keyword == "stop" {
+            self.tails.clear();
+        }
+    }
+
+    /// An activity step, numbered in order and following the pending steps
+    fn step(&mut self, kind: &str, label: Option<&str>, parent_id: Option<Id>) -> Id {
+        self.steps += 1;
+        let id: Id = Id::from(format!("{}_{}", kind, self.steps));
+        self.graph.nodes.insert(
+            id.clone(),
+            Node {
+                id: id.clone(),
+                kind: NodeKind::Custom(kind.to_string()),
+                label: label.map(Text::from),
+                parent: parent_id,
+                ..Default::default()
+            },
+        );
+        self.record(Change::Node(id.clone()));
+
+        std::mem::take(&mut self.tails)
+            .into_iter()
+            .for_each(|(from, label): (Id, Option<Text>)| self.flow(from, id.clone(), label));
+        self.tails = vec![(id.clone(), None)];
+        id
+    }
+
+    fn flow(&mut self, from: Id, to: Id, label: Option<Text>) {
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id.clone(),
+                from,
+                to,
+                directed: true,
+                kind: EdgeKind::Flow,
+                label,
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+        self.record(Change::Edge(edge_id));
+    }
+
+    /// The decision of an `if` or `while`, labelled with its first guard,
+    /// or the bar a `fork` splits at
+    fn open(&mut self, keyword: &str, guard: Option<&str>, parent_id: Option<Id>) -> Block {
+        Block {
+            keyword: keyword.to_string(),
+            node: self.step(activity::step_kind(keyword), guard, parent_id),
+            exits: Vec::new(),
+            otherwise: false,
+        }
+    }
+
+    /// Leads the flow into a branch of a block; an `elseif` is a further
+    /// decision taken when the previous one fails
+    fn branch(
+        &mut self,
+        block: &mut Block,
+        position: usize,
+        guard: Option<&str>,
+        label: Option<&str>,
+        parent_id: Option<Id>,
+    ) {
+        if position > 0 {
+            block.exits.append(&mut self.tails);
+        }
+        if block.keyword == "if" && position > 0 {
+            match guard {
+                Some(guard) => {
+                    self.tails = vec![(block.node.clone(), None)];
+                    block.node = self.step("decision", Some(guard), parent_id);
+                }
+                None => block.otherwise = true,
+            }
+        }
+        self.tails = vec![(block.node.clone(), label.map(Text::from))];
+    }
+
+    /// Where the flow goes after a block: back to a `while`'s decision and
+    /// out of it labelled `exit`, on from every branch of an `if`, or into
+    /// the bar a `fork` joins at
+    fn close(&mut self, mut block: Block, exit: Option<&str>, parent_id: Option<Id>) {
+        match block.keyword.as_str() {
+            "while" => {
+                std::mem::take(&mut self.tails).into_iter().for_each(
+                    |(from, label): (Id, Option<Text>)| self.flow(from, block.node.clone(), label),
+                );
+                self.tails = vec![(block.node, exit.map(Text::from))];
+            }
+            "fork" => {
+                block.exits.append(&mut self.tails);
+                self.tails = block.exits;
+                self.step("join", None, parent_id);
+            }
+            _ => {
+                block.exits.append(&mut self.tails);
+                if !block.otherwise {
+                    block.exits.push((block.node, None));
+                }
+                self.tails = block.exits;
+            }
+        }
+    }
+
+    /// A package, or the deployment element its keyword names
+    fn group(
+        &mut self,
+        group_id: Id,
+        keyword: &str,
+        name: &str,
+        children: Vec<Id>,
+        parent_id: Option<Id>,
+    ) {
+        self.graph.groups.insert(
+            group_id.clone(),
+            Group {
+                id: group_id.clone(),
+                kind: match keyword {
+                    "rectangle" => GroupKind::Boundary,
+                    keyword => DeploymentKind::from_keyword(keyword)
+                        .map_or(GroupKind::Package, GroupKind::Deployment),
+                },
+                label: Some(name.into()),
+                children,
+                parent: parent_id,
+                geometry: None,
+                hidden: self.hidden.contains(name),
+            },
+        );
+        self.record(Change::Group(group_id));
+    }
+
+    /// The title becomes the graph's, and a header, footer or caption a
+    /// property under its keyword
+    fn heading(&mut self, keyword: &str, text: &str) {
+        match keyword {
+            "title" => self.graph.metadata.title = Some(text.to_string()),
+            keyword => {
+                self.graph
+                    .metadata
+                    .properties
+                    .insert(keyword.to_string(), text.to_string());
+            }
+        }
+    }
+
+    fn ignore(&mut self, directive: &str, line: usize) {
+        let first: &str = directive
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('{')
+            .trim_end();
+        // Macros of the Archimate library are understood without it
+        if first.starts_with("!include <archimate/") {
+            return;
+        }
+        if let Some((element, settings)) = skinparam::declarations(directive) {
+            self.style(element, settings);
+            return;
+        }
+        if let Some(theme) = theme::settings(first) {
+            theme
+                .iter()
+                .for_each(|(element, settings): &(&str, &[(&str, &str)])| {
+                    self.style(element, settings.to_vec());
+                });
+            return;
+        }
+        self.warn(format!("`{}` is not supported", first), Some(line));
+    }
+
+    /// Adds settings to the style of `element`, the later of two settings of
+    /// the same key winning
+    fn style(&mut self, element: &str, settings: Vec<(&str, &str)>) {
+        let style: &mut Style = self
+            .graph
+            .styles
+            .entry(Id::from(element))
+            .or_insert_with(|| Style {
+                id: Id::from(element),
+                properties: HashMap::new(),
+            });
+        style.properties.extend(
+            settings
+                .into_iter()
+                .map(|(key, value): (&str, &str)| (key.to_string(), value.to_string())),
+        );
+    }
+
+    /// A `hide` or `show` command: parts it names are hidden, or shown again,
+    /// in the elements it applies to, and an element it names is hidden.
+    /// Renderings already leave empty compartments out, so `hide empty
+    /// members` has nothing left to do.
+    fn visibility(
+        &mut self,
+        show: bool,
+        target: Option<&str>,
+        part: Option<&str>,
+        empty: bool,
+        line: usize,
+    ) {
+        if empty {
+            return;
+        }
+        let command: Option<Command> = match (target, part) {
+            (None, Some(part)) => Some(Command {
+                show,
+                target: Target::All,
+                parts: Part::named(part),
+            }),
+            (Some(target), part) => Target::kind(target)
+                .or_else(|| part.map(|_| Target::Node(self.resolve_id(target.trim_matches('"')))))
+                .map(|target: Target| Command {
+                    show,
+                    target,
+                    parts: Part::named(part.unwrap_or_default()),
+                }),
+            (None, None) => None,
+        };
+        match (command, target.and_then(visibility::hidden_target)) {
+            (Some(command), _) => {
+                self.commands.push(command);
+                let ids: Vec<Id> = self.graph.nodes.keys().cloned().collect();
+                ids.iter().for_each(|id: &Id| {
+                    if self.apply_commands(id) {
+                        self.record(Change::Node(id.clone()));
+                    }
+                });
+            }
+            (None, Some(target)) => self.hide(target, !show),
+            (None, None) => {
+                let keyword: &str = if show { "show" } else { "hide" };
+                let text: &str = target.or(part).unwrap_or_default();
+                self.warn(
+                    format!("`{} {}` is not supported", keyword, text),
+                    Some(line),
+                );
+            }
+        }
+    }
+
+    /// Brings the node `id` in line with the `hide` and `show` commands of
+    /// parts and kinds, returning whether it changed
+    fn apply_commands(&mut self, id: &Id) -> bool {
+        let Some(node) = self.graph.nodes.get_mut(id) else {
+            return false;
+        };
+        let before: Node = node.clone();
+        let declared: &Vec<Member> = self
+            .declared
+            .entry(id.clone())
+            .or_insert_with(|| node.members.clone());
+        node.members = visibility::members(&self.commands, node, declared);
+        if let Some(hidden) = visibility::hidden(&self.commands, node) {
+            node.hidden = hidden;
+        }
+        [
+            ("hide_circle", Part::Circle),
+            ("hide_stereotype", Part::Stereotype),
+        ]
+        .into_iter()
+        .for_each(|(key, part): (&str, Part)| {
+            match visibility::shown(&self.commands, node, part) {
+                true => node.data.remove(key),
+                false => node.data.insert(key.into(), Value::Bool(true)),
+            };
+        });
+        *node != before
+    }
+
+    /// Hides the element named or aliased `target`, or the packages labelled
+    /// so, and any declared after; `show` shows them again
+    fn hide(&mut self, target: &str, hidden: bool) {
+        match hidden {
+            true => self.hidden.insert(target.to_string()),
+            false => self.hidden.remove(target),
+        };
+
+        let id: Id = self.resolve_id(target);
+        if let Some(node) = self.graph.nodes.get_mut(&id) {
+            node.hidden = hidden;
+            self.record(Change::Node(id));
+        }
+        let groups: Vec<Id> = self
+            .graph
+            .groups
+            .values_mut()
+            .filter(|group: &&mut Group| group.label.as_deref() == Some(target))
+            .map(|group: &mut Group| {
+                group.hidden = hidden;
+                group.id.clone()
+            })
+            .collect();
+        groups
+            .into_iter()
+            .for_each(|group: Id| self.record(Change::Group(group)));
+    }
+
+    /// A note on the relation read last, drawn on the side `position` names
+    fn link_note(
+        &mut self,
+        position: Option<&str>,
+        text: &str,
+        line: usize,
+        parent_id: Option<Id>,
+    ) {
+        let Some(edge) = self.last_link.clone() else {
+            self.warn("`note on link` follows no relation".to_string(), Some(line));
+            return;
+        };
+        self.notes += 1;
+        let id: Id = Id::from(format!("note_{}", self.notes));
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        if let Some(position) = position {
+            data.insert("position".into(), Value::String(position.to_string()));
+        }
+        self.adopt(parent_id.as_ref(), &id);
+        self.graph.nodes.insert(
+            id.clone(),
+            Node {
+                id: id.clone(),
+                kind: NodeKind::Annotation,
+                label: Some(text.into()),
+                data,
+                parent: parent_id,
+                target_edge: Some(edge),
+                ..Default::default()
+            },
+        );
+        self.record(Change::Node(id));
+    }
+
+    fn warn(&mut self, message: String, line: Option<usize>) {
+        self.graph.warnings.push(Warning {
+            source: "plantuml".to_string(),
+            message,
+            line,
+        });
+    }
+
+    fn resolve_id(&self, identifier: &str) -> Id {
         self.alias_map
             .get(identifier)
             .cloned()
-            .unwrap_or_else(|| identifier.to_string())
+            .unwrap_or_else(|| definition_id(None, identifier, None))
     }
 
     fn ensure_node_exists(&mut self, id: &str) {
-        if !self.graph.nodes.contains_key(id) {
+        // Composite states are groups, which transitions may reference
+        if !self.graph.nodes.contains_key(id) && !self.graph.groups.contains_key(id) {
+            // Sequence participants and states are commonly declared by
+            // their messages and transitions
+            let kind: NodeKind = match self.graph.kind {
+                GraphKind::Sequence => NodeKind::Component,
+                GraphKind::State => NodeKind::Custom("state".to_string()),
+                _ => {
+                    self.warn(format!("`{}` is not declared", id), None);
+                    NodeKind::Entity // Default kind for implicit nodes
+                }
+            };
+            let parent: Option<Id> = match self.graph.kind {
+                GraphKind::State => self.scope.clone(),
+                _ => None,
+            };
+            self.adopt(parent.as_ref(), &id.into());
             self.graph.nodes.insert(
-                id.to_string(),
+                id.into(),
                 Node {
-                    id: id.to_string(),
-                    kind: NodeKind::Entity, // Default kind for implicit nodes
-                    label: Some(id.to_string()),
+                    id: id.into(),
+                    kind,
+                    label: Some(id.into()),
+                    members: Vec::new(),
                     data: HashMap::new(),
                     style: None,
-                    parent: None,
+                    colors: Colors::default(),
+                    parent,
+                    geometry: None,
+                    label_anchor: None,
+                    hidden: false,
+                    rich_label: None,
+                    target_edge: None,
                 },
             );
+            self.record(Change::Node(id.into()));
         }
     }
 
@@ -154,10 +1484,147 @@ impl GraphBuilder {
         match arrow {
             "-->" | "<--" => (EdgeKind::Association, true),
             "--|>" | "<|--" => (EdgeKind::Inheritance, true),
+            "..|>" | "<|.." => (EdgeKind::Realization, true),
+            "..>" | "<.." => (EdgeKind::Dependency, true),
             "--*" | "*--" => (EdgeKind::Composition, true),
             "--o" | "o--" => (EdgeKind::Aggregation, true),
             "--" => (EdgeKind::Undirected, false),
+            ".." => (EdgeKind::Dependency, false),
             _ => (EdgeKind::Custom(arrow.to_string()), true),
         }
     }
 }
+
+/// The id a definition gives its element: its alias, or its name qualified
+/// by the class it is nested in, `Outer::Inner` being `Outer.Inner`
+fn definition_id(outer: Option<&Id>, name: &str, alias: Option<&str>) -> Id {
+    match (alias, outer) {
+        (Some(alias), _) => Id::from(alias),
+        (None, Some(outer)) => Id::from(format!("{}.{}", outer, name.replace("::", "."))),
+        (None, None) if name.contains("::") => Id::from(name.replace("::", ".")),
+        (None, None) => Id::from(name),
+    }
+}
+
+/// Ids of the elements and states declared directly in a package, in a
+/// `together` block of it or in a class body, for the package's child list
+fn declared_ids(children: &[AstNode], outer: Option<&Id>, ids: &mut Vec<Id>) {
+    children.iter().for_each(|child: &AstNode| match child {
+        AstNode::Definition {
+            alias,
+            name,
+            children,
+            ..
+        } => {
+            let id: Id = definition_id(outer, name, alias.as_deref());
+            if !ids.contains(&id) {
+                ids.push(id.clone());
+            }
+            declared_ids(children, Some(&id), ids);
+        }
+        AstNode::State { alias, name, .. } => {
+            let id: Id = Id::from(alias.as_deref().unwrap_or(name));
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        AstNode::Together { children } => declared_ids(children, outer, ids),
+        _ => {}
+    });
+}
+
+/// [`declared_ids`] of the package or class at `index` of an arena
+fn declared_arena_ids(arena: &AstArena, index: usize, outer: Option<&Id>, ids: &mut Vec<Id>) {
+    arena
+        .children(index)
+        .for_each(|child: usize| match arena.get(child) {
+            ArenaNode::Definition { alias, name, .. } => {
+                let id: Id = definition_id(outer, name, *alias);
+                if !ids.contains(&id) {
+                    ids.push(id.clone());
+                }
+                declared_arena_ids(arena, child, Some(&id), ids);
+            }
+            ArenaNode::State { alias, name, .. } => {
+                let id: Id = Id::from(alias.unwrap_or(name));
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+            ArenaNode::Together { .. } => declared_arena_ids(arena, child, outer, ids),
+            _ => {}
+        });
+}
+
+/// Each type parameter of `K, Map<K, V>`, commas inside brackets staying in
+/// theirs
+fn type_parameters(generics: &str) -> Vec<Value> {
+    let mut parameters: Vec<Value> = Vec::new();
+    let (mut depth, mut start): (usize, usize) = (0, 0);
+    generics
+        .char_indices()
+        .for_each(|(index, c): (usize, char)| match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parameters.push(Value::String(generics[start..index].trim().to_string()));
+                start = index + 1;
+            }
+            _ => {}
+        });
+    parameters.push(Value::String(generics[start..].trim().to_string()));
+    parameters
+}
+
+/// The graph's view of a member: modifiers and visibility become data,
+/// `{classifier}` being `static`, methods list their parameters and
+/// separators say how their line is drawn
+fn member(member: &AstMember) -> Member {
+    let mut data: HashMap<Text, Value> = member
+        .modifiers
+        .iter()
+        .filter(|modifier: &&Cow<str>| !matches!(modifier.as_ref(), "field" | "method"))
+        .map(|modifier: &Cow<str>| match modifier.as_ref() {
+            "classifier" => ("static".into(), Value::Bool(true)),
+            modifier => (Text::from(modifier), Value::Bool(true)),
+        })
+        .collect();
+
+    if let Some(symbol) = member.visibility {
+        data.insert("visibility".into(), Value::String(symbol.to_string()));
+    }
+    if let Some(value) = &member.value {
+        data.insert("value".into(), Value::String(value.to_string()));
+    }
+    let line: Option<&str> = match member
+        .separator
+        .as_deref()
+        .and_then(|bar: &str| bar.chars().next())
+    {
+        Some('.') => Some("dotted"),
+        Some('=') => Some("double"),
+        Some('_') => Some("thick"),
+        _ => None,
+    };
+    if let Some(line) = line {
+        data.insert("line".into(), Value::String(line.to_string()));
+    }
+    if let Some(parameters) = &member.parameters {
+        data.insert(
+            "parameters".into(),
+            Value::List(
+                parameters
+                    .iter()
+                    .map(|p: &Cow<str>| Value::String(p.to_string()))
+                    .collect(),
+            ),
+        );
+    }
+
+    Member {
+        name: member.name.to_string(),
+        kind: member.kind(),
+        type_name: member.type_name.as_deref().map(str::to_string),
+        data,
+    }
+}
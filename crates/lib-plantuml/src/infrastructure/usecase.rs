@@ -0,0 +1,60 @@
+//! The vocabulary of use case diagrams: use cases named in parentheses such
+//! as `(Log in)`, actors named between colons such as `:Customer:`, and the
+//! `usecase` keyword. A diagram using any of them is read as a use case
+//! diagram, where `->` links an actor to a use case like `-->` does.
+
+use crate::infrastructure::models::{
+    ast_arena::{ArenaNode, AstArena},
+    ast_node::AstNode,
+};
+
+/// A name without the quotes, parentheses or colons around it
+pub(crate) fn name(text: &str) -> &str {
+    [('"', '"'), ('(', ')'), (':', ':')]
+        .into_iter()
+        .find_map(|(open, close): (char, char)| {
+            text.strip_prefix(open)
+                .and_then(|rest: &str| rest.strip_suffix(close))
+        })
+        .map_or(text, str::trim)
+}
+
+/// The keyword and name of a use case or actor a relation end spells out,
+/// such as `(Log in)`
+pub(crate) fn endpoint(text: &str) -> Option<(&'static str, &str)> {
+    match text.chars().next()? {
+        '(' if text.len() > 2 => Some(("usecase", name(text))),
+        ':' if text.len() > 2 => Some(("actor", name(text))),
+        _ => None,
+    }
+}
+
+/// What an arrow between an actor and a use case means
+pub(crate) fn arrow(arrow: &str) -> &str {
+    match arrow {
+        "->" => "-->",
+        "<-" => "<--",
+        arrow => arrow,
+    }
+}
+
+/// Whether a statement only belongs in a use case diagram
+pub(crate) fn is_use_case(node: &AstNode) -> bool {
+    match node {
+        AstNode::Definition { keyword, .. } => keyword == "usecase",
+        AstNode::Relation { left, right, .. } => left.starts_with('(') || right.starts_with('('),
+        AstNode::Package { children, .. } | AstNode::Together { children } => {
+            children.iter().any(is_use_case)
+        }
+        _ => false,
+    }
+}
+
+/// Whether any statement of an arena only belongs in a use case diagram
+pub(crate) fn is_use_case_arena(arena: &AstArena) -> bool {
+    (0..arena.len()).any(|index: usize| match arena.get(index) {
+        ArenaNode::Definition { keyword, .. } => *keyword == "usecase",
+        ArenaNode::Relation { left, right, .. } => left.starts_with('(') || right.starts_with('('),
+        _ => false,
+    })
+}
@@ -0,0 +1,255 @@
+//! `hide` and `show` commands, which hide parts of elements or show them
+//! again (`hide members`, `show Foo methods`, `hide <<internal>> circle`), or
+//! hide whole elements (`hide Foo`, `hide interface`).
+
+use lib_core::entities::{
+    id::Id,
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+};
+
+/// Words naming the part of an element a command applies to
+const PARTS: [&str; 7] = [
+    "members",
+    "fields",
+    "attributes",
+    "methods",
+    "circle",
+    "stereotype",
+    "stereotypes",
+];
+
+/// A command as written: `hide`'s or `show`'s target, if any, the part it
+/// names, if any, and whether `empty` comes before that part
+pub(crate) fn words(command: &str) -> Option<(bool, Option<&str>, Option<&str>, bool)> {
+    let (show, rest): (bool, &str) = match command.split_once(char::is_whitespace) {
+        Some(("hide", rest)) => (false, rest.trim()),
+        Some(("show", rest)) => (true, rest.trim()),
+        _ => return None,
+    };
+    let (rest, part): (&str, Option<&str>) = match rest.rsplit_once(char::is_whitespace) {
+        Some((rest, part)) if PARTS.contains(&part) => (rest.trim_end(), Some(part)),
+        _ if PARTS.contains(&rest) => ("", Some(rest)),
+        _ => (rest, None),
+    };
+    let (rest, empty): (&str, bool) = match rest.strip_suffix("empty") {
+        Some(before) if part.is_some() && (before.is_empty() || before.ends_with(' ')) => {
+            (before.trim_end(), true)
+        }
+        _ => (rest, false),
+    };
+    let target: Option<&str> = Some(rest).filter(|target: &&str| !target.is_empty());
+
+    Some((show, target, part, empty))
+}
+
+/// The element a `hide` directive names, if it names one rather than parts
+/// of elements (`hide empty members`), kinds (`hide interface`) or tagged
+/// sets (`hide <<internal>>`, `hide @unlinked`)
+pub(crate) fn hidden_target(rest: &str) -> Option<&str> {
+    let rest: &str = rest.trim();
+    let quoted: Option<&str> = rest
+        .strip_prefix('"')
+        .and_then(|quoted: &str| quoted.strip_suffix('"'));
+    if quoted.is_some() {
+        return quoted.filter(|target: &&str| !target.is_empty());
+    }
+    let target: &str = rest;
+    let modifier: bool = matches!(
+        target,
+        "members"
+            | "methods"
+            | "fields"
+            | "attributes"
+            | "circle"
+            | "stereotype"
+            | "footbox"
+            | "class"
+            | "interface"
+            | "enum"
+            | "abstract"
+            | "annotation"
+            | "protocol"
+            | "struct"
+            | "exception"
+            | "metaclass"
+            | "package"
+            | "namespace"
+    );
+    let valid: bool = !target.is_empty()
+        && !target.contains(char::is_whitespace)
+        && !target.starts_with(['<', '@', '$']);
+    (valid && !modifier).then_some(target)
+}
+
+/// What a command applies to
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Target {
+    All,
+    Kind(NodeKind),
+    Stereotype(String),
+    Node(Id),
+}
+
+impl Target {
+    /// `class`, `interface`, `enum` and the other class-like keywords name
+    /// every element of that kind, and `<<name>>` every element with that
+    /// stereotype
+    pub(crate) fn kind(target: &str) -> Option<Target> {
+        let kind: NodeKind = match target {
+            "class" | "entity" => NodeKind::Entity,
+            "interface" => NodeKind::Interface,
+            "enum" => NodeKind::Enum,
+            "annotation" | "protocol" | "struct" | "exception" | "metaclass" => {
+                NodeKind::Custom(target.to_string())
+            }
+            _ => {
+                return target
+                    .strip_prefix("<<")
+                    .and_then(|name: &str| name.strip_suffix(">>"))
+                    .map(|name: &str| Target::Stereotype(name.trim().to_string()));
+            }
+        };
+        Some(Target::Kind(kind))
+    }
+
+    fn covers(&self, node: &Node) -> bool {
+        match self {
+            Target::All => true,
+            Target::Kind(kind) => node.kind == *kind,
+            Target::Stereotype(name) => {
+                node.data.get("stereotype") == Some(&Value::String(name.clone()))
+            }
+            Target::Node(id) => node.id == *id,
+        }
+    }
+}
+
+/// A part of an element that can be hidden, or the whole of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Part {
+    Whole,
+    Fields,
+    Methods,
+    Circle,
+    Stereotype,
+}
+
+impl Part {
+    /// The parts a word names, `members` being both fields and methods;
+    /// without one, a command applies to whole elements
+    pub(crate) fn named(word: &str) -> &'static [Part] {
+        match word {
+            "members" => &[Part::Fields, Part::Methods],
+            "fields" | "attributes" => &[Part::Fields],
+            "methods" => &[Part::Methods],
+            "circle" => &[Part::Circle],
+            "stereotype" | "stereotypes" => &[Part::Stereotype],
+            _ => &[Part::Whole],
+        }
+    }
+}
+
+/// A command hiding, or showing again, parts of the elements it applies to
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Command {
+    pub(crate) show: bool,
+    pub(crate) target: Target,
+    pub(crate) parts: &'static [Part],
+}
+
+/// Whether the commands, the later winning, leave `part` of `node` shown
+pub(crate) fn shown(commands: &[Command], node: &Node, part: Part) -> bool {
+    commands
+        .iter()
+        .rfind(|command: &&Command| command.parts.contains(&part) && command.target.covers(node))
+        .is_none_or(|command: &Command| command.show)
+}
+
+/// Whether the last command of whole elements applying to `node` hides it,
+/// if any applies
+pub(crate) fn hidden(commands: &[Command], node: &Node) -> Option<bool> {
+    commands
+        .iter()
+        .rfind(|command: &&Command| {
+            command.parts.contains(&Part::Whole) && command.target.covers(node)
+        })
+        .map(|command: &Command| !command.show)
+}
+
+/// The members of `node`, out of all it declares, that the commands leave
+/// shown
+pub(crate) fn members(commands: &[Command], node: &Node, declared: &[Member]) -> Vec<Member> {
+    let (fields, methods): (bool, bool) = (
+        shown(commands, node, Part::Fields),
+        shown(commands, node, Part::Methods),
+    );
+    declared
+        .iter()
+        .filter(|member: &&Member| match member.kind {
+            MemberKind::Field => fields,
+            MemberKind::Method => methods,
+            MemberKind::EnumValue => true,
+            MemberKind::Separator => fields || methods,
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_splits_commands_into_their_words() {
+        assert_eq!(
+            words("hide empty members"),
+            Some((false, None, Some("members"), true))
+        );
+        assert_eq!(
+            words("show Foo methods"),
+            Some((true, Some("Foo"), Some("methods"), false))
+        );
+        assert_eq!(
+            words("hide <<internal>> circle"),
+            Some((false, Some("<<internal>>"), Some("circle"), false))
+        );
+        assert_eq!(
+            words("hide \"My Class\""),
+            Some((false, Some("\"My Class\""), None, false))
+        );
+        assert_eq!(
+            words("hide Empty"),
+            Some((false, Some("Empty"), None, false))
+        );
+        assert_eq!(words("skinparam shadowing false"), None);
+    }
+
+    #[test]
+    fn test_later_commands_win() {
+        let node: Node = Node {
+            id: Id::from("Foo"),
+            kind: NodeKind::Interface,
+            ..Default::default()
+        };
+        let commands: Vec<Command> = vec![
+            Command {
+                show: false,
+                target: Target::All,
+                parts: Part::named("members"),
+            },
+            Command {
+                show: true,
+                target: Target::Kind(NodeKind::Interface),
+                parts: Part::named("methods"),
+            },
+        ];
+
+        assert!(!shown(&commands, &node, Part::Fields));
+        assert!(shown(&commands, &node, Part::Methods));
+        assert!(shown(&commands, &node, Part::Circle));
+    }
+}
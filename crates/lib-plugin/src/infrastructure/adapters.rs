@@ -0,0 +1,2 @@
+pub mod wasm_graph_emitter;
+pub mod wasm_graph_gateway;
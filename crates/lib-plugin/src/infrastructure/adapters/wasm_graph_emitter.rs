@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_emitter_adapter::{GraphEmitterAdapter, GraphEmitterError},
+    entities::graph::Graph,
+};
+use lib_json::infrastructure::emitter;
+use serde_json::Value as Json;
+
+use crate::infrastructure::host::{PluginError, WasmPlugin};
+
+/// Writes diagrams with a plugin's `diagrama_write`
+pub struct WasmGraphEmitter {
+    plugin: Arc<WasmPlugin>,
+}
+
+impl WasmGraphEmitter {
+    pub fn new(plugin: Arc<WasmPlugin>) -> Self {
+        Self { plugin }
+    }
+}
+
+#[async_trait]
+impl GraphEmitterAdapter for WasmGraphEmitter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError> {
+        let unsupported = |message: String| -> GraphEmitterError {
+            GraphEmitterError::Unsupported {
+                target: self.plugin.manifest().name.clone(),
+                message,
+            }
+        };
+
+        let result: Json = self
+            .plugin
+            .call(
+                "diagrama_write",
+                Some(&emitter::graph_to_json(graph).to_string()),
+            )
+            .map_err(|e: PluginError| unsupported(e.to_string()))?;
+        match (result["text"].as_str(), result["error"]["message"].as_str()) {
+            (Some(text), _) => Ok(text.to_string()),
+            (None, Some(message)) => Err(unsupported(message.to_string())),
+            (None, None) => Err(unsupported("The plugin returned no text".to_string())),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+use lib_json::infrastructure::reader;
+use serde_json::Value as Json;
+
+use crate::infrastructure::host::{PluginError, WasmPlugin};
+
+/// Reads diagrams with a plugin's `diagrama_read`
+pub struct WasmGraphGateway {
+    plugin: Arc<WasmPlugin>,
+}
+
+impl WasmGraphGateway {
+    pub fn new(plugin: Arc<WasmPlugin>) -> Self {
+        Self { plugin }
+    }
+}
+
+#[async_trait]
+impl GraphGateway for WasmGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        let source: &str = &self.plugin.manifest().name;
+        let semantic = |message: String| -> GraphGatewayError {
+            GraphGatewayError::Semantic {
+                source: source.to_string(),
+                message,
+            }
+        };
+
+        let result: Json = self
+            .plugin
+            .call("diagrama_read", Some(input))
+            .map_err(|e: PluginError| semantic(e.to_string()))?;
+        let error: &Json = &result["error"];
+        if !error.is_null() {
+            let message: String = error["message"]
+                .as_str()
+                .unwrap_or("Unknown plugin error")
+                .to_string();
+            return Err(match error["line"].as_u64() {
+                Some(line) => GraphGatewayError::Parse {
+                    source: source.to_string(),
+                    message,
+                    line: line as usize,
+                    column: error["column"].as_u64().unwrap_or(1) as usize,
+                },
+                None => semantic(message),
+            });
+        }
+
+        reader::graph_from_json(&result["graph"]).map_err(semantic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lib_core::adapters::graph_gateway::{GraphGateway, GraphGatewayError};
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::{
+        adapters::wasm_graph_gateway::WasmGraphGateway,
+        host::{WasmPlugin, tests::demo},
+    };
+
+    #[test]
+    fn test_reports_plugin_errors_and_runaway_plugins() {
+        let plugin: WasmPlugin = WasmPlugin::from_bytes(&demo("demo", "")).unwrap();
+        let looping: WasmPlugin = WasmPlugin::from_bytes(&demo("demo", "(loop (br 0))"))
+            .unwrap()
+            .with_fuel(100_000);
+
+        assert_eq!(
+            smol::block_on(WasmGraphGateway::new(Arc::new(plugin)).read_graph_from_raw_input("")),
+            Err(GraphGatewayError::Parse {
+                source: "demo".into(),
+                message: "empty diagram".into(),
+                line: 1,
+                column: 1,
+            })
+        );
+        assert!(matches!(
+            smol::block_on(WasmGraphGateway::new(Arc::new(looping)).read_graph_from_raw_input("x")),
+            Err(GraphGatewayError::Semantic { message, .. }) if message.starts_with("Plugin failed")
+        ));
+    }
+}
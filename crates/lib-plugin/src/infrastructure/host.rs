@@ -0,0 +1,320 @@
+//! Runs format plugins compiled to WebAssembly. A plugin is a core module
+//! without imports that exports:
+//!
+//! - `memory`
+//! - `diagrama_alloc(len: i32) -> i32`, reserving `len` bytes for the host
+//! - `diagrama_manifest() -> i64`, describing the plugin as JSON:
+//!   `{"name": "foo", "extensions": ["foo"], "reads": true, "writes": false}`
+//! - `diagrama_read(ptr: i32, len: i32) -> i64` when it reads, turning the
+//!   UTF-8 source into `{"graph": ...}` or
+//!   `{"error": {"message": "...", "line": 3, "column": 1}}`
+//! - `diagrama_write(ptr: i32, len: i32) -> i64` when it writes, turning a
+//!   graph into `{"text": "..."}` or `{"error": {"message": "..."}}`
+//!
+//! Graphs use the JSON output format's shape. Results are UTF-8 JSON in the
+//! plugin's memory, returned as `ptr << 32 | len`. Every call gets a fresh
+//! instance with bounded memory and fuel, so a plugin keeps no state between
+//! calls and cannot hang or exhaust the host.
+
+use std::{fmt, fs, path::Path, sync::Arc};
+
+use lib_convert::registry::{self, FormatPlugin};
+use serde_json::Value as Json;
+use wasmtime::{
+    Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+use crate::infrastructure::adapters::{
+    wasm_graph_emitter::WasmGraphEmitter, wasm_graph_gateway::WasmGraphGateway,
+};
+
+/// Instructions a single call may run, roughly
+const FUEL: u64 = 5_000_000_000;
+const MAX_MEMORY: usize = 256 * 1024 * 1024;
+
+pub struct WasmPlugin {
+    engine: Engine,
+    module: Module,
+    manifest: Manifest,
+    fuel: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub name: String,
+    pub extensions: Vec<String>,
+    pub reads: bool,
+    pub writes: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginError {
+    /// The plugin file could not be read
+    Io(String),
+    /// The module is not a valid plugin
+    Invalid(String),
+    /// The plugin failed while running, ran out of fuel or returned garbage
+    Failed(String),
+}
+
+impl WasmPlugin {
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let bytes: Vec<u8> = fs::read(path)
+            .map_err(|e: std::io::Error| PluginError::Io(format!("{}: {}", path.display(), e)))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Compiles a module and asks it for its manifest
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PluginError> {
+        let mut config: Config = Config::new();
+        config.consume_fuel(true);
+        let engine: Engine = Engine::new(&config).map_err(invalid)?;
+        let module: Module = Module::new(&engine, bytes).map_err(invalid)?;
+
+        let mut plugin: WasmPlugin = Self {
+            engine,
+            module,
+            manifest: Manifest {
+                name: String::new(),
+                extensions: Vec::new(),
+                reads: false,
+                writes: false,
+            },
+            fuel: FUEL,
+        };
+        let manifest: Json = plugin.call("diagrama_manifest", None)?;
+        plugin.manifest = Manifest {
+            name: manifest["name"]
+                .as_str()
+                .filter(|name: &&str| !name.is_empty())
+                .ok_or_else(|| PluginError::Invalid("The manifest has no name".into()))?
+                .to_string(),
+            extensions: manifest["extensions"]
+                .as_array()
+                .map(|extensions: &Vec<Json>| {
+                    extensions
+                        .iter()
+                        .filter_map(|extension: &Json| extension.as_str())
+                        .map(|extension: &str| extension.trim_start_matches('.').to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            reads: manifest["reads"].as_bool().unwrap_or_default(),
+            writes: manifest["writes"].as_bool().unwrap_or_default(),
+        };
+        Ok(plugin)
+    }
+
+    /// Limits how much work a single call may do
+    pub fn with_fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Adds the plugin's notation to the format registry
+    pub fn register(self) -> Result<(), String> {
+        let plugin: Arc<WasmPlugin> = Arc::new(self);
+        let manifest: &Manifest = plugin.manifest();
+
+        registry::register(FormatPlugin {
+            name: manifest.name.clone(),
+            extensions: manifest.extensions.clone(),
+            gateway: manifest
+                .reads
+                .then(|| Arc::new(WasmGraphGateway::new(plugin.clone())) as Arc<_>),
+            emitter: manifest
+                .writes
+                .then(|| Arc::new(WasmGraphEmitter::new(plugin.clone())) as Arc<_>),
+        })
+    }
+
+    /// Runs an export on a fresh instance, passing `input` when given, and
+    /// parses the JSON it returns
+    pub(crate) fn call(&self, export: &str, input: Option<&str>) -> Result<Json, PluginError> {
+        let mut store: Store<StoreLimits> = Store::new(
+            &self.engine,
+            StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build(),
+        );
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(self.fuel).map_err(failed)?;
+
+        let instance: Instance = Instance::new(&mut store, &self.module, &[]).map_err(invalid)?;
+        let memory: Memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| PluginError::Invalid("The module exports no memory".into()))?;
+
+        let packed: i64 = match input {
+            Some(input) => {
+                let alloc: TypedFunc<i32, i32> = instance
+                    .get_typed_func(&mut store, "diagrama_alloc")
+                    .map_err(invalid)?;
+                let len: i32 = i32::try_from(input.len())
+                    .map_err(|_| PluginError::Failed("The input is too large".into()))?;
+                let ptr: i32 = alloc.call(&mut store, len).map_err(failed)?;
+                memory
+                    .write(&mut store, ptr as u32 as usize, input.as_bytes())
+                    .map_err(failed)?;
+                let run: TypedFunc<(i32, i32), i64> = instance
+                    .get_typed_func(&mut store, export)
+                    .map_err(invalid)?;
+                run.call(&mut store, (ptr, len)).map_err(failed)?
+            }
+            None => {
+                let run: TypedFunc<(), i64> = instance
+                    .get_typed_func(&mut store, export)
+                    .map_err(invalid)?;
+                run.call(&mut store, ()).map_err(failed)?
+            }
+        };
+
+        let (ptr, len): (usize, usize) = ((packed as u64 >> 32) as usize, packed as u32 as usize);
+        if ptr.saturating_add(len) > memory.data_size(&store) {
+            return Err(PluginError::Failed(format!(
+                "`{}` returned a result outside its memory",
+                export
+            )));
+        }
+        let mut output: Vec<u8> = vec![0; len];
+        memory.read(&store, ptr, &mut output).map_err(failed)?;
+        serde_json::from_slice(&output).map_err(|e: serde_json::Error| {
+            PluginError::Failed(format!("`{}` returned invalid JSON: {}", export, e))
+        })
+    }
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(message) => write!(f, "Cannot read plugin {}", message),
+            Self::Invalid(message) => write!(f, "Invalid plugin: {}", message),
+            Self::Failed(message) => write!(f, "Plugin failed: {}", message),
+        }
+    }
+}
+
+fn invalid(e: impl fmt::Display) -> PluginError {
+    PluginError::Invalid(e.to_string())
+}
+
+fn failed(e: impl fmt::Display) -> PluginError {
+    PluginError::Failed(e.to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use lib_convert::{
+        formats::{InputFormat, OutputFormat},
+        pipeline,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// A plugin answering every call with a fixed document; reading an empty
+    /// source fails
+    pub(crate) fn demo(name: &str, body: &str) -> Vec<u8> {
+        let manifest: String = format!(
+            r#"{{"name":"{}","extensions":["{}"],"reads":true,"writes":true}}"#,
+            name, name
+        );
+        let segments: [(usize, &str); 4] = [
+            (0, &manifest),
+            (512, r#"{"graph":{"nodes":[{"id":"A","label":"Alpha"}]}}"#),
+            (
+                1024,
+                r#"{"error":{"message":"empty diagram","line":1,"column":1}}"#,
+            ),
+            (1536, r#"{"text":"drawn\n"}"#),
+        ];
+        let data: String = segments
+            .iter()
+            .map(|(offset, text): &(usize, &str)| {
+                format!(
+                    "(data (i32.const {}) \"{}\")",
+                    offset,
+                    text.replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        let len = |index: usize| -> usize { segments[index].1.len() };
+
+        wat::parse_str(format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (global $next (mut i32) (i32.const 4096))
+                {data}
+                (func $pack (param $ptr i32) (param $len i32) (result i64)
+                    (i64.or
+                        (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                        (i64.extend_i32_u (local.get $len))))
+                (func (export "diagrama_alloc") (param $len i32) (result i32)
+                    (global.get $next)
+                    (global.set $next (i32.add (global.get $next) (local.get $len))))
+                (func (export "diagrama_manifest") (result i64)
+                    (call $pack (i32.const 0) (i32.const {manifest})))
+                (func (export "diagrama_read") (param $ptr i32) (param $len i32) (result i64)
+                    {body}
+                    (if (result i64) (local.get $len)
+                        (then (call $pack (i32.const 512) (i32.const {graph})))
+                        (else (call $pack (i32.const 1024) (i32.const {error})))))
+                (func (export "diagrama_write") (param $ptr i32) (param $len i32) (result i64)
+                    (call $pack (i32.const 1536) (i32.const {text}))))"#,
+            manifest = len(0),
+            graph = len(1),
+            error = len(2),
+            text = len(3),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reads_the_manifest() {
+        let plugin: WasmPlugin = WasmPlugin::from_bytes(&demo("demo", "")).unwrap();
+
+        assert_eq!(
+            plugin.manifest(),
+            &Manifest {
+                name: "demo".to_string(),
+                extensions: vec!["demo".to_string()],
+                reads: true,
+                writes: true,
+            }
+        );
+        assert!(matches!(
+            WasmPlugin::from_bytes(b"\0asm"),
+            Err(PluginError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_registered_plugins_convert_like_built_in_formats() {
+        WasmPlugin::from_bytes(&demo("shapes", ""))
+            .unwrap()
+            .register()
+            .unwrap();
+
+        let from: InputFormat = "shapes".parse().unwrap();
+        let to: OutputFormat = "shapes".parse().unwrap();
+        assert_eq!(
+            InputFormat::from_path(Path::new("model.shapes")),
+            Some(from)
+        );
+        assert_eq!(
+            smol::block_on(pipeline::convert("anything", from, OutputFormat::Mermaid)),
+            Ok("flowchart TB\n    A[\"Alpha\"]\n".to_string())
+        );
+        assert_eq!(
+            smol::block_on(pipeline::convert(
+                "@startuml\n@enduml\n",
+                InputFormat::Plantuml,
+                to
+            )),
+            Ok("drawn\n".to_string())
+        );
+    }
+}
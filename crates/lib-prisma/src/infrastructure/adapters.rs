@@ -0,0 +1 @@
+pub mod prisma_graph_gateway;
@@ -0,0 +1,274 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, PrismaParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct PrismaGraphGateway;
+
+impl PrismaGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for PrismaGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_prisma(input)
+            .map_err(GraphGatewayError::from)
+            .map(|schema| transformer::GraphBuilder::new().build(schema))
+    }
+}
+
+impl From<PrismaParseError> for GraphGatewayError {
+    fn from(err: PrismaParseError) -> Self {
+        match err {
+            PrismaParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "prisma".into(),
+                message,
+                line,
+                column,
+            },
+            PrismaParseError::Internal(msg) => GraphGatewayError::Semantic {
+                source: "prisma".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            id::Id,
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::prisma_graph_gateway::PrismaGraphGateway;
+
+    const SCHEMA: &str = r#"
+        datasource db {
+          provider = "postgresql"
+          url      = env("DATABASE_URL")
+        }
+
+        model User {
+          id      Int      @id @default(autoincrement())
+          email   String   @unique @map("email_address")
+          role    Role     @default(USER)
+          profile Profile?
+          posts   Post[]   @relation("Authored")
+          liked   Post[]   @relation("Likes")
+
+          @@map("users")
+        }
+
+        model Profile {
+          id     Int    @id
+          bio    String @db.Text
+          user   User   @relation(fields: [userId], references: [id], onDelete: Cascade)
+          userId Int    @unique
+        }
+
+        model Post {
+          id       Int        @id
+          author   User?      @relation("Authored", fields: [authorId], references: [id])
+          authorId Int?
+          likedBy  User[]     @relation("Likes")
+          tags     Tag[]
+        }
+
+        model Tag {
+          name  String @id
+          posts Post[]
+        }
+
+        enum Role {
+          USER
+          ADMIN @map("admin")
+        }
+    "#;
+
+    fn edges<'a>(graph: &'a Graph, from: &str, to: &str) -> Vec<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .filter(|edge: &&Edge| edge.from == from && edge.to == to)
+            .collect()
+    }
+
+    fn text(value: &str) -> Option<Value> {
+        Some(Value::String(value.to_string()))
+    }
+
+    #[test]
+    fn test_models_become_entities_without_relation_fields() {
+        smol::block_on(async {
+            let graph: Graph = PrismaGraphGateway::new()
+                .read_graph_from_raw_input(SCHEMA)
+                .await
+                .unwrap();
+
+            assert_eq!(graph.kind, GraphKind::EntityRelationship);
+            assert_eq!(
+                graph
+                    .metadata
+                    .properties
+                    .get("provider")
+                    .map(String::as_str),
+                Some("postgresql")
+            );
+
+            let user: &Node = &graph.nodes["User"];
+            assert_eq!(user.kind, NodeKind::Entity);
+            assert_eq!(user.data.get("map").cloned(), text("users"));
+            assert_eq!(
+                user.members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), m.type_name.as_deref().unwrap()))
+                    .collect::<Vec<(&str, &str)>>(),
+                vec![("id", "Int"), ("email", "String"), ("role", "Role")]
+            );
+            assert_eq!(
+                user.members[0].data.get("primary_key"),
+                Some(&Value::Bool(true))
+            );
+            assert_eq!(
+                user.members[0].data.get("default").cloned(),
+                text("autoincrement()")
+            );
+            assert_eq!(user.members[1].data.get("unique"), Some(&Value::Bool(true)));
+            assert_eq!(
+                user.members[1].data.get("map").cloned(),
+                text("email_address")
+            );
+            assert_eq!(
+                graph.nodes["Profile"].members[1]
+                    .data
+                    .get("native_type")
+                    .cloned(),
+                text("Text")
+            );
+            assert_eq!(
+                graph.nodes["Post"].members[1].type_name.as_deref(),
+                Some("Int?")
+            );
+
+            let role: &Node = &graph.nodes["Role"];
+            assert_eq!(role.kind, NodeKind::Enum);
+            assert_eq!(role.members[1].kind, MemberKind::EnumValue);
+            assert_eq!(role.members[1].data.get("map").cloned(), text("admin"));
+        });
+    }
+
+    #[test]
+    fn test_relations_are_drawn_once_from_their_foreign_keys() {
+        smol::block_on(async {
+            let graph: Graph = PrismaGraphGateway::new()
+                .read_graph_from_raw_input(SCHEMA)
+                .await
+                .unwrap();
+
+            assert_eq!(graph.edges.len(), 4);
+
+            let profile: &Edge = edges(&graph, "Profile", "User")[0];
+            assert_eq!(profile.kind, EdgeKind::Association);
+            assert_eq!(profile.data.get("from_column").cloned(), text("userId"));
+            assert_eq!(profile.data.get("to_column").cloned(), text("id"));
+            assert_eq!(profile.from_cardinality.as_deref(), Some("1"));
+            assert_eq!(profile.to_cardinality.as_deref(), Some("1"));
+            assert_eq!(profile.data.get("on_delete").cloned(), text("Cascade"));
+            assert_eq!(
+                graph.nodes["Profile"].members[2].data.get("foreign_key"),
+                Some(&Value::Bool(true))
+            );
+
+            let author: &Edge = edges(&graph, "Post", "User")
+                .into_iter()
+                .find(|edge: &&Edge| edge.data.get("relation") == text("Authored").as_ref())
+                .expect("Missing authored relation");
+            assert_eq!(author.from_cardinality.as_deref(), Some("*"));
+            assert_eq!(author.to_cardinality.as_deref(), Some("0..1"));
+
+            // Implicit many-to-many relations have no foreign key on either side
+            let likes: Vec<&Edge> = [edges(&graph, "User", "Post"), edges(&graph, "Post", "User")]
+                .concat()
+                .into_iter()
+                .filter(|edge: &&Edge| edge.data.get("relation") == text("Likes").as_ref())
+                .collect();
+            assert_eq!(likes.len(), 1);
+            assert_eq!(likes[0].from_cardinality.as_deref(), Some("*"));
+            assert_eq!(likes[0].to_cardinality.as_deref(), Some("*"));
+            assert_eq!(edges(&graph, "Post", "Tag").len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_schemas_group_models_and_composite_types_are_embedded() {
+        smol::block_on(async {
+            let graph: Graph = PrismaGraphGateway::new()
+                .read_graph_from_raw_input(
+                    "model Order {\n  id Int @id\n  shipping Address?\n  lines Line[]\n  @@schema(\"sales\")\n}\ntype Address {\n  city String\n}\ntype Line {\n  sku String\n}",
+                )
+                .await
+                .unwrap();
+
+            let order: &Node = &graph.nodes["Order"];
+            assert_eq!(order.data.get("schema").cloned(), text("sales"));
+            let group: Id = order.parent.clone().expect("Missing schema group");
+            assert_eq!(graph.groups[&group].label.as_deref(), Some("sales"));
+            assert_eq!(order.members.len(), 3);
+
+            assert_eq!(
+                graph.nodes["Address"].data.get("composite"),
+                Some(&Value::Bool(true))
+            );
+            let shipping: &Edge = edges(&graph, "Order", "Address")[0];
+            assert_eq!(shipping.kind, EdgeKind::Composition);
+            assert_eq!(shipping.label.as_deref(), Some("shipping"));
+            assert_eq!(shipping.to_cardinality.as_deref(), Some("0..1"));
+            assert_eq!(
+                edges(&graph, "Order", "Line")[0].to_cardinality.as_deref(),
+                Some("*")
+            );
+        });
+    }
+
+    #[test]
+    fn test_schemas_without_models_are_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = PrismaGraphGateway::new()
+                .read_graph_from_raw_input(
+                    "generator client {\n  provider = \"prisma-client-js\"\n}",
+                )
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Semantic { source, message }) => {
+                    assert_eq!(source, "prisma");
+                    assert_eq!(message, "Schema defines no models or enums");
+                }
+                other => panic!("Expected a semantic error, got {:?}", other),
+            }
+        });
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod prisma_ast;
@@ -0,0 +1,119 @@
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrismaSchema {
+    /// Database the datasource block points at, such as `postgresql`
+    pub provider: Option<String>,
+    pub definitions: Vec<PrismaDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrismaDefinition {
+    Model {
+        name: String,
+        kind: PrismaModelKind,
+        fields: Vec<PrismaField>,
+        attributes: Vec<PrismaAttribute>,
+    },
+    Enum {
+        name: String,
+        values: Vec<PrismaEnumValue>,
+        attributes: Vec<PrismaAttribute>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrismaModelKind {
+    Model,
+    View,
+    /// A composite type, embedded in the documents of MongoDB models
+    Type,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrismaField {
+    pub name: String,
+    pub type_name: String,
+    pub modifier: PrismaModifier,
+    pub attributes: Vec<PrismaAttribute>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PrismaModifier {
+    #[default]
+    Required,
+    Optional,
+    List,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrismaEnumValue {
+    pub name: String,
+    pub attributes: Vec<PrismaAttribute>,
+}
+
+/// A field attribute such as `@id`, or a block one such as `@@map("users")`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrismaAttribute {
+    pub name: String,
+    pub arguments: Vec<PrismaArgument>,
+}
+
+impl PrismaAttribute {
+    /// Finds an argument by name, or the leading unnamed one that Prisma
+    /// accepts in its place, as in `@relation("Author")` or `@@id([a, b])`
+    pub fn argument(&self, name: &str) -> Option<&PrismaArgument> {
+        self.arguments
+            .iter()
+            .find(|argument: &&PrismaArgument| argument.name.as_deref() == Some(name))
+            .or_else(|| {
+                self.arguments
+                    .first()
+                    .filter(|argument: &&PrismaArgument| argument.name.is_none())
+            })
+    }
+
+    /// Finds an argument by name only
+    pub fn named(&self, name: &str) -> Option<&PrismaArgument> {
+        self.arguments
+            .iter()
+            .find(|argument: &&PrismaArgument| argument.name.as_deref() == Some(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrismaArgument {
+    pub name: Option<String>,
+    pub value: PrismaValue,
+    /// The value as written, as in `now()` or `"draft"`
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrismaValue {
+    List(Vec<PrismaValue>),
+    /// A string literal without its quotes
+    String(String),
+    /// Anything else as written: numbers, constants and function calls
+    Expression(String),
+}
+
+impl PrismaValue {
+    /// Names of the fields a `fields: [a, b(sort: Desc)]` argument lists
+    pub fn field_names(&self) -> Vec<String> {
+        match self {
+            PrismaValue::List(values) => values
+                .iter()
+                .flat_map(|value: &PrismaValue| value.field_names())
+                .collect(),
+            PrismaValue::String(text) | PrismaValue::Expression(text) => {
+                vec![text.split('(').next().unwrap_or_default().to_string()]
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PrismaValue::String(text) | PrismaValue::Expression(text) => Some(text),
+            PrismaValue::List(_) => None,
+        }
+    }
+}
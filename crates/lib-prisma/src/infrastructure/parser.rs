@@ -0,0 +1,362 @@
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::infrastructure::models::prisma_ast::{
+    PrismaArgument, PrismaAttribute, PrismaDefinition, PrismaEnumValue, PrismaField,
+    PrismaModelKind, PrismaModifier, PrismaSchema, PrismaValue,
+};
+
+#[derive(Parser)]
+#[grammar = "infrastructure/prisma.pest"]
+pub struct PrismaParser;
+
+pub fn parse_prisma(input: &str) -> Result<PrismaSchema, PrismaParseError> {
+    let root: pest::iterators::Pair<Rule> = PrismaParser::parse(Rule::schema, input)
+        .map_err(PrismaParseError::from)?
+        .next()
+        .ok_or_else(|| PrismaParseError::Internal("Missing schema root".to_string()))?;
+
+    let mut schema: PrismaSchema = PrismaSchema::default();
+    root.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::config => {
+                let mut inner: pest::iterators::Pairs<Rule> = item.into_inner();
+                if inner
+                    .next()
+                    .map(|keyword: pest::iterators::Pair<Rule>| keyword.as_str())
+                    != Some("datasource")
+                {
+                    return;
+                }
+
+                schema.provider = inner
+                    .filter(|part: &pest::iterators::Pair<Rule>| part.as_rule() == Rule::assignment)
+                    .find_map(|assignment: pest::iterators::Pair<Rule>| {
+                        let mut parts: pest::iterators::Pairs<Rule> = assignment.into_inner();
+                        let key: pest::iterators::Pair<Rule> = parts.next()?;
+                        let value: PrismaValue = parse_value(parts.next()?);
+                        match key.as_str() {
+                            "provider" => value.as_str().map(str::to_string),
+                            _ => None,
+                        }
+                    });
+            }
+            Rule::model => schema.definitions.push(parse_model(item)),
+            Rule::enum_def => schema.definitions.push(parse_enum(item)),
+            _ => {}
+        });
+
+    if schema.definitions.is_empty() {
+        return Err(PrismaParseError::Internal(
+            "Schema defines no models or enums".to_string(),
+        ));
+    }
+
+    Ok(schema)
+}
+
+fn parse_model(pair: pest::iterators::Pair<Rule>) -> PrismaDefinition {
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    let kind: PrismaModelKind = match inner
+        .next()
+        .map(|keyword: pest::iterators::Pair<Rule>| keyword.as_str())
+    {
+        Some("view") => PrismaModelKind::View,
+        Some("type") => PrismaModelKind::Type,
+        _ => PrismaModelKind::Model,
+    };
+    let name: String = inner
+        .next()
+        .map(|name: pest::iterators::Pair<Rule>| name.as_str().to_string())
+        .unwrap_or_default();
+
+    let mut fields: Vec<PrismaField> = Vec::new();
+    let mut attributes: Vec<PrismaAttribute> = Vec::new();
+    inner.for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+        Rule::field => fields.push(parse_field(item)),
+        Rule::block_attribute => attributes.push(parse_attribute(item)),
+        _ => {}
+    });
+
+    PrismaDefinition::Model {
+        name,
+        kind,
+        fields,
+        attributes,
+    }
+}
+
+fn parse_field(pair: pest::iterators::Pair<Rule>) -> PrismaField {
+    let mut field: PrismaField = PrismaField::default();
+
+    pair.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::ident => field.name = item.as_str().to_string(),
+            Rule::field_type => item
+                .into_inner()
+                .for_each(|part: pest::iterators::Pair<Rule>| match part.as_rule() {
+                    Rule::modifier if part.as_str() == "[]" => {
+                        field.modifier = PrismaModifier::List
+                    }
+                    Rule::modifier => field.modifier = PrismaModifier::Optional,
+                    _ => field.type_name = part.as_str().to_string(),
+                }),
+            Rule::field_attribute => field.attributes.push(parse_attribute(item)),
+            _ => {}
+        });
+
+    field
+}
+
+fn parse_enum(pair: pest::iterators::Pair<Rule>) -> PrismaDefinition {
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    let name: String = inner
+        .next()
+        .map(|name: pest::iterators::Pair<Rule>| name.as_str().to_string())
+        .unwrap_or_default();
+
+    let mut values: Vec<PrismaEnumValue> = Vec::new();
+    let mut attributes: Vec<PrismaAttribute> = Vec::new();
+    inner.for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+        Rule::enum_value => {
+            let mut parts: pest::iterators::Pairs<Rule> = item.into_inner();
+            values.push(PrismaEnumValue {
+                name: parts
+                    .next()
+                    .map(|name: pest::iterators::Pair<Rule>| name.as_str().to_string())
+                    .unwrap_or_default(),
+                attributes: parts.map(parse_attribute).collect(),
+            });
+        }
+        Rule::block_attribute => attributes.push(parse_attribute(item)),
+        _ => {}
+    });
+
+    PrismaDefinition::Enum {
+        name,
+        values,
+        attributes,
+    }
+}
+
+fn parse_attribute(pair: pest::iterators::Pair<Rule>) -> PrismaAttribute {
+    let mut attribute: PrismaAttribute = PrismaAttribute::default();
+
+    pair.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::attribute_name => attribute.name = item.as_str().to_string(),
+            Rule::arguments => attribute.arguments = parse_arguments(item),
+            _ => {}
+        });
+
+    attribute
+}
+
+fn parse_arguments(pair: pest::iterators::Pair<Rule>) -> Vec<PrismaArgument> {
+    pair.into_inner()
+        .map(|argument: pest::iterators::Pair<Rule>| {
+            let mut name: Option<String> = None;
+            let mut value: PrismaValue = PrismaValue::Expression(String::new());
+            let mut text: String = String::new();
+
+            argument
+                .into_inner()
+                .for_each(|part: pest::iterators::Pair<Rule>| match part.as_rule() {
+                    Rule::argument_name => name = Some(part.as_str().to_string()),
+                    _ => {
+                        text = part.as_str().to_string();
+                        value = parse_value(part);
+                    }
+                });
+
+            PrismaArgument { name, value, text }
+        })
+        .collect()
+}
+
+fn parse_value(pair: pest::iterators::Pair<Rule>) -> PrismaValue {
+    // Expressions wrap a single array, call, string, number or path
+    let pair: pest::iterators::Pair<Rule> = match pair.as_rule() {
+        Rule::expression => match pair.into_inner().next() {
+            Some(inner) => inner,
+            None => return PrismaValue::Expression(String::new()),
+        },
+        _ => pair,
+    };
+
+    match pair.as_rule() {
+        Rule::array => PrismaValue::List(pair.into_inner().map(parse_value).collect()),
+        Rule::string => {
+            let raw: &str = pair.as_str();
+            PrismaValue::String(raw[1..raw.len() - 1].to_string())
+        }
+        _ => PrismaValue::Expression(pair.as_str().to_string()),
+    }
+}
+
+#[derive(Debug)]
+pub enum PrismaParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Internal(String),
+}
+
+impl From<pest::error::Error<Rule>> for PrismaParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column): (usize, usize) = match err.line_col {
+            pest::error::LineColLocation::Pos((l, c)) => (l, c),
+            pest::error::LineColLocation::Span((l, c), _) => (l, c),
+        };
+
+        PrismaParseError::Syntax {
+            message: err.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parses_fields_modifiers_and_attributes() {
+        let schema: PrismaSchema = parse_prisma(
+            r#"
+            datasource db {
+              provider = "postgresql"
+              url      = env("DATABASE_URL")
+            }
+
+            generator client {
+              provider = "prisma-client-js"
+            }
+
+            /// A published article
+            model Post {
+              id       Int     @id @default(autoincrement())
+              title    String  @db.VarChar(255)
+              body     String?
+              tags     String[]
+              author   User    @relation("Posts", fields: [authorId], references: [id], onDelete: Cascade)
+              authorId Int
+
+              @@index([title(sort: Desc), authorId])
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(schema.provider.as_deref(), Some("postgresql"));
+        let PrismaDefinition::Model {
+            fields, attributes, ..
+        } = &schema.definitions[0]
+        else {
+            panic!("Expected a model");
+        };
+        assert_eq!(
+            fields
+                .iter()
+                .map(|f: &PrismaField| (f.name.as_str(), f.type_name.as_str(), f.modifier))
+                .collect::<Vec<(&str, &str, PrismaModifier)>>(),
+            vec![
+                ("id", "Int", PrismaModifier::Required),
+                ("title", "String", PrismaModifier::Required),
+                ("body", "String", PrismaModifier::Optional),
+                ("tags", "String", PrismaModifier::List),
+                ("author", "User", PrismaModifier::Required),
+                ("authorId", "Int", PrismaModifier::Required),
+            ]
+        );
+        assert_eq!(fields[0].attributes[1].arguments[0].text, "autoincrement()");
+        assert_eq!(fields[1].attributes[0].name, "db.VarChar");
+
+        let relation: &PrismaAttribute = &fields[4].attributes[0];
+        assert_eq!(
+            relation.argument("name").map(|a: &PrismaArgument| &a.value),
+            Some(&PrismaValue::String("Posts".to_string()))
+        );
+        assert_eq!(
+            relation
+                .named("fields")
+                .map(|a: &PrismaArgument| a.value.field_names()),
+            Some(vec!["authorId".to_string()])
+        );
+        assert_eq!(
+            attributes[0]
+                .argument("fields")
+                .unwrap()
+                .value
+                .field_names(),
+            vec!["title".to_string(), "authorId".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parses_enums_views_and_composite_types() {
+        let schema: PrismaSchema = parse_prisma(
+            "enum Role {\n  USER\n  ADMIN @map(\"admin\")\n  @@map(\"roles\")\n}\nview Stats {\n  total Int @unique\n}\ntype Address {\n  city String\n}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema.definitions[0],
+            PrismaDefinition::Enum {
+                name: "Role".to_string(),
+                values: vec![
+                    PrismaEnumValue {
+                        name: "USER".to_string(),
+                        attributes: Vec::new(),
+                    },
+                    PrismaEnumValue {
+                        name: "ADMIN".to_string(),
+                        attributes: vec![PrismaAttribute {
+                            name: "map".to_string(),
+                            arguments: vec![PrismaArgument {
+                                name: None,
+                                value: PrismaValue::String("admin".to_string()),
+                                text: "\"admin\"".to_string(),
+                            }],
+                        }],
+                    },
+                ],
+                attributes: vec![PrismaAttribute {
+                    name: "map".to_string(),
+                    arguments: vec![PrismaArgument {
+                        name: None,
+                        value: PrismaValue::String("roles".to_string()),
+                        text: "\"roles\"".to_string(),
+                    }],
+                }],
+            }
+        );
+        assert!(matches!(
+            schema.definitions[1],
+            PrismaDefinition::Model {
+                kind: PrismaModelKind::View,
+                ..
+            }
+        ));
+        assert!(matches!(
+            schema.definitions[2],
+            PrismaDefinition::Model {
+                kind: PrismaModelKind::Type,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_reports_syntax_errors_with_position() {
+        match parse_prisma("model User {\n  id Int @id\n  name\n}") {
+            Err(PrismaParseError::Syntax { line, .. }) => assert_eq!(line, 4),
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::prisma_ast::{
+    PrismaArgument, PrismaAttribute, PrismaDefinition, PrismaEnumValue, PrismaField,
+    PrismaModelKind, PrismaModifier, PrismaSchema,
+};
+
+/// A field pointing at another model, which Prisma keeps out of the table
+struct RelationField<'a> {
+    model: &'a str,
+    field: &'a PrismaField,
+    /// Name telling several relations between the same models apart
+    name: Option<&'a str>,
+    relation: Option<&'a PrismaAttribute>,
+}
+
+impl RelationField<'_> {
+    fn argument_fields(&self, argument: &str) -> Vec<String> {
+        self.relation
+            .and_then(|relation: &PrismaAttribute| relation.named(argument))
+            .map(|argument: &PrismaArgument| argument.value.field_names())
+            .unwrap_or_default()
+    }
+}
+
+pub struct GraphBuilder {
+    graph: Graph,
+    schemas: HashMap<String, Id>, // Maps `@@schema` names to the groups holding their models
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::EntityRelationship,
+                ..Default::default()
+            },
+            schemas: HashMap::new(),
+        }
+    }
+
+    pub fn build(mut self, schema: PrismaSchema) -> Graph {
+        if let Some(provider) = &schema.provider {
+            self.graph
+                .metadata
+                .properties
+                .insert("provider".to_string(), provider.clone());
+        }
+
+        let kinds: HashMap<&str, PrismaModelKind> = schema
+            .definitions
+            .iter()
+            .filter_map(|definition: &PrismaDefinition| match definition {
+                PrismaDefinition::Model { name, kind, .. } => Some((name.as_str(), *kind)),
+                PrismaDefinition::Enum { .. } => None,
+            })
+            .collect();
+
+        let mut relations: Vec<RelationField> = Vec::new();
+        schema
+            .definitions
+            .iter()
+            .for_each(|definition: &PrismaDefinition| match definition {
+                PrismaDefinition::Model {
+                    name,
+                    kind,
+                    fields,
+                    attributes,
+                } => {
+                    let (columns, references): (Vec<&PrismaField>, Vec<&PrismaField>) =
+                        fields.iter().partition(|field: &&PrismaField| {
+                            !matches!(
+                                kinds.get(field.type_name.as_str()),
+                                Some(PrismaModelKind::Model | PrismaModelKind::View)
+                            )
+                        });
+                    relations.extend(references.into_iter().map(|field: &PrismaField| {
+                        let relation: Option<&PrismaAttribute> =
+                            find(&field.attributes, "relation");
+                        RelationField {
+                            model: name,
+                            field,
+                            name: relation
+                                .and_then(|relation: &PrismaAttribute| relation.argument("name"))
+                                .and_then(|argument: &PrismaArgument| argument.value.as_str()),
+                            relation,
+                        }
+                    }));
+
+                    self.add_model(name, *kind, &columns, attributes);
+                    columns
+                        .iter()
+                        .filter(|field: &&&PrismaField| {
+                            kinds.get(field.type_name.as_str()) == Some(&PrismaModelKind::Type)
+                        })
+                        .for_each(|field: &&PrismaField| self.add_embedding(name, field));
+                }
+                PrismaDefinition::Enum {
+                    name,
+                    values,
+                    attributes,
+                } => self.add_enum(name, values, attributes),
+            });
+
+        relations
+            .iter()
+            .for_each(|relation: &RelationField| self.add_relationship(relation, &relations));
+
+        self.graph
+    }
+
+    fn add_model(
+        &mut self,
+        name: &str,
+        kind: PrismaModelKind,
+        fields: &[&PrismaField],
+        attributes: &[PrismaAttribute],
+    ) {
+        let mut members: Vec<Member> = fields
+            .iter()
+            .map(|field: &&PrismaField| map_field(field))
+            .collect();
+
+        // Block attributes flag the fields they list, as in `@@id([a, b])`
+        attributes.iter().for_each(|attribute: &PrismaAttribute| {
+            let columns: Vec<String> = attribute
+                .argument("fields")
+                .map(|argument: &PrismaArgument| argument.value.field_names())
+                .unwrap_or_default();
+            let flag: &str = match attribute.name.as_str() {
+                "id" => "primary_key",
+                "unique" if columns.len() == 1 => "unique",
+                _ => return,
+            };
+            members
+                .iter_mut()
+                .filter(|member: &&mut Member| columns.contains(&member.name))
+                .for_each(|member: &mut Member| {
+                    member.data.insert(flag.into(), Value::Bool(true));
+                });
+        });
+
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        match kind {
+            PrismaModelKind::Model => {}
+            PrismaModelKind::View => {
+                data.insert("view".into(), Value::Bool(true));
+            }
+            PrismaModelKind::Type => {
+                data.insert("composite".into(), Value::Bool(true));
+            }
+        }
+        let parent: Option<Id> = self.annotate(name, attributes, &mut data);
+
+        self.graph.nodes.insert(
+            Id::from(name),
+            Node {
+                id: Id::from(name),
+                kind: NodeKind::Entity,
+                label: Some(name.into()),
+                members,
+                data,
+                parent,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn add_enum(&mut self, name: &str, values: &[PrismaEnumValue], attributes: &[PrismaAttribute]) {
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        let parent: Option<Id> = self.annotate(name, attributes, &mut data);
+
+        self.graph.nodes.insert(
+            Id::from(name),
+            Node {
+                id: Id::from(name),
+                kind: NodeKind::Enum,
+                label: Some(name.into()),
+                members: values
+                    .iter()
+                    .map(|value: &PrismaEnumValue| Member {
+                        name: value.name.clone(),
+                        kind: MemberKind::EnumValue,
+                        data: mapped_name(&value.attributes).into_iter().collect(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                data,
+                parent,
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Records the database name of a model or enum, and places it in the
+    /// group of its `@@schema`
+    fn annotate(
+        &mut self,
+        name: &str,
+        attributes: &[PrismaAttribute],
+        data: &mut HashMap<Text, Value>,
+    ) -> Option<Id> {
+        data.extend(mapped_name(attributes));
+
+        let schema: &str = find(attributes, "schema")
+            .and_then(|attribute: &PrismaAttribute| attribute.argument("name"))
+            .and_then(|argument: &PrismaArgument| argument.value.as_str())?;
+        data.insert("schema".into(), Value::String(schema.to_string()));
+
+        let group_id: Id = self.schema_group(schema);
+        if let Some(group) = self.graph.groups.get_mut(&group_id) {
+            group.children.push(Id::from(name));
+        }
+        Some(group_id)
+    }
+
+    fn schema_group(&mut self, schema: &str) -> Id {
+        if let Some(group_id) = self.schemas.get(schema) {
+            return group_id.clone();
+        }
+
+        let group_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.groups.insert(
+            group_id.clone(),
+            Group {
+                id: group_id.clone(),
+                label: Some(schema.into()),
+                ..Default::default()
+            },
+        );
+        self.schemas.insert(schema.to_string(), group_id.clone());
+        group_id
+    }
+
+    /// Links a model to the composite type one of its fields embeds
+    fn add_embedding(&mut self, model: &str, field: &PrismaField) {
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id,
+                from: Id::from(model),
+                to: Id::from(field.type_name.as_str()),
+                directed: false,
+                kind: EdgeKind::Composition,
+                label: Some(field.name.as_str().into()),
+                to_cardinality: Some(cardinality(field.modifier).into()),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Draws a relation from the side holding its foreign key, or once for
+    /// both sides of an implicit many-to-many relation
+    fn add_relationship(&mut self, relation: &RelationField, relations: &[RelationField]) {
+        let target: &str = relation.field.type_name.as_str();
+        let opposite: Option<&RelationField> = relations.iter().find(|other: &&RelationField| {
+            other.model == target
+                && other.field.type_name == relation.model
+                && other.name == relation.name
+                && !std::ptr::eq(other.field, relation.field)
+        });
+
+        let from_columns: Vec<String> = relation.argument_fields("fields");
+        let (from_cardinality, to_cardinality): (&str, &str) = if !from_columns.is_empty() {
+            (
+                match opposite.map(|other: &RelationField| other.field.modifier) {
+                    Some(PrismaModifier::List) | None => "*",
+                    Some(_) => "1",
+                },
+                cardinality(relation.field.modifier),
+            )
+        } else {
+            match opposite {
+                Some(other)
+                    if relation.field.modifier == PrismaModifier::List
+                        && other.field.modifier == PrismaModifier::List
+                        && other.argument_fields("fields").is_empty()
+                        && (relation.model, relation.field.name.as_str())
+                            <= (other.model, other.field.name.as_str()) =>
+                {
+                    ("*", "*")
+                }
+                // The other side holds the foreign key and draws the relation
+                _ => return,
+            }
+        };
+
+        let to_columns: Vec<String> = relation.argument_fields("references");
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        [
+            ("from_column", from_columns.join(", ")),
+            ("to_column", to_columns.join(", ")),
+        ]
+        .into_iter()
+        .filter(|(_, columns): &(&str, String)| !columns.is_empty())
+        .for_each(|(key, columns): (&str, String)| {
+            data.insert(key.into(), Value::String(columns));
+        });
+        [
+            ("relation", relation.name),
+            ("on_delete", relation_argument(relation, "onDelete")),
+            ("on_update", relation_argument(relation, "onUpdate")),
+        ]
+        .into_iter()
+        .for_each(|(key, value): (&str, Option<&str>)| {
+            if let Some(value) = value {
+                data.insert(key.into(), Value::String(value.to_string()));
+            }
+        });
+
+        if let Some(node) = self.graph.nodes.get_mut(&Id::from(relation.model)) {
+            node.members
+                .iter_mut()
+                .filter(|member: &&mut Member| from_columns.contains(&member.name))
+                .for_each(|member: &mut Member| {
+                    member.data.insert("foreign_key".into(), Value::Bool(true));
+                });
+        }
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id,
+                from: Id::from(relation.model),
+                to: Id::from(target),
+                directed: false,
+                kind: EdgeKind::Association,
+                data,
+                from_cardinality: Some(from_cardinality.into()),
+                to_cardinality: Some(to_cardinality.into()),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+fn find<'a>(attributes: &'a [PrismaAttribute], name: &str) -> Option<&'a PrismaAttribute> {
+    attributes
+        .iter()
+        .find(|attribute: &&PrismaAttribute| attribute.name == name)
+}
+
+fn relation_argument<'a>(relation: &RelationField<'a>, name: &str) -> Option<&'a str> {
+    relation
+        .relation?
+        .named(name)
+        .and_then(|argument: &PrismaArgument| argument.value.as_str())
+}
+
+/// The name an element is stored under when `@map` or `@@map` renames it
+fn mapped_name(attributes: &[PrismaAttribute]) -> Option<(Text, Value)> {
+    find(attributes, "map")
+        .and_then(|attribute: &PrismaAttribute| attribute.argument("name"))
+        .and_then(|argument: &PrismaArgument| argument.value.as_str())
+        .map(|name: &str| ("map".into(), Value::String(name.to_string())))
+}
+
+fn cardinality(modifier: PrismaModifier) -> &'static str {
+    match modifier {
+        PrismaModifier::Required => "1",
+        PrismaModifier::Optional => "0..1",
+        PrismaModifier::List => "*",
+    }
+}
+
+fn map_field(field: &PrismaField) -> Member {
+    let mut data: HashMap<Text, Value> = mapped_name(&field.attributes).into_iter().collect();
+    field.attributes.iter().for_each(
+        |attribute: &PrismaAttribute| match attribute.name.as_str() {
+            "id" => {
+                data.insert("primary_key".into(), Value::Bool(true));
+            }
+            "unique" => {
+                data.insert("unique".into(), Value::Bool(true));
+            }
+            "updatedAt" => {
+                data.insert("updated_at".into(), Value::Bool(true));
+            }
+            "default" => {
+                if let Some(argument) = attribute.argument("value") {
+                    data.insert("default".into(), Value::String(argument.text.clone()));
+                }
+            }
+            // Native types, as in `@db.VarChar(255)`
+            name => {
+                if let Some(native) = name.strip_prefix("db.") {
+                    let arguments: Vec<&str> = attribute
+                        .arguments
+                        .iter()
+                        .map(|argument: &PrismaArgument| argument.text.as_str())
+                        .collect();
+                    let native: String = match arguments.is_empty() {
+                        true => native.to_string(),
+                        false => format!("{}({})", native, arguments.join(", ")),
+                    };
+                    data.insert("native_type".into(), Value::String(native));
+                }
+            }
+        },
+    );
+
+    let suffix: &str = match field.modifier {
+        PrismaModifier::Required => "",
+        PrismaModifier::Optional => "?",
+        PrismaModifier::List => "[]",
+    };
+    Member {
+        name: field.name.clone(),
+        kind: MemberKind::Field,
+        type_name: Some(format!("{}{}", field.type_name, suffix)),
+        data,
+    }
+}
@@ -0,0 +1 @@
+pub mod protobuf_graph_gateway;
@@ -0,0 +1,247 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, ProtobufParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct ProtobufGraphGateway;
+
+impl ProtobufGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for ProtobufGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_protobuf(input)
+            .map_err(GraphGatewayError::from)
+            .map(|file| transformer::GraphBuilder::new().build(file))
+    }
+}
+
+impl From<ProtobufParseError> for GraphGatewayError {
+    fn from(err: ProtobufParseError) -> Self {
+        match err {
+            ProtobufParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "protobuf".into(),
+                message,
+                line,
+                column,
+            },
+            ProtobufParseError::Internal(msg) => GraphGatewayError::Semantic {
+                source: "protobuf".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::protobuf_graph_gateway::ProtobufGraphGateway;
+
+    const SOURCE: &str = r#"
+        syntax = "proto3";
+
+        package shop.v1;
+
+        import "google/protobuf/timestamp.proto";
+
+        message Order {
+          message Line {
+            string sku = 1;
+            uint32 quantity = 2;
+          }
+
+          enum Status {
+            STATUS_UNSPECIFIED = 0;
+            STATUS_PAID = 1;
+          }
+
+          string id = 1;
+          repeated Line lines = 2;
+          Status status = 3;
+          .shop.v1.Customer customer = 4;
+          map<string, Line> by_sku = 5;
+          google.protobuf.Timestamp created_at = 6;
+        }
+
+        message Customer {
+          string name = 1;
+        }
+
+        service Orders {
+          rpc GetOrder (GetOrderRequest) returns (Order);
+          rpc WatchOrders (Customer) returns (stream Order) {
+            option deprecated = true;
+          }
+        }
+
+        message GetOrderRequest {
+          string id = 1;
+        }
+    "#;
+
+    fn edges<'a>(graph: &'a Graph, from: &str, to: &str) -> Vec<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .filter(|edge: &&Edge| edge.from == from && edge.to == to)
+            .collect()
+    }
+
+    #[test]
+    fn test_messages_enums_and_nested_types_become_classes() {
+        smol::block_on(async {
+            let graph: Graph = ProtobufGraphGateway::new()
+                .read_graph_from_raw_input(SOURCE)
+                .await
+                .unwrap();
+
+            assert_eq!(graph.kind, GraphKind::Class);
+            assert_eq!(graph.nodes.len(), 6);
+            assert_eq!(graph.groups["shop.v1"].children.len(), 6);
+
+            let order: &Node = graph.nodes.get("Order").expect("Missing Order");
+            assert_eq!(order.kind, NodeKind::Entity);
+            assert_eq!(
+                order
+                    .members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), m.type_name.as_deref().unwrap()))
+                    .collect::<Vec<(&str, &str)>>(),
+                vec![
+                    ("id", "string"),
+                    ("lines", "repeated Line"),
+                    ("status", "Status"),
+                    ("customer", ".shop.v1.Customer"),
+                    ("by_sku", "map<string, Line>"),
+                    ("created_at", "google.protobuf.Timestamp"),
+                ]
+            );
+            assert_eq!(
+                order.members[1].data.get("number"),
+                Some(&Value::Number(2.0))
+            );
+
+            let status: &Node = graph.nodes.get("Order.Status").expect("Missing enum");
+            assert_eq!(status.kind, NodeKind::Enum);
+            assert_eq!(status.label.as_deref(), Some("Order.Status"));
+            assert_eq!(status.members[1].name, "STATUS_PAID");
+            assert_eq!(status.members[1].kind, MemberKind::EnumValue);
+            assert!(
+                edges(&graph, "Order", "Order.Status")
+                    .iter()
+                    .any(|edge: &&Edge| edge.kind == EdgeKind::Composition)
+            );
+        });
+    }
+
+    #[test]
+    fn test_field_references_resolve_through_scopes() {
+        smol::block_on(async {
+            let graph: Graph = ProtobufGraphGateway::new()
+                .read_graph_from_raw_input(SOURCE)
+                .await
+                .unwrap();
+
+            let lines: Vec<&Edge> = edges(&graph, "Order", "Order.Line");
+            let associations: Vec<(Option<&str>, Option<&str>)> = lines
+                .iter()
+                .filter(|edge: &&&Edge| edge.kind == EdgeKind::Association)
+                .map(|edge: &&Edge| (edge.label.as_deref(), edge.to_cardinality.as_deref()))
+                .collect();
+            assert_eq!(associations.len(), 2);
+            assert!(
+                associations
+                    .iter()
+                    .all(
+                        |(_, cardinality): &(Option<&str>, Option<&str>)| *cardinality == Some("*")
+                    )
+            );
+
+            let customer: Vec<&Edge> = edges(&graph, "Order", "Customer");
+            assert_eq!(customer.len(), 1);
+            assert_eq!(customer[0].label.as_deref(), Some("customer"));
+            assert_eq!(customer[0].to_cardinality.as_deref(), Some("0..1"));
+
+            // Imported types are not part of the diagram
+            assert!(
+                graph
+                    .edges
+                    .values()
+                    .all(|edge: &Edge| graph.nodes.contains_key(&edge.to))
+            );
+        });
+    }
+
+    #[test]
+    fn test_services_depend_on_their_messages() {
+        smol::block_on(async {
+            let graph: Graph = ProtobufGraphGateway::new()
+                .read_graph_from_raw_input(SOURCE)
+                .await
+                .unwrap();
+
+            let service: &Node = graph.nodes.get("Orders").expect("Missing service");
+            assert_eq!(service.kind, NodeKind::Interface);
+            assert_eq!(
+                service.members[1].type_name.as_deref(),
+                Some("stream Order")
+            );
+            assert_eq!(
+                service.members[1].data.get("parameters"),
+                Some(&Value::List(vec![Value::String("Customer".to_string())]))
+            );
+
+            ["GetOrderRequest", "Order", "Customer"]
+                .iter()
+                .for_each(|target: &&str| {
+                    let dependencies: Vec<&Edge> = edges(&graph, "Orders", target);
+                    assert_eq!(dependencies.len(), 1, "Expected one edge to {}", target);
+                    assert_eq!(dependencies[0].kind, EdgeKind::Dependency);
+                });
+        });
+    }
+
+    #[test]
+    fn test_files_without_definitions_are_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = ProtobufGraphGateway::new()
+                .read_graph_from_raw_input("syntax = \"proto3\";\npackage empty;\n")
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Semantic { source, message }) => {
+                    assert_eq!(source, "protobuf");
+                    assert_eq!(message, "File defines no messages, enums or services");
+                }
+                other => panic!("Expected a semantic error, got {:?}", other),
+            }
+        });
+    }
+}
@@ -0,0 +1,68 @@
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProtoFile {
+    pub package: Option<String>,
+    pub definitions: Vec<ProtoDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProtoDefinition {
+    /// A message with the types declared inside of it
+    Message {
+        name: String,
+        fields: Vec<ProtoField>,
+        nested: Vec<ProtoDefinition>,
+    },
+    Enum {
+        name: String,
+        values: Vec<ProtoEnumValue>,
+    },
+    Service {
+        name: String,
+        methods: Vec<ProtoMethod>,
+    },
+}
+
+impl ProtoDefinition {
+    pub fn name(&self) -> &str {
+        match self {
+            ProtoDefinition::Message { name, .. }
+            | ProtoDefinition::Enum { name, .. }
+            | ProtoDefinition::Service { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProtoField {
+    pub name: String,
+    pub type_name: String,
+    pub label: ProtoLabel,
+    pub number: i64,
+    /// Key type of a `map<key, value>` field, whose value is `type_name`
+    pub key_type: Option<String>,
+    pub oneof: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ProtoLabel {
+    #[default]
+    Singular,
+    Optional,
+    Required,
+    Repeated,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtoEnumValue {
+    pub name: String,
+    pub number: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProtoMethod {
+    pub name: String,
+    pub request: String,
+    pub response: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
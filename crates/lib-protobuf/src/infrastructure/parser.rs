@@ -0,0 +1,315 @@
+use lib_core::nesting;
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::infrastructure::models::proto_ast::{
+    ProtoDefinition, ProtoEnumValue, ProtoField, ProtoFile, ProtoLabel, ProtoMethod,
+};
+
+/// How deep messages and option aggregates may nest inside each other
+const MAX_DEPTH: usize = 64;
+
+#[derive(Parser)]
+#[grammar = "infrastructure/proto.pest"]
+pub struct ProtoParser;
+
+pub fn parse_protobuf(input: &str) -> Result<ProtoFile, ProtobufParseError> {
+    if let Some((line, column)) = nesting::deepest_brace(input, &['"', '\''], MAX_DEPTH) {
+        return Err(ProtobufParseError::Syntax {
+            message: format!("Blocks nest past the depth limit of {}", MAX_DEPTH),
+            line,
+            column,
+        });
+    }
+    let file: pest::iterators::Pair<Rule> = ProtoParser::parse(Rule::file, input)
+        .map_err(ProtobufParseError::from)?
+        .next()
+        .ok_or_else(|| ProtobufParseError::Internal("Missing file root".to_string()))?;
+
+    let mut proto: ProtoFile = ProtoFile::default();
+    file.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::package => {
+                proto.package = item
+                    .into_inner()
+                    .next()
+                    .map(|name: pest::iterators::Pair<Rule>| name.as_str().to_string())
+            }
+            _ => proto.definitions.extend(parse_definition(item)),
+        });
+
+    if proto.definitions.is_empty() {
+        return Err(ProtobufParseError::Internal(
+            "File defines no messages, enums or services".to_string(),
+        ));
+    }
+
+    Ok(proto)
+}
+
+fn parse_definition(pair: pest::iterators::Pair<Rule>) -> Option<ProtoDefinition> {
+    let rule: Rule = pair.as_rule();
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+
+    match rule {
+        Rule::message => {
+            let name: String = inner.next()?.as_str().to_string();
+            let mut fields: Vec<ProtoField> = Vec::new();
+            let mut nested: Vec<ProtoDefinition> = Vec::new();
+
+            inner.for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+                Rule::field | Rule::map_field => fields.push(parse_field(item, None)),
+                Rule::oneof => {
+                    let mut parts: pest::iterators::Pairs<Rule> = item.into_inner();
+                    let oneof: Option<String> = parts
+                        .next()
+                        .map(|name: pest::iterators::Pair<Rule>| name.as_str().to_string());
+                    fields.extend(
+                        parts
+                            .filter(|part: &pest::iterators::Pair<Rule>| {
+                                part.as_rule() == Rule::oneof_field
+                            })
+                            .map(|field: pest::iterators::Pair<Rule>| {
+                                parse_field(field, oneof.clone())
+                            }),
+                    );
+                }
+                _ => nested.extend(parse_definition(item)),
+            });
+
+            Some(ProtoDefinition::Message {
+                name,
+                fields,
+                nested,
+            })
+        }
+        Rule::enum_def => Some(ProtoDefinition::Enum {
+            name: inner.next()?.as_str().to_string(),
+            values: inner
+                .filter(|item: &pest::iterators::Pair<Rule>| item.as_rule() == Rule::enum_value)
+                .filter_map(|value: pest::iterators::Pair<Rule>| {
+                    let mut parts: pest::iterators::Pairs<Rule> = value.into_inner();
+                    Some(ProtoEnumValue {
+                        name: parts.next()?.as_str().to_string(),
+                        number: parse_number(parts.next()?.as_str()),
+                    })
+                })
+                .collect(),
+        }),
+        Rule::service => Some(ProtoDefinition::Service {
+            name: inner.next()?.as_str().to_string(),
+            methods: inner
+                .filter(|item: &pest::iterators::Pair<Rule>| item.as_rule() == Rule::rpc)
+                .map(parse_method)
+                .collect(),
+        }),
+        _ => None,
+    }
+}
+
+fn parse_field(pair: pest::iterators::Pair<Rule>, oneof: Option<String>) -> ProtoField {
+    let is_map: bool = pair.as_rule() == Rule::map_field;
+    let mut field: ProtoField = ProtoField {
+        oneof,
+        ..Default::default()
+    };
+    let mut types: Vec<String> = Vec::new();
+
+    pair.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::label => {
+                field.label = match item.as_str() {
+                    "repeated" => ProtoLabel::Repeated,
+                    "optional" => ProtoLabel::Optional,
+                    _ => ProtoLabel::Required,
+                }
+            }
+            Rule::type_name => types.push(item.as_str().to_string()),
+            Rule::ident => field.name = item.as_str().to_string(),
+            Rule::int_lit => field.number = parse_number(item.as_str()),
+            _ => {}
+        });
+
+    // Maps hold their key type first, then the type of their values
+    if is_map {
+        field.key_type = types.first().cloned();
+    }
+    field.type_name = types.pop().unwrap_or_default();
+    field
+}
+
+fn parse_method(pair: pest::iterators::Pair<Rule>) -> ProtoMethod {
+    let mut method: ProtoMethod = ProtoMethod::default();
+    let mut streaming: bool = false;
+
+    pair.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::ident => method.name = item.as_str().to_string(),
+            Rule::stream => streaming = true,
+            Rule::type_name if method.request.is_empty() => {
+                method.request = item.as_str().to_string();
+                method.client_streaming = std::mem::take(&mut streaming);
+            }
+            Rule::type_name => {
+                method.response = item.as_str().to_string();
+                method.server_streaming = std::mem::take(&mut streaming);
+            }
+            _ => {}
+        });
+
+    method
+}
+
+fn parse_number(raw: &str) -> i64 {
+    let (negative, digits): (bool, &str) = match raw.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, raw),
+    };
+    let value: i64 = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => i64::from_str_radix(hex, 16).unwrap_or_default(),
+        None => digits.parse().unwrap_or_default(),
+    };
+
+    if negative { -value } else { value }
+}
+
+#[derive(Debug)]
+pub enum ProtobufParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Internal(String),
+}
+
+impl From<pest::error::Error<Rule>> for ProtobufParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column): (usize, usize) = match err.line_col {
+            pest::error::LineColLocation::Pos((l, c)) => (l, c),
+            pest::error::LineColLocation::Span((l, c), _) => (l, c),
+        };
+
+        ProtobufParseError::Syntax {
+            message: err.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parses_fields_with_labels_maps_and_oneofs() {
+        let file: ProtoFile = parse_protobuf(
+            r#"
+            syntax = "proto3";
+            package shop.v1;
+            option java_package = "com.example.shop";
+
+            message Order {
+              optional string note = 1 [deprecated = true];
+              repeated Item items = 2;
+              map<string, Price> prices = 3;
+              oneof payment {
+                Card card = 4;
+                string voucher = 0x5;
+              }
+              reserved 6, 8 to 10;
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(file.package.as_deref(), Some("shop.v1"));
+        let ProtoDefinition::Message { fields, .. } = &file.definitions[0] else {
+            panic!("Expected a message");
+        };
+        assert_eq!(
+            fields
+                .iter()
+                .map(|f: &ProtoField| (f.name.as_str(), f.type_name.as_str(), f.label, f.number))
+                .collect::<Vec<(&str, &str, ProtoLabel, i64)>>(),
+            vec![
+                ("note", "string", ProtoLabel::Optional, 1),
+                ("items", "Item", ProtoLabel::Repeated, 2),
+                ("prices", "Price", ProtoLabel::Singular, 3),
+                ("card", "Card", ProtoLabel::Singular, 4),
+                ("voucher", "string", ProtoLabel::Singular, 5),
+            ]
+        );
+        assert_eq!(fields[2].key_type.as_deref(), Some("string"));
+        assert_eq!(fields[3].oneof.as_deref(), Some("payment"));
+    }
+
+    #[test]
+    fn test_parses_streaming_rpcs() {
+        let file: ProtoFile = parse_protobuf(
+            "service Orders {\n  rpc Watch (stream WatchRequest) returns (stream Order) {}\n  rpc Get (GetRequest) returns (Order);\n}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.definitions[0],
+            ProtoDefinition::Service {
+                name: "Orders".to_string(),
+                methods: vec![
+                    ProtoMethod {
+                        name: "Watch".to_string(),
+                        request: "WatchRequest".to_string(),
+                        response: "Order".to_string(),
+                        client_streaming: true,
+                        server_streaming: true,
+                    },
+                    ProtoMethod {
+                        name: "Get".to_string(),
+                        request: "GetRequest".to_string(),
+                        response: "Order".to_string(),
+                        client_streaming: false,
+                        server_streaming: false,
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_reports_syntax_errors_with_position() {
+        match parse_protobuf("message Order {\n  string id = ;\n}") {
+            Err(ProtobufParseError::Syntax { line, .. }) => assert_eq!(line, 2),
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rejects_messages_nested_past_the_limit() {
+        let nested = |depth: usize| -> String {
+            format!(
+                "{}string id = 1;{}",
+                "message M {\n".repeat(depth),
+                "}".repeat(depth)
+            )
+        };
+        assert!(parse_protobuf(&nested(64)).is_ok());
+
+        match parse_protobuf(&nested(20_000)) {
+            Err(ProtobufParseError::Syntax {
+                message,
+                line,
+                column,
+            }) => {
+                assert_eq!(message, "Blocks nest past the depth limit of 64");
+                assert_eq!((line, column), (65, 11));
+            }
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+    }
+}
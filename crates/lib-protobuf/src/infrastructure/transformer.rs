@@ -0,0 +1,274 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::proto_ast::{
+    ProtoDefinition, ProtoEnumValue, ProtoField, ProtoFile, ProtoLabel, ProtoMethod,
+};
+
+pub struct GraphBuilder {
+    graph: Graph,
+    package: Option<String>,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::Class,
+                ..Default::default()
+            },
+            package: None,
+        }
+    }
+
+    pub fn build(mut self, file: ProtoFile) -> Graph {
+        if let Some(package) = &file.package {
+            self.graph.groups.insert(
+                Id::from(package.as_str()),
+                Group {
+                    id: Id::from(package.as_str()),
+                    label: Some(package.as_str().into()),
+                    ..Default::default()
+                },
+            );
+        }
+        self.package = file.package;
+
+        // Fields may use types declared further down, so all nodes go first
+        file.definitions
+            .iter()
+            .for_each(|definition: &ProtoDefinition| self.declare(definition, None));
+        file.definitions
+            .iter()
+            .for_each(|definition: &ProtoDefinition| self.relate(definition, None));
+        self.graph
+    }
+
+    fn declare(&mut self, definition: &ProtoDefinition, scope: Option<&str>) {
+        let (name, kind, members): (&String, NodeKind, Vec<Member>) = match definition {
+            ProtoDefinition::Message {
+                name,
+                fields,
+                nested,
+            } => {
+                let id: String = qualified(scope, name);
+                nested
+                    .iter()
+                    .for_each(|definition: &ProtoDefinition| self.declare(definition, Some(&id)));
+                (
+                    name,
+                    NodeKind::Entity,
+                    fields.iter().map(map_field).collect(),
+                )
+            }
+            ProtoDefinition::Enum { name, values } => (
+                name,
+                NodeKind::Enum,
+                values
+                    .iter()
+                    .map(|value: &ProtoEnumValue| Member {
+                        name: value.name.clone(),
+                        kind: MemberKind::EnumValue,
+                        data: HashMap::from([(
+                            "number".into(),
+                            Value::Number(value.number as f64),
+                        )]),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ProtoDefinition::Service { name, methods } => (
+                name,
+                NodeKind::Interface,
+                methods.iter().map(map_method).collect(),
+            ),
+        };
+
+        let id: String = qualified(scope, name);
+        let parent: Option<Id> = self.package.as_deref().map(Id::from);
+        if let Some(group) = parent.as_ref().and_then(|p| self.graph.groups.get_mut(p)) {
+            group.children.push(Id::from(id.as_str()));
+        }
+
+        self.graph.nodes.insert(
+            Id::from(id.as_str()),
+            Node {
+                id: Id::from(id.as_str()),
+                kind,
+                label: Some(id.as_str().into()),
+                members,
+                parent,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn relate(&mut self, definition: &ProtoDefinition, scope: Option<&str>) {
+        match definition {
+            ProtoDefinition::Message {
+                name,
+                fields,
+                nested,
+            } => {
+                let id: String = qualified(scope, name);
+
+                fields.iter().for_each(|field: &ProtoField| {
+                    let Some(target) = self.resolve(&field.type_name, Some(&id)) else {
+                        return;
+                    };
+                    let cardinality: &str =
+                        if field.key_type.is_some() || field.label == ProtoLabel::Repeated {
+                            "*"
+                        } else if field.label == ProtoLabel::Required {
+                            "1"
+                        } else {
+                            "0..1"
+                        };
+                    self.add_edge(
+                        &id,
+                        &target,
+                        EdgeKind::Association,
+                        Some(field.name.clone()),
+                        Some(cardinality.into()),
+                    );
+                });
+                // Nested types belong to the message declaring them
+                nested.iter().for_each(|definition: &ProtoDefinition| {
+                    let inner: String = qualified(Some(&id), definition.name());
+                    self.add_edge(&id, &inner, EdgeKind::Composition, None, None);
+                    self.relate(definition, Some(&id));
+                });
+            }
+            ProtoDefinition::Enum { .. } => {}
+            ProtoDefinition::Service { name, methods } => {
+                let mut targets: Vec<String> = Vec::new();
+                methods
+                    .iter()
+                    .flat_map(|method: &ProtoMethod| [&method.request, &method.response])
+                    .filter_map(|type_name: &String| self.resolve(type_name, None))
+                    .for_each(|target: String| {
+                        if !targets.contains(&target) {
+                            targets.push(target);
+                        }
+                    });
+
+                targets.iter().for_each(|target: &String| {
+                    self.add_edge(name, target, EdgeKind::Dependency, None, None);
+                });
+            }
+        }
+    }
+
+    fn add_edge(
+        &mut self,
+        from: &str,
+        to: &str,
+        kind: EdgeKind,
+        label: Option<String>,
+        to_cardinality: Option<Text>,
+    ) {
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id,
+                from: from.into(),
+                to: to.into(),
+                directed: true,
+                kind,
+                label: label.map(Text::from),
+                to_cardinality,
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Id of the type a name refers to, searching from the innermost scope
+    /// outwards as protoc does. Scalars and imported types resolve to nothing
+    fn resolve(&self, name: &str, scope: Option<&str>) -> Option<String> {
+        let name: &str = name.strip_prefix('.').unwrap_or(name);
+        let name: &str = match &self.package {
+            Some(package) => name
+                .strip_prefix(package.as_str())
+                .and_then(|rest: &str| rest.strip_prefix('.'))
+                .unwrap_or(name),
+            None => name,
+        };
+
+        let mut scope: Option<&str> = scope;
+        loop {
+            let candidate: String = qualified(scope, name);
+            if self.graph.nodes.contains_key(candidate.as_str()) {
+                return Some(candidate);
+            }
+            // Past the top level there is nowhere left to look
+            scope = scope?
+                .rsplit_once('.')
+                .map(|(outer, _): (&str, &str)| outer);
+        }
+    }
+}
+
+fn qualified(scope: Option<&str>, name: &str) -> String {
+    match scope {
+        Some(scope) => format!("{}.{}", scope, name),
+        None => name.to_string(),
+    }
+}
+
+fn map_field(field: &ProtoField) -> Member {
+    let mut data: HashMap<Text, Value> =
+        HashMap::from([("number".into(), Value::Number(field.number as f64))]);
+    if let Some(oneof) = &field.oneof {
+        data.insert("oneof".into(), Value::String(oneof.clone()));
+    }
+
+    let type_name: String = match (&field.key_type, field.label) {
+        (Some(key), _) => format!("map<{}, {}>", key, field.type_name),
+        (None, ProtoLabel::Repeated) => format!("repeated {}", field.type_name),
+        (None, ProtoLabel::Optional) => format!("optional {}", field.type_name),
+        (None, ProtoLabel::Required) => format!("required {}", field.type_name),
+        (None, ProtoLabel::Singular) => field.type_name.clone(),
+    };
+
+    Member {
+        name: field.name.clone(),
+        kind: MemberKind::Field,
+        type_name: Some(type_name),
+        data,
+    }
+}
+
+fn map_method(method: &ProtoMethod) -> Member {
+    let streamed = |streaming: bool, type_name: &str| -> String {
+        if streaming {
+            format!("stream {}", type_name)
+        } else {
+            type_name.to_string()
+        }
+    };
+
+    Member {
+        name: method.name.clone(),
+        kind: MemberKind::Method,
+        type_name: Some(streamed(method.server_streaming, &method.response)),
+        data: HashMap::from([(
+            "parameters".into(),
+            Value::List(vec![Value::String(streamed(
+                method.client_streaming,
+                &method.request,
+            ))]),
+        )]),
+    }
+}
@@ -0,0 +1,6 @@
+pub mod adapters;
+pub(crate) mod models;
+pub(crate) mod module_transformer;
+pub(crate) mod modules;
+pub(crate) mod parser;
+pub(crate) mod transformer;
@@ -0,0 +1,2 @@
+pub mod rust_graph_gateway;
+pub mod rust_module_graph_gateway;
@@ -0,0 +1,308 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, RustParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct RustGraphGateway;
+
+impl RustGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for RustGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_rust(input)
+            .map_err(GraphGatewayError::from)
+            .map(|items| transformer::GraphBuilder::new().build(items))
+    }
+}
+
+impl From<RustParseError> for GraphGatewayError {
+    fn from(err: RustParseError) -> Self {
+        match err {
+            RustParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "rust".into(),
+                message,
+                line,
+                column,
+            },
+            RustParseError::Semantic(msg) => GraphGatewayError::Semantic {
+                source: "rust".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            group::Group,
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::rust_graph_gateway::RustGraphGateway;
+
+    const SOURCE: &str = r#"
+        use std::collections::HashMap;
+
+        pub trait Entity {
+            fn id(&self) -> u64;
+        }
+
+        pub trait Payable: Entity {
+            fn total(&self) -> f64;
+            fn currency(&self) -> &str {
+                "EUR"
+            }
+        }
+
+        pub mod shop {
+            pub mod orders {
+                pub struct Order {
+                    pub id: u64,
+                    lines: Vec<OrderLine>,
+                    pub(crate) status: Status,
+                    notes: HashMap<String, String>,
+                }
+
+                pub struct OrderLine(pub u32, f64);
+
+                impl Order {
+                    pub fn new(id: u64) -> Self {
+                        todo!()
+                    }
+
+                    fn cancel(&mut self, reason: &str) -> Result<(), String> {
+                        todo!()
+                    }
+                }
+            }
+
+            pub enum Status {
+                Open,
+                Closed { by: Option<orders::Order> },
+            }
+        }
+
+        impl Entity for shop::orders::Order {
+            fn id(&self) -> u64 {
+                self.id
+            }
+        }
+
+        impl std::fmt::Debug for shop::Status {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                todo!()
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            struct Fixture;
+        }
+    "#;
+
+    async fn parse_source() -> Graph {
+        RustGraphGateway::new()
+            .read_graph_from_raw_input(SOURCE)
+            .await
+            .expect("Failed to parse valid Rust")
+    }
+
+    #[test]
+    fn test_parse_types_and_modules() {
+        smol::block_on(async {
+            let graph: Graph = parse_source().await;
+
+            assert_eq!(graph.kind, GraphKind::Class);
+            assert_eq!(graph.nodes.len(), 5, "Test modules are left out");
+            assert_eq!(graph.groups.len(), 2);
+
+            let orders: &Group = graph
+                .groups
+                .get("shop::orders")
+                .expect("Missing orders module");
+            assert_eq!(orders.label.as_deref(), Some("orders"));
+            assert_eq!(orders.parent.as_deref(), Some("shop"));
+            assert_eq!(
+                orders.children,
+                vec![
+                    "shop::orders::Order".to_string(),
+                    "shop::orders::OrderLine".to_string()
+                ]
+            );
+
+            assert_eq!(
+                graph.nodes.get("Payable").map(|n: &Node| &n.kind),
+                Some(&NodeKind::Interface)
+            );
+
+            let status: &Node = graph.nodes.get("shop::Status").expect("Missing Status");
+            assert_eq!(status.kind, NodeKind::Enum);
+            assert_eq!(status.parent.as_deref(), Some("shop"));
+            assert_eq!(
+                status
+                    .members
+                    .iter()
+                    .map(|m: &Member| m.name.as_str())
+                    .collect::<Vec<&str>>(),
+                vec!["Open", "Closed"]
+            );
+            assert_eq!(
+                status.data.get("traits"),
+                Some(&Value::List(vec![Value::String("Debug".to_string())]))
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_fields_and_methods() {
+        smol::block_on(async {
+            let graph: Graph = parse_source().await;
+            let order: &Node = graph
+                .nodes
+                .get("shop::orders::Order")
+                .expect("Missing Order");
+
+            assert_eq!(
+                order
+                    .members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), m.kind.clone()))
+                    .collect::<Vec<(&str, MemberKind)>>(),
+                vec![
+                    ("id", MemberKind::Field),
+                    ("lines", MemberKind::Field),
+                    ("status", MemberKind::Field),
+                    ("notes", MemberKind::Field),
+                    ("new", MemberKind::Method),
+                    ("cancel", MemberKind::Method),
+                ],
+                "Trait impls do not repeat their methods"
+            );
+
+            let notes: &Member = &order.members[3];
+            assert_eq!(notes.type_name.as_deref(), Some("HashMap<String, String>"));
+            assert_eq!(
+                notes.data.get("visibility"),
+                Some(&Value::String("-".to_string()))
+            );
+            assert_eq!(
+                order.members[2].data.get("visibility"),
+                Some(&Value::String("~".to_string()))
+            );
+
+            let new: &Member = &order.members[4];
+            assert_eq!(new.data.get("static"), Some(&Value::Bool(true)));
+            assert_eq!(new.type_name.as_deref(), Some("Self"));
+
+            let cancel: &Member = &order.members[5];
+            assert_eq!(cancel.data.get("static"), None);
+            assert_eq!(
+                cancel.data.get("parameters"),
+                Some(&Value::List(vec![Value::String(
+                    "reason: &str".to_string()
+                )]))
+            );
+
+            let payable: &Node = graph.nodes.get("Payable").expect("Missing Payable");
+            assert_eq!(
+                payable
+                    .members
+                    .iter()
+                    .map(|m: &Member| m.data.contains_key("abstract"))
+                    .collect::<Vec<bool>>(),
+                vec![true, false]
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_relationships() {
+        smol::block_on(async {
+            let graph: Graph = parse_source().await;
+
+            assert_eq!(
+                find_edge(&graph, "Payable", "Entity").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Inheritance)
+            );
+            assert_eq!(
+                find_edge(&graph, "shop::orders::Order", "Entity").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Realization)
+            );
+
+            let lines: &Edge = find_edge(&graph, "shop::orders::Order", "shop::orders::OrderLine")
+                .expect("Missing lines association");
+            assert_eq!(lines.kind, EdgeKind::Association);
+            assert_eq!(lines.label.as_deref(), Some("lines"));
+
+            assert_eq!(
+                find_edge(&graph, "shop::orders::Order", "shop::Status")
+                    .and_then(|e: &Edge| e.label.as_deref()),
+                Some("status")
+            );
+            assert_eq!(
+                find_edge(&graph, "shop::Status", "shop::orders::Order")
+                    .and_then(|e: &Edge| e.label.as_deref()),
+                Some("Closed")
+            );
+            assert_eq!(graph.edges.len(), 5);
+        });
+    }
+
+    #[test]
+    fn test_syntax_errors_are_reported_with_location() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = RustGraphGateway::new()
+                .read_graph_from_raw_input("struct User {\n    name String,\n}")
+                .await;
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Parse { ref source, line: 2, .. }) if source == "rust"),
+                "Expected a Rust parse error, got {:?}",
+                result
+            );
+        });
+    }
+
+    #[test]
+    fn test_source_without_types_is_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = RustGraphGateway::new()
+                .read_graph_from_raw_input("fn main() {}")
+                .await;
+
+            assert!(matches!(result, Err(GraphGatewayError::Semantic { .. })));
+        });
+    }
+
+    fn find_edge<'a>(graph: &'a Graph, from: &str, to: &str) -> Option<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .find(|e: &&Edge| e.from == from && e.to == to)
+    }
+}
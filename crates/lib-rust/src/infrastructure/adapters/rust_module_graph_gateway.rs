@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::{module_transformer, modules};
+
+/// Draws how the modules of a crate depend on each other through their `use`
+/// declarations, taking the path to the crate, or to its root file, as input
+#[derive(Default)]
+pub struct RustModuleGraphGateway;
+
+impl RustModuleGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for RustModuleGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        let root: PathBuf =
+            crate_root(Path::new(input.trim())).ok_or_else(|| GraphGatewayError::Semantic {
+                source: "rust".into(),
+                message: format!("No src/lib.rs or src/main.rs found in {}", input.trim()),
+            })?;
+
+        modules::read_modules(&root, &|file: &Path| std::fs::read_to_string(file))
+            .map_err(GraphGatewayError::from)
+            .map(|modules| module_transformer::ModuleGraphBuilder::new().build(modules))
+    }
+}
+
+/// The root file of a crate, given either it or the crate's directory
+fn crate_root(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    ["src/lib.rs", "src/main.rs", "lib.rs", "main.rs"]
+        .into_iter()
+        .map(|file: &str| path.join(file))
+        .find(|file: &PathBuf| file.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            group::Group,
+            id::Id,
+            node::Node,
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+    use std::path::{Path, PathBuf};
+
+    use crate::infrastructure::adapters::rust_module_graph_gateway::RustModuleGraphGateway;
+
+    /// Writes a crate into a fresh temporary directory
+    fn write_crate(files: &[(&str, &str)]) -> PathBuf {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("diagrama-rust-{}", uuid::Uuid::new_v4()));
+        files.iter().for_each(|(file, source): &(&str, &str)| {
+            let path: PathBuf = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        });
+        dir
+    }
+
+    fn read(dir: &Path) -> Result<Graph, GraphGatewayError> {
+        let result: Result<Graph, GraphGatewayError> = smol::block_on(
+            RustModuleGraphGateway::new().read_graph_from_raw_input(&dir.display().to_string()),
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+        result
+    }
+
+    fn edge<'a>(graph: &'a Graph, from: &str, to: &str) -> Option<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .find(|edge: &&Edge| edge.from == from && edge.to == to)
+    }
+
+    #[test]
+    fn test_uses_between_modules_become_dependencies() {
+        let graph: Graph = read(&write_crate(&[
+            (
+                "src/lib.rs",
+                "pub mod domain;\npub mod infrastructure;\nuse std::sync::Arc;",
+            ),
+            ("src/domain.rs", "pub mod order;\npub use order::Order;"),
+            ("src/domain/order.rs", "pub struct Order;"),
+            (
+                "src/infrastructure/mod.rs",
+                "pub mod db;\nmod web { use super::db::*; }",
+            ),
+            (
+                "src/infrastructure/db.rs",
+                "use crate::domain::{order::{self, Order}, Repository};\nuse serde::Serialize;",
+            ),
+        ]))
+        .unwrap();
+
+        assert_eq!(graph.kind, GraphKind::Generic);
+        let mut ids: Vec<&str> = graph.nodes.keys().map(Id::as_str).collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            vec![
+                "crate",
+                "crate::domain",
+                "crate::domain::order",
+                "crate::infrastructure",
+                "crate::infrastructure::db",
+                "crate::infrastructure::web",
+            ]
+        );
+
+        let db: &Node = &graph.nodes["crate::infrastructure::db"];
+        assert_eq!(db.label.as_deref(), Some("db"));
+        assert!(matches!(
+            db.data.get("file"),
+            Some(Value::String(file)) if file.ends_with("db.rs")
+        ));
+
+        assert_eq!(graph.edges.len(), 4);
+        assert_eq!(
+            edge(&graph, "crate::infrastructure::db", "crate::domain::order")
+                .map(|e: &Edge| &e.data["imports"]),
+            Some(&Value::List(vec![
+                Value::String("*".to_string()),
+                Value::String("Order".to_string())
+            ]))
+        );
+        assert_eq!(
+            edge(&graph, "crate::infrastructure::db", "crate::domain")
+                .map(|e: &Edge| &e.data["imports"]),
+            Some(&Value::List(vec![Value::String("Repository".to_string())]))
+        );
+        assert_eq!(
+            edge(
+                &graph,
+                "crate::infrastructure::web",
+                "crate::infrastructure::db"
+            )
+            .map(|e: &Edge| e.kind.clone()),
+            Some(EdgeKind::Dependency)
+        );
+        assert!(edge(&graph, "crate::domain", "crate::domain::order").is_some());
+    }
+
+    #[test]
+    fn test_modules_are_clustered_by_their_path() {
+        let graph: Graph = read(&write_crate(&[
+            ("src/main.rs", "mod app;\nmod cli;"),
+            ("src/app.rs", "mod state;\nmod ui { mod theme {} }"),
+            ("src/app/state.rs", ""),
+            ("src/cli.rs", ""),
+        ]))
+        .unwrap();
+
+        let cluster = |module: &str| -> &Group {
+            &graph.groups[graph.nodes[module]
+                .parent
+                .as_ref()
+                .expect("Missing cluster")]
+        };
+
+        assert_eq!(graph.nodes["crate"].parent, None);
+        assert_eq!(graph.nodes["crate::cli"].parent, None);
+        assert_eq!(cluster("crate::app").label.as_deref(), Some("app"));
+        assert_eq!(cluster("crate::app::state").label.as_deref(), Some("app"));
+
+        let ui: &Group = cluster("crate::app::ui::theme");
+        assert_eq!(ui.label.as_deref(), Some("app::ui"));
+        assert_eq!(
+            ui.children,
+            vec![
+                Id::from("crate::app::ui"),
+                Id::from("crate::app::ui::theme")
+            ]
+        );
+        assert_eq!(
+            ui.parent.as_ref(),
+            graph.nodes["crate::app"].parent.as_ref()
+        );
+    }
+
+    #[test]
+    fn test_missing_module_files_are_reported() {
+        match read(&write_crate(&[("src/lib.rs", "mod missing;")])) {
+            Err(GraphGatewayError::Semantic { source, message }) => {
+                assert_eq!(source, "rust");
+                assert!(
+                    message.starts_with("Cannot find the file of module `crate::missing`"),
+                    "Unexpected message {}",
+                    message
+                );
+            }
+            other => panic!("Expected a semantic error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_syntax_errors_name_their_file() {
+        match read(&write_crate(&[
+            ("src/lib.rs", "mod broken;"),
+            ("src/broken.rs", "\nfn ("),
+        ])) {
+            Err(GraphGatewayError::Parse { message, line, .. }) => {
+                assert!(
+                    message.contains("broken.rs: "),
+                    "Unexpected message {}",
+                    message
+                );
+                assert_eq!(line, 2);
+            }
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+}
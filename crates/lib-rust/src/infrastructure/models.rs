@@ -0,0 +1,2 @@
+pub(crate) mod rust_item;
+pub(crate) mod rust_module;
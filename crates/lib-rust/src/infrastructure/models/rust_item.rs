@@ -0,0 +1,59 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum RustItem {
+    /// An inline `mod name { .. }`, `path` joining the names of its ancestors
+    Module {
+        path: String,
+        parent: Option<String>,
+    },
+    Type {
+        name: String,
+        kind: RustTypeKind,
+        module: Option<String>,
+        visibility: Option<String>,
+        fields: Vec<RustField>,
+        variants: Vec<RustVariant>,
+        methods: Vec<RustMethod>,
+        /// Traits a trait requires, such as `Entity` in `trait Order: Entity`
+        supertraits: Vec<String>,
+    },
+    Impl {
+        self_type: String,
+        trait_name: Option<String>,
+        methods: Vec<RustMethod>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RustTypeKind {
+    Struct,
+    Enum,
+    Union,
+    Trait,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RustField {
+    pub name: String,
+    pub type_name: String,
+    pub visibility: Option<String>,
+    /// Names of the types the field's type mentions, outermost first
+    pub references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RustVariant {
+    pub name: String,
+    pub references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RustMethod {
+    pub name: String,
+    pub visibility: Option<String>,
+    pub parameters: Vec<String>,
+    pub returns: Option<String>,
+    /// Takes no `self`
+    pub is_static: bool,
+    /// A trait method without a default body
+    pub is_abstract: bool,
+}
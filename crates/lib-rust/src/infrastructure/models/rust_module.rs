@@ -0,0 +1,12 @@
+/// A module of a crate, inline or read from its own file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RustModule {
+    /// Names joined from the crate root, as in `crate::infrastructure::parser`
+    pub path: String,
+    pub parent: Option<String>,
+    /// File the module is read from, missing for inline modules
+    pub file: Option<String>,
+    /// Paths of its `use` declarations, one per imported name, as in
+    /// `["super", "models", "Order"]`. Glob imports end at their module
+    pub uses: Vec<Vec<String>>,
+}
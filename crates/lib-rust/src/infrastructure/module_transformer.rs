@@ -0,0 +1,215 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::infrastructure::models::rust_module::RustModule;
+
+const ROOT: &str = "crate";
+
+pub struct ModuleGraphBuilder {
+    graph: Graph,
+    clusters: HashMap<String, Id>, // Maps module paths to the groups holding their submodules
+    parents: HashMap<String, String>,
+}
+
+impl ModuleGraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::Generic,
+                ..Default::default()
+            },
+            clusters: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+
+    /// Draws a node per module, clustered by their parents, and an edge for
+    /// each module another one imports from
+    pub fn build(mut self, modules: Vec<RustModule>) -> Graph {
+        let paths: HashSet<&str> = modules
+            .iter()
+            .map(|module: &RustModule| module.path.as_str())
+            .collect();
+        self.parents = modules
+            .iter()
+            .filter_map(|module: &RustModule| Some((module.path.clone(), module.parent.clone()?)))
+            .collect();
+
+        modules
+            .iter()
+            .for_each(|module: &RustModule| self.add_module(module));
+
+        modules.iter().for_each(|module: &RustModule| {
+            // Names imported from each module, in the order they are used
+            let mut imports: Vec<(&str, Vec<String>)> = Vec::new();
+            module.uses.iter().for_each(|path: &Vec<String>| {
+                let Some((target, rest)) = resolve(&module.path, path, &paths) else {
+                    return;
+                };
+                if target == module.path {
+                    return;
+                }
+                let name: String = match rest.is_empty() {
+                    true => "*".to_string(),
+                    false => rest.join("::"),
+                };
+                match imports
+                    .iter_mut()
+                    .find(|(t, _): &&mut (&str, Vec<String>)| *t == target)
+                {
+                    Some((_, names)) => names.push(name),
+                    None => imports.push((target, vec![name])),
+                }
+            });
+
+            imports
+                .into_iter()
+                .for_each(|(target, names): (&str, Vec<String>)| {
+                    let edge_id: Id = Uuid::new_v4().to_string().into();
+                    self.graph.edges.insert(
+                        edge_id.clone(),
+                        Edge {
+                            id: edge_id,
+                            from: Id::from(module.path.as_str()),
+                            to: Id::from(target),
+                            directed: true,
+                            kind: EdgeKind::Dependency,
+                            data: HashMap::from([(
+                                "imports".into(),
+                                Value::List(names.into_iter().map(Value::String).collect()),
+                            )]),
+                            order: self.graph.edges.len(),
+                            ..Default::default()
+                        },
+                    );
+                });
+        });
+
+        self.graph
+    }
+
+    fn add_module(&mut self, module: &RustModule) {
+        // Modules with submodules are drawn inside a cluster holding them all,
+        // the crate root being left out as it would hold everything
+        let has_children: bool = self
+            .parents
+            .values()
+            .any(|parent: &String| *parent == module.path);
+        let clustered: Option<&str> = match has_children && module.path != ROOT {
+            true => Some(module.path.as_str()),
+            false => module
+                .parent
+                .as_deref()
+                .filter(|parent: &&str| *parent != ROOT),
+        };
+        let parent: Option<Id> = clustered.map(|path: &str| self.cluster(path));
+        if let Some(group) = parent
+            .as_ref()
+            .and_then(|p: &Id| self.graph.groups.get_mut(p))
+        {
+            group.children.push(Id::from(module.path.as_str()));
+        }
+
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        if let Some(file) = &module.file {
+            data.insert("file".into(), Value::String(file.clone()));
+        }
+
+        self.graph.nodes.insert(
+            Id::from(module.path.as_str()),
+            Node {
+                id: Id::from(module.path.as_str()),
+                kind: NodeKind::Component,
+                label: module.path.rsplit("::").next().map(Text::from),
+                data,
+                parent,
+                ..Default::default()
+            },
+        );
+    }
+
+    /// The group clustering a module with its submodules, nested in the one
+    /// of its own parent
+    fn cluster(&mut self, path: &str) -> Id {
+        if let Some(group_id) = self.clusters.get(path) {
+            return group_id.clone();
+        }
+
+        let group_id: Id = Uuid::new_v4().to_string().into();
+        let outer: Option<Id> = self
+            .parents
+            .get(path)
+            .filter(|parent: &&String| *parent != ROOT)
+            .cloned()
+            .map(|parent: String| self.cluster(&parent));
+        if let Some(group) = outer
+            .as_ref()
+            .and_then(|p: &Id| self.graph.groups.get_mut(p))
+        {
+            group.children.push(group_id.clone());
+        }
+
+        self.graph.groups.insert(
+            group_id.clone(),
+            Group {
+                id: group_id.clone(),
+                label: Some(path.strip_prefix("crate::").unwrap_or(path).into()),
+                parent: outer,
+                ..Default::default()
+            },
+        );
+        self.clusters.insert(path.to_string(), group_id.clone());
+        group_id
+    }
+}
+
+/// Finds the module a use path imports from, with the rest of the path
+/// naming what it imports. Paths into other crates resolve to nothing
+fn resolve<'a, 'p>(
+    module: &str,
+    path: &'p [String],
+    modules: &HashSet<&'a str>,
+) -> Option<(&'a str, &'p [String])> {
+    let (mut current, mut rest): (String, &[String]) = match path.first()?.as_str() {
+        "crate" => (ROOT.to_string(), &path[1..]),
+        "self" => (module.to_string(), &path[1..]),
+        "super" => {
+            let supers: usize = path
+                .iter()
+                .take_while(|segment: &&String| *segment == "super")
+                .count();
+            let mut ancestor: &str = module;
+            for _ in 0..supers {
+                ancestor = ancestor.rsplit_once("::")?.0;
+            }
+            (ancestor.to_string(), &path[supers..])
+        }
+        // Otherwise the path starts at a submodule, or in another crate
+        first if modules.contains(format!("{}::{}", module, first).as_str()) => {
+            (module.to_string(), path)
+        }
+        _ => return None,
+    };
+
+    while let Some(segment) = rest.first() {
+        let child: String = format!("{}::{}", current, segment);
+        if !modules.contains(child.as_str()) {
+            break;
+        }
+        current = child;
+        rest = &rest[1..];
+    }
+
+    modules
+        .get(current.as_str())
+        .map(|found: &&str| (*found, rest))
+}
@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+
+use syn::{Item, UseTree};
+
+use crate::infrastructure::{
+    models::rust_module::RustModule,
+    parser::{self, RustParseError},
+};
+
+/// Reads the modules of a crate from its root file, following `mod name;`
+/// declarations into the files holding them, as `rustc` would
+pub fn read_modules(
+    root: &Path,
+    read: &dyn Fn(&Path) -> std::io::Result<String>,
+) -> Result<Vec<RustModule>, RustParseError> {
+    let mut reader: ModuleReader = ModuleReader {
+        read,
+        modules: Vec::new(),
+    };
+    let dir: &Path = root.parent().unwrap_or(Path::new(""));
+    reader.read_file(&[root.to_path_buf()], "crate", None, dir)?;
+
+    Ok(reader.modules)
+}
+
+struct ModuleReader<'a> {
+    read: &'a dyn Fn(&Path) -> std::io::Result<String>,
+    modules: Vec<RustModule>,
+}
+
+impl ModuleReader<'_> {
+    /// Reads the first of the candidate files that exists, `children` holding
+    /// the files of its submodules
+    fn read_file(
+        &mut self,
+        candidates: &[PathBuf],
+        path: &str,
+        parent: Option<&str>,
+        children: &Path,
+    ) -> Result<(), RustParseError> {
+        let (file, source): (&PathBuf, String) = candidates
+            .iter()
+            .find_map(|file: &PathBuf| (self.read)(file).ok().map(|source: String| (file, source)))
+            .ok_or_else(|| {
+                RustParseError::Semantic(format!(
+                    "Cannot find the file of module `{}`, looked for {}",
+                    path,
+                    candidates
+                        .iter()
+                        .map(|file: &PathBuf| file.display().to_string())
+                        .collect::<Vec<String>>()
+                        .join(" and ")
+                ))
+            })?;
+        let syntax: syn::File = syn::parse_file(&source).map_err(|err: syn::Error| {
+            match RustParseError::from(err) {
+                RustParseError::Syntax {
+                    message,
+                    line,
+                    column,
+                } => RustParseError::Syntax {
+                    message: format!("{}: {}", file.display(), message),
+                    line,
+                    column,
+                },
+                other => other,
+            }
+        })?;
+
+        self.modules.push(RustModule {
+            path: path.to_string(),
+            parent: parent.map(str::to_string),
+            file: Some(file.display().to_string()),
+            uses: Vec::new(),
+        });
+
+        let base: &Path = file.parent().unwrap_or(Path::new(""));
+        self.visit(&syntax.items, path, children, base)
+    }
+
+    /// Collects the uses and submodules of a module, `dir` holding the files
+    /// of its submodules and `base` being where `#[path]` attributes start
+    fn visit(
+        &mut self,
+        items: &[Item],
+        path: &str,
+        dir: &Path,
+        base: &Path,
+    ) -> Result<(), RustParseError> {
+        items.iter().try_for_each(|item: &Item| match item {
+            Item::Use(declaration) if declaration.leading_colon.is_none() => {
+                let mut uses: Vec<Vec<String>> = Vec::new();
+                use_paths(&declaration.tree, &mut Vec::new(), &mut uses);
+                if let Some(module) = self
+                    .modules
+                    .iter_mut()
+                    .find(|module: &&mut RustModule| module.path == path)
+                {
+                    module.uses.extend(uses);
+                }
+                Ok(())
+            }
+            Item::Mod(module) if !parser::is_test_module(module) => {
+                let name: String = module.ident.to_string();
+                let child: String = format!("{}::{}", path, name);
+                let location: Option<PathBuf> =
+                    path_attribute(module).map(|file: String| base.join(file));
+
+                match &module.content {
+                    Some((_, content)) => {
+                        self.modules.push(RustModule {
+                            path: child.clone(),
+                            parent: Some(path.to_string()),
+                            ..Default::default()
+                        });
+                        let nested: PathBuf = location.unwrap_or_else(|| dir.join(&name));
+                        self.visit(content, &child, &nested, &nested)
+                    }
+                    // Files chosen with `#[path]` keep their submodules beside them
+                    None => match location {
+                        Some(file) => {
+                            let beside: PathBuf =
+                                file.parent().map(Path::to_path_buf).unwrap_or_default();
+                            self.read_file(&[file], &child, Some(path), &beside)
+                        }
+                        None => self.read_file(
+                            &[
+                                dir.join(format!("{}.rs", name)),
+                                dir.join(&name).join("mod.rs"),
+                            ],
+                            &child,
+                            Some(path),
+                            &dir.join(&name),
+                        ),
+                    },
+                }
+            }
+            _ => Ok(()),
+        })
+    }
+}
+
+/// Flattens a use tree into one path per imported name, as in `a::{b, c::*}`
+/// giving `a::b` and `a::c`
+fn use_paths(tree: &UseTree, prefix: &mut Vec<String>, output: &mut Vec<Vec<String>>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            use_paths(&path.tree, prefix, output);
+            prefix.pop();
+        }
+        UseTree::Name(name) if name.ident == "self" => output.push(prefix.clone()),
+        UseTree::Name(name) => output.push([prefix.as_slice(), &[name.ident.to_string()]].concat()),
+        UseTree::Rename(rename) => {
+            output.push([prefix.as_slice(), &[rename.ident.to_string()]].concat())
+        }
+        UseTree::Glob(_) => output.push(prefix.clone()),
+        UseTree::Group(group) => group
+            .items
+            .iter()
+            .for_each(|tree: &UseTree| use_paths(tree, prefix, output)),
+    }
+}
+
+/// The file a `#[path = "file.rs"]` attribute points a module at
+fn path_attribute(module: &syn::ItemMod) -> Option<String> {
+    module.attrs.iter().find_map(|attribute: &syn::Attribute| {
+        let syn::Meta::NameValue(pair) = &attribute.meta else {
+            return None;
+        };
+        match &pair.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(file),
+                ..
+            }) if pair.path.is_ident("path") => Some(file.value()),
+            _ => None,
+        }
+    })
+}
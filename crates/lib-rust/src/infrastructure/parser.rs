@@ -0,0 +1,386 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    FnArg, GenericArgument, ImplItem, Item, PathArguments, ReturnType, Signature, TraitItem, Type,
+    TypeParamBound, Visibility,
+};
+
+use crate::infrastructure::models::rust_item::{
+    RustField, RustItem, RustMethod, RustTypeKind, RustVariant,
+};
+
+pub fn parse_rust(input: &str) -> Result<Vec<RustItem>, RustParseError> {
+    let file: syn::File = syn::parse_file(input).map_err(RustParseError::from)?;
+
+    let mut items: Vec<RustItem> = Vec::new();
+    visit(&file.items, None, &mut items);
+
+    if !items
+        .iter()
+        .any(|item: &RustItem| matches!(item, RustItem::Type { .. }))
+    {
+        return Err(RustParseError::Semantic(
+            "Source declares no structs, enums or traits".to_string(),
+        ));
+    }
+
+    Ok(items)
+}
+
+fn visit(items: &[Item], module: Option<&str>, output: &mut Vec<RustItem>) {
+    items.iter().for_each(|item: &Item| match item {
+        Item::Struct(item) => output.push(RustItem::Type {
+            name: item.ident.to_string(),
+            kind: RustTypeKind::Struct,
+            module: module.map(str::to_string),
+            visibility: visibility(&item.vis),
+            fields: fields(item.fields.iter()),
+            variants: Vec::new(),
+            methods: Vec::new(),
+            supertraits: Vec::new(),
+        }),
+        Item::Union(item) => output.push(RustItem::Type {
+            name: item.ident.to_string(),
+            kind: RustTypeKind::Union,
+            module: module.map(str::to_string),
+            visibility: visibility(&item.vis),
+            fields: fields(item.fields.named.iter()),
+            variants: Vec::new(),
+            methods: Vec::new(),
+            supertraits: Vec::new(),
+        }),
+        Item::Enum(item) => output.push(RustItem::Type {
+            name: item.ident.to_string(),
+            kind: RustTypeKind::Enum,
+            module: module.map(str::to_string),
+            visibility: visibility(&item.vis),
+            fields: Vec::new(),
+            variants: item
+                .variants
+                .iter()
+                .map(|variant: &syn::Variant| RustVariant {
+                    name: variant.ident.to_string(),
+                    references: variant
+                        .fields
+                        .iter()
+                        .flat_map(|field: &syn::Field| references(&field.ty))
+                        .collect(),
+                })
+                .collect(),
+            methods: Vec::new(),
+            supertraits: Vec::new(),
+        }),
+        Item::Trait(item) => output.push(RustItem::Type {
+            name: item.ident.to_string(),
+            kind: RustTypeKind::Trait,
+            module: module.map(str::to_string),
+            visibility: visibility(&item.vis),
+            fields: Vec::new(),
+            variants: Vec::new(),
+            methods: item
+                .items
+                .iter()
+                .filter_map(|member: &TraitItem| match member {
+                    TraitItem::Fn(function) => {
+                        Some(method(&function.sig, None, function.default.is_none()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            supertraits: item
+                .supertraits
+                .iter()
+                .filter_map(|bound: &TypeParamBound| match bound {
+                    TypeParamBound::Trait(bound) => last_segment(&bound.path),
+                    _ => None,
+                })
+                .collect(),
+        }),
+        Item::Impl(item) => {
+            let Some(self_type) = base_name(&item.self_ty) else {
+                return;
+            };
+            output.push(RustItem::Impl {
+                self_type,
+                trait_name: item
+                    .trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| last_segment(path)),
+                methods: item
+                    .items
+                    .iter()
+                    .filter_map(|member: &ImplItem| match member {
+                        ImplItem::Fn(function) => {
+                            Some(method(&function.sig, visibility(&function.vis), false))
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+            });
+        }
+        Item::Mod(item) if !is_test_module(item) => {
+            let Some((_, content)) = &item.content else {
+                return;
+            };
+            let path: String = match module {
+                Some(parent) => format!("{}::{}", parent, item.ident),
+                None => item.ident.to_string(),
+            };
+            output.push(RustItem::Module {
+                path: path.clone(),
+                parent: module.map(str::to_string),
+            });
+            visit(content, Some(&path), output);
+        }
+        _ => {}
+    });
+}
+
+fn fields<'a>(fields: impl Iterator<Item = &'a syn::Field>) -> Vec<RustField> {
+    fields
+        .enumerate()
+        .map(|(index, field): (usize, &syn::Field)| RustField {
+            name: field
+                .ident
+                .as_ref()
+                .map_or_else(|| index.to_string(), |ident: &syn::Ident| ident.to_string()),
+            type_name: tokens_text(field.ty.to_token_stream()),
+            visibility: visibility(&field.vis),
+            references: references(&field.ty),
+        })
+        .collect()
+}
+
+fn method(signature: &Signature, visibility: Option<String>, is_abstract: bool) -> RustMethod {
+    RustMethod {
+        name: signature.ident.to_string(),
+        visibility,
+        parameters: signature
+            .inputs
+            .iter()
+            .filter_map(|input: &FnArg| match input {
+                FnArg::Typed(parameter) => Some(format!(
+                    "{}: {}",
+                    tokens_text(parameter.pat.to_token_stream()),
+                    tokens_text(parameter.ty.to_token_stream())
+                )),
+                FnArg::Receiver(_) => None,
+            })
+            .collect(),
+        returns: match &signature.output {
+            ReturnType::Type(_, ty) => Some(tokens_text(ty.to_token_stream())),
+            ReturnType::Default => None,
+        },
+        is_static: signature.receiver().is_none(),
+        is_abstract,
+    }
+}
+
+/// Names of the types a type mentions, such as `Vec` and `Order` in
+/// `Vec<Order>`
+fn references(ty: &Type) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    collect_references(ty, &mut names);
+    names
+}
+
+fn collect_references(ty: &Type, names: &mut Vec<String>) {
+    match ty {
+        Type::Path(path) => {
+            if let Some(qself) = &path.qself {
+                collect_references(&qself.ty, names);
+            }
+            collect_path(&path.path, names);
+        }
+        Type::Reference(reference) => collect_references(&reference.elem, names),
+        Type::Ptr(pointer) => collect_references(&pointer.elem, names),
+        Type::Slice(slice) => collect_references(&slice.elem, names),
+        Type::Array(array) => collect_references(&array.elem, names),
+        Type::Paren(paren) => collect_references(&paren.elem, names),
+        Type::Group(group) => collect_references(&group.elem, names),
+        Type::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .for_each(|elem: &Type| collect_references(elem, names)),
+        Type::TraitObject(object) => object.bounds.iter().for_each(|bound: &TypeParamBound| {
+            if let TypeParamBound::Trait(bound) = bound {
+                collect_path(&bound.path, names);
+            }
+        }),
+        Type::ImplTrait(object) => object.bounds.iter().for_each(|bound: &TypeParamBound| {
+            if let TypeParamBound::Trait(bound) = bound {
+                collect_path(&bound.path, names);
+            }
+        }),
+        _ => {}
+    }
+}
+
+fn collect_path(path: &syn::Path, names: &mut Vec<String>) {
+    names.extend(last_segment(path));
+    path.segments
+        .iter()
+        .for_each(|segment: &syn::PathSegment| match &segment.arguments {
+            PathArguments::AngleBracketed(arguments) => {
+                arguments
+                    .args
+                    .iter()
+                    .for_each(|argument: &GenericArgument| {
+                        if let GenericArgument::Type(ty) = argument {
+                            collect_references(ty, names);
+                        }
+                    })
+            }
+            PathArguments::Parenthesized(arguments) => {
+                arguments
+                    .inputs
+                    .iter()
+                    .for_each(|input: &Type| collect_references(input, names));
+                if let ReturnType::Type(_, output) = &arguments.output {
+                    collect_references(output, names);
+                }
+            }
+            PathArguments::None => {}
+        });
+}
+
+/// The type an impl block is for, without generics or module path
+fn base_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => last_segment(&path.path),
+        Type::Reference(reference) => base_name(&reference.elem),
+        Type::Paren(paren) => base_name(&paren.elem),
+        Type::Group(group) => base_name(&group.elem),
+        _ => None,
+    }
+}
+
+fn last_segment(path: &syn::Path) -> Option<String> {
+    path.segments
+        .last()
+        .map(|segment: &syn::PathSegment| segment.ident.to_string())
+}
+
+/// Maps Rust visibility onto the class diagram symbols: `pub` is public,
+/// restricted `pub(..)` is package-private and the rest private
+fn visibility(visibility: &Visibility) -> Option<String> {
+    Some(
+        match visibility {
+            Visibility::Public(_) => "+",
+            Visibility::Restricted(_) => "~",
+            Visibility::Inherited => "-",
+        }
+        .to_string(),
+    )
+}
+
+pub(crate) fn is_test_module(module: &syn::ItemMod) -> bool {
+    module.attrs.iter().any(|attribute: &syn::Attribute| {
+        attribute.path().is_ident("cfg")
+            && tokens_text(attribute.meta.to_token_stream()).contains("test")
+    })
+}
+
+/// Prints tokens the way they are usually written, e.g. `Vec<&'a str>`
+/// rather than `Vec < & 'a str >`
+fn tokens_text(tokens: TokenStream) -> String {
+    let text: String = tokens.to_string();
+    let mut output: String = String::new();
+    let mut previous: &str = "";
+
+    text.split_whitespace().for_each(|token: &str| {
+        let spaced: bool = matches!(
+            previous,
+            "," | ";" | "->" | "+" | "=" | ":" | "mut" | "dyn" | "impl" | "const"
+        ) || matches!(token, "->" | "+" | "=")
+            || (starts_word(previous) && starts_word(token));
+        if spaced && !output.is_empty() {
+            output.push(' ');
+        }
+        output.push_str(token);
+        previous = token;
+    });
+    output
+}
+
+fn starts_word(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .is_some_and(|c: char| c.is_alphanumeric() || c == '_' || c == '\'')
+}
+
+#[derive(Debug)]
+pub enum RustParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Semantic(String),
+}
+
+impl From<syn::Error> for RustParseError {
+    fn from(err: syn::Error) -> Self {
+        let start: proc_macro2::LineColumn = err.span().start();
+
+        RustParseError::Syntax {
+            message: err.to_string(),
+            line: start.line,
+            column: start.column + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn field_type(source: &str) -> String {
+        let file: syn::File = syn::parse_file(source).unwrap();
+        let Item::Struct(item) = &file.items[0] else {
+            panic!("expected a struct");
+        };
+        tokens_text(item.fields.iter().next().unwrap().ty.to_token_stream())
+    }
+
+    #[test]
+    fn test_prints_types_as_written() {
+        assert_eq!(
+            field_type("struct A<'a> { f: HashMap<&'a str, Vec<(u8, bool)>> }"),
+            "HashMap<&'a str, Vec<(u8, bool)>>"
+        );
+        assert_eq!(
+            field_type("struct A { f: Box<dyn Fn(&mut [u8; 4]) -> Result<(), E> + Send> }"),
+            "Box<dyn Fn(&mut [u8; 4]) -> Result<(), E> + Send>"
+        );
+        assert_eq!(
+            field_type("struct A { f: std::sync::Arc<T> }"),
+            "std::sync::Arc<T>"
+        );
+    }
+
+    #[test]
+    fn test_collects_types_mentioned_by_a_field() {
+        let file: syn::File =
+            syn::parse_file("struct A { f: Option<HashMap<Id, Vec<Order>>> }").unwrap();
+        let Item::Struct(item) = &file.items[0] else {
+            panic!("expected a struct");
+        };
+
+        assert_eq!(
+            references(&item.fields.iter().next().unwrap().ty),
+            vec!["Option", "HashMap", "Id", "Vec", "Order"]
+        );
+    }
+
+    #[test]
+    fn test_reports_where_the_source_is_broken() {
+        match parse_rust("struct User {\n    name: String,\n    age u8\n}") {
+            Err(RustParseError::Syntax { line, .. }) => assert_eq!(line, 3),
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+    }
+}
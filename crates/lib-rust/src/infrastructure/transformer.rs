@@ -0,0 +1,274 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::rust_item::{
+    RustField, RustItem, RustMethod, RustTypeKind, RustVariant,
+};
+
+pub struct GraphBuilder {
+    graph: Graph,
+    names: HashMap<String, Vec<String>>, // Maps type names to the ids of the types declaring them
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::Class,
+                ..Default::default()
+            },
+            names: HashMap::new(),
+        }
+    }
+
+    pub fn build(mut self, items: Vec<RustItem>) -> Graph {
+        items.iter().for_each(|item: &RustItem| {
+            if let RustItem::Type { name, module, .. } = item {
+                self.names
+                    .entry(name.clone())
+                    .or_default()
+                    .push(type_id(name, module.as_deref()));
+            }
+        });
+
+        // Impl blocks may come before the type they are for, so types go first
+        let (impls, declarations): (Vec<&RustItem>, Vec<&RustItem>) = items
+            .iter()
+            .partition(|item: &&RustItem| matches!(item, RustItem::Impl { .. }));
+        declarations
+            .into_iter()
+            .chain(impls)
+            .for_each(|item: &RustItem| self.process_item(item));
+        self.graph
+    }
+
+    fn process_item(&mut self, item: &RustItem) {
+        match item {
+            RustItem::Module { path, parent } => {
+                if let Some(group) = parent
+                    .as_deref()
+                    .and_then(|p: &str| self.graph.groups.get_mut(p))
+                {
+                    group.children.push(Id::from(path.as_str()));
+                }
+
+                self.graph.groups.insert(
+                    Id::from(path.as_str()),
+                    Group {
+                        id: Id::from(path.as_str()),
+                        label: path.rsplit("::").next().map(Text::from),
+                        parent: parent.as_deref().map(Id::from),
+                        ..Default::default()
+                    },
+                );
+            }
+            RustItem::Type {
+                name,
+                kind,
+                module,
+                visibility,
+                fields,
+                variants,
+                methods,
+                supertraits,
+            } => {
+                let id: String = type_id(name, module.as_deref());
+                let parent: Option<Id> = module.as_deref().map(Id::from);
+                if let Some(group) = parent.as_ref().and_then(|p| self.graph.groups.get_mut(p)) {
+                    group.children.push(Id::from(id.as_str()));
+                }
+
+                let node_kind: NodeKind = match kind {
+                    RustTypeKind::Struct | RustTypeKind::Union => NodeKind::Entity,
+                    RustTypeKind::Enum => NodeKind::Enum,
+                    RustTypeKind::Trait => NodeKind::Interface,
+                };
+
+                let mut members: Vec<Member> = fields.iter().map(map_field).collect();
+                members.extend(variants.iter().map(|variant: &RustVariant| Member {
+                    name: variant.name.clone(),
+                    kind: MemberKind::EnumValue,
+                    ..Default::default()
+                }));
+                members.extend(methods.iter().map(map_method));
+
+                let mut data: HashMap<Text, Value> = HashMap::new();
+                if let Some(visibility) = visibility {
+                    data.insert("visibility".into(), Value::String(visibility.clone()));
+                }
+                if *kind == RustTypeKind::Union {
+                    data.insert("union".into(), Value::Bool(true));
+                }
+
+                self.graph.nodes.insert(
+                    Id::from(id.as_str()),
+                    Node {
+                        id: Id::from(id.as_str()),
+                        kind: node_kind,
+                        label: Some(name.as_str().into()),
+                        members,
+                        data,
+                        parent,
+                        ..Default::default()
+                    },
+                );
+
+                fields.iter().for_each(|field: &RustField| {
+                    self.add_references(&id, module.as_deref(), &field.references, &field.name);
+                });
+                variants.iter().for_each(|variant: &RustVariant| {
+                    self.add_references(&id, module.as_deref(), &variant.references, &variant.name);
+                });
+                supertraits.iter().for_each(|supertrait: &String| {
+                    if let Some(target) = self.resolve(supertrait, module.as_deref()) {
+                        self.add_edge(&id, &target, EdgeKind::Inheritance, None);
+                    }
+                });
+            }
+            RustItem::Impl {
+                self_type,
+                trait_name,
+                methods,
+            } => {
+                // Impls of types declared elsewhere have nothing to attach to
+                let Some(id) = self.resolve(self_type, None) else {
+                    return;
+                };
+
+                match trait_name {
+                    Some(trait_name) => match self.resolve(trait_name, None) {
+                        Some(target) => {
+                            self.add_edge(&id, &target, EdgeKind::Realization, None);
+                        }
+                        None => {
+                            if let Some(node) = self.graph.nodes.get_mut(id.as_str()) {
+                                let traits: &mut Value = node
+                                    .data
+                                    .entry("traits".into())
+                                    .or_insert_with(|| Value::List(Vec::new()));
+                                if let Value::List(traits) = traits {
+                                    traits.push(Value::String(trait_name.clone()));
+                                }
+                            }
+                        }
+                    },
+                    // Trait impls only restate the trait's methods
+                    None => {
+                        if let Some(node) = self.graph.nodes.get_mut(id.as_str()) {
+                            node.members.extend(methods.iter().map(map_method));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Links a type to every declared type one of its fields mentions
+    fn add_references(
+        &mut self,
+        from: &str,
+        module: Option<&str>,
+        references: &[String],
+        label: &str,
+    ) {
+        let mut targets: Vec<String> = references
+            .iter()
+            .filter_map(|name: &String| self.resolve(name, module))
+            .collect();
+        targets.dedup();
+
+        targets.iter().for_each(|target: &String| {
+            self.add_edge(from, target, EdgeKind::Association, Some(label.to_string()));
+        });
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, kind: EdgeKind, label: Option<String>) -> Id {
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id.clone(),
+                from: from.into(),
+                to: to.into(),
+                directed: true,
+                kind,
+                label: label.map(Text::from),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+        edge_id
+    }
+
+    /// Id of the type a name refers to, preferring one declared in `module`
+    fn resolve(&self, name: &str, module: Option<&str>) -> Option<String> {
+        let ids: &Vec<String> = self.names.get(name)?;
+        let local: String = type_id(name, module);
+
+        ids.iter()
+            .find(|id: &&String| **id == local)
+            .or_else(|| ids.first())
+            .cloned()
+    }
+}
+
+fn type_id(name: &str, module: Option<&str>) -> String {
+    match module {
+        Some(module) => format!("{}::{}", module, name),
+        None => name.to_string(),
+    }
+}
+
+fn map_field(field: &RustField) -> Member {
+    let mut data: HashMap<Text, Value> = HashMap::new();
+    if let Some(visibility) = &field.visibility {
+        data.insert("visibility".into(), Value::String(visibility.clone()));
+    }
+
+    Member {
+        name: field.name.clone(),
+        kind: MemberKind::Field,
+        type_name: Some(field.type_name.clone()),
+        data,
+    }
+}
+
+fn map_method(method: &RustMethod) -> Member {
+    let mut data: HashMap<Text, Value> = HashMap::new();
+    if let Some(visibility) = &method.visibility {
+        data.insert("visibility".into(), Value::String(visibility.clone()));
+    }
+    if method.is_static {
+        data.insert("static".into(), Value::Bool(true));
+    }
+    if method.is_abstract {
+        data.insert("abstract".into(), Value::Bool(true));
+    }
+    data.insert(
+        "parameters".into(),
+        Value::List(
+            method
+                .parameters
+                .iter()
+                .map(|p: &String| Value::String(p.clone()))
+                .collect(),
+        ),
+    );
+
+    Member {
+        name: method.name.clone(),
+        kind: MemberKind::Method,
+        type_name: method.returns.clone(),
+        data,
+    }
+}
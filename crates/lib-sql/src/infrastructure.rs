@@ -0,0 +1,5 @@
+pub mod adapters;
+pub(crate) mod introspection;
+pub(crate) mod models;
+pub(crate) mod parser;
+pub(crate) mod transformer;
@@ -0,0 +1,2 @@
+pub mod database_graph_gateway;
+pub mod sql_graph_gateway;
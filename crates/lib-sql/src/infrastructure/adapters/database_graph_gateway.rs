@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{introspection, transformer};
+
+/// Reads the tables and foreign keys of a live database, taking its
+/// connection URL as input. Each database kind needs its feature enabled
+/// (`postgres`, `mysql` or `sqlite`), and queries run blocking.
+#[derive(Default)]
+pub struct DatabaseGraphGateway;
+
+impl DatabaseGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for DatabaseGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        introspection::introspect(input.trim())
+            .map(|ast| transformer::GraphBuilder::new().build(ast))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::graph::Graph,
+    };
+
+    use crate::infrastructure::adapters::database_graph_gateway::DatabaseGraphGateway;
+
+    #[test]
+    fn test_unreachable_database_is_reported() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = DatabaseGraphGateway::new()
+                .read_graph_from_raw_input("sqlite:///nonexistent/shop.db\n")
+                .await;
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Semantic { ref source, .. }) if source == "database"),
+                "Expected a database error, got {:?}",
+                result
+            );
+        });
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_reads_foreign_keys_of_a_live_database() {
+        use lib_core::entities::{edge::Edge, graph::GraphKind, value::Value};
+        use pretty_assertions::assert_eq;
+
+        let path: std::path::PathBuf =
+            std::env::temp_dir().join(format!("{}.db", uuid::Uuid::new_v4()));
+        rusqlite::Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE users (id INTEGER PRIMARY KEY);
+                 CREATE TABLE profiles (user_id INTEGER PRIMARY KEY REFERENCES users);",
+            )
+            .unwrap();
+
+        let graph: Graph = smol::block_on(
+            DatabaseGraphGateway::new()
+                .read_graph_from_raw_input(&format!("sqlite://{}", path.display())),
+        )
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(graph.kind, GraphKind::EntityRelationship);
+        assert_eq!(graph.nodes.len(), 2);
+        let edge: &Edge = graph.edges.values().next().expect("Missing foreign key");
+        assert_eq!(
+            (edge.from.as_str(), edge.to.as_str()),
+            ("profiles", "users")
+        );
+        assert_eq!(edge.from_cardinality.as_deref(), Some("1"));
+    }
+}
@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, SqlParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct SqlGraphGateway;
+
+impl SqlGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for SqlGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_sql(input)
+            .map_err(GraphGatewayError::from)
+            .map(|ast| transformer::GraphBuilder::new().build(ast))
+    }
+}
+
+impl From<SqlParseError> for GraphGatewayError {
+    fn from(err: SqlParseError) -> Self {
+        match err {
+            SqlParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "sql".into(),
+                message,
+                line,
+                column,
+            },
+            SqlParseError::Internal(msg) => GraphGatewayError::Semantic {
+                source: "sql".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::Edge,
+            graph::{Graph, GraphKind},
+            group::Group,
+            member::Member,
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::sql_graph_gateway::SqlGraphGateway;
+
+    const MIGRATION: &str = r#"
+    -- 0001_initial.sql
+    CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+    CREATE TABLE IF NOT EXISTS public.users (
+        id SERIAL PRIMARY KEY,
+        email character varying(255) NOT NULL UNIQUE,
+        status text DEFAULT 'it''s new' CHECK (status IN ('new', 'active')),
+        created_at timestamp with time zone NOT NULL DEFAULT now()
+    );
+
+    CREATE TABLE orders (
+        id bigint GENERATED ALWAYS AS IDENTITY,
+        user_id integer NOT NULL REFERENCES users ON DELETE CASCADE,
+        total numeric(10, 2),
+        PRIMARY KEY (id)
+    );
+
+    CREATE TABLE `order_items` (
+        `order_id` bigint,
+        `sku` varchar(32) COMMENT 'stock; keeping unit',
+        KEY `idx_sku` (`sku`(8)),
+        CONSTRAINT `fk_order` FOREIGN KEY (`order_id`) REFERENCES `orders` (`id`)
+    ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+    CREATE INDEX orders_user ON orders (user_id);
+    INSERT INTO users (email) VALUES ('a;b@example.com');
+
+    CREATE FUNCTION touch() RETURNS trigger AS $body$
+    BEGIN
+        NEW.updated_at = now();
+        RETURN NEW;
+    END;
+    $body$ LANGUAGE plpgsql;
+
+    ALTER TABLE orders ADD COLUMN coupon_id integer, ADD CONSTRAINT fk_coupon FOREIGN KEY (coupon_id) REFERENCES coupons (id);
+    ALTER TABLE orders DROP COLUMN total;
+    "#;
+
+    async fn parse_migration() -> Graph {
+        SqlGraphGateway::new()
+            .read_graph_from_raw_input(MIGRATION)
+            .await
+            .expect("Failed to parse valid DDL")
+    }
+
+    #[test]
+    fn test_parse_tables_and_columns() {
+        smol::block_on(async {
+            let graph: Graph = parse_migration().await;
+
+            assert_eq!(graph.kind, GraphKind::EntityRelationship);
+            assert_eq!(graph.nodes.len(), 4);
+
+            let users: &Node = graph.nodes.get("users").expect("Missing users table");
+            assert_eq!(users.kind, NodeKind::Entity);
+            assert_eq!(
+                users
+                    .members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), m.type_name.as_deref()))
+                    .collect::<Vec<(&str, Option<&str>)>>(),
+                vec![
+                    ("id", Some("SERIAL")),
+                    ("email", Some("character varying(255)")),
+                    ("status", Some("text")),
+                    ("created_at", Some("timestamp with time zone")),
+                ]
+            );
+
+            let email: &Member = &users.members[1];
+            assert_eq!(email.data.get("not_null"), Some(&Value::Bool(true)));
+            assert_eq!(email.data.get("unique"), Some(&Value::Bool(true)));
+            assert_eq!(
+                users.members[2].data.get("default"),
+                Some(&Value::String("it's new".to_string()))
+            );
+            assert_eq!(
+                users.members[3].data.get("default"),
+                Some(&Value::String("now()".to_string()))
+            );
+
+            let orders: &Node = graph.nodes.get("orders").expect("Missing orders table");
+            let id: &Member = &orders.members[0];
+            assert_eq!(id.data.get("primary_key"), Some(&Value::Bool(true)));
+            assert_eq!(id.data.get("increment"), Some(&Value::Bool(true)));
+            assert_eq!(
+                orders.members.last().map(|m: &Member| m.name.as_str()),
+                Some("coupon_id"),
+                "Columns added later belong to the table"
+            );
+
+            let items: &Node = graph
+                .nodes
+                .get("order_items")
+                .expect("Missing order_items table");
+            assert_eq!(
+                items.members[0].data.get("foreign_key"),
+                Some(&Value::Bool(true))
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_foreign_keys() {
+        smol::block_on(async {
+            let graph: Graph = parse_migration().await;
+
+            assert_eq!(graph.edges.len(), 3);
+
+            let inline: &Edge = find_edge(&graph, "orders", "users").expect("Missing user ref");
+            assert_eq!(
+                inline.data.get("to_column"),
+                Some(&Value::String("id".to_string())),
+                "A bare reference points at the primary key"
+            );
+            assert_eq!(inline.from_cardinality.as_deref(), Some("*"));
+            assert_eq!(inline.to_cardinality.as_deref(), Some("1"));
+            assert_eq!(
+                inline.data.get("on_delete"),
+                Some(&Value::String("cascade".to_string()))
+            );
+
+            let table_level: &Edge =
+                find_edge(&graph, "order_items", "orders").expect("Missing order ref");
+            assert_eq!(
+                table_level.data.get("constraint"),
+                Some(&Value::String("fk_order".to_string()))
+            );
+            assert_eq!(table_level.to_cardinality.as_deref(), Some("0..1"));
+
+            assert!(
+                find_edge(&graph, "orders", "coupons").is_some(),
+                "Tables only referenced are still shown"
+            );
+        });
+    }
+
+    #[test]
+    fn test_groups_tables_by_schema() {
+        smol::block_on(async {
+            let graph: Graph = parse_migration().await;
+
+            let group: &Group = graph.groups.values().next().expect("Missing schema group");
+            assert_eq!(graph.groups.len(), 1);
+            assert_eq!(group.label.as_deref(), Some("public"));
+            assert_eq!(group.children, vec!["users".to_string()]);
+            assert_eq!(
+                graph.nodes.get("users").and_then(|n| n.parent.as_ref()),
+                Some(&group.id)
+            );
+        });
+    }
+
+    #[test]
+    fn test_invalid_table_is_reported_as_parse_error() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = SqlGraphGateway::new()
+                .read_graph_from_raw_input("CREATE TABLE users (\n  id integer,\n  PRIMARY KEY\n);")
+                .await;
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Parse { ref source, line: 4, .. }) if source == "sql"),
+                "Expected an SQL parse error, got {:?}",
+                result
+            );
+        });
+    }
+
+    #[test]
+    fn test_script_without_tables_is_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = SqlGraphGateway::new()
+                .read_graph_from_raw_input("INSERT INTO users VALUES (1);")
+                .await;
+
+            assert!(matches!(result, Err(GraphGatewayError::Semantic { .. })));
+        });
+    }
+
+    fn find_edge<'a>(graph: &'a Graph, from: &str, to: &str) -> Option<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .find(|e: &&Edge| e.from == from && e.to == to)
+    }
+}
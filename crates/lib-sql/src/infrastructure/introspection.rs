@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use lib_core::adapters::graph_gateway::GraphGatewayError;
+
+use crate::infrastructure::models::sql_ast::{SqlAst, SqlColumn, SqlConstraint, SqlName};
+
+#[cfg(feature = "mysql")]
+mod mysql;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+/// Tables of a live database as read from its catalog, so they become the
+/// same definitions a DDL script would give
+#[derive(Debug, Default)]
+pub(crate) struct Catalog {
+    columns: Vec<(SqlName, SqlColumn)>,
+    constraints: Vec<(SqlName, SqlConstraint)>,
+}
+
+// Only the drivers fill catalogs, and none may be enabled
+#[cfg_attr(
+    not(any(feature = "postgres", feature = "mysql", feature = "sqlite")),
+    allow(dead_code)
+)]
+impl Catalog {
+    pub(crate) fn add_column(&mut self, table: SqlName, column: SqlColumn) {
+        self.columns.push((table, column));
+    }
+
+    pub(crate) fn add_constraint(&mut self, table: SqlName, constraint: SqlConstraint) {
+        self.constraints.push((table, constraint));
+    }
+
+    /// One definition per table, in the order their first column was read
+    pub(crate) fn into_tables(self) -> Vec<SqlAst> {
+        let mut order: Vec<SqlName> = Vec::new();
+        let mut tables: HashMap<SqlName, (Vec<SqlColumn>, Vec<SqlConstraint>)> = HashMap::new();
+
+        self.columns
+            .into_iter()
+            .for_each(|(table, column): (SqlName, SqlColumn)| {
+                if !tables.contains_key(&table) {
+                    order.push(table.clone());
+                }
+                tables.entry(table).or_default().0.push(column);
+            });
+        self.constraints
+            .into_iter()
+            .for_each(|(table, constraint): (SqlName, SqlConstraint)| {
+                if let Some((_, constraints)) = tables.get_mut(&table) {
+                    constraints.push(constraint);
+                }
+            });
+
+        order
+            .into_iter()
+            .filter_map(|name: SqlName| {
+                let (columns, constraints): (Vec<SqlColumn>, Vec<SqlConstraint>) =
+                    tables.remove(&name)?;
+                Some(SqlAst::CreateTable {
+                    name,
+                    columns,
+                    constraints,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads the tables of the database a URL such as `postgres://user@host/db`,
+/// `mysql://user@host/db` or `sqlite://path/to/file.db` points at
+pub(crate) fn introspect(url: &str) -> Result<Vec<SqlAst>, GraphGatewayError> {
+    let scheme: Option<String> = url
+        .split_once("://")
+        .map(|(scheme, _): (&str, &str)| scheme.to_lowercase());
+
+    let catalog: Catalog = match scheme.as_deref() {
+        #[cfg(feature = "postgres")]
+        Some("postgres" | "postgresql") => postgres::read(url),
+        #[cfg(feature = "mysql")]
+        Some("mysql" | "mariadb") => mysql::read(url),
+        #[cfg(feature = "sqlite")]
+        Some("sqlite") => sqlite::read(url),
+        other => Err(match other.and_then(feature) {
+            Some(feature) => format!(
+                "Reading this database needs diagrama built with the `{}` feature",
+                feature
+            ),
+            None => "Expected a postgres://, mysql:// or sqlite:// database URL".to_string(),
+        }),
+    }
+    .map_err(error)?;
+
+    let tables: Vec<SqlAst> = catalog.into_tables();
+    if tables.is_empty() {
+        return Err(error("Database has no tables".to_string()));
+    }
+    Ok(tables)
+}
+
+/// The referential action a catalog reports, leaving out the default one
+#[cfg_attr(
+    not(any(feature = "postgres", feature = "mysql", feature = "sqlite")),
+    allow(dead_code)
+)]
+pub(crate) fn referential_action(rule: &str) -> Option<String> {
+    let rule: String = rule.trim().to_lowercase();
+    (!rule.is_empty() && rule != "no action").then_some(rule)
+}
+
+/// A default as a catalog prints it, without the quotes and casts around text
+#[cfg_attr(
+    not(any(feature = "postgres", feature = "mysql", feature = "sqlite")),
+    allow(dead_code)
+)]
+pub(crate) fn literal(value: String) -> String {
+    // Postgres casts its literals, as in `'new'::text`
+    match value
+        .strip_prefix('\'')
+        .and_then(|rest: &str| rest.rsplit_once('\''))
+        .filter(|(text, cast): &(&str, &str)| {
+            !text.replace("''", "").contains('\'') && (cast.is_empty() || cast.starts_with("::"))
+        }) {
+        Some((text, _)) => text.replace("''", "'"),
+        None => value,
+    }
+}
+
+fn feature(scheme: &str) -> Option<&'static str> {
+    match scheme {
+        "postgres" | "postgresql" => Some("postgres"),
+        "mysql" | "mariadb" => Some("mysql"),
+        "sqlite" => Some("sqlite"),
+        _ => None,
+    }
+}
+
+fn error(message: String) -> GraphGatewayError {
+    GraphGatewayError::Semantic {
+        source: "database".into(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn name(table: &str) -> SqlName {
+        SqlName {
+            schema: None,
+            name: table.to_string(),
+        }
+    }
+
+    fn column(column: &str) -> SqlColumn {
+        SqlColumn {
+            name: column.to_string(),
+            data_type: "integer".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_gathers_columns_and_constraints_by_table() {
+        let mut catalog: Catalog = Catalog::default();
+        catalog.add_column(name("users"), column("id"));
+        catalog.add_column(name("orders"), column("id"));
+        catalog.add_column(name("users"), column("email"));
+        catalog.add_constraint(
+            name("users"),
+            SqlConstraint::PrimaryKey(vec!["id".to_string()]),
+        );
+        catalog.add_constraint(name("views"), SqlConstraint::Unique(vec!["id".to_string()]));
+
+        assert_eq!(
+            catalog.into_tables(),
+            vec![
+                SqlAst::CreateTable {
+                    name: name("users"),
+                    columns: vec![column("id"), column("email")],
+                    constraints: vec![SqlConstraint::PrimaryKey(vec!["id".to_string()])],
+                },
+                SqlAst::CreateTable {
+                    name: name("orders"),
+                    columns: vec![column("id")],
+                    constraints: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strips_quotes_and_casts_from_text_defaults() {
+        assert_eq!(literal("'it''s'::character varying".to_string()), "it's");
+        assert_eq!(literal("'new'".to_string()), "new");
+        assert_eq!(literal("now()".to_string()), "now()");
+        assert_eq!(
+            literal("'a'::text || 'b'::text".to_string()),
+            "'a'::text || 'b'::text"
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_urls() {
+        match introspect("oracle://localhost/shop") {
+            Err(GraphGatewayError::Semantic { message, .. }) => assert_eq!(
+                message,
+                "Expected a postgres://, mysql:// or sqlite:// database URL"
+            ),
+            other => panic!("Expected a semantic error, got {:?}", other),
+        }
+    }
+}
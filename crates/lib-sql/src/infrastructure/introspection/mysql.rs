@@ -0,0 +1,108 @@
+use mysql::{Conn, Opts, prelude::Queryable};
+
+use crate::infrastructure::{
+    introspection::{Catalog, literal, referential_action},
+    models::sql_ast::{SqlColumn, SqlConstraint, SqlName, SqlReference},
+};
+
+const COLUMNS: &str = "
+    SELECT c.TABLE_NAME, c.COLUMN_NAME, c.COLUMN_TYPE, c.IS_NULLABLE, c.COLUMN_DEFAULT, c.EXTRA
+    FROM information_schema.COLUMNS c
+    JOIN information_schema.TABLES t ON t.TABLE_SCHEMA = c.TABLE_SCHEMA AND t.TABLE_NAME = c.TABLE_NAME
+    WHERE c.TABLE_SCHEMA = DATABASE() AND t.TABLE_TYPE = 'BASE TABLE'
+    ORDER BY c.TABLE_NAME, c.ORDINAL_POSITION";
+
+const CONSTRAINTS: &str = "
+    SELECT k.TABLE_NAME, k.CONSTRAINT_NAME, t.CONSTRAINT_TYPE, k.COLUMN_NAME,
+           k.REFERENCED_TABLE_NAME, k.REFERENCED_COLUMN_NAME, r.DELETE_RULE, r.UPDATE_RULE
+    FROM information_schema.KEY_COLUMN_USAGE k
+    JOIN information_schema.TABLE_CONSTRAINTS t ON t.CONSTRAINT_SCHEMA = k.CONSTRAINT_SCHEMA
+        AND t.TABLE_NAME = k.TABLE_NAME AND t.CONSTRAINT_NAME = k.CONSTRAINT_NAME
+    LEFT JOIN information_schema.REFERENTIAL_CONSTRAINTS r ON r.CONSTRAINT_SCHEMA = k.CONSTRAINT_SCHEMA
+        AND r.TABLE_NAME = k.TABLE_NAME AND r.CONSTRAINT_NAME = k.CONSTRAINT_NAME
+    WHERE k.TABLE_SCHEMA = DATABASE() AND t.CONSTRAINT_TYPE IN ('PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY')
+    ORDER BY k.TABLE_NAME, k.CONSTRAINT_NAME, k.ORDINAL_POSITION";
+
+type ColumnRow = (String, String, String, String, Option<String>, String);
+type ConstraintRow = (
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+pub(crate) fn read(url: &str) -> Result<Catalog, String> {
+    let opts: Opts = Opts::from_url(url).map_err(|e| e.to_string())?;
+    let mut conn: Conn = Conn::new(opts).map_err(|e| e.to_string())?;
+    let mut catalog: Catalog = Catalog::default();
+
+    conn.query::<ColumnRow, _>(COLUMNS)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .for_each(
+            |(table, name, data_type, nullable, default, extra): ColumnRow| {
+                catalog.add_column(
+                    SqlName {
+                        schema: None,
+                        name: table,
+                    },
+                    SqlColumn {
+                        name,
+                        data_type,
+                        not_null: nullable == "NO",
+                        increment: extra.to_lowercase().contains("auto_increment"),
+                        default: default.map(literal),
+                        ..Default::default()
+                    },
+                );
+            },
+        );
+
+    // Keys come one row per column, so rows of one constraint are merged
+    let rows: Vec<ConstraintRow> = conn
+        .query::<ConstraintRow, _>(CONSTRAINTS)
+        .map_err(|e| e.to_string())?;
+    rows.chunk_by(|a: &ConstraintRow, b: &ConstraintRow| a.0 == b.0 && a.1 == b.1)
+        .for_each(|rows: &[ConstraintRow]| {
+            let (table, name, kind, _, referenced, _, on_delete, on_update): &ConstraintRow =
+                &rows[0];
+            let columns: Vec<String> = rows
+                .iter()
+                .map(|row: &ConstraintRow| row.3.clone())
+                .collect();
+
+            let constraint: SqlConstraint = match kind.as_str() {
+                "PRIMARY KEY" => SqlConstraint::PrimaryKey(columns),
+                "UNIQUE" => SqlConstraint::Unique(columns),
+                _ => SqlConstraint::ForeignKey {
+                    name: Some(name.clone()),
+                    columns,
+                    references: SqlReference {
+                        table: SqlName {
+                            schema: None,
+                            name: referenced.clone().unwrap_or_default(),
+                        },
+                        columns: rows
+                            .iter()
+                            .filter_map(|row: &ConstraintRow| row.5.clone())
+                            .collect(),
+                        on_delete: on_delete.as_deref().and_then(referential_action),
+                        on_update: on_update.as_deref().and_then(referential_action),
+                    },
+                },
+            };
+            catalog.add_constraint(
+                SqlName {
+                    schema: None,
+                    name: table.clone(),
+                },
+                constraint,
+            );
+        });
+
+    Ok(catalog)
+}
@@ -0,0 +1,106 @@
+use postgres::{Client, NoTls, Row};
+
+use crate::infrastructure::{
+    introspection::{Catalog, literal, referential_action},
+    models::sql_ast::{SqlColumn, SqlConstraint, SqlName, SqlReference},
+};
+
+const COLUMNS: &str = "
+    SELECT n.nspname, c.relname, a.attname, format_type(a.atttypid, a.atttypmod), a.attnotnull,
+           pg_get_expr(d.adbin, d.adrelid),
+           a.attidentity <> '' OR coalesce(pg_get_expr(d.adbin, d.adrelid), '') LIKE 'nextval(%'
+    FROM pg_attribute a
+    JOIN pg_class c ON c.oid = a.attrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+    WHERE c.relkind IN ('r', 'p') AND a.attnum > 0 AND NOT a.attisdropped
+      AND n.nspname NOT IN ('pg_catalog', 'information_schema') AND n.nspname NOT LIKE 'pg_toast%'
+    ORDER BY n.nspname, c.relname, a.attnum";
+
+const CONSTRAINTS: &str = "
+    SELECT n.nspname, c.relname, k.conname, k.contype::text,
+           ARRAY(SELECT a.attname::text FROM unnest(k.conkey) WITH ORDINALITY AS u(num, pos)
+                 JOIN pg_attribute a ON a.attrelid = k.conrelid AND a.attnum = u.num ORDER BY u.pos),
+           fn.nspname, f.relname,
+           ARRAY(SELECT a.attname::text FROM unnest(k.confkey) WITH ORDINALITY AS u(num, pos)
+                 JOIN pg_attribute a ON a.attrelid = k.confrelid AND a.attnum = u.num ORDER BY u.pos),
+           k.confdeltype::text, k.confupdtype::text
+    FROM pg_constraint k
+    JOIN pg_class c ON c.oid = k.conrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    LEFT JOIN pg_class f ON f.oid = k.confrelid
+    LEFT JOIN pg_namespace fn ON fn.oid = f.relnamespace
+    WHERE k.contype IN ('p', 'u', 'f') AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+    ORDER BY n.nspname, c.relname, k.conname";
+
+pub(crate) fn read(url: &str) -> Result<Catalog, String> {
+    let mut client: Client = Client::connect(url, NoTls).map_err(|e| e.to_string())?;
+    let mut catalog: Catalog = Catalog::default();
+
+    client
+        .query(COLUMNS, &[])
+        .map_err(|e| e.to_string())?
+        .iter()
+        .for_each(|row: &Row| {
+            let increment: bool = row.get(6);
+            catalog.add_column(
+                table(row.get(0), row.get(1)),
+                SqlColumn {
+                    name: row.get(2),
+                    data_type: row.get(3),
+                    not_null: row.get(4),
+                    increment,
+                    // Sequences behind serial columns say nothing about the data
+                    default: row
+                        .get::<_, Option<String>>(5)
+                        .filter(|_| !increment)
+                        .map(literal),
+                    ..Default::default()
+                },
+            );
+        });
+
+    client
+        .query(CONSTRAINTS, &[])
+        .map_err(|e| e.to_string())?
+        .iter()
+        .for_each(|row: &Row| {
+            let columns: Vec<String> = row.get(4);
+            let constraint: SqlConstraint = match row.get::<_, &str>(3) {
+                "p" => SqlConstraint::PrimaryKey(columns),
+                "u" => SqlConstraint::Unique(columns),
+                _ => SqlConstraint::ForeignKey {
+                    name: Some(row.get(2)),
+                    columns,
+                    references: SqlReference {
+                        table: table(row.get(5), row.get(6)),
+                        columns: row.get(7),
+                        on_delete: action(row.get(8)),
+                        on_update: action(row.get(9)),
+                    },
+                },
+            };
+            catalog.add_constraint(table(row.get(0), row.get(1)), constraint);
+        });
+
+    Ok(catalog)
+}
+
+/// Tables of the default schema go without one, as schemas become groups
+fn table(schema: Option<String>, name: String) -> SqlName {
+    SqlName {
+        schema: schema.filter(|schema: &String| schema != "public"),
+        name,
+    }
+}
+
+/// Expands the one-letter codes `pg_constraint` stores actions as
+fn action(code: Option<&str>) -> Option<String> {
+    referential_action(match code? {
+        "r" => "restrict",
+        "c" => "cascade",
+        "n" => "set null",
+        "d" => "set default",
+        _ => "no action",
+    })
+}
@@ -0,0 +1,231 @@
+use rusqlite::{Connection, OpenFlags, Params, Row};
+
+use crate::infrastructure::{
+    introspection::{Catalog, literal, referential_action},
+    models::sql_ast::{SqlColumn, SqlConstraint, SqlName, SqlReference},
+};
+
+type ColumnRow = (String, String, bool, Option<String>, usize);
+type ForeignKeyRow = (i64, String, String, Option<String>, String, String);
+
+pub(crate) fn read(url: &str) -> Result<Catalog, String> {
+    let path: &str = url.split_once("://").map_or(url, |(_, path)| path);
+    let connection: Connection =
+        Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("{}: {}", path, e))?;
+    let mut catalog: Catalog = Catalog::default();
+
+    let tables: Vec<(String, String)> = rows(
+        &connection,
+        "SELECT name, coalesce(sql, '') FROM sqlite_schema
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rowid",
+        [],
+        |row: &Row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    for (table, definition) in tables {
+        let name: SqlName = SqlName {
+            schema: None,
+            name: table.clone(),
+        };
+        // The catalog has no flag for it, only the statement that created the table
+        let autoincrement: bool = definition.to_lowercase().contains("autoincrement");
+
+        let mut columns: Vec<ColumnRow> = rows(
+            &connection,
+            "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid",
+            [&table],
+            |row: &Row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )?;
+        columns
+            .iter()
+            .for_each(|(column, data_type, not_null, default, key): &ColumnRow| {
+                catalog.add_column(
+                    name.clone(),
+                    SqlColumn {
+                        name: column.clone(),
+                        data_type: data_type.clone(),
+                        not_null: *not_null,
+                        increment: autoincrement && *key > 0,
+                        default: default.clone().map(literal),
+                        ..Default::default()
+                    },
+                );
+            });
+
+        // The position in the primary key is stored one-based, zero meaning outside of it
+        columns.retain(|column: &ColumnRow| column.4 > 0);
+        columns.sort_by_key(|column: &ColumnRow| column.4);
+        if !columns.is_empty() {
+            catalog.add_constraint(
+                name.clone(),
+                SqlConstraint::PrimaryKey(
+                    columns
+                        .into_iter()
+                        .map(|column: ColumnRow| column.0)
+                        .collect(),
+                ),
+            );
+        }
+
+        let unique: Vec<Vec<String>> = rows(
+            &connection,
+            "SELECT (SELECT group_concat(name, char(31)) FROM pragma_index_info(l.name))
+             FROM pragma_index_list(?1) l WHERE l.\"unique\" AND l.origin = 'u'",
+            [&table],
+            |row: &Row| {
+                Ok(row
+                    .get::<_, String>(0)?
+                    .split('\u{1f}')
+                    .map(str::to_string)
+                    .collect())
+            },
+        )?;
+        unique.into_iter().for_each(|columns: Vec<String>| {
+            catalog.add_constraint(name.clone(), SqlConstraint::Unique(columns));
+        });
+
+        // Composite keys come one row per column, sharing an id
+        let foreign: Vec<ForeignKeyRow> = rows(
+            &connection,
+            "SELECT id, \"table\", \"from\", \"to\", on_delete, on_update
+             FROM pragma_foreign_key_list(?1) ORDER BY id, seq",
+            [&table],
+            |row: &Row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            },
+        )?;
+        foreign
+            .chunk_by(|a: &ForeignKeyRow, b: &ForeignKeyRow| a.0 == b.0)
+            .for_each(|rows: &[ForeignKeyRow]| {
+                let (_, target, _, _, on_delete, on_update): &ForeignKeyRow = &rows[0];
+                catalog.add_constraint(
+                    name.clone(),
+                    SqlConstraint::ForeignKey {
+                        name: None,
+                        columns: rows
+                            .iter()
+                            .map(|row: &ForeignKeyRow| row.2.clone())
+                            .collect(),
+                        references: SqlReference {
+                            table: SqlName {
+                                schema: None,
+                                name: target.clone(),
+                            },
+                            columns: rows
+                                .iter()
+                                .filter_map(|row: &ForeignKeyRow| row.3.clone())
+                                .collect(),
+                            on_delete: referential_action(on_delete),
+                            on_update: referential_action(on_update),
+                        },
+                    },
+                );
+            });
+    }
+
+    Ok(catalog)
+}
+
+fn rows<T, P: Params>(
+    connection: &Connection,
+    sql: &str,
+    params: P,
+    map: impl FnMut(&Row) -> rusqlite::Result<T>,
+) -> Result<Vec<T>, String> {
+    connection
+        .prepare(sql)
+        .and_then(|mut statement| statement.query_map(params, map)?.collect())
+        .map_err(|e: rusqlite::Error| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::infrastructure::models::sql_ast::SqlAst;
+
+    #[test]
+    fn test_reads_tables_keys_and_references() {
+        let path: PathBuf = std::env::temp_dir().join(format!("{}.db", uuid::Uuid::new_v4()));
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL UNIQUE);
+                 CREATE TABLE orders (
+                     id INTEGER PRIMARY KEY,
+                     user_id INTEGER REFERENCES users (id) ON DELETE CASCADE,
+                     status TEXT DEFAULT 'new'
+                 );",
+            )
+            .unwrap();
+
+        let tables: Vec<SqlAst> = read(&format!("sqlite://{}", path.display()))
+            .unwrap()
+            .into_tables();
+        std::fs::remove_file(&path).unwrap();
+
+        let SqlAst::CreateTable {
+            name,
+            columns,
+            constraints,
+        } = &tables[0]
+        else {
+            panic!("Expected a table");
+        };
+        assert_eq!(name.name, "users");
+        assert!(columns[0].increment);
+        assert!(columns[1].not_null);
+        assert_eq!(
+            constraints,
+            &vec![
+                SqlConstraint::PrimaryKey(vec!["id".to_string()]),
+                SqlConstraint::Unique(vec!["email".to_string()]),
+            ]
+        );
+
+        let SqlAst::CreateTable {
+            columns,
+            constraints,
+            ..
+        } = &tables[1]
+        else {
+            panic!("Expected a table");
+        };
+        assert_eq!(columns[2].default.as_deref(), Some("new"));
+        assert_eq!(
+            constraints[1],
+            SqlConstraint::ForeignKey {
+                name: None,
+                columns: vec!["user_id".to_string()],
+                references: SqlReference {
+                    table: SqlName {
+                        schema: None,
+                        name: "users".to_string(),
+                    },
+                    columns: vec!["id".to_string()],
+                    on_delete: Some("cascade".to_string()),
+                    on_update: None,
+                },
+            }
+        );
+    }
+}
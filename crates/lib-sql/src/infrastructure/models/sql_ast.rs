@@ -0,0 +1,53 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlAst {
+    CreateTable {
+        name: SqlName,
+        columns: Vec<SqlColumn>,
+        constraints: Vec<SqlConstraint>,
+    },
+    /// An `ALTER TABLE .. ADD ..` of columns or constraints
+    AlterTable {
+        name: SqlName,
+        columns: Vec<SqlColumn>,
+        constraints: Vec<SqlConstraint>,
+    },
+}
+
+/// A possibly schema-qualified table name, e.g. `public.users`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SqlName {
+    pub schema: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SqlColumn {
+    pub name: String,
+    pub data_type: String,
+    pub primary_key: bool,
+    pub not_null: bool,
+    pub unique: bool,
+    pub increment: bool,
+    pub default: Option<String>,
+    pub references: Option<SqlReference>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlConstraint {
+    PrimaryKey(Vec<String>),
+    Unique(Vec<String>),
+    ForeignKey {
+        name: Option<String>,
+        columns: Vec<String>,
+        references: SqlReference,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SqlReference {
+    pub table: SqlName,
+    /// Empty when the referenced table's primary key is meant
+    pub columns: Vec<String>,
+    pub on_delete: Option<String>,
+    pub on_update: Option<String>,
+}
@@ -0,0 +1,237 @@
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::infrastructure::models::sql_ast::{
+    SqlAst, SqlColumn, SqlConstraint, SqlName, SqlReference,
+};
+
+#[derive(Parser)]
+#[grammar = "infrastructure/sql.pest"]
+pub struct SqlParser;
+
+pub fn parse_sql(input: &str) -> Result<Vec<SqlAst>, SqlParseError> {
+    let script: pest::iterators::Pair<Rule> = SqlParser::parse(Rule::script, input)
+        .map_err(SqlParseError::from)?
+        .next()
+        .ok_or_else(|| SqlParseError::Internal("Missing script root".to_string()))?;
+
+    let statements: Vec<SqlAst> = script.into_inner().filter_map(parse_statement).collect();
+    if statements.is_empty() {
+        return Err(SqlParseError::Internal(
+            "Script defines no tables".to_string(),
+        ));
+    }
+
+    Ok(statements)
+}
+
+fn parse_statement(pair: pest::iterators::Pair<Rule>) -> Option<SqlAst> {
+    let rule: Rule = pair.as_rule();
+    if rule != Rule::create_table && rule != Rule::alter_table {
+        return None;
+    }
+
+    let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+    let name: SqlName = parse_name(inner.next()?.as_str());
+    let mut columns: Vec<SqlColumn> = Vec::new();
+    let mut constraints: Vec<SqlConstraint> = Vec::new();
+
+    inner.for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+        Rule::column_def => columns.push(parse_column(item)),
+        Rule::table_constraint => constraints.extend(parse_constraint(item)),
+        _ => {}
+    });
+
+    Some(match rule {
+        Rule::create_table => SqlAst::CreateTable {
+            name,
+            columns,
+            constraints,
+        },
+        _ => SqlAst::AlterTable {
+            name,
+            columns,
+            constraints,
+        },
+    })
+}
+
+fn parse_column(pair: pest::iterators::Pair<Rule>) -> SqlColumn {
+    let mut column: SqlColumn = SqlColumn::default();
+
+    pair.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::identifier => column.name = unquote(item.as_str()),
+            Rule::data_type => {
+                column.data_type = item
+                    .as_str()
+                    .split_whitespace()
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            }
+            Rule::primary_key => column.primary_key = true,
+            Rule::not_null => column.not_null = true,
+            Rule::unique => column.unique = true,
+            Rule::auto_increment => column.increment = true,
+            Rule::generated => column.increment = item.as_str().to_lowercase().contains("identity"),
+            Rule::default_value => {
+                column.default = item
+                    .into_inner()
+                    .next()
+                    .map(|e: pest::iterators::Pair<Rule>| unquote_string(e.as_str()))
+            }
+            Rule::references => column.references = Some(parse_reference(item)),
+            _ => {}
+        });
+
+    column
+}
+
+fn parse_constraint(pair: pest::iterators::Pair<Rule>) -> Option<SqlConstraint> {
+    let mut name: Option<String> = None;
+
+    for item in pair.into_inner() {
+        match item.as_rule() {
+            Rule::identifier => name = Some(unquote(item.as_str())),
+            Rule::primary_key_constraint => {
+                return Some(SqlConstraint::PrimaryKey(parse_column_list(
+                    item.into_inner().next()?,
+                )));
+            }
+            Rule::unique_constraint => {
+                let list: pest::iterators::Pair<Rule> = item
+                    .into_inner()
+                    .find(|p: &pest::iterators::Pair<Rule>| p.as_rule() == Rule::column_list)?;
+                return Some(SqlConstraint::Unique(parse_column_list(list)));
+            }
+            Rule::foreign_key_constraint => {
+                let mut columns: Vec<String> = Vec::new();
+                let mut references: Option<SqlReference> = None;
+                item.into_inner()
+                    .for_each(|part: pest::iterators::Pair<Rule>| match part.as_rule() {
+                        Rule::column_list => columns = parse_column_list(part),
+                        Rule::references => references = Some(parse_reference(part)),
+                        _ => {}
+                    });
+
+                return Some(SqlConstraint::ForeignKey {
+                    name,
+                    columns,
+                    references: references?,
+                });
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_reference(pair: pest::iterators::Pair<Rule>) -> SqlReference {
+    let mut reference: SqlReference = SqlReference::default();
+
+    pair.into_inner()
+        .for_each(|item: pest::iterators::Pair<Rule>| match item.as_rule() {
+            Rule::qualified_name => reference.table = parse_name(item.as_str()),
+            Rule::column_list => reference.columns = parse_column_list(item),
+            Rule::on_delete => reference.on_delete = referential_action(item),
+            Rule::on_change => reference.on_update = referential_action(item),
+            _ => {}
+        });
+
+    reference
+}
+
+fn referential_action(pair: pest::iterators::Pair<Rule>) -> Option<String> {
+    pair.into_inner()
+        .next()
+        .map(|action: pest::iterators::Pair<Rule>| {
+            action
+                .as_str()
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ")
+                .to_lowercase()
+        })
+}
+
+fn parse_column_list(pair: pest::iterators::Pair<Rule>) -> Vec<String> {
+    pair.into_inner()
+        .map(|column: pest::iterators::Pair<Rule>| unquote(column.as_str()))
+        .collect()
+}
+
+/// Splits `schema.table` on dots outside of quotes, keeping the last two parts
+fn parse_name(raw: &str) -> SqlName {
+    let mut parts: Vec<String> = Vec::new();
+    let mut current: String = String::new();
+    let mut quote: Option<char> = None;
+
+    raw.chars().for_each(|c: char| match (quote, c) {
+        (None, '.') => parts.push(unquote(&std::mem::take(&mut current))),
+        (None, '"' | '`') => {
+            quote = Some(c);
+            current.push(c);
+        }
+        (None, '[') => {
+            quote = Some(']');
+            current.push(c);
+        }
+        (Some(q), _) if q == c => {
+            quote = None;
+            current.push(c);
+        }
+        _ => current.push(c),
+    });
+
+    SqlName {
+        schema: parts.pop(),
+        name: unquote(&current),
+    }
+}
+
+fn unquote(raw: &str) -> String {
+    raw.strip_prefix('"')
+        .and_then(|s: &str| s.strip_suffix('"'))
+        .or_else(|| {
+            raw.strip_prefix('`')
+                .and_then(|s: &str| s.strip_suffix('`'))
+        })
+        .or_else(|| {
+            raw.strip_prefix('[')
+                .and_then(|s: &str| s.strip_suffix(']'))
+        })
+        .unwrap_or(raw)
+        .to_string()
+}
+
+fn unquote_string(raw: &str) -> String {
+    raw.strip_prefix('\'')
+        .and_then(|s: &str| s.strip_suffix('\''))
+        .map(|s: &str| s.replace("''", "'"))
+        .unwrap_or_else(|| raw.to_string())
+}
+
+#[derive(Debug)]
+pub enum SqlParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Internal(String),
+}
+
+impl From<pest::error::Error<Rule>> for SqlParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column): (usize, usize) = match err.line_col {
+            pest::error::LineColLocation::Pos((l, c)) => (l, c),
+            pest::error::LineColLocation::Span((l, c), _) => (l, c),
+        };
+
+        SqlParseError::Syntax {
+            message: err.to_string(),
+            line,
+            column,
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::sql_ast::{
+    SqlAst, SqlColumn, SqlConstraint, SqlName, SqlReference,
+};
+
+pub struct GraphBuilder {
+    graph: Graph,
+    schemas: HashMap<String, Id>, // Maps schema names to the groups holding their tables
+    pending_refs: Vec<(Id, Option<String>, Vec<String>, SqlReference)>,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::EntityRelationship,
+                ..Default::default()
+            },
+            schemas: HashMap::new(),
+            pending_refs: Vec::new(),
+        }
+    }
+
+    pub fn build(mut self, ast: Vec<SqlAst>) -> Graph {
+        ast.into_iter().for_each(|statement: SqlAst| {
+            self.process_statement(statement);
+        });
+
+        // Keys may be declared after the columns pointing at them, so resolve them last
+        std::mem::take(&mut self.pending_refs).into_iter().for_each(
+            |(table, name, columns, reference): (Id, Option<String>, Vec<String>, SqlReference)| {
+                self.add_relationship(&table, name, columns, reference);
+            },
+        );
+
+        self.graph
+    }
+
+    fn process_statement(&mut self, statement: SqlAst) {
+        let (name, columns, constraints, creates): (
+            SqlName,
+            Vec<SqlColumn>,
+            Vec<SqlConstraint>,
+            bool,
+        ) = match statement {
+            SqlAst::CreateTable {
+                name,
+                columns,
+                constraints,
+            } => (name, columns, constraints, true),
+            SqlAst::AlterTable {
+                name,
+                columns,
+                constraints,
+            } => (name, columns, constraints, false),
+        };
+
+        let table: Id = Id::from(name.name.as_str());
+        if creates || !self.graph.nodes.contains_key(&table) {
+            self.add_table(&name);
+        }
+
+        columns.iter().for_each(|column: &SqlColumn| {
+            if let Some(reference) = &column.references {
+                self.pending_refs.push((
+                    table.clone(),
+                    None,
+                    vec![column.name.clone()],
+                    reference.clone(),
+                ));
+            }
+        });
+        if let Some(node) = self.graph.nodes.get_mut(&table) {
+            node.members.extend(columns.into_iter().map(map_column));
+        }
+
+        constraints
+            .into_iter()
+            .for_each(|constraint: SqlConstraint| match constraint {
+                SqlConstraint::PrimaryKey(columns) => {
+                    self.flag_columns(&table, &columns, "primary_key")
+                }
+                SqlConstraint::Unique(columns) if columns.len() == 1 => {
+                    self.flag_columns(&table, &columns, "unique")
+                }
+                SqlConstraint::Unique(_) => {}
+                SqlConstraint::ForeignKey {
+                    name,
+                    columns,
+                    references,
+                } => self
+                    .pending_refs
+                    .push((table.clone(), name, columns, references)),
+            });
+    }
+
+    fn add_table(&mut self, name: &SqlName) {
+        let table: Id = Id::from(name.name.as_str());
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        let parent: Option<Id> = name.schema.as_ref().map(|schema: &String| {
+            data.insert("schema".into(), Value::String(schema.clone()));
+            self.schema_group(schema)
+        });
+        if let Some(group) = parent
+            .as_ref()
+            .and_then(|p: &Id| self.graph.groups.get_mut(p))
+        {
+            group.children.push(table.clone());
+        }
+
+        self.graph.nodes.insert(
+            table.clone(),
+            Node {
+                id: table,
+                kind: NodeKind::Entity,
+                label: Some(name.name.as_str().into()),
+                data,
+                parent,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn schema_group(&mut self, schema: &str) -> Id {
+        if let Some(group_id) = self.schemas.get(schema) {
+            return group_id.clone();
+        }
+
+        let group_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.groups.insert(
+            group_id.clone(),
+            Group {
+                id: group_id.clone(),
+                label: Some(schema.into()),
+                ..Default::default()
+            },
+        );
+        self.schemas.insert(schema.to_string(), group_id.clone());
+        group_id
+    }
+
+    fn flag_columns(&mut self, table: &Id, columns: &[String], flag: &str) {
+        if let Some(node) = self.graph.nodes.get_mut(table) {
+            node.members
+                .iter_mut()
+                .filter(|member: &&mut Member| columns.contains(&member.name))
+                .for_each(|member: &mut Member| {
+                    member.data.insert(flag.into(), Value::Bool(true));
+                });
+        }
+    }
+
+    fn add_relationship(
+        &mut self,
+        table: &Id,
+        name: Option<String>,
+        columns: Vec<String>,
+        reference: SqlReference,
+    ) {
+        self.flag_columns(table, &columns, "foreign_key");
+
+        let target: Id = Id::from(reference.table.name.as_str());
+        if !self.graph.nodes.contains_key(&target) {
+            self.add_table(&reference.table);
+        }
+
+        // Without a column list the reference points at the target's primary key
+        let to_columns: Vec<String> = match reference.columns.is_empty() {
+            true => self.key_columns(&target, "primary_key"),
+            false => reference.columns,
+        };
+
+        // A key on the referencing columns allows one row per target, and a
+        // nullable one none at all
+        let from_members: Vec<&Member> = self.graph.nodes[table]
+            .members
+            .iter()
+            .filter(|member: &&Member| columns.contains(&member.name))
+            .collect();
+        let one_to_one: bool = self.key_columns(table, "primary_key") == columns
+            || (columns.len() == 1
+                && from_members
+                    .iter()
+                    .any(|m: &&Member| is_flagged(m, "unique")));
+        let required: bool = !from_members.is_empty()
+            && from_members
+                .iter()
+                .all(|m: &&Member| is_flagged(m, "not_null") || is_flagged(m, "primary_key"));
+
+        let mut data: HashMap<Text, Value> = HashMap::from([
+            ("from_column".into(), Value::String(columns.join(", "))),
+            ("to_column".into(), Value::String(to_columns.join(", "))),
+        ]);
+        [
+            ("constraint", name),
+            ("on_delete", reference.on_delete),
+            ("on_update", reference.on_update),
+        ]
+        .into_iter()
+        .for_each(|(key, value): (&str, Option<String>)| {
+            if let Some(value) = value {
+                data.insert(key.into(), Value::String(value));
+            }
+        });
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id,
+                from: table.clone(),
+                to: target,
+                directed: false,
+                kind: EdgeKind::Association,
+                data,
+                from_cardinality: Some(if one_to_one { "1" } else { "*" }.into()),
+                to_cardinality: Some(if required { "1" } else { "0..1" }.into()),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn key_columns(&self, table: &Id, flag: &str) -> Vec<String> {
+        self.graph
+            .nodes
+            .get(table)
+            .map(|node: &Node| {
+                node.members
+                    .iter()
+                    .filter(|member: &&Member| is_flagged(member, flag))
+                    .map(|member: &Member| member.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn is_flagged(member: &Member, flag: &str) -> bool {
+    member.data.get(flag) == Some(&Value::Bool(true))
+}
+
+fn map_column(column: SqlColumn) -> Member {
+    let mut data: HashMap<Text, Value> = [
+        ("primary_key", column.primary_key),
+        ("not_null", column.not_null),
+        ("unique", column.unique),
+        ("increment", column.increment),
+    ]
+    .into_iter()
+    .filter(|(_, set): &(&str, bool)| *set)
+    .map(|(key, _): (&str, bool)| (Text::from(key), Value::Bool(true)))
+    .collect();
+    if let Some(default) = column.default {
+        data.insert("default".into(), Value::String(default));
+    }
+
+    Member {
+        name: column.name,
+        kind: MemberKind::Field,
+        type_name: Some(column.data_type),
+        data,
+    }
+}
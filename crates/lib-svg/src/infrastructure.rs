@@ -0,0 +1,3 @@
+pub mod adapters;
+pub mod image_map;
+pub(crate) mod renderer;
@@ -0,0 +1,287 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter, GraphEmitterError},
+    entities::{computed_style::Theme, graph::Graph, node::Node},
+};
+
+use crate::infrastructure::renderer;
+
+/// Renders an already laid out graph as SVG
+#[derive(Default)]
+pub struct SvgGraphEmitter {
+    options: EmitOptions,
+    theme: Theme,
+}
+
+impl SvgGraphEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: EmitOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+#[async_trait]
+impl GraphEmitterAdapter for SvgGraphEmitter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError> {
+        if let Some(node) = graph
+            .nodes
+            .values()
+            .find(|node: &&Node| node.geometry.is_none())
+        {
+            return Err(GraphEmitterError::Unsupported {
+                target: "svg".into(),
+                message: format!(
+                    "Node '{}' has no geometry, the graph must be laid out before rendering",
+                    node.id
+                ),
+            });
+        }
+
+        Ok(renderer::render_svg(
+            &self.options.prepare(graph),
+            &self.theme,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use lib_core::{
+        adapters::graph_emitter_adapter::{GraphEmitterAdapter, GraphEmitterError},
+        entities::{
+            computed_style::Theme,
+            edge::{Edge, EdgeKind},
+            geometry::{Point, Rect},
+            graph::Graph,
+            id::Text,
+            member::Member,
+            node::Node,
+            rich_text::{Format, Line, RichText, Span},
+            style::{Color, Colors},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::svg_graph_emitter::SvgGraphEmitter;
+
+    #[test]
+    fn test_renders_nodes_edges_and_labels() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "A", 20.0, 20.0);
+            add_node(&mut graph, "B<T>", 20.0, 120.0);
+            graph.nodes.get_mut("A").unwrap().members = vec![Member {
+                name: "id".to_string(),
+                type_name: Some("u32".to_string()),
+                ..Default::default()
+            }];
+            graph.edges.insert(
+                "e".into(),
+                Edge {
+                    id: "e".into(),
+                    from: "B<T>".into(),
+                    to: "A".into(),
+                    kind: EdgeKind::Inheritance,
+                    label: Some("is a".into()),
+                    waypoints: vec![Point { x: 60.0, y: 120.0 }, Point { x: 60.0, y: 70.0 }],
+                    ..Default::default()
+                },
+            );
+
+            let output: String = SvgGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert!(output.starts_with(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"120\" height=\"170\""
+            ));
+            assert!(output.contains(
+                "<polyline points=\"60,120 60,70\" fill=\"none\" stroke=\"#333\" marker-end=\"url(#triangle)\"/>"
+            ));
+            assert!(output.contains(">is a</text>"));
+            assert!(output.contains(">B&lt;T&gt;</text>"));
+            assert!(output.contains(">id: u32</text>"));
+            assert!(output.trim_end().ends_with("</svg>"));
+        });
+    }
+
+    #[test]
+    fn test_renders_formatted_labels() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "A", 20.0, 20.0);
+            graph.nodes.get_mut("A").unwrap().rich_label = Some(RichText {
+                lines: vec![
+                    Line::Text(vec![Span {
+                        text: "Order".to_string(),
+                        format: Format {
+                            italic: true,
+                            ..Format::default()
+                        },
+                    }]),
+                    Line::Bullet {
+                        depth: 1,
+                        spans: vec![Span {
+                            text: "paid".to_string(),
+                            ..Default::default()
+                        }],
+                    },
+                ],
+            });
+
+            let output: String = SvgGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert!(output.contains(
+                "<tspan x=\"60\" dy=\"-7\"><tspan font-style=\"italic\">Order</tspan></tspan><tspan x=\"60\" dy=\"14\">\u{2022} paid</tspan>"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_underlines_static_members_and_slants_abstract_ones() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "A", 20.0, 20.0);
+            let member = |name: &str, modifier: &str| -> Member {
+                Member {
+                    name: name.to_string(),
+                    data: HashMap::from([(Text::from(modifier), Value::Bool(true))]),
+                    ..Default::default()
+                }
+            };
+            graph.nodes.get_mut("A").unwrap().members =
+                vec![member("count", "static"), member("area", "abstract")];
+
+            let output: String = SvgGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert!(output.contains("<tspan text-decoration=\"underline\">count</tspan>"));
+            assert!(output.contains("<tspan font-style=\"italic\">area</tspan>"));
+        });
+    }
+
+    #[test]
+    fn test_colours_elements_from_theme_and_inline_style() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "A", 20.0, 20.0);
+            add_node(&mut graph, "B", 20.0, 120.0);
+            graph
+                .nodes
+                .get_mut("B")
+                .unwrap()
+                .data
+                .insert("style".into(), Value::String("fill:#fc9".to_string()));
+
+            let output: String = SvgGraphEmitter::new()
+                .with_theme(
+                    Theme::new()
+                        .with_node("fill", "#eef")
+                        .with_node("stroke", "#00a"),
+                )
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert!(output.contains(
+                "<rect x=\"20\" y=\"20\" width=\"80\" height=\"30\" fill=\"#eef\" stroke=\"#00a\"/>"
+            ));
+            assert!(output.contains(
+                "<rect x=\"20\" y=\"120\" width=\"80\" height=\"30\" fill=\"#fc9\" stroke=\"#00a\"/>"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_colours_elements_from_their_own_colors() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "A", 20.0, 20.0);
+            add_node(&mut graph, "B", 20.0, 120.0);
+            graph.nodes.get_mut("A").unwrap().colors = Colors {
+                fill: Some(Color::Named("pink".into())),
+                stroke: Some(Color::Rgba([255, 0, 0, 255])),
+                text: Some(Color::Named("blue".into())),
+                ..Default::default()
+            };
+            graph.nodes.get_mut("B").unwrap().colors = Colors {
+                fill: Some(Color::Named("red".into())),
+                gradient: Some(Color::Named("white".into())),
+                ..Default::default()
+            };
+            graph.edges.insert(
+                "e".into(),
+                Edge {
+                    id: "e".into(),
+                    from: "B".into(),
+                    to: "A".into(),
+                    colors: Colors {
+                        stroke: Some(Color::Named("green".into())),
+                        ..Default::default()
+                    },
+                    waypoints: vec![Point { x: 60.0, y: 120.0 }, Point { x: 60.0, y: 50.0 }],
+                    ..Default::default()
+                },
+            );
+
+            let output: String = SvgGraphEmitter::new().emit_graph(&graph).await.unwrap();
+
+            assert!(output.contains(
+                "<g class=\"node\" fill=\"blue\"><rect x=\"20\" y=\"20\" width=\"80\" height=\"30\" fill=\"pink\" stroke=\"#ff0000\"/>"
+            ));
+            assert!(output.contains(
+                "<linearGradient id=\"gradient-B\"><stop offset=\"0\" stop-color=\"red\"/><stop offset=\"1\" stop-color=\"white\"/></linearGradient><rect x=\"20\" y=\"120\" width=\"80\" height=\"30\" fill=\"url(#gradient-B)\""
+            ));
+            assert!(
+                output.contains("<polyline points=\"60,120 60,50\" fill=\"none\" stroke=\"green\"")
+            );
+        });
+    }
+
+    #[test]
+    fn test_requires_laid_out_graph() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph::default();
+            add_node(&mut graph, "A", 0.0, 0.0);
+            graph.nodes.get_mut("A").unwrap().geometry = None;
+
+            let result: Result<String, GraphEmitterError> =
+                SvgGraphEmitter::new().emit_graph(&graph).await;
+
+            assert_eq!(
+                result,
+                Err(GraphEmitterError::Unsupported {
+                    target: "svg".to_string(),
+                    message:
+                        "Node 'A' has no geometry, the graph must be laid out before rendering"
+                            .to_string()
+                })
+            );
+        });
+    }
+
+    fn add_node(graph: &mut Graph, id: &str, x: f64, y: f64) {
+        graph.nodes.insert(
+            id.into(),
+            Node {
+                id: id.into(),
+                geometry: Some(Rect {
+                    x,
+                    y,
+                    width: 80.0,
+                    height: 30.0,
+                }),
+                ..Default::default()
+            },
+        );
+    }
+}
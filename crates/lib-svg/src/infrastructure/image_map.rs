@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+
+use lib_core::entities::{
+    geometry::Rect,
+    graph::Graph,
+    group::Group,
+    id::Id,
+    node::Node,
+    value::{LINK_KEYS, Value},
+};
+use serde_json::{Value as Json, json};
+
+use crate::infrastructure::renderer::{depth, escape, extent, number};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AreaKind {
+    Node,
+    Group,
+}
+
+/// A clickable region of a rendered diagram
+#[derive(Debug, Clone, PartialEq)]
+pub struct Area {
+    pub id: Id,
+    pub kind: AreaKind,
+    pub label: String,
+    /// In the image's pixels, which are the SVG's user units
+    pub bounds: Rect,
+    pub href: Option<String>,
+}
+
+/// Where each element of a rendered diagram sits, for making the image
+/// clickable. Areas are listed topmost first, nodes then groups from the
+/// innermost, as an HTML `<map>` picks the first area containing a click.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageMap {
+    pub width: f64,
+    pub height: f64,
+    pub areas: Vec<Area>,
+}
+
+impl ImageMap {
+    /// Maps a laid out graph as `render_svg` draws it; hidden elements and
+    /// those without geometry are left out
+    pub fn new(graph: &Graph) -> Self {
+        let (width, height): (f64, f64) = extent(graph);
+        let hidden: HashSet<Id> = graph.hidden();
+
+        let mut nodes: Vec<&Node> = graph
+            .nodes
+            .values()
+            .filter(|node: &&Node| !hidden.contains(&node.id))
+            .collect();
+        nodes.sort_by(|a: &&Node, b: &&Node| a.id.cmp(&b.id));
+        let mut groups: Vec<&Group> = graph
+            .groups
+            .values()
+            .filter(|group: &&Group| !hidden.contains(&group.id))
+            .collect();
+        groups.sort_by_key(|group: &&Group| {
+            (std::cmp::Reverse(depth(graph, &group.id)), group.id.clone())
+        });
+
+        let nodes = nodes.into_iter().filter_map(|node: &Node| {
+            Some(Area {
+                id: node.id.clone(),
+                kind: AreaKind::Node,
+                label: node.label.as_ref().unwrap_or(&node.id).to_string(),
+                bounds: node.geometry?,
+                href: LINK_KEYS
+                    .iter()
+                    .find_map(|key: &&str| match node.data.get(*key) {
+                        Some(Value::String(link)) => Some(link.clone()),
+                        _ => None,
+                    }),
+            })
+        });
+        let groups = groups.into_iter().filter_map(|group: &Group| {
+            Some(Area {
+                id: group.id.clone(),
+                kind: AreaKind::Group,
+                label: group.label.as_ref().unwrap_or(&group.id).to_string(),
+                bounds: group.geometry?,
+                href: None,
+            })
+        });
+
+        Self {
+            width,
+            height,
+            areas: nodes.chain(groups).collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        json!({
+            "width": rounded(self.width),
+            "height": rounded(self.height),
+            "areas": self.areas.iter().map(|area: &Area| json!({
+                "id": area.id.as_str(),
+                "kind": match area.kind {
+                    AreaKind::Node => "node",
+                    AreaKind::Group => "group",
+                },
+                "label": area.label,
+                "bounds": {
+                    "x": rounded(area.bounds.x),
+                    "y": rounded(area.bounds.y),
+                    "width": rounded(area.bounds.width),
+                    "height": rounded(area.bounds.height),
+                },
+                "href": area.href,
+            })).collect::<Vec<Json>>(),
+        })
+    }
+
+    /// An HTML `<map>` named `name`, for an `<img usemap="#name">`. Areas
+    /// without a link point at a fragment named after the element id.
+    pub fn to_html(&self, name: &str) -> String {
+        let mut lines: Vec<String> = vec![format!("<map name=\"{}\">", escape(name))];
+        lines.extend(self.areas.iter().map(|area: &Area| {
+            let bounds: Rect = area.bounds;
+            format!(
+                "  <area shape=\"rect\" coords=\"{},{},{},{}\" href=\"{}\" alt=\"{}\" title=\"{}\" data-id=\"{}\">",
+                number(bounds.x.round()),
+                number(bounds.y.round()),
+                number((bounds.x + bounds.width).round()),
+                number((bounds.y + bounds.height).round()),
+                escape(
+                    &area
+                        .href
+                        .clone()
+                        .unwrap_or_else(|| format!("#{}", area.id))
+                ),
+                escape(&area.label),
+                escape(&area.label),
+                escape(&area.id),
+            )
+        }));
+        lines.push("</map>".to_string());
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Two decimals, as the SVG coordinates are written
+fn rounded(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0 + 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Option<Rect> {
+        Some(Rect {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    fn graph() -> Graph {
+        let mut graph: Graph = Graph::default();
+        graph.nodes.insert(
+            "User".into(),
+            Node {
+                id: "User".into(),
+                label: Some("User <T>".into()),
+                data: [("file".into(), Value::String("src/user.rs".to_string()))].into(),
+                parent: Some("model".into()),
+                geometry: rect(30.0, 40.0, 80.5, 30.0),
+                ..Default::default()
+            },
+        );
+        graph.nodes.insert(
+            "Ghost".into(),
+            Node {
+                id: "Ghost".into(),
+                geometry: rect(40.0, 50.0, 10.0, 10.0),
+                hidden: true,
+                ..Default::default()
+            },
+        );
+        graph.nodes.insert(
+            "Floating".into(),
+            Node {
+                id: "Floating".into(),
+                ..Default::default()
+            },
+        );
+        for (id, parent, bounds) in [
+            ("app", None, rect(10.0, 10.0, 200.0, 100.0)),
+            ("model", Some("app"), rect(20.0, 30.0, 100.0, 50.0)),
+        ] {
+            graph.groups.insert(
+                id.into(),
+                Group {
+                    id: id.into(),
+                    parent: parent.map(Id::from),
+                    geometry: bounds,
+                    ..Default::default()
+                },
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_lists_areas_topmost_first() {
+        let map: ImageMap = ImageMap::new(&graph());
+
+        assert_eq!((map.width, map.height), (230.0, 130.0));
+        assert_eq!(
+            map.areas
+                .iter()
+                .map(|area: &Area| (area.id.as_str(), area.kind))
+                .collect::<Vec<(&str, AreaKind)>>(),
+            vec![
+                ("User", AreaKind::Node),
+                ("model", AreaKind::Group),
+                ("app", AreaKind::Group),
+            ]
+        );
+        assert_eq!(map.areas[0].href.as_deref(), Some("src/user.rs"));
+        assert_eq!(
+            map.to_json()["areas"][0]["bounds"],
+            json!({"x": 30.0, "y": 40.0, "width": 80.5, "height": 30.0})
+        );
+    }
+
+    #[test]
+    fn test_writes_an_html_map() {
+        assert_eq!(
+            ImageMap::new(&graph()).to_html("model"),
+            "<map name=\"model\">\n\
+            \x20 <area shape=\"rect\" coords=\"30,40,111,70\" href=\"src/user.rs\" alt=\"User &lt;T&gt;\" title=\"User &lt;T&gt;\" data-id=\"User\">\n\
+            \x20 <area shape=\"rect\" coords=\"20,30,120,80\" href=\"#model\" alt=\"model\" title=\"model\" data-id=\"model\">\n\
+            \x20 <area shape=\"rect\" coords=\"10,10,210,110\" href=\"#app\" alt=\"app\" title=\"app\" data-id=\"app\">\n\
+            </map>\n"
+        );
+    }
+}
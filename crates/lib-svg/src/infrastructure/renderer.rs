@@ -0,0 +1,523 @@
+use lib_core::entities::{
+    computed_style::{ComputedStyle, StyleResolver, Theme},
+    edge::{Edge, EdgeKind, EdgeLabel, EdgeLabelKind},
+    geometry::{Point, Rect},
+    graph::Graph,
+    group::Group,
+    id::Id,
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    rich_text::{Format, Line, RichText, Span},
+    value::Value,
+};
+
+const MARGIN: f64 = 20.0;
+const FONT_SIZE: f64 = 12.0;
+const HEADER_HEIGHT: f64 = 30.0;
+const MEMBER_HEIGHT: f64 = 18.0;
+const GROUP_LABEL_HEIGHT: f64 = 24.0;
+const LINE_HEIGHT: f64 = 14.0;
+
+const DEFS: &str = concat!(
+    "<defs>",
+    "<marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"10\" markerHeight=\"10\" orient=\"auto-start-reverse\">",
+    "<path d=\"M0,0 L10,5 L0,10\" fill=\"none\" stroke=\"#333\"/></marker>",
+    "<marker id=\"triangle\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"12\" markerHeight=\"12\" orient=\"auto-start-reverse\">",
+    "<path d=\"M0,0 L10,5 L0,10 Z\" fill=\"#fff\" stroke=\"#333\"/></marker>",
+    "<marker id=\"diamond\" viewBox=\"0 0 16 10\" refX=\"0\" refY=\"5\" markerWidth=\"16\" markerHeight=\"10\" orient=\"auto-start-reverse\">",
+    "<path d=\"M0,5 L8,0 L16,5 L8,10 Z\" fill=\"#fff\" stroke=\"#333\"/></marker>",
+    "<marker id=\"filled-diamond\" viewBox=\"0 0 16 10\" refX=\"0\" refY=\"5\" markerWidth=\"16\" markerHeight=\"10\" orient=\"auto-start-reverse\">",
+    "<path d=\"M0,5 L8,0 L16,5 L8,10 Z\" fill=\"#333\" stroke=\"#333\"/></marker>",
+    "</defs>",
+);
+
+/// Draws a laid out graph as a standalone SVG document: group frames first,
+/// then edges with their labels, then nodes on top, each coloured as `theme`
+/// and the graph's own styles say
+pub fn render_svg(graph: &Graph, theme: &Theme) -> String {
+    let styles: StyleResolver = StyleResolver::new(graph, theme);
+    let (width, height): (f64, f64) = extent(graph);
+    let mut parts: Vec<String> = vec![
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"{}\">",
+            number(width),
+            number(height),
+            number(width),
+            number(height),
+            number(FONT_SIZE)
+        ),
+        DEFS.to_string(),
+    ];
+    if let Some(title) = &graph.metadata.title {
+        parts.push(format!("<title>{}</title>", escape(title)));
+    }
+
+    let mut groups: Vec<&Group> = graph.groups.values().collect();
+    groups.sort_by_key(|group: &&Group| (depth(graph, &group.id), group.id.clone()));
+    groups
+        .iter()
+        .filter_map(|group: &&Group| group.geometry.map(|rect: Rect| (group, rect)))
+        .for_each(|(group, rect): (&&Group, Rect)| {
+            parts.push(format!(
+                "<g class=\"group\">{}{}</g>",
+                rectangle(&rect, &styles.group(group)),
+                text(
+                    &Point {
+                        x: rect.x + 8.0,
+                        y: rect.y + GROUP_LABEL_HEIGHT / 2.0,
+                    },
+                    group.label.as_deref().unwrap_or(&group.id),
+                    "start",
+                    true
+                )
+            ));
+        });
+
+    let mut edges: Vec<&Edge> = graph.edges.values().collect();
+    edges.sort_by(|a: &&Edge, b: &&Edge| a.id.cmp(&b.id));
+    edges
+        .iter()
+        .filter(|edge: &&&Edge| !edge.layout.hidden && edge.waypoints.len() >= 2)
+        .for_each(|edge: &&Edge| parts.push(render_edge(edge, &styles.edge(edge))));
+
+    let mut nodes: Vec<&Node> = graph.nodes.values().collect();
+    nodes.sort_by(|a: &&Node, b: &&Node| a.id.cmp(&b.id));
+    nodes
+        .iter()
+        .filter_map(|node: &&Node| node.geometry.map(|rect: Rect| (node, rect)))
+        .for_each(|(node, rect): (&&Node, Rect)| {
+            parts.push(render_node(node, &rect, &styles.node(node)))
+        });
+
+    parts.push("</svg>".to_string());
+    parts.join("\n") + "\n"
+}
+
+fn render_edge(edge: &Edge, style: &ComputedStyle) -> String {
+    let points: String = edge
+        .waypoints
+        .iter()
+        .map(|p: &Point| format!("{},{}", number(p.x), number(p.y)))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let dashed: String = style
+        .get("stroke-dasharray")
+        .map(|dashes: &str| format!(" stroke-dasharray=\"{}\"", escape(dashes)))
+        .unwrap_or_default();
+    let markers: &str = match &edge.kind {
+        EdgeKind::Inheritance | EdgeKind::Realization => " marker-end=\"url(#triangle)\"",
+        EdgeKind::Composition => " marker-start=\"url(#filled-diamond)\"",
+        EdgeKind::Aggregation => " marker-start=\"url(#diamond)\"",
+        EdgeKind::Undirected => "",
+        _ if edge.directed => " marker-end=\"url(#arrow)\"",
+        _ => "",
+    };
+
+    let mut parts: Vec<String> = vec![format!(
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\"{}{}/>",
+        points,
+        stroke(style),
+        dashed,
+        markers
+    )];
+    if edge.layout.labels.is_empty() {
+        // Without label placement, the label goes next to the middle waypoint
+        if let Some(label) = &edge.label {
+            let middle: Point = edge.waypoints[edge.waypoints.len() / 2];
+            parts.push(rich_text(
+                &Point {
+                    x: middle.x + 4.0,
+                    y: middle.y - 8.0,
+                },
+                label,
+                edge.rich_label.as_ref(),
+                "start",
+                false,
+            ));
+        }
+    }
+    edge.layout.labels.iter().for_each(|label: &EdgeLabel| {
+        let rich: Option<&RichText> = match label.kind {
+            EdgeLabelKind::Label => edge.rich_label.as_ref(),
+            _ => None,
+        };
+        parts.push(rich_text(
+            &label.bounds.center(),
+            &label.text,
+            rich,
+            "middle",
+            false,
+        ));
+    });
+
+    format!(
+        "<g class=\"edge\"{}>{}</g>",
+        text_color(style),
+        parts.join("")
+    )
+}
+
+fn render_node(node: &Node, rect: &Rect, style: &ComputedStyle) -> String {
+    let label: &str = node.label.as_deref().unwrap_or(&node.id);
+    let mut parts: Vec<String> = Vec::new();
+
+    // A gradient runs from the fill color to the gradient color
+    let mut gradient: ComputedStyle = style.clone();
+    if let (Some(from), Some(to)) = (style.get("fill"), style.get("gradient")) {
+        let id: String = format!("gradient-{}", node.id);
+        parts.push(format!(
+            "<linearGradient id=\"{}\"><stop offset=\"0\" stop-color=\"{}\"/><stop offset=\"1\" stop-color=\"{}\"/></linearGradient>",
+            escape(&id),
+            escape(from),
+            escape(to)
+        ));
+        gradient
+            .properties
+            .insert("fill".to_string(), format!("url(#{})", id));
+    }
+    let style: &ComputedStyle = &gradient;
+
+    match node.kind {
+        NodeKind::Actor => {
+            let center: Point = rect.center();
+            let head: f64 = rect.height.min(rect.width) / 6.0;
+            parts.push(format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"{}\"/>",
+                number(center.x),
+                number(rect.y + head),
+                number(head),
+                fill(style),
+                stroke(style)
+            ));
+            parts.push(format!(
+                "<path d=\"M{x},{neck} V{hip} M{left},{arms} H{right} M{left},{bottom} L{x},{hip} L{right},{bottom}\" fill=\"none\" stroke=\"{stroke}\"/>",
+                x = number(center.x),
+                neck = number(rect.y + 2.0 * head),
+                hip = number(rect.y + rect.height * 0.7),
+                arms = number(rect.y + rect.height * 0.45),
+                left = number(center.x - head * 1.5),
+                right = number(center.x + head * 1.5),
+                bottom = number(rect.y + rect.height),
+                stroke = stroke(style),
+            ));
+        }
+        NodeKind::Database => {
+            let rx: f64 = rect.width / 2.0;
+            let ry: f64 = (rect.height / 8.0).min(8.0);
+            parts.push(format!(
+                "<path d=\"M{left},{top} A{rx},{ry} 0 0 0 {right},{top} A{rx},{ry} 0 0 0 {left},{top} V{bottom} A{rx},{ry} 0 0 0 {right},{bottom} V{top}\" fill=\"{fill}\" stroke=\"{stroke}\"/>",
+                left = number(rect.x),
+                right = number(rect.x + rect.width),
+                top = number(rect.y + ry),
+                bottom = number(rect.y + rect.height - ry),
+                rx = number(rx),
+                ry = number(ry),
+                fill = fill(style),
+                stroke = stroke(style),
+            ));
+        }
+        _ => parts.push(rectangle(rect, style)),
+    }
+
+    let anchor: Point = node.label_anchor.unwrap_or_else(|| {
+        if node.members.is_empty() {
+            rect.center()
+        } else {
+            Point {
+                x: rect.center().x,
+                y: rect.y + HEADER_HEIGHT / 2.0,
+            }
+        }
+    });
+    let is_abstract: bool = matches!(node.data.get("abstract"), Some(Value::Bool(true)));
+    parts.push(rich_text(
+        &anchor,
+        label,
+        node.rich_label.as_ref(),
+        "middle",
+        !is_abstract,
+    ));
+
+    if !node.members.is_empty() {
+        let separator: f64 = rect.y + HEADER_HEIGHT;
+        parts.push(format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\"/>",
+            number(rect.x),
+            number(separator),
+            number(rect.x + rect.width),
+            number(separator),
+            stroke(style)
+        ));
+        node.members
+            .iter()
+            .enumerate()
+            .for_each(|(i, member): (usize, &Member)| {
+                let y: f64 = separator + (i as f64 + 1.0) * MEMBER_HEIGHT - MEMBER_HEIGHT / 4.0;
+                if member.kind == MemberKind::Separator {
+                    parts.push(section(rect, y - MEMBER_HEIGHT / 4.0, member, style));
+                    return;
+                }
+                // UML underlines static members and slants abstract ones
+                let format: Format = Format {
+                    underline: member.is_static(),
+                    italic: member.is_abstract(),
+                    ..Format::default()
+                };
+                let plain: String = member_text(member);
+                let rich: Option<RichText> = (format != Format::default()).then(|| RichText {
+                    lines: vec![Line::Text(vec![Span {
+                        text: plain.clone(),
+                        format,
+                    }])],
+                });
+                parts.push(rich_text(
+                    &Point { x: rect.x + 8.0, y },
+                    &plain,
+                    rich.as_ref(),
+                    "start",
+                    false,
+                ));
+            });
+    }
+
+    format!(
+        "<g class=\"node\"{}>{}</g>",
+        text_color(style),
+        parts.join("")
+    )
+}
+
+/// A separator line across the node at `y`, with its title in the middle
+fn section(rect: &Rect, y: f64, member: &Member, style: &ComputedStyle) -> String {
+    let dashes: &str = match member.data.get("line") {
+        Some(Value::String(line)) if line == "dotted" => " stroke-dasharray=\"2 2\"",
+        Some(Value::String(line)) if line == "thick" => " stroke-width=\"2\"",
+        _ => "",
+    };
+    let rule = |offset: f64| -> String {
+        format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\"{}/>",
+            number(rect.x),
+            number(y + offset),
+            number(rect.x + rect.width),
+            number(y + offset),
+            stroke(style),
+            dashes
+        )
+    };
+    let mut parts: Vec<String> = match member.data.get("line") {
+        Some(Value::String(line)) if line == "double" => vec![rule(-1.5), rule(1.5)],
+        _ => vec![rule(0.0)],
+    };
+    if !member.name.is_empty() {
+        parts.push(text(
+            &Point {
+                x: rect.center().x,
+                y,
+            },
+            &member.name,
+            "middle",
+            false,
+        ));
+    }
+    parts.join("")
+}
+
+fn member_text(member: &Member) -> String {
+    let visibility: &str = match member.data.get("visibility") {
+        Some(Value::String(symbol)) => symbol,
+        _ => "",
+    };
+    match &member.type_name {
+        Some(type_name) => format!("{}{}: {}", visibility, member.name, type_name),
+        None => format!("{}{}", visibility, member.name),
+    }
+}
+
+fn rectangle(rect: &Rect, style: &ComputedStyle) -> String {
+    format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"{}\"/>",
+        number(rect.x),
+        number(rect.y),
+        number(rect.width),
+        number(rect.height),
+        fill(style),
+        stroke(style)
+    )
+}
+
+fn fill(style: &ComputedStyle) -> String {
+    escape(style.get("fill").unwrap_or("none"))
+}
+
+fn stroke(style: &ComputedStyle) -> String {
+    escape(style.get("stroke").unwrap_or("none"))
+}
+
+/// The fill an element's texts inherit, when its style colors them
+fn text_color(style: &ComputedStyle) -> String {
+    style
+        .get("color")
+        .map(|color: &str| format!(" fill=\"{}\"", escape(color)))
+        .unwrap_or_default()
+}
+
+fn text(at: &Point, content: &str, anchor: &str, bold: bool) -> String {
+    format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"{}\" dominant-baseline=\"middle\"{}>{}</text>",
+        number(at.x),
+        number(at.y),
+        anchor,
+        if bold { " font-weight=\"bold\"" } else { "" },
+        escape(content)
+    )
+}
+
+/// A label drawn with its formatting, line under line around `at`, when it
+/// has any, else as `text` draws it
+fn rich_text(at: &Point, plain: &str, rich: Option<&RichText>, anchor: &str, bold: bool) -> String {
+    let Some(rich) = rich else {
+        return text(at, plain, anchor, bold);
+    };
+    let first: f64 = -(rich.lines.len() as f64 - 1.0) / 2.0 * LINE_HEIGHT;
+    let lines: String = rich
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(i, line): (usize, &Line)| {
+            let (indent, content): (usize, String) = match line {
+                Line::Text(spans) => (0, tspans(spans, Format::default())),
+                Line::Bullet { depth, spans } => (
+                    depth - 1,
+                    format!("\u{2022} {}", tspans(spans, Format::default())),
+                ),
+                Line::Row { header, cells } => {
+                    let format: Format = Format {
+                        bold: *header,
+                        ..Format::default()
+                    };
+                    let cells: Vec<String> = cells
+                        .iter()
+                        .map(|cell: &Vec<Span>| tspans(cell, format))
+                        .collect();
+                    (0, cells.join(" | "))
+                }
+            };
+            format!(
+                "<tspan x=\"{}\" dy=\"{}\">{}</tspan>",
+                number(at.x + indent as f64 * FONT_SIZE),
+                number(if i == 0 { first } else { LINE_HEIGHT }),
+                content
+            )
+        })
+        .collect();
+
+    format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"{}\" dominant-baseline=\"middle\"{}>{}</text>",
+        number(at.x),
+        number(at.y),
+        anchor,
+        if bold { " font-weight=\"bold\"" } else { "" },
+        lines
+    )
+}
+
+/// Runs of text, each formatted as it says on top of `base`
+fn tspans(spans: &[Span], base: Format) -> String {
+    spans
+        .iter()
+        .map(|span: &Span| {
+            let format: Format = Format {
+                bold: base.bold || span.format.bold,
+                italic: base.italic || span.format.italic,
+                monospace: base.monospace || span.format.monospace,
+                underline: base.underline || span.format.underline,
+                strike: base.strike || span.format.strike,
+            };
+            let decorations: Vec<&str> = [
+                (format.underline, "underline"),
+                (format.strike, "line-through"),
+            ]
+            .into_iter()
+            .filter_map(|(on, name): (bool, &str)| on.then_some(name))
+            .collect();
+            let attributes: String = [
+                format.bold.then(|| " font-weight=\"bold\"".to_string()),
+                format.italic.then(|| " font-style=\"italic\"".to_string()),
+                format
+                    .monospace
+                    .then(|| " font-family=\"monospace\"".to_string()),
+                (!decorations.is_empty())
+                    .then(|| format!(" text-decoration=\"{}\"", decorations.join(" "))),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            match attributes.is_empty() {
+                true => escape(&span.text),
+                false => format!("<tspan{}>{}</tspan>", attributes, escape(&span.text)),
+            }
+        })
+        .collect()
+}
+
+/// Size of the drawing, covering every node, group, waypoint and label
+pub(crate) fn extent(graph: &Graph) -> (f64, f64) {
+    let rects = graph
+        .nodes
+        .values()
+        .filter_map(|node: &Node| node.geometry)
+        .chain(
+            graph
+                .groups
+                .values()
+                .filter_map(|group: &Group| group.geometry),
+        )
+        .chain(graph.edges.values().flat_map(|edge: &Edge| {
+            edge.layout
+                .labels
+                .iter()
+                .map(|label: &EdgeLabel| label.bounds)
+        }));
+    let points = graph
+        .edges
+        .values()
+        .flat_map(|edge: &Edge| edge.waypoints.iter().copied());
+
+    let (right, bottom): (f64, f64) = rects
+        .map(|rect: Rect| (rect.x + rect.width, rect.y + rect.height))
+        .chain(points.map(|p: Point| (p.x, p.y)))
+        .fold((0.0, 0.0), |(r, b): (f64, f64), (x, y): (f64, f64)| {
+            (r.max(x), b.max(y))
+        });
+    (right + MARGIN, bottom + MARGIN)
+}
+
+pub(crate) fn depth(graph: &Graph, id: &str) -> usize {
+    let mut depth: usize = 0;
+    let mut current: Option<&Id> = graph.groups.get(id).and_then(|g: &Group| g.parent.as_ref());
+    // Bounded in case of malformed parent cycles
+    while let Some(parent) = current
+        && depth < graph.groups.len()
+    {
+        depth += 1;
+        current = graph
+            .groups
+            .get(parent)
+            .and_then(|g: &Group| g.parent.as_ref());
+    }
+    depth
+}
+
+/// Coordinates are rounded to two decimals to keep the output readable
+pub(crate) fn number(value: f64) -> String {
+    let rounded: f64 = (value * 100.0).round() / 100.0;
+    format!("{}", rounded + 0.0)
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
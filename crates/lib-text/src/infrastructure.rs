@@ -0,0 +1,3 @@
+pub mod adapters;
+pub mod emitter;
+pub mod narration;
@@ -0,0 +1,2 @@
+pub mod narration_graph_emitter;
+pub mod text_graph_emitter;
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter, GraphEmitterError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::narration;
+
+/// Writes a Markdown report telling each element's relations in words
+#[derive(Default)]
+pub struct NarrationGraphEmitter {
+    options: EmitOptions,
+}
+
+impl NarrationGraphEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: EmitOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+#[async_trait]
+impl GraphEmitterAdapter for NarrationGraphEmitter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError> {
+        Ok(narration::narrate(&self.options.prepare(graph)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_emitter_adapter::GraphEmitterAdapter,
+        builder::GraphBuilder,
+        entities::{
+            edge::EdgeKind,
+            graph::{Graph, GraphKind},
+            node::NodeKind,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::narration_graph_emitter::NarrationGraphEmitter;
+
+    #[test]
+    fn test_narrates_relations_per_element() {
+        smol::block_on(async {
+            let graph: Graph = GraphBuilder::new(GraphKind::Class)
+                .title("Checkout")
+                .node("OrderService", NodeKind::Entity, "OrderService")
+                .node("PaymentGateway", NodeKind::Interface, "PaymentGateway")
+                .node("StripeGateway", NodeKind::Entity, "StripeGateway")
+                .node("OrderRepository", NodeKind::Entity, "OrderRepository")
+                .node("Ledger", NodeKind::Entity, "Ledger")
+                .edge(
+                    "OrderService",
+                    "PaymentGateway",
+                    EdgeKind::Association,
+                    Some("charges"),
+                )
+                .edge(
+                    "OrderService",
+                    "OrderRepository",
+                    EdgeKind::Composition,
+                    None,
+                )
+                .edge("OrderService", "Ledger", EdgeKind::Association, None)
+                .edge(
+                    "StripeGateway",
+                    "PaymentGateway",
+                    EdgeKind::Realization,
+                    None,
+                )
+                .build();
+
+            let output: String = NarrationGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                output,
+                "# Relationships in Checkout\n\n\
+                ## OrderService\n\n\
+                OrderService uses Ledger and PaymentGateway (charges) and owns OrderRepository.\n\n\
+                ## StripeGateway\n\n\
+                StripeGateway implements PaymentGateway.\n"
+            );
+        });
+    }
+
+    #[test]
+    fn test_says_when_there_are_no_relations() {
+        smol::block_on(async {
+            let graph: Graph = GraphBuilder::new(GraphKind::Class)
+                .node("A", NodeKind::Entity, "A")
+                .build();
+
+            let output: String = NarrationGraphEmitter::new()
+                .emit_graph(&graph)
+                .await
+                .unwrap();
+
+            assert_eq!(output, "# Relationships\n\nThe diagram has no relations.\n");
+        });
+    }
+}
@@ -0,0 +1,150 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_emitter_adapter::{EmitOptions, GraphEmitterAdapter, GraphEmitterError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::emitter;
+
+#[derive(Default)]
+pub struct TextGraphEmitter {
+    options: EmitOptions,
+}
+
+impl TextGraphEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: EmitOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+#[async_trait]
+impl GraphEmitterAdapter for TextGraphEmitter {
+    async fn emit_graph(&self, graph: &Graph) -> Result<String, GraphEmitterError> {
+        Ok(emitter::describe(&self.options.prepare(graph)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_emitter_adapter::GraphEmitterAdapter,
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            group::Group,
+            id::Text,
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::text_graph_emitter::TextGraphEmitter;
+
+    fn node(id: &str, kind: NodeKind, members: &[(&str, MemberKind)]) -> Node {
+        Node {
+            id: id.into(),
+            kind,
+            label: Some(id.into()),
+            members: members
+                .iter()
+                .map(|(name, kind): &(&str, MemberKind)| Member {
+                    name: name.to_string(),
+                    kind: kind.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn edge(id: &str, from: &str, to: &str, kind: EdgeKind, label: Option<&str>) -> Edge {
+        Edge {
+            id: id.into(),
+            from: from.into(),
+            to: to.into(),
+            kind,
+            label: label.map(Text::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_describes_elements_packages_and_relations() {
+        smol::block_on(async {
+            let mut graph: Graph = Graph {
+                kind: GraphKind::Class,
+                ..Default::default()
+            };
+            graph.metadata.title = Some("Accounts".to_string());
+            [
+                node(
+                    "User",
+                    NodeKind::Entity,
+                    &[
+                        ("id", MemberKind::Field),
+                        ("name", MemberKind::Field),
+                        ("email", MemberKind::Field),
+                        ("rename", MemberKind::Method),
+                    ],
+                ),
+                node("Auth", NodeKind::Interface, &[]),
+                node("Person", NodeKind::Entity, &[]),
+                node(
+                    "Role",
+                    NodeKind::Enum,
+                    &[
+                        ("USER", MemberKind::EnumValue),
+                        ("ADMIN", MemberKind::EnumValue),
+                    ],
+                ),
+                Node {
+                    parent: Some("billing".into()),
+                    ..node("Invoice", NodeKind::Entity, &[])
+                },
+            ]
+            .into_iter()
+            .for_each(|node: Node| {
+                graph.nodes.insert(node.id.clone(), node);
+            });
+            [
+                edge("1", "User", "Auth", EdgeKind::Dependency, Some("login")),
+                edge("2", "User", "Person", EdgeKind::Inheritance, None),
+                edge("3", "User", "Role", EdgeKind::Association, None),
+                edge("4", "Person", "Role", EdgeKind::Association, None),
+            ]
+            .into_iter()
+            .for_each(|edge: Edge| {
+                graph.edges.insert(edge.id.clone(), edge);
+            });
+            graph.groups.insert(
+                "billing".into(),
+                Group {
+                    id: "billing".into(),
+                    label: Some("Billing".into()),
+                    ..Default::default()
+                },
+            );
+
+            assert_eq!(
+                TextGraphEmitter::new().emit_graph(&graph).await.unwrap(),
+                [
+                    "Class diagram \"Accounts\" with 5 elements and 4 relations.",
+                    "Package Billing contains Invoice.",
+                    "Interface Auth has no members or relations.",
+                    "Class Invoice has no members or relations.",
+                    "Class Person is associated with Role.",
+                    "Enum Role has 2 values: USER and ADMIN.",
+                    "Class User has 3 fields and 1 method, depends on Auth (login), inherits from Person and is associated with Role.",
+                    "",
+                ]
+                .join("\n")
+            );
+        });
+    }
+}
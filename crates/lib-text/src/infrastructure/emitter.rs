@@ -0,0 +1,270 @@
+use std::collections::BTreeSet;
+
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::Id,
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+};
+
+/// Describes a graph in plain sentences, one per line, for alt text and
+/// screen readers: an overview, what each package holds, then every node's
+/// members and relations. Elements come in name order so the text is stable.
+pub fn describe(graph: &Graph) -> String {
+    let mut sentences: Vec<String> = vec![overview(graph)];
+
+    let mut groups: Vec<&Group> = graph.groups.values().collect();
+    groups.sort_by(|a: &&Group, b: &&Group| group_name(a).cmp(group_name(b)));
+    sentences.extend(
+        groups
+            .into_iter()
+            .map(|group: &Group| contents(graph, group)),
+    );
+
+    let mut nodes: Vec<&Node> = graph.nodes.values().collect();
+    nodes.sort_by(|a: &&Node, b: &&Node| node_name(a).cmp(node_name(b)).then(a.id.cmp(&b.id)));
+    sentences.extend(nodes.into_iter().map(|node: &Node| relations(graph, node)));
+
+    sentences.join("\n") + "\n"
+}
+
+fn overview(graph: &Graph) -> String {
+    let kind: &str = match graph.kind {
+        GraphKind::Generic => "Diagram",
+        GraphKind::Class => "Class diagram",
+        GraphKind::EntityRelationship => "Entity-relationship diagram",
+        GraphKind::Sequence => "Sequence diagram",
+        GraphKind::Network => "Network diagram",
+        GraphKind::MindMap => "Mind map",
+        GraphKind::Wbs => "Work breakdown structure",
+        GraphKind::Wireframe => "Wireframe",
+        GraphKind::State => "State diagram",
+        GraphKind::Flowchart => "Flowchart",
+        GraphKind::UseCase => "Use case diagram",
+    };
+    let title: String = graph
+        .metadata
+        .title
+        .as_deref()
+        .map(|title: &str| format!(" \"{}\"", title))
+        .unwrap_or_default();
+
+    format!(
+        "{}{} with {} and {}.",
+        kind,
+        title,
+        count(graph.nodes.len(), "element", "elements"),
+        count(graph.edges.len(), "relation", "relations")
+    )
+}
+
+/// A package's nodes and nested packages, whether it lists them or they
+/// name it as their parent
+fn contents(graph: &Graph, group: &Group) -> String {
+    let ids: BTreeSet<&Id> = group
+        .children
+        .iter()
+        .chain(
+            graph
+                .nodes
+                .values()
+                .filter(|node: &&Node| node.parent.as_ref() == Some(&group.id))
+                .map(|node: &Node| &node.id),
+        )
+        .chain(
+            graph
+                .groups
+                .values()
+                .filter(|child: &&Group| child.parent.as_ref() == Some(&group.id))
+                .map(|child: &Group| &child.id),
+        )
+        .collect();
+
+    let mut names: Vec<String> = ids
+        .into_iter()
+        .filter_map(
+            |id: &Id| match (graph.nodes.get(id), graph.groups.get(id)) {
+                (Some(node), _) => Some(node_name(node).to_string()),
+                (None, Some(child)) => Some(format!("package {}", group_name(child))),
+                (None, None) => None,
+            },
+        )
+        .collect();
+    names.sort();
+
+    match names.is_empty() {
+        true => format!("Package {} is empty.", group_name(group)),
+        false => format!("Package {} contains {}.", group_name(group), join(&names)),
+    }
+}
+
+/// What a node holds and what it points at, as in "Class User has 3 fields
+/// and depends on Auth."
+fn relations(graph: &Graph, node: &Node) -> String {
+    let mut clauses: Vec<String> = members(node).into_iter().collect();
+
+    let mut edges: Vec<&Edge> = graph
+        .edges
+        .values()
+        .filter(|edge: &&Edge| edge.from == node.id)
+        .collect();
+    edges.sort_by(|a: &&Edge, b: &&Edge| {
+        name(graph, &a.to)
+            .cmp(name(graph, &b.to))
+            .then(a.id.cmp(&b.id))
+    });
+
+    // One clause per kind of relation, in the order they first appear
+    let mut verbs: Vec<(&str, Vec<String>)> = Vec::new();
+    edges.into_iter().for_each(|edge: &Edge| {
+        let target: String = match edge.label.as_deref().map(str::trim) {
+            Some(label) if !label.is_empty() => format!("{} ({})", name(graph, &edge.to), label),
+            _ => name(graph, &edge.to).to_string(),
+        };
+        match verbs
+            .iter_mut()
+            .find(|(verb, _): &&mut (&str, Vec<String>)| *verb == verb_of(&edge.kind))
+        {
+            Some((_, targets)) => targets.push(target),
+            None => verbs.push((verb_of(&edge.kind), vec![target])),
+        }
+    });
+    clauses.extend(
+        verbs
+            .into_iter()
+            .map(|(verb, targets): (&str, Vec<String>)| format!("{} {}", verb, join(&targets))),
+    );
+
+    match clauses.is_empty() {
+        true => format!(
+            "{} {} has no members or relations.",
+            kind_of(graph, node),
+            node_name(node)
+        ),
+        false => format!(
+            "{} {} {}.",
+            kind_of(graph, node),
+            node_name(node),
+            join(&clauses)
+        ),
+    }
+}
+
+/// Counts fields and methods, or lists the values of an enum
+fn members(node: &Node) -> Option<String> {
+    let of = |kind: MemberKind| -> Vec<&Member> {
+        node.members
+            .iter()
+            .filter(|member: &&Member| member.kind == kind)
+            .collect()
+    };
+    let values: Vec<String> = of(MemberKind::EnumValue)
+        .into_iter()
+        .map(|member: &Member| member.name.clone())
+        .collect();
+    if !values.is_empty() {
+        return Some(format!(
+            "has {}: {}",
+            count(values.len(), "value", "values"),
+            join(&values)
+        ));
+    }
+
+    let counts: Vec<String> = [
+        (of(MemberKind::Field).len(), "field", "fields"),
+        (of(MemberKind::Method).len(), "method", "methods"),
+    ]
+    .into_iter()
+    .filter(|(n, _, _): &(usize, &str, &str)| *n > 0)
+    .map(|(n, one, many): (usize, &str, &str)| count(n, one, many))
+    .collect();
+
+    (!counts.is_empty()).then(|| format!("has {}", join(&counts)))
+}
+
+fn verb_of(kind: &EdgeKind) -> &'static str {
+    match kind {
+        EdgeKind::Association => "is associated with",
+        EdgeKind::Dependency => "depends on",
+        EdgeKind::Inheritance => "inherits from",
+        EdgeKind::Realization => "implements",
+        EdgeKind::Aggregation => "aggregates",
+        EdgeKind::Composition => "is composed of",
+        EdgeKind::Flow => "leads to",
+        EdgeKind::Undirected => "is connected to",
+        EdgeKind::Custom(_) => "relates to",
+    }
+}
+
+fn kind_of(graph: &Graph, node: &Node) -> String {
+    match &node.kind {
+        NodeKind::Entity if graph.kind == GraphKind::EntityRelationship => "Entity".to_string(),
+        NodeKind::Entity => "Class".to_string(),
+        NodeKind::Interface => "Interface".to_string(),
+        NodeKind::Actor => "Actor".to_string(),
+        NodeKind::Component => "Component".to_string(),
+        NodeKind::Database => "Database".to_string(),
+        NodeKind::Enum => "Enum".to_string(),
+        NodeKind::Group => "Group".to_string(),
+        NodeKind::Annotation => "Note".to_string(),
+        NodeKind::Deployment(kind) => capitalized(kind.keyword()),
+        NodeKind::Custom(keyword) => capitalized(keyword),
+    }
+}
+
+fn capitalized(keyword: &str) -> String {
+    let mut chars: std::str::Chars = keyword.chars();
+    chars
+        .next()
+        .map(|first: char| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_else(|| "Element".to_string())
+}
+
+pub(crate) fn name<'a>(graph: &'a Graph, id: &'a Id) -> &'a str {
+    graph.nodes.get(id).map_or(id.as_str(), node_name)
+}
+
+pub(crate) fn node_name(node: &Node) -> &str {
+    node.label.as_deref().unwrap_or(&node.id)
+}
+
+fn group_name(group: &Group) -> &str {
+    group.label.as_deref().unwrap_or(&group.id)
+}
+
+fn count(n: usize, one: &str, many: &str) -> String {
+    match n {
+        0 => format!("no {}", many),
+        1 => format!("1 {}", one),
+        n => format!("{} {}", n, many),
+    }
+}
+
+/// "a", "a and b", "a, b and c"
+pub(crate) fn join(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_joins_lists_and_counts_in_words() {
+        let items: Vec<String> = ["a", "b", "c"].map(str::to_string).to_vec();
+
+        assert_eq!(join(&items[..1]), "a");
+        assert_eq!(join(&items[..2]), "a and b");
+        assert_eq!(join(&items), "a, b and c");
+        assert_eq!(count(0, "field", "fields"), "no fields");
+        assert_eq!(count(1, "field", "fields"), "1 field");
+    }
+}
@@ -0,0 +1,86 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::Graph,
+    node::Node,
+};
+
+use crate::infrastructure::emitter::{join, name, node_name};
+
+/// Tells a diagram's relations as a Markdown report with one section per
+/// element, as in "OrderService uses PaymentGateway and owns
+/// OrderRepository." Elements pointing at nothing are left out; sections and
+/// targets come in name order so the report is stable.
+pub fn narrate(graph: &Graph) -> String {
+    let heading: String = match &graph.metadata.title {
+        Some(title) => format!("# Relationships in {}", title),
+        None => "# Relationships".to_string(),
+    };
+
+    let mut nodes: Vec<&Node> = graph.nodes.values().collect();
+    nodes.sort_by(|a: &&Node, b: &&Node| node_name(a).cmp(node_name(b)).then(a.id.cmp(&b.id)));
+    let sections: Vec<String> = nodes
+        .into_iter()
+        .filter_map(|node: &Node| {
+            sentence(graph, node)
+                .map(|sentence: String| format!("## {}\n\n{}", node_name(node), sentence))
+        })
+        .collect();
+
+    match sections.is_empty() {
+        true => format!("{}\n\nThe diagram has no relations.\n", heading),
+        false => format!("{}\n\n{}\n", heading, sections.join("\n\n")),
+    }
+}
+
+/// Every relation leaving `node` in one sentence, a clause per verb
+fn sentence(graph: &Graph, node: &Node) -> Option<String> {
+    let mut edges: Vec<&Edge> = graph
+        .edges
+        .values()
+        .filter(|edge: &&Edge| edge.from == node.id)
+        .collect();
+    edges.sort_by(|a: &&Edge, b: &&Edge| {
+        name(graph, &a.to)
+            .cmp(name(graph, &b.to))
+            .then(a.id.cmp(&b.id))
+    });
+
+    // Clauses follow the order verbs first appear in
+    let mut verbs: Vec<(&str, Vec<String>)> = Vec::new();
+    edges.into_iter().for_each(|edge: &Edge| {
+        let target: String = match edge.label.as_deref().map(str::trim) {
+            Some(label) if !label.is_empty() => format!("{} ({})", name(graph, &edge.to), label),
+            _ => name(graph, &edge.to).to_string(),
+        };
+        match verbs
+            .iter_mut()
+            .find(|(verb, _): &&mut (&str, Vec<String>)| *verb == verb_of(&edge.kind))
+        {
+            Some((_, targets)) => targets.push(target),
+            None => verbs.push((verb_of(&edge.kind), vec![target])),
+        }
+    });
+    if verbs.is_empty() {
+        return None;
+    }
+
+    let clauses: Vec<String> = verbs
+        .into_iter()
+        .map(|(verb, targets): (&str, Vec<String>)| format!("{} {}", verb, join(&targets)))
+        .collect();
+    Some(format!("{} {}.", node_name(node), join(&clauses)))
+}
+
+fn verb_of(kind: &EdgeKind) -> &'static str {
+    match kind {
+        EdgeKind::Association => "uses",
+        EdgeKind::Dependency => "depends on",
+        EdgeKind::Inheritance => "extends",
+        EdgeKind::Realization => "implements",
+        EdgeKind::Aggregation => "holds",
+        EdgeKind::Composition => "owns",
+        EdgeKind::Flow => "hands over to",
+        EdgeKind::Undirected => "is linked to",
+        EdgeKind::Custom(_) => "relates to",
+    }
+}
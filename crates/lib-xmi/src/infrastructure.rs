@@ -0,0 +1,4 @@
+pub mod adapters;
+pub(crate) mod models;
+pub(crate) mod parser;
+pub(crate) mod transformer;
@@ -0,0 +1 @@
+pub mod xmi_graph_gateway;
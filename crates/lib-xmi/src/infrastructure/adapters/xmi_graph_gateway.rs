@@ -0,0 +1,291 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, XmiParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct XmiGraphGateway;
+
+impl XmiGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for XmiGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_xmi(input)
+            .map_err(GraphGatewayError::from)
+            .map(|elements| transformer::GraphBuilder::new().build(elements))
+    }
+}
+
+impl From<XmiParseError> for GraphGatewayError {
+    fn from(err: XmiParseError) -> Self {
+        match err {
+            XmiParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "xmi".into(),
+                message,
+                line,
+                column,
+            },
+            XmiParseError::Semantic(msg) => GraphGatewayError::Semantic {
+                source: "xmi".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            group::Group,
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::xmi_graph_gateway::XmiGraphGateway;
+
+    const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+    <xmi:XMI xmlns:xmi="http://www.omg.org/spec/XMI/20131001" xmlns:uml="http://www.omg.org/spec/UML/20131001">
+      <uml:Model xmi:id="model" name="Shop">
+        <packagedElement xmi:type="uml:Package" xmi:id="pkg" name="domain">
+          <packagedElement xmi:type="uml:Class" xmi:id="entity" name="Entity" isAbstract="true"/>
+          <packagedElement xmi:type="uml:Class" xmi:id="order" name="Order">
+            <generalization xmi:id="g1" general="entity"/>
+            <interfaceRealization xmi:id="r1" supplier="payable" contract="payable"/>
+            <ownedAttribute xmi:id="order_total" name="total" visibility="private">
+              <type href="http://www.omg.org/spec/UML/20131001/PrimitiveTypes.xmi#Real"/>
+            </ownedAttribute>
+            <ownedAttribute xmi:id="order_lines" name="lines" type="line" aggregation="composite" association="contains">
+              <lowerValue xmi:type="uml:LiteralInteger" xmi:id="lv"/>
+              <upperValue xmi:type="uml:LiteralUnlimitedNatural" xmi:id="uv" value="*"/>
+            </ownedAttribute>
+            <ownedOperation xmi:id="op" name="cancel" visibility="public">
+              <ownedParameter xmi:id="p1" name="reason" type="status"/>
+              <ownedParameter xmi:id="p2" direction="return">
+                <type xmi:idref="status"/>
+              </ownedParameter>
+            </ownedOperation>
+          </packagedElement>
+          <packagedElement xmi:type="uml:Class" xmi:id="line" name="OrderLine"/>
+          <packagedElement xmi:type="uml:Association" xmi:id="contains" name="contains" memberEnd="order_lines line_order">
+            <ownedEnd xmi:id="line_order" name="order" type="order" association="contains"/>
+          </packagedElement>
+        </packagedElement>
+        <packagedElement xmi:type="uml:Interface" xmi:id="payable" name="Payable"/>
+        <packagedElement xmi:type="uml:Enumeration" xmi:id="status" name="Status">
+          <ownedLiteral xmi:id="l1" name="OPEN"/>
+          <ownedLiteral xmi:id="l2" name="CLOSED"/>
+        </packagedElement>
+        <packagedElement xmi:type="uml:Usage" xmi:id="u1" client="order" supplier="status"/>
+      </uml:Model>
+    </xmi:XMI>
+    "#;
+
+    async fn parse_model() -> Graph {
+        XmiGraphGateway::new()
+            .read_graph_from_raw_input(MODEL)
+            .await
+            .expect("Failed to parse valid XMI")
+    }
+
+    #[test]
+    fn test_parse_classifiers_and_packages() {
+        smol::block_on(async {
+            let graph: Graph = parse_model().await;
+
+            assert_eq!(graph.kind, GraphKind::Class);
+            assert_eq!(graph.metadata.title.as_deref(), Some("Shop"));
+            assert_eq!(graph.nodes.len(), 5);
+
+            let package: &Group = graph.groups.get("pkg").expect("Missing domain package");
+            assert_eq!(package.label.as_deref(), Some("domain"));
+            assert_eq!(
+                package.children,
+                vec![
+                    "entity".to_string(),
+                    "order".to_string(),
+                    "line".to_string()
+                ]
+            );
+
+            let entity: &Node = graph.nodes.get("entity").expect("Missing Entity");
+            assert_eq!(entity.data.get("abstract"), Some(&Value::Bool(true)));
+            assert_eq!(entity.parent.as_deref(), Some("pkg"));
+
+            assert_eq!(
+                graph.nodes.get("payable").map(|n: &Node| &n.kind),
+                Some(&NodeKind::Interface)
+            );
+
+            let status: &Node = graph.nodes.get("status").expect("Missing Status");
+            assert_eq!(status.kind, NodeKind::Enum);
+            assert_eq!(
+                status
+                    .members
+                    .iter()
+                    .map(|m: &Member| m.name.as_str())
+                    .collect::<Vec<&str>>(),
+                vec!["OPEN", "CLOSED"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_attributes_and_operations() {
+        smol::block_on(async {
+            let graph: Graph = parse_model().await;
+            let order: &Node = graph.nodes.get("order").expect("Missing Order");
+
+            assert_eq!(
+                order.members.len(),
+                2,
+                "Association ends are not attributes"
+            );
+
+            let total: &Member = &order.members[0];
+            assert_eq!(total.kind, MemberKind::Field);
+            assert_eq!(total.type_name.as_deref(), Some("Real"));
+            assert_eq!(
+                total.data.get("visibility"),
+                Some(&Value::String("private".to_string()))
+            );
+
+            let cancel: &Member = &order.members[1];
+            assert_eq!(cancel.kind, MemberKind::Method);
+            assert_eq!(cancel.type_name.as_deref(), Some("Status"));
+            assert_eq!(
+                cancel.data.get("parameters"),
+                Some(&Value::List(vec![Value::String(
+                    "reason: Status".to_string()
+                )]))
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_relationships() {
+        smol::block_on(async {
+            let graph: Graph = parse_model().await;
+
+            assert_eq!(
+                find_edge(&graph, "order", "entity").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Inheritance)
+            );
+            assert_eq!(
+                find_edge(&graph, "order", "payable").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Realization)
+            );
+            assert_eq!(
+                find_edge(&graph, "order", "status").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Dependency)
+            );
+
+            let composition: &Edge =
+                find_edge(&graph, "order", "line").expect("Missing composition");
+            assert_eq!(composition.kind, EdgeKind::Composition);
+            assert_eq!(composition.label.as_deref(), Some("contains"));
+            assert_eq!(composition.to_cardinality.as_deref(), Some("0..*"));
+            assert_eq!(
+                composition.data.get("to_role"),
+                Some(&Value::String("lines".to_string()))
+            );
+        });
+    }
+
+    #[test]
+    fn test_malformed_xml_is_reported_with_location() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = XmiGraphGateway::new()
+                .read_graph_from_raw_input("<xmi:XMI>\n  <broken>\n</xmi:XMI>")
+                .await;
+
+            assert!(
+                matches!(result, Err(GraphGatewayError::Parse { ref source, line, .. }) if source == "xmi" && line > 0),
+                "Expected an XMI parse error, got {:?}",
+                result
+            );
+        });
+    }
+
+    #[test]
+    fn test_document_without_uml_elements_is_rejected() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = XmiGraphGateway::new()
+                .read_graph_from_raw_input("<root><child/></root>")
+                .await;
+
+            assert!(matches!(result, Err(GraphGatewayError::Semantic { .. })));
+        });
+    }
+
+    #[test]
+    fn test_deep_packages_are_rejected() {
+        smol::block_on(async {
+            let nested = |depth: usize| -> String {
+                format!(
+                    "<xmi:XMI xmlns:xmi=\"{}\" xmlns:uml=\"{}\">\n{}{}</xmi:XMI>",
+                    "http://www.omg.org/spec/XMI/20131001",
+                    "http://www.omg.org/spec/UML/20131001",
+                    (0..depth)
+                        .map(|i: usize| format!(
+                            "<packagedElement xmi:type=\"uml:Package\" xmi:id=\"p{}\">\n",
+                            i
+                        ))
+                        .collect::<String>(),
+                    "</packagedElement>".repeat(depth)
+                )
+            };
+            assert!(
+                XmiGraphGateway::new()
+                    .read_graph_from_raw_input(&nested(63))
+                    .await
+                    .is_ok()
+            );
+
+            match XmiGraphGateway::new()
+                .read_graph_from_raw_input(&nested(64))
+                .await
+            {
+                Err(GraphGatewayError::Parse { message, line, .. }) => {
+                    assert_eq!(message, "Elements nest past the depth limit of 64");
+                    assert_eq!(line, 65);
+                }
+                other => panic!("Expected GraphGatewayError::Parse, got {:?}", other),
+            }
+            assert!(
+                XmiGraphGateway::new()
+                    .read_graph_from_raw_input(&nested(20_000))
+                    .await
+                    .is_err()
+            );
+        });
+    }
+
+    fn find_edge<'a>(graph: &'a Graph, from: &str, to: &str) -> Option<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .find(|e: &&Edge| e.from == from && e.to == to)
+    }
+}
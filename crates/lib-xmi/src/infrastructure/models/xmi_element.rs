@@ -0,0 +1,60 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmiElement {
+    Model {
+        name: Option<String>,
+    },
+    Package {
+        id: String,
+        name: Option<String>,
+        parent: Option<String>,
+    },
+    Classifier {
+        id: String,
+        kind: String,
+        name: Option<String>,
+        parent: Option<String>,
+        is_abstract: bool,
+        attributes: Vec<XmiProperty>,
+        operations: Vec<XmiOperation>,
+        literals: Vec<String>,
+        generalizations: Vec<String>,
+        realizations: Vec<String>,
+    },
+    Association {
+        name: Option<String>,
+        ends: Vec<XmiProperty>,
+    },
+    Dependency {
+        kind: String,
+        name: Option<String>,
+        client: String,
+        supplier: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmiProperty {
+    pub name: Option<String>,
+    pub type_ref: Option<String>,
+    pub visibility: Option<String>,
+    pub aggregation: Option<String>,
+    pub lower: Option<String>,
+    pub upper: Option<String>,
+    pub association: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmiOperation {
+    pub name: String,
+    pub visibility: Option<String>,
+    pub is_static: bool,
+    pub is_abstract: bool,
+    pub parameters: Vec<XmiParameter>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmiParameter {
+    pub name: Option<String>,
+    pub type_ref: Option<String>,
+    pub direction: Option<String>,
+}
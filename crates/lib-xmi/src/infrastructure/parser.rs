@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use lib_core::nesting;
+use roxmltree::{Document, Node};
+
+use crate::infrastructure::models::xmi_element::{
+    XmiElement, XmiOperation, XmiParameter, XmiProperty,
+};
+
+const CLASSIFIER_TYPES: [&str; 6] = [
+    "Class",
+    "Interface",
+    "Enumeration",
+    "DataType",
+    "Component",
+    "AssociationClass",
+];
+const DEPENDENCY_TYPES: [&str; 5] = [
+    "Dependency",
+    "Usage",
+    "Abstraction",
+    "Realization",
+    "InterfaceRealization",
+];
+
+/// How deep elements may nest, packages and their classifiers included
+const MAX_DEPTH: usize = 64;
+
+pub fn parse_xmi(input: &str) -> Result<Vec<XmiElement>, XmiParseError> {
+    if let Some((line, column)) = nesting::deepest_element(input, MAX_DEPTH) {
+        return Err(XmiParseError::Syntax {
+            message: format!("Elements nest past the depth limit of {}", MAX_DEPTH),
+            line,
+            column,
+        });
+    }
+    let document: Document = Document::parse(input).map_err(XmiParseError::from)?;
+    let index: HashMap<&str, Node> = document
+        .descendants()
+        .filter_map(|node: Node| xmi_attribute(node, "id").map(|id: &str| (id, node)))
+        .collect();
+
+    let mut elements: Vec<XmiElement> = Vec::new();
+    visit(document.root_element(), None, &index, &mut elements);
+
+    if elements.is_empty() {
+        return Err(XmiParseError::Semantic(
+            "Document contains no UML model elements".to_string(),
+        ));
+    }
+
+    Ok(elements)
+}
+
+fn visit(
+    node: Node,
+    parent: Option<String>,
+    index: &HashMap<&str, Node>,
+    elements: &mut Vec<XmiElement>,
+) {
+    let element_type: Option<&str> = uml_type(node);
+    let id: Option<String> = xmi_attribute(node, "id").map(str::to_string);
+    let name: Option<String> = node.attribute("name").map(str::to_string);
+
+    let child_parent: Option<String> = match (element_type, &id) {
+        (Some("Model"), _) => {
+            elements.push(XmiElement::Model { name });
+            parent
+        }
+        (Some("Package"), Some(package_id)) => {
+            elements.push(XmiElement::Package {
+                id: package_id.clone(),
+                name,
+                parent: parent.clone(),
+            });
+            Some(package_id.clone())
+        }
+        (Some(kind), Some(classifier_id)) if CLASSIFIER_TYPES.contains(&kind) => {
+            elements.push(parse_classifier(node, kind, classifier_id, parent.clone()));
+            if kind == "AssociationClass" {
+                elements.push(parse_association(node, index));
+            }
+            parent
+        }
+        (Some("Association"), _) => {
+            elements.push(parse_association(node, index));
+            parent
+        }
+        (Some(kind), _)
+            if DEPENDENCY_TYPES.contains(&kind) && node.has_tag_name("packagedElement") =>
+        {
+            if let (Some(client), Some(supplier)) =
+                (reference(node, "client"), reference(node, "supplier"))
+            {
+                elements.push(XmiElement::Dependency {
+                    kind: kind.to_string(),
+                    name,
+                    client,
+                    supplier,
+                });
+            }
+            parent
+        }
+        _ => parent,
+    };
+
+    node.children()
+        .filter(|child: &Node| {
+            child.is_element()
+                && matches!(
+                    child.tag_name().name(),
+                    "XMI" | "Model" | "packagedElement" | "nestedClassifier" | "ownedType"
+                )
+        })
+        .for_each(|child: Node| visit(child, child_parent.clone(), index, elements));
+}
+
+fn parse_classifier(node: Node, kind: &str, id: &str, parent: Option<String>) -> XmiElement {
+    XmiElement::Classifier {
+        id: id.to_string(),
+        kind: kind.to_string(),
+        name: node.attribute("name").map(str::to_string),
+        parent,
+        is_abstract: node.attribute("isAbstract") == Some("true"),
+        attributes: children_named(node, "ownedAttribute")
+            .map(parse_property)
+            .collect(),
+        operations: children_named(node, "ownedOperation")
+            .map(parse_operation)
+            .collect(),
+        literals: children_named(node, "ownedLiteral")
+            .filter_map(|literal: Node| literal.attribute("name").map(str::to_string))
+            .collect(),
+        generalizations: children_named(node, "generalization")
+            .filter_map(|g: Node| reference(g, "general"))
+            .collect(),
+        realizations: children_named(node, "interfaceRealization")
+            .filter_map(|r: Node| reference(r, "contract").or_else(|| reference(r, "supplier")))
+            .collect(),
+    }
+}
+
+fn parse_association(node: Node, index: &HashMap<&str, Node>) -> XmiElement {
+    let mut member_ends: Vec<String> = node
+        .attribute("memberEnd")
+        .map(|ends: &str| ends.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default();
+    member_ends.extend(
+        children_named(node, "memberEnd")
+            .filter_map(|end: Node| xmi_attribute(end, "idref").map(str::to_string)),
+    );
+
+    let ends: Vec<XmiProperty> = if member_ends.is_empty() {
+        children_named(node, "ownedEnd")
+            .map(parse_property)
+            .collect()
+    } else {
+        member_ends
+            .iter()
+            .filter_map(|end: &String| index.get(end.as_str()).copied())
+            .map(parse_property)
+            .collect()
+    };
+
+    XmiElement::Association {
+        name: node.attribute("name").map(str::to_string),
+        ends,
+    }
+}
+
+fn parse_property(node: Node) -> XmiProperty {
+    XmiProperty {
+        name: node.attribute("name").map(str::to_string),
+        type_ref: reference(node, "type"),
+        visibility: node.attribute("visibility").map(str::to_string),
+        aggregation: node.attribute("aggregation").map(str::to_string),
+        lower: multiplicity(node, "lowerValue"),
+        upper: multiplicity(node, "upperValue"),
+        association: node.attribute("association").map(str::to_string),
+    }
+}
+
+fn parse_operation(node: Node) -> XmiOperation {
+    XmiOperation {
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        visibility: node.attribute("visibility").map(str::to_string),
+        is_static: node.attribute("isStatic") == Some("true"),
+        is_abstract: node.attribute("isAbstract") == Some("true"),
+        parameters: children_named(node, "ownedParameter")
+            .map(|parameter: Node| XmiParameter {
+                name: parameter.attribute("name").map(str::to_string),
+                type_ref: reference(parameter, "type"),
+                direction: parameter.attribute("direction").map(str::to_string),
+            })
+            .collect(),
+    }
+}
+
+fn multiplicity(node: Node, child: &str) -> Option<String> {
+    children_named(node, child)
+        .next()
+        .map(|value: Node| value.attribute("value").unwrap_or("0").to_string())
+}
+
+/// Resolves a reference that XMI exporters write either as an attribute
+/// (`type="id"`) or as a child element (`<type xmi:idref="id"/>`, `<type href="...#String"/>`)
+fn reference(node: Node, name: &str) -> Option<String> {
+    node.attribute(name)
+        .map(|value: &str| value.split_whitespace().next().unwrap_or(value).to_string())
+        .or_else(|| {
+            children_named(node, name).next().and_then(|child: Node| {
+                xmi_attribute(child, "idref")
+                    .or_else(|| {
+                        child
+                            .attribute("href")
+                            .and_then(|href: &str| href.rsplit('#').next())
+                    })
+                    .map(str::to_string)
+            })
+        })
+}
+
+fn children_named<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child: &Node| child.is_element() && child.tag_name().name() == name)
+}
+
+fn uml_type<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    xmi_attribute(node, "type")
+        .map(|t: &str| t.rsplit(':').next().unwrap_or(t))
+        .or_else(|| {
+            node.tag_name()
+                .namespace()
+                .filter(|ns: &&str| ns.contains("UML"))
+                .map(|_| node.tag_name().name())
+        })
+}
+
+fn xmi_attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|attribute: &roxmltree::Attribute| {
+            attribute.name() == name
+                && attribute
+                    .namespace()
+                    .is_some_and(|ns: &str| ns.to_uppercase().contains("XMI"))
+        })
+        .map(|attribute: roxmltree::Attribute| attribute.value())
+}
+
+#[derive(Debug)]
+pub enum XmiParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Semantic(String),
+}
+
+impl From<roxmltree::Error> for XmiParseError {
+    fn from(err: roxmltree::Error) -> Self {
+        let position: roxmltree::TextPos = err.pos();
+
+        XmiParseError::Syntax {
+            message: err.to_string(),
+            line: position.row as usize,
+            column: position.col as usize,
+        }
+    }
+}
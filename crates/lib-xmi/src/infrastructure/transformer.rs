@@ -0,0 +1,341 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::xmi_element::{
+    XmiElement, XmiOperation, XmiParameter, XmiProperty,
+};
+
+pub struct GraphBuilder {
+    graph: Graph,
+    names: HashMap<String, String>, // Maps XMI ids to the names of the elements they identify
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::Class,
+                ..Default::default()
+            },
+            names: HashMap::new(),
+        }
+    }
+
+    pub fn build(mut self, elements: Vec<XmiElement>) -> Graph {
+        elements
+            .iter()
+            .for_each(|element: &XmiElement| match element {
+                XmiElement::Package {
+                    id, name: Some(n), ..
+                }
+                | XmiElement::Classifier {
+                    id, name: Some(n), ..
+                } => {
+                    self.names.insert(id.clone(), n.clone());
+                }
+                _ => {}
+            });
+
+        elements.iter().for_each(|element: &XmiElement| {
+            self.process_element(element);
+        });
+        self.graph
+    }
+
+    fn process_element(&mut self, element: &XmiElement) {
+        match element {
+            XmiElement::Model { name } => {
+                self.graph.metadata.title = name.clone();
+            }
+            XmiElement::Package { id, name, parent } => {
+                if let Some(group) = parent
+                    .as_deref()
+                    .and_then(|p: &str| self.graph.groups.get_mut(p))
+                {
+                    group.children.push(Id::from(id.as_str()));
+                }
+
+                self.graph.groups.insert(
+                    Id::from(id.as_str()),
+                    Group {
+                        id: Id::from(id.as_str()),
+                        label: name.as_deref().map(Text::from),
+                        parent: parent.as_deref().map(Id::from),
+                        ..Default::default()
+                    },
+                );
+            }
+            XmiElement::Classifier {
+                id,
+                kind,
+                name,
+                parent,
+                is_abstract,
+                attributes,
+                operations,
+                literals,
+                generalizations,
+                realizations,
+            } => {
+                let parent: Option<Id> = parent
+                    .as_deref()
+                    .filter(|p: &&str| self.graph.groups.contains_key(*p))
+                    .map(Id::from);
+                if let Some(group) = parent.as_ref().and_then(|p| self.graph.groups.get_mut(p)) {
+                    group.children.push(Id::from(id.as_str()));
+                }
+
+                let node_kind: NodeKind = match kind.as_str() {
+                    "Interface" => NodeKind::Interface,
+                    "Enumeration" => NodeKind::Enum,
+                    "Component" => NodeKind::Component,
+                    "DataType" => NodeKind::Custom("datatype".to_string()),
+                    _ => NodeKind::Entity,
+                };
+
+                let mut members: Vec<Member> = attributes
+                    .iter()
+                    .filter(|a: &&XmiProperty| a.association.is_none())
+                    .map(|a: &XmiProperty| self.map_attribute(a))
+                    .collect();
+                members.extend(
+                    operations
+                        .iter()
+                        .map(|o: &XmiOperation| self.map_operation(o)),
+                );
+                members.extend(literals.iter().map(|literal: &String| Member {
+                    name: literal.clone(),
+                    kind: MemberKind::EnumValue,
+                    ..Default::default()
+                }));
+
+                let mut data: HashMap<Text, Value> = HashMap::new();
+                if *is_abstract {
+                    data.insert("abstract".into(), Value::Bool(true));
+                }
+
+                self.graph.nodes.insert(
+                    Id::from(id.as_str()),
+                    Node {
+                        id: Id::from(id.as_str()),
+                        kind: node_kind,
+                        label: name.as_deref().map(Text::from),
+                        members,
+                        data,
+                        parent,
+                        ..Default::default()
+                    },
+                );
+
+                generalizations.iter().for_each(|general: &String| {
+                    self.add_edge(id, general, EdgeKind::Inheritance, true, None);
+                });
+                realizations.iter().for_each(|contract: &String| {
+                    self.add_edge(id, contract, EdgeKind::Realization, true, None);
+                });
+            }
+            XmiElement::Association { name, ends } => {
+                let [first, second] = ends.as_slice() else {
+                    return;
+                };
+                let (Some(first_type), Some(second_type)) = (&first.type_ref, &second.type_ref)
+                else {
+                    return;
+                };
+
+                // The aggregation kind sits on the end typed by the part, so the whole is the other end
+                let (whole, part, kind): (&XmiProperty, &XmiProperty, EdgeKind) =
+                    match (first.aggregation.as_deref(), second.aggregation.as_deref()) {
+                        (_, Some("composite")) => (first, second, EdgeKind::Composition),
+                        (Some("composite"), _) => (second, first, EdgeKind::Composition),
+                        (_, Some("shared")) => (first, second, EdgeKind::Aggregation),
+                        (Some("shared"), _) => (second, first, EdgeKind::Aggregation),
+                        _ => (first, second, EdgeKind::Association),
+                    };
+                let directed: bool = kind != EdgeKind::Association;
+                let (from, to): (&String, &String) = if std::ptr::eq(whole, first) {
+                    (first_type, second_type)
+                } else {
+                    (second_type, first_type)
+                };
+
+                let edge_id: Id = self.add_edge(from, to, kind, directed, name.clone());
+                let Some(edge) = self.graph.edges.get_mut(edge_id.as_str()) else {
+                    return;
+                };
+
+                [("from", whole), ("to", part)].iter().for_each(
+                    |(side, end): &(&str, &XmiProperty)| {
+                        if let Some(cardinality) = cardinality_of(end) {
+                            match *side {
+                                "from" => edge.from_cardinality = Some(cardinality.into()),
+                                _ => edge.to_cardinality = Some(cardinality.into()),
+                            }
+                        }
+                        if let Some(role) = &end.name {
+                            edge.data.insert(
+                                format!("{}_role", side).into(),
+                                Value::String(role.clone()),
+                            );
+                        }
+                    },
+                );
+            }
+            XmiElement::Dependency {
+                kind,
+                name,
+                client,
+                supplier,
+            } => {
+                let edge_kind: EdgeKind = match kind.as_str() {
+                    "Realization" | "InterfaceRealization" => EdgeKind::Realization,
+                    _ => EdgeKind::Dependency,
+                };
+                self.add_edge(client, supplier, edge_kind, true, name.clone());
+            }
+        }
+    }
+
+    fn map_attribute(&self, attribute: &XmiProperty) -> Member {
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        if let Some(visibility) = &attribute.visibility {
+            data.insert("visibility".into(), Value::String(visibility.clone()));
+        }
+        if let Some(cardinality) = cardinality_of(attribute) {
+            data.insert("multiplicity".into(), Value::String(cardinality));
+        }
+
+        Member {
+            name: attribute.name.clone().unwrap_or_default(),
+            kind: MemberKind::Field,
+            type_name: attribute
+                .type_ref
+                .as_ref()
+                .map(|t: &String| self.resolve_name(t)),
+            data,
+        }
+    }
+
+    fn map_operation(&self, operation: &XmiOperation) -> Member {
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        if let Some(visibility) = &operation.visibility {
+            data.insert("visibility".into(), Value::String(visibility.clone()));
+        }
+        if operation.is_static {
+            data.insert("static".into(), Value::Bool(true));
+        }
+        if operation.is_abstract {
+            data.insert("abstract".into(), Value::Bool(true));
+        }
+
+        let (returns, parameters): (Vec<&XmiParameter>, Vec<&XmiParameter>) = operation
+            .parameters
+            .iter()
+            .partition(|p: &&XmiParameter| p.direction.as_deref() == Some("return"));
+
+        data.insert(
+            "parameters".into(),
+            Value::List(
+                parameters
+                    .iter()
+                    .map(|p: &&XmiParameter| {
+                        let name: &str = p.name.as_deref().unwrap_or_default();
+                        Value::String(match &p.type_ref {
+                            Some(t) => format!("{}: {}", name, self.resolve_name(t)),
+                            None => name.to_string(),
+                        })
+                    })
+                    .collect(),
+            ),
+        );
+
+        Member {
+            name: operation.name.clone(),
+            kind: MemberKind::Method,
+            type_name: returns
+                .first()
+                .and_then(|r: &&XmiParameter| r.type_ref.as_ref())
+                .map(|t: &String| self.resolve_name(t)),
+            data,
+        }
+    }
+
+    fn add_edge(
+        &mut self,
+        from: &str,
+        to: &str,
+        kind: EdgeKind,
+        directed: bool,
+        label: Option<String>,
+    ) -> Id {
+        self.ensure_node_exists(from);
+        self.ensure_node_exists(to);
+
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id.clone(),
+                from: from.into(),
+                to: to.into(),
+                directed,
+                kind,
+                label: label.map(Text::from),
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+        edge_id
+    }
+
+    fn ensure_node_exists(&mut self, id: &str) {
+        if !self.graph.nodes.contains_key(id) {
+            let label: String = self.resolve_name(id);
+            self.graph.nodes.insert(
+                id.into(),
+                Node {
+                    id: id.into(),
+                    kind: NodeKind::Entity, // Default kind for elements defined outside the model
+                    label: Some(label.into()),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    fn resolve_name(&self, id: &str) -> String {
+        self.names
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+}
+
+fn cardinality_of(property: &XmiProperty) -> Option<String> {
+    let normalize = |bound: &str| -> String {
+        match bound {
+            "-1" => "*".to_string(),
+            other => other.to_string(),
+        }
+    };
+
+    match (property.lower.as_deref(), property.upper.as_deref()) {
+        (Some(lower), Some(upper)) if normalize(lower) == normalize(upper) => {
+            Some(normalize(upper))
+        }
+        (Some(lower), Some(upper)) => Some(format!("{}..{}", normalize(lower), normalize(upper))),
+        (None, Some(upper)) => Some(normalize(upper)),
+        (Some(lower), None) => Some(normalize(lower)),
+        (None, None) => None,
+    }
+}
@@ -0,0 +1 @@
+pub mod yuml_graph_gateway;
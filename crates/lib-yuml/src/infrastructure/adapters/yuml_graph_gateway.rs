@@ -0,0 +1,265 @@
+use async_trait::async_trait;
+use lib_core::{
+    adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+    entities::graph::Graph,
+};
+
+use crate::infrastructure::{
+    parser::{self, YumlParseError},
+    transformer,
+};
+
+#[derive(Default)]
+pub struct YumlGraphGateway;
+
+impl YumlGraphGateway {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl GraphGateway for YumlGraphGateway {
+    async fn read_graph_from_raw_input(&self, input: &str) -> Result<Graph, GraphGatewayError> {
+        parser::parse_yuml(input)
+            .map_err(GraphGatewayError::from)
+            .map(|ast| transformer::GraphBuilder::new().build(ast))
+    }
+}
+
+impl From<YumlParseError> for GraphGatewayError {
+    fn from(err: YumlParseError) -> Self {
+        match err {
+            YumlParseError::Syntax {
+                message,
+                line,
+                column,
+            } => GraphGatewayError::Parse {
+                source: "yuml".into(),
+                message,
+                line,
+                column,
+            },
+            YumlParseError::Internal(msg) => GraphGatewayError::Semantic {
+                source: "yuml".into(),
+                message: msg,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_core::{
+        adapters::graph_gateway::{GraphGateway, GraphGatewayError},
+        entities::{
+            edge::{Edge, EdgeKind},
+            graph::{Graph, GraphKind},
+            group::Group,
+            member::{Member, MemberKind},
+            node::{Node, NodeKind},
+            style::Color,
+            value::Value,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::infrastructure::adapters::yuml_graph_gateway::YumlGraphGateway;
+
+    #[test]
+    fn test_parse_yuml_classes_and_members() {
+        smol::block_on(async {
+            let graph: Graph =
+                parse("[Customer|+name:String;-address|+save();load(id: Int):Bool{bg:orange}]")
+                    .await;
+
+            assert_eq!(graph.kind, GraphKind::Class);
+
+            let customer: &Node = graph.nodes.get("Customer").expect("Missing Customer");
+            assert_eq!(
+                customer
+                    .members
+                    .iter()
+                    .map(|m: &Member| (m.name.as_str(), &m.kind, m.type_name.as_deref()))
+                    .collect::<Vec<(&str, &MemberKind, Option<&str>)>>(),
+                vec![
+                    ("name", &MemberKind::Field, Some("String")),
+                    ("address", &MemberKind::Field, None),
+                    ("save", &MemberKind::Method, None),
+                    ("load", &MemberKind::Method, Some("Bool")),
+                ]
+            );
+            assert_eq!(
+                customer.members[0].data.get("visibility"),
+                Some(&Value::String("+".to_string()))
+            );
+            assert_eq!(customer.colors.fill, Some(Color::Named("orange".into())));
+            assert_eq!(customer.data.get("style"), None);
+            assert_eq!(
+                customer.members[3].data.get("parameters"),
+                Some(&Value::List(vec![Value::String("id: Int".to_string())]))
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_yuml_relations() {
+        smol::block_on(async {
+            let graph: Graph = parse(
+                "[Customer]<>1-orders 0..*>[Order], [Order]++-*>[LineItem]\n[Base]^-[Order]\n[<<IDisposable>>;Session]-.->[Customer]",
+            )
+            .await;
+
+            let orders: &Edge = find_edge(&graph, "Customer", "Order").expect("Missing edge");
+            assert_eq!(orders.kind, EdgeKind::Aggregation);
+            assert_eq!(orders.from_cardinality.as_deref(), Some("1"));
+            assert_eq!(orders.to_cardinality.as_deref(), Some("0..*"));
+            assert_eq!(
+                orders.data.get("to_role"),
+                Some(&Value::String("orders".to_string()))
+            );
+
+            assert_eq!(
+                find_edge(&graph, "Order", "LineItem").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Composition)
+            );
+            assert_eq!(
+                find_edge(&graph, "Order", "Base").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Inheritance),
+                "Inheritance points from the child to the parent"
+            );
+            assert_eq!(
+                find_edge(&graph, "Session", "Customer").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Dependency)
+            );
+            assert_eq!(
+                graph
+                    .nodes
+                    .get("Session")
+                    .and_then(|n: &Node| n.data.get("stereotype")),
+                Some(&Value::String("IDisposable".to_string()))
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_nomnoml_classifiers_and_packages() {
+        smol::block_on(async {
+            let source: &str = r#"
+            #direction: right
+            // Pirates
+            [<abstract> Pirate|eyeCount: Int|raid()]
+            [<actor> Captain] -:> [Pirate]
+            [Pirate] o-> [<interface> Parrot]
+            [<package> ship|
+              [Deck] -> [Cannon]
+            ]
+            [Pirate] 1 --> * [Deck]
+            "#;
+
+            let graph: Graph = parse(source).await;
+
+            assert_eq!(
+                graph
+                    .metadata
+                    .properties
+                    .get("direction")
+                    .map(String::as_str),
+                Some("right")
+            );
+
+            let pirate: &Node = graph.nodes.get("Pirate").expect("Missing Pirate");
+            assert_eq!(pirate.data.get("abstract"), Some(&Value::Bool(true)));
+            assert_eq!(pirate.members.len(), 2);
+            assert_eq!(
+                graph.nodes.get("Captain").map(|n: &Node| &n.kind),
+                Some(&NodeKind::Actor)
+            );
+            assert_eq!(
+                graph.nodes.get("Parrot").map(|n: &Node| &n.kind),
+                Some(&NodeKind::Interface)
+            );
+
+            assert_eq!(
+                find_edge(&graph, "Captain", "Pirate").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Inheritance)
+            );
+            assert_eq!(
+                find_edge(&graph, "Pirate", "Parrot").map(|e: &Edge| &e.kind),
+                Some(&EdgeKind::Aggregation)
+            );
+
+            let depends: &Edge = find_edge(&graph, "Pirate", "Deck").expect("Missing edge");
+            assert_eq!(depends.kind, EdgeKind::Dependency);
+            assert_eq!(depends.to_cardinality.as_deref(), Some("*"));
+
+            let ship: &Group = graph.groups.get("ship").expect("Missing ship package");
+            assert_eq!(
+                ship.children,
+                vec!["Deck".to_string(), "Cannon".to_string()]
+            );
+            assert!(find_edge(&graph, "Deck", "Cannon").is_some());
+        });
+    }
+
+    #[test]
+    fn test_invalid_association_is_reported_with_location() {
+        smol::block_on(async {
+            let result: Result<Graph, GraphGatewayError> = YumlGraphGateway::new()
+                .read_graph_from_raw_input("[A]\n[B] ?? [C]")
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Parse { source, line, .. }) => {
+                    assert_eq!(source, "yuml");
+                    assert_eq!(line, 2);
+                }
+                other => panic!("Expected GraphGatewayError::Parse, got {:?}", other),
+            }
+        });
+    }
+
+    #[test]
+    fn test_deep_nesting_is_a_syntax_error() {
+        smol::block_on(async {
+            let nested: String = format!("{}A{}", "[".repeat(64), "]".repeat(64));
+            assert!(
+                YumlGraphGateway::new()
+                    .read_graph_from_raw_input(&nested)
+                    .await
+                    .is_ok()
+            );
+
+            let result: Result<Graph, GraphGatewayError> = YumlGraphGateway::new()
+                .read_graph_from_raw_input(&format!("[A]\n{}", "[".repeat(20_000)))
+                .await;
+
+            match result {
+                Err(GraphGatewayError::Parse {
+                    message,
+                    line,
+                    column,
+                    ..
+                }) => {
+                    assert_eq!(message, "Classifiers nest past the depth limit of 64");
+                    assert_eq!((line, column), (2, 65));
+                }
+                other => panic!("Expected GraphGatewayError::Parse, got {:?}", other),
+            }
+        });
+    }
+
+    async fn parse(source: &str) -> Graph {
+        YumlGraphGateway::new()
+            .read_graph_from_raw_input(source)
+            .await
+            .expect("Failed to parse valid source")
+    }
+
+    fn find_edge<'a>(graph: &'a Graph, from: &str, to: &str) -> Option<&'a Edge> {
+        graph
+            .edges
+            .values()
+            .find(|e: &&Edge| e.from == from && e.to == to)
+    }
+}
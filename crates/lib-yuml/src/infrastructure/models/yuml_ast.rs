@@ -0,0 +1,29 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum YumlAst {
+    Directive {
+        key: String,
+        value: String,
+    },
+    Chain {
+        classifiers: Vec<YumlClassifier>,
+        associations: Vec<YumlAssociation>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct YumlClassifier {
+    pub name: String,
+    pub tag: Option<String>,
+    pub style: Option<String>,
+    pub compartments: Vec<Vec<String>>,
+    pub nested: Vec<YumlAst>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct YumlAssociation {
+    pub start_label: Option<String>,
+    pub start_head: Option<String>,
+    pub dashed: bool,
+    pub end_head: Option<String>,
+    pub end_label: Option<String>,
+}
@@ -0,0 +1,264 @@
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::infrastructure::models::yuml_ast::{YumlAssociation, YumlAst, YumlClassifier};
+
+const START_HEADS: [&str; 9] = ["<>", "<:", "<(", "++", "<", "^", "+", "o", "("];
+const END_HEADS: [&str; 9] = ["<>", ":>", ")>", "++", ">", "^", "+", "o", ")"];
+
+/// How deep classifiers may nest inside each other's compartments
+const MAX_DEPTH: usize = 64;
+
+#[derive(Parser)]
+#[grammar = "infrastructure/yuml.pest"]
+pub struct YumlParser;
+
+pub fn parse_yuml(input: &str) -> Result<Vec<YumlAst>, YumlParseError> {
+    check_depth(input)?;
+    let document: pest::iterators::Pair<Rule> = YumlParser::parse(Rule::document, input)
+        .map_err(YumlParseError::from)?
+        .next()
+        .ok_or_else(|| YumlParseError::Internal("Missing document root".to_string()))?;
+
+    document
+        .into_inner()
+        .filter(|pair: &pest::iterators::Pair<Rule>| pair.as_rule() != Rule::EOI)
+        .map(parse_statement)
+        .collect()
+}
+
+/// Rejects classifiers nested past `MAX_DEPTH` before the parser, which
+/// recurses once per level, gets to them
+fn check_depth(input: &str) -> Result<(), YumlParseError> {
+    let mut depth: usize = 0;
+    for (offset, byte) in input.bytes().enumerate() {
+        match byte {
+            b'[' if depth == MAX_DEPTH => {
+                let line: usize = input[..offset].matches('\n').count() + 1;
+                let column: usize =
+                    offset - input[..offset].rfind('\n').map_or(0, |n: usize| n + 1) + 1;
+                return Err(YumlParseError::Syntax {
+                    message: format!("Classifiers nest past the depth limit of {}", MAX_DEPTH),
+                    line,
+                    column,
+                });
+            }
+            b'[' => depth += 1,
+            b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn parse_statement(pair: pest::iterators::Pair<Rule>) -> Result<YumlAst, YumlParseError> {
+    match pair.as_rule() {
+        Rule::directive => {
+            let mut inner: pest::iterators::Pairs<Rule> = pair.into_inner();
+            let key: String = next_str(&mut inner)?.trim().to_string();
+            let value: String = next_str(&mut inner)?.trim().to_string();
+
+            Ok(YumlAst::Directive { key, value })
+        }
+        Rule::chain => {
+            let mut classifiers: Vec<YumlClassifier> = Vec::new();
+            let mut associations: Vec<YumlAssociation> = Vec::new();
+
+            for item in pair.into_inner() {
+                match item.as_rule() {
+                    Rule::classifier => classifiers.push(parse_classifier(item)?),
+                    _ => associations.push(parse_association(item)?),
+                }
+            }
+
+            Ok(YumlAst::Chain {
+                classifiers,
+                associations,
+            })
+        }
+        rule => Err(YumlParseError::Internal(format!(
+            "Unexpected statement {:?}",
+            rule
+        ))),
+    }
+}
+
+fn parse_classifier(pair: pest::iterators::Pair<Rule>) -> Result<YumlClassifier, YumlParseError> {
+    let mut compartments: pest::iterators::Pairs<Rule> = pair.into_inner();
+    let mut classifier: YumlClassifier = parse_name(next_str(&mut compartments)?);
+
+    for compartment in compartments {
+        let has_classifiers: bool = compartment
+            .clone()
+            .into_inner()
+            .any(|p: pest::iterators::Pair<Rule>| p.as_rule() == Rule::classifier);
+
+        if has_classifiers {
+            // Compartments holding classifiers are nested diagrams (e.g. nomnoml packages)
+            classifier.nested.extend(parse_yuml(compartment.as_str())?);
+        } else {
+            let mut text: &str = compartment.as_str().trim();
+
+            // yUML styling trails the last compartment (e.g. [Customer|name{bg:orange}])
+            if let Some(open) = text.rfind('{').filter(|_| text.ends_with('}')) {
+                classifier.style = Some(text[open + 1..text.len() - 1].trim().to_string());
+                text = &text[..open];
+            }
+
+            classifier.compartments.push(
+                text.split([';', '\n'])
+                    .map(str::trim)
+                    .filter(|member: &&str| !member.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            );
+        }
+    }
+
+    Ok(classifier)
+}
+
+fn parse_name(raw: &str) -> YumlClassifier {
+    let mut name: &str = raw.trim();
+    let mut tag: Option<String> = None;
+    let mut style: Option<String> = None;
+
+    // yUML styling (e.g. [Customer{bg:orange}])
+    if let Some(open) = name.rfind('{').filter(|_| name.ends_with('}')) {
+        style = Some(name[open + 1..name.len() - 1].trim().to_string());
+        name = name[..open].trim();
+    }
+
+    if let Some(rest) = name.strip_prefix("note:") {
+        tag = Some("note".to_string());
+        name = rest.trim();
+    } else if let Some((stereotype, rest)) = name
+        .strip_prefix("<<")
+        .and_then(|s: &str| s.split_once(">>"))
+    {
+        // yUML stereotypes (e.g. [<<IDisposable>>;Customer])
+        tag = Some(stereotype.trim().to_string());
+        name = rest.trim_start_matches(';').trim();
+    } else if let Some((kind, rest)) = name.strip_prefix('<').and_then(|s: &str| s.split_once('>'))
+    {
+        // nomnoml classifier types (e.g. [<abstract> Animal])
+        tag = Some(kind.trim().to_string());
+        name = rest.trim();
+    }
+
+    YumlClassifier {
+        name: name.to_string(),
+        tag,
+        style,
+        ..Default::default()
+    }
+}
+
+fn parse_association(pair: pest::iterators::Pair<Rule>) -> Result<YumlAssociation, YumlParseError> {
+    let (line, column): (usize, usize) = pair.line_col();
+    let text: &str = pair.as_str().trim();
+
+    let Some(dash) = text.find('-') else {
+        return Err(YumlParseError::Syntax {
+            message: format!(
+                "Expected an association between classifiers, found '{}'",
+                text
+            ),
+            line,
+            column,
+        });
+    };
+
+    let (dashed, line_length): (bool, usize) = if text[dash..].starts_with("-.-") {
+        (true, 3)
+    } else if text[dash..].starts_with("--") {
+        (true, 2)
+    } else {
+        (false, 1)
+    };
+
+    let (start_label, start_head): (&str, Option<&str>) =
+        split_head(&text[..dash], &START_HEADS, true);
+    let (end_label, end_head): (&str, Option<&str>) =
+        split_head(&text[dash + line_length..], &END_HEADS, false);
+
+    Ok(YumlAssociation {
+        start_label: Some(start_label.trim().to_string()).filter(|l: &String| !l.is_empty()),
+        start_head: start_head.map(str::to_string),
+        dashed,
+        end_head: end_head.map(str::to_string),
+        end_label: Some(end_label.trim().to_string()).filter(|l: &String| !l.is_empty()),
+    })
+}
+
+/// Splits an arrow head off an association end label. nomnoml places heads next to the line
+/// (`1 o-`) while yUML places them at the outer edge of the association (`<>1-`)
+fn split_head<'a>(
+    text: &'a str,
+    heads: &[&'a str],
+    line_after: bool,
+) -> (&'a str, Option<&'a str>) {
+    for head in heads {
+        let label: Option<&str> = if line_after {
+            text.strip_suffix(head)
+        } else {
+            text.strip_prefix(head)
+        };
+
+        if let Some(label) = label {
+            // `o` heads must stand apart from the label so words like `orders` stay intact
+            let separated: bool = label.is_empty()
+                || (line_after && label.ends_with(char::is_whitespace))
+                || (!line_after && label.starts_with(char::is_whitespace));
+
+            if *head != "o" || separated {
+                return (label, Some(head));
+            }
+        }
+    }
+
+    heads
+        .iter()
+        .filter(|head: &&&str| **head != "o")
+        .find_map(|head: &&str| {
+            let label: Option<&str> = if line_after {
+                text.strip_prefix(head)
+            } else {
+                text.strip_suffix(head)
+            };
+            label.map(|l: &str| (l, Some(*head)))
+        })
+        .unwrap_or((text, None))
+}
+
+fn next_str<'a>(inner: &mut pest::iterators::Pairs<'a, Rule>) -> Result<&'a str, YumlParseError> {
+    inner
+        .next()
+        .map(|p: pest::iterators::Pair<Rule>| p.as_str())
+        .ok_or_else(|| YumlParseError::Internal("Missing token".to_string()))
+}
+
+#[derive(Debug)]
+pub enum YumlParseError {
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    Internal(String),
+}
+
+impl From<pest::error::Error<Rule>> for YumlParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column): (usize, usize) = match err.line_col {
+            pest::error::LineColLocation::Pos((l, c)) => (l, c),
+            pest::error::LineColLocation::Span((l, c), _) => (l, c),
+        };
+
+        YumlParseError::Syntax {
+            message: err.to_string(),
+            line,
+            column,
+        }
+    }
+}
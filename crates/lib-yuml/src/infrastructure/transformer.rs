@@ -0,0 +1,325 @@
+use lib_core::entities::{
+    edge::{Edge, EdgeKind},
+    graph::{Graph, GraphKind},
+    group::Group,
+    id::{Id, Text},
+    member::{Member, MemberKind},
+    node::{Node, NodeKind},
+    style::{Color, Colors},
+    value::Value,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::infrastructure::models::yuml_ast::{YumlAssociation, YumlAst, YumlClassifier};
+
+pub struct GraphBuilder {
+    graph: Graph,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph {
+                id: Uuid::new_v4().to_string().into(),
+                kind: GraphKind::Class,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn build(mut self, ast: Vec<YumlAst>) -> Graph {
+        ast.iter().for_each(|statement: &YumlAst| {
+            self.process_ast_node(statement, None);
+        });
+        self.graph
+    }
+
+    fn process_ast_node(&mut self, statement: &YumlAst, parent_id: Option<&str>) {
+        match statement {
+            YumlAst::Directive { key, value } => {
+                if key == "title" {
+                    self.graph.metadata.title = Some(value.clone());
+                } else {
+                    self.graph
+                        .metadata
+                        .properties
+                        .insert(key.clone(), value.clone());
+                }
+            }
+            YumlAst::Chain {
+                classifiers,
+                associations,
+            } => {
+                classifiers.iter().for_each(|classifier: &YumlClassifier| {
+                    self.process_classifier(classifier, parent_id);
+                });
+
+                classifiers.windows(2).zip(associations.iter()).for_each(
+                    |(pair, association): (&[YumlClassifier], &YumlAssociation)| {
+                        self.add_association(&pair[0].name, &pair[1].name, association);
+                    },
+                );
+            }
+        }
+    }
+
+    fn process_classifier(&mut self, classifier: &YumlClassifier, parent_id: Option<&str>) {
+        let tag: Option<String> = classifier.tag.as_ref().map(|t: &String| t.to_lowercase());
+        let is_container: bool =
+            matches!(tag.as_deref(), Some("package" | "frame")) || !classifier.nested.is_empty();
+
+        if let Some(group) = parent_id.and_then(|p: &str| self.graph.groups.get_mut(p))
+            && !group
+                .children
+                .iter()
+                .any(|child: &Id| *child == classifier.name)
+        {
+            group.children.push(Id::from(classifier.name.as_str()));
+        }
+
+        if is_container {
+            self.graph
+                .groups
+                .entry(Id::from(classifier.name.as_str()))
+                .or_insert_with(|| Group {
+                    id: Id::from(classifier.name.as_str()),
+                    label: Some(classifier.name.as_str().into()),
+                    parent: parent_id.map(Id::from),
+                    ..Default::default()
+                });
+
+            classifier.nested.iter().for_each(|statement: &YumlAst| {
+                self.process_ast_node(statement, Some(&classifier.name));
+            });
+            return;
+        }
+
+        let members: Vec<Member> = classifier
+            .compartments
+            .iter()
+            .flatten()
+            .map(|member: &String| parse_member(member))
+            .collect();
+
+        // Classifiers are usually repeated across statements, with details given only once
+        if let Some(existing) = self.graph.nodes.get_mut(classifier.name.as_str()) {
+            if existing.members.is_empty() {
+                existing.members = members;
+            }
+            return;
+        }
+
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        let kind: NodeKind = match tag.as_deref() {
+            None => NodeKind::Entity,
+            Some("abstract") => {
+                data.insert("abstract".into(), Value::Bool(true));
+                NodeKind::Entity
+            }
+            Some("interface") => NodeKind::Interface,
+            Some("database") => NodeKind::Database,
+            Some("actor") => NodeKind::Actor,
+            Some("note") => NodeKind::Annotation,
+            Some(
+                kind @ ("start" | "end" | "state" | "choice" | "table" | "instance" | "usecase"
+                | "label" | "hidden" | "sender" | "receiver" | "input" | "lollipop"
+                | "socket" | "pipe" | "sync" | "reference" | "transceiver"),
+            ) => NodeKind::Custom(kind.to_string()),
+            Some(_) => {
+                data.insert(
+                    "stereotype".into(),
+                    Value::String(classifier.tag.clone().unwrap_or_default()),
+                );
+                NodeKind::Entity
+            }
+        };
+        // `{bg:orange}` fills the classifier, other styling is kept as written
+        let fill: Option<Color> = classifier
+            .style
+            .as_deref()
+            .and_then(|style: &str| style.strip_prefix("bg:"))
+            .and_then(Color::parse);
+        if let (None, Some(style)) = (&fill, &classifier.style) {
+            data.insert("style".into(), Value::String(style.clone()));
+        }
+
+        self.graph.nodes.insert(
+            Id::from(classifier.name.as_str()),
+            Node {
+                id: Id::from(classifier.name.as_str()),
+                kind,
+                label: Some(classifier.name.as_str().into()),
+                members,
+                data,
+                colors: Colors {
+                    fill,
+                    ..Default::default()
+                },
+                parent: parent_id.map(Id::from),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn add_association(&mut self, left: &str, right: &str, association: &YumlAssociation) {
+        let start: Option<&str> = association.start_head.as_deref();
+        let end: Option<&str> = association.end_head.as_deref();
+
+        // `reversed` means the relation reads from the right classifier to the left one
+        let (kind, directed, reversed): (EdgeKind, bool, bool) = match (start, end) {
+            (Some("^" | "<:"), _) => (inheritance(association), true, true),
+            (_, Some("^" | ":>")) => (inheritance(association), true, false),
+            (Some("++" | "+"), _) => (EdgeKind::Composition, true, false),
+            (_, Some("++" | "+")) => (EdgeKind::Composition, true, true),
+            (Some("<>" | "o"), _) => (EdgeKind::Aggregation, true, false),
+            (_, Some("<>" | "o")) => (EdgeKind::Aggregation, true, true),
+            (Some("(" | "<("), _) | (_, Some(")" | ")>")) => {
+                (EdgeKind::Custom("socket".to_string()), false, false)
+            }
+            (Some("<"), None) => (directed_kind(association), true, true),
+            (_, Some(">")) => (directed_kind(association), true, false),
+            _ => (EdgeKind::Undirected, false, false),
+        };
+
+        let (from, to, from_label, to_label) = if reversed {
+            (
+                right,
+                left,
+                &association.end_label,
+                &association.start_label,
+            )
+        } else {
+            (
+                left,
+                right,
+                &association.start_label,
+                &association.end_label,
+            )
+        };
+
+        let mut data: HashMap<Text, Value> = HashMap::new();
+        let mut cardinalities: [Option<Text>; 2] = [None, None];
+        [("from", from_label), ("to", to_label)]
+            .iter()
+            .zip(cardinalities.iter_mut())
+            .for_each(
+                |((side, label), cardinality): (&(&str, &Option<String>), &mut Option<Text>)| {
+                    let Some(label) = label else {
+                        return;
+                    };
+                    let (multiplicity, role): (Vec<&str>, Vec<&str>) = label
+                        .split_whitespace()
+                        .partition(|token: &&str| is_multiplicity(token));
+
+                    if !multiplicity.is_empty() {
+                        *cardinality = Some(multiplicity.join(" ").into());
+                    }
+                    if !role.is_empty() {
+                        data.insert(
+                            format!("{}_role", side).into(),
+                            Value::String(role.join(" ")),
+                        );
+                    }
+                },
+            );
+        if start == Some("<") && end == Some(">") {
+            data.insert("bidirectional".into(), Value::Bool(true));
+        }
+        if association.dashed {
+            data.insert("line_style".into(), Value::String("dashed".to_string()));
+        }
+
+        let [from_cardinality, to_cardinality]: [Option<Text>; 2] = cardinalities;
+        let edge_id: Id = Uuid::new_v4().to_string().into();
+        self.graph.edges.insert(
+            edge_id.clone(),
+            Edge {
+                id: edge_id,
+                from: from.into(),
+                to: to.into(),
+                directed,
+                kind,
+                data,
+                from_cardinality,
+                to_cardinality,
+                order: self.graph.edges.len(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+fn inheritance(association: &YumlAssociation) -> EdgeKind {
+    if association.dashed {
+        EdgeKind::Realization
+    } else {
+        EdgeKind::Inheritance
+    }
+}
+
+fn directed_kind(association: &YumlAssociation) -> EdgeKind {
+    if association.dashed {
+        EdgeKind::Dependency
+    } else {
+        EdgeKind::Association
+    }
+}
+
+fn is_multiplicity(token: &str) -> bool {
+    token.chars().any(|c: char| c.is_ascii_digit() || c == '*')
+        && token
+            .chars()
+            .all(|c: char| c.is_ascii_digit() || matches!(c, '.' | '*' | 'n'))
+}
+
+fn parse_member(raw: &str) -> Member {
+    let mut data: HashMap<Text, Value> = HashMap::new();
+    let mut text: &str = raw.trim();
+
+    if let Some(symbol) = text.chars().next().filter(|c: &char| "+-#~".contains(*c)) {
+        data.insert("visibility".into(), Value::String(symbol.to_string()));
+        text = text[1..].trim_start();
+    }
+
+    match text.split_once('(') {
+        Some((name, rest)) => {
+            let (parameters, returns): (&str, &str) = rest.split_once(')').unwrap_or((rest, ""));
+            data.insert(
+                "parameters".into(),
+                Value::List(
+                    parameters
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|p: &&str| !p.is_empty())
+                        .map(|p: &str| Value::String(p.to_string()))
+                        .collect(),
+                ),
+            );
+
+            Member {
+                name: name.trim().to_string(),
+                kind: MemberKind::Method,
+                type_name: returns
+                    .trim()
+                    .strip_prefix(':')
+                    .map(|t: &str| t.trim().to_string())
+                    .filter(|t: &String| !t.is_empty()),
+                data,
+            }
+        }
+        None => {
+            let (name, type_name): (&str, Option<&str>) = match text.split_once(':') {
+                Some((name, type_name)) => (name, Some(type_name.trim())),
+                None => (text, None),
+            };
+
+            Member {
+                name: name.trim().to_string(),
+                kind: MemberKind::Field,
+                type_name: type_name.map(str::to_string),
+                data,
+            }
+        }
+    }
+}